@@ -0,0 +1,18 @@
+PRAGMA foreign_keys = ON;
+
+-- Named snapshots of an attempt's git HEAD and executor session, used to
+-- start new attempts that resume from a known point
+CREATE TABLE task_attempt_checkpoints (
+    id                    BLOB PRIMARY KEY,
+    task_attempt_id       BLOB NOT NULL,
+    name                  TEXT NOT NULL,
+    commit_oid            TEXT NOT NULL,
+    execution_process_id  BLOB,
+    session_id            TEXT,
+    created_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_task_attempt_checkpoints_task_attempt_id
+    ON task_attempt_checkpoints(task_attempt_id);
@@ -0,0 +1,2 @@
+-- Archived attempts keep their logs and branch but have no worktree
+ALTER TABLE task_attempts ADD COLUMN archived_at TEXT;
@@ -0,0 +1,20 @@
+PRAGMA foreign_keys = ON;
+
+-- Cron schedules that periodically start a new attempt for a task
+CREATE TABLE task_schedules (
+    id                    BLOB PRIMARY KEY,
+    task_id               BLOB NOT NULL UNIQUE,
+    cron_expression       TEXT NOT NULL,
+    executor_profile_id   TEXT NOT NULL,
+    base_branch           TEXT NOT NULL,
+    enabled               BOOLEAN NOT NULL DEFAULT TRUE,
+    next_run_at           TEXT,
+    last_run_at           TEXT,
+    last_task_attempt_id  BLOB,
+    created_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (last_task_attempt_id) REFERENCES task_attempts(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_task_schedules_next_run_at ON task_schedules(next_run_at);
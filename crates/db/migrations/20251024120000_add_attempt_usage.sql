@@ -0,0 +1,20 @@
+PRAGMA foreign_keys = ON;
+
+-- Token and cost figures reported by coding agents, one row per execution process
+CREATE TABLE attempt_usage (
+    id                           BLOB PRIMARY KEY,
+    execution_process_id         BLOB NOT NULL UNIQUE,
+    task_attempt_id              BLOB NOT NULL,
+    model                        TEXT,
+    input_tokens                 INTEGER NOT NULL DEFAULT 0,
+    output_tokens                INTEGER NOT NULL DEFAULT 0,
+    cache_read_input_tokens      INTEGER NOT NULL DEFAULT 0,
+    cache_creation_input_tokens  INTEGER NOT NULL DEFAULT 0,
+    cost_usd                     REAL,
+    created_at                   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at                   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_attempt_usage_task_attempt_id ON attempt_usage(task_attempt_id);
@@ -0,0 +1,5 @@
+-- Optional run time limits (in minutes) for executions; the attempt value
+-- overrides the project value
+ALTER TABLE projects ADD COLUMN execution_timeout_minutes INTEGER;
+ALTER TABLE task_attempts ADD COLUMN execution_timeout_minutes INTEGER;
+ALTER TABLE task_attempts ADD COLUMN timed_out_at TEXT;
@@ -0,0 +1,2 @@
+-- Set while the attempt's running executor is suspended
+ALTER TABLE task_attempts ADD COLUMN paused_at TEXT;
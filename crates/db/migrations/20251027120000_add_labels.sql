@@ -0,0 +1,31 @@
+PRAGMA foreign_keys = ON;
+
+-- Free-form labels ("needs-review", "regression", ...) shared by tasks and attempts
+CREATE TABLE labels (
+    id          BLOB PRIMARY KEY,
+    name        TEXT NOT NULL UNIQUE CHECK(name != ''),
+    color       TEXT,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+CREATE TABLE task_labels (
+    task_id     BLOB NOT NULL,
+    label_id    BLOB NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (task_id, label_id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (label_id) REFERENCES labels(id) ON DELETE CASCADE
+);
+
+CREATE TABLE task_attempt_labels (
+    task_attempt_id BLOB NOT NULL,
+    label_id        BLOB NOT NULL,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (task_attempt_id, label_id),
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE,
+    FOREIGN KEY (label_id) REFERENCES labels(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_labels_label_id ON task_labels(label_id);
+CREATE INDEX idx_task_attempt_labels_label_id ON task_attempt_labels(label_id);
@@ -0,0 +1,4 @@
+-- Attempts stacked on another attempt's branch point at that attempt
+ALTER TABLE task_attempts ADD COLUMN parent_attempt_id BLOB REFERENCES task_attempts(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_task_attempts_parent_attempt_id ON task_attempts(parent_attempt_id);
@@ -0,0 +1,18 @@
+PRAGMA foreign_keys = ON;
+
+-- Extra git repositories checked out next to the project's main repository
+-- for every attempt
+CREATE TABLE project_repositories (
+    id            BLOB PRIMARY KEY,
+    project_id    BLOB NOT NULL,
+    name          TEXT NOT NULL CHECK(name != ''),
+    git_repo_path TEXT NOT NULL,
+    base_branch   TEXT NOT NULL,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    UNIQUE (project_id, name),
+    UNIQUE (project_id, git_repo_path),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_project_repositories_project_id ON project_repositories(project_id);
@@ -0,0 +1,15 @@
+PRAGMA foreign_keys = ON;
+
+-- Follow-up prompts waiting for the attempt's current turn to finish,
+-- delivered oldest first
+CREATE TABLE queued_follow_ups (
+    id              BLOB PRIMARY KEY,
+    task_attempt_id BLOB NOT NULL,
+    prompt          TEXT NOT NULL,
+    variant         TEXT,
+    image_ids       TEXT,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_queued_follow_ups_task_attempt_id ON queued_follow_ups(task_attempt_id, created_at);
@@ -0,0 +1,2 @@
+-- Push attempt branches to the remote after every coding agent turn
+ALTER TABLE projects ADD COLUMN auto_push_branches BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,7 @@
+PRAGMA foreign_keys = ON;
+
+-- Subtasks: a task may be broken down into child tasks whose progress rolls
+-- up into the parent's status
+ALTER TABLE tasks ADD COLUMN parent_task_id BLOB REFERENCES tasks(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_tasks_parent_task_id ON tasks(parent_task_id);
@@ -0,0 +1,18 @@
+PRAGMA foreign_keys = ON;
+
+-- Reusable task blueprints; title and description may contain {{placeholders}}
+-- that are filled in when a task is created from the template
+CREATE TABLE task_templates (
+    id                  BLOB PRIMARY KEY,
+    project_id          BLOB,  -- NULL for templates available in every project
+    name                TEXT NOT NULL CHECK(name != ''),
+    title               TEXT NOT NULL,
+    description         TEXT,
+    executor_profile_id TEXT,
+    base_branch         TEXT,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_templates_project_id ON task_templates(project_id);
@@ -0,0 +1,16 @@
+PRAGMA foreign_keys = ON;
+
+-- Tasks imported from GitHub issues, with the issue they link back to
+CREATE TABLE github_issue_links (
+    task_id       BLOB PRIMARY KEY,
+    repo_owner    TEXT NOT NULL,
+    repo_name     TEXT NOT NULL,
+    issue_number  INTEGER NOT NULL,
+    issue_url     TEXT NOT NULL,
+    -- Close the issue once the task is done
+    sync_status   BOOLEAN NOT NULL DEFAULT FALSE,
+    closed_at     TEXT,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    UNIQUE (repo_owner, repo_name, issue_number)
+);
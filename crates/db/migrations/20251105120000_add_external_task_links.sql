@@ -0,0 +1,20 @@
+PRAGMA foreign_keys = ON;
+
+-- Tasks mirrored from an external tracker (Jira, Linear)
+CREATE TABLE external_task_links (
+    task_id             BLOB PRIMARY KEY,
+    provider            TEXT NOT NULL CHECK (provider IN ('jira', 'linear')),
+    -- Identifier used by the tracker's API
+    external_id         TEXT NOT NULL,
+    -- Human readable key, e.g. ENG-123
+    external_key        TEXT NOT NULL,
+    external_url        TEXT NOT NULL,
+    -- Task status as of the last sync, to detect local transitions
+    last_synced_status  TEXT NOT NULL,
+    -- Tracker state as of the last sync, to detect remote transitions
+    remote_state        TEXT NOT NULL,
+    synced_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    UNIQUE (provider, external_id)
+);
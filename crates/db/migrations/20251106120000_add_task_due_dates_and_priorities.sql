@@ -0,0 +1,7 @@
+PRAGMA foreign_keys = ON;
+
+ALTER TABLE tasks ADD COLUMN priority TEXT NOT NULL DEFAULT 'medium'
+    CHECK (priority IN ('low', 'medium', 'high', 'urgent'));
+ALTER TABLE tasks ADD COLUMN due_date TEXT;
+-- When an in-progress task was reported as overdue; cleared when the due date changes
+ALTER TABLE tasks ADD COLUMN overdue_notified_at TEXT;
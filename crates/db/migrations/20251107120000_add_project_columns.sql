@@ -0,0 +1,42 @@
+PRAGMA foreign_keys = ON;
+
+-- Board columns of a project. Every column maps onto one task status, which
+-- keeps driving automation (attempts, PRs, dependencies); columns let a
+-- project split, rename and reorder the stages it shows.
+CREATE TABLE project_columns (
+    id          BLOB PRIMARY KEY,
+    project_id  BLOB NOT NULL,
+    name        TEXT NOT NULL,
+    position    INTEGER NOT NULL,
+    status      TEXT NOT NULL
+                   CHECK (status IN ('todo', 'inprogress', 'inreview', 'done', 'cancelled')),
+    wip_limit   INTEGER CHECK (wip_limit IS NULL OR wip_limit > 0),
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, name)
+);
+
+CREATE INDEX idx_project_columns_project_id ON project_columns(project_id);
+
+-- One column per status for every existing project
+INSERT INTO project_columns (id, project_id, name, position, status)
+SELECT randomblob(16), p.id, s.name, s.position, s.status
+  FROM projects p
+  CROSS JOIN (
+      SELECT 'To Do' AS name, 0 AS position, 'todo' AS status
+      UNION ALL SELECT 'In Progress', 1, 'inprogress'
+      UNION ALL SELECT 'In Review', 2, 'inreview'
+      UNION ALL SELECT 'Done', 3, 'done'
+      UNION ALL SELECT 'Cancelled', 4, 'cancelled'
+  ) s;
+
+-- Column a task was moved into. Only honoured while the column's status
+-- matches the task's; otherwise the task shows in the first column of its status.
+ALTER TABLE tasks ADD COLUMN column_id BLOB REFERENCES project_columns(id) ON DELETE SET NULL;
+
+UPDATE tasks
+   SET column_id = (
+       SELECT c.id FROM project_columns c
+        WHERE c.project_id = tasks.project_id AND c.status = tasks.status
+   );
@@ -0,0 +1,23 @@
+PRAGMA foreign_keys = ON;
+
+-- Discussion on a task, optionally about one attempt and a line of its diff
+CREATE TABLE task_comments (
+    id               BLOB PRIMARY KEY,
+    task_id          BLOB NOT NULL,
+    task_attempt_id  BLOB,
+    author           TEXT NOT NULL,
+    -- Markdown
+    body             TEXT NOT NULL,
+    -- Anchor in the attempt's diff
+    file_path        TEXT,
+    line_number      INTEGER,
+    created_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE,
+    CHECK (file_path IS NOT NULL OR line_number IS NULL),
+    CHECK (task_attempt_id IS NOT NULL OR file_path IS NULL)
+);
+
+CREATE INDEX idx_task_comments_task_id ON task_comments(task_id);
+CREATE INDEX idx_task_comments_task_attempt_id ON task_comments(task_attempt_id);
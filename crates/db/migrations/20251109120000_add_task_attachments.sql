@@ -0,0 +1,19 @@
+PRAGMA foreign_keys = ON;
+
+-- Files attached to a task (specs, CSVs, design exports). Stored under the
+-- asset dir and copied into every attempt's worktree.
+CREATE TABLE task_attachments (
+    id             BLOB PRIMARY KEY,
+    task_id        BLOB NOT NULL,
+    file_path      TEXT NOT NULL,  -- relative path within assets/attachments/
+    original_name  TEXT NOT NULL,
+    mime_type      TEXT NOT NULL,
+    size_bytes     INTEGER NOT NULL,
+    hash           TEXT NOT NULL,  -- SHA256, one copy of a file per task
+    created_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    UNIQUE(task_id, hash)
+);
+
+CREATE INDEX idx_task_attachments_task_id ON task_attachments(task_id);
@@ -0,0 +1,144 @@
+PRAGMA foreign_keys = ON;
+
+-- What each row of `search_index` is about; `id` is the row's rowid.
+-- `entity_id` is the task id for 'task' rows and the execution process id for
+-- 'attempt_summary' and 'execution_log' rows. Logs are appended one line at a
+-- time, so each line becomes its own row.
+CREATE TABLE search_index_entries (
+    id        INTEGER PRIMARY KEY,
+    kind      TEXT NOT NULL,
+    entity_id BLOB NOT NULL
+);
+
+CREATE INDEX idx_search_index_entries_entity ON search_index_entries(kind, entity_id);
+
+-- Full-text index over task text, attempt summaries and executor output
+CREATE VIRTUAL TABLE search_index USING fts5(
+    content,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+-- Logs stored before the index existed; the server splits them into lines
+-- and indexes them in the background, removing each row once it is done
+CREATE TABLE search_index_log_backfill (
+    execution_id BLOB PRIMARY KEY
+);
+
+INSERT INTO search_index_log_backfill (execution_id)
+SELECT execution_id FROM execution_process_logs;
+
+CREATE TEMP TABLE search_index_backfill (
+    id        INTEGER PRIMARY KEY,
+    kind      TEXT NOT NULL,
+    entity_id BLOB NOT NULL,
+    content   TEXT NOT NULL
+);
+
+INSERT INTO search_index_backfill (kind, entity_id, content)
+SELECT 'task', id, title || char(10) || COALESCE(description, '') FROM tasks;
+
+INSERT INTO search_index_backfill (kind, entity_id, content)
+SELECT 'attempt_summary', execution_process_id, summary
+  FROM executor_sessions
+ WHERE summary IS NOT NULL;
+
+INSERT INTO search_index_entries (id, kind, entity_id)
+SELECT id, kind, entity_id FROM search_index_backfill;
+
+INSERT INTO search_index (rowid, content)
+SELECT id, content FROM search_index_backfill;
+
+DROP TABLE search_index_backfill;
+
+CREATE TRIGGER search_index_task_insert AFTER INSERT ON tasks
+BEGIN
+    INSERT INTO search_index_entries (kind, entity_id) VALUES ('task', NEW.id);
+    INSERT INTO search_index (rowid, content)
+    VALUES (last_insert_rowid(), NEW.title || char(10) || COALESCE(NEW.description, ''));
+END;
+
+CREATE TRIGGER search_index_task_update AFTER UPDATE OF title, description ON tasks
+BEGIN
+    UPDATE search_index
+       SET content = NEW.title || char(10) || COALESCE(NEW.description, '')
+     WHERE rowid IN (
+         SELECT id FROM search_index_entries WHERE kind = 'task' AND entity_id = NEW.id
+     );
+END;
+
+CREATE TRIGGER search_index_task_delete AFTER DELETE ON tasks
+BEGIN
+    DELETE FROM search_index
+     WHERE rowid IN (
+         SELECT id FROM search_index_entries WHERE kind = 'task' AND entity_id = OLD.id
+     );
+    DELETE FROM search_index_entries WHERE kind = 'task' AND entity_id = OLD.id;
+END;
+
+CREATE TRIGGER search_index_summary_insert AFTER INSERT ON executor_sessions
+WHEN NEW.summary IS NOT NULL
+BEGIN
+    INSERT INTO search_index_entries (kind, entity_id)
+    VALUES ('attempt_summary', NEW.execution_process_id);
+    INSERT INTO search_index (rowid, content) VALUES (last_insert_rowid(), NEW.summary);
+END;
+
+CREATE TRIGGER search_index_summary_update AFTER UPDATE OF summary ON executor_sessions
+BEGIN
+    DELETE FROM search_index
+     WHERE rowid IN (
+         SELECT id FROM search_index_entries
+          WHERE kind = 'attempt_summary' AND entity_id = OLD.execution_process_id
+     );
+    DELETE FROM search_index_entries
+     WHERE kind = 'attempt_summary' AND entity_id = OLD.execution_process_id;
+    INSERT INTO search_index_entries (kind, entity_id)
+    SELECT 'attempt_summary', NEW.execution_process_id WHERE NEW.summary IS NOT NULL;
+    INSERT INTO search_index (rowid, content)
+    SELECT last_insert_rowid(), NEW.summary WHERE NEW.summary IS NOT NULL;
+END;
+
+-- Stored logs are JSONL; only the text of stdout and stderr lines is indexed
+CREATE TRIGGER search_index_log_insert AFTER INSERT ON execution_process_logs
+WHEN json_valid(NEW.logs)
+ AND COALESCE(json_extract(NEW.logs, '$.Stdout'), json_extract(NEW.logs, '$.Stderr')) IS NOT NULL
+BEGIN
+    INSERT INTO search_index_entries (kind, entity_id) VALUES ('execution_log', NEW.execution_id);
+    INSERT INTO search_index (rowid, content)
+    VALUES (
+        last_insert_rowid(),
+        COALESCE(json_extract(NEW.logs, '$.Stdout'), json_extract(NEW.logs, '$.Stderr'))
+    );
+END;
+
+CREATE TRIGGER search_index_log_append AFTER UPDATE OF logs ON execution_process_logs
+WHEN length(NEW.logs) > length(OLD.logs)
+ AND json_valid(substr(NEW.logs, length(OLD.logs) + 1))
+ AND COALESCE(
+         json_extract(substr(NEW.logs, length(OLD.logs) + 1), '$.Stdout'),
+         json_extract(substr(NEW.logs, length(OLD.logs) + 1), '$.Stderr')
+     ) IS NOT NULL
+BEGIN
+    INSERT INTO search_index_entries (kind, entity_id) VALUES ('execution_log', NEW.execution_id);
+    INSERT INTO search_index (rowid, content)
+    VALUES (
+        last_insert_rowid(),
+        COALESCE(
+            json_extract(substr(NEW.logs, length(OLD.logs) + 1), '$.Stdout'),
+            json_extract(substr(NEW.logs, length(OLD.logs) + 1), '$.Stderr')
+        )
+    );
+END;
+
+-- Covers summaries and logs, including those removed with their task
+CREATE TRIGGER search_index_execution_process_delete AFTER DELETE ON execution_processes
+BEGIN
+    DELETE FROM search_index
+     WHERE rowid IN (
+         SELECT id FROM search_index_entries
+          WHERE kind IN ('attempt_summary', 'execution_log') AND entity_id = OLD.id
+     );
+    DELETE FROM search_index_entries
+     WHERE kind IN ('attempt_summary', 'execution_log') AND entity_id = OLD.id;
+    DELETE FROM search_index_log_backfill WHERE execution_id = OLD.id;
+END;
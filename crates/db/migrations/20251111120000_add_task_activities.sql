@@ -0,0 +1,92 @@
+PRAGMA foreign_keys = ON;
+
+-- History of a task, recorded by triggers so that every code path that changes
+-- a task, attempt or merge shows up in it
+CREATE TABLE task_activities (
+    id               BLOB PRIMARY KEY,
+    task_id          BLOB NOT NULL,
+    task_attempt_id  BLOB,
+    kind             TEXT NOT NULL
+                        CHECK (kind IN ('created', 'status_changed', 'attempt_started',
+                                        'attempt_finished', 'pr_opened', 'pr_closed', 'merged')),
+    -- status_changed only
+    from_status      TEXT,
+    to_status        TEXT,
+    -- Final process status for attempt_finished, PR URL or merge commit otherwise
+    detail           TEXT,
+    created_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_task_activities_task_id_created_at ON task_activities(task_id, created_at);
+
+-- Backfill what can be reconstructed from existing rows
+INSERT INTO task_activities (id, task_id, kind, created_at)
+SELECT randomblob(16), id, 'created', created_at FROM tasks;
+
+INSERT INTO task_activities (id, task_id, task_attempt_id, kind, created_at)
+SELECT randomblob(16), task_id, id, 'attempt_started', created_at FROM task_attempts;
+
+INSERT INTO task_activities (id, task_id, task_attempt_id, kind, detail, created_at)
+SELECT randomblob(16), ta.task_id, m.task_attempt_id,
+       CASE m.merge_type WHEN 'pr' THEN 'pr_opened' ELSE 'merged' END,
+       COALESCE(m.pr_url, m.merge_commit), m.created_at
+  FROM merges m
+  JOIN task_attempts ta ON ta.id = m.task_attempt_id;
+
+INSERT INTO task_activities (id, task_id, task_attempt_id, kind, detail, created_at)
+SELECT randomblob(16), ta.task_id, m.task_attempt_id, 'merged', m.pr_url,
+       COALESCE(m.pr_merged_at, m.created_at)
+  FROM merges m
+  JOIN task_attempts ta ON ta.id = m.task_attempt_id
+ WHERE m.merge_type = 'pr' AND m.pr_status = 'merged';
+
+CREATE TRIGGER task_activity_created AFTER INSERT ON tasks
+BEGIN
+    INSERT INTO task_activities (id, task_id, kind) VALUES (randomblob(16), NEW.id, 'created');
+END;
+
+CREATE TRIGGER task_activity_status_changed AFTER UPDATE OF status ON tasks
+WHEN OLD.status != NEW.status
+BEGIN
+    INSERT INTO task_activities (id, task_id, kind, from_status, to_status)
+    VALUES (randomblob(16), NEW.id, 'status_changed', OLD.status, NEW.status);
+END;
+
+CREATE TRIGGER task_activity_attempt_started AFTER INSERT ON task_attempts
+BEGIN
+    INSERT INTO task_activities (id, task_id, task_attempt_id, kind)
+    VALUES (randomblob(16), NEW.task_id, NEW.id, 'attempt_started');
+END;
+
+-- An attempt is finished when one of its coding agent runs stops
+CREATE TRIGGER task_activity_attempt_finished AFTER UPDATE OF status ON execution_processes
+WHEN OLD.status = 'running' AND NEW.status != 'running' AND NEW.run_reason = 'codingagent'
+BEGIN
+    INSERT INTO task_activities (id, task_id, task_attempt_id, kind, detail)
+    SELECT randomblob(16), task_id, id, 'attempt_finished', NEW.status
+      FROM task_attempts
+     WHERE id = NEW.task_attempt_id;
+END;
+
+CREATE TRIGGER task_activity_merge_created AFTER INSERT ON merges
+BEGIN
+    INSERT INTO task_activities (id, task_id, task_attempt_id, kind, detail)
+    SELECT randomblob(16), task_id, id,
+           CASE NEW.merge_type WHEN 'pr' THEN 'pr_opened' ELSE 'merged' END,
+           COALESCE(NEW.pr_url, NEW.merge_commit)
+      FROM task_attempts
+     WHERE id = NEW.task_attempt_id;
+END;
+
+CREATE TRIGGER task_activity_pr_status_changed AFTER UPDATE OF pr_status ON merges
+WHEN OLD.pr_status = 'open' AND NEW.pr_status IN ('merged', 'closed')
+BEGIN
+    INSERT INTO task_activities (id, task_id, task_attempt_id, kind, detail)
+    SELECT randomblob(16), task_id, id,
+           CASE NEW.pr_status WHEN 'merged' THEN 'merged' ELSE 'pr_closed' END,
+           NEW.pr_url
+      FROM task_attempts
+     WHERE id = NEW.task_attempt_id;
+END;
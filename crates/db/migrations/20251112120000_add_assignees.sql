@@ -0,0 +1,8 @@
+PRAGMA foreign_keys = ON;
+
+-- Who is working on a task or attempt, e.g. a GitHub username
+ALTER TABLE tasks ADD COLUMN assignee TEXT;
+ALTER TABLE task_attempts ADD COLUMN assignee TEXT;
+
+CREATE INDEX idx_tasks_assignee ON tasks(assignee);
+CREATE INDEX idx_task_attempts_assignee ON task_attempts(assignee);
@@ -0,0 +1,9 @@
+PRAGMA foreign_keys = ON;
+
+-- Deleted tasks and projects stay in the trash, with their attempts and
+-- worktrees, until restored or purged after the retention window
+ALTER TABLE tasks ADD COLUMN deleted_at TEXT;
+ALTER TABLE projects ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX idx_tasks_deleted_at ON tasks(deleted_at);
+CREATE INDEX idx_projects_deleted_at ON projects(deleted_at);
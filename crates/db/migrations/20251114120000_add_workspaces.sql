@@ -0,0 +1,14 @@
+PRAGMA foreign_keys = ON;
+
+-- Groups of related projects (e.g. the parts of a monorepo) shown together
+CREATE TABLE workspaces (
+    id          BLOB PRIMARY KEY,
+    name        TEXT NOT NULL CHECK(name != ''),
+    description TEXT,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
+
+ALTER TABLE projects ADD COLUMN workspace_id BLOB REFERENCES workspaces(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_projects_workspace_id ON projects(workspace_id);
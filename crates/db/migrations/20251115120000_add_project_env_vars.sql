@@ -0,0 +1,15 @@
+PRAGMA foreign_keys = ON;
+
+-- Environment variables set on every process started for a project's
+-- attempts. Values are encrypted with a key kept outside the database.
+CREATE TABLE project_env_vars (
+    id              BLOB PRIMARY KEY,
+    project_id      BLOB NOT NULL,
+    name            TEXT NOT NULL CHECK(name != ''),
+    encrypted_value BLOB NOT NULL,
+    is_secret       BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, name)
+);
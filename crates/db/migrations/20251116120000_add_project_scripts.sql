@@ -0,0 +1,54 @@
+PRAGMA foreign_keys = ON;
+
+-- Scripts of a project, run in position order within their kind. A kind with
+-- no scripts falls back to the project's legacy script column.
+CREATE TABLE project_scripts (
+    id              BLOB PRIMARY KEY,
+    project_id      BLOB NOT NULL,
+    kind            TEXT NOT NULL
+                       CHECK (kind IN ('setup', 'dev', 'test', 'cleanup')),
+    name            TEXT NOT NULL CHECK(name != ''),
+    script          TEXT NOT NULL,
+    position        INTEGER NOT NULL,
+    timeout_seconds INTEGER CHECK (timeout_seconds IS NULL OR timeout_seconds > 0),
+    max_retries     INTEGER NOT NULL DEFAULT 0 CHECK (max_retries >= 0),
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, kind, name)
+);
+
+CREATE INDEX idx_project_scripts_project_id ON project_scripts(project_id, kind, position);
+
+-- Test scripts get their own run reason. The trigger and index on run_reason
+-- have to go while the column is swapped for one with the wider CHECK.
+DROP TRIGGER IF EXISTS task_activity_attempt_finished;
+DROP INDEX IF EXISTS idx_execution_processes_type;
+
+ALTER TABLE execution_processes
+  ADD COLUMN run_reason_new TEXT NOT NULL DEFAULT 'setupscript'
+    CHECK (run_reason_new IN ('setupscript',
+                              'cleanupscript',
+                              'testscript',
+                              'codingagent',
+                              'devserver'));
+
+UPDATE execution_processes
+  SET run_reason_new = run_reason;
+
+ALTER TABLE execution_processes DROP COLUMN run_reason;
+
+ALTER TABLE execution_processes
+  RENAME COLUMN run_reason_new TO run_reason;
+
+CREATE INDEX idx_execution_processes_type
+        ON execution_processes(run_reason);
+
+CREATE TRIGGER task_activity_attempt_finished AFTER UPDATE OF status ON execution_processes
+WHEN OLD.status = 'running' AND NEW.status != 'running' AND NEW.run_reason = 'codingagent'
+BEGIN
+    INSERT INTO task_activities (id, task_id, task_attempt_id, kind, detail)
+    SELECT randomblob(16), task_id, id, 'attempt_finished', NEW.status
+      FROM task_attempts
+     WHERE id = NEW.task_attempt_id;
+END;
@@ -0,0 +1,3 @@
+-- Archived projects are hidden from default listings and read-only: nothing
+-- runs for them and their attempts have no worktrees
+ALTER TABLE projects ADD COLUMN archived_at TEXT;
@@ -0,0 +1,13 @@
+PRAGMA foreign_keys = ON;
+
+-- Recurrence rule of a task. Once the task is done, its next occurrence is
+-- created as a new task and the rule moves on to that task.
+CREATE TABLE task_recurrences (
+    id          BLOB PRIMARY KEY,
+    task_id     BLOB NOT NULL UNIQUE,
+    rule        TEXT NOT NULL,
+    occurrence  INTEGER NOT NULL DEFAULT 1 CHECK (occurrence > 0),
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
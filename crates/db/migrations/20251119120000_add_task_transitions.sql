@@ -0,0 +1,54 @@
+PRAGMA foreign_keys = ON;
+
+-- Status and board column a task entered, recorded by triggers so that every
+-- code path moving a task shows up. A task stays in a column until its next
+-- transition.
+CREATE TABLE task_transitions (
+    id          BLOB PRIMARY KEY,
+    task_id     BLOB NOT NULL,
+    status      TEXT NOT NULL
+                   CHECK (status IN ('todo', 'inprogress', 'inreview', 'done', 'cancelled')),
+    column_id   BLOB,  -- column the task shows in; NULL once the column is deleted
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (column_id) REFERENCES project_columns(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_task_transitions_task_id_created_at ON task_transitions(task_id, created_at);
+
+-- Backfill from the task history. Columns were not tracked, so each entry gets
+-- the first column of its status.
+INSERT INTO task_transitions (id, task_id, status, column_id, created_at)
+SELECT randomblob(16), h.task_id, h.status,
+       (SELECT c.id FROM project_columns c
+         WHERE c.project_id = h.project_id AND c.status = h.status
+         ORDER BY c.position LIMIT 1),
+       h.created_at
+  FROM (SELECT a.task_id, t.project_id, a.created_at,
+               CASE a.kind WHEN 'created' THEN 'todo' ELSE a.to_status END AS status
+          FROM task_activities a
+          JOIN tasks t ON t.id = a.task_id
+         WHERE a.kind IN ('created', 'status_changed')) h;
+
+-- A column only counts while its status matches the task's; otherwise the task
+-- shows in the first column of its status
+CREATE TRIGGER task_transition_created AFTER INSERT ON tasks
+BEGIN
+    INSERT INTO task_transitions (id, task_id, status, column_id)
+    VALUES (randomblob(16), NEW.id, NEW.status,
+            COALESCE((SELECT id FROM project_columns WHERE id = NEW.column_id AND status = NEW.status),
+                     (SELECT id FROM project_columns
+                       WHERE project_id = NEW.project_id AND status = NEW.status
+                       ORDER BY position LIMIT 1)));
+END;
+
+CREATE TRIGGER task_transition_moved AFTER UPDATE OF status, column_id ON tasks
+WHEN OLD.status != NEW.status OR OLD.column_id IS NOT NEW.column_id
+BEGIN
+    INSERT INTO task_transitions (id, task_id, status, column_id)
+    VALUES (randomblob(16), NEW.id, NEW.status,
+            COALESCE((SELECT id FROM project_columns WHERE id = NEW.column_id AND status = NEW.status),
+                     (SELECT id FROM project_columns
+                       WHERE project_id = NEW.project_id AND status = NEW.status
+                       ORDER BY position LIMIT 1)));
+END;
@@ -0,0 +1,3 @@
+-- Enforced WIP limits reject moves into a full column; otherwise the move
+-- goes through with a warning
+ALTER TABLE project_columns ADD COLUMN enforce_wip_limit BOOLEAN NOT NULL DEFAULT FALSE;
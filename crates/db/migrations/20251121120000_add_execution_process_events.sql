@@ -0,0 +1,13 @@
+PRAGMA foreign_keys = ON;
+
+-- Executor-agnostic events of coding agent runs, appended while they stream.
+-- seq orders the events of one execution process.
+CREATE TABLE execution_process_events (
+    execution_process_id  BLOB NOT NULL,
+    seq                   INTEGER NOT NULL,
+    event_type            TEXT NOT NULL,
+    event                 TEXT NOT NULL,  -- JSON AgentEvent
+    created_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (execution_process_id, seq),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
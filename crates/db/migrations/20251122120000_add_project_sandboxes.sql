@@ -0,0 +1,13 @@
+PRAGMA foreign_keys = ON;
+
+-- Docker image the coding agents of a project's attempts run in instead of on
+-- the host. Projects without a row run agents on the host.
+CREATE TABLE project_sandboxes (
+    project_id       BLOB PRIMARY KEY,
+    image            TEXT NOT NULL CHECK(image != ''),
+    -- Baked into a layer on top of the image, e.g. to install agent CLIs
+    bootstrap_script TEXT,
+    created_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
@@ -0,0 +1,17 @@
+PRAGMA foreign_keys = ON;
+
+-- MCP servers given to the coding agents of a project's attempts, next to
+-- the servers of each agent's own config.
+CREATE TABLE project_mcp_servers (
+    id         BLOB PRIMARY KEY,
+    project_id BLOB NOT NULL,
+    name       TEXT NOT NULL CHECK(name != ''),
+    command    TEXT NOT NULL CHECK(command != ''),
+    args       TEXT NOT NULL DEFAULT '[]',  -- JSON array of strings
+    env        TEXT NOT NULL DEFAULT '{}',  -- JSON object of strings
+    enabled    BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, name)
+);
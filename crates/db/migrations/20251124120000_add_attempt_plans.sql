@@ -0,0 +1,17 @@
+PRAGMA foreign_keys = ON;
+
+-- Plan of an attempt started in plan-first mode. The first coding agent run
+-- only plans; implementation starts once the plan is approved.
+CREATE TABLE attempt_plans (
+    task_attempt_id      BLOB PRIMARY KEY,
+    status               TEXT NOT NULL DEFAULT 'planning'
+                         CHECK (status IN ('planning', 'awaiting_approval', 'approved')),
+    plan                 TEXT,
+    -- The planning run the plan was taken from
+    execution_process_id BLOB,
+    approved_at          TEXT,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL
+);
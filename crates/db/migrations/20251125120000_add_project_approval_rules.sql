@@ -0,0 +1,16 @@
+PRAGMA foreign_keys = ON;
+
+-- Tool calls approved without asking, for agents running with approvals
+-- enabled. A rule matches a tool by name, or any tool with '*', and
+-- optionally the call's command or path against a '*' wildcard pattern.
+CREATE TABLE project_approval_rules (
+    id         BLOB PRIMARY KEY,
+    project_id BLOB NOT NULL,
+    tool_name  TEXT NOT NULL CHECK(tool_name != ''),
+    pattern    TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_project_approval_rules_project_id ON project_approval_rules(project_id);
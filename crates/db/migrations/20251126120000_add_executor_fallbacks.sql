@@ -0,0 +1,29 @@
+PRAGMA foreign_keys = ON;
+
+-- Executor profiles a project's coding agent runs fall back to, in order,
+-- when the model provider refuses a run (rate limits, quota, outages)
+CREATE TABLE project_executor_fallbacks (
+    project_id          BLOB NOT NULL,
+    position            INTEGER NOT NULL,
+    executor_profile_id TEXT NOT NULL,  -- JSON ExecutorProfileId
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (project_id, position),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+-- Runs started in place of a run the provider refused, and the profile they
+-- ran with
+CREATE TABLE execution_fallbacks (
+    execution_process_id        BLOB PRIMARY KEY,
+    failed_execution_process_id BLOB NOT NULL,
+    task_attempt_id             BLOB NOT NULL,
+    executor_profile_id         TEXT NOT NULL,  -- JSON ExecutorProfileId
+    -- The provider error that caused the fallback
+    reason                      TEXT NOT NULL,
+    created_at                  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    FOREIGN KEY (failed_execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_execution_fallbacks_task_attempt_id ON execution_fallbacks(task_attempt_id);
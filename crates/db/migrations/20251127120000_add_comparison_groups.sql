@@ -0,0 +1,26 @@
+PRAGMA foreign_keys = ON;
+
+-- Attempts of one task started together with different executor profiles,
+-- so their results can be compared and a winner picked
+CREATE TABLE comparison_groups (
+    id                BLOB PRIMARY KEY,
+    task_id           BLOB NOT NULL,
+    winner_attempt_id BLOB,
+    created_at        TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at        TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (winner_attempt_id) REFERENCES task_attempts(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_comparison_groups_task_id ON comparison_groups(task_id);
+
+CREATE TABLE comparison_group_attempts (
+    task_attempt_id     BLOB PRIMARY KEY,
+    comparison_group_id BLOB NOT NULL,
+    position            INTEGER NOT NULL,
+    executor_profile_id TEXT NOT NULL,  -- JSON ExecutorProfileId
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE,
+    FOREIGN KEY (comparison_group_id) REFERENCES comparison_groups(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_comparison_group_attempts_group_id ON comparison_group_attempts(comparison_group_id);
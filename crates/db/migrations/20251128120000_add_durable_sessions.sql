@@ -0,0 +1,27 @@
+PRAGMA foreign_keys = ON;
+
+-- OS process of a running execution, so that a restarted server can stop
+-- the process group it left behind
+CREATE TABLE execution_process_handles (
+    execution_process_id BLOB PRIMARY KEY,
+    pid                  INTEGER NOT NULL,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+-- Coding agent runs started at startup to continue the agent session of a
+-- run the server restart interrupted
+CREATE TABLE execution_resumptions (
+    execution_process_id             BLOB PRIMARY KEY,
+    interrupted_execution_process_id BLOB NOT NULL,
+    task_attempt_id                  BLOB NOT NULL,
+    session_id                       TEXT NOT NULL,
+    -- Last agent event stored for the interrupted run; null when it had none
+    last_event_seq                   INTEGER,
+    created_at                       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    FOREIGN KEY (interrupted_execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_execution_resumptions_task_attempt_id ON execution_resumptions(task_attempt_id);
@@ -0,0 +1,17 @@
+PRAGMA foreign_keys = ON;
+
+-- Attempts forked from the conversation of another attempt. The fork starts
+-- from the commit the source attempt was at before the forked message, and
+-- continues the agent session as it was before that message.
+CREATE TABLE attempt_forks (
+    task_attempt_id             BLOB PRIMARY KEY,
+    source_task_attempt_id      BLOB,
+    source_execution_process_id BLOB,
+    base_commit                 TEXT NOT NULL,
+    created_at                  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE,
+    FOREIGN KEY (source_task_attempt_id) REFERENCES task_attempts(id) ON DELETE SET NULL,
+    FOREIGN KEY (source_execution_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_attempt_forks_source_task_attempt_id ON attempt_forks(source_task_attempt_id);
@@ -0,0 +1,41 @@
+PRAGMA foreign_keys = ON;
+
+-- Test script run after every coding agent turn of a project's attempts.
+-- Projects without a row only run tests on demand.
+CREATE TABLE project_turn_tests (
+    project_id          BLOB PRIMARY KEY,
+    script_id           BLOB NOT NULL,
+    -- Send failing results back to the agent as a follow-up
+    feed_back_failures  INTEGER NOT NULL DEFAULT 0,
+    -- Follow-ups sent in a row before failures are left to the user
+    max_feedback_rounds INTEGER NOT NULL DEFAULT 3 CHECK(max_feedback_rounds >= 0),
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (script_id) REFERENCES project_scripts(id) ON DELETE CASCADE
+);
+
+-- Tests run after one coding agent turn. Counts are parsed from the test
+-- output and stay null when it has none.
+CREATE TABLE turn_test_runs (
+    execution_process_id           BLOB PRIMARY KEY,
+    coding_agent_process_id        BLOB NOT NULL,
+    task_attempt_id                BLOB NOT NULL,
+    passed                         INTEGER,
+    failed                         INTEGER,
+    skipped                        INTEGER,
+    -- Null while the tests run
+    success                        INTEGER,
+    -- Follow-up the failures were sent to the agent in
+    follow_up_execution_process_id BLOB,
+    created_at                     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    completed_at                   TEXT,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    FOREIGN KEY (coding_agent_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE,
+    FOREIGN KEY (follow_up_execution_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_turn_test_runs_task_attempt_id ON turn_test_runs(task_attempt_id);
+CREATE INDEX idx_turn_test_runs_coding_agent_process_id ON turn_test_runs(coding_agent_process_id);
+CREATE INDEX idx_turn_test_runs_follow_up_execution_process_id ON turn_test_runs(follow_up_execution_process_id);
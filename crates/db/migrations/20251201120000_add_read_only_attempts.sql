@@ -0,0 +1,2 @@
+-- Read-only attempts answer questions about the code without changing it
+ALTER TABLE task_attempts ADD COLUMN read_only BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,24 @@
+PRAGMA foreign_keys = ON;
+
+-- Follow-up work a coding agent proposed at the end of a run, kept as draft
+-- tasks until they are accepted into the board or dismissed
+CREATE TABLE proposed_tasks (
+    id                   BLOB PRIMARY KEY,
+    task_attempt_id      BLOB NOT NULL,
+    -- The coding agent run that proposed it
+    execution_process_id BLOB,
+    title                TEXT NOT NULL,
+    description          TEXT,
+    status               TEXT NOT NULL DEFAULT 'pending'
+                         CHECK (status IN ('pending', 'accepted', 'dismissed')),
+    -- The task created when it was accepted
+    task_id              BLOB,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE SET NULL
+);
+
+-- An agent repeating its proposals in a later turn does not add them twice
+CREATE UNIQUE INDEX idx_proposed_tasks_attempt_title ON proposed_tasks(task_attempt_id, title);
@@ -0,0 +1,16 @@
+PRAGMA foreign_keys = ON;
+
+-- Context prepended to the prompt of every attempt of a project: notes and
+-- the files matching the patterns
+CREATE TABLE project_context_packs (
+    id         BLOB PRIMARY KEY,
+    project_id BLOB NOT NULL,
+    name       TEXT NOT NULL CHECK(name != ''),
+    patterns   TEXT NOT NULL DEFAULT '[]',  -- JSON array of gitignore-style patterns
+    notes      TEXT,
+    enabled    BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, name)
+);
@@ -0,0 +1,11 @@
+PRAGMA foreign_keys = ON;
+
+-- Code host a project's pull requests are opened on. Projects without a row
+-- use the host detected from their remote URL.
+CREATE TABLE project_hosting (
+    project_id  BLOB PRIMARY KEY,
+    provider    TEXT NOT NULL CHECK (provider IN ('github', 'gitlab')),
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
@@ -0,0 +1,19 @@
+PRAGMA foreign_keys = ON;
+
+-- Allow Bitbucket Cloud as a project's code host. SQLite cannot alter a CHECK
+-- constraint, so the table is recreated.
+CREATE TABLE project_hosting_new (
+    project_id  BLOB PRIMARY KEY,
+    provider    TEXT NOT NULL CHECK (provider IN ('github', 'gitlab', 'bitbucket')),
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+INSERT INTO project_hosting_new (project_id, provider, created_at, updated_at)
+SELECT project_id, provider, created_at, updated_at
+FROM project_hosting;
+
+DROP TABLE project_hosting;
+
+ALTER TABLE project_hosting_new RENAME TO project_hosting;
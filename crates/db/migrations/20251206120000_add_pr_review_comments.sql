@@ -0,0 +1,32 @@
+PRAGMA foreign_keys = ON;
+
+-- Review comments left on the pull requests of attempts, copied from the code
+-- host so they can be sent back to the attempt's agent
+CREATE TABLE pr_review_comments (
+    id                   BLOB PRIMARY KEY,
+    task_attempt_id      BLOB NOT NULL,
+    -- The pull request the comment was left on
+    merge_id             BLOB NOT NULL,
+    -- Id of the comment on the code host
+    external_id          TEXT NOT NULL,
+    author               TEXT NOT NULL,
+    body                 TEXT NOT NULL,
+    -- File and line of inline comments
+    path                 TEXT,
+    line                 INTEGER,
+    url                  TEXT,
+    commented_at         TEXT NOT NULL,
+    -- The follow-up the comment was sent to the agent in
+    execution_process_id BLOB,
+    sent_at              TEXT,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE,
+    FOREIGN KEY (merge_id) REFERENCES merges(id) ON DELETE CASCADE,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL
+);
+
+-- Polling the same comment again updates it instead of adding it twice
+CREATE UNIQUE INDEX idx_pr_review_comments_merge_external
+    ON pr_review_comments(merge_id, external_id);
+CREATE INDEX idx_pr_review_comments_task_attempt_id ON pr_review_comments(task_attempt_id);
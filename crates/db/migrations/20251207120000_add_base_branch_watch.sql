@@ -0,0 +1,26 @@
+PRAGMA foreign_keys = ON;
+
+-- What to do with a project's attempts when their base branch gets new
+-- commits. Projects without a row leave their attempts alone.
+CREATE TABLE project_rebase_policies (
+    project_id BLOB PRIMARY KEY,
+    mode       TEXT NOT NULL CHECK (mode IN ('flag', 'rebase')),
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+-- How far an attempt's branch is behind its base branch, as last checked
+CREATE TABLE attempt_base_statuses (
+    task_attempt_id BLOB PRIMARY KEY,
+    -- Tip of the base branch when checked
+    base_commit     TEXT NOT NULL,
+    commits_behind  INTEGER NOT NULL DEFAULT 0,
+    -- Since when the base branch has commits the attempt branch lacks
+    stale_since     TEXT,
+    rebased_at      TEXT,
+    -- Why rebasing onto base_commit failed
+    rebase_error    TEXT,
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE
+);
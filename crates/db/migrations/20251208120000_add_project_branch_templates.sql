@@ -0,0 +1,12 @@
+PRAGMA foreign_keys = ON;
+
+-- Template new attempt branches of a project are named from, e.g.
+-- 'feat/{ticket}-{slug}'. Projects without a row use the branch prefix from
+-- the config.
+CREATE TABLE project_branch_templates (
+    project_id  BLOB PRIMARY KEY,
+    template    TEXT NOT NULL,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
@@ -0,0 +1,14 @@
+PRAGMA foreign_keys = ON;
+
+-- How a project's attempts are merged, locally and as pull requests.
+-- Projects without a row squash local merges and merge pull requests the way
+-- their code host defaults to.
+CREATE TABLE project_merge_settings (
+    project_id              BLOB PRIMARY KEY,
+    strategy                TEXT NOT NULL CHECK (strategy IN ('squash', 'rebase', 'merge_commit')),
+    -- e.g. '{title} ({task_id})'
+    commit_message_template TEXT,
+    created_at              TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at              TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+-- Whether a pull request is a draft. Drafts are open pull requests, so
+-- pr_status stays 'open' for them.
+ALTER TABLE merges ADD COLUMN pr_is_draft BOOLEAN NOT NULL DEFAULT FALSE;
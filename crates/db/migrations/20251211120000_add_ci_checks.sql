@@ -0,0 +1,21 @@
+PRAGMA foreign_keys = ON;
+
+-- CI checks and commit statuses of an attempt's pushed branch, copied from
+-- the code host so the board can show failing CI
+CREATE TABLE ci_checks (
+    id              BLOB PRIMARY KEY,
+    task_attempt_id BLOB NOT NULL,
+    -- Check run name or status context, unique per commit
+    name            TEXT NOT NULL,
+    state           TEXT NOT NULL CHECK (state IN ('pending', 'success', 'failure', 'neutral')),
+    url             TEXT,
+    -- Commit of the branch the check ran on
+    commit_sha      TEXT NOT NULL,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE
+);
+
+-- Polling the same check again updates it instead of adding it twice
+CREATE UNIQUE INDEX idx_ci_checks_task_attempt_name
+    ON ci_checks(task_attempt_id, name);
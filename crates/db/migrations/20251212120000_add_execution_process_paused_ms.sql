@@ -0,0 +1,2 @@
+-- Time a run spent suspended; its timeout deadline is pushed back by as much
+ALTER TABLE execution_processes ADD COLUMN paused_ms INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,19 @@
+PRAGMA foreign_keys = ON;
+
+-- Attempt starts waiting for executor capacity, so the queue survives a
+-- restart. Rows are in queue order by `queued_at`.
+CREATE TABLE execution_queue_entries (
+    task_attempt_id              BLOB PRIMARY KEY,
+    project_id                   BLOB NOT NULL,
+    executor_profile_id          TEXT NOT NULL,
+    -- Action of a start prepared by its caller, e.g. a fork continuing a
+    -- session; NULL to start from the task prompt
+    executor_action              TEXT,
+    run_reason                   TEXT,
+    -- Interrupted execution process the prepared run continues
+    resumes_execution_process_id BLOB,
+    queued_at                    TEXT NOT NULL,
+    FOREIGN KEY (task_attempt_id) REFERENCES task_attempts(id) ON DELETE CASCADE,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (resumes_execution_process_id) REFERENCES execution_processes(id) ON DELETE SET NULL
+);
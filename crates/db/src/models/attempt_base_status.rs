@@ -0,0 +1,110 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// How far an attempt's branch is behind its base branch, as last checked by
+/// the base branch watcher
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AttemptBaseStatus {
+    pub task_attempt_id: Uuid,
+    /// Tip of the base branch when checked
+    pub base_commit: String,
+    pub commits_behind: i64,
+    /// Since when the base branch has commits the attempt branch lacks; null
+    /// when the attempt is up to date
+    pub stale_since: Option<DateTime<Utc>>,
+    /// When the attempt was last rebased automatically
+    pub rebased_at: Option<DateTime<Utc>>,
+    /// Why rebasing onto `base_commit` failed
+    pub rebase_error: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AttemptBaseStatus {
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptBaseStatus,
+            r#"SELECT task_attempt_id as "task_attempt_id!: Uuid",
+                      base_commit,
+                      commits_behind,
+                      stale_since as "stale_since: DateTime<Utc>",
+                      rebased_at as "rebased_at: DateTime<Utc>",
+                      rebase_error,
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM attempt_base_statuses
+               WHERE task_attempt_id = $1"#,
+            task_attempt_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Statuses of the project's attempts that are behind their base branch
+    pub async fn find_stale_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptBaseStatus,
+            r#"SELECT s.task_attempt_id as "task_attempt_id!: Uuid",
+                      s.base_commit,
+                      s.commits_behind,
+                      s.stale_since as "stale_since: DateTime<Utc>",
+                      s.rebased_at as "rebased_at: DateTime<Utc>",
+                      s.rebase_error,
+                      s.updated_at as "updated_at!: DateTime<Utc>"
+               FROM attempt_base_statuses s
+               JOIN task_attempts ta ON ta.id = s.task_attempt_id
+               JOIN tasks t ON t.id = ta.task_id
+               WHERE t.project_id = $1 AND s.stale_since IS NOT NULL
+               ORDER BY s.stale_since ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        base_commit: &str,
+        commits_behind: i64,
+        stale_since: Option<DateTime<Utc>>,
+        rebased_at: Option<DateTime<Utc>>,
+        rebase_error: Option<&str>,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptBaseStatus,
+            r#"INSERT INTO attempt_base_statuses
+                   (task_attempt_id, base_commit, commits_behind, stale_since, rebased_at, rebase_error)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               ON CONFLICT(task_attempt_id) DO UPDATE SET
+                   base_commit = excluded.base_commit,
+                   commits_behind = excluded.commits_behind,
+                   stale_since = excluded.stale_since,
+                   rebased_at = excluded.rebased_at,
+                   rebase_error = excluded.rebase_error,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING task_attempt_id as "task_attempt_id!: Uuid",
+                         base_commit,
+                         commits_behind,
+                         stale_since as "stale_since: DateTime<Utc>",
+                         rebased_at as "rebased_at: DateTime<Utc>",
+                         rebase_error,
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            task_attempt_id,
+            base_commit,
+            commits_behind,
+            stale_since,
+            rebased_at,
+            rebase_error
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
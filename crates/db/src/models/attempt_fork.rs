@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Where an attempt forked from another attempt's conversation
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AttemptFork {
+    pub task_attempt_id: Uuid,
+    /// Null once the source attempt is deleted
+    pub source_task_attempt_id: Option<Uuid>,
+    /// The coding agent run whose prompt the fork replaced
+    pub source_execution_process_id: Option<Uuid>,
+    /// Commit the fork's branch started from
+    pub base_commit: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AttemptFork {
+    pub async fn create(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        source_task_attempt_id: Uuid,
+        source_execution_process_id: Uuid,
+        base_commit: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptFork,
+            r#"INSERT INTO attempt_forks (task_attempt_id, source_task_attempt_id, source_execution_process_id, base_commit)
+               VALUES ($1, $2, $3, $4)
+               RETURNING task_attempt_id as "task_attempt_id!: Uuid",
+                         source_task_attempt_id as "source_task_attempt_id: Uuid",
+                         source_execution_process_id as "source_execution_process_id: Uuid",
+                         base_commit,
+                         created_at as "created_at!: DateTime<Utc>""#,
+            task_attempt_id,
+            source_task_attempt_id,
+            source_execution_process_id,
+            base_commit
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptFork,
+            r#"SELECT task_attempt_id as "task_attempt_id!: Uuid",
+                      source_task_attempt_id as "source_task_attempt_id: Uuid",
+                      source_execution_process_id as "source_execution_process_id: Uuid",
+                      base_commit,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_forks
+               WHERE task_attempt_id = $1"#,
+            task_attempt_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Forks made from an attempt, oldest first
+    pub async fn find_by_source_task_attempt_id(
+        pool: &SqlitePool,
+        source_task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptFork,
+            r#"SELECT task_attempt_id as "task_attempt_id!: Uuid",
+                      source_task_attempt_id as "source_task_attempt_id: Uuid",
+                      source_execution_process_id as "source_execution_process_id: Uuid",
+                      base_commit,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM attempt_forks
+               WHERE source_task_attempt_id = $1
+               ORDER BY created_at ASC"#,
+            source_task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
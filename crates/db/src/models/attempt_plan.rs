@@ -0,0 +1,133 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AttemptPlanStatus {
+    /// The coding agent is writing the plan
+    Planning,
+    /// The plan is written and the attempt waits for it to be approved
+    AwaitingApproval,
+    /// Implementation was started from the plan
+    Approved,
+}
+
+/// Plan of an attempt started in plan-first mode
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AttemptPlan {
+    pub task_attempt_id: Uuid,
+    pub status: AttemptPlanStatus,
+    /// The plan as written by the agent, or as edited when it was approved
+    pub plan: Option<String>,
+    /// The planning run the plan was taken from
+    pub execution_process_id: Option<Uuid>,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ApproveAttemptPlan {
+    /// Edited plan to implement instead of the one the agent wrote
+    #[serde(default)]
+    #[ts(optional)]
+    pub plan: Option<String>,
+}
+
+impl AttemptPlan {
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptPlan,
+            r#"SELECT task_attempt_id as "task_attempt_id!: Uuid",
+                      status as "status!: AttemptPlanStatus",
+                      plan,
+                      execution_process_id as "execution_process_id: Uuid",
+                      approved_at as "approved_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM attempt_plans
+               WHERE task_attempt_id = $1"#,
+            task_attempt_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, task_attempt_id: Uuid) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptPlan,
+            r#"INSERT INTO attempt_plans (task_attempt_id)
+               VALUES ($1)
+               RETURNING task_attempt_id as "task_attempt_id!: Uuid",
+                         status as "status!: AttemptPlanStatus",
+                         plan,
+                         execution_process_id as "execution_process_id: Uuid",
+                         approved_at as "approved_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            task_attempt_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Store the plan written by a planning run. Returns false when the
+    /// attempt is not planning, so a later run cannot replace the plan.
+    pub async fn submit(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        plan: &str,
+        execution_process_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE attempt_plans
+               SET status = 'awaiting_approval',
+                   plan = $2,
+                   execution_process_id = $3,
+                   updated_at = datetime('now', 'subsec')
+               WHERE task_attempt_id = $1 AND status = 'planning'"#,
+            task_attempt_id,
+            plan,
+            execution_process_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Approve the plan awaiting approval, replacing its text with `plan`.
+    /// Returns None when no plan awaits approval.
+    pub async fn approve(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        plan: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptPlan,
+            r#"UPDATE attempt_plans
+               SET status = 'approved',
+                   plan = $2,
+                   approved_at = datetime('now', 'subsec'),
+                   updated_at = datetime('now', 'subsec')
+               WHERE task_attempt_id = $1 AND status = 'awaiting_approval'
+               RETURNING task_attempt_id as "task_attempt_id!: Uuid",
+                         status as "status!: AttemptPlanStatus",
+                         plan,
+                         execution_process_id as "execution_process_id: Uuid",
+                         approved_at as "approved_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            task_attempt_id,
+            plan
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
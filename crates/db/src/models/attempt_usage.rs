@@ -0,0 +1,194 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Token usage and cost reported by the coding agent of one execution process
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AttemptUsage {
+    pub id: Uuid,
+    pub execution_process_id: Uuid,
+    pub task_attempt_id: Uuid,
+    pub model: Option<String>,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_input_tokens: i64,
+    pub cache_creation_input_tokens: i64,
+    /// Cost in USD, when the executor reports one
+    pub cost_usd: Option<f64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UpsertAttemptUsage {
+    pub model: Option<String>,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_input_tokens: i64,
+    pub cache_creation_input_tokens: i64,
+    pub cost_usd: Option<f64>,
+}
+
+/// Usage summed over a set of execution processes
+#[derive(Debug, Clone, Default, FromRow, Serialize, TS)]
+pub struct UsageTotals {
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_input_tokens: i64,
+    pub cache_creation_input_tokens: i64,
+    /// Sum of reported costs; null when no execution reported a cost
+    pub cost_usd: Option<f64>,
+    /// Number of execution processes that reported usage
+    pub executions: i64,
+}
+
+impl AttemptUsage {
+    /// Store the latest usage report for an execution process. Reports are
+    /// running totals, so a newer one replaces the previous row.
+    pub async fn upsert(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        data: &UpsertAttemptUsage,
+    ) -> Result<(), sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO attempt_usage (id, execution_process_id, task_attempt_id, model, input_tokens, output_tokens, cache_read_input_tokens, cache_creation_input_tokens, cost_usd)
+               SELECT $1, ep.id, ep.task_attempt_id, $3, $4, $5, $6, $7, $8
+               FROM execution_processes ep
+               WHERE ep.id = $2
+               ON CONFLICT(execution_process_id) DO UPDATE SET
+                 model = COALESCE(excluded.model, attempt_usage.model),
+                 input_tokens = excluded.input_tokens,
+                 output_tokens = excluded.output_tokens,
+                 cache_read_input_tokens = excluded.cache_read_input_tokens,
+                 cache_creation_input_tokens = excluded.cache_creation_input_tokens,
+                 cost_usd = excluded.cost_usd,
+                 updated_at = datetime('now', 'subsec')"#,
+            id,
+            execution_process_id,
+            data.model,
+            data.input_tokens,
+            data.output_tokens,
+            data.cache_read_input_tokens,
+            data.cache_creation_input_tokens,
+            data.cost_usd
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AttemptUsage,
+            r#"SELECT id as "id!: Uuid",
+                      execution_process_id as "execution_process_id!: Uuid",
+                      task_attempt_id as "task_attempt_id!: Uuid",
+                      model,
+                      input_tokens,
+                      output_tokens,
+                      cache_read_input_tokens,
+                      cache_creation_input_tokens,
+                      cost_usd,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM attempt_usage
+               WHERE task_attempt_id = $1
+               ORDER BY created_at ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn totals_for_task_attempt(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<UsageTotals, sqlx::Error> {
+        sqlx::query_as!(
+            UsageTotals,
+            r#"SELECT COALESCE(SUM(input_tokens), 0) as "input_tokens!: i64",
+                      COALESCE(SUM(output_tokens), 0) as "output_tokens!: i64",
+                      COALESCE(SUM(cache_read_input_tokens), 0) as "cache_read_input_tokens!: i64",
+                      COALESCE(SUM(cache_creation_input_tokens), 0) as "cache_creation_input_tokens!: i64",
+                      SUM(cost_usd) as "cost_usd: f64",
+                      COUNT(*) as "executions!: i64"
+               FROM attempt_usage
+               WHERE task_attempt_id = $1"#,
+            task_attempt_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn totals_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<UsageTotals, sqlx::Error> {
+        sqlx::query_as!(
+            UsageTotals,
+            r#"SELECT COALESCE(SUM(au.input_tokens), 0) as "input_tokens!: i64",
+                      COALESCE(SUM(au.output_tokens), 0) as "output_tokens!: i64",
+                      COALESCE(SUM(au.cache_read_input_tokens), 0) as "cache_read_input_tokens!: i64",
+                      COALESCE(SUM(au.cache_creation_input_tokens), 0) as "cache_creation_input_tokens!: i64",
+                      SUM(au.cost_usd) as "cost_usd: f64",
+                      COUNT(au.id) as "executions!: i64"
+               FROM attempt_usage au
+               JOIN task_attempts ta ON ta.id = au.task_attempt_id
+               WHERE ta.task_id = $1"#,
+            task_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn totals_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<UsageTotals, sqlx::Error> {
+        sqlx::query_as!(
+            UsageTotals,
+            r#"SELECT COALESCE(SUM(au.input_tokens), 0) as "input_tokens!: i64",
+                      COALESCE(SUM(au.output_tokens), 0) as "output_tokens!: i64",
+                      COALESCE(SUM(au.cache_read_input_tokens), 0) as "cache_read_input_tokens!: i64",
+                      COALESCE(SUM(au.cache_creation_input_tokens), 0) as "cache_creation_input_tokens!: i64",
+                      SUM(au.cost_usd) as "cost_usd: f64",
+                      COUNT(au.id) as "executions!: i64"
+               FROM attempt_usage au
+               JOIN task_attempts ta ON ta.id = au.task_attempt_id
+               JOIN tasks t ON t.id = ta.task_id
+               WHERE t.project_id = $1"#,
+            project_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn totals_for_workspace(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<UsageTotals, sqlx::Error> {
+        sqlx::query_as!(
+            UsageTotals,
+            r#"SELECT COALESCE(SUM(au.input_tokens), 0) as "input_tokens!: i64",
+                      COALESCE(SUM(au.output_tokens), 0) as "output_tokens!: i64",
+                      COALESCE(SUM(au.cache_read_input_tokens), 0) as "cache_read_input_tokens!: i64",
+                      COALESCE(SUM(au.cache_creation_input_tokens), 0) as "cache_creation_input_tokens!: i64",
+                      SUM(au.cost_usd) as "cost_usd: f64",
+                      COUNT(au.id) as "executions!: i64"
+               FROM attempt_usage au
+               JOIN task_attempts ta ON ta.id = au.task_attempt_id
+               JOIN tasks t ON t.id = ta.task_id
+               JOIN projects p ON p.id = t.project_id
+               WHERE p.workspace_id = $1"#,
+            workspace_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -0,0 +1,103 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A named snapshot of a task attempt: the worktree HEAD commit plus the
+/// executor session that produced it, so a new attempt can resume from here.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct Checkpoint {
+    pub id: Uuid,
+    pub task_attempt_id: Uuid,
+    pub name: String,
+    pub commit_oid: String,
+    pub execution_process_id: Option<Uuid>,
+    pub session_id: Option<String>, // External executor session ID, if any
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateCheckpoint {
+    pub name: String,
+    pub commit_oid: String,
+    pub execution_process_id: Option<Uuid>,
+    pub session_id: Option<String>,
+}
+
+impl Checkpoint {
+    pub async fn create(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        data: &CreateCheckpoint,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            Checkpoint,
+            r#"INSERT INTO task_attempt_checkpoints (id, task_attempt_id, name, commit_oid, execution_process_id, session_id)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         task_attempt_id as "task_attempt_id!: Uuid",
+                         name as "name!",
+                         commit_oid as "commit_oid!",
+                         execution_process_id as "execution_process_id: Uuid",
+                         session_id,
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            task_attempt_id,
+            data.name,
+            data.commit_oid,
+            data.execution_process_id,
+            data.session_id,
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Checkpoint,
+            r#"SELECT id as "id!: Uuid",
+                      task_attempt_id as "task_attempt_id!: Uuid",
+                      name as "name!",
+                      commit_oid as "commit_oid!",
+                      execution_process_id as "execution_process_id: Uuid",
+                      session_id,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_attempt_checkpoints
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Checkpoint,
+            r#"SELECT id as "id!: Uuid",
+                      task_attempt_id as "task_attempt_id!: Uuid",
+                      name as "name!",
+                      commit_oid as "commit_oid!",
+                      execution_process_id as "execution_process_id: Uuid",
+                      session_id,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_attempt_checkpoints
+               WHERE task_attempt_id = $1
+               ORDER BY created_at ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_attempt_checkpoints WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,188 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::Display;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Outcome of a CI check, with the states of the different code hosts mapped
+/// onto these
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, Display)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum CiCheckState {
+    /// Queued or running
+    Pending,
+    Success,
+    /// Failed, errored, timed out or cancelled
+    Failure,
+    /// Skipped, or finished without passing or failing
+    Neutral,
+}
+
+impl CiCheckState {
+    /// State of a set of checks as a whole: failure if any failed, pending
+    /// while any runs, success otherwise. None without checks.
+    pub fn combined<'a>(checks: impl IntoIterator<Item = &'a CiCheck>) -> Option<Self> {
+        checks.into_iter().fold(None, |combined, check| {
+            Some(match (combined, check.state) {
+                (Some(Self::Failure), _) | (_, Self::Failure) => Self::Failure,
+                (Some(Self::Pending), _) | (_, Self::Pending) => Self::Pending,
+                _ => Self::Success,
+            })
+        })
+    }
+}
+
+/// A CI check or commit status of an attempt's pushed branch, as last fetched
+/// from the code host
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct CiCheck {
+    pub id: Uuid,
+    pub task_attempt_id: Uuid,
+    /// Check run name or status context
+    pub name: String,
+    pub state: CiCheckState,
+    /// Page of the check on the CI service or code host
+    pub url: Option<String>,
+    /// Commit of the branch the check ran on
+    pub commit_sha: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A check as read from the code host
+#[derive(Debug, Clone)]
+pub struct CreateCiCheck {
+    pub name: String,
+    pub state: CiCheckState,
+    pub url: Option<String>,
+    pub commit_sha: String,
+}
+
+impl CiCheck {
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CiCheck,
+            r#"SELECT id as "id!: Uuid",
+                      task_attempt_id as "task_attempt_id!: Uuid",
+                      name,
+                      state as "state!: CiCheckState",
+                      url,
+                      commit_sha,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM ci_checks
+               WHERE task_attempt_id = $1
+               ORDER BY name ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Checks of the latest attempt of each of the project's tasks
+    pub async fn find_latest_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CiCheck,
+            r#"SELECT cc.id as "id!: Uuid",
+                      cc.task_attempt_id as "task_attempt_id!: Uuid",
+                      cc.name,
+                      cc.state as "state!: CiCheckState",
+                      cc.url,
+                      cc.commit_sha,
+                      cc.created_at as "created_at!: DateTime<Utc>",
+                      cc.updated_at as "updated_at!: DateTime<Utc>"
+               FROM ci_checks cc
+               JOIN task_attempts ta ON ta.id = cc.task_attempt_id
+               JOIN tasks t ON t.id = ta.task_id
+               WHERE t.project_id = $1
+                 AND ta.id = (SELECT latest.id
+                                FROM task_attempts latest
+                               WHERE latest.task_id = t.id
+                               ORDER BY latest.created_at DESC
+                               LIMIT 1)
+               ORDER BY cc.name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            CiCheck,
+            r#"SELECT id as "id!: Uuid",
+                      task_attempt_id as "task_attempt_id!: Uuid",
+                      name,
+                      state as "state!: CiCheckState",
+                      url,
+                      commit_sha,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM ci_checks
+               WHERE rowid = $1"#,
+            rowid
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Store a check of the attempt, or refresh the stored copy when its
+    /// state, link or commit changed. Unchanged checks are left alone so
+    /// polling doesn't produce events.
+    pub async fn upsert(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        data: &CreateCiCheck,
+    ) -> Result<(), sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO ci_checks (id, task_attempt_id, name, state, url, commit_sha)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               ON CONFLICT (task_attempt_id, name) DO UPDATE
+               SET state = excluded.state,
+                   url = excluded.url,
+                   commit_sha = excluded.commit_sha,
+                   updated_at = datetime('now', 'subsec')
+               WHERE state IS NOT excluded.state
+                  OR url IS NOT excluded.url
+                  OR commit_sha IS NOT excluded.commit_sha"#,
+            id,
+            task_attempt_id,
+            data.name,
+            data.state,
+            data.url,
+            data.commit_sha
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Forget checks that didn't run on the branch's latest commit
+    pub async fn delete_missing(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        names: &[String],
+    ) -> Result<u64, sqlx::Error> {
+        let keep = serde_json::to_string(names).unwrap_or_else(|_| "[]".to_string());
+        let result = sqlx::query!(
+            r#"DELETE FROM ci_checks
+               WHERE task_attempt_id = $1
+                 AND name NOT IN (SELECT value FROM json_each($2))"#,
+            task_attempt_id,
+            keep
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,138 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Attempts of one task started together with different executor profiles
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ComparisonGroup {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// The attempt the user picked, once they did
+    pub winner_attempt_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// An attempt of a comparison group and the profile it was started with
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ComparisonGroupAttempt {
+    pub task_attempt_id: Uuid,
+    pub comparison_group_id: Uuid,
+    pub position: i64,
+    #[ts(type = "ExecutorProfileId")]
+    pub executor_profile_id: Json<ExecutorProfileId>,
+}
+
+impl ComparisonGroup {
+    /// Create a group of the attempts in `attempts`, in order
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        attempts: &[(Uuid, ExecutorProfileId)],
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let mut tx = pool.begin().await?;
+        let group = sqlx::query_as!(
+            ComparisonGroup,
+            r#"INSERT INTO comparison_groups (id, task_id)
+               VALUES ($1, $2)
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         winner_attempt_id as "winner_attempt_id: Uuid",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            task_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        for (position, (task_attempt_id, profile)) in attempts.iter().enumerate() {
+            let position = position as i64;
+            let profile = Json(profile);
+            sqlx::query!(
+                "INSERT INTO comparison_group_attempts (task_attempt_id, comparison_group_id, position, executor_profile_id) VALUES ($1, $2, $3, $4)",
+                task_attempt_id,
+                id,
+                position,
+                profile
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(group)
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ComparisonGroup,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      winner_attempt_id as "winner_attempt_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM comparison_groups
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ComparisonGroup,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      winner_attempt_id as "winner_attempt_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM comparison_groups
+               WHERE task_id = $1
+               ORDER BY created_at DESC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_attempts(
+        pool: &SqlitePool,
+        id: Uuid,
+    ) -> Result<Vec<ComparisonGroupAttempt>, sqlx::Error> {
+        sqlx::query_as!(
+            ComparisonGroupAttempt,
+            r#"SELECT task_attempt_id as "task_attempt_id!: Uuid",
+                      comparison_group_id as "comparison_group_id!: Uuid",
+                      position as "position!: i64",
+                      executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>"
+               FROM comparison_group_attempts
+               WHERE comparison_group_id = $1
+               ORDER BY position ASC"#,
+            id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn set_winner(
+        pool: &SqlitePool,
+        id: Uuid,
+        winner_attempt_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE comparison_groups SET winner_attempt_id = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            id,
+            winner_attempt_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A coding agent run started in place of one the model provider refused
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ExecutionFallback {
+    pub execution_process_id: Uuid,
+    pub failed_execution_process_id: Uuid,
+    pub task_attempt_id: Uuid,
+    /// The profile the run actually used
+    #[ts(type = "ExecutorProfileId")]
+    pub executor_profile_id: Json<ExecutorProfileId>,
+    /// The provider error of the failed run
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ExecutionFallback {
+    pub async fn create(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        failed_execution_process_id: Uuid,
+        task_attempt_id: Uuid,
+        executor_profile_id: &ExecutorProfileId,
+        reason: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let executor_profile_id = Json(executor_profile_id);
+        sqlx::query_as!(
+            ExecutionFallback,
+            r#"INSERT INTO execution_fallbacks (execution_process_id, failed_execution_process_id, task_attempt_id, executor_profile_id, reason)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING execution_process_id as "execution_process_id!: Uuid",
+                         failed_execution_process_id as "failed_execution_process_id!: Uuid",
+                         task_attempt_id as "task_attempt_id!: Uuid",
+                         executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                         reason,
+                         created_at as "created_at!: DateTime<Utc>""#,
+            execution_process_id,
+            failed_execution_process_id,
+            task_attempt_id,
+            executor_profile_id,
+            reason
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionFallback,
+            r#"SELECT execution_process_id as "execution_process_id!: Uuid",
+                      failed_execution_process_id as "failed_execution_process_id!: Uuid",
+                      task_attempt_id as "task_attempt_id!: Uuid",
+                      executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                      reason,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM execution_fallbacks
+               WHERE task_attempt_id = $1
+               ORDER BY created_at ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -45,6 +45,7 @@ pub enum ExecutionProcessStatus {
 pub enum ExecutionProcessRunReason {
     SetupScript,
     CleanupScript,
+    TestScript,
     CodingAgent,
     DevServer,
 }
@@ -267,6 +268,27 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Count running processes other than dev servers, grouped by project
+    pub async fn count_running_by_project(
+        pool: &SqlitePool,
+    ) -> Result<Vec<(Uuid, i64)>, sqlx::Error> {
+        let records = sqlx::query!(
+            r#"SELECT t.project_id as "project_id!: Uuid", COUNT(*) as "count!: i64"
+               FROM execution_processes ep
+               JOIN task_attempts ta ON ep.task_attempt_id = ta.id
+               JOIN tasks t ON ta.task_id = t.id
+               WHERE ep.status = 'running' AND ep.run_reason != 'devserver'
+               GROUP BY t.project_id"#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| (r.project_id, r.count))
+            .collect())
+    }
+
     /// Find latest session_id by task attempt (simple scalar query)
     pub async fn find_latest_session_id_by_task_attempt(
         pool: &SqlitePool,
@@ -296,6 +318,32 @@ impl ExecutionProcess {
         Ok(row.and_then(|r| r.session_id))
     }
 
+    /// Session of the latest coding agent run of an attempt that started
+    /// before `boundary_process_id`
+    pub async fn find_session_id_before(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        boundary_process_id: Uuid,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT es.session_id
+               FROM execution_processes ep
+               JOIN executor_sessions es ON ep.id = es.execution_process_id
+               WHERE ep.task_attempt_id = $1
+                 AND ep.run_reason = 'codingagent'
+                 AND ep.dropped = FALSE
+                 AND es.session_id IS NOT NULL
+                 AND ep.created_at < (SELECT created_at FROM execution_processes WHERE id = $2)
+               ORDER BY ep.created_at DESC
+               LIMIT 1"#,
+            task_attempt_id,
+            boundary_process_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.and_then(|r| r.session_id))
+    }
+
     /// Find latest execution process by task attempt and run reason
     pub async fn find_latest_by_task_attempt_and_run_reason(
         pool: &SqlitePool,
@@ -391,6 +439,18 @@ impl ExecutionProcess {
         Ok(())
     }
 
+    /// Milliseconds the process spent paused, not counting a pause still
+    /// in progress
+    pub async fn find_paused_ms(pool: &SqlitePool, id: Uuid) -> Result<i64, sqlx::Error> {
+        let paused_ms = sqlx::query_scalar!(
+            r#"SELECT paused_ms as "paused_ms!: i64" FROM execution_processes WHERE id = $1"#,
+            id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(paused_ms)
+    }
+
     /// Update the "after" commit oid for the process
     pub async fn update_after_head_commit(
         pool: &SqlitePool,
@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use executors::logs::events::AgentEvent;
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// An [`AgentEvent`] as stored for an execution process
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ExecutionProcessEvent {
+    pub execution_process_id: Uuid,
+    pub seq: i64,
+    #[ts(type = "AgentEvent")]
+    pub event: sqlx::types::Json<AgentEvent>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ExecutionProcessEvent {
+    pub async fn append(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        seq: i64,
+        event: &AgentEvent,
+    ) -> Result<(), sqlx::Error> {
+        let event_type = event.type_name();
+        let event = sqlx::types::Json(event);
+        sqlx::query!(
+            r#"INSERT INTO execution_process_events (execution_process_id, seq, event_type, event)
+               VALUES ($1, $2, $3, $4)"#,
+            execution_process_id,
+            seq,
+            event_type,
+            event
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Events of an execution process in order, optionally only those after
+    /// `after_seq`
+    pub async fn find_by_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        after_seq: Option<i64>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let after_seq = after_seq.unwrap_or(-1);
+        sqlx::query_as!(
+            ExecutionProcessEvent,
+            r#"SELECT execution_process_id as "execution_process_id!: Uuid",
+                      seq,
+                      event as "event!: sqlx::types::Json<AgentEvent>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM execution_process_events
+               WHERE execution_process_id = $1 AND seq > $2
+               ORDER BY seq ASC"#,
+            execution_process_id,
+            after_seq
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Sequence number of the last event stored for an execution process
+    pub async fn last_seq(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Option<i64>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT MAX(seq) as "seq: i64"
+               FROM execution_process_events
+               WHERE execution_process_id = $1"#,
+            execution_process_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -0,0 +1,48 @@
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// The OS process a running execution was spawned as
+pub struct ExecutionProcessHandle;
+
+impl ExecutionProcessHandle {
+    pub async fn upsert(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        pid: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO execution_process_handles (execution_process_id, pid)
+               VALUES ($1, $2)
+               ON CONFLICT(execution_process_id) DO UPDATE SET pid = excluded.pid"#,
+            execution_process_id,
+            pid
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_pid(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Option<i64>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT pid as "pid!: i64"
+               FROM execution_process_handles
+               WHERE execution_process_id = $1"#,
+            execution_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, execution_process_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM execution_process_handles WHERE execution_process_id = $1",
+            execution_process_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+use executors::{actions::ExecutorAction, profile::ExecutorProfileId};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use uuid::Uuid;
+
+use super::execution_process::ExecutionProcessRunReason;
+
+/// An attempt start waiting in the execution queue
+#[derive(Debug, Clone, FromRow)]
+pub struct ExecutionQueueEntry {
+    pub task_attempt_id: Uuid,
+    pub project_id: Uuid,
+    pub executor_profile_id: Json<ExecutorProfileId>,
+    /// Set, with `run_reason`, for starts prepared by their caller instead of
+    /// built from the task prompt
+    pub executor_action: Option<Json<ExecutorAction>>,
+    pub run_reason: Option<ExecutionProcessRunReason>,
+    /// Interrupted execution process the prepared run continues
+    pub resumes_execution_process_id: Option<Uuid>,
+    pub queued_at: DateTime<Utc>,
+}
+
+impl ExecutionQueueEntry {
+    /// All entries in queue order
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionQueueEntry,
+            r#"SELECT task_attempt_id as "task_attempt_id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                      executor_action as "executor_action: Json<ExecutorAction>",
+                      run_reason as "run_reason: ExecutionProcessRunReason",
+                      resumes_execution_process_id as "resumes_execution_process_id: Uuid",
+                      queued_at as "queued_at!: DateTime<Utc>"
+               FROM execution_queue_entries
+               ORDER BY queued_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Store an entry; an attempt that is already queued keeps its place
+    pub async fn create(pool: &SqlitePool, entry: &Self) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO execution_queue_entries
+                   (task_attempt_id, project_id, executor_profile_id, executor_action,
+                    run_reason, resumes_execution_process_id, queued_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               ON CONFLICT (task_attempt_id) DO NOTHING"#,
+            entry.task_attempt_id,
+            entry.project_id,
+            entry.executor_profile_id,
+            entry.executor_action,
+            entry.run_reason,
+            entry.resumes_execution_process_id,
+            entry.queued_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, task_attempt_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM execution_queue_entries WHERE task_attempt_id = $1",
+            task_attempt_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
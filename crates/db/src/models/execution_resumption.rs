@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A coding agent run that continued the session of a run interrupted by a
+/// server restart
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ExecutionResumption {
+    pub execution_process_id: Uuid,
+    pub interrupted_execution_process_id: Uuid,
+    pub task_attempt_id: Uuid,
+    /// The agent session that was continued
+    pub session_id: String,
+    /// Last agent event stored for the interrupted run; null when it had none
+    pub last_event_seq: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ExecutionResumption {
+    pub async fn create(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        interrupted_execution_process_id: Uuid,
+        task_attempt_id: Uuid,
+        session_id: &str,
+        last_event_seq: Option<i64>,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionResumption,
+            r#"INSERT INTO execution_resumptions (execution_process_id, interrupted_execution_process_id, task_attempt_id, session_id, last_event_seq)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING execution_process_id as "execution_process_id!: Uuid",
+                         interrupted_execution_process_id as "interrupted_execution_process_id!: Uuid",
+                         task_attempt_id as "task_attempt_id!: Uuid",
+                         session_id,
+                         last_event_seq,
+                         created_at as "created_at!: DateTime<Utc>""#,
+            execution_process_id,
+            interrupted_execution_process_id,
+            task_attempt_id,
+            session_id,
+            last_event_seq
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionResumption,
+            r#"SELECT execution_process_id as "execution_process_id!: Uuid",
+                      interrupted_execution_process_id as "interrupted_execution_process_id!: Uuid",
+                      task_attempt_id as "task_attempt_id!: Uuid",
+                      session_id,
+                      last_event_seq,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM execution_resumptions
+               WHERE task_attempt_id = $1
+               ORDER BY created_at ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,189 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::{Display, EnumString};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+#[derive(
+    Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, EnumString, Display,
+)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum ExternalProvider {
+    Jira,
+    Linear,
+}
+
+/// Link from a task to the tracker ticket it mirrors
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ExternalTaskLink {
+    pub task_id: Uuid,
+    pub provider: ExternalProvider,
+    pub external_id: String,
+    pub external_key: String,
+    pub external_url: String,
+    /// Task status as of the last sync
+    pub last_synced_status: TaskStatus,
+    /// Tracker state as of the last sync
+    pub remote_state: String,
+    pub synced_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct CreateExternalTaskLink<'a> {
+    pub task_id: Uuid,
+    pub provider: ExternalProvider,
+    pub external_id: &'a str,
+    pub external_key: &'a str,
+    pub external_url: &'a str,
+    pub last_synced_status: TaskStatus,
+    pub remote_state: &'a str,
+}
+
+/// A link together with the current status of its task
+#[derive(Debug, Clone)]
+pub struct ExternalTaskLinkWithStatus {
+    pub link: ExternalTaskLink,
+    pub task_status: TaskStatus,
+}
+
+impl ExternalTaskLink {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExternalTaskLink,
+            r#"SELECT task_id as "task_id!: Uuid",
+                      provider as "provider!: ExternalProvider",
+                      external_id,
+                      external_key,
+                      external_url,
+                      last_synced_status as "last_synced_status!: TaskStatus",
+                      remote_state,
+                      synced_at as "synced_at!: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM external_task_links
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// External ids of a provider that already have a task
+    pub async fn linked_external_ids(
+        pool: &SqlitePool,
+        provider: ExternalProvider,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT external_id as "external_id!: String"
+               FROM external_task_links
+               WHERE provider = $1"#,
+            provider
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Every link of a provider with the status its task has now, outside
+    /// archived projects
+    pub async fn find_by_provider_with_status(
+        pool: &SqlitePool,
+        provider: ExternalProvider,
+    ) -> Result<Vec<ExternalTaskLinkWithStatus>, sqlx::Error> {
+        let records = sqlx::query!(
+            r#"SELECT l.task_id as "task_id!: Uuid",
+                      l.provider as "provider!: ExternalProvider",
+                      l.external_id,
+                      l.external_key,
+                      l.external_url,
+                      l.last_synced_status as "last_synced_status!: TaskStatus",
+                      l.remote_state,
+                      l.synced_at as "synced_at!: DateTime<Utc>",
+                      l.created_at as "created_at!: DateTime<Utc>",
+                      t.status as "task_status!: TaskStatus"
+               FROM external_task_links l
+               JOIN tasks t ON t.id = l.task_id
+               JOIN projects p ON p.id = t.project_id
+               WHERE l.provider = $1
+                 AND t.deleted_at IS NULL
+                 AND p.deleted_at IS NULL
+                 AND p.archived_at IS NULL"#,
+            provider
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|rec| ExternalTaskLinkWithStatus {
+                link: ExternalTaskLink {
+                    task_id: rec.task_id,
+                    provider: rec.provider,
+                    external_id: rec.external_id,
+                    external_key: rec.external_key,
+                    external_url: rec.external_url,
+                    last_synced_status: rec.last_synced_status,
+                    remote_state: rec.remote_state,
+                    synced_at: rec.synced_at,
+                    created_at: rec.created_at,
+                },
+                task_status: rec.task_status,
+            })
+            .collect())
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateExternalTaskLink<'_>,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ExternalTaskLink,
+            r#"INSERT INTO external_task_links (task_id, provider, external_id, external_key, external_url, last_synced_status, remote_state)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING task_id as "task_id!: Uuid",
+                         provider as "provider!: ExternalProvider",
+                         external_id,
+                         external_key,
+                         external_url,
+                         last_synced_status as "last_synced_status!: TaskStatus",
+                         remote_state,
+                         synced_at as "synced_at!: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            data.task_id,
+            data.provider,
+            data.external_id,
+            data.external_key,
+            data.external_url,
+            data.last_synced_status,
+            data.remote_state
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Record that task and ticket agree on `status` / `remote_state`
+    pub async fn mark_synced(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        status: TaskStatus,
+        remote_state: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE external_task_links
+               SET last_synced_status = $2, remote_state = $3, synced_at = datetime('now', 'subsec')
+               WHERE task_id = $1"#,
+            task_id,
+            status,
+            remote_state
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Back-link from a task to the GitHub issue it was imported from
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct GitHubIssueLink {
+    pub task_id: Uuid,
+    pub repo_owner: String,
+    pub repo_name: String,
+    pub issue_number: i64,
+    pub issue_url: String,
+    /// Close the issue once the task is done
+    pub sync_status: bool,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct CreateGitHubIssueLink<'a> {
+    pub task_id: Uuid,
+    pub repo_owner: &'a str,
+    pub repo_name: &'a str,
+    pub issue_number: i64,
+    pub issue_url: &'a str,
+    pub sync_status: bool,
+}
+
+impl GitHubIssueLink {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueLink,
+            r#"SELECT task_id as "task_id!: Uuid",
+                      repo_owner,
+                      repo_name,
+                      issue_number,
+                      issue_url,
+                      sync_status as "sync_status!: bool",
+                      closed_at as "closed_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM github_issue_links
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Issue numbers of a repository that already have a task
+    pub async fn linked_issue_numbers(
+        pool: &SqlitePool,
+        repo_owner: &str,
+        repo_name: &str,
+    ) -> Result<Vec<i64>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT issue_number as "issue_number!: i64"
+               FROM github_issue_links
+               WHERE repo_owner = $1 AND repo_name = $2"#,
+            repo_owner,
+            repo_name
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Synced links whose task is done but whose issue is still open, outside
+    /// archived projects
+    pub async fn find_pending_close(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueLink,
+            r#"SELECT l.task_id as "task_id!: Uuid",
+                      l.repo_owner,
+                      l.repo_name,
+                      l.issue_number,
+                      l.issue_url,
+                      l.sync_status as "sync_status!: bool",
+                      l.closed_at as "closed_at: DateTime<Utc>",
+                      l.created_at as "created_at!: DateTime<Utc>"
+               FROM github_issue_links l
+               JOIN tasks t ON t.id = l.task_id
+               JOIN projects p ON p.id = t.project_id
+               WHERE l.sync_status = TRUE
+                 AND l.closed_at IS NULL
+                 AND t.status = 'done'
+                 AND t.deleted_at IS NULL
+                 AND p.deleted_at IS NULL
+                 AND p.archived_at IS NULL"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateGitHubIssueLink<'_>,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            GitHubIssueLink,
+            r#"INSERT INTO github_issue_links (task_id, repo_owner, repo_name, issue_number, issue_url, sync_status)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING task_id as "task_id!: Uuid",
+                         repo_owner,
+                         repo_name,
+                         issue_number,
+                         issue_url,
+                         sync_status as "sync_status!: bool",
+                         closed_at as "closed_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            data.task_id,
+            data.repo_owner,
+            data.repo_name,
+            data.issue_number,
+            data.issue_url,
+            data.sync_status
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn mark_closed(pool: &SqlitePool, task_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE github_issue_links SET closed_at = datetime('now', 'subsec') WHERE task_id = $1",
+            task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
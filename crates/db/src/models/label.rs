@@ -0,0 +1,250 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A label that can be attached to any number of tasks and attempts
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct Label {
+    pub id: Uuid,
+    pub name: String,
+    /// Display color, e.g. "#f97316"
+    pub color: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateLabel {
+    pub name: String,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateLabel {
+    pub name: Option<String>,
+    pub color: Option<String>,
+}
+
+/// The full set of labels to keep on a task or attempt
+#[derive(Debug, Deserialize, TS)]
+pub struct SetLabels {
+    pub label_ids: Vec<Uuid>,
+}
+
+impl Label {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT id as "id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels
+               ORDER BY name ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT id as "id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, data: &CreateLabel) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            Label,
+            r#"INSERT INTO labels (id, name, color)
+               VALUES ($1, $2, $3)
+               RETURNING id as "id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.name,
+            data.color
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateLabel,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let color = data.color.as_ref().or(existing.color.as_ref());
+
+        sqlx::query_as!(
+            Label,
+            r#"UPDATE labels
+               SET name = $2, color = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", name, color, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            color
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM labels WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT l.id as "id!: Uuid", l.name, l.color, l.created_at as "created_at!: DateTime<Utc>", l.updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels l
+               JOIN task_labels tl ON tl.label_id = l.id
+               WHERE tl.task_id = $1
+               ORDER BY l.name ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT l.id as "id!: Uuid", l.name, l.color, l.created_at as "created_at!: DateTime<Utc>", l.updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels l
+               JOIN task_attempt_labels tal ON tal.label_id = l.id
+               WHERE tal.task_attempt_id = $1
+               ORDER BY l.name ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Ids of the tasks carrying this label
+    pub async fn task_ids(pool: &SqlitePool, label_id: Uuid) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT task_id as "task_id!: Uuid" FROM task_labels WHERE label_id = $1"#,
+            label_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// (task id, label id) pairs for the labelled tasks of a project
+    pub async fn task_label_ids_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<(Uuid, Uuid)>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT tl.task_id as "task_id!: Uuid", tl.label_id as "label_id!: Uuid"
+               FROM task_labels tl
+               JOIN tasks t ON t.id = tl.task_id
+               WHERE t.project_id = $1"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.task_id, row.label_id))
+            .collect())
+    }
+
+    /// Ids of the task attempts carrying this label
+    pub async fn task_attempt_ids(
+        pool: &SqlitePool,
+        label_id: Uuid,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT task_attempt_id as "task_attempt_id!: Uuid" FROM task_attempt_labels WHERE label_id = $1"#,
+            label_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Attach a label to a task, keeping its other labels
+    pub async fn add_to_task<'e, E>(
+        executor: E,
+        task_id: Uuid,
+        label_id: Uuid,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query!(
+            "INSERT OR IGNORE INTO task_labels (task_id, label_id) VALUES ($1, $2)",
+            task_id,
+            label_id
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Replace the labels of a task with `label_ids`
+    pub async fn set_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        label_ids: &[Uuid],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!("DELETE FROM task_labels WHERE task_id = $1", task_id)
+            .execute(&mut *tx)
+            .await?;
+        for label_id in label_ids {
+            sqlx::query!(
+                "INSERT OR IGNORE INTO task_labels (task_id, label_id) VALUES ($1, $2)",
+                task_id,
+                label_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Replace the labels of a task attempt with `label_ids`
+    pub async fn set_for_task_attempt(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        label_ids: &[Uuid],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            "DELETE FROM task_attempt_labels WHERE task_attempt_id = $1",
+            task_attempt_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        for label_id in label_ids {
+            sqlx::query!(
+                "INSERT OR IGNORE INTO task_attempt_labels (task_attempt_id, label_id) VALUES ($1, $2)",
+                task_attempt_id,
+                label_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+}
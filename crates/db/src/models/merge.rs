@@ -47,6 +47,9 @@ pub struct PullRequestInfo {
     pub status: MergeStatus,
     pub merged_at: Option<chrono::DateTime<chrono::Utc>>,
     pub merge_commit_sha: Option<String>,
+    /// Open but not ready for review yet
+    #[serde(default)]
+    pub is_draft: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -68,6 +71,7 @@ struct MergeRow {
     pr_status: Option<MergeStatus>,
     pr_merged_at: Option<DateTime<Utc>>,
     pr_merge_commit_sha: Option<String>,
+    pr_is_draft: bool,
     created_at: DateTime<Utc>,
 }
 
@@ -104,6 +108,7 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_is_draft as "pr_is_draft!: bool",
                 created_at as "created_at!: DateTime<Utc>",
                 target_branch_name as "target_branch_name!: String"
             "#,
@@ -124,6 +129,7 @@ impl Merge {
         target_branch_name: &str,
         pr_number: i64,
         pr_url: &str,
+        is_draft: bool,
     ) -> Result<PrMerge, sqlx::Error> {
         let id = Uuid::new_v4();
         let now = Utc::now();
@@ -131,8 +137,8 @@ impl Merge {
         sqlx::query_as!(
             MergeRow,
             r#"INSERT INTO merges (
-                id, task_attempt_id, merge_type, pr_number, pr_url, pr_status, created_at, target_branch_name
-            ) VALUES ($1, $2, 'pr', $3, $4, 'open', $5, $6)
+                id, task_attempt_id, merge_type, pr_number, pr_url, pr_status, pr_is_draft, created_at, target_branch_name
+            ) VALUES ($1, $2, 'pr', $3, $4, 'open', $5, $6, $7)
             RETURNING 
                 id as "id!: Uuid",
                 task_attempt_id as "task_attempt_id!: Uuid",
@@ -143,6 +149,7 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_is_draft as "pr_is_draft!: bool",
                 created_at as "created_at!: DateTime<Utc>",
                 target_branch_name as "target_branch_name!: String"
             "#,
@@ -150,6 +157,7 @@ impl Merge {
             task_attempt_id,
             pr_number,
             pr_url,
+            is_draft,
             now,
             target_branch_name
         )
@@ -158,7 +166,7 @@ impl Merge {
         .map(Into::into)
     }
 
-    /// Get all open PRs for monitoring
+    /// Get all open PRs for monitoring, outside archived projects
     pub async fn get_open_prs(pool: &SqlitePool) -> Result<Vec<PrMerge>, sqlx::Error> {
         let rows = sqlx::query_as!(
             MergeRow,
@@ -172,10 +180,17 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_is_draft as "pr_is_draft!: bool",
                 created_at as "created_at!: DateTime<Utc>",
                 target_branch_name as "target_branch_name!: String"
                FROM merges 
                WHERE merge_type = 'pr' AND pr_status = 'open'
+                 AND task_attempt_id NOT IN (
+                     SELECT ta.id FROM task_attempts ta
+                       JOIN tasks t ON t.id = ta.task_id
+                       JOIN projects p ON p.id = t.project_id
+                      WHERE p.archived_at IS NOT NULL
+                 )
                ORDER BY created_at DESC"#,
         )
         .fetch_all(pool)
@@ -213,6 +228,22 @@ impl Merge {
 
         Ok(())
     }
+    /// Record whether a pull request is a draft
+    pub async fn update_draft(
+        pool: &SqlitePool,
+        merge_id: Uuid,
+        is_draft: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE merges SET pr_is_draft = $1 WHERE id = $2",
+            is_draft,
+            merge_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     /// Find all merges for a task attempt (returns both direct and PR merges)
     pub async fn find_by_task_attempt_id(
         pool: &SqlitePool,
@@ -231,6 +262,7 @@ impl Merge {
                 pr_status as "pr_status?: MergeStatus",
                 pr_merged_at as "pr_merged_at?: DateTime<Utc>",
                 pr_merge_commit_sha,
+                pr_is_draft as "pr_is_draft!: bool",
                 target_branch_name as "target_branch_name!: String",
                 created_at as "created_at!: DateTime<Utc>"
             FROM merges 
@@ -283,6 +315,7 @@ impl From<MergeRow> for PrMerge {
                 status: row.pr_status.expect("pr merge must have status"),
                 merged_at: row.pr_merged_at,
                 merge_commit_sha: row.pr_merge_commit_sha,
+                is_draft: row.pr_is_draft,
             },
             created_at: row.created_at,
         }
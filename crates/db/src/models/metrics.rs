@@ -0,0 +1,285 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::{task::TaskStatus, task_transition::TaskTransition};
+
+/// Summary of a set of durations, in seconds
+#[derive(Debug, Clone, Default, Serialize, TS)]
+pub struct DurationStats {
+    pub count: i64,
+    pub average_seconds: Option<f64>,
+    pub median_seconds: Option<f64>,
+    /// 85% of the durations are at most this long
+    pub p85_seconds: Option<f64>,
+}
+
+impl DurationStats {
+    pub fn from_seconds(mut seconds: Vec<f64>) -> Self {
+        if seconds.is_empty() {
+            return Self::default();
+        }
+        seconds.sort_by(f64::total_cmp);
+        let count = seconds.len();
+        // Nearest-rank percentile
+        let percentile = |p: f64| seconds[((p * count as f64).ceil() as usize).clamp(1, count) - 1];
+        Self {
+            count: count as i64,
+            average_seconds: Some(seconds.iter().sum::<f64>() / count as f64),
+            median_seconds: Some(percentile(0.5)),
+            p85_seconds: Some(percentile(0.85)),
+        }
+    }
+}
+
+/// Time tasks spent in one board column. The time in done and cancelled
+/// columns only counts once a task moved out of them again.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct ColumnTime {
+    pub column_id: Uuid,
+    pub name: String,
+    pub status: TaskStatus,
+    /// Tasks that spent time in the column
+    pub tasks: i64,
+    pub total_seconds: f64,
+    pub average_seconds: f64,
+}
+
+/// Tasks completed during the week starting on `week_start` (a Monday)
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct WeeklyThroughput {
+    pub week_start: NaiveDate,
+    pub completed: i64,
+}
+
+/// How long the processes of an attempt ran
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct AttemptDuration {
+    pub task_attempt_id: Uuid,
+    pub task_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    /// Time the coding agent ran, summed over its finished runs
+    pub agent_seconds: f64,
+    /// From the first process start to the last process end; null until a
+    /// process has finished
+    pub wall_seconds: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ProjectMetrics {
+    /// Start of the measured period, on a Monday
+    pub since: DateTime<Utc>,
+    /// From when work started on a task (its first move to in progress, or
+    /// its creation if it never was) to when it was last moved to done, for
+    /// tasks completed during the period
+    pub cycle_time: DurationStats,
+    pub time_in_column: Vec<ColumnTime>,
+    /// One entry per week of the period, oldest first
+    pub throughput: Vec<WeeklyThroughput>,
+    /// Coding agent time of the attempts started during the period
+    pub attempt_agent_time: DurationStats,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct TaskMetrics {
+    /// Set once the task is done
+    pub cycle_time_seconds: Option<f64>,
+    pub time_in_column: Vec<ColumnTime>,
+    pub attempts: Vec<AttemptDuration>,
+    pub transitions: Vec<TaskTransition>,
+}
+
+#[derive(Debug, FromRow)]
+struct CompletedTask {
+    started_at: DateTime<Utc>,
+    completed_at: DateTime<Utc>,
+}
+
+impl CompletedTask {
+    fn cycle_seconds(&self) -> f64 {
+        (self.completed_at - self.started_at).num_milliseconds() as f64 / 1000.0
+    }
+}
+
+/// Monday of the week `date` falls in
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+impl ProjectMetrics {
+    /// Metrics over the last `weeks` calendar weeks, the current one included
+    pub async fn for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        weeks: u32,
+    ) -> Result<Self, sqlx::Error> {
+        let first_week = week_start(Utc::now().date_naive())
+            - Duration::weeks(i64::from(weeks.saturating_sub(1)));
+        let since = first_week
+            .and_hms_opt(0, 0, 0)
+            .unwrap_or_default()
+            .and_utc();
+
+        let completed = completed_tasks(pool, Some(project_id), None, since).await?;
+        let mut throughput: Vec<WeeklyThroughput> = (0..i64::from(weeks.max(1)))
+            .map(|week| WeeklyThroughput {
+                week_start: first_week + Duration::weeks(week),
+                completed: 0,
+            })
+            .collect();
+        for task in &completed {
+            let week = week_start(task.completed_at.date_naive());
+            if let Some(entry) = throughput.iter_mut().find(|entry| entry.week_start == week) {
+                entry.completed += 1;
+            }
+        }
+
+        let attempt_agent_time = attempt_durations(pool, Some(project_id), None, since)
+            .await?
+            .into_iter()
+            .map(|attempt| attempt.agent_seconds)
+            .collect();
+
+        Ok(Self {
+            since,
+            cycle_time: DurationStats::from_seconds(
+                completed.iter().map(CompletedTask::cycle_seconds).collect(),
+            ),
+            time_in_column: time_in_column(pool, Some(project_id), None, since).await?,
+            throughput,
+            attempt_agent_time: DurationStats::from_seconds(attempt_agent_time),
+        })
+    }
+}
+
+impl TaskMetrics {
+    pub async fn for_task(pool: &SqlitePool, task_id: Uuid) -> Result<Self, sqlx::Error> {
+        let since = DateTime::<Utc>::UNIX_EPOCH;
+        let cycle_time_seconds = completed_tasks(pool, None, Some(task_id), since)
+            .await?
+            .first()
+            .map(CompletedTask::cycle_seconds);
+        Ok(Self {
+            cycle_time_seconds,
+            time_in_column: time_in_column(pool, None, Some(task_id), since).await?,
+            attempts: attempt_durations(pool, None, Some(task_id), since).await?,
+            transitions: TaskTransition::find_by_task_id(pool, task_id).await?,
+        })
+    }
+}
+
+/// Done tasks, outside the trash, last moved to done at or after `since`
+async fn completed_tasks(
+    pool: &SqlitePool,
+    project_id: Option<Uuid>,
+    task_id: Option<Uuid>,
+    since: DateTime<Utc>,
+) -> Result<Vec<CompletedTask>, sqlx::Error> {
+    sqlx::query_as!(
+        CompletedTask,
+        r#"SELECT started_at as "started_at!: DateTime<Utc>",
+                  completed_at as "completed_at!: DateTime<Utc>"
+           FROM (
+               SELECT COALESCE(
+                          (SELECT MIN(tr.created_at) FROM task_transitions tr
+                            WHERE tr.task_id = t.id AND tr.status = 'inprogress'),
+                          t.created_at
+                      ) AS started_at,
+                      (SELECT MAX(tr.created_at) FROM task_transitions tr
+                        WHERE tr.task_id = t.id AND tr.status = 'done') AS completed_at
+               FROM tasks t
+               WHERE t.status = 'done'
+                 AND t.deleted_at IS NULL
+                 AND ($1 IS NULL OR t.project_id = $1)
+                 AND ($2 IS NULL OR t.id = $2)
+           )
+           WHERE completed_at IS NOT NULL
+             AND datetime(completed_at) >= datetime($3)
+           ORDER BY completed_at ASC"#,
+        project_id,
+        task_id,
+        since
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Time spent per column in intervals that started at or after `since`, in
+/// board order
+async fn time_in_column(
+    pool: &SqlitePool,
+    project_id: Option<Uuid>,
+    task_id: Option<Uuid>,
+    since: DateTime<Utc>,
+) -> Result<Vec<ColumnTime>, sqlx::Error> {
+    sqlx::query_as!(
+        ColumnTime,
+        r#"SELECT c.id as "column_id!: Uuid",
+                  c.name as "name!",
+                  c.status as "status!: TaskStatus",
+                  COUNT(DISTINCT i.task_id) as "tasks!: i64",
+                  SUM(i.seconds) as "total_seconds!: f64",
+                  AVG(i.seconds) as "average_seconds!: f64"
+           FROM (
+               SELECT tr.task_id,
+                      tr.column_id,
+                      tr.status,
+                      tr.created_at,
+                      (julianday(COALESCE(
+                           LEAD(tr.created_at) OVER (PARTITION BY tr.task_id ORDER BY tr.created_at),
+                           datetime('now', 'subsec')
+                       )) - julianday(tr.created_at)) * 86400.0 AS seconds,
+                      LEAD(tr.created_at) OVER (PARTITION BY tr.task_id ORDER BY tr.created_at) IS NULL AS is_current
+               FROM task_transitions tr
+               JOIN tasks t ON t.id = tr.task_id
+               WHERE t.deleted_at IS NULL
+                 AND ($1 IS NULL OR t.project_id = $1)
+                 AND ($2 IS NULL OR t.id = $2)
+           ) i
+           JOIN project_columns c ON c.id = i.column_id
+           WHERE datetime(i.created_at) >= datetime($3)
+             AND NOT (i.is_current AND i.status IN ('done', 'cancelled'))
+           GROUP BY c.id
+           ORDER BY c.position ASC"#,
+        project_id,
+        task_id,
+        since
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Attempts created at or after `since`, oldest first
+async fn attempt_durations(
+    pool: &SqlitePool,
+    project_id: Option<Uuid>,
+    task_id: Option<Uuid>,
+    since: DateTime<Utc>,
+) -> Result<Vec<AttemptDuration>, sqlx::Error> {
+    sqlx::query_as!(
+        AttemptDuration,
+        r#"SELECT ta.id as "task_attempt_id!: Uuid",
+                  ta.task_id as "task_id!: Uuid",
+                  ta.created_at as "created_at!: DateTime<Utc>",
+                  COALESCE(SUM(CASE WHEN ep.run_reason = 'codingagent' AND ep.completed_at IS NOT NULL
+                                    THEN (julianday(ep.completed_at) - julianday(ep.started_at)) * 86400.0
+                               END), 0.0) as "agent_seconds!: f64",
+                  (julianday(MAX(ep.completed_at)) - julianday(MIN(ep.started_at))) * 86400.0 as "wall_seconds: f64"
+           FROM task_attempts ta
+           JOIN tasks t ON t.id = ta.task_id
+           LEFT JOIN execution_processes ep ON ep.task_attempt_id = ta.id AND ep.dropped = FALSE
+           WHERE t.deleted_at IS NULL
+             AND ($1 IS NULL OR t.project_id = $1)
+             AND ($2 IS NULL OR ta.task_id = $2)
+             AND datetime(ta.created_at) >= datetime($3)
+           GROUP BY ta.id
+           ORDER BY ta.created_at ASC"#,
+        project_id,
+        task_id,
+        since
+    )
+    .fetch_all(pool)
+    .await
+}
@@ -1,10 +1,55 @@
+pub mod attempt_base_status;
+pub mod attempt_fork;
+pub mod attempt_plan;
+pub mod attempt_usage;
+pub mod checkpoint;
+pub mod ci_check;
+pub mod comparison_group;
 pub mod draft;
+pub mod execution_fallback;
 pub mod execution_process;
+pub mod execution_process_event;
+pub mod execution_process_handle;
 pub mod execution_process_logs;
+pub mod execution_queue_entry;
+pub mod execution_resumption;
 pub mod executor_session;
+pub mod external_task_link;
+pub mod github_issue_link;
 pub mod image;
+pub mod label;
 pub mod merge;
+pub mod metrics;
+pub mod pr_review_comment;
 pub mod project;
+pub mod project_approval_rule;
+pub mod project_branch_template;
+pub mod project_column;
+pub mod project_context_pack;
+pub mod project_env_var;
+pub mod project_executor_fallback;
+pub mod project_hosting;
+pub mod project_mcp_server;
+pub mod project_merge_settings;
+pub mod project_rebase_policy;
+pub mod project_repository;
+pub mod project_sandbox;
+pub mod project_script;
+pub mod project_turn_test;
+pub mod proposed_task;
+pub mod queued_follow_up;
+pub mod search;
 pub mod tag;
 pub mod task;
+pub mod task_activity;
+pub mod task_attachment;
 pub mod task_attempt;
+pub mod task_comment;
+pub mod task_dependency;
+pub mod task_recurrence;
+pub mod task_schedule;
+pub mod task_template;
+pub mod task_transition;
+pub mod trash;
+pub mod turn_test_run;
+pub mod workspace;
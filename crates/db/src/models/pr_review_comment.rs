@@ -0,0 +1,152 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A review comment on one of an attempt's pull requests, as last fetched
+/// from the code host
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct PrReviewComment {
+    pub id: Uuid,
+    pub task_attempt_id: Uuid,
+    /// The pull request the comment was left on
+    pub merge_id: Uuid,
+    /// Id of the comment on the code host
+    pub external_id: String,
+    pub author: String,
+    pub body: String,
+    /// File of an inline comment
+    pub path: Option<String>,
+    /// Line of an inline comment in the new version of the file
+    pub line: Option<i64>,
+    pub url: Option<String>,
+    pub commented_at: DateTime<Utc>,
+    /// The follow-up the comment was sent to the agent in
+    pub execution_process_id: Option<Uuid>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A review comment as read from the code host
+#[derive(Debug, Clone)]
+pub struct CreatePrReviewComment {
+    pub external_id: String,
+    pub author: String,
+    pub body: String,
+    pub path: Option<String>,
+    pub line: Option<i64>,
+    pub url: Option<String>,
+    pub commented_at: DateTime<Utc>,
+}
+
+impl PrReviewComment {
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PrReviewComment,
+            r#"SELECT id as "id!: Uuid",
+                      task_attempt_id as "task_attempt_id!: Uuid",
+                      merge_id as "merge_id!: Uuid",
+                      external_id,
+                      author,
+                      body,
+                      path,
+                      line,
+                      url,
+                      commented_at as "commented_at!: DateTime<Utc>",
+                      execution_process_id as "execution_process_id: Uuid",
+                      sent_at as "sent_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM pr_review_comments
+               WHERE task_attempt_id = $1
+               ORDER BY commented_at ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Store a comment of the pull request `merge_id`, or refresh the stored
+    /// copy when it was edited. Returns whether it is new.
+    pub async fn upsert(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        merge_id: Uuid,
+        data: &CreatePrReviewComment,
+    ) -> Result<bool, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let stored_id = sqlx::query_scalar!(
+            r#"INSERT INTO pr_review_comments
+                   (id, task_attempt_id, merge_id, external_id, author, body, path, line, url, commented_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+               ON CONFLICT (merge_id, external_id) DO UPDATE
+               SET body = excluded.body,
+                   path = excluded.path,
+                   line = excluded.line,
+                   updated_at = datetime('now', 'subsec')
+               WHERE body IS NOT excluded.body
+                  OR path IS NOT excluded.path
+                  OR line IS NOT excluded.line
+               RETURNING id as "id!: Uuid""#,
+            id,
+            task_attempt_id,
+            merge_id,
+            data.external_id,
+            data.author,
+            data.body,
+            data.path,
+            data.line,
+            data.url,
+            data.commented_at
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(stored_id == Some(id))
+    }
+
+    /// Forget comments of the pull request that were deleted on the code
+    /// host, keeping those already sent to the agent
+    pub async fn delete_missing(
+        pool: &SqlitePool,
+        merge_id: Uuid,
+        external_ids: &[String],
+    ) -> Result<u64, sqlx::Error> {
+        let keep = serde_json::to_string(external_ids).unwrap_or_else(|_| "[]".to_string());
+        let result = sqlx::query!(
+            r#"DELETE FROM pr_review_comments
+               WHERE merge_id = $1
+                 AND sent_at IS NULL
+                 AND external_id NOT IN (SELECT value FROM json_each($2))"#,
+            merge_id,
+            keep
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Record that the comment was sent to the agent in a follow-up
+    pub async fn mark_sent(
+        pool: &SqlitePool,
+        id: Uuid,
+        execution_process_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE pr_review_comments
+               SET execution_process_id = $2,
+                   sent_at = datetime('now', 'subsec'),
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            execution_process_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -7,6 +7,8 @@ use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
+use super::project_column::ProjectColumn;
+
 #[derive(Debug, Error)]
 pub enum ProjectError {
     #[error(transparent)]
@@ -30,6 +32,15 @@ pub struct Project {
     pub dev_script: Option<String>,
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
+    /// Cancel executions running longer than this many minutes
+    pub execution_timeout_minutes: Option<i64>,
+    /// Push attempt branches to the remote after every coding agent turn
+    pub auto_push_branches: bool,
+    /// Workspace the project is grouped under
+    pub workspace_id: Option<Uuid>,
+    /// Archived projects are read-only and run nothing
+    #[ts(type = "Date | null")]
+    pub archived_at: Option<DateTime<Utc>>,
 
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
@@ -56,6 +67,24 @@ pub struct UpdateProject {
     pub dev_script: Option<String>,
     pub cleanup_script: Option<String>,
     pub copy_files: Option<String>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub execution_timeout_minutes: Option<i64>,
+    /// Left unchanged when omitted
+    #[serde(default)]
+    #[ts(optional)]
+    pub auto_push_branches: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct DuplicateProject {
+    /// Defaults to the source project's name with a " (copy)" suffix
+    pub name: Option<String>,
+    /// Existing git repository the copy points at
+    pub git_repo_path: String,
+    /// Also copy the tasks that are not done or cancelled, as to do
+    #[serde(default)]
+    pub include_open_tasks: bool,
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -79,10 +108,18 @@ impl Project {
             .await
     }
 
-    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+    /// Projects outside the trash; archived ones only when `include_archived`
+    pub async fn find_all(
+        pool: &SqlitePool,
+        include_archived: bool,
+    ) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects ORDER BY created_at DESC"#
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, execution_timeout_minutes, auto_push_branches as "auto_push_branches!: bool", workspace_id as "workspace_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM projects
+               WHERE deleted_at IS NULL AND ($1 OR archived_at IS NULL)
+               ORDER BY created_at DESC"#,
+            include_archived
         )
         .fetch_all(pool)
         .await
@@ -93,10 +130,10 @@ impl Project {
         sqlx::query_as!(
             Project,
             r#"
-            SELECT p.id as "id!: Uuid", p.name, p.git_repo_path, p.setup_script, p.dev_script, p.cleanup_script, p.copy_files, 
+            SELECT p.id as "id!: Uuid", p.name, p.git_repo_path, p.setup_script, p.dev_script, p.cleanup_script, p.copy_files, p.execution_timeout_minutes, p.auto_push_branches as "auto_push_branches!: bool", p.workspace_id as "workspace_id: Uuid", p.archived_at as "archived_at: DateTime<Utc>",
                    p.created_at as "created_at!: DateTime<Utc>", p.updated_at as "updated_at!: DateTime<Utc>"
             FROM projects p
-            WHERE p.id IN (
+            WHERE p.deleted_at IS NULL AND p.archived_at IS NULL AND p.id IN (
                 SELECT DISTINCT t.project_id
                 FROM tasks t
                 INNER JOIN task_attempts ta ON ta.task_id = t.id
@@ -113,7 +150,7 @@ impl Project {
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE id = $1"#,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, execution_timeout_minutes, auto_push_branches as "auto_push_branches!: bool", workspace_id as "workspace_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE id = $1"#,
             id
         )
         .fetch_optional(pool)
@@ -126,7 +163,7 @@ impl Project {
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1"#,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, execution_timeout_minutes, auto_push_branches as "auto_push_branches!: bool", workspace_id as "workspace_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1"#,
             git_repo_path
         )
         .fetch_optional(pool)
@@ -140,7 +177,7 @@ impl Project {
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1 AND id != $2"#,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, execution_timeout_minutes, auto_push_branches as "auto_push_branches!: bool", workspace_id as "workspace_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>" FROM projects WHERE git_repo_path = $1 AND id != $2"#,
             git_repo_path,
             exclude_id
         )
@@ -153,9 +190,9 @@ impl Project {
         data: &CreateProject,
         project_id: Uuid,
     ) -> Result<Self, sqlx::Error> {
-        sqlx::query_as!(
+        let project = sqlx::query_as!(
             Project,
-            r#"INSERT INTO projects (id, name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO projects (id, name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, execution_timeout_minutes, auto_push_branches as "auto_push_branches!: bool", workspace_id as "workspace_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             project_id,
             data.name,
             data.git_repo_path,
@@ -165,7 +202,281 @@ impl Project {
             data.copy_files
         )
         .fetch_one(pool)
-        .await
+        .await?;
+        ProjectColumn::create_defaults(pool, project.id).await?;
+        Ok(project)
+    }
+
+    /// Create a copy of `source` pointing at another repository, with its
+    /// settings, board columns, scripts, environment variables, MCP servers,
+    /// context packs, approval rules, executor fallbacks, sandbox, code host,
+    /// rebase policy, branch name template, merge settings, turn tests and
+    /// templates.
+    /// With `include_open_tasks`, open tasks are copied as to do, keeping
+    /// their labels, subtask links and dependencies among the copied tasks.
+    pub async fn duplicate(
+        pool: &SqlitePool,
+        source: &Project,
+        project_id: Uuid,
+        name: &str,
+        git_repo_path: &str,
+        include_open_tasks: bool,
+    ) -> Result<Self, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!(
+            r#"INSERT INTO projects (id, name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, execution_timeout_minutes, auto_push_branches, workspace_id)
+               SELECT $1, $2, $3, setup_script, dev_script, cleanup_script, copy_files, execution_timeout_minutes, auto_push_branches, workspace_id
+                 FROM projects
+                WHERE id = $4"#,
+            project_id,
+            name,
+            git_repo_path,
+            source.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"INSERT INTO project_columns (id, project_id, name, position, status, wip_limit, enforce_wip_limit)
+               SELECT randomblob(16), $1, name, position, status, wip_limit, enforce_wip_limit
+                 FROM project_columns
+                WHERE project_id = $2"#,
+            project_id,
+            source.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"INSERT INTO project_scripts (id, project_id, kind, name, script, position, timeout_seconds, max_retries)
+               SELECT randomblob(16), $1, kind, name, script, position, timeout_seconds, max_retries
+                 FROM project_scripts
+                WHERE project_id = $2"#,
+            project_id,
+            source.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"INSERT INTO project_env_vars (id, project_id, name, encrypted_value, is_secret)
+               SELECT randomblob(16), $1, name, encrypted_value, is_secret
+                 FROM project_env_vars
+                WHERE project_id = $2"#,
+            project_id,
+            source.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"INSERT INTO project_mcp_servers (id, project_id, name, command, args, env, enabled)
+               SELECT randomblob(16), $1, name, command, args, env, enabled
+                 FROM project_mcp_servers
+                WHERE project_id = $2"#,
+            project_id,
+            source.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"INSERT INTO project_context_packs (id, project_id, name, patterns, notes, enabled)
+               SELECT randomblob(16), $1, name, patterns, notes, enabled
+                 FROM project_context_packs
+                WHERE project_id = $2"#,
+            project_id,
+            source.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"INSERT INTO project_approval_rules (id, project_id, tool_name, pattern)
+               SELECT randomblob(16), $1, tool_name, pattern
+                 FROM project_approval_rules
+                WHERE project_id = $2"#,
+            project_id,
+            source.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"INSERT INTO project_executor_fallbacks (project_id, position, executor_profile_id)
+               SELECT $1, position, executor_profile_id
+                 FROM project_executor_fallbacks
+                WHERE project_id = $2"#,
+            project_id,
+            source.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"INSERT INTO project_sandboxes (project_id, image, bootstrap_script)
+               SELECT $1, image, bootstrap_script
+                 FROM project_sandboxes
+                WHERE project_id = $2"#,
+            project_id,
+            source.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"INSERT INTO project_hosting (project_id, provider)
+               SELECT $1, provider
+                 FROM project_hosting
+                WHERE project_id = $2"#,
+            project_id,
+            source.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"INSERT INTO project_rebase_policies (project_id, mode)
+               SELECT $1, mode
+                 FROM project_rebase_policies
+                WHERE project_id = $2"#,
+            project_id,
+            source.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"INSERT INTO project_branch_templates (project_id, template)
+               SELECT $1, template
+                 FROM project_branch_templates
+                WHERE project_id = $2"#,
+            project_id,
+            source.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"INSERT INTO project_merge_settings (project_id, strategy, commit_message_template)
+               SELECT $1, strategy, commit_message_template
+                 FROM project_merge_settings
+                WHERE project_id = $2"#,
+            project_id,
+            source.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        // The test script is matched by kind and name among the copied scripts
+        sqlx::query!(
+            r#"INSERT INTO project_turn_tests (project_id, script_id, feed_back_failures, max_feedback_rounds)
+               SELECT $1, copy.id, turn_tests.feed_back_failures, turn_tests.max_feedback_rounds
+                 FROM project_turn_tests turn_tests
+                 JOIN project_scripts source_script ON source_script.id = turn_tests.script_id
+                 JOIN project_scripts copy
+                   ON copy.project_id = $1
+                  AND copy.kind = source_script.kind
+                  AND copy.name = source_script.name
+                WHERE turn_tests.project_id = $2"#,
+            project_id,
+            source.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"INSERT INTO task_templates (id, project_id, name, title, description, executor_profile_id, base_branch)
+               SELECT randomblob(16), $1, name, title, description, executor_profile_id, base_branch
+                 FROM task_templates
+                WHERE project_id = $2"#,
+            project_id,
+            source.id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if include_open_tasks {
+            let tasks = sqlx::query!(
+                r#"SELECT id as "id!: Uuid", parent_task_id as "parent_task_id: Uuid"
+                   FROM tasks
+                   WHERE project_id = $1
+                     AND deleted_at IS NULL
+                     AND status NOT IN ('done', 'cancelled')
+                   ORDER BY created_at ASC"#,
+                source.id
+            )
+            .fetch_all(&mut *tx)
+            .await?;
+
+            let new_ids: HashMap<Uuid, Uuid> =
+                tasks.iter().map(|task| (task.id, Uuid::new_v4())).collect();
+            for task in &tasks {
+                let new_id = new_ids[&task.id];
+                sqlx::query!(
+                    r#"INSERT INTO tasks (id, project_id, title, description, status, priority, due_date, assignee)
+                       SELECT $1, $2, title, description, 'todo', priority, due_date, assignee
+                         FROM tasks
+                        WHERE id = $3"#,
+                    new_id,
+                    project_id,
+                    task.id
+                )
+                .execute(&mut *tx)
+                .await?;
+                sqlx::query!(
+                    r#"INSERT INTO task_labels (task_id, label_id)
+                       SELECT $1, label_id FROM task_labels WHERE task_id = $2"#,
+                    new_id,
+                    task.id
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            for task in &tasks {
+                let new_id = new_ids[&task.id];
+                if let Some(parent_id) = task.parent_task_id.and_then(|id| new_ids.get(&id)) {
+                    sqlx::query!(
+                        "UPDATE tasks SET parent_task_id = $2 WHERE id = $1",
+                        new_id,
+                        parent_id
+                    )
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                let dependencies = sqlx::query_scalar!(
+                    r#"SELECT depends_on_task_id as "depends_on_task_id!: Uuid"
+                       FROM task_dependencies
+                       WHERE task_id = $1"#,
+                    task.id
+                )
+                .fetch_all(&mut *tx)
+                .await?;
+                for depends_on_id in dependencies.iter().filter_map(|id| new_ids.get(id)) {
+                    sqlx::query!(
+                        "INSERT INTO task_dependencies (task_id, depends_on_task_id) VALUES ($1, $2)",
+                        new_id,
+                        depends_on_id
+                    )
+                    .execute(&mut *tx)
+                    .await?;
+                }
+            }
+        }
+
+        let project = sqlx::query_as!(
+            Project,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, execution_timeout_minutes, auto_push_branches as "auto_push_branches!: bool", workspace_id as "workspace_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM projects
+               WHERE id = $1"#,
+            project_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(project)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -178,22 +489,135 @@ impl Project {
         dev_script: Option<String>,
         cleanup_script: Option<String>,
         copy_files: Option<String>,
+        execution_timeout_minutes: Option<i64>,
+        auto_push_branches: bool,
     ) -> Result<Self, sqlx::Error> {
         sqlx::query_as!(
             Project,
-            r#"UPDATE projects SET name = $2, git_repo_path = $3, setup_script = $4, dev_script = $5, cleanup_script = $6, copy_files = $7 WHERE id = $1 RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"UPDATE projects SET name = $2, git_repo_path = $3, setup_script = $4, dev_script = $5, cleanup_script = $6, copy_files = $7, execution_timeout_minutes = $8, auto_push_branches = $9 WHERE id = $1 RETURNING id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, execution_timeout_minutes, auto_push_branches as "auto_push_branches!: bool", workspace_id as "workspace_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             name,
             git_repo_path,
             setup_script,
             dev_script,
             cleanup_script,
-            copy_files
+            copy_files,
+            execution_timeout_minutes,
+            auto_push_branches
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Move the project, with its tasks, to the trash until restored or purged
+    pub async fn soft_delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE projects SET deleted_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec') WHERE id = $1 AND deleted_at IS NULL",
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn restore(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE projects SET deleted_at = NULL, updated_at = datetime('now', 'subsec') WHERE id = $1 AND deleted_at IS NOT NULL",
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn is_trashed(pool: &SqlitePool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let trashed = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1 AND deleted_at IS NOT NULL) as "trashed!: bool""#,
+            id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(trashed)
+    }
+
+    pub async fn archive(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE projects SET archived_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec') WHERE id = $1 AND archived_at IS NULL",
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn unarchive(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE projects SET archived_at = NULL, updated_at = datetime('now', 'subsec') WHERE id = $1 AND archived_at IS NOT NULL",
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn is_archived(pool: &SqlitePool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let archived = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM projects WHERE id = $1 AND archived_at IS NOT NULL) as "archived!: bool""#,
+            id
         )
         .fetch_one(pool)
+        .await?;
+        Ok(archived)
+    }
+
+    /// Projects moved to the trash before `cutoff`, due to be purged
+    pub async fn find_trashed_before(
+        pool: &SqlitePool,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Project,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, execution_timeout_minutes, auto_push_branches as "auto_push_branches!: bool", workspace_id as "workspace_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM projects
+               WHERE deleted_at IS NOT NULL AND datetime(deleted_at) < datetime($1)"#,
+            cutoff
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_workspace_id(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Project,
+            r#"SELECT id as "id!: Uuid", name, git_repo_path, setup_script, dev_script, cleanup_script, copy_files, execution_timeout_minutes, auto_push_branches as "auto_push_branches!: bool", workspace_id as "workspace_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM projects
+               WHERE workspace_id = $1 AND deleted_at IS NULL
+               ORDER BY name ASC"#,
+            workspace_id
+        )
+        .fetch_all(pool)
         .await
     }
 
+    /// Move the project into a workspace, or out of any with `None`
+    pub async fn set_workspace(
+        pool: &SqlitePool,
+        id: Uuid,
+        workspace_id: Option<Uuid>,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE projects SET workspace_id = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            id,
+            workspace_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
         let result = sqlx::query!("DELETE FROM projects WHERE id = $1", id)
             .execute(pool)
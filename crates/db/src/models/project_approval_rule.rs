@@ -0,0 +1,157 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Tool calls of a project's agents that are approved without asking
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectApprovalRule {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// Tool name as reported by the agent, e.g. `Bash` or `edit`, compared
+    /// case-insensitively; `*` matches any tool
+    pub tool_name: String,
+    /// Wildcard pattern the call's command or path must match, e.g.
+    /// `cargo test*`; any call of the tool matches when null
+    pub pattern: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateProjectApprovalRule {
+    pub tool_name: String,
+    #[serde(default)]
+    #[ts(optional)]
+    pub pattern: Option<String>,
+}
+
+impl ProjectApprovalRule {
+    /// Whether a call of `tool_name` with `tool_input` is approved by this rule
+    pub fn matches(&self, tool_name: &str, tool_input: &Value) -> bool {
+        if self.tool_name != "*" && !self.tool_name.eq_ignore_ascii_case(tool_name) {
+            return false;
+        }
+        match &self.pattern {
+            None => true,
+            Some(pattern) => {
+                call_subject(tool_input).is_some_and(|subject| wildcard_match(pattern, &subject))
+            }
+        }
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectApprovalRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      tool_name,
+                      pattern,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_approval_rules
+               WHERE project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectApprovalRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      tool_name,
+                      pattern,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_approval_rules
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        tool_name: &str,
+        pattern: Option<&str>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            ProjectApprovalRule,
+            r#"INSERT INTO project_approval_rules (id, project_id, tool_name, pattern)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         tool_name,
+                         pattern,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            tool_name,
+            pattern
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM project_approval_rules WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+/// The command a call runs, or the path it touches. Agents name these
+/// differently: Claude Code passes `command` as a string, Codex as an argv
+/// array, and file tools carry `file_path` or `path`.
+fn call_subject(tool_input: &Value) -> Option<String> {
+    match tool_input.get("command") {
+        Some(Value::String(command)) => return Some(command.clone()),
+        Some(Value::Array(argv)) => {
+            return Some(
+                argv.iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            );
+        }
+        _ => {}
+    }
+    ["file_path", "path"]
+        .iter()
+        .find_map(|key| tool_input.get(key)?.as_str().map(str::to_string))
+}
+
+/// Match `text` against `pattern`, where `*` matches any run of characters
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No wildcard: the whole text must match
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
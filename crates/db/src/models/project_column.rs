@@ -0,0 +1,348 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::{TaskStatus, deserialize_present};
+
+/// A board column. Tasks in it carry the column's status, so automation keeps
+/// working on statuses while the board shows the project's own workflow.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectColumn {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub position: i64,
+    /// Status of the tasks in this column
+    pub status: TaskStatus,
+    /// Maximum number of tasks the column should hold
+    pub wip_limit: Option<i64>,
+    /// Reject moves into the column once it holds `wip_limit` tasks, instead
+    /// of allowing them with a warning
+    pub enforce_wip_limit: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateProjectColumn {
+    pub name: String,
+    pub status: TaskStatus,
+    pub wip_limit: Option<i64>,
+    #[serde(default)]
+    pub enforce_wip_limit: bool,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateProjectColumn {
+    pub name: Option<String>,
+    pub status: Option<TaskStatus>,
+    /// New limit, or null to remove it; left unchanged when omitted
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[ts(optional, type = "bigint | null")]
+    pub wip_limit: Option<Option<i64>>,
+    pub enforce_wip_limit: Option<bool>,
+}
+
+/// Tasks held by a column with a WIP limit, as exposed on the events stream
+/// while the column is over its limit
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ColumnWipStatus {
+    pub column_id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub count: i64,
+    pub wip_limit: i64,
+}
+
+impl ColumnWipStatus {
+    pub fn is_over_limit(&self) -> bool {
+        self.count > self.wip_limit
+    }
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ReorderProjectColumns {
+    /// Every column of the project, in the new order
+    pub column_ids: Vec<Uuid>,
+}
+
+const DEFAULT_COLUMNS: [(&str, TaskStatus); 5] = [
+    ("To Do", TaskStatus::Todo),
+    ("In Progress", TaskStatus::InProgress),
+    ("In Review", TaskStatus::InReview),
+    ("Done", TaskStatus::Done),
+    ("Cancelled", TaskStatus::Cancelled),
+];
+
+impl ProjectColumn {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectColumn,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      position,
+                      status as "status!: TaskStatus",
+                      wip_limit,
+                      enforce_wip_limit as "enforce_wip_limit!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_columns
+               WHERE project_id = $1
+               ORDER BY position ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectColumn,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      position,
+                      status as "status!: TaskStatus",
+                      wip_limit,
+                      enforce_wip_limit as "enforce_wip_limit!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_columns
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn name_exists(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        name: &str,
+        exclude_id: Option<Uuid>,
+    ) -> Result<bool, sqlx::Error> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM project_columns
+                    WHERE project_id = $1 AND name = $2 AND ($3 IS NULL OR id != $3)
+               ) as "exists!: bool""#,
+            project_id,
+            name,
+            exclude_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(exists)
+    }
+
+    /// Number of columns of the project mapped onto `status`
+    pub async fn count_for_status(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        status: TaskStatus,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64" FROM project_columns WHERE project_id = $1 AND status = $2"#,
+            project_id,
+            status
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// The column tasks of `status` show in unless they are pinned to another
+    pub async fn find_first_for_status(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        status: TaskStatus,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectColumn,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      position,
+                      status as "status!: TaskStatus",
+                      wip_limit,
+                      enforce_wip_limit as "enforce_wip_limit!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_columns
+               WHERE project_id = $1 AND status = $2
+               ORDER BY position ASC
+               LIMIT 1"#,
+            project_id,
+            status
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Tasks outside the trash showing in the column, leaving out
+    /// `exclude_task_id`
+    pub async fn task_count(
+        pool: &SqlitePool,
+        id: Uuid,
+        exclude_task_id: Option<Uuid>,
+    ) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64"
+               FROM tasks t
+               JOIN project_columns c ON c.id = $1
+               WHERE t.project_id = c.project_id
+                 AND t.status = c.status
+                 AND t.deleted_at IS NULL
+                 AND ($2 IS NULL OR t.id != $2)
+                 AND COALESCE(
+                         ( SELECT c2.id FROM project_columns c2
+                            WHERE c2.id = t.column_id AND c2.status = t.status ),
+                         ( SELECT c2.id FROM project_columns c2
+                            WHERE c2.project_id = t.project_id AND c2.status = t.status
+                            ORDER BY c2.position ASC
+                            LIMIT 1 )
+                     ) = c.id"#,
+            id,
+            exclude_task_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// How full the column is; None when it has no WIP limit
+    pub async fn wip_status(
+        &self,
+        pool: &SqlitePool,
+    ) -> Result<Option<ColumnWipStatus>, sqlx::Error> {
+        let Some(wip_limit) = self.wip_limit else {
+            return Ok(None);
+        };
+        Ok(Some(ColumnWipStatus {
+            column_id: self.id,
+            project_id: self.project_id,
+            name: self.name.clone(),
+            count: Self::task_count(pool, self.id, None).await?,
+            wip_limit,
+        }))
+    }
+
+    /// Give a new project one column per status
+    pub async fn create_defaults(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        for (position, (name, status)) in DEFAULT_COLUMNS.into_iter().enumerate() {
+            let id = Uuid::new_v4();
+            let position = position as i64;
+            sqlx::query!(
+                "INSERT INTO project_columns (id, project_id, name, position, status) VALUES ($1, $2, $3, $4, $5)",
+                id,
+                project_id,
+                name,
+                position,
+                status
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Self::find_by_project_id(pool, project_id).await
+    }
+
+    /// Append a column after the project's existing ones
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateProjectColumn,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            ProjectColumn,
+            r#"INSERT INTO project_columns (id, project_id, name, position, status, wip_limit, enforce_wip_limit)
+               VALUES ($1, $2, $3,
+                       (SELECT COALESCE(MAX(position) + 1, 0) FROM project_columns WHERE project_id = $2),
+                       $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         position,
+                         status as "status!: TaskStatus",
+                         wip_limit,
+                         enforce_wip_limit as "enforce_wip_limit!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.name,
+            data.status,
+            data.wip_limit,
+            data.enforce_wip_limit
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        name: &str,
+        status: TaskStatus,
+        wip_limit: Option<i64>,
+        enforce_wip_limit: bool,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectColumn,
+            r#"UPDATE project_columns
+               SET name = $2, status = $3, wip_limit = $4, enforce_wip_limit = $5, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         position,
+                         status as "status!: TaskStatus",
+                         wip_limit,
+                         enforce_wip_limit as "enforce_wip_limit!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            status,
+            wip_limit,
+            enforce_wip_limit
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn set_position<'e, E>(
+        executor: E,
+        id: Uuid,
+        position: i64,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query!(
+            "UPDATE project_columns SET position = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            id,
+            position
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Tasks pinned to the column fall back to the first column of their status
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM project_columns WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,188 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Architectural context prepended to the prompts of a project's attempts,
+/// so that it does not have to be repeated in every task description
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectContextPack {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    /// Files to include, as paths or gitignore-style globs relative to the
+    /// repository root
+    #[ts(type = "string[]")]
+    pub patterns: Json<Vec<String>>,
+    /// Free-form notes included as they are
+    pub notes: Option<String>,
+    /// Disabled packs are kept but not included
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateProjectContextPack {
+    pub name: String,
+    #[serde(default)]
+    #[ts(optional)]
+    pub patterns: Option<Vec<String>>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub notes: Option<String>,
+    /// Defaults to true
+    #[serde(default)]
+    #[ts(optional)]
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateProjectContextPack {
+    pub name: Option<String>,
+    pub patterns: Option<Vec<String>>,
+    /// An empty string removes the notes
+    pub notes: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+impl ProjectContextPack {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectContextPack,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      patterns as "patterns!: Json<Vec<String>>",
+                      notes,
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_context_packs
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectContextPack,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      patterns as "patterns!: Json<Vec<String>>",
+                      notes,
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_context_packs
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn name_exists(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        name: &str,
+        exclude_id: Option<Uuid>,
+    ) -> Result<bool, sqlx::Error> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM project_context_packs
+                    WHERE project_id = $1 AND name = $2 AND ($3 IS NULL OR id != $3)
+               ) as "exists!: bool""#,
+            project_id,
+            name,
+            exclude_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(exists)
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateProjectContextPack,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let patterns = Json(data.patterns.clone().unwrap_or_default());
+        let notes = data
+            .notes
+            .as_deref()
+            .filter(|notes| !notes.trim().is_empty());
+        let enabled = data.enabled.unwrap_or(true);
+        sqlx::query_as!(
+            ProjectContextPack,
+            r#"INSERT INTO project_context_packs (id, project_id, name, patterns, notes, enabled)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         patterns as "patterns!: Json<Vec<String>>",
+                         notes,
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.name,
+            patterns,
+            notes,
+            enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        name: &str,
+        patterns: &[String],
+        notes: Option<&str>,
+        enabled: bool,
+    ) -> Result<Self, sqlx::Error> {
+        let patterns = Json(patterns);
+        let notes = notes.filter(|notes| !notes.trim().is_empty());
+        sqlx::query_as!(
+            ProjectContextPack,
+            r#"UPDATE project_context_packs
+               SET name = $2, patterns = $3, notes = $4, enabled = $5,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         patterns as "patterns!: Json<Vec<String>>",
+                         notes,
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            patterns,
+            notes,
+            enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM project_context_packs WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
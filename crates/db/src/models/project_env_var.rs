@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// An environment variable set on the processes of a project's attempts. The
+/// value is stored encrypted and never serialized from here.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectEnvVar {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub encrypted_value: Vec<u8>,
+    /// Secret values are write-only through the API
+    pub is_secret: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpsertProjectEnvVar {
+    pub name: String,
+    pub value: String,
+    /// Defaults to true
+    #[serde(default)]
+    #[ts(optional)]
+    pub is_secret: Option<bool>,
+}
+
+impl ProjectEnvVar {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectEnvVar,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      encrypted_value,
+                      is_secret as "is_secret!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_env_vars
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectEnvVar,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      encrypted_value,
+                      is_secret as "is_secret!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_env_vars
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Set the variable, replacing the value of an existing one with the same
+    /// name
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        name: &str,
+        encrypted_value: &[u8],
+        is_secret: bool,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            ProjectEnvVar,
+            r#"INSERT INTO project_env_vars (id, project_id, name, encrypted_value, is_secret)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT(project_id, name) DO UPDATE SET
+                   encrypted_value = excluded.encrypted_value,
+                   is_secret = excluded.is_secret,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         encrypted_value,
+                         is_secret as "is_secret!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            name,
+            encrypted_value,
+            is_secret
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM project_env_vars WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// An executor profile a project's coding agent runs fall back to when the
+/// model provider refuses a run
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectExecutorFallback {
+    pub project_id: Uuid,
+    /// Order in the chain, starting at 0
+    pub position: i64,
+    #[ts(type = "ExecutorProfileId")]
+    pub executor_profile_id: Json<ExecutorProfileId>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct SetProjectExecutorFallbacks {
+    /// Profiles to fall back to, tried in order
+    pub profiles: Vec<ExecutorProfileId>,
+}
+
+impl ProjectExecutorFallback {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectExecutorFallback,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      position as "position!: i64",
+                      executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM project_executor_fallbacks
+               WHERE project_id = $1
+               ORDER BY position ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Replace the fallback chain of a project with `profiles`
+    pub async fn replace(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        profiles: &[ExecutorProfileId],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            "DELETE FROM project_executor_fallbacks WHERE project_id = $1",
+            project_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        for (position, profile) in profiles.iter().enumerate() {
+            let position = position as i64;
+            let profile = Json(profile);
+            sqlx::query!(
+                "INSERT INTO project_executor_fallbacks (project_id, position, executor_profile_id) VALUES ($1, $2, $3)",
+                project_id,
+                position,
+                profile
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+}
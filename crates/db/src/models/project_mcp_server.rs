@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use executors::mcp_config::McpServerConfig;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A stdio MCP server given to the coding agents of a project's attempts
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectMcpServer {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// Key of the server in the agent's config
+    pub name: String,
+    pub command: String,
+    #[ts(type = "string[]")]
+    pub args: Json<Vec<String>>,
+    #[ts(type = "Record<string, string>")]
+    pub env: Json<HashMap<String, String>>,
+    /// Disabled servers are kept but not given to agents
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateProjectMcpServer {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    #[ts(optional)]
+    pub args: Option<Vec<String>>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub env: Option<HashMap<String, String>>,
+    /// Defaults to true
+    #[serde(default)]
+    #[ts(optional)]
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateProjectMcpServer {
+    pub name: Option<String>,
+    pub command: Option<String>,
+    pub args: Option<Vec<String>>,
+    pub env: Option<HashMap<String, String>>,
+    pub enabled: Option<bool>,
+}
+
+impl ProjectMcpServer {
+    /// The server in the canonical form executors translate for their agent
+    pub fn config(&self) -> McpServerConfig {
+        McpServerConfig {
+            command: self.command.clone(),
+            args: self.args.0.clone(),
+            env: self.env.0.clone(),
+        }
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectMcpServer,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      command,
+                      args as "args!: Json<Vec<String>>",
+                      env as "env!: Json<HashMap<String, String>>",
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_mcp_servers
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectMcpServer,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      command,
+                      args as "args!: Json<Vec<String>>",
+                      env as "env!: Json<HashMap<String, String>>",
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_mcp_servers
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn name_exists(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        name: &str,
+        exclude_id: Option<Uuid>,
+    ) -> Result<bool, sqlx::Error> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM project_mcp_servers
+                    WHERE project_id = $1 AND name = $2 AND ($3 IS NULL OR id != $3)
+               ) as "exists!: bool""#,
+            project_id,
+            name,
+            exclude_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(exists)
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateProjectMcpServer,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let args = Json(data.args.clone().unwrap_or_default());
+        let env = Json(data.env.clone().unwrap_or_default());
+        let enabled = data.enabled.unwrap_or(true);
+        sqlx::query_as!(
+            ProjectMcpServer,
+            r#"INSERT INTO project_mcp_servers (id, project_id, name, command, args, env, enabled)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         command,
+                         args as "args!: Json<Vec<String>>",
+                         env as "env!: Json<HashMap<String, String>>",
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.name,
+            data.command,
+            args,
+            env,
+            enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        name: &str,
+        command: &str,
+        args: &[String],
+        env: &HashMap<String, String>,
+        enabled: bool,
+    ) -> Result<Self, sqlx::Error> {
+        let args = Json(args);
+        let env = Json(env);
+        sqlx::query_as!(
+            ProjectMcpServer,
+            r#"UPDATE project_mcp_servers
+               SET name = $2, command = $3, args = $4, env = $5, enabled = $6,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         command,
+                         args as "args!: Json<Vec<String>>",
+                         env as "env!: Json<HashMap<String, String>>",
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            command,
+            args,
+            env,
+            enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM project_mcp_servers WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
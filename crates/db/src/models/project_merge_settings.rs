@@ -0,0 +1,95 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::Display;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// How an attempt branch is brought into its target branch
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, Display)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum MergeStrategy {
+    /// One commit with all of the attempt's changes
+    Squash,
+    /// The attempt's commits on top of the target branch, without a merge
+    /// commit
+    Rebase,
+    /// A merge commit joining the attempt branch into the target branch
+    MergeCommit,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectMergeSettings {
+    pub project_id: Uuid,
+    pub strategy: MergeStrategy,
+    /// Message of squash and merge commits, e.g. `{title} ({task_id})`
+    pub commit_message_template: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpsertProjectMergeSettings {
+    pub strategy: MergeStrategy,
+    pub commit_message_template: Option<String>,
+}
+
+impl ProjectMergeSettings {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectMergeSettings,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      strategy as "strategy!: MergeStrategy",
+                      commit_message_template,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_merge_settings
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        strategy: MergeStrategy,
+        commit_message_template: Option<&str>,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectMergeSettings,
+            r#"INSERT INTO project_merge_settings (project_id, strategy, commit_message_template)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   strategy = excluded.strategy,
+                   commit_message_template = excluded.commit_message_template,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         strategy as "strategy!: MergeStrategy",
+                         commit_message_template,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            strategy,
+            commit_message_template
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_merge_settings WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,100 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::Display;
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// What happens to idle attempts when their base branch gets new commits
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, Display)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum RebasePolicyMode {
+    /// Only mark the attempts as stale
+    Flag,
+    /// Rebase the attempts, and mark them as stale when that fails
+    Rebase,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectRebasePolicy {
+    pub project_id: Uuid,
+    pub mode: RebasePolicyMode,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpsertProjectRebasePolicy {
+    pub mode: RebasePolicyMode,
+}
+
+impl ProjectRebasePolicy {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectRebasePolicy,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      mode as "mode!: RebasePolicyMode",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_rebase_policies
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Policies of projects that are neither archived nor in the trash
+    pub async fn find_active(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectRebasePolicy,
+            r#"SELECT rp.project_id as "project_id!: Uuid",
+                      rp.mode as "mode!: RebasePolicyMode",
+                      rp.created_at as "created_at!: DateTime<Utc>",
+                      rp.updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_rebase_policies rp
+               JOIN projects p ON p.id = rp.project_id
+               WHERE p.archived_at IS NULL AND p.deleted_at IS NULL"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        mode: RebasePolicyMode,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectRebasePolicy,
+            r#"INSERT INTO project_rebase_policies (project_id, mode)
+               VALUES ($1, $2)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   mode = excluded.mode,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         mode as "mode!: RebasePolicyMode",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            mode
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_rebase_policies WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,112 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// An additional git repository of a project. Every attempt of the project
+/// gets a worktree of it on the attempt branch, next to the main worktree.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectRepository {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// Short unique name within the project, used in the worktree directory name
+    pub name: String,
+    pub git_repo_path: PathBuf,
+    /// Branch the attempt branch is created from in this repository
+    pub base_branch: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateProjectRepository {
+    pub name: String,
+    pub git_repo_path: String,
+    pub base_branch: String,
+}
+
+impl ProjectRepository {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectRepository,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, git_repo_path, base_branch, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_repositories
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectRepository,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", name, git_repo_path, base_branch, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_repositories
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateProjectRepository,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            ProjectRepository,
+            r#"INSERT INTO project_repositories (id, project_id, name, git_repo_path, base_branch)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", name, git_repo_path, base_branch, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.name,
+            data.git_repo_path,
+            data.base_branch
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM project_repositories WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Worktree location of this repository for the attempt whose main
+    /// worktree lives at `attempt_worktree`: a sibling directory suffixed
+    /// with the repository name.
+    pub fn worktree_path(&self, attempt_worktree: &Path) -> PathBuf {
+        let dir_name = attempt_worktree
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        attempt_worktree.with_file_name(format!("{dir_name}--{}", self.name))
+    }
+
+    /// Inverse of [`Self::worktree_path`]: the attempt's main worktree for a
+    /// repository worktree, or `path` itself for a main worktree. Attempt
+    /// directory names never contain "--".
+    pub fn owning_attempt_worktree(path: &Path) -> PathBuf {
+        match path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.split_once("--"))
+        {
+            Some((attempt_dir, _)) => path.with_file_name(attempt_dir),
+            None => path.to_path_buf(),
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::deserialize_present;
+
+/// When a project script runs
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectScriptKind {
+    /// Before the coding agent, when an attempt is provisioned
+    Setup,
+    /// On demand, as the attempt's dev server
+    Dev,
+    /// On demand, against the attempt's worktree
+    Test,
+    /// After each coding agent run
+    Cleanup,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectScript {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub kind: ProjectScriptKind,
+    pub name: String,
+    pub script: String,
+    /// Run order among the project's scripts of the same kind
+    pub position: i64,
+    /// Cancel a run after this many seconds. Dev servers are never timed out.
+    pub timeout_seconds: Option<i64>,
+    /// How many times a failed or timed out run is retried
+    pub max_retries: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateProjectScript {
+    pub kind: ProjectScriptKind,
+    pub name: String,
+    pub script: String,
+    pub timeout_seconds: Option<i64>,
+    pub max_retries: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateProjectScript {
+    pub name: Option<String>,
+    pub script: Option<String>,
+    /// New timeout, or null to remove it; left unchanged when omitted
+    #[serde(default, deserialize_with = "deserialize_present")]
+    #[ts(optional, type = "bigint | null")]
+    pub timeout_seconds: Option<Option<i64>>,
+    pub max_retries: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ReorderProjectScripts {
+    pub kind: ProjectScriptKind,
+    /// Every script of the kind, in the new run order
+    pub script_ids: Vec<Uuid>,
+}
+
+impl ProjectScript {
+    /// Scripts of the project, of one kind when `kind` is set, in run order
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        kind: Option<ProjectScriptKind>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectScript,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      kind as "kind!: ProjectScriptKind",
+                      name,
+                      script,
+                      position,
+                      timeout_seconds,
+                      max_retries,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_scripts
+               WHERE project_id = $1 AND ($2 IS NULL OR kind = $2)
+               ORDER BY kind ASC, position ASC"#,
+            project_id,
+            kind
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectScript,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      kind as "kind!: ProjectScriptKind",
+                      name,
+                      script,
+                      position,
+                      timeout_seconds,
+                      max_retries,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_scripts
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn name_exists(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        kind: ProjectScriptKind,
+        name: &str,
+        exclude_id: Option<Uuid>,
+    ) -> Result<bool, sqlx::Error> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM project_scripts
+                    WHERE project_id = $1 AND kind = $2 AND name = $3 AND ($4 IS NULL OR id != $4)
+               ) as "exists!: bool""#,
+            project_id,
+            kind,
+            name,
+            exclude_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(exists)
+    }
+
+    /// Append a script after the project's existing ones of the same kind
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateProjectScript,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let max_retries = data.max_retries.unwrap_or(0);
+        sqlx::query_as!(
+            ProjectScript,
+            r#"INSERT INTO project_scripts (id, project_id, kind, name, script, position, timeout_seconds, max_retries)
+               VALUES ($1, $2, $3, $4, $5,
+                       (SELECT COALESCE(MAX(position) + 1, 0) FROM project_scripts WHERE project_id = $2 AND kind = $3),
+                       $6, $7)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         kind as "kind!: ProjectScriptKind",
+                         name,
+                         script,
+                         position,
+                         timeout_seconds,
+                         max_retries,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.kind,
+            data.name,
+            data.script,
+            data.timeout_seconds,
+            max_retries
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        name: &str,
+        script: &str,
+        timeout_seconds: Option<i64>,
+        max_retries: i64,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectScript,
+            r#"UPDATE project_scripts
+               SET name = $2, script = $3, timeout_seconds = $4, max_retries = $5,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         kind as "kind!: ProjectScriptKind",
+                         name,
+                         script,
+                         position,
+                         timeout_seconds,
+                         max_retries,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            script,
+            timeout_seconds,
+            max_retries
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn set_position<'e, E>(
+        executor: E,
+        id: Uuid,
+        position: i64,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query!(
+            "UPDATE project_scripts SET position = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            id,
+            position
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM project_scripts WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
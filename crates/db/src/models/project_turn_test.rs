@@ -0,0 +1,93 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Test script run after every coding agent turn of the project's attempts
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectTurnTest {
+    pub project_id: Uuid,
+    /// A test script of the project
+    pub script_id: Uuid,
+    /// Send failing results back to the agent as a follow-up
+    pub feed_back_failures: bool,
+    /// Follow-ups sent in a row before failures are left to the user
+    pub max_feedback_rounds: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpsertProjectTurnTest {
+    pub script_id: Uuid,
+    #[serde(default)]
+    #[ts(optional)]
+    pub feed_back_failures: Option<bool>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub max_feedback_rounds: Option<i64>,
+}
+
+impl ProjectTurnTest {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectTurnTest,
+            r#"SELECT project_id as "project_id!: Uuid",
+                      script_id as "script_id!: Uuid",
+                      feed_back_failures as "feed_back_failures!: bool",
+                      max_feedback_rounds,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_turn_tests
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        script_id: Uuid,
+        feed_back_failures: bool,
+        max_feedback_rounds: i64,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectTurnTest,
+            r#"INSERT INTO project_turn_tests (project_id, script_id, feed_back_failures, max_feedback_rounds)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   script_id = excluded.script_id,
+                   feed_back_failures = excluded.feed_back_failures,
+                   max_feedback_rounds = excluded.max_feedback_rounds,
+                   updated_at = datetime('now', 'subsec')
+               RETURNING project_id as "project_id!: Uuid",
+                         script_id as "script_id!: Uuid",
+                         feed_back_failures as "feed_back_failures!: bool",
+                         max_feedback_rounds,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            project_id,
+            script_id,
+            feed_back_failures,
+            max_feedback_rounds
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, project_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_turn_tests WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,170 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ProposedTaskStatus {
+    /// Waiting to be accepted or dismissed
+    Pending,
+    /// A task was created from it
+    Accepted,
+    Dismissed,
+}
+
+/// Follow-up work a coding agent proposed, as a draft task
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProposedTask {
+    pub id: Uuid,
+    pub task_attempt_id: Uuid,
+    /// The coding agent run that proposed it
+    pub execution_process_id: Option<Uuid>,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: ProposedTaskStatus,
+    /// The task created when it was accepted
+    pub task_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct AcceptProposedTask {
+    /// Title of the task instead of the proposed one
+    #[serde(default)]
+    #[ts(optional)]
+    pub title: Option<String>,
+    /// Description of the task instead of the proposed one
+    #[serde(default)]
+    #[ts(optional)]
+    pub description: Option<String>,
+}
+
+impl ProposedTask {
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProposedTask,
+            r#"SELECT id as "id!: Uuid",
+                      task_attempt_id as "task_attempt_id!: Uuid",
+                      execution_process_id as "execution_process_id: Uuid",
+                      title,
+                      description,
+                      status as "status!: ProposedTaskStatus",
+                      task_id as "task_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM proposed_tasks
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProposedTask,
+            r#"SELECT id as "id!: Uuid",
+                      task_attempt_id as "task_attempt_id!: Uuid",
+                      execution_process_id as "execution_process_id: Uuid",
+                      title,
+                      description,
+                      status as "status!: ProposedTaskStatus",
+                      task_id as "task_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM proposed_tasks
+               WHERE task_attempt_id = $1
+               ORDER BY created_at ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Proposals of all attempts in the project still waiting for a decision
+    pub async fn find_pending_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProposedTask,
+            r#"SELECT pt.id as "id!: Uuid",
+                      pt.task_attempt_id as "task_attempt_id!: Uuid",
+                      pt.execution_process_id as "execution_process_id: Uuid",
+                      pt.title,
+                      pt.description,
+                      pt.status as "status!: ProposedTaskStatus",
+                      pt.task_id as "task_id: Uuid",
+                      pt.created_at as "created_at!: DateTime<Utc>",
+                      pt.updated_at as "updated_at!: DateTime<Utc>"
+               FROM proposed_tasks pt
+               JOIN task_attempts ta ON ta.id = pt.task_attempt_id
+               JOIN tasks t ON t.id = ta.task_id
+               WHERE t.project_id = $1 AND pt.status = 'pending'
+               ORDER BY pt.created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Store a proposal unless the attempt already has one with this title.
+    /// Returns whether it was added.
+    pub async fn create(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        execution_process_id: Uuid,
+        title: &str,
+        description: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let result = sqlx::query!(
+            r#"INSERT INTO proposed_tasks (id, task_attempt_id, execution_process_id, title, description)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT (task_attempt_id, title) DO NOTHING"#,
+            id,
+            task_attempt_id,
+            execution_process_id,
+            title,
+            description
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record that `task_id` was created from the proposal. Returns false
+    /// when it was no longer pending.
+    pub async fn accept(pool: &SqlitePool, id: Uuid, task_id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE proposed_tasks
+               SET status = 'accepted', task_id = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND status = 'pending'"#,
+            id,
+            task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Returns false when the proposal was no longer pending
+    pub async fn dismiss(pool: &SqlitePool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE proposed_tasks
+               SET status = 'dismissed', updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND status = 'pending'"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,118 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A follow-up prompt waiting for the attempt's current turn to finish
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct QueuedFollowUp {
+    pub id: Uuid,
+    pub task_attempt_id: Uuid,
+    pub prompt: String,
+    pub variant: Option<String>,
+    #[ts(type = "Array<string> | null")]
+    pub image_ids: Option<sqlx::types::Json<Vec<Uuid>>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateQueuedFollowUp {
+    pub prompt: String,
+    pub variant: Option<String>,
+    pub image_ids: Option<Vec<Uuid>>,
+}
+
+impl QueuedFollowUp {
+    /// Queued follow-ups of an attempt in delivery order
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            QueuedFollowUp,
+            r#"SELECT id as "id!: Uuid",
+                      task_attempt_id as "task_attempt_id!: Uuid",
+                      prompt,
+                      variant,
+                      image_ids as "image_ids: sqlx::types::Json<Vec<Uuid>>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM queued_follow_ups
+               WHERE task_attempt_id = $1
+               ORDER BY created_at ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The next follow-up to deliver for an attempt
+    pub async fn find_next(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            QueuedFollowUp,
+            r#"SELECT id as "id!: Uuid",
+                      task_attempt_id as "task_attempt_id!: Uuid",
+                      prompt,
+                      variant,
+                      image_ids as "image_ids: sqlx::types::Json<Vec<Uuid>>",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM queued_follow_ups
+               WHERE task_attempt_id = $1
+               ORDER BY created_at ASC
+               LIMIT 1"#,
+            task_attempt_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+        data: &CreateQueuedFollowUp,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let image_ids = data.image_ids.as_ref().map(sqlx::types::Json);
+        sqlx::query_as!(
+            QueuedFollowUp,
+            r#"INSERT INTO queued_follow_ups (id, task_attempt_id, prompt, variant, image_ids)
+               VALUES ($1, $2, $3, $4, $5)
+               RETURNING id as "id!: Uuid",
+                         task_attempt_id as "task_attempt_id!: Uuid",
+                         prompt,
+                         variant,
+                         image_ids as "image_ids: sqlx::types::Json<Vec<Uuid>>",
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            task_attempt_id,
+            data.prompt,
+            data.variant,
+            image_ids
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM queued_follow_ups WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM queued_follow_ups WHERE task_attempt_id = $1",
+            task_attempt_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
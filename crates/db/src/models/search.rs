@@ -0,0 +1,191 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// What a search hit matched
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, Hash, TS)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SearchResultKind {
+    /// Title or description of a task
+    Task,
+    /// Summary the coding agent gave at the end of a run
+    AttemptSummary,
+    /// Output of an execution process
+    ExecutionLog,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct SearchResult {
+    pub kind: SearchResultKind,
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub task_id: Uuid,
+    pub task_title: String,
+    /// Set for attempt summaries and execution logs
+    pub task_attempt_id: Option<Uuid>,
+    /// Set for attempt summaries and execution logs
+    pub execution_process_id: Option<Uuid>,
+    /// Excerpt around the match, with matched terms wrapped in `**`
+    pub snippet: String,
+    /// Lower is a better match
+    #[serde(skip)]
+    #[ts(skip)]
+    pub rank: f64,
+}
+
+/// Turn free text into an FTS5 query matching every word, the last one as a
+/// prefix, so that users never hit FTS5 syntax errors
+pub fn fts_query(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+    if terms.is_empty() {
+        return None;
+    }
+    Some(format!("{}*", terms.join(" ")))
+}
+
+impl SearchResult {
+    /// Best matches for `text` across projects, one result per task or
+    /// execution process
+    pub async fn search(
+        pool: &SqlitePool,
+        text: &str,
+        project_id: Option<Uuid>,
+        kind: Option<SearchResultKind>,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let Some(query) = fts_query(text) else {
+            return Ok(Vec::new());
+        };
+        // Logs are indexed per line, so one process can match many
+        // times; fetch extra rows to fill the limit after de-duplicating
+        let fetch_limit = limit * 4;
+        let rows = sqlx::query_as!(
+            SearchResult,
+            r#"SELECT entry.kind as "kind!: SearchResultKind",
+                      p.id as "project_id!: Uuid",
+                      p.name as "project_name!",
+                      t.id as "task_id!: Uuid",
+                      t.title as "task_title!",
+                      ta.id as "task_attempt_id: Uuid",
+                      ep.id as "execution_process_id: Uuid",
+                      snippet(search_index, 0, '**', '**', '…', 16) as "snippet!: String",
+                      search_index.rank as "rank!: f64"
+               FROM search_index
+               JOIN search_index_entries entry ON entry.id = search_index.rowid
+               LEFT JOIN execution_processes ep
+                      ON entry.kind != 'task' AND ep.id = entry.entity_id
+               LEFT JOIN task_attempts ta ON ta.id = ep.task_attempt_id
+               JOIN tasks t
+                 ON t.id = CASE WHEN entry.kind = 'task' THEN entry.entity_id ELSE ta.task_id END
+               JOIN projects p ON p.id = t.project_id
+               WHERE search_index MATCH $1
+                 AND t.deleted_at IS NULL
+                 AND p.deleted_at IS NULL
+                 AND ($2 IS NULL OR t.project_id = $2)
+                 AND ($3 IS NULL OR entry.kind = $3)
+               ORDER BY search_index.rank
+               LIMIT $4"#,
+            query,
+            project_id,
+            kind,
+            fetch_limit
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut seen = HashSet::new();
+        Ok(rows
+            .into_iter()
+            .filter(|row| seen.insert((row.kind, row.execution_process_id.unwrap_or(row.task_id))))
+            .take(limit as usize)
+            .collect())
+    }
+}
+
+/// Text worth indexing in a stored log line: the output of stdout and stderr
+/// lines, as the log triggers extract it
+fn log_line_text(line: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+    value
+        .get("Stdout")
+        .or_else(|| value.get("Stderr"))?
+        .as_str()
+        .map(str::to_string)
+}
+
+pub struct SearchIndex;
+
+impl SearchIndex {
+    /// Index the lines of one log stored before the search index existed.
+    /// Returns false once there are none left.
+    pub async fn backfill_next_log(pool: &SqlitePool) -> Result<bool, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        let Some(execution_id) = sqlx::query_scalar!(
+            r#"SELECT execution_id as "execution_id!: Uuid" FROM search_index_log_backfill LIMIT 1"#
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(false);
+        };
+        let logs = sqlx::query_scalar!(
+            "SELECT logs FROM execution_process_logs WHERE execution_id = $1",
+            execution_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?
+        .unwrap_or_default();
+
+        // Lines appended since the migration were indexed by the log trigger;
+        // drop them so that the full log is indexed exactly once
+        sqlx::query!(
+            r#"DELETE FROM search_index
+                WHERE rowid IN (
+                    SELECT id FROM search_index_entries
+                     WHERE kind = 'execution_log' AND entity_id = $1
+                )"#,
+            execution_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "DELETE FROM search_index_entries WHERE kind = 'execution_log' AND entity_id = $1",
+            execution_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        for text in logs.lines().filter_map(log_line_text) {
+            let entry_id = sqlx::query!(
+                "INSERT INTO search_index_entries (kind, entity_id) VALUES ('execution_log', $1)",
+                execution_id
+            )
+            .execute(&mut *tx)
+            .await?
+            .last_insert_rowid();
+            sqlx::query!(
+                "INSERT INTO search_index (rowid, content) VALUES ($1, $2)",
+                entry_id,
+                text
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query!(
+            "DELETE FROM search_index_log_backfill WHERE execution_id = $1",
+            execution_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
@@ -5,7 +7,11 @@ use strum_macros::{Display, EnumString};
 use ts_rs::TS;
 use uuid::Uuid;
 
-use super::{project::Project, task_attempt::TaskAttempt};
+use super::{
+    ci_check::{CiCheck, CiCheckState},
+    project::Project,
+    task_attempt::TaskAttempt,
+};
 
 #[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display)]
 #[sqlx(type_name = "task_status", rename_all = "lowercase")]
@@ -19,6 +25,33 @@ pub enum TaskStatus {
     Cancelled,
 }
 
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Type,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    TS,
+    EnumString,
+    Display,
+)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum TaskPriority {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Urgent,
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct Task {
     pub id: Uuid,
@@ -27,6 +60,12 @@ pub struct Task {
     pub description: Option<String>,
     pub status: TaskStatus,
     pub parent_task_attempt: Option<Uuid>, // Foreign key to parent TaskAttempt
+    /// Parent task when this is a subtask
+    pub parent_task_id: Option<Uuid>,
+    pub priority: TaskPriority,
+    pub due_date: Option<DateTime<Utc>>,
+    /// Who is working on the task, e.g. a GitHub username
+    pub assignee: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -39,7 +78,18 @@ pub struct TaskWithAttemptStatus {
     pub has_in_progress_attempt: bool,
     pub has_merged_attempt: bool,
     pub last_attempt_failed: bool,
+    /// Some task this one depends on is not done yet
+    pub is_blocked: bool,
+    /// Board column the task shows in
+    pub column_id: Option<Uuid>,
+    /// The due date has passed and the task is not done or cancelled
+    pub is_overdue: bool,
     pub executor: String,
+    /// CI checks of the latest attempt's pushed branch
+    pub ci_checks: Vec<CiCheck>,
+    /// Combined state of `ci_checks`: failure if any failed, pending while
+    /// any runs, success otherwise. Null without checks.
+    pub ci_state: Option<CiCheckState>,
 }
 
 impl std::ops::Deref for TaskWithAttemptStatus {
@@ -69,6 +119,19 @@ pub struct CreateTask {
     pub description: Option<String>,
     pub parent_task_attempt: Option<Uuid>,
     pub image_ids: Option<Vec<Uuid>>,
+    /// Create the task as a subtask of this task
+    #[serde(default)]
+    #[ts(optional)]
+    pub parent_task_id: Option<Uuid>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub priority: Option<TaskPriority>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub due_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub assignee: Option<String>,
 }
 
 impl CreateTask {
@@ -83,6 +146,10 @@ impl CreateTask {
             description,
             parent_task_attempt: None,
             image_ids: None,
+            parent_task_id: None,
+            priority: None,
+            due_date: None,
+            assignee: None,
         }
     }
 }
@@ -94,6 +161,35 @@ pub struct UpdateTask {
     pub status: Option<TaskStatus>,
     pub parent_task_attempt: Option<Uuid>,
     pub image_ids: Option<Vec<Uuid>>,
+    /// Move the task under this parent task; left unchanged when omitted
+    #[serde(default)]
+    #[ts(optional)]
+    pub parent_task_id: Option<Uuid>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub priority: Option<TaskPriority>,
+    /// Move the task into this board column; its status follows the column
+    #[serde(default)]
+    #[ts(optional)]
+    pub column_id: Option<Uuid>,
+    /// New due date, or null to clear it; left unchanged when omitted
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[ts(optional, type = "string | null")]
+    pub due_date: Option<Option<DateTime<Utc>>>,
+}
+
+/// Deserialize a field that is present (even as null) to `Some`, so an
+/// omitted field can be told apart from an explicit null
+pub(crate) fn deserialize_present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
 }
 
 impl Task {
@@ -105,6 +201,12 @@ impl Task {
         }
     }
 
+    /// The due date has passed and the task is still open
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        self.due_date.is_some_and(|due_date| due_date <= now)
+            && !matches!(self.status, TaskStatus::Done | TaskStatus::Cancelled)
+    }
+
     pub async fn parent_project(&self, pool: &SqlitePool) -> Result<Option<Project>, sqlx::Error> {
         Project::find_by_id(pool, self.project_id).await
     }
@@ -121,6 +223,10 @@ impl Task {
   t.description,
   t.status                        AS "status!: TaskStatus",
   t.parent_task_attempt           AS "parent_task_attempt: Uuid",
+  t.parent_task_id                AS "parent_task_id: Uuid",
+  t.priority                      AS "priority!: TaskPriority",
+  t.due_date                      AS "due_date: DateTime<Utc>",
+  t.assignee,
   t.created_at                    AS "created_at!: DateTime<Utc>",
   t.updated_at                    AS "updated_at!: DateTime<Utc>",
 
@@ -147,38 +253,94 @@ impl Task {
   ) IN ('failed','killed') THEN 1 ELSE 0 END
                                  AS "last_attempt_failed!: i64",
 
+  CASE WHEN EXISTS (
+    SELECT 1
+      FROM task_dependencies td
+      JOIN tasks blocker
+        ON blocker.id = td.depends_on_task_id
+     WHERE td.task_id       = t.id
+       AND blocker.status  != 'done'
+       AND blocker.deleted_at IS NULL
+     LIMIT 1
+  ) THEN 1 ELSE 0 END            AS "is_blocked!: i64",
+
+  COALESCE(
+    ( SELECT c.id
+        FROM project_columns c
+       WHERE c.id     = t.column_id
+         AND c.status = t.status ),
+    ( SELECT c.id
+        FROM project_columns c
+       WHERE c.project_id = t.project_id
+         AND c.status     = t.status
+       ORDER BY c.position ASC
+       LIMIT 1 )
+  )                               AS "column_id: Uuid",
+
   ( SELECT ta.executor
       FROM task_attempts ta
       WHERE ta.task_id = t.id
      ORDER BY ta.created_at DESC
       LIMIT 1
-    )                               AS "executor!: String"
+    )                               AS "executor!: String",
+
+  ( SELECT ta.id
+      FROM task_attempts ta
+      WHERE ta.task_id = t.id
+     ORDER BY ta.created_at DESC
+      LIMIT 1
+    )                               AS "latest_attempt_id: Uuid"
 
 FROM tasks t
-WHERE t.project_id = $1
+WHERE t.project_id = $1 AND t.deleted_at IS NULL
 ORDER BY t.created_at DESC"#,
             project_id
         )
         .fetch_all(pool)
         .await?;
 
+        let mut ci_checks: HashMap<Uuid, Vec<CiCheck>> = HashMap::new();
+        for check in CiCheck::find_latest_by_project_id(pool, project_id).await? {
+            ci_checks
+                .entry(check.task_attempt_id)
+                .or_default()
+                .push(check);
+        }
+
+        let now = Utc::now();
         let tasks = records
             .into_iter()
-            .map(|rec| TaskWithAttemptStatus {
-                task: Task {
+            .map(|rec| {
+                let ci_checks = rec
+                    .latest_attempt_id
+                    .and_then(|attempt_id| ci_checks.remove(&attempt_id))
+                    .unwrap_or_default();
+                let task = Task {
                     id: rec.id,
                     project_id: rec.project_id,
                     title: rec.title,
                     description: rec.description,
                     status: rec.status,
                     parent_task_attempt: rec.parent_task_attempt,
+                    parent_task_id: rec.parent_task_id,
+                    priority: rec.priority,
+                    due_date: rec.due_date,
+                    assignee: rec.assignee,
                     created_at: rec.created_at,
                     updated_at: rec.updated_at,
-                },
-                has_in_progress_attempt: rec.has_in_progress_attempt != 0,
-                has_merged_attempt: false, // TODO use merges table
-                last_attempt_failed: rec.last_attempt_failed != 0,
-                executor: rec.executor,
+                };
+                TaskWithAttemptStatus {
+                    is_overdue: task.is_overdue(now),
+                    task,
+                    has_in_progress_attempt: rec.has_in_progress_attempt != 0,
+                    has_merged_attempt: false, // TODO use merges table
+                    last_attempt_failed: rec.last_attempt_failed != 0,
+                    is_blocked: rec.is_blocked != 0,
+                    column_id: rec.column_id,
+                    executor: rec.executor,
+                    ci_state: CiCheckState::combined(&ci_checks),
+                    ci_checks,
+                }
             })
             .collect();
 
@@ -188,7 +350,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", parent_task_id as "parent_task_id: Uuid", priority as "priority!: TaskPriority", due_date as "due_date: DateTime<Utc>", assignee, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks 
                WHERE id = $1"#,
             id
@@ -200,7 +362,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", parent_task_id as "parent_task_id: Uuid", priority as "priority!: TaskPriority", due_date as "due_date: DateTime<Utc>", assignee, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks 
                WHERE rowid = $1"#,
             rowid
@@ -216,7 +378,7 @@ ORDER BY t.created_at DESC"#,
     ) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", parent_task_id as "parent_task_id: Uuid", priority as "priority!: TaskPriority", due_date as "due_date: DateTime<Utc>", assignee, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks 
                WHERE id = $1 AND project_id = $2"#,
             id,
@@ -231,22 +393,28 @@ ORDER BY t.created_at DESC"#,
         data: &CreateTask,
         task_id: Uuid,
     ) -> Result<Self, sqlx::Error> {
+        let priority = data.priority.unwrap_or_default();
         sqlx::query_as!(
             Task,
-            r#"INSERT INTO tasks (id, project_id, title, description, status, parent_task_attempt) 
-               VALUES ($1, $2, $3, $4, $5, $6) 
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO tasks (id, project_id, title, description, status, parent_task_attempt, parent_task_id, priority, due_date, assignee) 
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) 
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", parent_task_id as "parent_task_id: Uuid", priority as "priority!: TaskPriority", due_date as "due_date: DateTime<Utc>", assignee, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             task_id,
             data.project_id,
             data.title,
             data.description,
             TaskStatus::Todo as TaskStatus,
-            data.parent_task_attempt
+            data.parent_task_attempt,
+            data.parent_task_id,
+            priority,
+            data.due_date,
+            data.assignee
         )
         .fetch_one(pool)
         .await
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn update(
         pool: &SqlitePool,
         id: Uuid,
@@ -255,19 +423,48 @@ ORDER BY t.created_at DESC"#,
         description: Option<String>,
         status: TaskStatus,
         parent_task_attempt: Option<Uuid>,
+        parent_task_id: Option<Uuid>,
+        priority: TaskPriority,
+        due_date: Option<DateTime<Utc>>,
     ) -> Result<Self, sqlx::Error> {
-        sqlx::query_as!(
+        let task = sqlx::query_as!(
             Task,
             r#"UPDATE tasks 
-               SET title = $3, description = $4, status = $5, parent_task_attempt = $6 
+               SET title = $3, description = $4, status = $5, parent_task_attempt = $6, parent_task_id = $7, priority = $8,
+                   overdue_notified_at = CASE WHEN due_date IS $9 THEN overdue_notified_at ELSE NULL END,
+                   due_date = $9 
                WHERE id = $1 AND project_id = $2 
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", parent_task_id as "parent_task_id: Uuid", priority as "priority!: TaskPriority", due_date as "due_date: DateTime<Utc>", assignee, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             project_id,
             title,
             description,
             status,
-            parent_task_attempt
+            parent_task_attempt,
+            parent_task_id,
+            priority,
+            due_date
+        )
+        .fetch_one(pool)
+        .await?;
+        Self::roll_up_status(pool, task.parent_task_id).await?;
+        Ok(task)
+    }
+
+    /// Assign the task to `assignee`, or unassign it with `None`
+    pub async fn set_assignee(
+        pool: &SqlitePool,
+        id: Uuid,
+        assignee: Option<&str>,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"UPDATE tasks
+               SET assignee = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", parent_task_id as "parent_task_id: Uuid", priority as "priority!: TaskPriority", due_date as "due_date: DateTime<Utc>", assignee, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            assignee
         )
         .fetch_one(pool)
         .await
@@ -285,9 +482,233 @@ ORDER BY t.created_at DESC"#,
         )
         .execute(pool)
         .await?;
+
+        let parent_task_id = sqlx::query_scalar!(
+            r#"SELECT parent_task_id as "parent_task_id: Uuid" FROM tasks WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?
+        .flatten();
+        Self::roll_up_status(pool, parent_task_id).await
+    }
+
+    /// Set the status of a task inside a caller-managed transaction. Unlike
+    /// [`Self::update_status`] this does not roll the status up to parents.
+    pub async fn set_status<'e, E>(
+        executor: E,
+        id: Uuid,
+        status: TaskStatus,
+    ) -> Result<u64, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let result = sqlx::query!(
+            "UPDATE tasks SET status = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+            id,
+            status
+        )
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Move a task to another project. Links that only make sense within the
+    /// old project (parent task, originating attempt) are dropped.
+    pub async fn move_to_project<'e, E>(
+        executor: E,
+        id: Uuid,
+        project_id: Uuid,
+    ) -> Result<u64, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let result = sqlx::query!(
+            "UPDATE tasks SET project_id = $2, parent_task_id = NULL, parent_task_attempt = NULL, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+            id,
+            project_id
+        )
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Turn the subtasks of a task into top-level tasks
+    pub async fn detach_subtasks<'e, E>(
+        executor: E,
+        parent_task_id: Uuid,
+    ) -> Result<u64, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let result = sqlx::query!(
+            "UPDATE tasks SET parent_task_id = NULL WHERE parent_task_id = $1",
+            parent_task_id
+        )
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Derive the status of `parent_task_id` (and its ancestors) from its
+    /// subtasks: in progress while any subtask is in progress, done once all
+    /// subtasks are done. Cancelled subtasks are ignored; any other mix leaves
+    /// the parent's status as it is.
+    pub async fn roll_up_status(
+        pool: &SqlitePool,
+        mut parent_task_id: Option<Uuid>,
+    ) -> Result<(), sqlx::Error> {
+        while let Some(parent_id) = parent_task_id {
+            let Some(parent) = Self::find_by_id(pool, parent_id).await? else {
+                return Ok(());
+            };
+            let statuses: Vec<TaskStatus> = Self::find_subtasks(pool, parent.id)
+                .await?
+                .into_iter()
+                .map(|subtask| subtask.status)
+                .filter(|status| *status != TaskStatus::Cancelled)
+                .collect();
+
+            let rolled_up = if statuses.contains(&TaskStatus::InProgress) {
+                TaskStatus::InProgress
+            } else if !statuses.is_empty() && statuses.iter().all(|s| *s == TaskStatus::Done) {
+                TaskStatus::Done
+            } else {
+                return Ok(());
+            };
+            if rolled_up == parent.status {
+                return Ok(());
+            }
+
+            sqlx::query!(
+                "UPDATE tasks SET status = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+                parent.id,
+                rolled_up
+            )
+            .execute(pool)
+            .await?;
+            parent_task_id = parent.parent_task_id;
+        }
+        Ok(())
+    }
+
+    /// Pin the task to a board column. The pin is only honoured while the
+    /// column's status matches the task's.
+    pub async fn set_column(
+        pool: &SqlitePool,
+        id: Uuid,
+        column_id: Option<Uuid>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE tasks SET column_id = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+            id,
+            column_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Board column the task shows in: the column it was moved into while that
+    /// still matches its status, else the first column of its status
+    pub async fn find_column_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Uuid>, sqlx::Error> {
+        let column_id = sqlx::query_scalar!(
+            r#"SELECT COALESCE(
+                   ( SELECT c.id FROM project_columns c
+                      WHERE c.id = t.column_id AND c.status = t.status ),
+                   ( SELECT c.id FROM project_columns c
+                      WHERE c.project_id = t.project_id AND c.status = t.status
+                      ORDER BY c.position ASC
+                      LIMIT 1 )
+               ) as "column_id: Uuid"
+               FROM tasks t
+               WHERE t.id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(column_id.flatten())
+    }
+
+    /// In-progress or in-review tasks that passed their due date and have not
+    /// been reported as overdue yet
+    pub async fn find_newly_overdue(
+        pool: &SqlitePool,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", parent_task_id as "parent_task_id: Uuid", priority as "priority!: TaskPriority", due_date as "due_date: DateTime<Utc>", assignee, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE status IN ('inprogress', 'inreview')
+                 AND due_date IS NOT NULL
+                 AND datetime(due_date) <= datetime($1)
+                 AND overdue_notified_at IS NULL
+                 AND deleted_at IS NULL
+                 AND project_id IN (
+                     SELECT id FROM projects WHERE deleted_at IS NULL AND archived_at IS NULL
+                 )
+               ORDER BY due_date ASC"#,
+            now
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Record that the task was reported as overdue. The write also pushes the
+    /// task, now flagged `is_overdue`, to the events stream.
+    pub async fn mark_overdue_notified(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE tasks SET overdue_notified_at = datetime('now', 'subsec') WHERE id = $1",
+            id
+        )
+        .execute(pool)
+        .await?;
         Ok(())
     }
 
+    /// Direct subtasks of a task
+    pub async fn find_subtasks(
+        pool: &SqlitePool,
+        parent_task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", parent_task_id as "parent_task_id: Uuid", priority as "priority!: TaskPriority", due_date as "due_date: DateTime<Utc>", assignee, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE parent_task_id = $1 AND deleted_at IS NULL
+               ORDER BY created_at ASC"#,
+            parent_task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Whether `ancestor_id` is `task_id` itself or one of its parent tasks.
+    /// Used to keep the subtask hierarchy free of cycles.
+    pub async fn is_self_or_descendant_of(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        ancestor_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let found = sqlx::query_scalar!(
+            r#"WITH RECURSIVE ancestors(id) AS (
+                   SELECT $1
+                   UNION
+                   SELECT t.parent_task_id
+                     FROM tasks t
+                     JOIN ancestors a ON t.id = a.id
+                    WHERE t.parent_task_id IS NOT NULL
+               )
+               SELECT EXISTS(SELECT 1 FROM ancestors WHERE id = $2) as "found!: bool""#,
+            task_id,
+            ancestor_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(found)
+    }
+
     /// Nullify parent_task_attempt for all tasks that reference the given attempt ID
     /// This breaks parent-child relationships before deleting a parent task
     pub async fn nullify_children_by_attempt_id<'e, E>(
@@ -306,6 +727,57 @@ ORDER BY t.created_at DESC"#,
         Ok(result.rows_affected())
     }
 
+    /// Move the task to the trash. It disappears from the board but keeps its
+    /// attempts and worktrees until restored or purged.
+    pub async fn soft_delete<'e, E>(executor: E, id: Uuid) -> Result<u64, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let result = sqlx::query!(
+            "UPDATE tasks SET deleted_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec') WHERE id = $1 AND deleted_at IS NULL",
+            id
+        )
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn restore(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "UPDATE tasks SET deleted_at = NULL, updated_at = datetime('now', 'subsec') WHERE id = $1 AND deleted_at IS NOT NULL",
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn is_trashed(pool: &SqlitePool, id: Uuid) -> Result<bool, sqlx::Error> {
+        let trashed = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM tasks WHERE id = $1 AND deleted_at IS NOT NULL) as "trashed!: bool""#,
+            id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(trashed)
+    }
+
+    /// Tasks moved to the trash before `cutoff`, due to be purged
+    pub async fn find_trashed_before(
+        pool: &SqlitePool,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", parent_task_id as "parent_task_id: Uuid", priority as "priority!: TaskPriority", due_date as "due_date: DateTime<Utc>", assignee, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE deleted_at IS NOT NULL AND datetime(deleted_at) < datetime($1)"#,
+            cutoff
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     pub async fn delete<'e, E>(executor: E, id: Uuid) -> Result<u64, sqlx::Error>
     where
         E: Executor<'e, Database = Sqlite>,
@@ -338,7 +810,7 @@ ORDER BY t.created_at DESC"#,
         // Find only child tasks that have this attempt as their parent
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_task_attempt as "parent_task_attempt: Uuid", parent_task_id as "parent_task_id: Uuid", priority as "priority!: TaskPriority", due_date as "due_date: DateTime<Utc>", assignee, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks 
                WHERE parent_task_attempt = $1
                ORDER BY created_at DESC"#,
@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TaskActivityKind {
+    Created,
+    StatusChanged,
+    AttemptStarted,
+    AttemptFinished,
+    PrOpened,
+    PrClosed,
+    Merged,
+}
+
+/// An entry of a task's history. Rows are written by database triggers, so
+/// there is no create method.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskActivity {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub task_attempt_id: Option<Uuid>,
+    pub kind: TaskActivityKind,
+    /// Set for `status_changed`
+    pub from_status: Option<TaskStatus>,
+    /// Set for `status_changed`
+    pub to_status: Option<TaskStatus>,
+    /// Final process status for `attempt_finished`, PR URL or merge commit for
+    /// PR and merge entries
+    pub detail: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaskActivity {
+    /// History of a task, oldest first
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskActivity,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      task_attempt_id as "task_attempt_id: Uuid",
+                      kind as "kind!: TaskActivityKind",
+                      from_status as "from_status: TaskStatus",
+                      to_status as "to_status: TaskStatus",
+                      detail,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_activities
+               WHERE task_id = $1
+               ORDER BY created_at ASC, rowid ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,156 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A file attached to a task. Unlike images, attachments are not embedded in
+/// the description; their paths are listed in the coding agent's prompt.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskAttachment {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub file_path: String, // relative path within assets/attachments/
+    pub original_name: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub hash: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct CreateTaskAttachment {
+    pub file_path: String,
+    pub original_name: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub hash: String,
+}
+
+impl TaskAttachment {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttachment,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      file_path,
+                      original_name,
+                      mime_type,
+                      size_bytes,
+                      hash,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_attachments
+               WHERE task_id = $1
+               ORDER BY created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttachment,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      file_path,
+                      original_name,
+                      mime_type,
+                      size_bytes,
+                      hash,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_attachments
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_task_and_hash(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        hash: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttachment,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      file_path,
+                      original_name,
+                      mime_type,
+                      size_bytes,
+                      hash,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_attachments
+               WHERE task_id = $1 AND hash = $2"#,
+            task_id,
+            hash
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Combined size of the task's attachments
+    pub async fn total_size_for_task(pool: &SqlitePool, task_id: Uuid) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COALESCE(SUM(size_bytes), 0) as "total!: i64" FROM task_attachments WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Stored file names of every attachment, used to clean up files whose
+    /// task has been deleted
+    pub async fn all_file_paths(pool: &SqlitePool) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar!(r#"SELECT file_path FROM task_attachments"#)
+            .fetch_all(pool)
+            .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        data: &CreateTaskAttachment,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskAttachment,
+            r#"INSERT INTO task_attachments (id, task_id, file_path, original_name, mime_type, size_bytes, hash)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         file_path,
+                         original_name,
+                         mime_type,
+                         size_bytes,
+                         hash,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            data.file_path,
+            data.original_name,
+            data.mime_type,
+            data.size_bytes,
+            data.hash
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_attachments WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -45,6 +45,13 @@ pub struct TaskAttempt {
     // "GEMINI", etc.)
     pub worktree_deleted: bool, // Flag indicating if worktree has been cleaned up
     pub setup_completed_at: Option<DateTime<Utc>>, // When setup script was last completed
+    pub archived_at: Option<DateTime<Utc>>, // When the attempt was archived (worktree torn down)
+    pub execution_timeout_minutes: Option<i64>, // Overrides the project execution timeout
+    pub timed_out_at: Option<DateTime<Utc>>, // Set when the latest run was cancelled for exceeding its timeout
+    pub paused_at: Option<DateTime<Utc>>,    // Set while the running executor is suspended
+    pub parent_attempt_id: Option<Uuid>,     // Attempt whose branch this attempt is stacked on
+    pub assignee: Option<String>,            // Who is responsible for the attempt
+    pub read_only: bool, // The agent answers questions but may not change the worktree
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -107,6 +114,13 @@ impl TaskAttempt {
                               executor AS "executor!",
                               worktree_deleted AS "worktree_deleted!: bool",
                               setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                              archived_at AS "archived_at: DateTime<Utc>",
+                              execution_timeout_minutes AS "execution_timeout_minutes: i64",
+                              timed_out_at AS "timed_out_at: DateTime<Utc>",
+                              paused_at AS "paused_at: DateTime<Utc>",
+                              parent_attempt_id AS "parent_attempt_id: Uuid",
+                              assignee,
+                              read_only AS "read_only!: bool",
                               created_at AS "created_at!: DateTime<Utc>",
                               updated_at AS "updated_at!: DateTime<Utc>"
                        FROM task_attempts
@@ -127,6 +141,13 @@ impl TaskAttempt {
                               executor AS "executor!",
                               worktree_deleted AS "worktree_deleted!: bool",
                               setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                              archived_at AS "archived_at: DateTime<Utc>",
+                              execution_timeout_minutes AS "execution_timeout_minutes: i64",
+                              timed_out_at AS "timed_out_at: DateTime<Utc>",
+                              paused_at AS "paused_at: DateTime<Utc>",
+                              parent_attempt_id AS "parent_attempt_id: Uuid",
+                              assignee,
+                              read_only AS "read_only!: bool",
                               created_at AS "created_at!: DateTime<Utc>",
                               updated_at AS "updated_at!: DateTime<Utc>"
                        FROM task_attempts
@@ -158,6 +179,13 @@ impl TaskAttempt {
                        ta.executor AS "executor!",
                        ta.worktree_deleted  AS "worktree_deleted!: bool",
                        ta.setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       ta.archived_at        AS "archived_at: DateTime<Utc>",
+                       ta.execution_timeout_minutes AS "execution_timeout_minutes: i64",
+                       ta.timed_out_at AS "timed_out_at: DateTime<Utc>",
+                       ta.paused_at AS "paused_at: DateTime<Utc>",
+                       ta.parent_attempt_id AS "parent_attempt_id: Uuid",
+                       ta.assignee,
+                       ta.read_only AS "read_only!: bool",
                        ta.created_at        AS "created_at!: DateTime<Utc>",
                        ta.updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts ta
@@ -220,6 +248,158 @@ impl TaskAttempt {
         Ok(())
     }
 
+    /// Clear the deleted flag once a worktree is back in place, e.g. after it
+    /// was relinked or repaired
+    pub async fn mark_worktree_restored(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE task_attempts SET worktree_deleted = FALSE, updated_at = datetime('now') WHERE id = ?",
+            attempt_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Latest attempt working on `branch`, to match worktree directories that
+    /// lost their attempt back to it
+    pub async fn find_id_by_branch(
+        pool: &SqlitePool,
+        branch: &str,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        let record = sqlx::query!(
+            r#"SELECT id as "id!: Uuid" FROM task_attempts WHERE branch = $1 ORDER BY created_at DESC LIMIT 1"#,
+            branch
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(record.map(|r| r.id))
+    }
+
+    /// How many attempts the task has, including archived ones
+    pub async fn count_by_task_id(pool: &SqlitePool, task_id: Uuid) -> Result<i64, sqlx::Error> {
+        let record = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!: i64" FROM task_attempts WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(record.count)
+    }
+
+    /// Mark an attempt as archived
+    pub async fn archive(pool: &SqlitePool, attempt_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE task_attempts SET archived_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec') WHERE id = $1",
+            attempt_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Clear the archived flag; the worktree is recreated lazily on next use
+    pub async fn unarchive(pool: &SqlitePool, attempt_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE task_attempts SET archived_at = NULL, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            attempt_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_execution_timeout(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        minutes: Option<i64>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE task_attempts SET execution_timeout_minutes = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            attempt_id,
+            minutes
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_read_only(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        read_only: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE task_attempts SET read_only = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            attempt_id,
+            read_only
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Flag the attempt's latest run as cancelled for exceeding its timeout
+    pub async fn mark_timed_out(pool: &SqlitePool, attempt_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE task_attempts SET timed_out_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec') WHERE id = $1",
+            attempt_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn clear_timed_out(pool: &SqlitePool, attempt_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE task_attempts SET timed_out_at = NULL, updated_at = datetime('now', 'subsec') WHERE id = $1 AND timed_out_at IS NOT NULL",
+            attempt_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Flag the attempt as paused while its executor is suspended
+    pub async fn mark_paused(pool: &SqlitePool, attempt_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE task_attempts SET paused_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec') WHERE id = $1",
+            attempt_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Clear the paused flag, adding the time since it was set to the paused
+    /// time of the attempt's running executions so their timeouts move back
+    pub async fn clear_paused(pool: &SqlitePool, attempt_id: Uuid) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            r#"UPDATE execution_processes
+                  SET paused_ms = paused_ms + CAST(
+                      (julianday('now') - julianday(
+                          (SELECT paused_at FROM task_attempts WHERE id = $1)
+                      )) * 86400000 AS INTEGER)
+                WHERE task_attempt_id = $1
+                  AND status = 'running'
+                  AND run_reason != 'devserver'
+                  AND EXISTS (SELECT 1 FROM task_attempts WHERE id = $1 AND paused_at IS NOT NULL)"#,
+            attempt_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "UPDATE task_attempts SET paused_at = NULL, updated_at = datetime('now', 'subsec') WHERE id = $1 AND paused_at IS NOT NULL",
+            attempt_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await
+    }
+
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             TaskAttempt,
@@ -231,6 +411,13 @@ impl TaskAttempt {
                        executor AS "executor!",
                        worktree_deleted  AS "worktree_deleted!: bool",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       archived_at        AS "archived_at: DateTime<Utc>",
+                       execution_timeout_minutes AS "execution_timeout_minutes: i64",
+                       timed_out_at AS "timed_out_at: DateTime<Utc>",
+                       paused_at AS "paused_at: DateTime<Utc>",
+                       parent_attempt_id AS "parent_attempt_id: Uuid",
+                       assignee,
+                       read_only AS "read_only!: bool",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts
@@ -241,6 +428,70 @@ impl TaskAttempt {
         .await
     }
 
+    /// Attempts stacked directly on the given attempt's branch
+    pub async fn find_by_parent_attempt_id(
+        pool: &SqlitePool,
+        parent_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttempt,
+            r#"SELECT  id                AS "id!: Uuid",
+                       task_id           AS "task_id!: Uuid",
+                       container_ref,
+                       branch,
+                       target_branch,
+                       executor AS "executor!",
+                       worktree_deleted  AS "worktree_deleted!: bool",
+                       setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       archived_at        AS "archived_at: DateTime<Utc>",
+                       execution_timeout_minutes AS "execution_timeout_minutes: i64",
+                       timed_out_at AS "timed_out_at: DateTime<Utc>",
+                       paused_at AS "paused_at: DateTime<Utc>",
+                       parent_attempt_id AS "parent_attempt_id: Uuid",
+                       assignee,
+                       read_only AS "read_only!: bool",
+                       created_at        AS "created_at!: DateTime<Utc>",
+                       updated_at        AS "updated_at!: DateTime<Utc>"
+               FROM    task_attempts
+               WHERE   parent_attempt_id = $1
+               ORDER BY created_at ASC"#,
+            parent_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn set_parent_attempt(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        parent_attempt_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE task_attempts SET parent_attempt_id = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            attempt_id,
+            parent_attempt_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Assign the attempt to `assignee`, or unassign it with `None`
+    pub async fn set_assignee(
+        pool: &SqlitePool,
+        attempt_id: Uuid,
+        assignee: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE task_attempts SET assignee = $2, updated_at = datetime('now', 'subsec') WHERE id = $1",
+            attempt_id,
+            assignee
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             TaskAttempt,
@@ -252,6 +503,13 @@ impl TaskAttempt {
                        executor AS "executor!",
                        worktree_deleted  AS "worktree_deleted!: bool",
                        setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                       archived_at        AS "archived_at: DateTime<Utc>",
+                       execution_timeout_minutes AS "execution_timeout_minutes: i64",
+                       timed_out_at AS "timed_out_at: DateTime<Utc>",
+                       paused_at AS "paused_at: DateTime<Utc>",
+                       parent_attempt_id AS "parent_attempt_id: Uuid",
+                       assignee,
+                       read_only AS "read_only!: bool",
                        created_at        AS "created_at!: DateTime<Utc>",
                        updated_at        AS "updated_at!: DateTime<Utc>"
                FROM    task_attempts
@@ -286,6 +544,69 @@ impl TaskAttempt {
             .collect())
     }
 
+    /// Find task attempts of every task in a project, with the project git repo
+    /// path, for cleanup operations
+    pub async fn find_by_project_id_with_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<(Uuid, Option<String>, String)>, sqlx::Error> {
+        let records = sqlx::query!(
+            r#"
+            SELECT ta.id as "attempt_id!: Uuid", ta.container_ref, p.git_repo_path as "git_repo_path!"
+            FROM task_attempts ta
+            JOIN tasks t ON ta.task_id = t.id
+            JOIN projects p ON t.project_id = p.id
+            WHERE t.project_id = $1
+            "#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| (r.attempt_id, r.container_ref, r.git_repo_path))
+            .collect())
+    }
+
+    /// Attempts of the project that are not archived, whose task is still
+    /// open and not in the trash
+    pub async fn find_open_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskAttempt,
+            r#"SELECT ta.id AS "id!: Uuid",
+                      ta.task_id AS "task_id!: Uuid",
+                      ta.container_ref,
+                      ta.branch,
+                      ta.target_branch,
+                      ta.executor AS "executor!",
+                      ta.worktree_deleted AS "worktree_deleted!: bool",
+                      ta.setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                      ta.archived_at AS "archived_at: DateTime<Utc>",
+                      ta.execution_timeout_minutes AS "execution_timeout_minutes: i64",
+                      ta.timed_out_at AS "timed_out_at: DateTime<Utc>",
+                      ta.paused_at AS "paused_at: DateTime<Utc>",
+                      ta.parent_attempt_id AS "parent_attempt_id: Uuid",
+                      ta.assignee,
+                      ta.read_only AS "read_only!: bool",
+                      ta.created_at AS "created_at!: DateTime<Utc>",
+                      ta.updated_at AS "updated_at!: DateTime<Utc>"
+               FROM task_attempts ta
+               JOIN tasks t ON t.id = ta.task_id
+               WHERE t.project_id = $1
+                 AND t.deleted_at IS NULL
+                 AND t.status IN ('todo', 'inprogress', 'inreview')
+                 AND ta.archived_at IS NULL
+               ORDER BY ta.created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     pub async fn find_by_worktree_deleted(
         pool: &SqlitePool,
     ) -> Result<Vec<(Uuid, String)>, sqlx::Error> {
@@ -313,12 +634,14 @@ impl TaskAttempt {
         Ok(result.exists)
     }
 
-    /// Find task attempts that are expired (72+ hours since last activity) and eligible for worktree cleanup
+    /// Find task attempts that are expired (`max_age_hours`+ hours since last activity) and eligible for worktree cleanup
     /// Activity includes: execution completion, task attempt updates (including worktree recreation),
     /// and any attempts that are currently in progress
     pub async fn find_expired_for_cleanup(
         pool: &SqlitePool,
+        max_age_hours: i64,
     ) -> Result<Vec<(Uuid, String, String)>, sqlx::Error> {
+        let cutoff = format!("-{max_age_hours} hours");
         let records = sqlx::query!(
             r#"
             SELECT ta.id as "attempt_id!: Uuid", ta.container_ref, p.git_repo_path as "git_repo_path!"
@@ -334,7 +657,7 @@ impl TaskAttempt {
                     WHERE ep2.completed_at IS NULL
                 )
             GROUP BY ta.id, ta.container_ref, p.git_repo_path, ta.updated_at
-            HAVING datetime('now', '-72 hours') > datetime(
+            HAVING datetime('now', $1) > datetime(
                 MAX(
                     CASE
                         WHEN ep.completed_at IS NOT NULL THEN ep.completed_at
@@ -348,6 +671,43 @@ impl TaskAttempt {
                     ELSE ta.updated_at
                 END
             ) ASC
+            "#,
+            cutoff
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .filter_map(|r| {
+                r.container_ref
+                    .map(|path| (r.attempt_id, path, r.git_repo_path))
+            })
+            .collect())
+    }
+
+    /// Find task attempts whose changes were merged (directly or through a merged PR)
+    /// and whose worktree still exists, skipping attempts with running processes
+    pub async fn find_merged_for_cleanup(
+        pool: &SqlitePool,
+    ) -> Result<Vec<(Uuid, String, String)>, sqlx::Error> {
+        let records = sqlx::query!(
+            r#"
+            SELECT ta.id as "attempt_id!: Uuid", ta.container_ref, p.git_repo_path as "git_repo_path!"
+            FROM task_attempts ta
+            JOIN tasks t ON ta.task_id = t.id
+            JOIN projects p ON t.project_id = p.id
+            WHERE ta.worktree_deleted = FALSE
+                AND EXISTS (
+                    SELECT 1 FROM merges m
+                    WHERE m.task_attempt_id = ta.id
+                        AND (m.merge_type = 'direct' OR m.pr_status = 'merged')
+                )
+                AND ta.id NOT IN (
+                    SELECT DISTINCT ep.task_attempt_id
+                    FROM execution_processes ep
+                    WHERE ep.completed_at IS NULL
+                )
             "#
         )
         .fetch_all(pool)
@@ -374,7 +734,7 @@ impl TaskAttempt {
             TaskAttempt,
             r#"INSERT INTO task_attempts (id, task_id, container_ref, branch, target_branch, executor, worktree_deleted, setup_completed_at)
                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, target_branch, executor as "executor!",  worktree_deleted as "worktree_deleted!: bool", setup_completed_at as "setup_completed_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+               RETURNING id as "id!: Uuid", task_id as "task_id!: Uuid", container_ref, branch, target_branch, executor as "executor!",  worktree_deleted as "worktree_deleted!: bool", setup_completed_at as "setup_completed_at: DateTime<Utc>", archived_at as "archived_at: DateTime<Utc>", execution_timeout_minutes as "execution_timeout_minutes: i64", timed_out_at as "timed_out_at: DateTime<Utc>", paused_at as "paused_at: DateTime<Utc>", parent_attempt_id as "parent_attempt_id: Uuid", assignee, read_only as "read_only!: bool", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             task_id,
             Option::<String>::None, // Container isn't known yet
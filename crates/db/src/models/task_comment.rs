@@ -0,0 +1,148 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskComment {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// Attempt the comment is about, if any
+    pub task_attempt_id: Option<Uuid>,
+    pub author: String,
+    /// Markdown
+    pub body: String,
+    /// File of the attempt's diff the comment is anchored to
+    pub file_path: Option<String>,
+    /// Line of `file_path` in the new version of the file
+    pub line_number: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateTaskComment {
+    pub body: String,
+    /// Defaults to the configured GitHub username
+    pub author: Option<String>,
+    pub task_attempt_id: Option<Uuid>,
+    pub file_path: Option<String>,
+    pub line_number: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateTaskComment {
+    pub body: String,
+}
+
+impl TaskComment {
+    /// Comments of a task, oldest first. With `task_attempt_id`, only the
+    /// comments about that attempt.
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        task_attempt_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskComment,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      task_attempt_id as "task_attempt_id: Uuid",
+                      author,
+                      body,
+                      file_path,
+                      line_number,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_comments
+               WHERE task_id = $1 AND ($2 IS NULL OR task_attempt_id = $2)
+               ORDER BY created_at ASC"#,
+            task_id,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskComment,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      task_attempt_id as "task_attempt_id: Uuid",
+                      author,
+                      body,
+                      file_path,
+                      line_number,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_comments
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        author: &str,
+        data: &CreateTaskComment,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskComment,
+            r#"INSERT INTO task_comments (id, task_id, task_attempt_id, author, body, file_path, line_number)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         task_attempt_id as "task_attempt_id: Uuid",
+                         author,
+                         body,
+                         file_path,
+                         line_number,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            data.task_attempt_id,
+            author,
+            data.body,
+            data.file_path,
+            data.line_number
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(pool: &SqlitePool, id: Uuid, body: &str) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            TaskComment,
+            r#"UPDATE task_comments
+               SET body = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         task_attempt_id as "task_attempt_id: Uuid",
+                         author,
+                         body,
+                         file_path,
+                         line_number,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            body
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_comments WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,175 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::{Task, TaskPriority, TaskStatus};
+
+/// `task_id` is blocked by `depends_on_task_id` until the latter is done
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskDependency {
+    pub task_id: Uuid,
+    pub depends_on_task_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateTaskDependency {
+    pub depends_on_task_id: Uuid,
+}
+
+/// Both directions of a task's dependency edges
+#[derive(Debug, Serialize, TS)]
+pub struct TaskDependencies {
+    /// Tasks that must be done before this one
+    pub blocked_by: Vec<Task>,
+    /// Tasks waiting for this one
+    pub blocks: Vec<Task>,
+}
+
+impl TaskDependency {
+    /// Tasks the given task depends on. Trashed blockers don't count, so
+    /// trashing a task unblocks its dependents until it's restored.
+    pub async fn find_blockers(pool: &SqlitePool, task_id: Uuid) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.parent_task_attempt as "parent_task_attempt: Uuid", t.parent_task_id as "parent_task_id: Uuid", t.priority as "priority!: TaskPriority", t.due_date as "due_date: DateTime<Utc>", t.assignee, t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks t
+               JOIN task_dependencies td ON td.depends_on_task_id = t.id
+               WHERE td.task_id = $1 AND t.deleted_at IS NULL
+               ORDER BY t.created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Tasks that depend on the given task
+    pub async fn find_dependents(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT t.id as "id!: Uuid", t.project_id as "project_id!: Uuid", t.title, t.description, t.status as "status!: TaskStatus", t.parent_task_attempt as "parent_task_attempt: Uuid", t.parent_task_id as "parent_task_id: Uuid", t.priority as "priority!: TaskPriority", t.due_date as "due_date: DateTime<Utc>", t.assignee, t.created_at as "created_at!: DateTime<Utc>", t.updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks t
+               JOIN task_dependencies td ON td.task_id = t.id
+               WHERE td.depends_on_task_id = $1
+               ORDER BY t.created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_for_task(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<TaskDependencies, sqlx::Error> {
+        Ok(TaskDependencies {
+            blocked_by: Self::find_blockers(pool, task_id).await?,
+            blocks: Self::find_dependents(pool, task_id).await?,
+        })
+    }
+
+    /// Blockers of the task that are not done yet
+    pub async fn find_unfinished_blockers(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Task>, sqlx::Error> {
+        Ok(Self::find_blockers(pool, task_id)
+            .await?
+            .into_iter()
+            .filter(|task| task.status != TaskStatus::Done)
+            .collect())
+    }
+
+    pub async fn exists(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        depends_on_task_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"SELECT task_id as "task_id!: Uuid" FROM task_dependencies WHERE task_id = $1 AND depends_on_task_id = $2"#,
+            task_id,
+            depends_on_task_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(result.is_some())
+    }
+
+    /// Whether adding "`task_id` depends on `depends_on_task_id`" would close a
+    /// cycle, i.e. `depends_on_task_id` already transitively depends on `task_id`.
+    pub async fn would_create_cycle(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        depends_on_task_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        if task_id == depends_on_task_id {
+            return Ok(true);
+        }
+        let reachable = sqlx::query_scalar!(
+            r#"WITH RECURSIVE upstream(id) AS (
+                   SELECT depends_on_task_id FROM task_dependencies WHERE task_id = $1
+                   UNION
+                   SELECT td.depends_on_task_id
+                     FROM task_dependencies td
+                     JOIN upstream u ON td.task_id = u.id
+               )
+               SELECT EXISTS(SELECT 1 FROM upstream WHERE id = $2) as "reachable!: bool""#,
+            depends_on_task_id,
+            task_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(reachable)
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        depends_on_task_id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            TaskDependency,
+            r#"INSERT INTO task_dependencies (task_id, depends_on_task_id)
+               VALUES ($1, $2)
+               RETURNING task_id as "task_id!: Uuid", depends_on_task_id as "depends_on_task_id!: Uuid", created_at as "created_at!: DateTime<Utc>""#,
+            task_id,
+            depends_on_task_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        depends_on_task_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM task_dependencies WHERE task_id = $1 AND depends_on_task_id = $2",
+            task_id,
+            depends_on_task_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Remove every dependency edge the task takes part in, in either direction
+    pub async fn delete_for_task<'e, E>(executor: E, task_id: Uuid) -> Result<u64, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let result = sqlx::query!(
+            "DELETE FROM task_dependencies WHERE task_id = $1 OR depends_on_task_id = $1",
+            task_id
+        )
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,141 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A recurrence rule that creates the next occurrence of its task once the
+/// task is done. The rule then moves on to the new task.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskRecurrence {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// RRULE-like rule, e.g. `FREQ=WEEKLY;BYDAY=MO`, evaluated in UTC
+    pub rule: String,
+    /// Which occurrence the task is, starting at 1
+    pub occurrence: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpsertTaskRecurrence {
+    pub rule: String,
+}
+
+impl TaskRecurrence {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskRecurrence,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      rule,
+                      occurrence,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_recurrences
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Recurrences whose task is done, outside the trash and archived
+    /// projects
+    pub async fn find_completed(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskRecurrence,
+            r#"SELECT r.id as "id!: Uuid",
+                      r.task_id as "task_id!: Uuid",
+                      r.rule,
+                      r.occurrence,
+                      r.created_at as "created_at!: DateTime<Utc>",
+                      r.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_recurrences r
+               JOIN tasks t ON t.id = r.task_id
+               JOIN projects p ON p.id = t.project_id
+               WHERE t.status = 'done'
+                 AND t.deleted_at IS NULL
+                 AND p.deleted_at IS NULL
+                 AND p.archived_at IS NULL"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Create or replace the recurrence rule of a task
+    pub async fn upsert(pool: &SqlitePool, task_id: Uuid, rule: &str) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskRecurrence,
+            r#"INSERT INTO task_recurrences (id, task_id, rule)
+               VALUES ($1, $2, $3)
+               ON CONFLICT(task_id) DO UPDATE SET
+                 rule = excluded.rule,
+                 updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         rule,
+                         occurrence,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            rule
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Copy the recurrence's task, with its labels, into a new to do task due
+    /// at `due_date`, and move the recurrence on to it. Returns the new task's id.
+    pub async fn create_next_occurrence(
+        pool: &SqlitePool,
+        recurrence: &TaskRecurrence,
+        due_date: DateTime<Utc>,
+    ) -> Result<Uuid, sqlx::Error> {
+        let task_id = Uuid::new_v4();
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            r#"INSERT INTO tasks (id, project_id, title, description, status, parent_task_id, priority, due_date, assignee)
+               SELECT $1, project_id, title, description, 'todo', parent_task_id, priority, $2, assignee
+                 FROM tasks
+                WHERE id = $3"#,
+            task_id,
+            due_date,
+            recurrence.task_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"INSERT INTO task_labels (task_id, label_id)
+               SELECT $1, label_id FROM task_labels WHERE task_id = $2"#,
+            task_id,
+            recurrence.task_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"UPDATE task_recurrences
+               SET task_id = $2, occurrence = occurrence + 1, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            recurrence.id,
+            task_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(task_id)
+    }
+
+    pub async fn delete_by_task_id(pool: &SqlitePool, task_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_recurrences WHERE task_id = $1", task_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,166 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A cron schedule that starts a new attempt for its task whenever it fires.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskSchedule {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    /// Five-field cron expression, evaluated in UTC
+    pub cron_expression: String,
+    #[ts(type = "ExecutorProfileId")]
+    pub executor_profile_id: sqlx::types::Json<ExecutorProfileId>,
+    pub base_branch: String,
+    pub enabled: bool,
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_task_attempt_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpsertTaskSchedule {
+    pub cron_expression: String,
+    pub executor_profile_id: ExecutorProfileId,
+    pub base_branch: String,
+    pub enabled: bool,
+}
+
+impl TaskSchedule {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      cron_expression,
+                      executor_profile_id as "executor_profile_id!: sqlx::types::Json<ExecutorProfileId>",
+                      base_branch,
+                      enabled as "enabled!: bool",
+                      next_run_at as "next_run_at: DateTime<Utc>",
+                      last_run_at as "last_run_at: DateTime<Utc>",
+                      last_task_attempt_id as "last_task_attempt_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_schedules
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Enabled schedules whose next run is at or before `now`, outside
+    /// archived projects
+    pub async fn find_due(pool: &SqlitePool, now: DateTime<Utc>) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      cron_expression,
+                      executor_profile_id as "executor_profile_id!: sqlx::types::Json<ExecutorProfileId>",
+                      base_branch,
+                      enabled as "enabled!: bool",
+                      next_run_at as "next_run_at: DateTime<Utc>",
+                      last_run_at as "last_run_at: DateTime<Utc>",
+                      last_task_attempt_id as "last_task_attempt_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_schedules
+               WHERE enabled = TRUE AND next_run_at IS NOT NULL AND next_run_at <= $1
+                 AND task_id IN (
+                     SELECT t.id FROM tasks t
+                       JOIN projects p ON p.id = t.project_id
+                      WHERE t.deleted_at IS NULL
+                        AND p.deleted_at IS NULL
+                        AND p.archived_at IS NULL
+                 )
+               ORDER BY next_run_at ASC"#,
+            now
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Create or replace the schedule of a task
+    pub async fn upsert(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        data: &UpsertTaskSchedule,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let executor_profile_id = sqlx::types::Json(&data.executor_profile_id);
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"INSERT INTO task_schedules (id, task_id, cron_expression, executor_profile_id, base_branch, enabled, next_run_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               ON CONFLICT(task_id) DO UPDATE SET
+                 cron_expression = excluded.cron_expression,
+                 executor_profile_id = excluded.executor_profile_id,
+                 base_branch = excluded.base_branch,
+                 enabled = excluded.enabled,
+                 next_run_at = excluded.next_run_at,
+                 updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         cron_expression,
+                         executor_profile_id as "executor_profile_id!: sqlx::types::Json<ExecutorProfileId>",
+                         base_branch,
+                         enabled as "enabled!: bool",
+                         next_run_at as "next_run_at: DateTime<Utc>",
+                         last_run_at as "last_run_at: DateTime<Utc>",
+                         last_task_attempt_id as "last_task_attempt_id: Uuid",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            task_id,
+            data.cron_expression,
+            executor_profile_id,
+            data.base_branch,
+            data.enabled,
+            next_run_at,
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Record a fired run and move the schedule on to its next occurrence
+    pub async fn record_run(
+        pool: &SqlitePool,
+        id: Uuid,
+        ran_at: DateTime<Utc>,
+        task_attempt_id: Option<Uuid>,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE task_schedules
+               SET last_run_at = $2,
+                   last_task_attempt_id = COALESCE($3, last_task_attempt_id),
+                   next_run_at = $4,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            ran_at,
+            task_attempt_id,
+            next_run_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_by_task_id(pool: &SqlitePool, task_id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_schedules WHERE task_id = $1", task_id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,173 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A reusable task blueprint. Title and description may contain
+/// `{{placeholders}}` that are filled in when a task is created from it.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskTemplate {
+    pub id: Uuid,
+    /// None for templates available in every project
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    pub title: String,
+    pub description: Option<String>,
+    /// Executor used when a task created from the template is started
+    #[ts(type = "ExecutorProfileId | null")]
+    pub executor_profile_id: Option<sqlx::types::Json<ExecutorProfileId>>,
+    /// Base branch used when a task created from the template is started
+    pub base_branch: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateTaskTemplate {
+    pub project_id: Option<Uuid>,
+    pub name: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    pub base_branch: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateTaskTemplate {
+    pub name: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    pub base_branch: Option<String>,
+}
+
+impl TaskTemplate {
+    /// Global templates plus, when given, the templates of `project_id`
+    pub async fn find_all(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      name,
+                      title,
+                      description,
+                      executor_profile_id as "executor_profile_id: sqlx::types::Json<ExecutorProfileId>",
+                      base_branch,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_templates
+               WHERE project_id IS NULL OR project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id: Uuid",
+                      name,
+                      title,
+                      description,
+                      executor_profile_id as "executor_profile_id: sqlx::types::Json<ExecutorProfileId>",
+                      base_branch,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_templates
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, data: &CreateTaskTemplate) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let executor_profile_id = data.executor_profile_id.as_ref().map(sqlx::types::Json);
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"INSERT INTO task_templates (id, project_id, name, title, description, executor_profile_id, base_branch)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id: Uuid",
+                         name,
+                         title,
+                         description,
+                         executor_profile_id as "executor_profile_id: sqlx::types::Json<ExecutorProfileId>",
+                         base_branch,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            data.name,
+            data.title,
+            data.description,
+            executor_profile_id,
+            data.base_branch
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateTaskTemplate,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let title = data.title.as_ref().unwrap_or(&existing.title);
+        let description = data.description.as_ref().or(existing.description.as_ref());
+        let executor_profile_id =
+            data.executor_profile_id
+                .as_ref()
+                .map(sqlx::types::Json)
+                .or(existing
+                    .executor_profile_id
+                    .as_ref()
+                    .map(|p| sqlx::types::Json(&p.0)));
+        let base_branch = data.base_branch.as_ref().or(existing.base_branch.as_ref());
+
+        sqlx::query_as!(
+            TaskTemplate,
+            r#"UPDATE task_templates
+               SET name = $2, title = $3, description = $4, executor_profile_id = $5, base_branch = $6, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id: Uuid",
+                         name,
+                         title,
+                         description,
+                         executor_profile_id as "executor_profile_id: sqlx::types::Json<ExecutorProfileId>",
+                         base_branch,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            title,
+            description,
+            executor_profile_id,
+            base_branch
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_templates WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+/// A status and board column a task entered. Rows are written by database
+/// triggers, so there is no create method.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskTransition {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub status: TaskStatus,
+    /// Column the task showed in; null once the column is deleted
+    pub column_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TaskTransition {
+    /// Transitions of a task, oldest first
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskTransition,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      status as "status!: TaskStatus",
+                      column_id as "column_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_transitions
+               WHERE task_id = $1
+               ORDER BY created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
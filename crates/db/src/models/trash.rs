@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "TEXT", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum TrashItemKind {
+    Project,
+    Task,
+}
+
+/// A deleted task or project that can still be restored
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct TrashItem {
+    pub kind: TrashItemKind,
+    pub id: Uuid,
+    /// Project name or task title
+    pub name: String,
+    /// Project the task belongs to; null for projects
+    pub project_id: Option<Uuid>,
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl TrashItem {
+    /// Everything in the trash, most recently deleted first. Tasks of a
+    /// deleted project are restored with it, so they are not listed.
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TrashItem,
+            r#"SELECT 'project' as "kind!: TrashItemKind",
+                      id as "id!: Uuid",
+                      name as "name!",
+                      NULL as "project_id: Uuid",
+                      deleted_at as "deleted_at!: DateTime<Utc>"
+               FROM projects
+               WHERE deleted_at IS NOT NULL
+               UNION ALL
+               SELECT 'task',
+                      t.id,
+                      t.title,
+                      t.project_id,
+                      t.deleted_at
+               FROM tasks t
+               JOIN projects p ON p.id = t.project_id
+               WHERE t.deleted_at IS NOT NULL AND p.deleted_at IS NULL
+               ORDER BY 5 DESC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
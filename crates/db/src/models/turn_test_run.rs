@@ -0,0 +1,171 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// Tests run after a coding agent turn, with the counts parsed from their
+/// output
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TurnTestRun {
+    /// The test script run
+    pub execution_process_id: Uuid,
+    /// The turn that was tested
+    pub coding_agent_process_id: Uuid,
+    pub task_attempt_id: Uuid,
+    /// Null when the output has no count
+    pub passed: Option<i64>,
+    pub failed: Option<i64>,
+    pub skipped: Option<i64>,
+    /// Null while the tests run
+    pub success: Option<bool>,
+    /// Follow-up the failures were sent to the agent in
+    pub follow_up_execution_process_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl TurnTestRun {
+    pub async fn create(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        coding_agent_process_id: Uuid,
+        task_attempt_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT INTO turn_test_runs (execution_process_id, coding_agent_process_id, task_attempt_id) VALUES ($1, $2, $3)",
+            execution_process_id,
+            coding_agent_process_id,
+            task_attempt_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_by_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TurnTestRun,
+            r#"SELECT execution_process_id as "execution_process_id!: Uuid",
+                      coding_agent_process_id as "coding_agent_process_id!: Uuid",
+                      task_attempt_id as "task_attempt_id!: Uuid",
+                      passed,
+                      failed,
+                      skipped,
+                      success as "success: bool",
+                      follow_up_execution_process_id as "follow_up_execution_process_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>",
+                      completed_at as "completed_at: DateTime<Utc>"
+               FROM turn_test_runs
+               WHERE execution_process_id = $1"#,
+            execution_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// The run whose failures started the follow-up `execution_process_id`
+    pub async fn find_by_follow_up_execution_process_id(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TurnTestRun,
+            r#"SELECT execution_process_id as "execution_process_id!: Uuid",
+                      coding_agent_process_id as "coding_agent_process_id!: Uuid",
+                      task_attempt_id as "task_attempt_id!: Uuid",
+                      passed,
+                      failed,
+                      skipped,
+                      success as "success: bool",
+                      follow_up_execution_process_id as "follow_up_execution_process_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>",
+                      completed_at as "completed_at: DateTime<Utc>"
+               FROM turn_test_runs
+               WHERE follow_up_execution_process_id = $1"#,
+            execution_process_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn exists_for_turn(
+        pool: &SqlitePool,
+        coding_agent_process_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM turn_test_runs WHERE coding_agent_process_id = $1
+               ) as "exists!: bool""#,
+            coding_agent_process_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(exists)
+    }
+
+    /// Runs of the attempt, oldest first
+    pub async fn find_by_task_attempt_id(
+        pool: &SqlitePool,
+        task_attempt_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TurnTestRun,
+            r#"SELECT execution_process_id as "execution_process_id!: Uuid",
+                      coding_agent_process_id as "coding_agent_process_id!: Uuid",
+                      task_attempt_id as "task_attempt_id!: Uuid",
+                      passed,
+                      failed,
+                      skipped,
+                      success as "success: bool",
+                      follow_up_execution_process_id as "follow_up_execution_process_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>",
+                      completed_at as "completed_at: DateTime<Utc>"
+               FROM turn_test_runs
+               WHERE task_attempt_id = $1
+               ORDER BY created_at ASC"#,
+            task_attempt_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn complete(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        passed: Option<i64>,
+        failed: Option<i64>,
+        skipped: Option<i64>,
+        success: bool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE turn_test_runs SET passed = $2, failed = $3, skipped = $4, success = $5, completed_at = datetime('now', 'subsec') WHERE execution_process_id = $1",
+            execution_process_id,
+            passed,
+            failed,
+            skipped,
+            success
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn set_follow_up(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        follow_up_execution_process_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE turn_test_runs SET follow_up_execution_process_id = $2 WHERE execution_process_id = $1",
+            execution_process_id,
+            follow_up_execution_process_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A group of related projects, e.g. the parts of a monorepo, with a
+/// combined board and stats
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct Workspace {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateWorkspace {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateWorkspace {
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Task counts of one project of a workspace, by status
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct WorkspaceProjectStats {
+    pub project_id: Uuid,
+    pub project_name: String,
+    pub todo: i64,
+    pub inprogress: i64,
+    pub inreview: i64,
+    pub done: i64,
+    pub cancelled: i64,
+}
+
+impl Workspace {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Workspace,
+            r#"SELECT id as "id!: Uuid", name, description, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM workspaces
+               ORDER BY name ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Workspace,
+            r#"SELECT id as "id!: Uuid", name, description, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM workspaces
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, data: &CreateWorkspace) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            Workspace,
+            r#"INSERT INTO workspaces (id, name, description)
+               VALUES ($1, $2, $3)
+               RETURNING id as "id!: Uuid", name, description, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.name,
+            data.description
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateWorkspace,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let description = data.description.as_ref().or(existing.description.as_ref());
+
+        sqlx::query_as!(
+            Workspace,
+            r#"UPDATE workspaces
+               SET name = $2, description = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid", name, description, created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            description
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Delete the workspace. Its projects are kept and become ungrouped.
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM workspaces WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Task counts per project of the workspace, leaving out trashed projects
+    /// and tasks
+    pub async fn project_stats(
+        pool: &SqlitePool,
+        id: Uuid,
+    ) -> Result<Vec<WorkspaceProjectStats>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceProjectStats,
+            r#"SELECT p.id as "project_id!: Uuid",
+                      p.name as "project_name!",
+                      COALESCE(SUM(t.status = 'todo'), 0) as "todo!: i64",
+                      COALESCE(SUM(t.status = 'inprogress'), 0) as "inprogress!: i64",
+                      COALESCE(SUM(t.status = 'inreview'), 0) as "inreview!: i64",
+                      COALESCE(SUM(t.status = 'done'), 0) as "done!: i64",
+                      COALESCE(SUM(t.status = 'cancelled'), 0) as "cancelled!: i64"
+               FROM projects p
+               LEFT JOIN tasks t ON t.project_id = p.id AND t.deleted_at IS NULL
+               WHERE p.workspace_id = $1 AND p.deleted_at IS NULL
+               GROUP BY p.id
+               ORDER BY p.name ASC"#,
+            id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
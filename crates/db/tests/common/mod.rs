@@ -0,0 +1,38 @@
+#![allow(dead_code)]
+
+use db::models::{
+    project::{CreateProject, Project},
+    task::{CreateTask, Task},
+};
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+use uuid::Uuid;
+
+/// Fresh in-memory database with all migrations applied
+pub async fn test_pool() -> SqlitePool {
+    // One connection, as every connection to :memory: is its own database
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+    pool
+}
+
+pub async fn create_project(pool: &SqlitePool, name: &str) -> Project {
+    let data = CreateProject {
+        name: name.to_string(),
+        git_repo_path: format!("/tmp/{name}-{}", Uuid::new_v4()),
+        use_existing_repo: true,
+        setup_script: None,
+        dev_script: None,
+        cleanup_script: None,
+        copy_files: None,
+    };
+    Project::create(pool, &data, Uuid::new_v4()).await.unwrap()
+}
+
+pub async fn create_task(pool: &SqlitePool, project_id: Uuid, title: &str) -> Task {
+    let data = CreateTask::from_title_description(project_id, title.to_string(), None);
+    Task::create(pool, &data, Uuid::new_v4()).await.unwrap()
+}
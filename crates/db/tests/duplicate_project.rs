@@ -0,0 +1,65 @@
+mod common;
+
+use db::models::{
+    project::Project,
+    project_branch_template::ProjectBranchTemplate,
+    project_merge_settings::{MergeStrategy, ProjectMergeSettings},
+    project_rebase_policy::{ProjectRebasePolicy, RebasePolicyMode},
+};
+use uuid::Uuid;
+
+use crate::common::{create_project, test_pool};
+
+#[tokio::test]
+async fn duplicate_copies_project_settings() {
+    let pool = test_pool().await;
+    let source = create_project(&pool, "source").await;
+    ProjectRebasePolicy::upsert(&pool, source.id, RebasePolicyMode::Rebase)
+        .await
+        .unwrap();
+    ProjectBranchTemplate::upsert(&pool, source.id, "feat/{slug}")
+        .await
+        .unwrap();
+    ProjectMergeSettings::upsert(
+        &pool,
+        source.id,
+        MergeStrategy::Rebase,
+        Some("{title} ({task_id})"),
+    )
+    .await
+    .unwrap();
+
+    let copy = Project::duplicate(
+        &pool,
+        &source,
+        Uuid::new_v4(),
+        "copy",
+        "/tmp/copy-of-source",
+        false,
+    )
+    .await
+    .unwrap();
+    assert_eq!(copy.name, "copy");
+
+    let policy = ProjectRebasePolicy::find_by_project_id(&pool, copy.id)
+        .await
+        .unwrap()
+        .expect("rebase policy is copied");
+    assert_eq!(policy.mode, RebasePolicyMode::Rebase);
+
+    let template = ProjectBranchTemplate::find_by_project_id(&pool, copy.id)
+        .await
+        .unwrap()
+        .expect("branch template is copied");
+    assert_eq!(template.template, "feat/{slug}");
+
+    let merge_settings = ProjectMergeSettings::find_by_project_id(&pool, copy.id)
+        .await
+        .unwrap()
+        .expect("merge settings are copied");
+    assert_eq!(merge_settings.strategy, MergeStrategy::Rebase);
+    assert_eq!(
+        merge_settings.commit_message_template.as_deref(),
+        Some("{title} ({task_id})")
+    );
+}
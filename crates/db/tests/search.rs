@@ -0,0 +1,132 @@
+mod common;
+
+use db::models::{
+    execution_process::{CreateExecutionProcess, ExecutionProcess, ExecutionProcessRunReason},
+    execution_process_logs::ExecutionProcessLogs,
+    search::{SearchIndex, SearchResult, SearchResultKind},
+    task::Task,
+    task_attempt::{CreateTaskAttempt, TaskAttempt},
+};
+use executors::{
+    actions::{
+        ExecutorAction, ExecutorActionType,
+        script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+    },
+    executors::BaseCodingAgent,
+};
+use uuid::Uuid;
+
+use crate::common::{create_project, create_task, test_pool};
+
+#[tokio::test]
+async fn renamed_tasks_are_found_by_their_new_title() {
+    let pool = test_pool().await;
+    let project = create_project(&pool, "search").await;
+    let task = create_task(&pool, project.id, "Fix flaky login").await;
+
+    let hits = SearchResult::search(&pool, "flaky", None, None, 10)
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].kind, SearchResultKind::Task);
+    assert_eq!(hits[0].task_id, task.id);
+
+    Task::update(
+        &pool,
+        task.id,
+        project.id,
+        "Repair session refresh".to_string(),
+        None,
+        task.status,
+        None,
+        None,
+        task.priority,
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(
+        SearchResult::search(&pool, "flaky", None, None, 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(
+        SearchResult::search(&pool, "refresh", None, None, 10)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn backfilled_logs_are_indexed_once() {
+    let pool = test_pool().await;
+    let project = create_project(&pool, "backfill").await;
+    let task = create_task(&pool, project.id, "Run the setup script").await;
+    let attempt = TaskAttempt::create(
+        &pool,
+        &CreateTaskAttempt {
+            executor: BaseCodingAgent::ClaudeCode,
+            base_branch: "main".to_string(),
+            branch: "vk/backfill".to_string(),
+        },
+        Uuid::new_v4(),
+        task.id,
+    )
+    .await
+    .unwrap();
+    let action = ExecutorAction::new(
+        ExecutorActionType::ScriptRequest(ScriptRequest {
+            script: "npm install".to_string(),
+            language: ScriptRequestLanguage::Bash,
+            context: ScriptContext::SetupScript,
+            timeout_seconds: None,
+            retries: 0,
+        }),
+        None,
+    );
+    let process = ExecutionProcess::create(
+        &pool,
+        &CreateExecutionProcess {
+            task_attempt_id: attempt.id,
+            executor_action: action,
+            run_reason: ExecutionProcessRunReason::SetupScript,
+        },
+        Uuid::new_v4(),
+        None,
+    )
+    .await
+    .unwrap();
+    for line in [
+        r#"{"Stdout":"added 212 packages"}"#,
+        r#"{"Stderr":"deprecated inflight"}"#,
+        r#"{"JsonPatch":[]}"#,
+    ] {
+        ExecutionProcessLogs::append_log_line(&pool, process.id, &format!("{line}\n"))
+            .await
+            .unwrap();
+    }
+    // As if the log had been stored before the index existed
+    sqlx::query("INSERT INTO search_index_log_backfill (execution_id) VALUES ($1)")
+        .bind(process.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    while SearchIndex::backfill_next_log(&pool).await.unwrap() {}
+
+    let indexed: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM search_index_entries WHERE kind = 'execution_log'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(indexed, 2);
+    let hits = SearchResult::search(&pool, "inflight", None, None, 10)
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].execution_process_id, Some(process.id));
+}
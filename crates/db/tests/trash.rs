@@ -0,0 +1,128 @@
+mod common;
+
+use chrono::{Duration, Utc};
+use db::models::{
+    project::Project,
+    task::{Task, TaskStatus},
+    task_dependency::TaskDependency,
+    task_schedule::{TaskSchedule, UpsertTaskSchedule},
+};
+use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::common::{create_project, create_task, test_pool};
+
+async fn is_blocked(pool: &SqlitePool, project_id: Uuid, task_id: Uuid) -> bool {
+    Task::find_by_project_id_with_attempt_status(pool, project_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|t| t.task.id == task_id)
+        .unwrap()
+        .is_blocked
+}
+
+#[tokio::test]
+async fn trashed_task_schedules_do_not_fire() {
+    let pool = test_pool().await;
+    let project = create_project(&pool, "scheduled").await;
+    let task = create_task(&pool, project.id, "Nightly dependency bump").await;
+    let schedule = UpsertTaskSchedule {
+        cron_expression: "0 3 * * *".to_string(),
+        executor_profile_id: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+        base_branch: "main".to_string(),
+        enabled: true,
+    };
+    let now = Utc::now();
+    TaskSchedule::upsert(&pool, task.id, &schedule, Some(now - Duration::minutes(1)))
+        .await
+        .unwrap();
+
+    let due = TaskSchedule::find_due(&pool, now).await.unwrap();
+    assert_eq!(due.len(), 1);
+
+    Task::soft_delete(&pool, task.id).await.unwrap();
+    assert!(TaskSchedule::find_due(&pool, now).await.unwrap().is_empty());
+
+    Task::restore(&pool, task.id).await.unwrap();
+    assert_eq!(TaskSchedule::find_due(&pool, now).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn trashed_blockers_do_not_block() {
+    let pool = test_pool().await;
+    let project = create_project(&pool, "blocked").await;
+    let blocker = create_task(&pool, project.id, "Design the schema").await;
+    let task = create_task(&pool, project.id, "Write the migration").await;
+    TaskDependency::create(&pool, task.id, blocker.id)
+        .await
+        .unwrap();
+
+    assert!(is_blocked(&pool, project.id, task.id).await);
+    assert_eq!(
+        TaskDependency::find_unfinished_blockers(&pool, task.id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    Task::soft_delete(&pool, blocker.id).await.unwrap();
+    assert!(!is_blocked(&pool, project.id, task.id).await);
+    assert!(
+        TaskDependency::find_unfinished_blockers(&pool, task.id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        TaskDependency::find_blockers(&pool, task.id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    Task::restore(&pool, blocker.id).await.unwrap();
+    assert!(is_blocked(&pool, project.id, task.id).await);
+}
+
+#[tokio::test]
+async fn trashed_tasks_are_not_reported_overdue() {
+    let pool = test_pool().await;
+    let project = create_project(&pool, "overdue").await;
+    let task = create_task(&pool, project.id, "Ship the release notes").await;
+    let now = Utc::now();
+    Task::update(
+        &pool,
+        task.id,
+        project.id,
+        task.title.clone(),
+        None,
+        TaskStatus::InProgress,
+        None,
+        None,
+        task.priority.clone(),
+        Some(now - Duration::hours(1)),
+    )
+    .await
+    .unwrap();
+    assert_eq!(Task::find_newly_overdue(&pool, now).await.unwrap().len(), 1);
+
+    Task::soft_delete(&pool, task.id).await.unwrap();
+    assert!(
+        Task::find_newly_overdue(&pool, now)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    Task::restore(&pool, task.id).await.unwrap();
+    Project::soft_delete(&pool, project.id).await.unwrap();
+    assert!(
+        Task::find_newly_overdue(&pool, now)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+};
 
 use anyhow::Error as AnyhowError;
 use async_trait::async_trait;
@@ -6,10 +10,16 @@ use axum::response::sse::Event;
 use db::{
     DBService,
     models::{
+        attempt_base_status::AttemptBaseStatus,
         execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+        merge::{Merge, MergeStatus},
         project::{CreateProject, Project},
+        project_rebase_policy::{ProjectRebasePolicy, RebasePolicyMode},
+        search::SearchIndex,
         task::{Task, TaskStatus},
-        task_attempt::{TaskAttempt, TaskAttemptError},
+        task_attempt::{CreateTaskAttempt, TaskAttempt, TaskAttemptError},
+        task_recurrence::TaskRecurrence,
+        task_schedule::TaskSchedule,
     },
 };
 use executors::executors::ExecutorError;
@@ -20,22 +30,30 @@ use services::services::{
     analytics::{AnalyticsContext, AnalyticsService},
     approvals::Approvals,
     auth::{AuthError, AuthService},
+    base_branch_watch::{BaseBranchAction, base_branch_action},
     config::{Config, ConfigError},
-    container::{ContainerError, ContainerService},
+    container::{ContainerError, ContainerService, WorktreeCleanupData, cleanup_worktrees_direct},
     drafts::DraftsService,
     events::{EventError, EventService},
     file_search_cache::FileSearchCache,
     filesystem::{FilesystemError, FilesystemService},
     filesystem_watcher::FilesystemWatcherError,
     git::{GitService, GitServiceError},
+    github_issues::GitHubIssueSyncService,
+    hosting,
     image::{ImageError, ImageService},
+    integrations::IntegrationSyncService,
+    notification::NotificationService,
     pr_monitor::PrMonitorService,
+    secrets::{SecretsError, SecretsService},
     worktree_manager::WorktreeError,
 };
 use sqlx::{Error as SqlxError, types::Uuid};
 use thiserror::Error;
 use tokio::sync::RwLock;
-use utils::{msg_store::MsgStore, sentry as sentry_utils};
+use utils::{
+    cron::CronSchedule, msg_store::MsgStore, recurrence::RecurrenceRule, sentry as sentry_utils,
+};
 
 #[derive(Debug, Error)]
 pub enum DeploymentError {
@@ -68,6 +86,8 @@ pub enum DeploymentError {
     #[error(transparent)]
     Config(#[from] ConfigError),
     #[error(transparent)]
+    Secrets(#[from] SecretsError),
+    #[error(transparent)]
     Other(#[from] AnyhowError),
 }
 
@@ -93,6 +113,8 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn image(&self) -> &ImageService;
 
+    fn secrets(&self) -> &SecretsService;
+
     fn filesystem(&self) -> &FilesystemService;
 
     fn msg_stores(&self) -> &Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>;
@@ -128,6 +150,552 @@ pub trait Deployment: Clone + Send + Sync + 'static {
         PrMonitorService::spawn(db, config, analytics).await
     }
 
+    /// Close the GitHub issues of imported tasks once the tasks are done
+    async fn spawn_github_issue_sync_service(&self) -> tokio::task::JoinHandle<()> {
+        GitHubIssueSyncService::spawn(self.db().clone(), self.config().clone()).await
+    }
+
+    /// Keep tasks imported from Jira or Linear in sync with their tickets
+    async fn spawn_integration_sync_service(&self) -> tokio::task::JoinHandle<()> {
+        IntegrationSyncService::spawn(self.db().clone(), self.config().clone()).await
+    }
+
+    /// Periodically start attempts for task schedules that are due
+    async fn spawn_task_scheduler(&self) -> tokio::task::JoinHandle<()> {
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            tracing::info!("Starting task scheduler");
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+            loop {
+                interval.tick().await;
+                if let Err(e) = deployment.run_due_task_schedules().await {
+                    tracing::error!("Failed to run due task schedules: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Periodically flag in-progress tasks that passed their due date. Marking
+    /// a task pushes it to the events stream; a notification is sent as well.
+    async fn spawn_overdue_task_monitor(&self) -> tokio::task::JoinHandle<()> {
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            tracing::info!("Starting overdue task monitor");
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                if let Err(e) = deployment.report_overdue_tasks().await {
+                    tracing::error!("Failed to report overdue tasks: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Periodically create the next occurrence of recurring tasks that are done
+    async fn spawn_task_recurrence_monitor(&self) -> tokio::task::JoinHandle<()> {
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            tracing::info!("Starting task recurrence monitor");
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            loop {
+                interval.tick().await;
+                if let Err(e) = deployment.create_recurring_task_occurrences().await {
+                    tracing::error!("Failed to create recurring task occurrences: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Periodically purge tasks and projects that stayed in the trash longer
+    /// than the configured retention
+    async fn spawn_trash_purge(&self) -> tokio::task::JoinHandle<()> {
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            tracing::info!("Starting trash purge");
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+            loop {
+                interval.tick().await;
+                if let Err(e) = deployment.purge_trash().await {
+                    tracing::error!("Failed to purge trash: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Index the logs stored before the search index existed, one execution
+    /// process at a time so that startup isn't held up by large logs
+    async fn spawn_search_index_backfill(&self) -> tokio::task::JoinHandle<()> {
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            let mut indexed = 0;
+            loop {
+                match SearchIndex::backfill_next_log(&deployment.db().pool).await {
+                    Ok(true) => indexed += 1,
+                    Ok(false) => break,
+                    Err(e) => {
+                        tracing::error!("Failed to backfill the search index: {}", e);
+                        break;
+                    }
+                }
+            }
+            if indexed > 0 {
+                tracing::info!(
+                    "Indexed the logs of {} execution processes for search",
+                    indexed
+                );
+            }
+        })
+    }
+
+    /// Periodically compare the attempts of projects with a rebase policy to
+    /// their base branch, and rebase or flag the ones that fell behind
+    async fn spawn_base_branch_watcher(&self) -> tokio::task::JoinHandle<()> {
+        let deployment = self.clone();
+        tokio::spawn(async move {
+            tracing::info!("Starting base branch watcher");
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(5 * 60));
+            loop {
+                interval.tick().await;
+                if let Err(e) = deployment.check_attempt_base_branches().await {
+                    tracing::error!("Failed to check attempt base branches: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Check the open attempts of every project with a rebase policy. Remote
+    /// base branches are fetched once per project.
+    async fn check_attempt_base_branches(&self) -> Result<(), DeploymentError> {
+        let pool = &self.db().pool;
+        let policies = ProjectRebasePolicy::find_active(pool).await?;
+        if policies.is_empty() {
+            return Ok(());
+        }
+        let running: HashSet<Uuid> = ExecutionProcess::find_running(pool)
+            .await?
+            .into_iter()
+            .map(|process| process.task_attempt_id)
+            .collect();
+
+        for policy in policies {
+            let Some(project) = Project::find_by_id(pool, policy.project_id).await? else {
+                continue;
+            };
+            let config = self.config().read().await.clone();
+            let token = hosting::resolve_provider(pool, self.git(), &config, &project)
+                .await
+                .ok()
+                .map(|provider| provider.token().to_string());
+            let mut fetched = HashSet::new();
+            for attempt in TaskAttempt::find_open_by_project_id(pool, project.id).await? {
+                if let Some(token) = &token
+                    && fetched.insert(attempt.target_branch.clone())
+                    && let Err(e) = self.git().fetch_remote_branch(
+                        &project.git_repo_path,
+                        &attempt.target_branch,
+                        token,
+                    )
+                {
+                    tracing::warn!(
+                        "Failed to fetch base branch {}: {}",
+                        attempt.target_branch,
+                        e
+                    );
+                }
+                let idle = !running.contains(&attempt.id) && attempt.paused_at.is_none();
+                if let Err(e) = self
+                    .check_attempt_base_branch(&project, &attempt, policy.mode, idle, token.clone())
+                    .await
+                {
+                    tracing::warn!(
+                        "Failed to check the base branch of attempt {}: {}",
+                        attempt.id,
+                        e
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Attempts with an open pull request are only flagged: rebasing them
+    /// would need a force push. A rebase that conflicts is aborted and
+    /// recorded, and not retried until the base branch moves on.
+    async fn check_attempt_base_branch(
+        &self,
+        project: &Project,
+        attempt: &TaskAttempt,
+        mode: RebasePolicyMode,
+        idle: bool,
+        token: Option<String>,
+    ) -> Result<(), DeploymentError> {
+        let pool = &self.db().pool;
+        let repo_path = &project.git_repo_path;
+        let base_commit = self
+            .git()
+            .get_branch_oid(repo_path, &attempt.target_branch)?;
+        let (_, behind) =
+            self.git()
+                .get_branch_status(repo_path, &attempt.branch, &attempt.target_branch)?;
+        let previous = AttemptBaseStatus::find_by_task_attempt_id(pool, attempt.id).await?;
+        let stale_since = previous
+            .as_ref()
+            .and_then(|status| status.stale_since)
+            .unwrap_or_else(chrono::Utc::now);
+        let rebased_at = previous.as_ref().and_then(|status| status.rebased_at);
+        // Keep why rebasing failed until the base branch moves on
+        let rebase_error = previous
+            .as_ref()
+            .filter(|status| status.base_commit == base_commit)
+            .and_then(|status| status.rebase_error.clone());
+
+        let worktree = attempt
+            .container_ref
+            .as_deref()
+            .map(PathBuf::from)
+            .filter(|path| !attempt.worktree_deleted && path.exists());
+        let has_open_pr = Merge::find_by_task_attempt_id(pool, attempt.id)
+            .await?
+            .iter()
+            .any(|merge| match merge {
+                Merge::Pr(pr) => matches!(pr.pr_info.status, MergeStatus::Open),
+                Merge::Direct(_) => false,
+            });
+        let idle = idle && worktree.is_some() && !has_open_pr;
+
+        let worktree = match (
+            base_branch_action(mode, behind, idle, previous.as_ref(), &base_commit),
+            worktree,
+        ) {
+            (BaseBranchAction::Rebase, Some(worktree)) => worktree,
+            (BaseBranchAction::UpToDate, _) => {
+                AttemptBaseStatus::upsert(
+                    pool,
+                    attempt.id,
+                    &base_commit,
+                    0,
+                    None,
+                    rebased_at,
+                    None,
+                )
+                .await?;
+                return Ok(());
+            }
+            _ => {
+                if previous
+                    .as_ref()
+                    .is_none_or(|status| status.stale_since.is_none())
+                {
+                    tracing::info!(
+                        "Attempt {} is {} commits behind {}",
+                        attempt.id,
+                        behind,
+                        attempt.target_branch
+                    );
+                }
+                AttemptBaseStatus::upsert(
+                    pool,
+                    attempt.id,
+                    &base_commit,
+                    behind as i64,
+                    Some(stale_since),
+                    rebased_at,
+                    rebase_error.as_deref(),
+                )
+                .await?;
+                return Ok(());
+            }
+        };
+
+        match self.git().rebase_branch(
+            repo_path,
+            &worktree,
+            &attempt.target_branch,
+            &attempt.target_branch,
+            &attempt.branch,
+            token,
+        ) {
+            Ok(head_commit) => {
+                tracing::info!(
+                    "Rebased attempt {} onto {} at {}",
+                    attempt.id,
+                    attempt.target_branch,
+                    head_commit
+                );
+                AttemptBaseStatus::upsert(
+                    pool,
+                    attempt.id,
+                    &base_commit,
+                    0,
+                    None,
+                    Some(chrono::Utc::now()),
+                    None,
+                )
+                .await?;
+                self.track_if_analytics_allowed(
+                    "attempt_auto_rebased",
+                    serde_json::json!({
+                        "attempt_id": attempt.id.to_string(),
+                        "project_id": project.id.to_string(),
+                        "commits_behind": behind,
+                    }),
+                )
+                .await;
+            }
+            // Someone is working in the worktree; try again on the next check
+            Err(GitServiceError::WorktreeDirty(..) | GitServiceError::RebaseInProgress) => {
+                AttemptBaseStatus::upsert(
+                    pool,
+                    attempt.id,
+                    &base_commit,
+                    behind as i64,
+                    Some(stale_since),
+                    rebased_at,
+                    None,
+                )
+                .await?;
+            }
+            Err(e) => {
+                if matches!(e, GitServiceError::MergeConflicts(_))
+                    && let Err(abort_err) = self.git().abort_conflicts(&worktree)
+                {
+                    tracing::error!(
+                        "Failed to abort the rebase of attempt {}: {}",
+                        attempt.id,
+                        abort_err
+                    );
+                }
+                tracing::warn!("Failed to rebase attempt {}: {}", attempt.id, e);
+                AttemptBaseStatus::upsert(
+                    pool,
+                    attempt.id,
+                    &base_commit,
+                    behind as i64,
+                    Some(stale_since),
+                    rebased_at,
+                    Some(&e.to_string()),
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn purge_trash(&self) -> Result<(), DeploymentError> {
+        let Some(retention_days) = self.config().read().await.trash_retention_days else {
+            return Ok(());
+        };
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(retention_days as i64);
+        let pool = &self.db().pool;
+        for project in Project::find_trashed_before(pool, cutoff).await? {
+            if let Err(e) = self.purge_project(&project).await {
+                tracing::error!("Failed to purge project {}: {}", project.id, e);
+            }
+        }
+        for task in Task::find_trashed_before(pool, cutoff).await? {
+            if let Err(e) = self.purge_task(&task).await {
+                tracing::error!("Failed to purge task {}: {}", task.id, e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Permanently delete a task with its attempts, then remove their
+    /// worktrees
+    async fn purge_task(&self, task: &Task) -> Result<(), DeploymentError> {
+        let pool = &self.db().pool;
+        if self.container().has_running_processes(task.id).await? {
+            tracing::info!("Skipping purge of task {}: an attempt is running", task.id);
+            return Ok(());
+        }
+        let attempts = TaskAttempt::find_by_task_id_with_project(pool, task.id).await?;
+
+        // Use a transaction to ensure atomicity: either all operations succeed or all are rolled back
+        let mut tx = pool.begin().await?;
+        // Nullify parent_task_attempt for all child tasks before deletion
+        // This breaks parent-child relationships to avoid foreign key constraint violations
+        for (attempt_id, _, _) in &attempts {
+            Task::nullify_children_by_attempt_id(&mut *tx, *attempt_id).await?;
+        }
+        // FK CASCADE will handle task_attempts
+        Task::delete(&mut *tx, task.id).await?;
+        tx.commit().await?;
+
+        tracing::info!("Purged task {} from the trash", task.id);
+        cleanup_worktrees_direct(&worktree_cleanup_data(attempts)).await?;
+        Ok(())
+    }
+
+    /// Permanently delete a project with its tasks, then remove the worktrees
+    /// of their attempts
+    async fn purge_project(&self, project: &Project) -> Result<(), DeploymentError> {
+        let pool = &self.db().pool;
+        let attempts = TaskAttempt::find_by_project_id_with_project(pool, project.id).await?;
+        let running = ExecutionProcess::find_running(pool).await?;
+        if running.iter().any(|process| {
+            attempts
+                .iter()
+                .any(|(id, _, _)| *id == process.task_attempt_id)
+        }) {
+            tracing::info!(
+                "Skipping purge of project {}: an attempt is running",
+                project.id
+            );
+            return Ok(());
+        }
+        Project::delete(pool, project.id).await?;
+
+        tracing::info!("Purged project {} from the trash", project.id);
+        cleanup_worktrees_direct(&worktree_cleanup_data(attempts)).await?;
+        Ok(())
+    }
+
+    async fn report_overdue_tasks(&self) -> Result<(), DeploymentError> {
+        let pool = &self.db().pool;
+        for task in Task::find_newly_overdue(pool, chrono::Utc::now()).await? {
+            tracing::info!("Task {} is overdue", task.id);
+            Task::mark_overdue_notified(pool, task.id).await?;
+            let notifications = self.config().read().await.notifications.clone();
+            NotificationService::notify(
+                notifications,
+                "Task overdue",
+                &format!("'{}' has passed its due date", task.title),
+            )
+            .await;
+        }
+        Ok(())
+    }
+
+    /// The next occurrence follows the done task's due date (or its last
+    /// update when it had none), skipping occurrences already in the past. A
+    /// rule that has ended is removed.
+    async fn create_recurring_task_occurrences(&self) -> Result<(), DeploymentError> {
+        let pool = &self.db().pool;
+        let now = chrono::Utc::now();
+        for recurrence in TaskRecurrence::find_completed(pool).await? {
+            let Some(task) = Task::find_by_id(pool, recurrence.task_id).await? else {
+                continue;
+            };
+            let rule = match RecurrenceRule::parse(&recurrence.rule) {
+                Ok(rule) => rule,
+                Err(e) => {
+                    tracing::error!("Task {} has an invalid recurrence: {}", task.id, e);
+                    TaskRecurrence::delete_by_task_id(pool, task.id).await?;
+                    continue;
+                }
+            };
+            let exhausted = rule
+                .count()
+                .is_some_and(|count| recurrence.occurrence >= i64::from(count));
+            let mut next_due = rule.next_after(task.due_date.unwrap_or(task.updated_at));
+            while let Some(due) = next_due.filter(|due| *due <= now) {
+                next_due = rule.next_after(due);
+            }
+
+            match next_due.filter(|_| !exhausted) {
+                Some(due_date) => {
+                    let task_id =
+                        TaskRecurrence::create_next_occurrence(pool, &recurrence, due_date).await?;
+                    tracing::info!(
+                        "Created occurrence {} of recurring task {} as {}",
+                        recurrence.occurrence + 1,
+                        task.id,
+                        task_id
+                    );
+                }
+                None => {
+                    tracing::info!("Recurrence of task {} has ended", task.id);
+                    TaskRecurrence::delete_by_task_id(pool, task.id).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn run_due_task_schedules(&self) -> Result<(), DeploymentError> {
+        let pool = &self.db().pool;
+        let now = chrono::Utc::now();
+        for schedule in TaskSchedule::find_due(pool, now).await? {
+            let next_run_at = CronSchedule::parse(&schedule.cron_expression)
+                .ok()
+                .and_then(|cron| cron.next_after(now));
+
+            let task_attempt_id = match self.start_scheduled_attempt(&schedule).await {
+                Ok(attempt_id) => attempt_id,
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to start scheduled attempt for task {}: {}",
+                        schedule.task_id,
+                        e
+                    );
+                    None
+                }
+            };
+            TaskSchedule::record_run(pool, schedule.id, now, task_attempt_id, next_run_at).await?;
+        }
+        Ok(())
+    }
+
+    /// Create and start (or queue) an attempt for a due schedule. Runs are
+    /// skipped while an earlier attempt of the task is still executing, and
+    /// for tasks in the trash.
+    async fn start_scheduled_attempt(
+        &self,
+        schedule: &TaskSchedule,
+    ) -> Result<Option<Uuid>, DeploymentError> {
+        let pool = &self.db().pool;
+        let Some(task) = Task::find_by_id(pool, schedule.task_id).await? else {
+            return Ok(None);
+        };
+        // Trashed tasks keep their schedule for a restore but don't run
+        if Task::is_trashed(pool, task.id).await? {
+            return Ok(None);
+        }
+        if self.container().has_running_processes(task.id).await? {
+            tracing::info!(
+                "Skipping scheduled run for task {}: an attempt is still running",
+                task.id
+            );
+            return Ok(None);
+        }
+
+        let executor_profile_id = schedule.executor_profile_id.0.clone();
+        let attempt_id = Uuid::new_v4();
+        let git_branch_name = self
+            .container()
+            .git_branch_from_task_attempt(&attempt_id, &task, &executor_profile_id.executor)
+            .await?;
+        let task_attempt = TaskAttempt::create(
+            pool,
+            &CreateTaskAttempt {
+                executor: executor_profile_id.executor,
+                base_branch: schedule.base_branch.clone(),
+                branch: git_branch_name,
+            },
+            attempt_id,
+            task.id,
+        )
+        .await?;
+
+        self.container()
+            .queue_or_start_attempt(&task_attempt, executor_profile_id.clone())
+            .await?;
+
+        self.track_if_analytics_allowed(
+            "task_attempt_started",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "variant": &executor_profile_id.variant,
+                "executor": &executor_profile_id.executor,
+                "attempt_id": task_attempt.id.to_string(),
+                "trigger": "schedule",
+            }),
+        )
+        .await;
+
+        Ok(Some(task_attempt.id))
+    }
+
     async fn track_if_analytics_allowed(&self, event_name: &str, properties: Value) {
         let analytics_enabled = self.config().read().await.analytics_enabled;
         // Only skip tracking if user explicitly opted out (Some(false))
@@ -139,7 +707,10 @@ pub trait Deployment: Clone + Send + Sync + 'static {
         }
     }
 
-    /// Cleanup executions marked as running in the db, call at startup
+    /// Cleanup executions marked as running in the db, call at startup.
+    /// Their processes are stopped, and coding agent runs that recorded an
+    /// agent session continue it in a new, queued run instead of failing the
+    /// attempt.
     async fn cleanup_orphan_executions(&self) -> Result<(), DeploymentError> {
         let running_processes = ExecutionProcess::find_running(&self.db().pool).await?;
         for process in running_processes {
@@ -148,6 +719,13 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                 process.id,
                 process.task_attempt_id
             );
+            if let Err(e) = self.container().stop_orphaned_process(process.id).await {
+                tracing::warn!(
+                    "Failed to stop process of orphaned execution {}: {}",
+                    process.id,
+                    e
+                );
+            }
             // Update the execution process status first
             if let Err(e) = ExecutionProcess::update_completion(
                 &self.db().pool,
@@ -181,14 +759,32 @@ pub trait Deployment: Clone + Send + Sync + 'static {
             }
             // Process marked as failed
             tracing::info!("Marked orphaned execution process {} as failed", process.id);
+            let resumed = process.run_reason == ExecutionProcessRunReason::CodingAgent
+                && match self
+                    .container()
+                    .resume_interrupted_execution(&process)
+                    .await
+                {
+                    Ok(resumed) => resumed,
+                    Err(e) => {
+                        tracing::error!(
+                            "Failed to resume orphaned execution process {}: {}",
+                            process.id,
+                            e
+                        );
+                        false
+                    }
+                };
             // Update task status to InReview for coding agent and setup script failures
-            if matches!(
-                process.run_reason,
-                ExecutionProcessRunReason::CodingAgent
-                    | ExecutionProcessRunReason::SetupScript
-                    | ExecutionProcessRunReason::CleanupScript
-            ) && let Ok(Some(task_attempt)) =
-                TaskAttempt::find_by_id(&self.db().pool, process.task_attempt_id).await
+            if !resumed
+                && matches!(
+                    process.run_reason,
+                    ExecutionProcessRunReason::CodingAgent
+                        | ExecutionProcessRunReason::SetupScript
+                        | ExecutionProcessRunReason::CleanupScript
+                )
+                && let Ok(Some(task_attempt)) =
+                    TaskAttempt::find_by_id(&self.db().pool, process.task_attempt_id).await
                 && let Ok(Some(task)) = task_attempt.parent_task(&self.db().pool).await
                 && let Err(e) =
                     Task::update_status(&self.db().pool, task.id, TaskStatus::InReview).await
@@ -199,6 +795,8 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                 );
             }
         }
+        // Start the resumptions that were queued behind the concurrency limits
+        self.container().dispatch_queued_attempts().await?;
         Ok(())
     }
 
@@ -332,3 +930,18 @@ pub trait Deployment: Clone + Send + Sync + 'static {
             .boxed()
     }
 }
+
+fn worktree_cleanup_data(
+    attempts: Vec<(Uuid, Option<String>, String)>,
+) -> Vec<WorktreeCleanupData> {
+    attempts
+        .into_iter()
+        .filter_map(|(attempt_id, container_ref, git_repo_path)| {
+            container_ref.map(|worktree_path| WorktreeCleanupData {
+                attempt_id,
+                worktree_path: PathBuf::from(worktree_path),
+                git_repo_path: Some(PathBuf::from(git_repo_path)),
+            })
+        })
+        .collect()
+}
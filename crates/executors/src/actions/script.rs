@@ -10,6 +10,7 @@ use workspace_utils::shell::get_shell_command;
 use crate::{
     actions::Executable,
     approvals::ExecutorApprovalService,
+    env::ExecutionEnv,
     executors::{ExecutorError, SpawnedChild},
 };
 
@@ -22,6 +23,7 @@ pub enum ScriptRequestLanguage {
 pub enum ScriptContext {
     SetupScript,
     CleanupScript,
+    TestScript,
     DevServer,
 }
 
@@ -30,6 +32,13 @@ pub struct ScriptRequest {
     pub script: String,
     pub language: ScriptRequestLanguage,
     pub context: ScriptContext,
+    /// Cancel the script after this many seconds instead of the attempt's
+    /// execution timeout
+    #[serde(default)]
+    pub timeout_seconds: Option<u32>,
+    /// How many more times to run the script if it fails
+    #[serde(default)]
+    pub retries: u32,
 }
 
 #[async_trait]
@@ -38,6 +47,7 @@ impl Executable for ScriptRequest {
         &self,
         current_dir: &Path,
         _approvals: Arc<dyn ExecutorApprovalService>,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         let (shell_cmd, shell_arg) = get_shell_command();
         let mut command = Command::new(shell_cmd);
@@ -49,6 +59,7 @@ impl Executable for ScriptRequest {
             .arg(shell_arg)
             .arg(&self.script)
             .current_dir(current_dir);
+        env.apply(&mut command);
 
         let child = command.group_spawn()?;
 
@@ -29,6 +29,29 @@ impl CommandParts {
         Self { program, args }
     }
 
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// Append arguments as they are, without the splitting a builder's
+    /// params go through
+    pub fn extend_args(mut self, more: impl IntoIterator<Item = String>) -> Self {
+        self.args.extend(more);
+        self
+    }
+
+    /// Rewrite each argument, e.g. to substitute placeholders
+    pub fn map_args(self, f: impl FnMut(String) -> String) -> Self {
+        Self {
+            program: self.program,
+            args: self.args.into_iter().map(f).collect(),
+        }
+    }
+
+    pub fn into_parts(self) -> (String, Vec<String>) {
+        (self.program, self.args)
+    }
+
     pub async fn into_resolved(self) -> Result<(PathBuf, Vec<String>), ExecutorError> {
         let CommandParts { program, args } = self;
         let executable = resolve_executable_path(&program)
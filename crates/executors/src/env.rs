@@ -0,0 +1,163 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+
+use tokio::process::Command;
+
+use crate::{
+    command::CommandParts, executors::ExecutorError, mcp_config::McpServerConfig, sandbox::Sandbox,
+};
+
+/// Hooks git runs before anything that would change the branch or its
+/// remote
+const READ_ONLY_HOOKS: &[&str] = &[
+    "pre-commit",
+    "pre-merge-commit",
+    "pre-rebase",
+    "pre-applypatch",
+    "pre-push",
+];
+
+/// Extra environment variables for the processes of one execution, such as a
+/// project's secrets. They are passed at spawn time and never stored with the
+/// executor action.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionEnv {
+    vars: HashMap<String, String>,
+    sandbox: Option<Sandbox>,
+    mcp_servers: BTreeMap<String, McpServerConfig>,
+    read_only: bool,
+}
+
+impl ExecutionEnv {
+    pub fn new(vars: HashMap<String, String>) -> Self {
+        Self {
+            vars,
+            sandbox: None,
+            mcp_servers: BTreeMap::new(),
+            read_only: false,
+        }
+    }
+
+    /// Run the execution's coding agent in `sandbox` rather than on the host
+    pub fn with_sandbox(mut self, sandbox: Sandbox) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
+
+    pub fn sandbox(&self) -> Option<&Sandbox> {
+        self.sandbox.as_ref()
+    }
+
+    /// MCP servers the coding agent gets in addition to its own config, by
+    /// name
+    pub fn with_mcp_servers(mut self, servers: BTreeMap<String, McpServerConfig>) -> Self {
+        self.mcp_servers = servers;
+        self
+    }
+
+    pub fn mcp_servers(&self) -> &BTreeMap<String, McpServerConfig> {
+        &self.mcp_servers
+    }
+
+    /// Keep the coding agent from changing the attempt. Git runs the hooks
+    /// written by [`write_read_only_hooks`] to `hooks_dir` instead of the
+    /// repository's, which reject commits, merges, rebases and pushes, and a
+    /// sandbox mounts the worktree and repository read-only.
+    pub fn with_read_only(mut self, hooks_dir: &Path) -> Self {
+        self.push_git_config("core.hooksPath", &hooks_dir.to_string_lossy());
+        self.read_only = true;
+        self
+    }
+
+    /// Set git config for every git command of the execution, e.g. to sign
+    /// the commits the coding agent makes
+    pub fn with_git_config(mut self, config: &[(String, String)]) -> Self {
+        for (key, value) in config {
+            self.push_git_config(key, value);
+        }
+        self
+    }
+
+    /// Applies to every git command of the execution without touching the
+    /// repository's config. Entries are appended after the ones already set,
+    /// including any from the project's variables.
+    fn push_git_config(&mut self, key: &str, value: &str) {
+        let index = self
+            .vars
+            .get("GIT_CONFIG_COUNT")
+            .and_then(|count| count.parse::<usize>().ok())
+            .unwrap_or(0);
+        self.vars
+            .insert(format!("GIT_CONFIG_KEY_{index}"), key.to_string());
+        self.vars
+            .insert(format!("GIT_CONFIG_VALUE_{index}"), value.to_string());
+        self.vars
+            .insert("GIT_CONFIG_COUNT".to_string(), (index + 1).to_string());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+
+    /// Names of the variables, e.g. to forward them into a container
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.vars.keys().map(String::as_str)
+    }
+
+    /// Set the variables on a command before it is spawned
+    pub fn apply(&self, command: &mut Command) {
+        command.envs(&self.vars);
+    }
+
+    /// Program and arguments to spawn `parts` with in `current_dir`. In a
+    /// sandbox that is a `docker run` of the command, which receives the
+    /// variables set by [`Self::apply`]; variables an executor sets itself
+    /// stay with the docker client.
+    pub async fn resolve(
+        &self,
+        parts: CommandParts,
+        current_dir: &Path,
+    ) -> Result<(PathBuf, Vec<String>), ExecutorError> {
+        self.resolve_with_mounts(parts, current_dir, &[]).await
+    }
+
+    /// Like [`Self::resolve`], for commands that also use host directories
+    /// outside the worktree
+    pub async fn resolve_with_mounts(
+        &self,
+        parts: CommandParts,
+        current_dir: &Path,
+        mounts: &[&Path],
+    ) -> Result<(PathBuf, Vec<String>), ExecutorError> {
+        match &self.sandbox {
+            Some(sandbox) => {
+                sandbox
+                    .wrap(parts, current_dir, self.names(), mounts, self.read_only)
+                    .await
+            }
+            None => parts.into_resolved().await,
+        }
+    }
+}
+
+/// Write the hooks of read-only executions to `dir`. Each prints why it
+/// refuses and fails, which makes git abort.
+pub async fn write_read_only_hooks(dir: &Path) -> std::io::Result<()> {
+    tokio::fs::create_dir_all(dir).await?;
+    for hook in READ_ONLY_HOOKS {
+        let path = dir.join(hook);
+        tokio::fs::write(
+            &path,
+            "#!/bin/sh\necho \"This attempt is read-only: answer without changing the repository.\" >&2\nexit 1\n",
+        )
+        .await?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            tokio::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).await?;
+        }
+    }
+    Ok(())
+}
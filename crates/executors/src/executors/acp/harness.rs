@@ -19,6 +19,7 @@ use workspace_utils::stream_lines::LinesStreamExt;
 use super::{AcpClient, SessionManager};
 use crate::{
     command::CommandParts,
+    env::ExecutionEnv,
     executors::{ExecutorError, SpawnedChild, acp::AcpEvent},
 };
 
@@ -54,8 +55,9 @@ impl AcpAgentHarness {
         current_dir: &Path,
         prompt: String,
         command_parts: CommandParts,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let (program_path, args) = command_parts.into_resolved().await?;
+        let (program_path, args) = env.resolve(command_parts, current_dir).await?;
         let mut command = Command::new(program_path);
         command
             .kill_on_drop(true)
@@ -66,6 +68,8 @@ impl AcpAgentHarness {
             .args(&args)
             .env("NODE_NO_WARNINGS", "1");
 
+        env.apply(&mut command);
+
         let mut child = command.group_spawn()?;
 
         let (exit_tx, exit_rx) = tokio::sync::oneshot::channel::<()>();
@@ -76,6 +80,7 @@ impl AcpAgentHarness {
             prompt,
             Some(exit_tx),
             self.session_namespace.clone(),
+            acp_mcp_servers(env),
         )
         .await?;
 
@@ -91,8 +96,9 @@ impl AcpAgentHarness {
         prompt: String,
         session_id: &str,
         command_parts: CommandParts,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let (program_path, args) = command_parts.into_resolved().await?;
+        let (program_path, args) = env.resolve(command_parts, current_dir).await?;
         let mut command = Command::new(program_path);
         command
             .kill_on_drop(true)
@@ -103,6 +109,8 @@ impl AcpAgentHarness {
             .args(&args)
             .env("NODE_NO_WARNINGS", "1");
 
+        env.apply(&mut command);
+
         let mut child = command.group_spawn()?;
 
         let (exit_tx, exit_rx) = tokio::sync::oneshot::channel::<()>();
@@ -113,6 +121,7 @@ impl AcpAgentHarness {
             prompt,
             Some(exit_tx),
             self.session_namespace.clone(),
+            acp_mcp_servers(env),
         )
         .await?;
 
@@ -129,6 +138,7 @@ impl AcpAgentHarness {
         prompt: String,
         exit_signal: Option<tokio::sync::oneshot::Sender<()>>,
         session_namespace: String,
+        mcp_servers: Vec<proto::McpServer>,
     ) -> Result<(), ExecutorError> {
         // Take child's stdio for ACP wiring
         let orig_stdout = child.inner().stdout.take().ok_or_else(|| {
@@ -289,7 +299,7 @@ impl AcpAgentHarness {
 
                                 match conn
                                     .new_session(proto::NewSessionRequest {
-                                        mcp_servers: vec![],
+                                        mcp_servers,
                                         cwd: cwd.clone(),
                                         meta,
                                     })
@@ -310,7 +320,7 @@ impl AcpAgentHarness {
                                 // New session
                                 match conn
                                     .new_session(proto::NewSessionRequest {
-                                        mcp_servers: vec![],
+                                        mcp_servers,
                                         cwd: cwd.clone(),
                                         meta: None,
                                     })
@@ -411,3 +421,26 @@ impl AcpAgentHarness {
         Ok(())
     }
 }
+
+/// The execution's MCP servers as ACP session parameters
+fn acp_mcp_servers(env: &ExecutionEnv) -> Vec<proto::McpServer> {
+    env.mcp_servers()
+        .iter()
+        .filter_map(|(name, server)| {
+            let env: Vec<_> = server
+                .env
+                .iter()
+                .map(|(name, value)| serde_json::json!({ "name": name, "value": value }))
+                .collect();
+            let value = serde_json::json!({
+                "name": name,
+                "command": server.command,
+                "args": server.args,
+                "env": env,
+            });
+            serde_json::from_value(value)
+                .inspect_err(|e| tracing::warn!("Skipping MCP server {name}: {e}"))
+                .ok()
+        })
+        .collect()
+}
@@ -0,0 +1,352 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use command_group::AsyncCommandGroup;
+use futures::StreamExt;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::{fs, process::Command};
+use ts_rs::TS;
+use uuid::Uuid;
+use workspace_utils::{
+    msg_store::MsgStore, path::get_vibe_kanban_temp_dir, shell::resolve_executable_path,
+};
+
+use crate::{
+    command::{CmdOverrides, CommandBuilder, apply_overrides},
+    env::ExecutionEnv,
+    executors::{AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor},
+    logs::{
+        ActionType, NormalizedEntry, NormalizedEntryError, NormalizedEntryType, ToolStatus,
+        plain_text_processor::PlainTextLogProcessor,
+        stderr_processor::normalize_stderr_logs,
+        utils::{ConversationPatch, EntryIndexProvider},
+    },
+    stdout_dup,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+pub struct Aider {
+    #[serde(default)]
+    pub append_prompt: AppendPrompt,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Main model, e.g. sonnet, gpt-4o or openrouter/<model>")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Let the main model plan changes and an editor model apply them")]
+    pub architect: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "diff, whole, udiff, ...")]
+    pub edit_format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Let Aider commit its own edits to the attempt branch")]
+    pub auto_commits: Option<bool>,
+    #[serde(flatten)]
+    pub cmd: CmdOverrides,
+}
+
+impl Aider {
+    pub fn base_command() -> &'static str {
+        "aider"
+    }
+
+    fn build_command_builder(&self) -> CommandBuilder {
+        let mut builder = CommandBuilder::new(Self::base_command()).params([
+            "--yes-always",
+            "--no-pretty",
+            "--no-fancy-input",
+            "--no-check-update",
+            "--no-show-release-notes",
+            "--no-gitignore",
+            "--analytics-disable",
+        ]);
+
+        if !self.auto_commits.unwrap_or(false) {
+            builder = builder.extend_params(["--no-auto-commits", "--no-dirty-commits"]);
+        }
+
+        if let Some(model) = &self.model {
+            builder = builder.extend_params(["--model", model]);
+        }
+
+        if self.architect.unwrap_or(false) {
+            builder = builder.extend_params(["--architect"]);
+        }
+
+        if let Some(edit_format) = &self.edit_format {
+            builder = builder.extend_params(["--edit-format", edit_format]);
+        }
+
+        apply_overrides(builder, &self.cmd)
+    }
+
+    async fn spawn_with_session(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        session_id: &str,
+        resume: bool,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let prompt_file = Self::write_prompt_file(session_id, &combined_prompt).await?;
+
+        let builder = self.build_command_builder();
+        let command_parts = if resume {
+            builder.build_follow_up(&["--restore-chat-history".to_string()])?
+        } else {
+            builder.build_initial()?
+        };
+        let (program_path, args) = env.resolve(command_parts, current_dir).await?;
+
+        let mut command = Command::new(program_path);
+        command
+            .kill_on_drop(true)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(current_dir)
+            .args(&args)
+            // Paths are passed as separate arguments so they need no quoting
+            .arg("--chat-history-file")
+            .arg(Self::chat_history_file(session_id))
+            .arg("--message-file")
+            .arg(&prompt_file)
+            .env("NO_COLOR", "1");
+
+        env.apply(&mut command);
+
+        let mut child = command.group_spawn()?;
+
+        let (_, appender) = stdout_dup::tee_stdout_with_appender(&mut child)?;
+        appender.append_line(format!("{}{}\n", Self::SESSION_PREFIX, session_id));
+
+        Ok(child.into())
+    }
+}
+
+#[async_trait]
+impl StandardCodingAgentExecutor for Aider {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let session_id = Uuid::new_v4().to_string();
+        self.spawn_with_session(current_dir, prompt, &session_id, false, env)
+            .await
+    }
+
+    async fn spawn_follow_up(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        session_id: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        self.spawn_with_session(current_dir, prompt, session_id, true, env)
+            .await
+    }
+
+    /// Parses Aider's plain text output. Edits it applied become file edit tool
+    /// uses, its status lines system messages, and everything else assistant
+    /// messages.
+    fn normalize_logs(&self, msg_store: Arc<MsgStore>, _worktree_path: &Path) {
+        let entry_index_counter = EntryIndexProvider::start_from(&msg_store);
+        normalize_stderr_logs(msg_store.clone(), entry_index_counter.clone());
+
+        tokio::spawn(async move {
+            let mut stdout_lines = msg_store.stdout_lines_stream();
+            let mut processor = Self::create_assistant_normalizer(entry_index_counter.clone());
+            let mut in_message = false;
+
+            while let Some(Ok(line)) = stdout_lines.next().await {
+                if let Some(session_id) = line.strip_prefix(Self::SESSION_PREFIX) {
+                    msg_store.push_session_id(session_id.trim().to_string());
+                    continue;
+                }
+
+                let line = strip_ansi_escapes::strip_str(&line);
+                match AiderLine::parse(&line) {
+                    // Blank lines only matter within a message
+                    AiderLine::Blank if !in_message => {}
+                    AiderLine::Text | AiderLine::Blank => {
+                        in_message = true;
+                        for patch in processor.process(line + "\n") {
+                            msg_store.push_patch(patch);
+                        }
+                    }
+                    parsed => {
+                        // Close the assistant message so later text starts a new one
+                        processor = Self::create_assistant_normalizer(entry_index_counter.clone());
+                        in_message = false;
+                        if let Some(entry) = parsed.into_entry(line.trim()) {
+                            let index = entry_index_counter.next();
+                            msg_store
+                                .push_patch(ConversationPatch::add_normalized_entry(index, entry));
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    // Aider does not support MCP servers
+    fn default_mcp_config_path(&self) -> Option<std::path::PathBuf> {
+        None
+    }
+
+    async fn check_availability(&self) -> bool {
+        resolve_executable_path(Self::base_command())
+            .await
+            .is_some()
+    }
+}
+
+impl Aider {
+    const SESSION_PREFIX: &'static str = "[aider-session] ";
+
+    fn create_assistant_normalizer(index_provider: EntryIndexProvider) -> PlainTextLogProcessor {
+        PlainTextLogProcessor::builder()
+            .normalized_entry_producer(Box::new(|content: String| NormalizedEntry {
+                timestamp: None,
+                entry_type: NormalizedEntryType::AssistantMessage,
+                content,
+                metadata: None,
+            }))
+            .index_provider(index_provider)
+            .build()
+    }
+
+    fn sessions_dir() -> PathBuf {
+        get_vibe_kanban_temp_dir().join("aider_sessions")
+    }
+
+    /// Aider restores a conversation from its markdown chat history, so a
+    /// session is a history file kept outside the worktree
+    fn chat_history_file(session_id: &str) -> PathBuf {
+        Self::sessions_dir().join(format!("{session_id}.chat.md"))
+    }
+
+    async fn write_prompt_file(session_id: &str, prompt: &str) -> Result<PathBuf, ExecutorError> {
+        let dir = Self::sessions_dir();
+        fs::create_dir_all(&dir).await.map_err(ExecutorError::Io)?;
+        let path = dir.join(format!("{session_id}.prompt.md"));
+        fs::write(&path, prompt).await.map_err(ExecutorError::Io)?;
+        Ok(path)
+    }
+}
+
+/// A line of Aider output, classified
+#[derive(Debug, PartialEq)]
+enum AiderLine {
+    /// Part of the model's response
+    Text,
+    Blank,
+    /// An edit Aider wrote to a file
+    AppliedEdit {
+        path: String,
+    },
+    /// Startup banner, token usage and other status lines
+    Status,
+    Error,
+}
+
+impl AiderLine {
+    const STATUS_PREFIXES: [&'static str; 12] = [
+        "Aider v",
+        "Main model:",
+        "Weak model:",
+        "Editor model:",
+        "Model:",
+        "Git repo:",
+        "Repo-map:",
+        "Added ",
+        "Restored previous conversation history",
+        "Tokens:",
+        "Cost:",
+        "Commit ",
+    ];
+
+    fn parse(line: &str) -> Self {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            return Self::Blank;
+        }
+        if let Some(path) = trimmed.strip_prefix("Applied edit to ") {
+            return Self::AppliedEdit {
+                path: path.to_string(),
+            };
+        }
+        if trimmed.starts_with("litellm.") || trimmed.starts_with("Error:") {
+            return Self::Error;
+        }
+        if Self::STATUS_PREFIXES
+            .iter()
+            .any(|prefix| trimmed.starts_with(prefix))
+        {
+            return Self::Status;
+        }
+        Self::Text
+    }
+
+    fn into_entry(self, line: &str) -> Option<NormalizedEntry> {
+        let entry_type = match self {
+            Self::Text | Self::Blank => return None,
+            Self::AppliedEdit { path } => NormalizedEntryType::ToolUse {
+                tool_name: "edit".to_string(),
+                action_type: ActionType::FileEdit {
+                    path,
+                    changes: vec![],
+                },
+                status: ToolStatus::Success,
+            },
+            Self::Status => NormalizedEntryType::SystemMessage,
+            Self::Error => NormalizedEntryType::ErrorMessage {
+                error_type: NormalizedEntryError::Other,
+            },
+        };
+        Some(NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content: line.to_string(),
+            metadata: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_aider_output_lines() {
+        assert_eq!(AiderLine::parse("Aider v0.86.1"), AiderLine::Status);
+        assert_eq!(
+            AiderLine::parse(
+                "Tokens: 4.2k sent, 310 received. Cost: $0.02 message, $0.02 session."
+            ),
+            AiderLine::Status
+        );
+        assert_eq!(
+            AiderLine::parse("Applied edit to src/main.rs"),
+            AiderLine::AppliedEdit {
+                path: "src/main.rs".to_string()
+            }
+        );
+        assert_eq!(
+            AiderLine::parse("litellm.AuthenticationError: invalid api key"),
+            AiderLine::Error
+        );
+        assert_eq!(AiderLine::parse("   "), AiderLine::Blank);
+        assert_eq!(
+            AiderLine::parse("I'll add the flag to the parser."),
+            AiderLine::Text
+        );
+    }
+}
@@ -15,7 +15,7 @@ use ts_rs::TS;
 use workspace_utils::{
     approvals::ApprovalStatus,
     diff::{concatenate_diff_hunks, create_unified_diff, create_unified_diff_hunk},
-    log_msg::LogMsg,
+    log_msg::{LogMsg, TokenUsage},
     msg_store::MsgStore,
     path::make_path_relative,
 };
@@ -24,6 +24,7 @@ use self::{client::ClaudeAgentClient, protocol::ProtocolPeer, types::PermissionM
 use crate::{
     approvals::ExecutorApprovalService,
     command::{CmdOverrides, CommandBuilder, CommandParts, apply_overrides},
+    env::ExecutionEnv,
     executors::{
         AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
         codex::client::LogWriter,
@@ -113,6 +114,20 @@ impl ClaudeCode {
         apply_overrides(builder, &self.cmd)
     }
 
+    pub fn validate(&self) -> Result<(), String> {
+        let plan = self.plan.unwrap_or(false);
+        let approvals = self.approvals.unwrap_or(false);
+        if plan && approvals {
+            return Err("plan and approvals cannot both be enabled".to_string());
+        }
+        if (plan || approvals) && self.dangerously_skip_permissions.unwrap_or(false) {
+            return Err(
+                "dangerously_skip_permissions would bypass plan and approval prompts".to_string(),
+            );
+        }
+        Ok(())
+    }
+
     pub fn permission_mode(&self) -> PermissionMode {
         if self.plan.unwrap_or(false) {
             PermissionMode::Plan
@@ -154,10 +169,17 @@ impl StandardCodingAgentExecutor for ClaudeCode {
         self.approvals_service = Some(approvals);
     }
 
-    async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
         let command_builder = self.build_command_builder().await;
-        let command_parts = command_builder.build_initial()?;
-        self.spawn_internal(current_dir, prompt, command_parts)
+        let command_parts = command_builder
+            .build_initial()?
+            .extend_args(Self::mcp_config_args(env)?);
+        self.spawn_internal(current_dir, prompt, command_parts, env)
             .await
     }
 
@@ -166,14 +188,17 @@ impl StandardCodingAgentExecutor for ClaudeCode {
         current_dir: &Path,
         prompt: &str,
         session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         let command_builder = self.build_command_builder().await;
-        let command_parts = command_builder.build_follow_up(&[
-            "--fork-session".to_string(),
-            "--resume".to_string(),
-            session_id.to_string(),
-        ])?;
-        self.spawn_internal(current_dir, prompt, command_parts)
+        let command_parts = command_builder
+            .build_follow_up(&[
+                "--fork-session".to_string(),
+                "--resume".to_string(),
+                session_id.to_string(),
+            ])?
+            .extend_args(Self::mcp_config_args(env)?);
+        self.spawn_internal(current_dir, prompt, command_parts, env)
             .await
     }
 
@@ -199,13 +224,27 @@ impl StandardCodingAgentExecutor for ClaudeCode {
 }
 
 impl ClaudeCode {
+    /// `--mcp-config` with the execution's MCP servers, given inline in the
+    /// form of `.mcp.json`
+    fn mcp_config_args(env: &ExecutionEnv) -> Result<Vec<String>, ExecutorError> {
+        if env.mcp_servers().is_empty() {
+            return Ok(vec![]);
+        }
+        let config = serde_json::json!({ "mcpServers": env.mcp_servers() });
+        Ok(vec![
+            "--mcp-config".to_string(),
+            serde_json::to_string(&config)?,
+        ])
+    }
+
     async fn spawn_internal(
         &self,
         current_dir: &Path,
         prompt: &str,
         command_parts: CommandParts,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let (program_path, args) = command_parts.into_resolved().await?;
+        let (program_path, args) = env.resolve(command_parts, current_dir).await?;
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
         let mut command = Command::new(program_path);
@@ -217,6 +256,8 @@ impl ClaudeCode {
             .current_dir(current_dir)
             .args(&args);
 
+        env.apply(&mut command);
+
         let mut child = command.group_spawn()?;
         let child_stdout = child.inner().stdout.take().ok_or_else(|| {
             ExecutorError::Io(std::io::Error::other("Claude Code missing stdout"))
@@ -320,7 +361,10 @@ impl ClaudeLogProcessor {
             while let Some(Ok(msg)) = stream.next().await {
                 let chunk = match msg {
                     LogMsg::Stdout(x) => x,
-                    LogMsg::JsonPatch(_) | LogMsg::SessionId(_) | LogMsg::Stderr(_) => continue,
+                    LogMsg::JsonPatch(_)
+                    | LogMsg::SessionId(_)
+                    | LogMsg::Usage(_)
+                    | LogMsg::Stderr(_) => continue,
                     LogMsg::Finished => break,
                 };
 
@@ -364,6 +408,10 @@ impl ClaudeLogProcessor {
                             for patch in patches {
                                 msg_store.push_patch(patch);
                             }
+
+                            if let Some(usage) = processor.extract_usage(&claude_json) {
+                                msg_store.push_usage(usage);
+                            }
                         }
                         Err(_) => {
                             // Handle non-JSON output as raw system message
@@ -419,6 +467,30 @@ impl ClaudeLogProcessor {
         }
     }
 
+    /// Extract token usage and cost from the final result message of a run
+    fn extract_usage(&self, claude_json: &ClaudeJson) -> Option<TokenUsage> {
+        let ClaudeJson::Result {
+            usage,
+            total_cost_usd,
+            ..
+        } = claude_json
+        else {
+            return None;
+        };
+        if usage.is_none() && total_cost_usd.is_none() {
+            return None;
+        }
+        let usage = usage.clone().unwrap_or_default();
+        Some(TokenUsage {
+            input_tokens: usage.input_tokens.unwrap_or(0),
+            output_tokens: usage.output_tokens.unwrap_or(0),
+            cache_read_input_tokens: usage.cache_read_input_tokens.unwrap_or(0),
+            cache_creation_input_tokens: usage.cache_creation_input_tokens.unwrap_or(0),
+            cost_usd: *total_cost_usd,
+            model: self.model_name.clone(),
+        })
+    }
+
     /// Generate warning entry if API key source is ANTHROPIC_API_KEY
     fn warn_if_unmanaged_key(src: &Option<String>) -> Option<NormalizedEntry> {
         match src.as_deref() {
@@ -1460,6 +1532,10 @@ pub enum ClaudeJson {
         num_turns: Option<u32>,
         #[serde(default, alias = "sessionId")]
         session_id: Option<String>,
+        #[serde(default, alias = "cost_usd")]
+        total_cost_usd: Option<f64>,
+        #[serde(default)]
+        usage: Option<ClaudeUsage>,
     },
     #[serde(rename = "approval_response")]
     ApprovalResponse {
@@ -1861,6 +1937,29 @@ mod tests {
         assert_eq!(entries.len(), 0); // Should be ignored like in old implementation
     }
 
+    #[test]
+    fn test_result_message_usage_extraction() {
+        let mut processor = ClaudeLogProcessor::new();
+        let assistant_json = r#"{"type":"assistant","message":{"role":"assistant","model":"claude-sonnet-4-20250514","content":[{"type":"text","text":"Done"}]}}"#;
+        let parsed: ClaudeJson = serde_json::from_str(assistant_json).unwrap();
+        normalize_helper(&mut processor, &parsed, "");
+        assert_eq!(processor.extract_usage(&parsed), None);
+
+        let result_json = r#"{"type":"result","subtype":"success","is_error":false,"total_cost_usd":0.0421,"usage":{"input_tokens":12,"output_tokens":340,"cache_creation_input_tokens":2048,"cache_read_input_tokens":9000}}"#;
+        let parsed: ClaudeJson = serde_json::from_str(result_json).unwrap();
+        assert_eq!(
+            processor.extract_usage(&parsed),
+            Some(TokenUsage {
+                input_tokens: 12,
+                output_tokens: 340,
+                cache_read_input_tokens: 9000,
+                cache_creation_input_tokens: 2048,
+                cost_usd: Some(0.0421),
+                model: Some("claude-sonnet-4-20250514".to_string()),
+            })
+        );
+    }
+
     #[test]
     fn test_thinking_content() {
         let thinking_json = r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"thinking","thinking":"Let me think about this..."}]}}"#;
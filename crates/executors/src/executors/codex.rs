@@ -3,7 +3,7 @@ pub mod jsonrpc;
 pub mod normalize_logs;
 pub mod session;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -32,10 +32,12 @@ use self::{
 use crate::{
     approvals::ExecutorApprovalService,
     command::{CmdOverrides, CommandBuilder, CommandParts, apply_overrides},
+    env::ExecutionEnv,
     executors::{
         AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
         codex::{jsonrpc::ExitSignalSender, normalize_logs::Error},
     },
+    mcp_config::McpServerConfig,
     stdout_dup::create_stdout_pipe_writer,
 };
 
@@ -141,9 +143,15 @@ impl StandardCodingAgentExecutor for Codex {
         self.approvals = Some(approvals);
     }
 
-    async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError> {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
         let command_parts = self.build_command_builder().build_initial()?;
-        self.spawn(current_dir, prompt, command_parts, None).await
+        self.spawn(current_dir, prompt, command_parts, None, env)
+            .await
     }
 
     async fn spawn_follow_up(
@@ -151,9 +159,10 @@ impl StandardCodingAgentExecutor for Codex {
         current_dir: &Path,
         prompt: &str,
         session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         let command_parts = self.build_command_builder().build_follow_up(&[])?;
-        self.spawn(current_dir, prompt, command_parts, Some(session_id))
+        self.spawn(current_dir, prompt, command_parts, Some(session_id), env)
             .await
     }
 
@@ -177,7 +186,11 @@ impl Codex {
         apply_overrides(builder, &self.cmd)
     }
 
-    fn build_new_conversation_params(&self, cwd: &Path) -> NewConversationParams {
+    fn build_new_conversation_params(
+        &self,
+        cwd: &Path,
+        mcp_servers: &BTreeMap<String, McpServerConfig>,
+    ) -> NewConversationParams {
         let sandbox = match self.sandbox.as_ref() {
             None | Some(SandboxMode::Auto) => Some(CodexSandboxMode::WorkspaceWrite), // match the Auto preset in codex
             Some(SandboxMode::ReadOnly) => Some(CodexSandboxMode::ReadOnly),
@@ -203,16 +216,31 @@ impl Codex {
             cwd: Some(cwd.to_string_lossy().to_string()),
             approval_policy,
             sandbox,
-            config: self.build_config_overrides(),
+            config: self.build_config_overrides(mcp_servers),
             base_instructions: self.base_instructions.clone(),
             include_plan_tool: self.include_plan_tool,
             include_apply_patch_tool: self.include_apply_patch_tool,
         }
     }
 
-    fn build_config_overrides(&self) -> Option<HashMap<String, Value>> {
+    fn build_config_overrides(
+        &self,
+        mcp_servers: &BTreeMap<String, McpServerConfig>,
+    ) -> Option<HashMap<String, Value>> {
         let mut overrides = HashMap::new();
 
+        // Added next to the servers of config.toml
+        for (name, server) in mcp_servers {
+            overrides.insert(
+                format!("mcp_servers.{name}"),
+                serde_json::json!({
+                    "command": server.command,
+                    "args": server.args,
+                    "env": server.env,
+                }),
+            );
+        }
+
         if let Some(effort) = &self.model_reasoning_effort {
             overrides.insert(
                 "model_reasoning_effort".to_string(),
@@ -249,9 +277,10 @@ impl Codex {
         prompt: &str,
         command_parts: CommandParts,
         resume_session: Option<&str>,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
-        let (program_path, args) = command_parts.into_resolved().await?;
+        let (program_path, args) = env.resolve(command_parts, current_dir).await?;
 
         let mut process = Command::new(program_path);
         process
@@ -265,6 +294,8 @@ impl Codex {
             .env("NO_COLOR", "1")
             .env("RUST_LOG", "error");
 
+        env.apply(&mut process);
+
         let mut child = process.group_spawn()?;
 
         let child_stdout = child.inner().stdout.take().ok_or_else(|| {
@@ -277,7 +308,7 @@ impl Codex {
         let new_stdout = create_stdout_pipe_writer(&mut child)?;
         let (exit_signal_tx, exit_signal_rx) = tokio::sync::oneshot::channel();
 
-        let params = self.build_new_conversation_params(current_dir);
+        let params = self.build_new_conversation_params(current_dir, env.mcp_servers());
         let resume_session = resume_session.map(|s| s.to_string());
         let auto_approve = matches!(
             (&self.sandbox, &self.ask_for_approval),
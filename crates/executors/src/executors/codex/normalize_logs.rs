@@ -29,6 +29,7 @@ use serde_json::Value;
 use workspace_utils::{
     approvals::ApprovalStatus,
     diff::{concatenate_diff_hunks, extract_unified_diff_hunks},
+    log_msg::TokenUsage,
     msg_store::MsgStore,
     path::make_path_relative,
 };
@@ -216,6 +217,7 @@ struct LogState {
     patches: HashMap<String, PatchState>,
     web_searches: HashMap<String, WebSearchState>,
     token_usage_info: Option<TokenUsageInfo>,
+    model: Option<String>,
 }
 
 enum StreamingTextKind {
@@ -234,6 +236,7 @@ impl LogState {
             patches: HashMap::new(),
             web_searches: HashMap::new(),
             token_usage_info: None,
+            model: None,
         }
     }
 
@@ -424,6 +427,7 @@ pub fn normalize_logs(msg_store: Arc<MsgStore>, worktree_path: &Path) {
                     server_notification
                 {
                     msg_store.push_session_id(session_configured.session_id.to_string());
+                    state.model = Some(session_configured.model.clone());
                     handle_model_params(
                         session_configured.model,
                         session_configured.reasoning_effort,
@@ -462,6 +466,7 @@ pub fn normalize_logs(msg_store: Arc<MsgStore>, worktree_path: &Path) {
             match event {
                 EventMsg::SessionConfigured(payload) => {
                     msg_store.push_session_id(payload.session_id.to_string());
+                    state.model = Some(payload.model.clone());
                     handle_model_params(
                         payload.model,
                         payload.reasoning_effort,
@@ -965,6 +970,7 @@ pub fn normalize_logs(msg_store: Arc<MsgStore>, worktree_path: &Path) {
                 }
                 EventMsg::TokenCount(payload) => {
                     if let Some(info) = payload.info {
+                        msg_store.push_usage(token_usage_from_info(&info, state.model.clone()));
                         state.token_usage_info = Some(info);
                     }
                 }
@@ -1010,6 +1016,19 @@ fn handle_jsonrpc_response(
     );
 }
 
+/// Codex reports cumulative totals where cached tokens are part of the input count
+fn token_usage_from_info(info: &TokenUsageInfo, model: Option<String>) -> TokenUsage {
+    let total = &info.total_token_usage;
+    TokenUsage {
+        input_tokens: total.input_tokens.saturating_sub(total.cached_input_tokens),
+        output_tokens: total.output_tokens,
+        cache_read_input_tokens: total.cached_input_tokens,
+        cache_creation_input_tokens: 0,
+        cost_usd: None,
+        model,
+    }
+}
+
 fn handle_model_params(
     model: String,
     reasoning_effort: Option<ReasoningEffort>,
@@ -0,0 +1,587 @@
+use std::{collections::HashMap, path::Path, process::Stdio, sync::Arc};
+
+use async_trait::async_trait;
+use command_group::AsyncCommandGroup;
+use futures::StreamExt;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::{io::AsyncWriteExt, process::Command};
+use ts_rs::TS;
+use uuid::Uuid;
+use workspace_utils::{msg_store::MsgStore, shell::resolve_executable_path};
+
+use crate::{
+    command::{CommandBuilder, CommandParts},
+    env::ExecutionEnv,
+    executors::{AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor},
+    logs::{
+        ActionType, NormalizedEntry, NormalizedEntryError, NormalizedEntryType, ToolStatus,
+        plain_text_processor::PlainTextLogProcessor,
+        stderr_processor::normalize_stderr_logs,
+        utils::{ConversationPatch, EntryIndexProvider},
+    },
+    stdout_dup,
+};
+
+const PROMPT_PLACEHOLDER: &str = "{prompt}";
+const SESSION_ID_PLACEHOLDER: &str = "{session_id}";
+
+/// How the command prints its output
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomOutputFormat {
+    /// Plain text; lines no rule matches are assistant messages
+    #[default]
+    Text,
+    /// One JSON object per line; lines no rule matches are dropped
+    JsonLines,
+}
+
+/// What a matched line becomes in the conversation
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomEntryType {
+    Assistant,
+    Thinking,
+    System,
+    Error,
+    Tool,
+    /// Drop the line
+    Ignore,
+}
+
+/// Maps output lines onto conversation entries. Rules are tried in order and
+/// the first that matches a line wins.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+pub struct CustomOutputRule {
+    /// Text output: regex a line must match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// JSON output: path a line must have, e.g. `$.type` or `$.items[0].kind`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_path: Option<String>,
+    /// JSON output: value the `json_path` must equal, compared as text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equals: Option<String>,
+    pub entry_type: CustomEntryType,
+    /// Text output: content template with `$1` or `${name}` for capture
+    /// groups. JSON output: path of the content. Defaults to the whole line.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Tool name shown for `tool` entries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_name: Option<String>,
+}
+
+/// An in-house CLI run as a coding agent, configured entirely from the
+/// executor profiles
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+pub struct Custom {
+    #[serde(default)]
+    pub append_prompt: AppendPrompt,
+    #[schemars(
+        description = "Command line of the first turn. {prompt} is replaced with the prompt; without it the prompt is written to stdin"
+    )]
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Command line of follow-ups, with {session_id} and {prompt}. Follow-ups are unsupported without it"
+    )]
+    pub follow_up_command: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Directory to run in, relative to the worktree")]
+    pub working_dir: Option<String>,
+    #[serde(default)]
+    pub output_format: CustomOutputFormat,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<CustomOutputRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Session id for follow-ups: a regex whose first group captures it (text), or a JSON path (JSON lines). A generated id is used without it"
+    )]
+    pub session_id: Option<String>,
+}
+
+impl Custom {
+    pub fn validate(&self) -> Result<(), String> {
+        let commands = [Some(&self.command), self.follow_up_command.as_ref()];
+        for command in commands.into_iter().flatten() {
+            if shlex::split(command).is_none_or(|parts| parts.is_empty()) {
+                return Err(format!("'{command}' is not a valid command line"));
+            }
+        }
+        let patterns = self.rules.iter().filter_map(|rule| rule.pattern.as_ref());
+        let session_pattern = match self.output_format {
+            CustomOutputFormat::Text => self.session_id.as_ref(),
+            CustomOutputFormat::JsonLines => None,
+        };
+        for pattern in patterns.chain(session_pattern) {
+            Regex::new(pattern).map_err(|e| format!("invalid regex '{pattern}': {e}"))?;
+        }
+        Ok(())
+    }
+
+    fn program(&self) -> Option<String> {
+        shlex::split(&self.command)?.into_iter().next()
+    }
+
+    /// Split a command line, then substitute placeholders in its arguments so
+    /// that their values need no quoting
+    fn command_parts(
+        template: &str,
+        prompt: &str,
+        session_id: &str,
+    ) -> Result<(CommandParts, bool), ExecutorError> {
+        let parts = CommandBuilder::new(template).build_initial()?;
+        let prompt_in_args = parts
+            .args()
+            .iter()
+            .any(|arg| arg.contains(PROMPT_PLACEHOLDER));
+        let parts = parts.map_args(|arg| {
+            arg.replace(SESSION_ID_PLACEHOLDER, session_id)
+                .replace(PROMPT_PLACEHOLDER, prompt)
+        });
+        Ok((parts, prompt_in_args))
+    }
+
+    async fn spawn_command(
+        &self,
+        current_dir: &Path,
+        template: &str,
+        prompt: &str,
+        session_id: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        let (command_parts, prompt_in_args) =
+            Self::command_parts(template, &combined_prompt, session_id)?;
+        let (program_path, args) = env.resolve(command_parts, current_dir).await?;
+
+        let working_dir = match &self.working_dir {
+            Some(dir) => current_dir.join(dir),
+            None => current_dir.to_path_buf(),
+        };
+        let mut command = Command::new(program_path);
+        command
+            .kill_on_drop(true)
+            .stdin(if prompt_in_args {
+                Stdio::null()
+            } else {
+                Stdio::piped()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(working_dir)
+            .args(&args)
+            .envs(&self.env);
+
+        env.apply(&mut command);
+
+        let mut child = command.group_spawn()?;
+
+        if !prompt_in_args && let Some(mut stdin) = child.inner().stdin.take() {
+            stdin.write_all(combined_prompt.as_bytes()).await?;
+            stdin.shutdown().await?;
+        }
+
+        // Without a way to read the session id from the output, use the one
+        // the command was started with
+        if self.session_id.is_none() {
+            let (_, appender) = stdout_dup::tee_stdout_with_appender(&mut child)?;
+            appender.append_line(format!("{}{}\n", Self::SESSION_PREFIX, session_id));
+        }
+
+        Ok(child.into())
+    }
+}
+
+#[async_trait]
+impl StandardCodingAgentExecutor for Custom {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let session_id = Uuid::new_v4().to_string();
+        self.spawn_command(current_dir, &self.command, prompt, &session_id, env)
+            .await
+    }
+
+    async fn spawn_follow_up(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        session_id: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let template = self.follow_up_command.as_deref().ok_or_else(|| {
+            ExecutorError::FollowUpNotSupported(
+                "Custom executor has no follow_up_command".to_string(),
+            )
+        })?;
+        self.spawn_command(current_dir, template, prompt, session_id, env)
+            .await
+    }
+
+    /// Applies the configured rules to each stdout line. Consecutive assistant
+    /// lines of text output form one message.
+    fn normalize_logs(&self, msg_store: Arc<MsgStore>, _worktree_path: &Path) {
+        let entry_index_counter = EntryIndexProvider::start_from(&msg_store);
+        normalize_stderr_logs(msg_store.clone(), entry_index_counter.clone());
+
+        let parser = OutputParser::new(self);
+        tokio::spawn(async move {
+            let mut stdout_lines = msg_store.stdout_lines_stream();
+            let mut processor = Self::create_assistant_normalizer(entry_index_counter.clone());
+            let mut in_message = false;
+
+            while let Some(Ok(line)) = stdout_lines.next().await {
+                if let Some(session_id) = line.strip_prefix(Self::SESSION_PREFIX) {
+                    msg_store.push_session_id(session_id.trim().to_string());
+                    continue;
+                }
+
+                let line = strip_ansi_escapes::strip_str(&line);
+                if let Some(session_id) = parser.session_id(&line) {
+                    msg_store.push_session_id(session_id);
+                }
+
+                match parser.parse(&line) {
+                    ParsedLine::Skip => {}
+                    ParsedLine::Text(text) => {
+                        if text.trim().is_empty() && !in_message {
+                            continue;
+                        }
+                        in_message = true;
+                        for patch in processor.process(text + "\n") {
+                            msg_store.push_patch(patch);
+                        }
+                    }
+                    ParsedLine::Entry(entry) => {
+                        // Close the assistant message so later text starts a new one
+                        processor = Self::create_assistant_normalizer(entry_index_counter.clone());
+                        in_message = false;
+                        let index = entry_index_counter.next();
+                        msg_store.push_patch(ConversationPatch::add_normalized_entry(index, entry));
+                    }
+                }
+            }
+        });
+    }
+
+    fn default_mcp_config_path(&self) -> Option<std::path::PathBuf> {
+        None
+    }
+
+    async fn check_availability(&self) -> bool {
+        match self.program() {
+            Some(program) => resolve_executable_path(&program).await.is_some(),
+            None => false,
+        }
+    }
+}
+
+impl Custom {
+    const SESSION_PREFIX: &'static str = "[custom-session] ";
+
+    fn create_assistant_normalizer(index_provider: EntryIndexProvider) -> PlainTextLogProcessor {
+        PlainTextLogProcessor::builder()
+            .normalized_entry_producer(Box::new(|content: String| NormalizedEntry {
+                timestamp: None,
+                entry_type: NormalizedEntryType::AssistantMessage,
+                content,
+                metadata: None,
+            }))
+            .index_provider(index_provider)
+            .build()
+    }
+}
+
+enum ParsedLine {
+    Skip,
+    /// Part of an assistant message
+    Text(String),
+    Entry(NormalizedEntry),
+}
+
+struct CompiledRule {
+    pattern: Option<Regex>,
+    rule: CustomOutputRule,
+}
+
+/// The rules of a custom executor, with their regexes compiled
+struct OutputParser {
+    format: CustomOutputFormat,
+    rules: Vec<CompiledRule>,
+    session_id_pattern: Option<Regex>,
+    session_id_path: Option<String>,
+}
+
+impl OutputParser {
+    fn new(config: &Custom) -> Self {
+        let compile = |pattern: &str| match Regex::new(pattern) {
+            Ok(regex) => Some(regex),
+            Err(e) => {
+                tracing::warn!(
+                    "Ignoring invalid custom executor regex '{}': {}",
+                    pattern,
+                    e
+                );
+                None
+            }
+        };
+        let rules = config
+            .rules
+            .iter()
+            .filter_map(|rule| {
+                let pattern = match (&rule.pattern, config.output_format) {
+                    (Some(pattern), CustomOutputFormat::Text) => Some(compile(pattern)?),
+                    _ => None,
+                };
+                Some(CompiledRule {
+                    pattern,
+                    rule: rule.clone(),
+                })
+            })
+            .collect();
+        let (session_id_pattern, session_id_path) = match config.output_format {
+            CustomOutputFormat::Text => (config.session_id.as_deref().and_then(compile), None),
+            CustomOutputFormat::JsonLines => (None, config.session_id.clone()),
+        };
+        Self {
+            format: config.output_format,
+            rules,
+            session_id_pattern,
+            session_id_path,
+        }
+    }
+
+    fn session_id(&self, line: &str) -> Option<String> {
+        if let Some(pattern) = &self.session_id_pattern {
+            return Some(pattern.captures(line)?.get(1)?.as_str().to_string());
+        }
+        let path = self.session_id_path.as_deref()?;
+        let value: Value = serde_json::from_str(line.trim()).ok()?;
+        json_text(json_path(&value, path)?)
+    }
+
+    fn parse(&self, line: &str) -> ParsedLine {
+        match self.format {
+            CustomOutputFormat::Text => self.parse_text(line),
+            CustomOutputFormat::JsonLines => match serde_json::from_str::<Value>(line.trim()) {
+                Ok(value) => self.parse_json(&value),
+                Err(_) => ParsedLine::Skip,
+            },
+        }
+    }
+
+    fn parse_text(&self, line: &str) -> ParsedLine {
+        for CompiledRule { pattern, rule } in &self.rules {
+            let Some(captures) = pattern.as_ref().and_then(|p| p.captures(line)) else {
+                continue;
+            };
+            let content = match &rule.content {
+                Some(template) => {
+                    let mut content = String::new();
+                    captures.expand(template, &mut content);
+                    content
+                }
+                None => line.to_string(),
+            };
+            return Self::to_parsed(rule, content);
+        }
+        ParsedLine::Text(line.to_string())
+    }
+
+    fn parse_json(&self, value: &Value) -> ParsedLine {
+        for CompiledRule { rule, .. } in &self.rules {
+            let Some(matched) = rule.json_path.as_deref().and_then(|p| json_path(value, p)) else {
+                continue;
+            };
+            if let Some(expected) = &rule.equals
+                && json_text(matched).as_deref() != Some(expected.as_str())
+            {
+                continue;
+            }
+            let content = match &rule.content {
+                Some(path) => match json_path(value, path).and_then(json_text) {
+                    Some(content) => content,
+                    None => continue,
+                },
+                None => value.to_string(),
+            };
+            return Self::to_parsed(rule, content);
+        }
+        ParsedLine::Skip
+    }
+
+    fn to_parsed(rule: &CustomOutputRule, content: String) -> ParsedLine {
+        let entry_type = match rule.entry_type {
+            CustomEntryType::Ignore => return ParsedLine::Skip,
+            CustomEntryType::Assistant => return ParsedLine::Text(content),
+            CustomEntryType::Thinking => NormalizedEntryType::Thinking,
+            CustomEntryType::System => NormalizedEntryType::SystemMessage,
+            CustomEntryType::Error => NormalizedEntryType::ErrorMessage {
+                error_type: NormalizedEntryError::Other,
+            },
+            CustomEntryType::Tool => {
+                let tool_name = rule.tool_name.clone().unwrap_or_else(|| "tool".to_string());
+                NormalizedEntryType::ToolUse {
+                    tool_name: tool_name.clone(),
+                    action_type: ActionType::Tool {
+                        tool_name,
+                        arguments: None,
+                        result: None,
+                    },
+                    status: ToolStatus::Success,
+                }
+            }
+        };
+        ParsedLine::Entry(NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content,
+            metadata: None,
+        })
+    }
+}
+
+/// Resolve a path like `$.message.content[0].text` in `value`
+fn json_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let mut current = value;
+    for segment in path.split('.').filter(|segment| !segment.is_empty()) {
+        let (key, indices) = match segment.find('[') {
+            Some(bracket) => segment.split_at(bracket),
+            None => (segment, ""),
+        };
+        if !key.is_empty() {
+            current = current.get(key)?;
+        }
+        for index in indices.split(['[', ']']).filter(|index| !index.is_empty()) {
+            current = current.get(index.parse::<usize>().ok()?)?;
+        }
+    }
+    Some(current)
+}
+
+/// Strings as they are, other values as JSON
+fn json_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(text) => Some(text.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn config(output_format: CustomOutputFormat, rules: Vec<CustomOutputRule>) -> Custom {
+        Custom {
+            append_prompt: AppendPrompt::default(),
+            command: "my-agent --task {prompt}".to_string(),
+            follow_up_command: None,
+            env: HashMap::new(),
+            working_dir: None,
+            output_format,
+            rules,
+            session_id: None,
+        }
+    }
+
+    fn rule(entry_type: CustomEntryType) -> CustomOutputRule {
+        CustomOutputRule {
+            pattern: None,
+            json_path: None,
+            equals: None,
+            entry_type,
+            content: None,
+            tool_name: None,
+        }
+    }
+
+    #[test]
+    fn resolves_json_paths() {
+        let value = json!({"message": {"content": [{"text": "hi"}]}, "n": 3});
+        assert_eq!(
+            json_path(&value, "$.message.content[0].text"),
+            Some(&json!("hi"))
+        );
+        assert_eq!(json_path(&value, "n"), Some(&json!(3)));
+        assert_eq!(json_path(&value, "$.message.missing"), None);
+    }
+
+    #[test]
+    fn substitutes_placeholders_after_splitting() {
+        let (parts, prompt_in_args) = Custom::command_parts(
+            "agent --resume {session_id} -m {prompt}",
+            "fix 'it' now",
+            "s1",
+        )
+        .unwrap();
+        assert!(prompt_in_args);
+        assert_eq!(parts.args(), vec!["--resume", "s1", "-m", "fix 'it' now"]);
+    }
+
+    #[test]
+    fn applies_text_rules_in_order() {
+        let parser = OutputParser::new(&config(
+            CustomOutputFormat::Text,
+            vec![
+                CustomOutputRule {
+                    pattern: Some(r"^ERROR: (?<msg>.*)$".to_string()),
+                    content: Some("${msg}".to_string()),
+                    ..rule(CustomEntryType::Error)
+                },
+                CustomOutputRule {
+                    pattern: Some(r"^DEBUG".to_string()),
+                    ..rule(CustomEntryType::Ignore)
+                },
+            ],
+        ));
+        assert!(matches!(
+            parser.parse("ERROR: disk full"),
+            ParsedLine::Entry(NormalizedEntry { ref content, .. }) if content == "disk full"
+        ));
+        assert!(matches!(parser.parse("DEBUG tick"), ParsedLine::Skip));
+        assert!(matches!(parser.parse("Done."), ParsedLine::Text(ref text) if text == "Done."));
+    }
+
+    #[test]
+    fn applies_json_rules() {
+        let parser = OutputParser::new(&Custom {
+            session_id: Some("$.session".to_string()),
+            ..config(
+                CustomOutputFormat::JsonLines,
+                vec![CustomOutputRule {
+                    json_path: Some("$.type".to_string()),
+                    equals: Some("tool".to_string()),
+                    content: Some("$.name".to_string()),
+                    tool_name: Some("run".to_string()),
+                    ..rule(CustomEntryType::Tool)
+                }],
+            )
+        });
+        assert!(matches!(
+            parser.parse(r#"{"type":"tool","name":"cargo test"}"#),
+            ParsedLine::Entry(NormalizedEntry { ref content, .. }) if content == "cargo test"
+        ));
+        assert!(matches!(
+            parser.parse(r#"{"type":"other"}"#),
+            ParsedLine::Skip
+        ));
+        assert_eq!(
+            parser.session_id(r#"{"session":"abc"}"#),
+            Some("abc".to_string())
+        );
+    }
+}
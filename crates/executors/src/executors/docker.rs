@@ -0,0 +1,139 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+};
+
+use command_group::AsyncCommandGroup;
+use tokio::{fs, process::Command};
+use uuid::Uuid;
+use workspace_utils::path::get_vibe_kanban_temp_dir;
+
+use crate::{
+    command::{CmdOverrides, CommandBuilder, apply_overrides},
+    env::ExecutionEnv,
+    executors::{ExecutorError, SpawnedChild},
+    stdout_dup,
+};
+
+/// What to run in the container, besides the mounts the harness adds
+pub struct DockerRun {
+    pub image: String,
+    /// Options for `docker run`, placed before the image
+    pub docker_args: Vec<String>,
+    /// Command run in the container, after the image
+    pub command: Vec<String>,
+    /// Host environment variables passed through to the container, in
+    /// addition to the execution's own
+    pub forward_env: Vec<&'static str>,
+}
+
+/// Reusable harness for agents that run in a Docker container rather than on
+/// the host (OpenHands, SWE-agent, etc.). The attempt's worktree is mounted at
+/// [`Self::WORKSPACE_DIR`], and a per-session directory holding the prompt
+/// and any state the agent keeps between turns at [`Self::SESSION_DIR`].
+pub struct DockerAgentHarness {
+    session_namespace: String,
+}
+
+impl DockerAgentHarness {
+    pub const WORKSPACE_DIR: &'static str = "/workspace";
+    pub const SESSION_DIR: &'static str = "/vibe-session";
+    /// File in the session directory holding the prompt of the current turn
+    pub const PROMPT_FILE: &'static str = "prompt.md";
+    /// Prefix of the line announcing the session id on stdout
+    pub const SESSION_PREFIX: &'static str = "[docker-session] ";
+
+    pub fn new(session_namespace: impl Into<String>) -> Self {
+        Self {
+            session_namespace: session_namespace.into(),
+        }
+    }
+
+    pub fn base_command() -> &'static str {
+        "docker"
+    }
+
+    /// Host directory mounted at [`Self::SESSION_DIR`]
+    pub fn session_dir(&self, session_id: &str) -> PathBuf {
+        get_vibe_kanban_temp_dir()
+            .join(&self.session_namespace)
+            .join(session_id)
+    }
+
+    /// Container path of the prompt file
+    pub fn prompt_path() -> String {
+        format!("{}/{}", Self::SESSION_DIR, Self::PROMPT_FILE)
+    }
+
+    /// Run the container for one turn of `session_id`. Signals sent to the
+    /// `docker` client are proxied to the container, so stopping the process
+    /// stops the agent.
+    pub async fn spawn(
+        &self,
+        current_dir: &Path,
+        session_id: &str,
+        prompt: &str,
+        run: DockerRun,
+        cmd: &CmdOverrides,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let session_dir = self.session_dir(session_id);
+        fs::create_dir_all(&session_dir)
+            .await
+            .map_err(ExecutorError::Io)?;
+        fs::write(session_dir.join(Self::PROMPT_FILE), prompt)
+            .await
+            .map_err(ExecutorError::Io)?;
+
+        // Overrides can swap in e.g. podman, and additional params become
+        // options of `run`
+        let builder = apply_overrides(
+            CommandBuilder::new(Self::base_command()).params(["run", "--rm", "-i"]),
+            cmd,
+        );
+        let (program_path, args) = builder.build_initial()?.into_resolved().await?;
+
+        let container_name = format!(
+            "vibe-kanban-{}-{}",
+            self.session_namespace.replace('_', "-"),
+            Uuid::new_v4().simple()
+        );
+        let mut command = Command::new(program_path);
+        command
+            .kill_on_drop(true)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(current_dir)
+            .args(&args)
+            .args(["--name", &container_name])
+            .arg("-v")
+            .arg(mount(current_dir, Self::WORKSPACE_DIR))
+            .arg("-v")
+            .arg(mount(&session_dir, Self::SESSION_DIR))
+            .args(["-w", Self::WORKSPACE_DIR]);
+        // Pass values through the environment of the docker client so that
+        // secrets stay off the command line
+        for name in run.forward_env.iter().copied().chain(env.names()) {
+            command.args(["-e", name]);
+        }
+        command
+            .args(&run.docker_args)
+            .arg(&run.image)
+            .args(&run.command);
+
+        env.apply(&mut command);
+
+        let mut child = command.group_spawn()?;
+
+        let (_, appender) = stdout_dup::tee_stdout_with_appender(&mut child)?;
+        appender.append_line(format!("{}{}\n", Self::SESSION_PREFIX, session_id));
+
+        Ok(child.into())
+    }
+}
+
+/// `-v` value binding a host path into the container
+pub fn mount(host_path: &Path, container_path: &str) -> String {
+    format!("{}:{}", host_path.to_string_lossy(), container_path)
+}
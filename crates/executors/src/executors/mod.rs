@@ -16,21 +16,27 @@ use crate::{
     actions::ExecutorAction,
     approvals::ExecutorApprovalService,
     command::CommandBuildError,
+    env::ExecutionEnv,
     executors::{
-        amp::Amp, claude::ClaudeCode, codex::Codex, copilot::Copilot, cursor::CursorAgent,
-        gemini::Gemini, opencode::Opencode, qwen::QwenCode,
+        aider::Aider, amp::Amp, claude::ClaudeCode, codex::Codex, copilot::Copilot,
+        cursor::CursorAgent, custom::Custom, gemini::Gemini, opencode::Opencode,
+        openhands::Openhands, qwen::QwenCode,
     },
     mcp_config::McpConfig,
 };
 
 pub mod acp;
+pub mod aider;
 pub mod amp;
 pub mod claude;
 pub mod codex;
 pub mod copilot;
 pub mod cursor;
+pub mod custom;
+pub mod docker;
 pub mod gemini;
 pub mod opencode;
+pub mod openhands;
 pub mod qwen;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
@@ -95,6 +101,9 @@ pub enum CodingAgent {
     CursorAgent,
     QwenCode,
     Copilot,
+    Aider,
+    Openhands,
+    Custom,
 }
 
 impl CodingAgent {
@@ -148,7 +157,22 @@ impl CodingAgent {
             Self::Gemini(_) => vec![BaseAgentCapability::SessionFork],
             Self::QwenCode(_) => vec![BaseAgentCapability::SessionFork],
             Self::CursorAgent(_) => vec![BaseAgentCapability::SetupHelper],
-            Self::Opencode(_) | Self::Copilot(_) => vec![],
+            Self::Opencode(_)
+            | Self::Copilot(_)
+            | Self::Aider(_)
+            | Self::Openhands(_)
+            | Self::Custom(_) => {
+                vec![]
+            }
+        }
+    }
+
+    /// Check a configuration's settings against what the executor supports
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::ClaudeCode(claude) => claude.validate(),
+            Self::Custom(custom) => custom.validate(),
+            _ => Ok(()),
         }
     }
 }
@@ -158,12 +182,18 @@ impl CodingAgent {
 pub trait StandardCodingAgentExecutor {
     fn use_approvals(&mut self, _approvals: Arc<dyn ExecutorApprovalService>) {}
 
-    async fn spawn(&self, current_dir: &Path, prompt: &str) -> Result<SpawnedChild, ExecutorError>;
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError>;
     async fn spawn_follow_up(
         &self,
         current_dir: &Path,
         prompt: &str,
         session_id: &str,
+        env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError>;
     fn normalize_logs(&self, _raw_logs_event_store: Arc<MsgStore>, _worktree_path: &Path);
 
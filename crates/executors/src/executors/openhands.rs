@@ -0,0 +1,175 @@
+pub mod normalize_logs;
+
+use std::{path::Path, sync::Arc};
+
+use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+use workspace_utils::{msg_store::MsgStore, shell::resolve_executable_path};
+
+use self::normalize_logs::normalize_logs;
+use crate::{
+    command::CmdOverrides,
+    env::ExecutionEnv,
+    executors::{
+        AppendPrompt, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+        docker::{DockerAgentHarness, DockerRun, mount},
+    },
+};
+
+const OPENHANDS_VERSION: &str = "0.59";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+pub struct Openhands {
+    #[serde(default)]
+    pub append_prompt: AppendPrompt,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "LiteLLM model name, e.g. anthropic/claude-sonnet-4-5. Defaults to LLM_MODEL"
+    )]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Agent class, e.g. CodeActAgent")]
+    pub agent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_iterations: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "OpenHands image to run the agent in")]
+    pub image: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Image of the sandbox the agent's actions run in")]
+    pub runtime_image: Option<String>,
+    #[serde(flatten)]
+    pub cmd: CmdOverrides,
+}
+
+impl Openhands {
+    fn harness() -> DockerAgentHarness {
+        DockerAgentHarness::new("openhands_sessions")
+    }
+
+    /// The OpenHands container starts a sandbox container for the agent's
+    /// actions through the host's Docker socket, and mounts the worktree into
+    /// it. Its event store lives in the session directory so a follow-up with
+    /// the same session name restores the conversation.
+    fn docker_run(&self, current_dir: &Path, session_id: &str) -> DockerRun {
+        let image = self.image.clone().unwrap_or_else(|| {
+            format!("docker.all-hands.dev/all-hands-ai/openhands:{OPENHANDS_VERSION}")
+        });
+        let runtime_image = self.runtime_image.clone().unwrap_or_else(|| {
+            format!("docker.all-hands.dev/all-hands-ai/runtime:{OPENHANDS_VERSION}-nikolaik")
+        });
+
+        let mut docker_args = vec![
+            "-v".to_string(),
+            "/var/run/docker.sock:/var/run/docker.sock".to_string(),
+            "--add-host".to_string(),
+            "host.docker.internal:host-gateway".to_string(),
+        ];
+        let mut vars = vec![
+            format!("SANDBOX_RUNTIME_CONTAINER_IMAGE={runtime_image}"),
+            format!(
+                "SANDBOX_VOLUMES={}:rw",
+                mount(current_dir, DockerAgentHarness::WORKSPACE_DIR)
+            ),
+            "LOG_ALL_EVENTS=true".to_string(),
+            "LOG_JSON=true".to_string(),
+            "FILE_STORE=local".to_string(),
+            format!("FILE_STORE_PATH={}/state", DockerAgentHarness::SESSION_DIR),
+        ];
+        let mut forward_env = vec!["LLM_API_KEY", "LLM_BASE_URL"];
+        match &self.model {
+            Some(model) => vars.push(format!("LLM_MODEL={model}")),
+            None => forward_env.push("LLM_MODEL"),
+        }
+        for var in vars {
+            docker_args.push("-e".to_string());
+            docker_args.push(var);
+        }
+
+        let mut command = vec![
+            "python".to_string(),
+            "-m".to_string(),
+            "openhands.core.main".to_string(),
+            "-f".to_string(),
+            DockerAgentHarness::prompt_path(),
+            "-n".to_string(),
+            session_id.to_string(),
+        ];
+        if let Some(agent) = &self.agent {
+            command.extend(["-c".to_string(), agent.clone()]);
+        }
+        if let Some(max_iterations) = self.max_iterations {
+            command.extend(["-i".to_string(), max_iterations.to_string()]);
+        }
+
+        DockerRun {
+            image,
+            docker_args,
+            command,
+            forward_env,
+        }
+    }
+
+    async fn spawn_session(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        session_id: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let combined_prompt = self.append_prompt.combine_prompt(prompt);
+        Self::harness()
+            .spawn(
+                current_dir,
+                session_id,
+                &combined_prompt,
+                self.docker_run(current_dir, session_id),
+                &self.cmd,
+                env,
+            )
+            .await
+    }
+}
+
+#[async_trait]
+impl StandardCodingAgentExecutor for Openhands {
+    async fn spawn(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        let session_id = Uuid::new_v4().to_string();
+        self.spawn_session(current_dir, prompt, &session_id, env)
+            .await
+    }
+
+    async fn spawn_follow_up(
+        &self,
+        current_dir: &Path,
+        prompt: &str,
+        session_id: &str,
+        env: &ExecutionEnv,
+    ) -> Result<SpawnedChild, ExecutorError> {
+        self.spawn_session(current_dir, prompt, session_id, env)
+            .await
+    }
+
+    fn normalize_logs(&self, msg_store: Arc<MsgStore>, worktree_path: &Path) {
+        normalize_logs(msg_store, worktree_path);
+    }
+
+    // MCP servers are configured inside the OpenHands container
+    fn default_mcp_config_path(&self) -> Option<std::path::PathBuf> {
+        None
+    }
+
+    async fn check_availability(&self) -> bool {
+        resolve_executable_path(DockerAgentHarness::base_command())
+            .await
+            .is_some()
+    }
+}
@@ -0,0 +1,308 @@
+use std::{path::Path, sync::Arc};
+
+use futures::StreamExt;
+use serde::Deserialize;
+use workspace_utils::msg_store::MsgStore;
+
+use crate::{
+    executors::docker::DockerAgentHarness,
+    logs::{
+        ActionType, CommandExitStatus, CommandRunResult, NormalizedEntry, NormalizedEntryError,
+        NormalizedEntryType, ToolStatus,
+        stderr_processor::normalize_stderr_logs,
+        utils::{ConversationPatch, EntryIndexProvider},
+    },
+};
+
+/// A line of OpenHands' JSON log. With `LOG_ALL_EVENTS` every action and
+/// observation is logged, rendered as text in `message`.
+#[derive(Debug, Deserialize)]
+struct LogRecord {
+    message: String,
+    #[serde(default)]
+    msg_type: Option<String>,
+    #[serde(default)]
+    level: Option<String>,
+}
+
+/// Events of the OpenHands event stream that show in the conversation
+#[derive(Debug, PartialEq)]
+enum OpenHandsEvent {
+    AgentMessage(String),
+    Think(String),
+    Finish(String),
+    CommandRun {
+        command: String,
+    },
+    CommandOutput {
+        output: String,
+        exit_code: Option<i32>,
+    },
+    FileRead {
+        path: String,
+    },
+    FileEdit {
+        path: String,
+    },
+    Browse {
+        url: String,
+    },
+    Error(String),
+}
+
+impl OpenHandsEvent {
+    /// Parse an event rendered as text, e.g. `**CmdRunAction (source=...)**`
+    /// followed by its fields
+    fn parse(message: &str) -> Option<Self> {
+        let rest = message.trim_start().strip_prefix("**")?;
+        let (header, body) = rest.split_once('\n').unwrap_or((rest, ""));
+        let name: String = header
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect();
+        let from_agent =
+            !header.contains("source=EventSource.USER") && !header.contains("source=user");
+
+        let event = match name.as_str() {
+            "MessageAction" if from_agent => {
+                Self::AgentMessage(field(body, "CONTENT:").unwrap_or(body).trim().to_string())
+            }
+            "AgentThinkAction" => {
+                Self::Think(field(body, "THOUGHT:").unwrap_or(body).trim().to_string())
+            }
+            "AgentFinishAction" => {
+                Self::Finish(field(body, "THOUGHT:").unwrap_or(body).trim().to_string())
+            }
+            "CmdRunAction" => Self::CommandRun {
+                command: body
+                    .split_once("COMMAND:\n")
+                    .map(|(_, command)| command)
+                    .unwrap_or(body)
+                    .trim()
+                    .to_string(),
+            },
+            "CmdOutputObservation" => Self::CommandOutput {
+                output: body
+                    .replace("--BEGIN AGENT OBSERVATION--", "")
+                    .replace("--END AGENT OBSERVATION--", "")
+                    .trim()
+                    .to_string(),
+                exit_code: header.split_once("exit code=").and_then(|(_, rest)| {
+                    rest.split(|c: char| c == ',' || c == ')')
+                        .next()?
+                        .trim()
+                        .parse()
+                        .ok()
+                }),
+            },
+            "FileReadAction" => Self::FileRead {
+                path: field(body, "Path:")?.to_string(),
+            },
+            "FileEditAction" | "FileWriteAction" => Self::FileEdit {
+                path: field(body, "Path:")?.to_string(),
+            },
+            "BrowseURLAction" => Self::Browse {
+                url: field(body, "URL:")?.to_string(),
+            },
+            "ErrorObservation" | "AgentErrorObservation" => Self::Error(body.trim().to_string()),
+            _ => return None,
+        };
+        Some(event)
+    }
+}
+
+/// Value of a `Label: value` line, without the brackets OpenHands puts
+/// around paths
+fn field<'a>(body: &'a str, label: &str) -> Option<&'a str> {
+    body.lines().find_map(|line| {
+        let value = line.trim().strip_prefix(label)?.trim();
+        Some(
+            value
+                .strip_prefix('[')
+                .and_then(|v| v.strip_suffix(']'))
+                .unwrap_or(value),
+        )
+    })
+}
+
+/// Container paths relative to the mounted worktree
+fn relative_path(path: &str) -> String {
+    path.strip_prefix(&format!("{}/", DockerAgentHarness::WORKSPACE_DIR))
+        .unwrap_or(path)
+        .to_string()
+}
+
+fn entry(entry_type: NormalizedEntryType, content: String) -> NormalizedEntry {
+    NormalizedEntry {
+        timestamp: None,
+        entry_type,
+        content,
+        metadata: None,
+    }
+}
+
+fn tool_use(tool_name: &str, action_type: ActionType, status: ToolStatus) -> NormalizedEntryType {
+    NormalizedEntryType::ToolUse {
+        tool_name: tool_name.to_string(),
+        action_type,
+        status,
+    }
+}
+
+pub fn normalize_logs(msg_store: Arc<MsgStore>, _worktree_path: &Path) {
+    let entry_index = EntryIndexProvider::start_from(&msg_store);
+    normalize_stderr_logs(msg_store.clone(), entry_index.clone());
+
+    tokio::spawn(async move {
+        // Command waiting for its output: (entry index, command)
+        let mut pending_command: Option<(usize, String)> = None;
+
+        let mut stdout_lines = msg_store.stdout_lines_stream();
+        while let Some(Ok(line)) = stdout_lines.next().await {
+            if let Some(session_id) = line.strip_prefix(DockerAgentHarness::SESSION_PREFIX) {
+                msg_store.push_session_id(session_id.trim().to_string());
+                continue;
+            }
+
+            // Anything else, like image pull progress, is not part of the
+            // conversation
+            let Ok(record) = serde_json::from_str::<LogRecord>(line.trim()) else {
+                continue;
+            };
+            let event = match OpenHandsEvent::parse(&record.message) {
+                Some(event) => event,
+                None if record.msg_type.is_none() && record.level.as_deref() == Some("ERROR") => {
+                    OpenHandsEvent::Error(record.message)
+                }
+                None => continue,
+            };
+
+            let new_entry = match event {
+                OpenHandsEvent::AgentMessage(content) | OpenHandsEvent::Finish(content) => {
+                    if content.is_empty() {
+                        continue;
+                    }
+                    entry(NormalizedEntryType::AssistantMessage, content)
+                }
+                OpenHandsEvent::Think(content) => entry(NormalizedEntryType::Thinking, content),
+                OpenHandsEvent::CommandRun { command } => {
+                    let index = entry_index.next();
+                    let action_type = ActionType::CommandRun {
+                        command: command.clone(),
+                        result: None,
+                    };
+                    msg_store.push_patch(ConversationPatch::add_normalized_entry(
+                        index,
+                        entry(
+                            tool_use("bash", action_type, ToolStatus::Created),
+                            command.clone(),
+                        ),
+                    ));
+                    pending_command = Some((index, command));
+                    continue;
+                }
+                OpenHandsEvent::CommandOutput { output, exit_code } => {
+                    let Some((index, command)) = pending_command.take() else {
+                        continue;
+                    };
+                    let status = match exit_code {
+                        Some(0) | None => ToolStatus::Success,
+                        Some(_) => ToolStatus::Failed,
+                    };
+                    let action_type = ActionType::CommandRun {
+                        command: command.clone(),
+                        result: Some(CommandRunResult {
+                            exit_status: exit_code.map(|code| CommandExitStatus::ExitCode { code }),
+                            output: Some(output),
+                        }),
+                    };
+                    msg_store.push_patch(ConversationPatch::replace(
+                        index,
+                        entry(tool_use("bash", action_type, status), command),
+                    ));
+                    continue;
+                }
+                OpenHandsEvent::FileRead { path } => {
+                    let path = relative_path(&path);
+                    entry(
+                        tool_use(
+                            "read",
+                            ActionType::FileRead { path: path.clone() },
+                            ToolStatus::Success,
+                        ),
+                        path,
+                    )
+                }
+                OpenHandsEvent::FileEdit { path } => {
+                    let path = relative_path(&path);
+                    entry(
+                        tool_use(
+                            "edit",
+                            ActionType::FileEdit {
+                                path: path.clone(),
+                                changes: vec![],
+                            },
+                            ToolStatus::Success,
+                        ),
+                        path,
+                    )
+                }
+                OpenHandsEvent::Browse { url } => entry(
+                    tool_use(
+                        "browse",
+                        ActionType::WebFetch { url: url.clone() },
+                        ToolStatus::Success,
+                    ),
+                    url,
+                ),
+                OpenHandsEvent::Error(content) => entry(
+                    NormalizedEntryType::ErrorMessage {
+                        error_type: NormalizedEntryError::Other,
+                    },
+                    content,
+                ),
+            };
+            let index = entry_index.next();
+            msg_store.push_patch(ConversationPatch::add_normalized_entry(index, new_entry));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rendered_events() {
+        assert_eq!(
+            OpenHandsEvent::parse(
+                "**CmdRunAction (source=EventSource.AGENT, is_input=False)**\nTHOUGHT: List files\nCOMMAND:\nls -la"
+            ),
+            Some(OpenHandsEvent::CommandRun {
+                command: "ls -la".to_string()
+            })
+        );
+        assert_eq!(
+            OpenHandsEvent::parse(
+                "**CmdOutputObservation (source=EventSource.AGENT, exit code=2, metadata={})**\n--BEGIN AGENT OBSERVATION--\nno such file\n--END AGENT OBSERVATION--"
+            ),
+            Some(OpenHandsEvent::CommandOutput {
+                output: "no such file".to_string(),
+                exit_code: Some(2)
+            })
+        );
+        assert_eq!(
+            OpenHandsEvent::parse(
+                "**FileEditAction**\nPath: [/workspace/src/lib.rs]\nThought: fix"
+            ),
+            Some(OpenHandsEvent::FileEdit {
+                path: "/workspace/src/lib.rs".to_string()
+            })
+        );
+        assert_eq!(
+            OpenHandsEvent::parse("**MessageAction** (source=EventSource.USER)\nCONTENT: do it"),
+            None
+        );
+        assert_eq!(relative_path("/workspace/src/lib.rs"), "src/lib.rs");
+    }
+}
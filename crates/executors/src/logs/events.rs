@@ -0,0 +1,428 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+use futures::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use workspace_utils::log_msg::{LogMsg, TokenUsage};
+
+use crate::logs::{
+    ActionType, FileChange, NormalizedEntry, NormalizedEntryError, NormalizedEntryType, ToolStatus,
+    utils::patch::extract_normalized_entry_from_patch,
+};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageRole {
+    User,
+    Assistant,
+    System,
+    Thinking,
+}
+
+/// Executor-agnostic view of a coding agent run. Every executor's normalized
+/// conversation projects onto these events, so consumers need not know how
+/// an agent formats its output or how entries are revised while streaming.
+///
+/// `entry_index` refers to the conversation entry an event was derived from.
+/// When an executor revises a message after it was emitted, the message is
+/// emitted again with the same index and the later event wins.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AgentEvent {
+    Message {
+        entry_index: usize,
+        role: MessageRole,
+        content: String,
+    },
+    /// The agent invoked a tool. Followed by a `tool_result` once it finishes.
+    ToolCall {
+        entry_index: usize,
+        tool_name: String,
+        action: ActionType,
+        content: String,
+    },
+    ToolResult {
+        entry_index: usize,
+        tool_name: String,
+        status: ToolStatus,
+        /// The action with its result, e.g. a command's output
+        action: ActionType,
+    },
+    /// A tool call waits for the user's decision, given through the approval
+    /// endpoints with `approval_id`
+    ApprovalRequested {
+        entry_index: usize,
+        tool_name: String,
+        approval_id: String,
+        action: ActionType,
+        timeout_at: DateTime<Utc>,
+    },
+    /// The decision on an `approval_requested` call; denied and timed out
+    /// calls are not approved
+    ApprovalResolved {
+        entry_index: usize,
+        approval_id: String,
+        approved: bool,
+    },
+    /// A file the agent changed, emitted after the edit's `tool_result`
+    FileEdit {
+        entry_index: usize,
+        path: String,
+        changes: Vec<FileChange>,
+    },
+    Error {
+        entry_index: usize,
+        error_type: NormalizedEntryError,
+        content: String,
+    },
+    /// Running totals for the execution; a later report supersedes an
+    /// earlier one
+    Usage {
+        input_tokens: u64,
+        output_tokens: u64,
+        cache_read_input_tokens: u64,
+        cache_creation_input_tokens: u64,
+        cost_usd: Option<f64>,
+        model: Option<String>,
+    },
+}
+
+impl AgentEvent {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Message { .. } => "message",
+            Self::ToolCall { .. } => "tool_call",
+            Self::ToolResult { .. } => "tool_result",
+            Self::ApprovalRequested { .. } => "approval_requested",
+            Self::ApprovalResolved { .. } => "approval_resolved",
+            Self::FileEdit { .. } => "file_edit",
+            Self::Error { .. } => "error",
+            Self::Usage { .. } => "usage",
+        }
+    }
+}
+
+impl From<&TokenUsage> for AgentEvent {
+    fn from(usage: &TokenUsage) -> Self {
+        Self::Usage {
+            input_tokens: usage.input_tokens,
+            output_tokens: usage.output_tokens,
+            cache_read_input_tokens: usage.cache_read_input_tokens,
+            cache_creation_input_tokens: usage.cache_creation_input_tokens,
+            cost_usd: usage.cost_usd,
+            model: usage.model.clone(),
+        }
+    }
+}
+
+/// Turns the messages of an execution's store into [`AgentEvent`]s.
+///
+/// Messages are streamed into their entry piece by piece, so a message is
+/// only emitted once a later entry starts or the execution finishes. Tool
+/// calls are emitted as soon as they appear, and their result once they
+/// reach a final status.
+#[derive(Debug, Default)]
+pub struct AgentEventProjector {
+    /// Latest version of messages not emitted yet
+    pending_messages: BTreeMap<usize, (MessageRole, String)>,
+    /// Last emitted content of each message
+    emitted_messages: HashMap<usize, String>,
+    /// Tool calls seen, and whether their result was emitted
+    tool_calls: HashMap<usize, bool>,
+    /// Approval requested for a tool call and not resolved yet
+    pending_approvals: HashMap<usize, String>,
+}
+
+impl AgentEventProjector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, msg: &LogMsg) -> Vec<AgentEvent> {
+        match msg {
+            LogMsg::JsonPatch(patch) => match extract_normalized_entry_from_patch(patch) {
+                Some((index, entry)) => self.push_entry(index, entry),
+                None => vec![],
+            },
+            LogMsg::Usage(usage) => vec![usage.into()],
+            LogMsg::Finished => self.finish(),
+            LogMsg::Stdout(_) | LogMsg::Stderr(_) | LogMsg::SessionId(_) => vec![],
+        }
+    }
+
+    /// Project a stream of log messages, ending once the execution finishes
+    pub fn project(
+        logs: BoxStream<'static, Result<LogMsg, std::io::Error>>,
+    ) -> BoxStream<'static, Result<AgentEvent, std::io::Error>> {
+        stream::unfold(Some((logs, Self::new())), |state| async move {
+            let (mut logs, mut projector) = state?;
+            let (events, next) = match logs.next().await {
+                Some(Ok(LogMsg::Finished)) | None => (projector.finish(), None),
+                Some(Ok(msg)) => (projector.push(&msg), Some((logs, projector))),
+                Some(Err(e)) => return Some((vec![Err(e)], None)),
+            };
+            Some((events.into_iter().map(Ok).collect::<Vec<_>>(), next))
+        })
+        .flat_map(stream::iter)
+        .boxed()
+    }
+
+    /// Emit the messages still pending
+    pub fn finish(&mut self) -> Vec<AgentEvent> {
+        self.flush_messages_before(usize::MAX)
+    }
+
+    fn push_entry(&mut self, index: usize, entry: NormalizedEntry) -> Vec<AgentEvent> {
+        let mut events = self.flush_messages_before(index);
+
+        let role = match &entry.entry_type {
+            NormalizedEntryType::UserMessage | NormalizedEntryType::UserFeedback { .. } => {
+                Some(MessageRole::User)
+            }
+            NormalizedEntryType::AssistantMessage => Some(MessageRole::Assistant),
+            NormalizedEntryType::SystemMessage => Some(MessageRole::System),
+            NormalizedEntryType::Thinking => Some(MessageRole::Thinking),
+            _ => None,
+        };
+        if let Some(role) = role {
+            if self.emitted_messages.contains_key(&index) {
+                // Revised after it was emitted
+                events.extend(self.emit_message(index, role, entry.content));
+            } else {
+                self.pending_messages.insert(index, (role, entry.content));
+            }
+            return events;
+        }
+
+        match entry.entry_type {
+            NormalizedEntryType::ToolUse {
+                tool_name,
+                action_type,
+                status,
+            } => {
+                if !self.tool_calls.contains_key(&index) {
+                    self.tool_calls.insert(index, false);
+                    events.push(AgentEvent::ToolCall {
+                        entry_index: index,
+                        tool_name: tool_name.clone(),
+                        action: action_type.clone(),
+                        content: entry.content,
+                    });
+                }
+                if let ToolStatus::PendingApproval {
+                    approval_id,
+                    timeout_at,
+                    ..
+                } = &status
+                {
+                    if self.pending_approvals.get(&index) != Some(approval_id) {
+                        self.pending_approvals.insert(index, approval_id.clone());
+                        events.push(AgentEvent::ApprovalRequested {
+                            entry_index: index,
+                            tool_name: tool_name.clone(),
+                            approval_id: approval_id.clone(),
+                            action: action_type.clone(),
+                            timeout_at: *timeout_at,
+                        });
+                    }
+                } else if let Some(approval_id) = self.pending_approvals.remove(&index) {
+                    events.push(AgentEvent::ApprovalResolved {
+                        entry_index: index,
+                        approval_id,
+                        approved: !matches!(
+                            status,
+                            ToolStatus::Denied { .. } | ToolStatus::TimedOut
+                        ),
+                    });
+                }
+                let finished = matches!(
+                    status,
+                    ToolStatus::Success
+                        | ToolStatus::Failed
+                        | ToolStatus::Denied { .. }
+                        | ToolStatus::TimedOut
+                );
+                if finished && self.tool_calls.insert(index, true) == Some(false) {
+                    events.push(AgentEvent::ToolResult {
+                        entry_index: index,
+                        tool_name,
+                        status: status.clone(),
+                        action: action_type.clone(),
+                    });
+                    if let (ToolStatus::Success, ActionType::FileEdit { path, changes }) =
+                        (status, action_type)
+                    {
+                        events.push(AgentEvent::FileEdit {
+                            entry_index: index,
+                            path,
+                            changes,
+                        });
+                    }
+                }
+            }
+            NormalizedEntryType::ErrorMessage { error_type } => events.push(AgentEvent::Error {
+                entry_index: index,
+                error_type,
+                content: entry.content,
+            }),
+            _ => {}
+        }
+        events
+    }
+
+    fn flush_messages_before(&mut self, index: usize) -> Vec<AgentEvent> {
+        let mut events = Vec::new();
+        while let Some(entry) = self.pending_messages.first_entry() {
+            if *entry.key() >= index {
+                break;
+            }
+            let (entry_index, (role, content)) = entry.remove_entry();
+            events.extend(self.emit_message(entry_index, role, content));
+        }
+        events
+    }
+
+    fn emit_message(
+        &mut self,
+        entry_index: usize,
+        role: MessageRole,
+        content: String,
+    ) -> Option<AgentEvent> {
+        if content.trim().is_empty() || self.emitted_messages.get(&entry_index) == Some(&content) {
+            return None;
+        }
+        self.emitted_messages.insert(entry_index, content.clone());
+        Some(AgentEvent::Message {
+            entry_index,
+            role,
+            content,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::utils::ConversationPatch;
+
+    fn entry(entry_type: NormalizedEntryType, content: &str) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content: content.to_string(),
+            metadata: None,
+        }
+    }
+
+    fn edit(status: ToolStatus) -> NormalizedEntry {
+        entry(
+            NormalizedEntryType::ToolUse {
+                tool_name: "edit".to_string(),
+                action_type: ActionType::FileEdit {
+                    path: "src/lib.rs".to_string(),
+                    changes: vec![],
+                },
+                status,
+            },
+            "src/lib.rs",
+        )
+    }
+
+    fn types(events: &[AgentEvent]) -> Vec<&'static str> {
+        events.iter().map(AgentEvent::type_name).collect()
+    }
+
+    #[test]
+    fn emits_messages_once_complete() {
+        let mut projector = AgentEventProjector::new();
+        let partial = ConversationPatch::add_normalized_entry(
+            0,
+            entry(NormalizedEntryType::AssistantMessage, "Let me"),
+        );
+        let complete = ConversationPatch::replace(
+            0,
+            entry(NormalizedEntryType::AssistantMessage, "Let me look."),
+        );
+        assert!(projector.push(&LogMsg::JsonPatch(partial)).is_empty());
+        assert!(projector.push(&LogMsg::JsonPatch(complete)).is_empty());
+
+        let events = projector.push(&LogMsg::JsonPatch(ConversationPatch::add_normalized_entry(
+            1,
+            edit(ToolStatus::Created),
+        )));
+        assert_eq!(types(&events), vec!["message", "tool_call"]);
+        assert!(matches!(
+            &events[0],
+            AgentEvent::Message { content, role: MessageRole::Assistant, .. } if content == "Let me look."
+        ));
+
+        let events = projector.push(&LogMsg::JsonPatch(ConversationPatch::replace(
+            1,
+            edit(ToolStatus::Success),
+        )));
+        assert_eq!(types(&events), vec!["tool_result", "file_edit"]);
+        // A repeated final status does not repeat the result
+        assert!(
+            projector
+                .push(&LogMsg::JsonPatch(ConversationPatch::replace(
+                    1,
+                    edit(ToolStatus::Success),
+                )))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn surfaces_approvals() {
+        let mut projector = AgentEventProjector::new();
+        projector.push(&LogMsg::JsonPatch(ConversationPatch::add_normalized_entry(
+            0,
+            edit(ToolStatus::Created),
+        )));
+        let pending = ToolStatus::PendingApproval {
+            approval_id: "approval-1".to_string(),
+            requested_at: chrono::Utc::now(),
+            timeout_at: chrono::Utc::now(),
+        };
+        let events = projector.push(&LogMsg::JsonPatch(ConversationPatch::replace(
+            0,
+            edit(pending.clone()),
+        )));
+        assert_eq!(types(&events), vec!["approval_requested"]);
+        // Re-sent entries do not repeat the request
+        assert!(
+            projector
+                .push(&LogMsg::JsonPatch(ConversationPatch::replace(
+                    0,
+                    edit(pending)
+                )))
+                .is_empty()
+        );
+
+        let events = projector.push(&LogMsg::JsonPatch(ConversationPatch::replace(
+            0,
+            edit(ToolStatus::Denied { reason: None }),
+        )));
+        assert_eq!(types(&events), vec!["approval_resolved", "tool_result"]);
+        assert!(matches!(
+            &events[0],
+            AgentEvent::ApprovalResolved { approved: false, approval_id, .. } if approval_id == "approval-1"
+        ));
+    }
+
+    #[test]
+    fn flushes_pending_messages_on_finish() {
+        let mut projector = AgentEventProjector::new();
+        projector.push(&LogMsg::JsonPatch(ConversationPatch::add_normalized_entry(
+            0,
+            entry(NormalizedEntryType::AssistantMessage, "Done."),
+        )));
+        let events = projector.push(&LogMsg::Usage(TokenUsage::default()));
+        assert_eq!(types(&events), vec!["usage"]);
+        assert_eq!(types(&projector.push(&LogMsg::Finished)), vec!["message"]);
+    }
+}
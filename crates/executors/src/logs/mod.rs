@@ -3,7 +3,9 @@ use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use workspace_utils::approvals::ApprovalStatus;
 
+pub mod events;
 pub mod plain_text_processor;
+pub mod provider_errors;
 pub mod stderr_processor;
 pub mod utils;
 
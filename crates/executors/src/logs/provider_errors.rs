@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+use workspace_utils::log_msg::LogMsg;
+
+use crate::logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch};
+
+/// Phrases of model providers' rate limit, quota and outage errors, lowercase
+const PROVIDER_FAILURE_MARKERS: &[&str] = &[
+    "rate limit",
+    "rate_limit",
+    "ratelimit",
+    "too many requests",
+    "overloaded",
+    "service unavailable",
+    "insufficient_quota",
+    "quota exceeded",
+    "exceeded your current quota",
+    "429",
+    "529",
+    "503",
+];
+
+/// The error line showing that a failed run was refused by the model
+/// provider, rather than failing on its own. Only the agent's stderr and the
+/// errors it reported are checked, so that output of commands the agent ran
+/// cannot trigger a fallback.
+pub fn find_provider_failure(history: &[LogMsg]) -> Option<String> {
+    history.iter().rev().find_map(|msg| {
+        let text = match msg {
+            LogMsg::Stderr(line) => line.clone(),
+            LogMsg::JsonPatch(patch) => match extract_normalized_entry_from_patch(patch) {
+                Some((_, entry))
+                    if matches!(entry.entry_type, NormalizedEntryType::ErrorMessage { .. })
+                        || (matches!(entry.entry_type, NormalizedEntryType::AssistantMessage)
+                            && entry.content.starts_with("API Error")) =>
+                {
+                    entry.content
+                }
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let lower = text.to_lowercase();
+        PROVIDER_FAILURE_MARKERS
+            .iter()
+            .any(|marker| lower.contains(marker))
+            .then(|| text.trim().chars().take(500).collect())
+    })
+}
+
+/// Phrases providers put before the wait they ask for, lowercase
+const RETRY_AFTER_MARKERS: &[&str] = &["retry-after:", "retry after", "try again in"];
+
+/// How long a provider error asks to wait before retrying, e.g. "Please try
+/// again in 1m30s" or "retry-after: 20"; a bare number is in seconds
+pub fn parse_retry_after(error: &str) -> Option<Duration> {
+    let lower = error.to_lowercase();
+    RETRY_AFTER_MARKERS.iter().find_map(|marker| {
+        let start = lower.find(marker)? + marker.len();
+        parse_wait(lower[start..].trim_start())
+    })
+}
+
+fn parse_wait(text: &str) -> Option<Duration> {
+    let mut total = 0.0;
+    let mut rest = text;
+    loop {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let Ok(value) = rest[..number_len].parse::<f64>() else {
+            break;
+        };
+        let after = rest[number_len..].trim_start();
+        let unit_len = after
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(after.len());
+        let seconds = match &after[..unit_len] {
+            "ms" => value / 1000.0,
+            "" | "s" | "sec" | "secs" | "second" | "seconds" => value,
+            "m" | "min" | "mins" | "minute" | "minutes" => value * 60.0,
+            "h" | "hr" | "hour" | "hours" => value * 3600.0,
+            _ => break,
+        };
+        total += seconds;
+        rest = &after[unit_len..];
+        // A bare number ends the wait; "1m30s" carries on
+        if unit_len == 0 || !rest.starts_with(|c: char| c.is_ascii_digit()) {
+            break;
+        }
+    }
+    (total > 0.0).then(|| Duration::from_secs_f64(total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::{NormalizedEntry, NormalizedEntryError, utils::ConversationPatch};
+
+    fn entry(entry_type: NormalizedEntryType, content: &str) -> LogMsg {
+        LogMsg::JsonPatch(ConversationPatch::add_normalized_entry(
+            0,
+            NormalizedEntry {
+                timestamp: None,
+                entry_type,
+                content: content.to_string(),
+                metadata: None,
+            },
+        ))
+    }
+
+    #[test]
+    fn detects_provider_errors_only() {
+        let overloaded = entry(
+            NormalizedEntryType::AssistantMessage,
+            "API Error: 529 {\"type\":\"overloaded_error\"}",
+        );
+        assert!(find_provider_failure(&[overloaded]).is_some());
+        assert!(
+            find_provider_failure(&[LogMsg::Stderr("Error: 429 Too Many Requests".to_string())])
+                .is_some()
+        );
+        assert!(
+            find_provider_failure(&[entry(
+                NormalizedEntryType::ErrorMessage {
+                    error_type: NormalizedEntryError::Other
+                },
+                "stream disconnected: rate limit reached"
+            )])
+            .is_some()
+        );
+
+        // Tool output and ordinary failures do not count
+        assert!(
+            find_provider_failure(&[
+                LogMsg::Stdout("HTTP/1.1 503 Service Unavailable".to_string()),
+                entry(
+                    NormalizedEntryType::AssistantMessage,
+                    "The server returned 503, let me check."
+                ),
+                LogMsg::Stderr("error: could not compile `db`".to_string()),
+            ])
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn parses_retry_hints() {
+        assert_eq!(
+            parse_retry_after("Rate limit reached. Please try again in 1m30s."),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(
+            parse_retry_after("Error: 429 Too Many Requests (retry-after: 20)"),
+            Some(Duration::from_secs(20))
+        );
+        assert_eq!(
+            parse_retry_after("quota exceeded, retry after 250ms"),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(parse_retry_after("API Error: 529 overloaded"), None);
+        assert_eq!(parse_retry_after("try again in a moment"), None);
+    }
+}
@@ -45,6 +45,18 @@ impl McpConfig {
     }
 }
 
+/// A stdio MCP server in the canonical `.mcp.json` form, such as one
+/// configured for a project. Executors that take servers per run translate it
+/// into their own format.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct McpServerConfig {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
 /// Read an agent's external config file (JSON or TOML) and normalize it to serde_json::Value.
 pub async fn read_agent_config(
     config_path: &std::path::Path,
@@ -293,7 +305,11 @@ impl CodingAgent {
         use Adapter::*;
 
         let adapter = match self {
-            CodingAgent::ClaudeCode(_) | CodingAgent::Amp(_) => Passthrough,
+            CodingAgent::ClaudeCode(_)
+            | CodingAgent::Amp(_)
+            | CodingAgent::Aider(_)
+            | CodingAgent::Openhands(_)
+            | CodingAgent::Custom(_) => Passthrough,
             CodingAgent::QwenCode(_) | CodingAgent::Gemini(_) => Gemini,
             CodingAgent::CursorAgent(_) => Cursor,
             CodingAgent::Codex(_) => Codex,
@@ -43,6 +43,9 @@ pub enum ProfileError {
 
     #[error("No available executor profile")]
     NoAvailableExecutorProfile,
+
+    #[error("Executor profile '{0}' not found")]
+    NotFound(ExecutorProfileId),
 }
 
 lazy_static! {
@@ -363,18 +366,108 @@ impl ExecutorConfigs {
                 )));
             }
 
-            // Ensure configuration names don't conflict with reserved words
-            for config_name in profile.configurations.keys() {
-                if config_name.starts_with("__") {
-                    return Err(ProfileError::Validation(format!(
-                        "Configuration name '{config_name}' is reserved (starts with '__')"
-                    )));
-                }
+            for (config_name, config) in &profile.configurations {
+                Self::validate_profile(*executor_key, config_name, config)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate a single configuration against the executor it is stored under
+    pub fn validate_profile(
+        executor: BaseCodingAgent,
+        variant: &str,
+        config: &CodingAgent,
+    ) -> Result<(), ProfileError> {
+        // Ensure configuration names don't conflict with reserved words
+        if variant.starts_with("__") {
+            return Err(ProfileError::Validation(format!(
+                "Configuration name '{variant}' is reserved (starts with '__')"
+            )));
+        }
+        if BaseCodingAgent::from(config) != executor {
+            return Err(ProfileError::Validation(format!(
+                "Configuration '{variant}' of executor '{executor}' is a '{config}' configuration"
+            )));
+        }
+        config.validate().map_err(|e| {
+            ProfileError::Validation(format!("Configuration '{executor}:{variant}': {e}"))
+        })
+    }
+
+    /// Add or replace one configuration. Call [`Self::save_overrides`] to
+    /// persist the change.
+    pub fn upsert_profile(
+        &mut self,
+        profile_id: &ExecutorProfileId,
+        config: CodingAgent,
+    ) -> Result<(), ProfileError> {
+        let variant = canonical_variant_key(profile_id.variant.as_deref().unwrap_or("DEFAULT"));
+        Self::validate_profile(profile_id.executor, &variant, &config)?;
+        match self.executors.get_mut(&profile_id.executor) {
+            Some(executor) => {
+                executor.configurations.insert(variant, config);
+            }
+            None if variant == "DEFAULT" => {
+                self.executors.insert(
+                    profile_id.executor,
+                    ExecutorConfig::new_with_default(config),
+                );
+            }
+            None => {
+                return Err(ProfileError::Validation(format!(
+                    "Executor '{}' needs a DEFAULT configuration first",
+                    profile_id.executor
+                )));
             }
         }
         Ok(())
     }
 
+    /// Remove a user-defined configuration. Built-in configurations can be
+    /// changed but not removed.
+    pub fn remove_profile(
+        &mut self,
+        profile_id: &ExecutorProfileId,
+    ) -> Result<CodingAgent, ProfileError> {
+        let variant = canonical_variant_key(profile_id.variant.as_deref().unwrap_or("DEFAULT"));
+        let is_built_in = Self::from_defaults()
+            .executors
+            .get(&profile_id.executor)
+            .is_some_and(|executor| {
+                executor
+                    .configurations
+                    .keys()
+                    .any(|key| canonical_variant_key(key) == variant)
+            });
+        if is_built_in {
+            return Err(ProfileError::CannotDeleteBuiltInConfig {
+                executor: profile_id.executor,
+                variant,
+            });
+        }
+        let executor = self
+            .executors
+            .get_mut(&profile_id.executor)
+            .ok_or_else(|| ProfileError::NotFound(profile_id.clone()))?;
+        // The DEFAULT configuration of a user-defined executor goes last,
+        // together with the executor
+        if variant == "DEFAULT" && executor.configurations.len() > 1 {
+            return Err(ProfileError::Validation(format!(
+                "Remove the other configurations of executor '{}' before its DEFAULT",
+                profile_id.executor
+            )));
+        }
+        let removed = executor
+            .configurations
+            .remove(&variant)
+            .ok_or_else(|| ProfileError::NotFound(profile_id.clone()))?;
+        if executor.configurations.is_empty() {
+            self.executors.remove(&profile_id.executor);
+        }
+        Ok(removed)
+    }
+
     /// Load from the new v3 defaults
     pub fn from_defaults() -> Self {
         serde_json::from_str(DEFAULT_PROFILES_JSON).unwrap_or_else(|e| {
@@ -0,0 +1,261 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+};
+
+use sha2::{Digest, Sha256};
+use tokio::{fs, io::AsyncWriteExt, process::Command, sync::Mutex};
+use uuid::Uuid;
+use workspace_utils::shell::resolve_executable_path;
+
+use crate::{command::CommandParts, executors::ExecutorError};
+
+/// Serializes bootstrap builds, so that attempts starting together build a
+/// missing image once
+static BUILD_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// Docker container a coding agent runs in instead of on the host. The agent
+/// sees the attempt's worktree and the git objects and refs it commits to,
+/// mounted at their host paths so nothing in the agent's output needs
+/// rewriting. The rest of the host, including the repository's hooks and
+/// config, stays out of reach.
+///
+/// The optional bootstrap script is baked into a layer on top of the image,
+/// e.g. to install the agent's CLI. The layer is tagged by a hash of the
+/// image and script, and only built when missing.
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    image: String,
+    bootstrap_script: Option<String>,
+    name_prefix: String,
+}
+
+impl Sandbox {
+    pub fn new(
+        image: impl Into<String>,
+        bootstrap_script: Option<String>,
+        name_prefix: impl Into<String>,
+    ) -> Self {
+        Self {
+            image: image.into(),
+            bootstrap_script: bootstrap_script.filter(|script| !script.trim().is_empty()),
+            name_prefix: name_prefix.into(),
+        }
+    }
+
+    pub fn base_command() -> &'static str {
+        "docker"
+    }
+
+    /// Turn `parts` into a `docker run` of it in a fresh container. Variables
+    /// named in `env_names` are forwarded from the environment of the docker
+    /// client, and `mounts` are bound in addition to the worktree. With
+    /// `read_only` the worktree and its repository are mounted read-only.
+    pub async fn wrap<'a>(
+        &self,
+        parts: CommandParts,
+        current_dir: &Path,
+        env_names: impl Iterator<Item = &'a str>,
+        mounts: &[&Path],
+        read_only: bool,
+    ) -> Result<(PathBuf, Vec<String>), ExecutorError> {
+        let docker = resolve_executable_path(Self::base_command())
+            .await
+            .ok_or_else(|| ExecutorError::ExecutableNotFound {
+                program: Self::base_command().to_string(),
+            })?;
+        let image = self.ensure_image(&docker).await?;
+
+        let mut args = vec![
+            "run".to_string(),
+            "--rm".to_string(),
+            "-i".to_string(),
+            "--name".to_string(),
+            format!("{}-{}", self.name_prefix, Uuid::new_v4().simple()),
+        ];
+        // Files the agent creates stay owned by the worktree's owner
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let metadata = fs::metadata(current_dir).await.map_err(ExecutorError::Io)?;
+            args.extend([
+                "--user".to_string(),
+                format!("{}:{}", metadata.uid(), metadata.gid()),
+                "-e".to_string(),
+                "HOME=/tmp".to_string(),
+            ]);
+        }
+        for mount in sandbox_mounts(current_dir, mounts, read_only).await {
+            args.extend(["-v".to_string(), mount]);
+        }
+        args.extend(["-w".to_string(), current_dir.to_string_lossy().into_owned()]);
+        for name in env_names {
+            args.extend(["-e".to_string(), name.to_string()]);
+        }
+        args.push(image);
+
+        let (program, program_args) = parts.into_parts();
+        args.push(program);
+        args.extend(program_args);
+        Ok((docker, args))
+    }
+
+    /// Tag of the image with the bootstrap layer
+    fn bootstrap_tag(&self, script: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.image.as_bytes());
+        hasher.update([0]);
+        hasher.update(script.as_bytes());
+        let hex = hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        format!("vibe-kanban-sandbox:{}", &hex[..16])
+    }
+
+    fn dockerfile(&self, script: &str) -> String {
+        format!(
+            "# syntax=docker/dockerfile:1\nFROM {}\nRUN <<'VIBE_KANBAN_BOOTSTRAP'\n{}\nVIBE_KANBAN_BOOTSTRAP\n",
+            self.image,
+            script.trim_end()
+        )
+    }
+
+    /// The image to run, building the bootstrap layer first if needed
+    async fn ensure_image(&self, docker: &Path) -> Result<String, ExecutorError> {
+        let Some(script) = &self.bootstrap_script else {
+            return Ok(self.image.clone());
+        };
+        let tag = self.bootstrap_tag(script);
+
+        let _guard = BUILD_LOCK.lock().await;
+        let exists = Command::new(docker)
+            .args(["image", "inspect", &tag])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await
+            .map_err(ExecutorError::Io)?
+            .success();
+        if exists {
+            return Ok(tag);
+        }
+
+        tracing::info!("Building sandbox image {} from {}", tag, self.image);
+        let mut build = Command::new(docker)
+            .args(["build", "-t", &tag, "-"])
+            .kill_on_drop(true)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(ExecutorError::Io)?;
+        if let Some(mut stdin) = build.stdin.take() {
+            stdin
+                .write_all(self.dockerfile(script).as_bytes())
+                .await
+                .map_err(ExecutorError::Io)?;
+        }
+        let output = build.wait_with_output().await.map_err(ExecutorError::Io)?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(ExecutorError::Io(std::io::Error::other(format!(
+                "Building the sandbox image failed: {}",
+                stderr.trim()
+            ))));
+        }
+        Ok(tag)
+    }
+}
+
+/// `-v` values for the worktree and the parts of its repository a commit
+/// writes to. The repository's git directory is mounted read-only first, so
+/// that hooks and config, which run on the host, cannot be changed. A
+/// read-only run gets nothing of the worktree or repository writable.
+async fn sandbox_mounts(current_dir: &Path, extra: &[&Path], read_only: bool) -> Vec<String> {
+    let mut paths = vec![(current_dir.to_path_buf(), !read_only)];
+    if let Some((common_dir, git_dir)) = worktree_git_dirs(current_dir).await {
+        paths.push((common_dir.clone(), false));
+        if !read_only {
+            for writable in ["objects", "refs", "logs"] {
+                let path = common_dir.join(writable);
+                if fs::try_exists(&path).await.unwrap_or(false) {
+                    paths.push((path, true));
+                }
+            }
+        }
+        paths.push((git_dir, !read_only));
+    }
+    paths.extend(extra.iter().map(|path| (path.to_path_buf(), true)));
+
+    paths
+        .into_iter()
+        .map(|(path, writable)| {
+            let path = path.to_string_lossy();
+            if writable {
+                format!("{path}:{path}")
+            } else {
+                format!("{path}:{path}:ro")
+            }
+        })
+        .collect()
+}
+
+/// For a worktree, the repository's git directory and the worktree's own
+/// directory within it, as named by the worktree's `.git` file
+async fn worktree_git_dirs(current_dir: &Path) -> Option<(PathBuf, PathBuf)> {
+    let contents = fs::read_to_string(current_dir.join(".git")).await.ok()?;
+    let git_dir = current_dir.join(contents.trim().strip_prefix("gitdir:")?.trim());
+    // <repo>/.git/worktrees/<name>
+    let common_dir = git_dir.parent()?.parent()?.to_path_buf();
+    Some((common_dir, git_dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bootstrap_layer_is_tagged_by_image_and_script() {
+        let sandbox = Sandbox::new("node:22", Some("npm i -g x".to_string()), "test");
+        let tag = sandbox.bootstrap_tag("npm i -g x");
+        assert!(tag.starts_with("vibe-kanban-sandbox:"));
+        assert_eq!(tag, sandbox.bootstrap_tag("npm i -g x"));
+        assert_ne!(tag, sandbox.bootstrap_tag("npm i -g y"));
+        assert_ne!(
+            tag,
+            Sandbox::new("node:20", None, "test").bootstrap_tag("npm i -g x")
+        );
+        assert!(sandbox.dockerfile("npm i -g x\n").contains(
+            "FROM node:22\nRUN <<'VIBE_KANBAN_BOOTSTRAP'\nnpm i -g x\nVIBE_KANBAN_BOOTSTRAP\n"
+        ));
+        assert!(
+            Sandbox::new("node:22", Some("  ".to_string()), "test")
+                .bootstrap_script
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn read_only_runs_mount_the_worktree_read_only() {
+        let worktree = Path::new("/nonexistent/worktree");
+        let cache = Path::new("/nonexistent/cache");
+        assert_eq!(
+            sandbox_mounts(worktree, &[cache], false).await,
+            vec![
+                "/nonexistent/worktree:/nonexistent/worktree",
+                "/nonexistent/cache:/nonexistent/cache",
+            ]
+        );
+        // Directories outside the worktree, such as the agent's own config,
+        // stay writable
+        assert_eq!(
+            sandbox_mounts(worktree, &[cache], true).await,
+            vec![
+                "/nonexistent/worktree:/nonexistent/worktree:ro",
+                "/nonexistent/cache:/nonexistent/cache",
+            ]
+        );
+    }
+}
@@ -16,6 +16,9 @@ pub async fn kill_process_group(child: &mut AsyncGroupChild) -> Result<(), Conta
             let pgid = getpgid(Some(Pid::from_raw(pid as i32)))
                 .map_err(|e| ContainerError::KillFailed(std::io::Error::other(e)))?;
 
+            // a suspended group only handles the signals below once continued
+            let _ = killpg(pgid, Signal::SIGCONT);
+
             for sig in [Signal::SIGINT, Signal::SIGTERM, Signal::SIGKILL] {
                 if let Err(e) = killpg(pgid, sig) {
                     tracing::warn!(
@@ -42,3 +45,68 @@ pub async fn kill_process_group(child: &mut AsyncGroupChild) -> Result<(), Conta
     let _ = child.wait().await;
     Ok(())
 }
+
+/// Kill the process group a previous server run spawned as `pid`. Nothing
+/// is signalled unless `pid` still leads a process group, which spawned
+/// executions always do, so a reused pid is not mistaken for it.
+pub fn kill_orphaned_process_group(pid: i64) -> Result<(), ContainerError> {
+    #[cfg(unix)]
+    {
+        let pid = Pid::from_raw(pid as i32);
+        match getpgid(Some(pid)) {
+            Ok(pgid) if pgid == pid => {
+                let _ = killpg(pgid, Signal::SIGCONT);
+                killpg(pgid, Signal::SIGKILL)
+                    .map_err(|e| ContainerError::KillFailed(std::io::Error::other(e)))
+            }
+            // Already gone, or the pid now belongs to another process
+            _ => Ok(()),
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = pid;
+        Ok(())
+    }
+}
+
+/// Suspend the whole process group so it can be continued later.
+pub fn suspend_process_group(child: &mut AsyncGroupChild) -> Result<(), ContainerError> {
+    #[cfg(unix)]
+    {
+        signal_process_group(child, Signal::SIGSTOP)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = child;
+        Err(ContainerError::Other(anyhow::anyhow!(
+            "Pausing executions is only supported on Unix"
+        )))
+    }
+}
+
+/// Continue a process group stopped by [`suspend_process_group`].
+pub fn resume_process_group(child: &mut AsyncGroupChild) -> Result<(), ContainerError> {
+    #[cfg(unix)]
+    {
+        signal_process_group(child, Signal::SIGCONT)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = child;
+        Err(ContainerError::Other(anyhow::anyhow!(
+            "Resuming executions is only supported on Unix"
+        )))
+    }
+}
+
+#[cfg(unix)]
+fn signal_process_group(child: &mut AsyncGroupChild, sig: Signal) -> Result<(), ContainerError> {
+    let pid = child
+        .inner()
+        .id()
+        .ok_or_else(|| ContainerError::Io(std::io::Error::other("Process has already exited")))?;
+    let pgid = getpgid(Some(Pid::from_raw(pid as i32)))
+        .map_err(|e| ContainerError::Io(std::io::Error::other(e)))?;
+    killpg(pgid, sig).map_err(|e| ContainerError::Io(std::io::Error::other(e)))
+}
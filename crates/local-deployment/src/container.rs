@@ -8,26 +8,38 @@ use std::{
 
 use anyhow::anyhow;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use command_group::AsyncGroupChild;
 use db::{
     DBService,
     models::{
+        attempt_plan::{AttemptPlan, AttemptPlanStatus},
         draft::{Draft, DraftType},
         execution_process::{
             ExecutionContext, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
         },
+        execution_process_handle::ExecutionProcessHandle,
         executor_session::ExecutorSession,
         image::TaskImage,
         merge::Merge,
         project::Project,
+        project_mcp_server::ProjectMcpServer,
+        project_repository::ProjectRepository,
+        project_sandbox::ProjectSandbox,
+        project_script::ProjectScript,
+        project_turn_test::ProjectTurnTest,
+        proposed_task::ProposedTask,
+        queued_follow_up::QueuedFollowUp,
         task::{Task, TaskStatus},
         task_attempt::TaskAttempt,
+        turn_test_run::TurnTestRun,
     },
 };
 use deployment::DeploymentError;
 use executors::{
-    actions::{Executable, ExecutorAction},
+    actions::{Executable, ExecutorAction, ExecutorActionType, script::ScriptRequest},
     approvals::{ExecutorApprovalService, NoopExecutorApprovalService},
+    env::write_read_only_hooks,
     executors::BaseCodingAgent,
     logs::{
         NormalizedEntryType,
@@ -36,23 +48,34 @@ use executors::{
             patch::{escape_json_pointer_segment, extract_normalized_entry_from_patch},
         },
     },
+    sandbox::Sandbox,
 };
-use futures::{FutureExt, StreamExt, TryStreamExt, stream::select};
+use futures::{FutureExt, StreamExt, TryStreamExt, future::BoxFuture, stream::select};
 use serde_json::json;
 use services::services::{
     analytics::AnalyticsContext,
     approvals::{Approvals, executor_approvals::ExecutorApprovalBridge},
+    commit_signing,
     config::Config,
-    container::{ContainerError, ContainerRef, ContainerService},
+    container::{ContainerError, ContainerRef, ContainerService, project_script_request},
     diff_stream::{self, DiffStreamHandle},
-    git::{Commit, DiffTarget, GitService},
+    execution_queue::ExecutionQueue,
+    git::{Commit, DIFF_HUNK_CONTEXT_LINES, DiffTarget, GitService},
+    hosting,
     image::ImageService,
     notification::NotificationService,
+    proposed_tasks::parse_proposals,
+    secrets::SecretsService,
+    turn_tests::{feedback_prompt, parse_test_counts},
     worktree_manager::WorktreeManager,
 };
-use tokio::{sync::RwLock, task::JoinHandle};
+use tokio::{
+    sync::{Notify, RwLock},
+    task::JoinHandle,
+};
 use tokio_util::io::ReaderStream;
 use utils::{
+    diff::{Diff, DiffFileHunks, DiffFilePage},
     log_msg::LogMsg,
     msg_store::MsgStore,
     text::{git_branch_id, short_uuid},
@@ -61,16 +84,62 @@ use uuid::Uuid;
 
 use crate::command;
 
+/// Where an attempt's diff is read from
+enum AttemptDiffSource {
+    Merged {
+        repo_path: PathBuf,
+        merge_commit: String,
+    },
+    Branch {
+        repo_path: PathBuf,
+    },
+    Worktree {
+        worktree_path: PathBuf,
+        base_commit: Commit,
+    },
+}
+
+impl AttemptDiffSource {
+    fn target<'a>(&'a self, task_attempt: &'a TaskAttempt) -> DiffTarget<'a> {
+        match self {
+            Self::Merged {
+                repo_path,
+                merge_commit,
+            } => DiffTarget::Commit {
+                repo_path,
+                commit_sha: merge_commit,
+            },
+            Self::Branch { repo_path } => DiffTarget::Branch {
+                repo_path,
+                branch_name: &task_attempt.branch,
+                base_branch: &task_attempt.target_branch,
+            },
+            Self::Worktree {
+                worktree_path,
+                base_commit,
+            } => DiffTarget::Worktree {
+                worktree_path,
+                base_commit,
+            },
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct LocalContainerService {
     db: DBService,
     child_store: Arc<RwLock<HashMap<Uuid, Arc<RwLock<AsyncGroupChild>>>>>,
     msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
+    /// Wakes an execution's exit monitor to re-read its timeout
+    timeout_changed: Arc<RwLock<HashMap<Uuid, Arc<Notify>>>>,
     config: Arc<RwLock<Config>>,
     git: GitService,
     image_service: ImageService,
+    secrets: SecretsService,
     analytics: Option<AnalyticsContext>,
     approvals: Approvals,
+    execution_queue: ExecutionQueue,
+    events_msg_store: Arc<MsgStore>,
 }
 
 impl LocalContainerService {
@@ -80,20 +149,28 @@ impl LocalContainerService {
         config: Arc<RwLock<Config>>,
         git: GitService,
         image_service: ImageService,
+        secrets: SecretsService,
         analytics: Option<AnalyticsContext>,
         approvals: Approvals,
+        execution_queue: ExecutionQueue,
+        events_msg_store: Arc<MsgStore>,
     ) -> Self {
         let child_store = Arc::new(RwLock::new(HashMap::new()));
+        let timeout_changed = Arc::new(RwLock::new(HashMap::new()));
 
         LocalContainerService {
             db,
             child_store,
             msg_stores,
+            timeout_changed,
             config,
             git,
             image_service,
+            secrets,
             analytics,
             approvals,
+            execution_queue,
+            events_msg_store,
         }
     }
 
@@ -112,9 +189,14 @@ impl LocalContainerService {
         map.remove(id);
     }
 
+    async fn timeout_notifier(&self, id: Uuid) -> Arc<Notify> {
+        let mut map = self.timeout_changed.write().await;
+        map.entry(id).or_default().clone()
+    }
+
     /// A context is finalized when
     /// - The next action is None (no follow-up actions)
-    /// - The run reason is not DevServer
+    /// - The run reason is not DevServer or TestScript
     fn should_finalize(ctx: &ExecutionContext) -> bool {
         ctx.execution_process
             .executor_action()
@@ -123,16 +205,69 @@ impl LocalContainerService {
             .is_none()
             && (!matches!(
                 ctx.execution_process.run_reason,
-                ExecutionProcessRunReason::DevServer
+                ExecutionProcessRunReason::DevServer | ExecutionProcessRunReason::TestScript
             ))
     }
 
+    /// Remaining run time before an execution is cancelled, from the script's
+    /// own timeout, the attempt's or, failing that, its project's. The time
+    /// the run spent paused doesn't count. Dev servers are never timed out,
+    /// and a paused run isn't timed until it's resumed.
+    async fn execution_time_remaining(db: &DBService, exec_id: Uuid) -> Option<Duration> {
+        let ctx = ExecutionProcess::load_context(&db.pool, exec_id)
+            .await
+            .ok()?;
+        if matches!(
+            ctx.execution_process.run_reason,
+            ExecutionProcessRunReason::DevServer
+        ) {
+            return None;
+        }
+        let timeout = if let Ok(action) = ctx.execution_process.executor_action()
+            && let ExecutorActionType::ScriptRequest(script) = action.typ()
+            && let Some(seconds) = script.timeout_seconds
+        {
+            chrono::Duration::seconds(seconds.into())
+        } else {
+            let minutes = match ctx.task_attempt.execution_timeout_minutes {
+                Some(minutes) => minutes,
+                None => {
+                    Project::find_by_id(&db.pool, ctx.task.project_id)
+                        .await
+                        .ok()
+                        .flatten()?
+                        .execution_timeout_minutes?
+                }
+            };
+            chrono::Duration::minutes(minutes)
+        };
+        let paused_ms = ExecutionProcess::find_paused_ms(&db.pool, exec_id)
+            .await
+            .unwrap_or(0);
+        time_remaining(
+            ctx.execution_process.started_at + timeout,
+            chrono::Duration::milliseconds(paused_ms),
+            ctx.task_attempt.paused_at,
+            Utc::now(),
+        )
+    }
+
+    /// Sleep until the execution's timeout, or forever if it has none
+    async fn timeout_sleep(db: &DBService, exec_id: Uuid) -> BoxFuture<'static, ()> {
+        match Self::execution_time_remaining(db, exec_id).await {
+            Some(remaining) => tokio::time::sleep(remaining).boxed(),
+            None => std::future::pending::<()>().boxed(),
+        }
+    }
+
     /// Finalize task execution by updating status to InReview and sending notifications
-    async fn finalize_task(db: &DBService, config: &Arc<RwLock<Config>>, ctx: &ExecutionContext) {
-        if let Err(e) = Task::update_status(&db.pool, ctx.task.id, TaskStatus::InReview).await {
+    async fn finalize_task(&self, ctx: &ExecutionContext) {
+        if let Err(e) = Task::update_status(&self.db.pool, ctx.task.id, TaskStatus::InReview).await
+        {
             tracing::error!("Failed to update task status to InReview: {e}");
         }
-        let notify_cfg = config.read().await.notifications.clone();
+        self.publish_wip_status(ctx.task.project_id).await;
+        let notify_cfg = self.config.read().await.notifications.clone();
         NotificationService::notify_execution_halted(notify_cfg, ctx).await;
     }
 
@@ -208,8 +343,13 @@ impl LocalContainerService {
             }
 
             let worktree_path_str = path.to_string_lossy().to_string();
+            // Worktrees of additional project repositories live as long as
+            // their attempt's main worktree
+            let owner_path_str = ProjectRepository::owning_attempt_worktree(&path)
+                .to_string_lossy()
+                .to_string();
             if let Ok(false) =
-                TaskAttempt::container_ref_exists(&self.db().pool, &worktree_path_str).await
+                TaskAttempt::container_ref_exists(&self.db().pool, &owner_path_str).await
             {
                 // This is an orphaned worktree - delete it
                 tracing::info!("Found orphaned worktree: {}", worktree_path_str);
@@ -236,14 +376,31 @@ impl LocalContainerService {
         git_repo_path: PathBuf,
     ) -> Result<(), DeploymentError> {
         WorktreeManager::cleanup_worktree(&worktree_path, Some(&git_repo_path)).await?;
+        if let Some(attempt) = TaskAttempt::find_by_id(&db.pool, attempt_id).await?
+            && let Some(task) = attempt.parent_task(&db.pool).await?
+        {
+            for repository in
+                ProjectRepository::find_by_project_id(&db.pool, task.project_id).await?
+            {
+                WorktreeManager::cleanup_worktree(
+                    &repository.worktree_path(&worktree_path),
+                    Some(&repository.git_repo_path),
+                )
+                .await?;
+            }
+        }
         // Mark worktree as deleted in database after successful cleanup
         TaskAttempt::mark_worktree_deleted(&db.pool, attempt_id).await?;
         tracing::info!("Successfully marked worktree as deleted for attempt {attempt_id}",);
         Ok(())
     }
 
-    pub async fn cleanup_expired_attempts(db: &DBService) -> Result<(), DeploymentError> {
-        let expired_attempts = TaskAttempt::find_expired_for_cleanup(&db.pool).await?;
+    pub async fn cleanup_expired_attempts(
+        db: &DBService,
+        max_age_days: u32,
+    ) -> Result<(), DeploymentError> {
+        let expired_attempts =
+            TaskAttempt::find_expired_for_cleanup(&db.pool, i64::from(max_age_days) * 24).await?;
         if expired_attempts.is_empty() {
             tracing::debug!("No expired worktrees found");
             return Ok(());
@@ -267,8 +424,35 @@ impl LocalContainerService {
         Ok(())
     }
 
+    /// Delete the worktrees of attempts whose changes have been merged
+    pub async fn cleanup_merged_attempts(db: &DBService) -> Result<(), DeploymentError> {
+        let merged_attempts = TaskAttempt::find_merged_for_cleanup(&db.pool).await?;
+        if merged_attempts.is_empty() {
+            tracing::debug!("No merged worktrees found");
+            return Ok(());
+        }
+        tracing::info!(
+            "Found {} merged worktrees to clean up",
+            merged_attempts.len()
+        );
+        for (attempt_id, worktree_path, git_repo_path) in merged_attempts {
+            Self::cleanup_expired_attempt(
+                db,
+                attempt_id,
+                PathBuf::from(worktree_path),
+                PathBuf::from(git_repo_path),
+            )
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!("Failed to clean up merged attempt {attempt_id}: {e}",);
+            });
+        }
+        Ok(())
+    }
+
     pub async fn spawn_worktree_cleanup(&self) {
         let db = self.db.clone();
+        let config = self.config.clone();
         let mut cleanup_interval = tokio::time::interval(tokio::time::Duration::from_secs(1800)); // 30 minutes
         self.cleanup_orphaned_worktrees().await;
         tokio::spawn(async move {
@@ -280,11 +464,21 @@ impl LocalContainerService {
                     .unwrap_or_else(|e| {
                         tracing::error!("Failed to check externally deleted worktrees: {}", e);
                     });
-                Self::cleanup_expired_attempts(&db)
-                    .await
-                    .unwrap_or_else(|e| {
-                        tracing::error!("Failed to clean up expired worktree attempts: {}", e)
-                    });
+                let policy = config.read().await.worktree_cleanup.clone();
+                if policy.delete_on_merge {
+                    Self::cleanup_merged_attempts(&db)
+                        .await
+                        .unwrap_or_else(|e| {
+                            tracing::error!("Failed to clean up merged worktree attempts: {}", e)
+                        });
+                }
+                if let Some(max_age_days) = policy.max_age_days {
+                    Self::cleanup_expired_attempts(&db, max_age_days)
+                        .await
+                        .unwrap_or_else(|e| {
+                            tracing::error!("Failed to clean up expired worktree attempts: {}", e)
+                        });
+                }
             }
         });
     }
@@ -305,34 +499,64 @@ impl LocalContainerService {
         let analytics = self.analytics.clone();
 
         let mut process_exit_rx = self.spawn_os_exit_watcher(exec_id);
+        let timeout_changed = self.timeout_changed.clone();
 
         tokio::spawn(async move {
             let mut exit_signal_future = exit_signal
                 .map(|rx| rx.map(|_| ()).boxed()) // wait for signal
                 .unwrap_or_else(|| std::future::pending::<()>().boxed()); // no signal, stall forever
 
-            let status_result: std::io::Result<std::process::ExitStatus>;
+            let timeout_notify = container.timeout_notifier(exec_id).await;
+            let mut timeout_future = Self::timeout_sleep(&db, exec_id).await;
+            let mut timed_out = false;
 
             // Wait for process to exit, or exit signal from executor
-            tokio::select! {
-                // Exit signal.
-                // Some coding agent processes do not automatically exit after processing the user request; instead the executor
-                // signals when processing has finished to gracefully kill the process.
-                _ = &mut exit_signal_future => {
-                    // Executor signaled completion: kill group and remember to force Completed(0)
-                    if let Some(child_lock) = child_store.read().await.get(&exec_id).cloned() {
-                        let mut child = child_lock.write().await ;
-                        if let Err(err) = command::kill_process_group(&mut child).await {
-                            tracing::error!("Failed to kill process group after exit signal: {} {}", exec_id, err);
+            let status_result: std::io::Result<std::process::ExitStatus> = loop {
+                tokio::select! {
+                    // Exit signal.
+                    // Some coding agent processes do not automatically exit after processing the user request; instead the executor
+                    // signals when processing has finished to gracefully kill the process.
+                    _ = &mut exit_signal_future => {
+                        // Executor signaled completion: kill group and remember to force Completed(0)
+                        if let Some(child_lock) = child_store.read().await.get(&exec_id).cloned() {
+                            let mut child = child_lock.write().await ;
+                            if let Err(err) = command::kill_process_group(&mut child).await {
+                                tracing::error!("Failed to kill process group after exit signal: {} {}", exec_id, err);
+                            }
                         }
+                        break Ok(success_exit_status());
+                    }
+                    // Process exit
+                    exit_status_result = &mut process_exit_rx => {
+                        break exit_status_result.unwrap_or_else(|e| Err(std::io::Error::other(e)));
+                    }
+                    // Paused, resumed or given a new timeout: re-arm the timer
+                    _ = timeout_notify.notified() => {
+                        timeout_future = Self::timeout_sleep(&db, exec_id).await;
+                    }
+                    // Execution timeout: cancel the run and flag the attempt as timed out
+                    _ = &mut timeout_future => {
+                        tracing::warn!("Execution {} exceeded its timeout, cancelling", exec_id);
+                        if let Some(child_lock) = child_store.read().await.get(&exec_id).cloned() {
+                            let mut child = child_lock.write().await ;
+                            if let Err(err) = command::kill_process_group(&mut child).await {
+                                tracing::error!("Failed to kill process group after timeout: {} {}", exec_id, err);
+                            }
+                        }
+                        if let Err(e) = ExecutionProcess::update_completion(&db.pool, exec_id, ExecutionProcessStatus::Killed, None).await {
+                            tracing::error!("Failed to mark timed out execution as killed: {}", e);
+                        }
+                        if let Ok(Some(process)) = ExecutionProcess::find_by_id(&db.pool, exec_id).await
+                            && let Err(e) = TaskAttempt::mark_timed_out(&db.pool, process.task_attempt_id).await
+                        {
+                            tracing::error!("Failed to mark task attempt as timed out: {}", e);
+                        }
+                        timed_out = true;
+                        break Err(std::io::Error::new(io::ErrorKind::TimedOut, "execution timed out"));
                     }
-                    status_result = Ok(success_exit_status());
-                }
-                // Process exit
-                exit_status_result = &mut process_exit_rx => {
-                    status_result = exit_status_result.unwrap_or_else(|e| Err(std::io::Error::other(e)));
                 }
-            }
+            };
+            timeout_changed.write().await.remove(&exec_id);
 
             let (exit_code, status) = match status_result {
                 Ok(exit_status) => {
@@ -353,8 +577,26 @@ impl LocalContainerService {
             {
                 tracing::error!("Failed to update execution process completion: {}", e);
             }
+            if let Err(e) = ExecutionProcessHandle::delete(&db.pool, exec_id).await {
+                tracing::warn!(
+                    "Failed to remove pid of execution process {}: {}",
+                    exec_id,
+                    e
+                );
+            }
 
             if let Ok(ctx) = ExecutionProcess::load_context(&db.pool, exec_id).await {
+                // A finished run can no longer be suspended
+                if ctx.task_attempt.paused_at.is_some()
+                    && !matches!(
+                        ctx.execution_process.run_reason,
+                        ExecutionProcessRunReason::DevServer
+                    )
+                    && let Err(e) = TaskAttempt::clear_paused(&db.pool, ctx.task_attempt.id).await
+                {
+                    tracing::warn!("Failed to clear paused flag: {}", e);
+                }
+
                 // Update executor session summary if available
                 if let Err(e) = container.update_executor_session_summary(&exec_id).await {
                     tracing::warn!("Failed to update executor session summary: {}", e);
@@ -365,6 +607,79 @@ impl LocalContainerService {
                     ExecutionProcessStatus::Completed
                 ) && exit_code == Some(0);
 
+                if success
+                    && matches!(
+                        ctx.execution_process.run_reason,
+                        ExecutionProcessRunReason::CodingAgent
+                    )
+                    && let Err(e) = container.try_submit_plan(&ctx).await
+                {
+                    tracing::error!(
+                        "Failed to store plan of attempt {}: {}",
+                        ctx.task_attempt.id,
+                        e
+                    );
+                }
+
+                if success
+                    && matches!(
+                        ctx.execution_process.run_reason,
+                        ExecutionProcessRunReason::CodingAgent
+                    )
+                    && let Err(e) = container.try_record_proposed_tasks(&ctx).await
+                {
+                    tracing::error!(
+                        "Failed to store proposed tasks of attempt {}: {}",
+                        ctx.task_attempt.id,
+                        e
+                    );
+                }
+
+                // A failed or timed out script with retries left runs again
+                // in place of the rest of its chain
+                let retried = (timed_out
+                    || matches!(ctx.execution_process.status, ExecutionProcessStatus::Failed))
+                    && match container.try_retry_script(&ctx).await {
+                        Ok(retried) => retried,
+                        Err(e) => {
+                            tracing::error!("Failed to retry script: {}", e);
+                            false
+                        }
+                    };
+
+                // A provider refusal holds back new attempts with the same
+                // executor; they are dispatched again once the backoff is over
+                if matches!(
+                    ctx.execution_process.run_reason,
+                    ExecutionProcessRunReason::CodingAgent
+                ) && let Some(backoff) = container.update_provider_limits(&ctx).await
+                {
+                    let container = container.clone();
+                    tokio::spawn(async move {
+                        tokio::time::sleep(backoff).await;
+                        if let Err(e) = container.dispatch_queued_attempts().await {
+                            tracing::error!("Failed to dispatch queued task attempts: {}", e);
+                        }
+                    });
+                }
+
+                // A coding agent run the model provider refused runs again
+                // with the project's next fallback profile
+                let fell_back = !retried
+                    && !timed_out
+                    && matches!(ctx.execution_process.status, ExecutionProcessStatus::Failed)
+                    && matches!(
+                        ctx.execution_process.run_reason,
+                        ExecutionProcessRunReason::CodingAgent
+                    )
+                    && match container.try_fallback(&ctx).await {
+                        Ok(fell_back) => fell_back,
+                        Err(e) => {
+                            tracing::error!("Failed to start executor fallback: {}", e);
+                            false
+                        }
+                    };
+
                 let cleanup_done = matches!(
                     ctx.execution_process.run_reason,
                     ExecutionProcessRunReason::CleanupScript
@@ -373,7 +688,7 @@ impl LocalContainerService {
                     ExecutionProcessStatus::Running
                 );
 
-                if success || cleanup_done {
+                if !retried && (success || cleanup_done) {
                     // Commit changes (if any) and get feedback about whether changes were made
                     let changes_committed = match container.try_commit_changes(&ctx).await {
                         Ok(committed) => committed,
@@ -384,6 +699,8 @@ impl LocalContainerService {
                         }
                     };
 
+                    container.try_auto_push(&ctx).await;
+
                     let should_start_next = if matches!(
                         ctx.execution_process.run_reason,
                         ExecutionProcessRunReason::CodingAgent
@@ -405,20 +722,51 @@ impl LocalContainerService {
                         );
 
                         // Manually finalize task since we're bypassing normal execution flow
-                        Self::finalize_task(&db, &config, &ctx).await;
+                        container.finalize_task(&ctx).await;
                     }
                 }
 
-                if Self::should_finalize(&ctx) {
-                    Self::finalize_task(&db, &config, &ctx).await;
+                if retried || fell_back {
+                    // The retry or fallback finalizes the chain once it is done
+                } else if timed_out {
+                    // Any remaining actions in the chain are skipped, and queued
+                    // follow-ups wait for the user
+                    container.finalize_task(&ctx).await;
+                } else if Self::should_finalize(&ctx) {
+                    container.finalize_task(&ctx).await;
+                    // The project's turn tests run first; queued follow-ups
+                    // wait for their results
+                    let tests_started = match container.try_start_turn_tests(&ctx).await {
+                        Ok(started) => started,
+                        Err(e) => {
+                            tracing::error!(
+                                "Failed to start turn tests for attempt {}: {}",
+                                ctx.task_attempt.id,
+                                e
+                            );
+                            false
+                        }
+                    };
                     // After finalization, check if a queued follow-up exists and start it
-                    if let Err(e) = container.try_consume_queued_followup(&ctx).await {
+                    if !tests_started
+                        && let Err(e) = container.try_consume_queued_followup(&ctx).await
+                    {
                         tracing::error!(
                             "Failed to start queued follow-up for attempt {}: {}",
                             ctx.task_attempt.id,
                             e
                         );
                     }
+                } else if matches!(
+                    ctx.execution_process.run_reason,
+                    ExecutionProcessRunReason::TestScript
+                ) && let Err(e) = container.try_record_turn_tests(&ctx, success).await
+                {
+                    tracing::error!(
+                        "Failed to record turn tests for attempt {}: {}",
+                        ctx.task_attempt.id,
+                        e
+                    );
                 }
 
                 // Fire analytics event when CodingAgent execution has finished
@@ -468,6 +816,11 @@ impl LocalContainerService {
 
             // Cleanup child handle
             child_store.write().await.remove(&exec_id);
+
+            // A slot may have freed up for attempts waiting in the execution queue
+            if let Err(e) = container.dispatch_queued_attempts().await {
+                tracing::error!("Failed to dispatch queued task attempts: {}", e);
+            }
         })
     }
 
@@ -572,6 +925,57 @@ impl LocalContainerService {
         Ok(project_repo_path)
     }
 
+    /// Where the attempt's diff is read from: the merge commit once it's
+    /// merged with nothing left over, the branch once it's archived, and the
+    /// worktree otherwise
+    async fn attempt_diff_source(
+        &self,
+        task_attempt: &TaskAttempt,
+    ) -> Result<AttemptDiffSource, ContainerError> {
+        let project_repo_path = self.get_project_repo_path(task_attempt).await?;
+        let latest_merge =
+            Merge::find_latest_by_task_attempt_id(&self.db.pool, task_attempt.id).await?;
+
+        let is_ahead = if let Ok((ahead, _)) = self.git().get_branch_status(
+            &project_repo_path,
+            &task_attempt.branch,
+            &task_attempt.target_branch,
+        ) {
+            ahead > 0
+        } else {
+            false
+        };
+
+        if let Some(merge) = &latest_merge
+            && let Some(commit) = merge.merge_commit()
+            && self.is_container_clean(task_attempt).await?
+            && !is_ahead
+        {
+            return Ok(AttemptDiffSource::Merged {
+                repo_path: project_repo_path,
+                merge_commit: commit,
+            });
+        }
+
+        // Archived attempts have no worktree; serve the committed branch diff instead
+        if task_attempt.archived_at.is_some() {
+            return Ok(AttemptDiffSource::Branch {
+                repo_path: project_repo_path,
+            });
+        }
+
+        let container_ref = self.ensure_container_exists(task_attempt).await?;
+        let base_commit = self.git().get_base_commit(
+            &project_repo_path,
+            &task_attempt.branch,
+            &task_attempt.target_branch,
+        )?;
+        Ok(AttemptDiffSource::Worktree {
+            worktree_path: PathBuf::from(container_ref),
+            base_commit,
+        })
+    }
+
     /// Create a diff log stream for merged attempts (never changes) for WebSocket
     fn create_merged_diff_stream(
         &self,
@@ -587,6 +991,29 @@ impl LocalContainerService {
             None,
         )?;
 
+        Ok(Self::static_diff_stream(diffs, stats_only))
+    }
+
+    /// Create a diff stream from the committed attempt branch, for attempts without a worktree
+    fn create_branch_diff_stream(
+        &self,
+        project_repo_path: &Path,
+        task_attempt: &TaskAttempt,
+        stats_only: bool,
+    ) -> Result<DiffStreamHandle, ContainerError> {
+        let diffs = self.git().get_diffs(
+            DiffTarget::Branch {
+                repo_path: project_repo_path,
+                branch_name: &task_attempt.branch,
+                base_branch: &task_attempt.target_branch,
+            },
+            None,
+        )?;
+
+        Ok(Self::static_diff_stream(diffs, stats_only))
+    }
+
+    fn static_diff_stream(diffs: Vec<Diff>, stats_only: bool) -> DiffStreamHandle {
         let cum = Arc::new(AtomicUsize::new(0));
         let diffs: Vec<_> = diffs
             .into_iter()
@@ -607,7 +1034,7 @@ impl LocalContainerService {
         }))
         .boxed();
 
-        Ok(diff_stream::DiffStreamHandle::new(stream, None))
+        diff_stream::DiffStreamHandle::new(stream, None)
     }
 
     /// Create a live diff log stream for ongoing attempts for WebSocket
@@ -656,6 +1083,14 @@ impl ContainerService for LocalContainerService {
         &self.git
     }
 
+    fn execution_queue(&self) -> &ExecutionQueue {
+        &self.execution_queue
+    }
+
+    fn events_msg_store(&self) -> &Arc<MsgStore> {
+        &self.events_msg_store
+    }
+
     async fn git_branch_prefix(&self) -> String {
         self.config.read().await.git_branch_prefix.clone()
     }
@@ -688,6 +1123,17 @@ impl ContainerService for LocalContainerService {
         )
         .await?;
 
+        for repository in ProjectRepository::find_by_project_id(&self.db.pool, project.id).await? {
+            WorktreeManager::create_worktree(
+                &repository.git_repo_path,
+                &task_attempt.branch,
+                &repository.worktree_path(&worktree_path),
+                &repository.base_branch,
+                true, // create new branch
+            )
+            .await?;
+        }
+
         // Copy files specified in the project's copy_files field
         if let Some(copy_files) = &project.copy_files
             && !copy_files.trim().is_empty()
@@ -708,6 +1154,15 @@ impl ContainerService for LocalContainerService {
             tracing::warn!("Failed to copy task images to worktree: {}", e);
         }
 
+        // Copy task attachments from the asset dir to worktree
+        if let Err(e) = self
+            .image_service
+            .copy_attachments_by_task_to_worktree(&worktree_path, task.id)
+            .await
+        {
+            tracing::warn!("Failed to copy task attachments to worktree: {}", e);
+        }
+
         // Update both container_ref and branch in the database
         TaskAttempt::update_container_ref(
             &self.db.pool,
@@ -733,18 +1188,42 @@ impl ContainerService for LocalContainerService {
                 None
             }
         };
-        WorktreeManager::cleanup_worktree(
-            &PathBuf::from(task_attempt.container_ref.clone().unwrap_or_default()),
-            git_repo_path.as_deref(),
-        )
-        .await
-        .unwrap_or_else(|e| {
-            tracing::warn!(
-                "Failed to clean up worktree for task attempt {}: {}",
-                task_attempt.id,
-                e
-            );
-        });
+        let worktree_path = PathBuf::from(task_attempt.container_ref.clone().unwrap_or_default());
+        WorktreeManager::cleanup_worktree(&worktree_path, git_repo_path.as_deref())
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Failed to clean up worktree for task attempt {}: {}",
+                    task_attempt.id,
+                    e
+                );
+            });
+
+        let repositories = ProjectRepository::find_by_project_id(&self.db.pool, task.project_id)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!(
+                    "Failed to fetch repositories of project {}: {}",
+                    task.project_id,
+                    e
+                );
+                Vec::new()
+            });
+        for repository in repositories {
+            WorktreeManager::cleanup_worktree(
+                &repository.worktree_path(&worktree_path),
+                Some(&repository.git_repo_path),
+            )
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!(
+                    "Failed to clean up '{}' worktree for task attempt {}: {}",
+                    repository.name,
+                    task_attempt.id,
+                    e
+                );
+            });
+        }
         Ok(())
     }
 
@@ -762,6 +1241,14 @@ impl ContainerService for LocalContainerService {
             .parent_project(&self.db.pool)
             .await?
             .ok_or(sqlx::Error::RowNotFound)?;
+        // Archived projects keep their attempts without worktrees
+        if project.archived_at.is_some() {
+            return Err(ContainerError::ProjectArchived);
+        }
+        // Archived attempts stay without a worktree until unarchived
+        if task_attempt.archived_at.is_some() {
+            return Err(ContainerError::AttemptArchived);
+        }
 
         let container_ref = task_attempt.container_ref.as_ref().ok_or_else(|| {
             ContainerError::Other(anyhow!("Container ref not found for task attempt"))
@@ -775,6 +1262,32 @@ impl ContainerService for LocalContainerService {
         )
         .await?;
 
+        // Repositories added to the project after the attempt was created
+        // get their attempt branch on first use
+        for repository in ProjectRepository::find_by_project_id(&self.db.pool, project.id).await? {
+            let repository_worktree = repository.worktree_path(&worktree_path);
+            if self
+                .git()
+                .check_branch_exists(&repository.git_repo_path, &task_attempt.branch)?
+            {
+                WorktreeManager::ensure_worktree_exists(
+                    &repository.git_repo_path,
+                    &task_attempt.branch,
+                    &repository_worktree,
+                )
+                .await?;
+            } else {
+                WorktreeManager::create_worktree(
+                    &repository.git_repo_path,
+                    &task_attempt.branch,
+                    &repository_worktree,
+                    &repository.base_branch,
+                    true, // create new branch
+                )
+                .await?;
+            }
+        }
+
         Ok(container_ref.to_string())
     }
 
@@ -819,15 +1332,81 @@ impl ContainerService for LocalContainerService {
                 _ => Arc::new(NoopExecutorApprovalService {}),
             };
 
-        // Create the child and stream, add to execution tracker
-        let mut spawned = executor_action
-            .spawn(&current_dir, approvals_service)
+        // Project environment variables are set on every process of the attempt
+        let task = task_attempt
+            .parent_task(&self.db.pool)
+            .await?
+            .ok_or(ContainerError::Other(anyhow!(
+                "Task not found for task attempt"
+            )))?;
+        let mut env = self
+            .secrets
+            .project_env(&self.db.pool, task.project_id)
             .await?;
+        // Only the coding agent gets the project's MCP servers and runs in its
+        // sandbox; scripts keep running on the host
+        if matches!(
+            executor_action.typ(),
+            ExecutorActionType::CodingAgentInitialRequest(_)
+                | ExecutorActionType::CodingAgentFollowUpRequest(_)
+        ) {
+            let mcp_servers = ProjectMcpServer::find_by_project_id(&self.db.pool, task.project_id)
+                .await?
+                .into_iter()
+                .filter(|server| server.enabled)
+                .map(|server| (server.name.clone(), server.config()))
+                .collect();
+            env = env.with_mcp_servers(mcp_servers);
+
+            if let Some(sandbox) =
+                ProjectSandbox::find_by_project_id(&self.db.pool, task.project_id).await?
+            {
+                env = env.with_sandbox(Sandbox::new(
+                    sandbox.image,
+                    sandbox.bootstrap_script,
+                    format!("vibe-kanban-sandbox-{}", short_uuid(&task_attempt.id)),
+                ));
+            }
 
-        self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
-            .await;
+            if task_attempt.read_only {
+                let hooks_dir = utils::cache_dir().join("read-only-hooks");
+                write_read_only_hooks(&hooks_dir).await.map_err(|e| {
+                    ContainerError::Other(anyhow!("Failed to write read-only git hooks: {e}"))
+                })?;
+                env = env.with_read_only(&hooks_dir);
+            }
+        }
 
-        self.add_child_to_store(execution_process.id, spawned.child)
+        // The signing key and program are on the host, so sandboxed agents'
+        // commits stay unsigned
+        if env.sandbox().is_none()
+            && let Some(signing) = self.config.read().await.commit_signing.clone()
+        {
+            env = env.with_git_config(&commit_signing::git_config(&signing));
+        }
+
+        // Create the child and stream, add to execution tracker
+        let mut spawned = executor_action
+            .spawn(&current_dir, approvals_service, &env)
+            .await?;
+
+        // Recorded so a restarted server can stop it if this one goes away
+        if let Some(pid) = spawned.child.inner().id()
+            && let Err(e) =
+                ExecutionProcessHandle::upsert(&self.db.pool, execution_process.id, pid as i64)
+                    .await
+        {
+            tracing::warn!(
+                "Failed to record pid of execution process {}: {}",
+                execution_process.id,
+                e
+            );
+        }
+
+        self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
+            .await;
+
+        self.add_child_to_store(execution_process.id, spawned.child)
             .await;
 
         // Spawn unified exit monitor: watches OS exit and optional executor signal
@@ -881,10 +1460,13 @@ impl ContainerService for LocalContainerService {
                 ctx.execution_process.run_reason,
                 ExecutionProcessRunReason::DevServer
             )
-            && let Err(e) =
-                Task::update_status(&self.db.pool, ctx.task.id, TaskStatus::InReview).await
         {
-            tracing::error!("Failed to update task status to InReview: {e}");
+            if let Err(e) =
+                Task::update_status(&self.db.pool, ctx.task.id, TaskStatus::InReview).await
+            {
+                tracing::error!("Failed to update task status to InReview: {e}");
+            }
+            self.publish_wip_status(ctx.task.project_id).await;
         }
 
         tracing::debug!(
@@ -908,48 +1490,116 @@ impl ContainerService for LocalContainerService {
         Ok(())
     }
 
+    async fn pause_execution(
+        &self,
+        execution_process: &ExecutionProcess,
+    ) -> Result<(), ContainerError> {
+        let child = self
+            .get_child_from_store(&execution_process.id)
+            .await
+            .ok_or_else(|| {
+                ContainerError::Other(anyhow!("Child process not found for execution"))
+            })?;
+        command::suspend_process_group(&mut *child.write().await)
+    }
+
+    async fn resume_execution(
+        &self,
+        execution_process: &ExecutionProcess,
+    ) -> Result<(), ContainerError> {
+        let child = self
+            .get_child_from_store(&execution_process.id)
+            .await
+            .ok_or_else(|| {
+                ContainerError::Other(anyhow!("Child process not found for execution"))
+            })?;
+        command::resume_process_group(&mut *child.write().await)
+    }
+
+    async fn refresh_execution_timeout(&self, execution_process_id: Uuid) {
+        if let Some(notify) = self.timeout_changed.read().await.get(&execution_process_id) {
+            notify.notify_one();
+        }
+    }
+
+    async fn stop_orphaned_process(
+        &self,
+        execution_process_id: Uuid,
+    ) -> Result<(), ContainerError> {
+        let Some(pid) =
+            ExecutionProcessHandle::find_pid(&self.db.pool, execution_process_id).await?
+        else {
+            return Ok(());
+        };
+        command::kill_orphaned_process_group(pid)?;
+        ExecutionProcessHandle::delete(&self.db.pool, execution_process_id).await?;
+        Ok(())
+    }
+
+    async fn start_queued_follow_up(
+        &self,
+        task_attempt: &TaskAttempt,
+    ) -> Result<bool, ContainerError> {
+        if self.has_running_process(task_attempt.id).await?
+            || self.plan_awaits_approval(task_attempt.id).await?
+        {
+            return Ok(false);
+        }
+        let task = task_attempt
+            .parent_task(&self.db.pool)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+        self.start_next_queued_follow_up(task_attempt, &task).await
+    }
+
     async fn stream_diff(
         &self,
         task_attempt: &TaskAttempt,
         stats_only: bool,
     ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, ContainerError>
     {
-        let project_repo_path = self.get_project_repo_path(task_attempt).await?;
-        let latest_merge =
-            Merge::find_latest_by_task_attempt_id(&self.db.pool, task_attempt.id).await?;
-
-        let is_ahead = if let Ok((ahead, _)) = self.git().get_branch_status(
-            &project_repo_path,
-            &task_attempt.branch,
-            &task_attempt.target_branch,
-        ) {
-            ahead > 0
-        } else {
-            false
+        let wrapper = match self.attempt_diff_source(task_attempt).await? {
+            AttemptDiffSource::Merged {
+                repo_path,
+                merge_commit,
+            } => self.create_merged_diff_stream(&repo_path, &merge_commit, stats_only)?,
+            AttemptDiffSource::Branch { repo_path } => {
+                self.create_branch_diff_stream(&repo_path, task_attempt, stats_only)?
+            }
+            AttemptDiffSource::Worktree {
+                worktree_path,
+                base_commit,
+            } => {
+                self.create_live_diff_stream(&worktree_path, &base_commit, stats_only)
+                    .await?
+            }
         };
+        Ok(Box::pin(wrapper))
+    }
 
-        if let Some(merge) = &latest_merge
-            && let Some(commit) = merge.merge_commit()
-            && self.is_container_clean(task_attempt).await?
-            && !is_ahead
-        {
-            let wrapper =
-                self.create_merged_diff_stream(&project_repo_path, &commit, stats_only)?;
-            return Ok(Box::pin(wrapper));
-        }
-
-        let container_ref = self.ensure_container_exists(task_attempt).await?;
-        let worktree_path = PathBuf::from(container_ref);
-        let base_commit = self.git().get_base_commit(
-            &project_repo_path,
-            &task_attempt.branch,
-            &task_attempt.target_branch,
-        )?;
+    async fn diff_files(
+        &self,
+        task_attempt: &TaskAttempt,
+        offset: usize,
+        limit: usize,
+    ) -> Result<DiffFilePage, ContainerError> {
+        let source = self.attempt_diff_source(task_attempt).await?;
+        Ok(self
+            .git()
+            .get_diff_file_page(source.target(task_attempt), offset, limit)?)
+    }
 
-        let wrapper = self
-            .create_live_diff_stream(&worktree_path, &base_commit, stats_only)
-            .await?;
-        Ok(Box::pin(wrapper))
+    async fn diff_file_hunks(
+        &self,
+        task_attempt: &TaskAttempt,
+        path: &str,
+    ) -> Result<Option<DiffFileHunks>, ContainerError> {
+        let source = self.attempt_diff_source(task_attempt).await?;
+        Ok(self.git().get_diff_file_hunks(
+            source.target(task_attempt),
+            path,
+            DIFF_HUNK_CONTEXT_LINES,
+        )?)
     }
 
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError> {
@@ -960,6 +1610,13 @@ impl ContainerService for LocalContainerService {
             return Ok(false);
         }
 
+        // Nothing a read-only attempt's agent left behind is kept, and with no
+        // changes there is nothing for the cleanup scripts to do
+        if ctx.task_attempt.read_only {
+            self.discard_read_only_changes(ctx).await?;
+            return Ok(false);
+        }
+
         let message = match ctx.execution_process.run_reason {
             ExecutionProcessRunReason::CodingAgent => {
                 // Try to retrieve the task summary from the executor session
@@ -1016,7 +1673,17 @@ impl ContainerService for LocalContainerService {
             message
         );
 
-        let changes_committed = self.git().commit(Path::new(container_ref), &message)?;
+        let mut changes_committed = self.git().commit(Path::new(container_ref), &message)?;
+
+        let repositories =
+            ProjectRepository::find_by_project_id(&self.db().pool, ctx.task.project_id).await?;
+        for repository in repositories {
+            let repository_worktree = repository.worktree_path(Path::new(container_ref));
+            if !repository_worktree.exists() {
+                continue;
+            }
+            changes_committed |= self.git().commit(&repository_worktree, &message)?;
+        }
         Ok(changes_committed)
     }
 
@@ -1065,8 +1732,8 @@ impl ContainerService for LocalContainerService {
 }
 
 impl LocalContainerService {
-    /// Extract the last assistant message from the MsgStore history
-    fn extract_last_assistant_message(&self, exec_id: &Uuid) -> Option<String> {
+    /// The last assistant message in the MsgStore history
+    fn last_assistant_message(&self, exec_id: &Uuid) -> Option<String> {
         // Get the MsgStore for this execution
         let msg_stores = self.msg_stores.try_read().ok()?;
         let msg_store = msg_stores.get(exec_id)?;
@@ -1082,11 +1749,6 @@ impl LocalContainerService {
                 {
                     let content = entry.content.trim();
                     if !content.is_empty() {
-                        const MAX_SUMMARY_LENGTH: usize = 4096;
-                        if content.len() > MAX_SUMMARY_LENGTH {
-                            let truncated = truncate_to_char_boundary(content, MAX_SUMMARY_LENGTH);
-                            return Some(format!("{truncated}..."));
-                        }
                         return Some(content.to_string());
                     }
                 }
@@ -1096,6 +1758,61 @@ impl LocalContainerService {
         None
     }
 
+    /// Extract the last assistant message, truncated for use as a summary
+    fn extract_last_assistant_message(&self, exec_id: &Uuid) -> Option<String> {
+        let content = self.last_assistant_message(exec_id)?;
+        const MAX_SUMMARY_LENGTH: usize = 4096;
+        if content.len() > MAX_SUMMARY_LENGTH {
+            let truncated = truncate_to_char_boundary(&content, MAX_SUMMARY_LENGTH);
+            return Some(format!("{truncated}..."));
+        }
+        Some(content)
+    }
+
+    /// Store the final message of a plan-first attempt's planning run as its
+    /// plan, which then awaits approval
+    async fn try_submit_plan(&self, ctx: &ExecutionContext) -> Result<(), anyhow::Error> {
+        let plan = AttemptPlan::find_by_task_attempt_id(&self.db.pool, ctx.task_attempt.id).await?;
+        if !plan.is_some_and(|plan| plan.status == AttemptPlanStatus::Planning) {
+            return Ok(());
+        }
+        let Some(content) = self.last_assistant_message(&ctx.execution_process.id) else {
+            tracing::warn!(
+                "Planning run {} of attempt {} ended without a plan",
+                ctx.execution_process.id,
+                ctx.task_attempt.id
+            );
+            return Ok(());
+        };
+        AttemptPlan::submit(
+            &self.db.pool,
+            ctx.task_attempt.id,
+            &content,
+            ctx.execution_process.id,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Store the follow-up work the agent proposed in its final message as
+    /// draft tasks of the attempt
+    async fn try_record_proposed_tasks(&self, ctx: &ExecutionContext) -> Result<(), anyhow::Error> {
+        let Some(content) = self.last_assistant_message(&ctx.execution_process.id) else {
+            return Ok(());
+        };
+        for proposal in parse_proposals(&content) {
+            ProposedTask::create(
+                &self.db.pool,
+                ctx.task_attempt.id,
+                ctx.execution_process.id,
+                &proposal.title,
+                proposal.description.as_deref(),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
     /// Update the executor session summary with the final assistant message
     async fn update_executor_session_summary(&self, exec_id: &Uuid) -> Result<(), anyhow::Error> {
         // Check if there's an executor session for this execution process
@@ -1116,8 +1833,261 @@ impl LocalContainerService {
         Ok(())
     }
 
+    /// Push the attempt branch (and those of the project's other repositories)
+    /// when the project has auto-push enabled. Failures are logged only.
+    async fn try_auto_push(&self, ctx: &ExecutionContext) {
+        let project = match Project::find_by_id(&self.db.pool, ctx.task.project_id).await {
+            Ok(Some(project)) if project.auto_push_branches => project,
+            Ok(_) => return,
+            Err(e) => {
+                tracing::warn!("Failed to load project for auto-push: {}", e);
+                return;
+            }
+        };
+        let Some(container_ref) = ctx.task_attempt.container_ref.clone() else {
+            return;
+        };
+        let config = self.config.read().await.clone();
+        let token =
+            match hosting::resolve_provider(&self.db.pool, self.git(), &config, &project).await {
+                Ok(provider) => provider.token().to_string(),
+                Err(e) => {
+                    tracing::warn!(
+                        "Auto-push is enabled for project {} but its code host is not usable: {}",
+                        project.id,
+                        e
+                    );
+                    return;
+                }
+            };
+
+        let worktree_path = PathBuf::from(container_ref);
+        let mut worktrees = vec![worktree_path.clone()];
+        match ProjectRepository::find_by_project_id(&self.db.pool, project.id).await {
+            Ok(repositories) => worktrees.extend(
+                repositories
+                    .iter()
+                    .map(|repository| repository.worktree_path(&worktree_path))
+                    .filter(|path| path.exists()),
+            ),
+            Err(e) => tracing::warn!("Failed to load project repositories for auto-push: {}", e),
+        }
+
+        let git = self.git().clone();
+        let branch = ctx.task_attempt.branch.clone();
+        let attempt_id = ctx.task_attempt.id;
+        let result = tokio::task::spawn_blocking(move || {
+            for worktree in worktrees {
+                if let Err(e) = git.force_push_with_lease(&worktree, &branch, &token) {
+                    tracing::warn!(
+                        "Auto-push of {} for attempt {} failed: {}",
+                        worktree.display(),
+                        attempt_id,
+                        e
+                    );
+                }
+            }
+        })
+        .await;
+        if let Err(e) = result {
+            tracing::error!("Auto-push task failed: {}", e);
+        }
+    }
+
+    /// Put a read-only attempt's worktree back to where it was before the
+    /// run, in case the agent changed files anyway or committed without
+    /// running the hooks
+    async fn discard_read_only_changes(
+        &self,
+        ctx: &ExecutionContext,
+    ) -> Result<(), ContainerError> {
+        let Some(before_head_commit) = &ctx.execution_process.before_head_commit else {
+            return Ok(());
+        };
+        let container_ref = ctx.task_attempt.container_ref.as_ref().ok_or_else(|| {
+            ContainerError::Other(anyhow::anyhow!("Container reference not found"))
+        })?;
+        if self
+            .git()
+            .discard_worktree_changes(Path::new(container_ref), before_head_commit)?
+        {
+            tracing::info!(
+                "Discarded changes of read-only task attempt {}",
+                ctx.task_attempt.id
+            );
+        }
+        Ok(())
+    }
+
+    /// Run the project's turn tests once the chain of a coding agent turn has
+    /// finished. Returns whether they were started.
+    async fn try_start_turn_tests(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError> {
+        if !matches!(
+            ctx.execution_process.run_reason,
+            ExecutionProcessRunReason::CodingAgent | ExecutionProcessRunReason::CleanupScript
+        ) || ctx.execution_process.status != ExecutionProcessStatus::Completed
+        {
+            return Ok(false);
+        }
+        let Some(settings) =
+            ProjectTurnTest::find_by_project_id(&self.db.pool, ctx.task.project_id).await?
+        else {
+            return Ok(false);
+        };
+        let Some(script) = ProjectScript::find_by_id(&self.db.pool, settings.script_id).await?
+        else {
+            return Ok(false);
+        };
+        let Some(turn) = ExecutionProcess::find_latest_by_task_attempt_and_run_reason(
+            &self.db.pool,
+            ctx.task_attempt.id,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?
+        .filter(|turn| turn.status == ExecutionProcessStatus::Completed) else {
+            return Ok(false);
+        };
+        if TurnTestRun::exists_for_turn(&self.db.pool, turn.id).await? {
+            return Ok(false);
+        }
+
+        // Retries would hide flaky failures from the agent
+        let request = ScriptRequest {
+            retries: 0,
+            ..project_script_request(&script)
+        };
+        let action = ExecutorAction::new(ExecutorActionType::ScriptRequest(request), None);
+        let execution_process = self
+            .start_execution(
+                &ctx.task_attempt,
+                &action,
+                &ExecutionProcessRunReason::TestScript,
+            )
+            .await?;
+        TurnTestRun::create(
+            &self.db.pool,
+            execution_process.id,
+            turn.id,
+            ctx.task_attempt.id,
+        )
+        .await?;
+        Ok(true)
+    }
+
+    /// Store the results of finished turn tests and send failures back to the
+    /// agent when the project asks for it. Otherwise the attempt's queued
+    /// follow-ups, which waited for the results, are started.
+    async fn try_record_turn_tests(
+        &self,
+        ctx: &ExecutionContext,
+        success: bool,
+    ) -> Result<(), ContainerError> {
+        let pool = &self.db.pool;
+        let Some(run) =
+            TurnTestRun::find_by_execution_process_id(pool, ctx.execution_process.id).await?
+        else {
+            return Ok(());
+        };
+        let output = self
+            .get_msg_store_by_id(&ctx.execution_process.id)
+            .await
+            .map(|store| {
+                store
+                    .get_history()
+                    .iter()
+                    .filter_map(|msg| match msg {
+                        LogMsg::Stdout(text) | LogMsg::Stderr(text) => Some(text.as_str()),
+                        _ => None,
+                    })
+                    .collect::<String>()
+            })
+            .unwrap_or_default();
+        let counts = parse_test_counts(&output);
+        TurnTestRun::complete(
+            pool,
+            run.execution_process_id,
+            counts.passed,
+            counts.failed,
+            counts.skipped,
+            success,
+        )
+        .await?;
+
+        if !success
+            && ctx.execution_process.status == ExecutionProcessStatus::Failed
+            && self.try_feed_back_turn_tests(ctx, &run, &output).await?
+        {
+            return Ok(());
+        }
+        self.try_consume_queued_followup(ctx).await
+    }
+
+    /// Send failed turn tests back to the agent as a follow-up, unless the
+    /// project does not ask for it or the failures were sent back too many
+    /// times in a row. Returns whether the follow-up was started.
+    async fn try_feed_back_turn_tests(
+        &self,
+        ctx: &ExecutionContext,
+        run: &TurnTestRun,
+        output: &str,
+    ) -> Result<bool, ContainerError> {
+        let pool = &self.db.pool;
+        let Some(settings) = ProjectTurnTest::find_by_project_id(pool, ctx.task.project_id)
+            .await?
+            .filter(|settings| settings.feed_back_failures)
+        else {
+            return Ok(false);
+        };
+
+        // Follow the chain of turns started by earlier failures
+        let mut rounds = 0;
+        let mut turn_id = run.coding_agent_process_id;
+        while let Some(previous) =
+            TurnTestRun::find_by_follow_up_execution_process_id(pool, turn_id).await?
+        {
+            rounds += 1;
+            turn_id = previous.coding_agent_process_id;
+        }
+        if rounds >= settings.max_feedback_rounds {
+            tracing::info!(
+                "Turn tests of attempt {} still fail after {} follow-ups; leaving them to the user",
+                ctx.task_attempt.id,
+                rounds
+            );
+            return Ok(false);
+        }
+
+        let variant = ExecutionProcess::find_by_id(pool, run.coding_agent_process_id)
+            .await?
+            .and_then(|turn| turn.executor_action().ok().cloned())
+            .and_then(|action| match action.typ {
+                ExecutorActionType::CodingAgentInitialRequest(req) => {
+                    req.executor_profile_id.variant
+                }
+                ExecutorActionType::CodingAgentFollowUpRequest(req) => {
+                    req.executor_profile_id.variant
+                }
+                ExecutorActionType::ScriptRequest(_) => None,
+            });
+        let Some(follow_up) = self
+            .start_follow_up_prompt(
+                &ctx.task_attempt,
+                &ctx.task,
+                feedback_prompt(&parse_test_counts(output), output),
+                variant,
+                None,
+            )
+            .await?
+        else {
+            return Ok(false);
+        };
+        TurnTestRun::set_follow_up(pool, run.execution_process_id, follow_up.id).await?;
+        Ok(true)
+    }
+
     /// If a queued follow-up draft exists for this attempt and nothing is running,
-    /// start it immediately and clear the draft.
+    /// start it immediately and clear the draft. Otherwise deliver the oldest
+    /// prompt of the attempt's follow-up queue.
     async fn try_consume_queued_followup(
         &self,
         ctx: &ExecutionContext,
@@ -1131,31 +2101,29 @@ impl LocalContainerService {
         }
 
         // If anything is running for this attempt, bail
-        let procs =
-            ExecutionProcess::find_by_task_attempt_id(&self.db.pool, ctx.task_attempt.id, false)
-                .await?;
-        if procs
-            .iter()
-            .any(|p| matches!(p.status, ExecutionProcessStatus::Running))
-        {
+        if self.has_running_process(ctx.task_attempt.id).await? {
+            return Ok(());
+        }
+
+        // Queued prompts wait until the attempt's plan is approved
+        if self.plan_awaits_approval(ctx.task_attempt.id).await? {
             return Ok(());
         }
 
         // Load draft and ensure it's eligible
-        let Some(draft) = Draft::find_by_task_attempt_and_type(
+        let draft = Draft::find_by_task_attempt_and_type(
             &self.db.pool,
             ctx.task_attempt.id,
             DraftType::FollowUp,
         )
         .await?
-        else {
+        .filter(|draft| draft.queued && !draft.prompt.trim().is_empty());
+        let Some(draft) = draft else {
+            self.start_next_queued_follow_up(&ctx.task_attempt, &ctx.task)
+                .await?;
             return Ok(());
         };
 
-        if !draft.queued || draft.prompt.trim().is_empty() {
-            return Ok(());
-        }
-
         // Atomically acquire sending lock; if not acquired, someone else is sending.
         if !Draft::try_mark_sending(&self.db.pool, ctx.task_attempt.id, DraftType::FollowUp)
             .await
@@ -1164,68 +2132,140 @@ impl LocalContainerService {
             return Ok(());
         }
 
+        if self
+            .start_follow_up_prompt(
+                &ctx.task_attempt,
+                &ctx.task,
+                draft.prompt.clone(),
+                draft.variant.clone(),
+                draft.image_ids.as_deref(),
+            )
+            .await?
+            .is_some()
+        {
+            // Clear the draft to reflect that it has been consumed
+            let _ =
+                Draft::clear_after_send(&self.db.pool, ctx.task_attempt.id, DraftType::FollowUp)
+                    .await;
+        }
+
+        Ok(())
+    }
+
+    async fn has_running_process(&self, task_attempt_id: Uuid) -> Result<bool, ContainerError> {
+        let procs =
+            ExecutionProcess::find_by_task_attempt_id(&self.db.pool, task_attempt_id, false)
+                .await?;
+        Ok(procs
+            .iter()
+            .any(|p| matches!(p.status, ExecutionProcessStatus::Running)))
+    }
+
+    /// Start the oldest entry of the attempt's follow-up queue and remove it
+    /// from the queue once its execution has started.
+    async fn plan_awaits_approval(&self, task_attempt_id: Uuid) -> Result<bool, ContainerError> {
+        Ok(
+            AttemptPlan::find_by_task_attempt_id(&self.db.pool, task_attempt_id)
+                .await?
+                .is_some_and(|plan| plan.status == AttemptPlanStatus::AwaitingApproval),
+        )
+    }
+
+    async fn start_next_queued_follow_up(
+        &self,
+        task_attempt: &TaskAttempt,
+        task: &Task,
+    ) -> Result<bool, ContainerError> {
+        let Some(queued) = QueuedFollowUp::find_next(&self.db.pool, task_attempt.id).await? else {
+            return Ok(false);
+        };
+
+        let started = self
+            .start_follow_up_prompt(
+                task_attempt,
+                task,
+                queued.prompt.clone(),
+                queued.variant.clone(),
+                queued.image_ids.as_ref().map(|ids| ids.0.as_slice()),
+            )
+            .await?
+            .is_some();
+        if started {
+            QueuedFollowUp::delete(&self.db.pool, queued.id).await?;
+        }
+        Ok(started)
+    }
+
+    /// Continue the attempt's latest coding agent session with `prompt`.
+    /// Returns None when there is no session to continue.
+    async fn start_follow_up_prompt(
+        &self,
+        task_attempt: &TaskAttempt,
+        task: &Task,
+        prompt: String,
+        variant: Option<String>,
+        image_ids: Option<&[Uuid]>,
+    ) -> Result<Option<ExecutionProcess>, ContainerError> {
         // Ensure worktree exists
-        let container_ref = self.ensure_container_exists(&ctx.task_attempt).await?;
+        let container_ref = self.ensure_container_exists(task_attempt).await?;
 
         // Get session id
         let Some(session_id) = ExecutionProcess::find_latest_session_id_by_task_attempt(
             &self.db.pool,
-            ctx.task_attempt.id,
+            task_attempt.id,
         )
         .await?
         else {
             tracing::warn!(
                 "No session id found for attempt {}. Cannot start queued follow-up.",
-                ctx.task_attempt.id
+                task_attempt.id
             );
-            return Ok(());
+            return Ok(None);
         };
 
         // Get last coding agent process to inherit executor profile
         let Some(latest) = ExecutionProcess::find_latest_by_task_attempt_and_run_reason(
             &self.db.pool,
-            ctx.task_attempt.id,
+            task_attempt.id,
             &ExecutionProcessRunReason::CodingAgent,
         )
         .await?
         else {
             tracing::warn!(
                 "No prior CodingAgent process for attempt {}. Cannot start queued follow-up.",
-                ctx.task_attempt.id
+                task_attempt.id
             );
-            return Ok(());
+            return Ok(None);
         };
 
-        use executors::actions::ExecutorActionType;
         let initial_executor_profile_id = match &latest.executor_action()?.typ {
             ExecutorActionType::CodingAgentInitialRequest(req) => req.executor_profile_id.clone(),
             ExecutorActionType::CodingAgentFollowUpRequest(req) => req.executor_profile_id.clone(),
             _ => {
                 tracing::warn!(
                     "Latest process for attempt {} is not a coding agent; skipping queued follow-up",
-                    ctx.task_attempt.id
+                    task_attempt.id
                 );
-                return Ok(());
+                return Ok(None);
             }
         };
 
         let executor_profile_id = executors::profile::ExecutorProfileId {
             executor: initial_executor_profile_id.executor,
-            variant: draft.variant.clone(),
+            variant,
         };
 
         // Prepare cleanup action
-        let cleanup_action = ctx
-            .task
-            .parent_project(&self.db.pool)
-            .await?
-            .and_then(|project| self.cleanup_action(project.cleanup_script));
+        let cleanup_action = match task.parent_project(&self.db.pool).await? {
+            Some(project) => self.cleanup_action(&project).await?,
+            None => None,
+        };
 
         // Handle images: associate, copy to worktree, canonicalize prompt
-        let mut prompt = draft.prompt.clone();
-        if let Some(image_ids) = &draft.image_ids {
+        let mut prompt = prompt;
+        if let Some(image_ids) = image_ids {
             // Associate to task
-            let _ = TaskImage::associate_many_dedup(&self.db.pool, ctx.task.id, image_ids).await;
+            let _ = TaskImage::associate_many_dedup(&self.db.pool, task.id, image_ids).await;
 
             // Copy to worktree and canonicalize
             let worktree_path = std::path::PathBuf::from(&container_ref);
@@ -1253,19 +2293,15 @@ impl LocalContainerService {
         );
 
         // Start the execution
-        let _ = self
+        let execution_process = self
             .start_execution(
-                &ctx.task_attempt,
+                task_attempt,
                 &follow_up_action,
                 &ExecutionProcessRunReason::CodingAgent,
             )
             .await?;
 
-        // Clear the draft to reflect that it has been consumed
-        let _ =
-            Draft::clear_after_send(&self.db.pool, ctx.task_attempt.id, DraftType::FollowUp).await;
-
-        Ok(())
+        Ok(Some(execution_process))
     }
 }
 
@@ -1286,9 +2322,58 @@ fn truncate_to_char_boundary(content: &str, max_len: usize) -> &str {
     &content[..cutoff]
 }
 
+/// Time left until `deadline`, moved back by the time spent paused. `None`
+/// while the run is paused, as a suspended process can't use up its time.
+fn time_remaining(
+    deadline: DateTime<Utc>,
+    paused_for: chrono::Duration,
+    paused_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Option<Duration> {
+    if paused_at.is_some() {
+        return None;
+    }
+    Some(
+        (deadline + paused_for - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
 #[cfg(test)]
 mod tests {
 
+    #[test]
+    fn paused_time_moves_the_timeout_back() {
+        use chrono::{Duration as ChronoDuration, Utc};
+
+        use super::time_remaining;
+
+        let now = Utc::now();
+        let deadline = now - ChronoDuration::minutes(5);
+
+        // Past its deadline without pauses
+        assert_eq!(
+            time_remaining(deadline, ChronoDuration::zero(), None, now),
+            Some(std::time::Duration::ZERO)
+        );
+        // Paused past the timeout: no timer while suspended
+        assert_eq!(
+            time_remaining(
+                deadline,
+                ChronoDuration::zero(),
+                Some(now - ChronoDuration::minutes(10)),
+                now
+            ),
+            None
+        );
+        // Resumed after a 10 minute pause: 5 minutes left
+        assert_eq!(
+            time_remaining(deadline, ChronoDuration::minutes(10), None, now),
+            Some(std::time::Duration::from_secs(5 * 60))
+        );
+    }
+
     #[test]
     fn test_truncate_to_char_boundary() {
         use super::truncate_to_char_boundary;
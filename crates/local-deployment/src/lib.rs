@@ -12,10 +12,13 @@ use services::services::{
     container::ContainerService,
     drafts::DraftsService,
     events::EventService,
+    execution_queue::ExecutionQueue,
     file_search_cache::FileSearchCache,
     filesystem::FilesystemService,
     git::GitService,
     image::ImageService,
+    rate_limiter::ProviderRateLimiter,
+    secrets::SecretsService,
 };
 use tokio::sync::RwLock;
 use utils::{assets::config_path, msg_store::MsgStore};
@@ -36,6 +39,7 @@ pub struct LocalDeployment {
     git: GitService,
     auth: AuthService,
     image: ImageService,
+    secrets: SecretsService,
     filesystem: FilesystemService,
     events: EventService,
     file_search_cache: Arc<FileSearchCache>,
@@ -74,6 +78,7 @@ impl Deployment for LocalDeployment {
         let user_id = generate_user_id();
         let analytics = AnalyticsConfig::new().map(AnalyticsService::new);
         let git = GitService::new();
+        git.set_commit_signing(config.read().await.commit_signing.clone());
         let msg_stores = Arc::new(RwLock::new(HashMap::new()));
         let auth = AuthService::new();
         let filesystem = FilesystemService::new();
@@ -100,9 +105,14 @@ impl Deployment for LocalDeployment {
                 if let Err(e) = image_service.delete_orphaned_images().await {
                     tracing::error!("Failed to clean up orphaned images: {}", e);
                 }
+                if let Err(e) = image_service.delete_orphaned_attachments().await {
+                    tracing::error!("Failed to clean up orphaned attachments: {}", e);
+                }
             });
         }
 
+        let secrets = SecretsService::new()?;
+
         let approvals = Approvals::new(msg_stores.clone());
 
         // We need to make analytics accessible to the ContainerService
@@ -111,14 +121,24 @@ impl Deployment for LocalDeployment {
             user_id: user_id.clone(),
             analytics_service: s.clone(),
         });
+        let execution_queue = ExecutionQueue::new(
+            db.clone(),
+            config.clone(),
+            events_msg_store.clone(),
+            ProviderRateLimiter::new(),
+        );
+        execution_queue.restore().await?;
         let container = LocalContainerService::new(
             db.clone(),
             msg_stores.clone(),
             config.clone(),
             git.clone(),
             image.clone(),
+            secrets.clone(),
             analytics_ctx,
             approvals.clone(),
+            execution_queue,
+            events_msg_store.clone(),
         );
         container.spawn_worktree_cleanup().await;
 
@@ -136,6 +156,7 @@ impl Deployment for LocalDeployment {
             git,
             auth,
             image,
+            secrets,
             filesystem,
             events,
             file_search_cache,
@@ -179,6 +200,10 @@ impl Deployment for LocalDeployment {
         &self.image
     }
 
+    fn secrets(&self) -> &SecretsService {
+        &self.secrets
+    }
+
     fn filesystem(&self) -> &FilesystemService {
         &self.filesystem
     }
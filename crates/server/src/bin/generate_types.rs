@@ -15,8 +15,11 @@ fn generate_types_content() -> String {
         db::models::project::Project::decl(),
         db::models::project::CreateProject::decl(),
         db::models::project::UpdateProject::decl(),
+        db::models::project::DuplicateProject::decl(),
         db::models::project::SearchResult::decl(),
         db::models::project::SearchMatchType::decl(),
+        db::models::project_repository::ProjectRepository::decl(),
+        db::models::project_repository::CreateProjectRepository::decl(),
         executors::actions::ExecutorAction::decl(),
         executors::mcp_config::McpConfig::decl(),
         executors::actions::ExecutorActionType::decl(),
@@ -28,13 +31,85 @@ fn generate_types_content() -> String {
         db::models::tag::Tag::decl(),
         db::models::tag::CreateTag::decl(),
         db::models::tag::UpdateTag::decl(),
+        db::models::label::Label::decl(),
+        db::models::label::CreateLabel::decl(),
+        db::models::label::UpdateLabel::decl(),
+        db::models::label::SetLabels::decl(),
         server::routes::tags::TagSearchParams::decl(),
+        server::routes::search::SearchQuery::decl(),
+        db::models::search::SearchResultKind::decl(),
+        db::models::search::SearchResult::decl(),
         db::models::task::TaskStatus::decl(),
+        db::models::task::TaskPriority::decl(),
+        db::models::task_comment::TaskComment::decl(),
+        db::models::task_comment::CreateTaskComment::decl(),
+        db::models::task_comment::UpdateTaskComment::decl(),
+        db::models::task_attachment::TaskAttachment::decl(),
+        db::models::task_activity::TaskActivityKind::decl(),
+        db::models::task_activity::TaskActivity::decl(),
+        db::models::task_transition::TaskTransition::decl(),
+        server::routes::tasks::assignment::AssignRequest::decl(),
+        db::models::trash::TrashItemKind::decl(),
+        db::models::trash::TrashItem::decl(),
+        db::models::workspace::Workspace::decl(),
+        db::models::workspace::CreateWorkspace::decl(),
+        db::models::workspace::UpdateWorkspace::decl(),
+        db::models::workspace::WorkspaceProjectStats::decl(),
+        server::routes::workspaces::WorkspaceStats::decl(),
+        db::models::project_column::ProjectColumn::decl(),
+        db::models::project_env_var::ProjectEnvVar::decl(),
+        db::models::project_env_var::UpsertProjectEnvVar::decl(),
+        services::services::secrets::ProjectEnvVarEntry::decl(),
+        db::models::project_mcp_server::ProjectMcpServer::decl(),
+        db::models::project_mcp_server::CreateProjectMcpServer::decl(),
+        db::models::project_mcp_server::UpdateProjectMcpServer::decl(),
+        db::models::project_context_pack::ProjectContextPack::decl(),
+        db::models::project_context_pack::CreateProjectContextPack::decl(),
+        db::models::project_context_pack::UpdateProjectContextPack::decl(),
+        db::models::project_approval_rule::ProjectApprovalRule::decl(),
+        db::models::project_approval_rule::CreateProjectApprovalRule::decl(),
+        db::models::project_executor_fallback::ProjectExecutorFallback::decl(),
+        db::models::project_executor_fallback::SetProjectExecutorFallbacks::decl(),
+        db::models::project_sandbox::ProjectSandbox::decl(),
+        db::models::project_sandbox::UpsertProjectSandbox::decl(),
+        db::models::project_hosting::HostingProviderKind::decl(),
+        db::models::project_hosting::ProjectHosting::decl(),
+        db::models::project_hosting::UpsertProjectHosting::decl(),
+        db::models::project_rebase_policy::RebasePolicyMode::decl(),
+        db::models::project_rebase_policy::ProjectRebasePolicy::decl(),
+        db::models::project_rebase_policy::UpsertProjectRebasePolicy::decl(),
+        db::models::project_branch_template::ProjectBranchTemplate::decl(),
+        db::models::project_branch_template::UpsertProjectBranchTemplate::decl(),
+        db::models::project_merge_settings::MergeStrategy::decl(),
+        db::models::project_merge_settings::ProjectMergeSettings::decl(),
+        db::models::project_merge_settings::UpsertProjectMergeSettings::decl(),
+        db::models::project_turn_test::ProjectTurnTest::decl(),
+        db::models::project_turn_test::UpsertProjectTurnTest::decl(),
+        db::models::project_column::CreateProjectColumn::decl(),
+        db::models::project_column::UpdateProjectColumn::decl(),
+        db::models::project_column::ColumnWipStatus::decl(),
+        db::models::project_column::ReorderProjectColumns::decl(),
+        db::models::project_script::ProjectScriptKind::decl(),
+        db::models::project_script::ProjectScript::decl(),
+        db::models::project_script::CreateProjectScript::decl(),
+        db::models::project_script::UpdateProjectScript::decl(),
+        db::models::project_script::ReorderProjectScripts::decl(),
+        server::routes::projects::scripts::RunProjectScript::decl(),
         db::models::task::Task::decl(),
         db::models::task::TaskWithAttemptStatus::decl(),
         db::models::task::TaskRelationships::decl(),
         db::models::task::CreateTask::decl(),
         db::models::task::UpdateTask::decl(),
+        db::models::task_schedule::TaskSchedule::decl(),
+        db::models::task_schedule::UpsertTaskSchedule::decl(),
+        db::models::task_recurrence::TaskRecurrence::decl(),
+        db::models::task_recurrence::UpsertTaskRecurrence::decl(),
+        db::models::task_dependency::TaskDependency::decl(),
+        db::models::task_dependency::CreateTaskDependency::decl(),
+        db::models::task_dependency::TaskDependencies::decl(),
+        db::models::task_template::TaskTemplate::decl(),
+        db::models::task_template::CreateTaskTemplate::decl(),
+        db::models::task_template::UpdateTaskTemplate::decl(),
         db::models::image::Image::decl(),
         db::models::image::CreateImage::decl(),
         utils::response::ApiResponse::<()>::decl(),
@@ -50,6 +125,23 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::ChangeTargetBranchRequest::decl(),
         server::routes::task_attempts::ChangeTargetBranchResponse::decl(),
         server::routes::tasks::CreateAndStartTaskRequest::decl(),
+        server::routes::tasks::CreateTaskFromTemplate::decl(),
+        server::routes::tasks::bulk::BulkTaskAction::decl(),
+        server::routes::tasks::bulk::BulkTaskRequest::decl(),
+        server::routes::tasks::bulk::BulkTaskResponse::decl(),
+        server::routes::tasks::swimlanes::SwimlaneGroupBy::decl(),
+        server::routes::tasks::swimlanes::BoardColumnCount::decl(),
+        server::routes::tasks::swimlanes::SwimlaneColumnCount::decl(),
+        server::routes::tasks::swimlanes::Swimlane::decl(),
+        server::routes::tasks::swimlanes::SwimlaneBoard::decl(),
+        server::routes::tasks::swimlanes::TaskListing::decl(),
+        server::routes::projects::ImportGitHubIssuesRequest::decl(),
+        server::routes::projects::ImportGitHubIssuesResponse::decl(),
+        server::routes::projects::ImportExternalTicketsRequest::decl(),
+        server::routes::projects::ImportExternalTicketsResponse::decl(),
+        db::models::external_task_link::ExternalProvider::decl(),
+        db::models::external_task_link::ExternalTaskLink::decl(),
+        server::routes::task_attempts::MergeTaskAttemptRequest::decl(),
         server::routes::task_attempts::CreateGitHubPrRequest::decl(),
         server::routes::images::ImageResponse::decl(),
         services::services::github_service::GitHubServiceError::decl(),
@@ -62,12 +154,35 @@ fn generate_types_content() -> String {
         services::services::config::SoundFile::decl(),
         services::services::config::UiLanguage::decl(),
         services::services::config::ShowcaseState::decl(),
+        services::services::config::WorktreeCleanupConfig::decl(),
+        services::services::config::IntegrationsConfig::decl(),
+        services::services::config::JiraConfig::decl(),
+        services::services::config::LinearConfig::decl(),
+        services::services::config::HostingConfig::decl(),
+        services::services::config::GitLabConfig::decl(),
+        services::services::config::BitbucketConfig::decl(),
+        services::services::config::SigningFormat::decl(),
+        services::services::config::CommitSigningConfig::decl(),
+        server::routes::containers::WorktreeUsage::decl(),
+        server::routes::containers::WorktreeDiskUsage::decl(),
+        server::routes::containers::WorktreeState::decl(),
+        server::routes::containers::WorktreeOverview::decl(),
+        server::routes::containers::RelinkWorktreeRequest::decl(),
+        server::routes::task_attempts::worktree::AttemptWorktree::decl(),
+        server::routes::task_attempts::worktree::RelocateWorktreeRequest::decl(),
         services::services::auth::DeviceFlowStartResponse::decl(),
         server::routes::auth::DevicePollStatus::decl(),
         server::routes::auth::CheckTokenResponse::decl(),
         services::services::git::GitBranch::decl(),
+        services::services::git::GitCommitSummary::decl(),
         utils::diff::Diff::decl(),
         utils::diff::DiffChangeKind::decl(),
+        utils::diff::DiffFile::decl(),
+        utils::diff::DiffFilePage::decl(),
+        utils::diff::DiffFileHunks::decl(),
+        services::services::git::DiffSelection::decl(),
+        server::routes::task_attempts::partial_commit::PartialCommitRequest::decl(),
+        server::routes::task_attempts::partial_commit::PartialCommitResponse::decl(),
         services::services::github_service::RepositoryInfo::decl(),
         executors::command::CommandBuilder::decl(),
         executors::profile::ExecutorProfileId::decl(),
@@ -84,25 +199,90 @@ fn generate_types_content() -> String {
         executors::executors::codex::ReasoningSummary::decl(),
         executors::executors::codex::ReasoningSummaryFormat::decl(),
         executors::executors::cursor::CursorAgent::decl(),
+        executors::executors::custom::CustomOutputFormat::decl(),
+        executors::executors::custom::CustomEntryType::decl(),
+        executors::executors::custom::CustomOutputRule::decl(),
+        executors::executors::custom::Custom::decl(),
         executors::executors::copilot::Copilot::decl(),
+        executors::executors::aider::Aider::decl(),
         executors::executors::opencode::Opencode::decl(),
+        executors::executors::openhands::Openhands::decl(),
         executors::executors::qwen::QwenCode::decl(),
         executors::executors::AppendPrompt::decl(),
         executors::actions::coding_agent_initial::CodingAgentInitialRequest::decl(),
         executors::actions::coding_agent_follow_up::CodingAgentFollowUpRequest::decl(),
         server::routes::task_attempts::CreateTaskAttemptBody::decl(),
+        server::routes::task_attempts::SetExecutionTimeoutRequest::decl(),
+        server::routes::task_attempts::AttemptStack::decl(),
         server::routes::task_attempts::RunAgentSetupRequest::decl(),
         server::routes::task_attempts::RunAgentSetupResponse::decl(),
         server::routes::task_attempts::RebaseTaskAttemptRequest::decl(),
+        server::routes::task_attempts::RebaseStrategy::decl(),
+        server::routes::task_attempts::RebaseTaskAttemptResponse::decl(),
         server::routes::task_attempts::GitOperationError::decl(),
         server::routes::task_attempts::ReplaceProcessRequest::decl(),
+        server::routes::task_attempts::fork::ForkTaskAttemptRequest::decl(),
+        db::models::attempt_fork::AttemptFork::decl(),
+        server::routes::task_attempts::fork::AttemptForks::decl(),
         server::routes::task_attempts::CommitInfo::decl(),
         server::routes::task_attempts::BranchStatus::decl(),
         services::services::git::ConflictOp::decl(),
+        services::services::conflicts::ConflictSegment::decl(),
+        services::services::conflicts::ConflictedFile::decl(),
+        services::services::conflicts::HunkResolution::decl(),
+        services::services::conflicts::FileResolution::decl(),
+        server::routes::task_attempts::conflicts::AttemptConflicts::decl(),
+        server::routes::task_attempts::conflicts::ResolveConflictsRequest::decl(),
+        db::models::pr_review_comment::PrReviewComment::decl(),
+        server::routes::task_attempts::review_comments::SendReviewCommentsRequest::decl(),
+        db::models::attempt_base_status::AttemptBaseStatus::decl(),
+        services::services::commit_signing::CommitSignature::decl(),
+        db::models::ci_check::CiCheckState::decl(),
+        db::models::ci_check::CiCheck::decl(),
         db::models::task_attempt::TaskAttempt::decl(),
+        db::models::checkpoint::Checkpoint::decl(),
+        server::routes::task_attempts::checkpoints::CreateCheckpointRequest::decl(),
+        server::routes::task_attempts::checkpoints::ResumeFromCheckpointRequest::decl(),
+        server::routes::task_attempts::compare::DiffSummaryStats::decl(),
+        server::routes::task_attempts::compare::AttemptComparisonSide::decl(),
+        server::routes::task_attempts::compare::AttemptComparison::decl(),
+        db::models::comparison_group::ComparisonGroup::decl(),
+        db::models::comparison_group::ComparisonGroupAttempt::decl(),
+        server::routes::task_attempts::comparison_groups::CreateComparisonGroup::decl(),
+        server::routes::task_attempts::comparison_groups::PickComparisonWinner::decl(),
+        server::routes::task_attempts::comparison_groups::ComparisonAttemptSummary::decl(),
+        server::routes::task_attempts::comparison_groups::ComparisonGroupSummary::decl(),
+        server::routes::task_attempts::export::TranscriptExecution::decl(),
+        server::routes::task_attempts::export::AttemptTranscript::decl(),
+        server::routes::task_attempts::repositories::RepositoryDiff::decl(),
+        server::routes::task_attempts::repositories::CommitRepositoriesRequest::decl(),
+        server::routes::task_attempts::repositories::RepositoryCommitResult::decl(),
+        server::routes::task_attempts::repositories::CreateRepositoryPrsRequest::decl(),
+        server::routes::task_attempts::repositories::RepositoryPrResult::decl(),
         db::models::execution_process::ExecutionProcess::decl(),
         db::models::execution_process::ExecutionProcessStatus::decl(),
         db::models::execution_process::ExecutionProcessRunReason::decl(),
+        db::models::execution_process_event::ExecutionProcessEvent::decl(),
+        db::models::execution_fallback::ExecutionFallback::decl(),
+        db::models::execution_resumption::ExecutionResumption::decl(),
+        db::models::turn_test_run::TurnTestRun::decl(),
+        services::services::execution_queue::QueuedAttempt::decl(),
+        services::services::rate_limiter::ProviderLimitStatus::decl(),
+        db::models::attempt_usage::AttemptUsage::decl(),
+        db::models::attempt_plan::AttemptPlanStatus::decl(),
+        db::models::attempt_plan::AttemptPlan::decl(),
+        db::models::attempt_plan::ApproveAttemptPlan::decl(),
+        db::models::proposed_task::ProposedTaskStatus::decl(),
+        db::models::proposed_task::ProposedTask::decl(),
+        db::models::proposed_task::AcceptProposedTask::decl(),
+        db::models::attempt_usage::UsageTotals::decl(),
+        db::models::metrics::DurationStats::decl(),
+        db::models::metrics::ColumnTime::decl(),
+        db::models::metrics::WeeklyThroughput::decl(),
+        db::models::metrics::AttemptDuration::decl(),
+        db::models::metrics::ProjectMetrics::decl(),
+        db::models::metrics::TaskMetrics::decl(),
+        server::routes::task_attempts::AttemptUsageSummary::decl(),
         db::models::merge::Merge::decl(),
         db::models::merge::DirectMerge::decl(),
         db::models::merge::PrMerge::decl(),
@@ -110,6 +290,8 @@ fn generate_types_content() -> String {
         db::models::merge::PullRequestInfo::decl(),
         db::models::draft::Draft::decl(),
         db::models::draft::DraftType::decl(),
+        db::models::queued_follow_up::QueuedFollowUp::decl(),
+        db::models::queued_follow_up::CreateQueuedFollowUp::decl(),
         executors::logs::CommandExitStatus::decl(),
         executors::logs::CommandRunResult::decl(),
         executors::logs::NormalizedEntry::decl(),
@@ -122,9 +304,13 @@ fn generate_types_content() -> String {
         executors::logs::ToolResultValueType::decl(),
         executors::logs::ToolStatus::decl(),
         executors::logs::utils::patch::PatchType::decl(),
+        executors::logs::events::MessageRole::decl(),
+        executors::logs::events::AgentEvent::decl(),
         utils::approvals::ApprovalStatus::decl(),
         utils::approvals::CreateApprovalRequest::decl(),
         utils::approvals::ApprovalResponse::decl(),
+        utils::approvals::ApprovalRequest::decl(),
+        server::routes::approvals::DenyApprovalRequest::decl(),
         serde_json::Value::decl(),
     ];
 
@@ -186,10 +372,18 @@ fn generate_schemas() -> Result<HashMap<&'static str, String>, serde_json::Error
             "cursor_agent",
             generate_json_schema::<executors::executors::cursor::CursorAgent>()?,
         ),
+        (
+            "custom",
+            generate_json_schema::<executors::executors::custom::Custom>()?,
+        ),
         (
             "opencode",
             generate_json_schema::<executors::executors::opencode::Opencode>()?,
         ),
+        (
+            "openhands",
+            generate_json_schema::<executors::executors::openhands::Openhands>()?,
+        ),
         (
             "qwen_code",
             generate_json_schema::<executors::executors::qwen::QwenCode>()?,
@@ -198,6 +392,10 @@ fn generate_schemas() -> Result<HashMap<&'static str, String>, serde_json::Error
             "copilot",
             generate_json_schema::<executors::executors::copilot::Copilot>()?,
         ),
+        (
+            "aider",
+            generate_json_schema::<executors::executors::aider::Aider>()?,
+        ),
     ]);
     println!(
         "✅ JSON schemas generated. {} schemas created.",
@@ -8,12 +8,13 @@ use db::models::{
     execution_process::ExecutionProcessError, project::ProjectError, task_attempt::TaskAttemptError,
 };
 use deployment::DeploymentError;
-use executors::executors::ExecutorError;
+use executors::{executors::ExecutorError, profile::ProfileError};
 use git2::Error as Git2Error;
 use services::services::{
-    auth::AuthError, config::ConfigError, container::ContainerError, drafts::DraftsServiceError,
-    git::GitServiceError, github_service::GitHubServiceError, image::ImageError,
-    worktree_manager::WorktreeError,
+    approvals::ApprovalError, auth::AuthError, config::ConfigError, conflicts::ConflictError,
+    container::ContainerError, drafts::DraftsServiceError, git::GitServiceError,
+    github_service::GitHubServiceError, hosting::HostingError, image::ImageError,
+    integrations::IntegrationError, secrets::SecretsError, worktree_manager::WorktreeError,
 };
 use thiserror::Error;
 use utils::response::ApiResponse;
@@ -40,6 +41,8 @@ pub enum ApiError {
     #[error(transparent)]
     Executor(#[from] ExecutorError),
     #[error(transparent)]
+    Profile(#[from] ProfileError),
+    #[error(transparent)]
     Database(#[from] sqlx::Error),
     #[error(transparent)]
     Worktree(#[from] WorktreeError),
@@ -49,12 +52,24 @@ pub enum ApiError {
     Image(#[from] ImageError),
     #[error(transparent)]
     Drafts(#[from] DraftsServiceError),
+    #[error(transparent)]
+    Integration(#[from] IntegrationError),
+    #[error(transparent)]
+    Hosting(#[from] HostingError),
+    #[error(transparent)]
+    Secrets(#[from] SecretsError),
+    #[error(transparent)]
+    Approval(#[from] ApprovalError),
+    #[error(transparent)]
+    Conflicts(#[from] ConflictError),
     #[error("Multipart error: {0}")]
     Multipart(#[from] MultipartError),
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     #[error("Conflict: {0}")]
     Conflict(String),
+    #[error("Bad request: {0}")]
+    BadRequest(String),
 }
 
 impl From<Git2Error> for ApiError {
@@ -82,20 +97,46 @@ impl IntoResponse for ApiError {
                 services::services::git::GitServiceError::RebaseInProgress => {
                     (StatusCode::CONFLICT, "GitServiceError")
                 }
+                services::services::git::GitServiceError::InvalidSelection(_) => {
+                    (StatusCode::BAD_REQUEST, "GitServiceError")
+                }
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "GitServiceError"),
             },
             ApiError::GitHubService(_) => (StatusCode::INTERNAL_SERVER_ERROR, "GitHubServiceError"),
             ApiError::Auth(_) => (StatusCode::INTERNAL_SERVER_ERROR, "AuthError"),
             ApiError::Deployment(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DeploymentError"),
-            ApiError::Container(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ContainerError"),
+            ApiError::Container(container_err) => match container_err {
+                ContainerError::ProjectArchived
+                | ContainerError::AttemptArchived
+                | ContainerError::TaskTrashed => (StatusCode::CONFLICT, "ContainerError"),
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "ContainerError"),
+            },
             ApiError::Executor(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ExecutorError"),
+            ApiError::Profile(profile_err) => match profile_err {
+                ProfileError::NotFound(_) => (StatusCode::NOT_FOUND, "ProfileError"),
+                ProfileError::Validation(_) => (StatusCode::BAD_REQUEST, "ProfileError"),
+                ProfileError::CannotDeleteExecutor { .. }
+                | ProfileError::CannotDeleteBuiltInConfig { .. } => {
+                    (StatusCode::CONFLICT, "ProfileError")
+                }
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "ProfileError"),
+            },
             ApiError::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DatabaseError"),
-            ApiError::Worktree(_) => (StatusCode::INTERNAL_SERVER_ERROR, "WorktreeError"),
+            ApiError::Worktree(worktree_err) => match worktree_err {
+                WorktreeError::BranchCheckedOut(..) => (StatusCode::CONFLICT, "WorktreeError"),
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "WorktreeError"),
+            },
             ApiError::Config(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ConfigError"),
             ApiError::Image(img_err) => match img_err {
                 ImageError::InvalidFormat => (StatusCode::BAD_REQUEST, "InvalidImageFormat"),
                 ImageError::TooLarge(_, _) => (StatusCode::PAYLOAD_TOO_LARGE, "ImageTooLarge"),
                 ImageError::NotFound => (StatusCode::NOT_FOUND, "ImageNotFound"),
+                ImageError::EmptyAttachment => (StatusCode::BAD_REQUEST, "EmptyAttachment"),
+                ImageError::AttachmentTooLarge(_, _)
+                | ImageError::TaskAttachmentsTooLarge(_, _) => {
+                    (StatusCode::PAYLOAD_TOO_LARGE, "AttachmentTooLarge")
+                }
+                ImageError::AttachmentNotFound => (StatusCode::NOT_FOUND, "AttachmentNotFound"),
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "ImageError"),
             },
             ApiError::Drafts(drafts_err) => match drafts_err {
@@ -111,9 +152,38 @@ impl IntoResponse for ApiError {
                     (StatusCode::INTERNAL_SERVER_ERROR, "ExecutionProcessError")
                 }
             },
+            ApiError::Integration(integration_err) => match integration_err {
+                IntegrationError::NotConfigured(_) => (StatusCode::BAD_REQUEST, "IntegrationError"),
+                IntegrationError::Api(..) => (StatusCode::BAD_GATEWAY, "IntegrationError"),
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "IntegrationError"),
+            },
+            ApiError::Hosting(hosting_err) => match hosting_err {
+                HostingError::NotConfigured(_)
+                | HostingError::InvalidUrl(..)
+                | HostingError::Unsupported(..) => (StatusCode::BAD_REQUEST, "HostingError"),
+                HostingError::TokenInvalid(_) => (StatusCode::UNAUTHORIZED, "HostingError"),
+                HostingError::Api(..) => (StatusCode::BAD_GATEWAY, "HostingError"),
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "HostingError"),
+            },
+            ApiError::Secrets(secrets_err) => match secrets_err {
+                SecretsError::InvalidName(_) => (StatusCode::BAD_REQUEST, "SecretsError"),
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "SecretsError"),
+            },
+            ApiError::Approval(approval_err) => match approval_err {
+                ApprovalError::NotFound => (StatusCode::NOT_FOUND, "ApprovalError"),
+                ApprovalError::AlreadyCompleted => (StatusCode::CONFLICT, "ApprovalError"),
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "ApprovalError"),
+            },
+            ApiError::Conflicts(conflict_err) => match conflict_err {
+                ConflictError::NotConflicted(_) | ConflictError::InvalidResolution(..) => {
+                    (StatusCode::BAD_REQUEST, "ConflictResolutionError")
+                }
+                _ => (StatusCode::INTERNAL_SERVER_ERROR, "ConflictResolutionError"),
+            },
             ApiError::Io(_) => (StatusCode::INTERNAL_SERVER_ERROR, "IoError"),
             ApiError::Multipart(_) => (StatusCode::BAD_REQUEST, "MultipartError"),
             ApiError::Conflict(_) => (StatusCode::CONFLICT, "ConflictError"),
+            ApiError::BadRequest(_) => (StatusCode::BAD_REQUEST, "BadRequest"),
         };
 
         let error_message = match &self {
@@ -125,12 +195,25 @@ impl IntoResponse for ApiError {
                     *max as f64 / 1_048_576.0
                 ),
                 ImageError::NotFound => "Image not found.".to_string(),
+                ImageError::EmptyAttachment => "The attached file is empty.".to_string(),
+                ImageError::AttachmentTooLarge(size, max) => format!(
+                    "This file is too large ({:.1} MB). Maximum file size is {:.1} MB.",
+                    *size as f64 / 1_048_576.0,
+                    *max as f64 / 1_048_576.0
+                ),
+                ImageError::TaskAttachmentsTooLarge(size, max) => format!(
+                    "This task's attachments would take {:.1} MB. A task can hold at most {:.1} MB of attachments.",
+                    *size as f64 / 1_048_576.0,
+                    *max as f64 / 1_048_576.0
+                ),
+                ImageError::AttachmentNotFound => "Attachment not found.".to_string(),
                 _ => {
                     "Failed to process image. Please try again.".to_string()
                 }
             },
             ApiError::GitService(git_err) => match git_err {
                 services::services::git::GitServiceError::MergeConflicts(msg) => msg.clone(),
+                services::services::git::GitServiceError::InvalidSelection(msg) => msg.clone(),
                 services::services::git::GitServiceError::RebaseInProgress => {
                     "A rebase is already in progress. Resolve conflicts or abort the rebase, then retry.".to_string()
                 }
@@ -138,6 +221,24 @@ impl IntoResponse for ApiError {
             },
             ApiError::Multipart(_) => "Failed to upload file. Please ensure the file is valid and try again.".to_string(),
             ApiError::Conflict(msg) => msg.clone(),
+            ApiError::Profile(
+                profile_err @ (ProfileError::NotFound(_)
+                | ProfileError::Validation(_)
+                | ProfileError::CannotDeleteExecutor { .. }
+                | ProfileError::CannotDeleteBuiltInConfig { .. }),
+            ) => profile_err.to_string(),
+            ApiError::BadRequest(msg) => msg.clone(),
+            ApiError::Approval(
+                approval_err @ (ApprovalError::NotFound | ApprovalError::AlreadyCompleted),
+            ) => approval_err.to_string(),
+            ApiError::Conflicts(
+                conflict_err @ (ConflictError::NotConflicted(_)
+                | ConflictError::InvalidResolution(..)),
+            ) => conflict_err.to_string(),
+            ApiError::Secrets(SecretsError::InvalidName(name)) => format!(
+                "'{}' is not a valid variable name. Use letters, digits and underscores, not starting with a digit.",
+                name
+            ),
             ApiError::Drafts(drafts_err) => match drafts_err {
                 DraftsServiceError::Conflict(msg) => msg.clone(),
                 DraftsServiceError::Database(_) => format!("{}: {}", error_type, drafts_err),
@@ -49,6 +49,14 @@ async fn main() -> Result<(), VibeKanbanError> {
     deployment.cleanup_orphan_executions().await?;
     deployment.backfill_before_head_commits().await?;
     deployment.spawn_pr_monitor_service().await;
+    deployment.spawn_github_issue_sync_service().await;
+    deployment.spawn_integration_sync_service().await;
+    deployment.spawn_task_scheduler().await;
+    deployment.spawn_overdue_task_monitor().await;
+    deployment.spawn_task_recurrence_monitor().await;
+    deployment.spawn_trash_purge().await;
+    deployment.spawn_search_index_backfill().await;
+    deployment.spawn_base_branch_watcher().await;
     deployment
         .track_if_analytics_allowed("session_start", serde_json::json!({}))
         .await;
@@ -210,6 +210,10 @@ pub struct StartTaskAttemptRequest {
     pub variant: Option<String>,
     #[schemars(description = "The base branch to use for the attempt")]
     pub base_branch: String,
+    #[schemars(
+        description = "Optional: have the agent answer without changing code, for questions about the codebase"
+    )]
+    pub read_only: Option<bool>,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -444,6 +448,7 @@ impl TaskServer {
             executor,
             variant,
             base_branch,
+            read_only,
         }): Parameters<StartTaskAttemptRequest>,
     ) -> Result<CallToolResult, ErrorData> {
         let base_branch = base_branch.trim().to_string();
@@ -485,6 +490,10 @@ impl TaskServer {
             task_id,
             executor_profile_id,
             base_branch,
+            execution_timeout_minutes: None,
+            parent_attempt_id: None,
+            plan_first: None,
+            read_only,
         };
 
         let url = self.url("/api/task-attempts");
@@ -534,6 +543,10 @@ impl TaskServer {
             status,
             parent_task_attempt: None,
             image_ids: None,
+            parent_task_id: None,
+            priority: None,
+            column_id: None,
+            due_date: None,
         };
         let url = self.url(&format!("/api/tasks/{}", task_id));
         let updated_task: Task = match self.send_json(self.client.put(&url).json(&payload)).await {
@@ -1,18 +1,90 @@
 use axum::{
     extract::{Path, Request, State},
-    http::StatusCode,
+    http::{Method, StatusCode},
     middleware::Next,
     response::Response,
 };
 use db::models::{
-    execution_process::ExecutionProcess, project::Project, tag::Tag, task::Task,
-    task_attempt::TaskAttempt,
+    execution_process::ExecutionProcess, label::Label, project::Project, tag::Tag, task::Task,
+    task_attempt::TaskAttempt, task_template::TaskTemplate, workspace::Workspace,
 };
 use deployment::Deployment;
 use uuid::Uuid;
 
 use crate::DeploymentImpl;
 
+/// Archived projects are read-only: only GET requests reach their project,
+/// tasks and attempts
+async fn reject_archived_project_write(
+    deployment: &DeploymentImpl,
+    request: &Request,
+    project_id: Uuid,
+) -> Result<(), StatusCode> {
+    if request.method() == Method::GET {
+        return Ok(());
+    }
+    match Project::is_archived(&deployment.db().pool, project_id).await {
+        Ok(false) => Ok(()),
+        Ok(true) => {
+            tracing::warn!(
+                "Rejected {} on archived project {}",
+                request.method(),
+                project_id
+            );
+            Err(StatusCode::CONFLICT)
+        }
+        Err(e) => {
+            tracing::error!(
+                "Failed to check whether project {} is archived: {}",
+                project_id,
+                e
+            );
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+/// Trashed tasks and projects are read-only: besides GET requests, only a
+/// restore reaches them until they are purged
+async fn reject_trashed_write(
+    deployment: &DeploymentImpl,
+    request: &Request,
+    project_id: Uuid,
+    task_id: Option<Uuid>,
+) -> Result<(), StatusCode> {
+    if request.method() == Method::GET || request.uri().path().ends_with("/restore") {
+        return Ok(());
+    }
+    let pool = &deployment.db().pool;
+    let trashed = match Project::is_trashed(pool, project_id).await {
+        Ok(true) => Ok(true),
+        Ok(false) => match task_id {
+            Some(task_id) => Task::is_trashed(pool, task_id).await,
+            None => Ok(false),
+        },
+        Err(e) => Err(e),
+    };
+    match trashed {
+        Ok(false) => Ok(()),
+        Ok(true) => {
+            tracing::warn!(
+                "Rejected {} on trashed task or project {}",
+                request.method(),
+                task_id.unwrap_or(project_id)
+            );
+            Err(StatusCode::CONFLICT)
+        }
+        Err(e) => {
+            tracing::error!(
+                "Failed to check whether project {} is trashed: {}",
+                project_id,
+                e
+            );
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 pub async fn load_project_middleware(
     State(deployment): State<DeploymentImpl>,
     Path(project_id): Path<Uuid>,
@@ -32,6 +104,9 @@ pub async fn load_project_middleware(
         }
     };
 
+    reject_archived_project_write(&deployment, &request, project.id).await?;
+    reject_trashed_write(&deployment, &request, project.id, None).await?;
+
     // Insert the project as an extension
     let mut request = request;
     request.extensions_mut().insert(project);
@@ -59,6 +134,9 @@ pub async fn load_task_middleware(
         }
     };
 
+    reject_archived_project_write(&deployment, &request, task.project_id).await?;
+    reject_trashed_write(&deployment, &request, task.project_id, Some(task.id)).await?;
+
     // Insert both models as extensions
     let mut request = request;
     request.extensions_mut().insert(task);
@@ -86,6 +164,19 @@ pub async fn load_task_attempt_middleware(
         }
     };
 
+    if request.method() != Method::GET {
+        let task = match attempt.parent_task(&deployment.db().pool).await {
+            Ok(Some(task)) => task,
+            Ok(None) => return Err(StatusCode::NOT_FOUND),
+            Err(e) => {
+                tracing::error!("Failed to fetch task of TaskAttempt {}: {}", attempt.id, e);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        };
+        reject_archived_project_write(&deployment, &request, task.project_id).await?;
+        reject_trashed_write(&deployment, &request, task.project_id, Some(task.id)).await?;
+    }
+
     // Insert the attempt into extensions
     request.extensions_mut().insert(attempt);
 
@@ -203,3 +294,70 @@ pub async fn load_tag_middleware(
     // Continue with the next middleware/handler
     Ok(next.run(request).await)
 }
+
+// Middleware that loads and injects Label based on the label_id path parameter
+pub async fn load_label_middleware(
+    State(deployment): State<DeploymentImpl>,
+    Path(label_id): Path<Uuid>,
+    mut request: axum::extract::Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let label = match Label::find_by_id(&deployment.db().pool, label_id).await {
+        Ok(Some(label)) => label,
+        Ok(None) => {
+            tracing::warn!("Label {} not found", label_id);
+            return Err(StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch label {}: {}", label_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    request.extensions_mut().insert(label);
+    Ok(next.run(request).await)
+}
+
+pub async fn load_task_template_middleware(
+    State(deployment): State<DeploymentImpl>,
+    Path(template_id): Path<Uuid>,
+    mut request: axum::extract::Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let template = match TaskTemplate::find_by_id(&deployment.db().pool, template_id).await {
+        Ok(Some(template)) => template,
+        Ok(None) => {
+            tracing::warn!("Task template {} not found", template_id);
+            return Err(StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch task template {}: {}", template_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    request.extensions_mut().insert(template);
+    Ok(next.run(request).await)
+}
+
+pub async fn load_workspace_middleware(
+    State(deployment): State<DeploymentImpl>,
+    Path(workspace_id): Path<Uuid>,
+    mut request: axum::extract::Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let workspace = match Workspace::find_by_id(&deployment.db().pool, workspace_id).await {
+        Ok(Some(workspace)) => workspace,
+        Ok(None) => {
+            tracing::warn!("Workspace {} not found", workspace_id);
+            return Err(StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch workspace {}: {}", workspace_id, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+
+    request.extensions_mut().insert(workspace);
+    Ok(next.run(request).await)
+}
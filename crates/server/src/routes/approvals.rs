@@ -2,12 +2,46 @@ use axum::{
     Json, Router,
     extract::{Path, State},
     http::StatusCode,
+    response::Json as ResponseJson,
     routing::post,
 };
 use deployment::Deployment;
-use utils::approvals::{ApprovalResponse, ApprovalStatus};
+use serde::Deserialize;
+use services::services::approvals::ToolContext;
+use ts_rs::TS;
+use utils::{
+    approvals::{ApprovalResponse, ApprovalStatus},
+    response::ApiResponse,
+};
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct DenyApprovalRequest {
+    /// Passed to the agent as the reason the call was denied
+    #[serde(default)]
+    #[ts(optional)]
+    pub reason: Option<String>,
+}
 
-use crate::DeploymentImpl;
+async fn track_response(
+    deployment: &DeploymentImpl,
+    id: &str,
+    status: &ApprovalStatus,
+    context: ToolContext,
+) {
+    deployment
+        .track_if_analytics_allowed(
+            "approval_responded",
+            serde_json::json!({
+                "approval_id": id,
+                "status": format!("{:?}", status),
+                "tool_name": context.tool_name,
+                "execution_process_id": context.execution_process_id.to_string(),
+            }),
+        )
+        .await;
+}
 
 pub async fn respond_to_approval(
     State(deployment): State<DeploymentImpl>,
@@ -18,18 +52,7 @@ pub async fn respond_to_approval(
 
     match service.respond(&deployment.db().pool, &id, request).await {
         Ok((status, context)) => {
-            deployment
-                .track_if_analytics_allowed(
-                    "approval_responded",
-                    serde_json::json!({
-                        "approval_id": &id,
-                        "status": format!("{:?}", status),
-                        "tool_name": context.tool_name,
-                        "execution_process_id": context.execution_process_id.to_string(),
-                    }),
-                )
-                .await;
-
+            track_response(&deployment, &id, &status, context).await;
             Ok(Json(status))
         }
         Err(e) => {
@@ -39,6 +62,41 @@ pub async fn respond_to_approval(
     }
 }
 
+async fn resolve_approval(
+    deployment: &DeploymentImpl,
+    id: &str,
+    status: ApprovalStatus,
+) -> Result<ResponseJson<ApiResponse<ApprovalStatus>>, ApiError> {
+    let (status, context) = deployment
+        .approvals()
+        .resolve(&deployment.db().pool, id, status)
+        .await?;
+    track_response(deployment, id, &status, context).await;
+    Ok(ResponseJson(ApiResponse::success(status)))
+}
+
+/// Let a tool call waiting for approval run
+pub async fn approve_tool_call(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<String>,
+) -> Result<ResponseJson<ApiResponse<ApprovalStatus>>, ApiError> {
+    resolve_approval(&deployment, &id, ApprovalStatus::Approved).await
+}
+
+/// Refuse a tool call waiting for approval; the agent is told the reason
+/// and carries on without it
+pub async fn deny_tool_call(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<String>,
+    Json(payload): Json<DenyApprovalRequest>,
+) -> Result<ResponseJson<ApiResponse<ApprovalStatus>>, ApiError> {
+    let reason = payload.reason.filter(|reason| !reason.trim().is_empty());
+    resolve_approval(&deployment, &id, ApprovalStatus::Denied { reason }).await
+}
+
 pub fn router() -> Router<DeploymentImpl> {
-    Router::new().route("/approvals/{id}/respond", post(respond_to_approval))
+    Router::new()
+        .route("/approvals/{id}/respond", post(respond_to_approval))
+        .route("/approvals/{id}/approve", post(approve_tool_call))
+        .route("/approvals/{id}/deny", post(deny_tool_call))
 }
@@ -6,17 +6,21 @@ use axum::{
     extract::{Path, Query, State},
     http,
     response::{Json as ResponseJson, Response},
-    routing::{get, put},
+    routing::{delete, get, put},
 };
 use deployment::{Deployment, DeploymentError};
 use executors::{
-    executors::{BaseAgentCapability, BaseCodingAgent, StandardCodingAgentExecutor},
+    executors::{BaseAgentCapability, BaseCodingAgent, CodingAgent, StandardCodingAgentExecutor},
     mcp_config::{McpConfig, read_agent_config, write_agent_config},
-    profile::{ExecutorConfigs, ExecutorProfileId},
+    profile::{ExecutorConfigs, ExecutorProfileId, ProfileError},
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use services::services::config::{Config, ConfigError, SoundFile, save_config_to_file};
+use services::services::{
+    config::{Config, ConfigError, SoundFile, save_config_to_file},
+    container::ContainerService,
+    rate_limiter::ProviderLimitStatus,
+};
 use tokio::fs;
 use ts_rs::TS;
 use utils::{assets::config_path, response::ApiResponse};
@@ -30,6 +34,12 @@ pub fn router() -> Router<DeploymentImpl> {
         .route("/sounds/{sound}", get(get_sound))
         .route("/mcp-config", get(get_mcp_servers).post(update_mcp_servers))
         .route("/profiles", get(get_profiles).put(update_profiles))
+        .route(
+            "/profiles/{executor}/{variant}",
+            get(get_profile).put(upsert_profile).delete(delete_profile),
+        )
+        .route("/rate-limits", get(get_rate_limits))
+        .route("/rate-limits/{executor}", delete(clear_rate_limit))
 }
 
 #[derive(Debug, Serialize, Deserialize, TS)]
@@ -117,6 +127,9 @@ async fn update_config(
             let mut config = deployment.config().write().await;
             *config = new_config.clone();
             drop(config);
+            deployment
+                .git()
+                .set_commit_signing(new_config.commit_signing.clone());
 
             // Track config events when fields transition from false → true and run side effects
             handle_config_events(&deployment, &old_config, &new_config).await;
@@ -187,6 +200,22 @@ async fn handle_config_events(deployment: &DeploymentImpl, old: &Config, new: &C
             deployment_clone.trigger_auto_project_setup().await;
         });
     }
+
+    // Raised concurrency limits can free capacity for queued attempts
+    if old.max_concurrent_executions != new.max_concurrent_executions
+        || old.max_concurrent_executions_per_project != new.max_concurrent_executions_per_project
+    {
+        let deployment_clone = deployment.clone();
+        tokio::spawn(async move {
+            if let Err(e) = deployment_clone
+                .container()
+                .dispatch_queued_attempts()
+                .await
+            {
+                tracing::error!("Failed to dispatch queued task attempts: {}", e);
+            }
+        });
+    }
 }
 
 async fn get_sound(Path(sound): Path<SoundFile>) -> Result<Response, ApiError> {
@@ -441,3 +470,67 @@ async fn update_profiles(
         ))),
     }
 }
+
+async fn get_profile(
+    Path((executor, variant)): Path<(BaseCodingAgent, String)>,
+) -> Result<ResponseJson<ApiResponse<CodingAgent>>, ApiError> {
+    let profile_id = ExecutorProfileId::with_variant(executor, variant);
+    let config = ExecutorConfigs::get_cached()
+        .get_coding_agent(&profile_id)
+        .ok_or(ProfileError::NotFound(profile_id))?;
+    Ok(ResponseJson(ApiResponse::success(config)))
+}
+
+/// Create or replace one configuration, e.g. a "PLAN_FIRST" variant of
+/// CLAUDE_CODE, without rewriting the whole profiles file
+async fn upsert_profile(
+    Path((executor, variant)): Path<(BaseCodingAgent, String)>,
+    Json(config): Json<CodingAgent>,
+) -> Result<ResponseJson<ApiResponse<CodingAgent>>, ApiError> {
+    let profile_id = ExecutorProfileId::with_variant(executor, variant);
+    let mut profiles = ExecutorConfigs::get_cached();
+    profiles.upsert_profile(&profile_id, config.clone())?;
+    profiles.save_overrides()?;
+    ExecutorConfigs::reload();
+    tracing::info!("Saved executor profile {}", profile_id);
+    Ok(ResponseJson(ApiResponse::success(config)))
+}
+
+async fn delete_profile(
+    Path((executor, variant)): Path<(BaseCodingAgent, String)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let profile_id = ExecutorProfileId::with_variant(executor, variant);
+    let mut profiles = ExecutorConfigs::get_cached();
+    profiles.remove_profile(&profile_id)?;
+    profiles.save_overrides()?;
+    ExecutorConfigs::reload();
+    tracing::info!("Deleted executor profile {}", profile_id);
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Provider rate limits seen per executor and the attempts they hold back
+async fn get_rate_limits(
+    State(deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<Vec<ProviderLimitStatus>>> {
+    let statuses = deployment
+        .container()
+        .execution_queue()
+        .rate_limiter()
+        .snapshot();
+    ResponseJson(ApiResponse::success(statuses))
+}
+
+/// Start attempts held back by an executor's rate limit without waiting for
+/// the backoff to run out
+async fn clear_rate_limit(
+    State(deployment): State<DeploymentImpl>,
+    Path(executor): Path<BaseCodingAgent>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    deployment
+        .container()
+        .execution_queue()
+        .rate_limiter()
+        .record_success(executor);
+    deployment.container().dispatch_queued_attempts().await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
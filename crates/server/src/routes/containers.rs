@@ -1,17 +1,27 @@
+use std::path::{Path, PathBuf};
+
 use axum::{
-    Router,
+    Json, Router,
     extract::{Query, State},
     response::Json as ResponseJson,
-    routing::get,
+    routing::{get, post},
+};
+use db::models::{
+    project::Project, project_repository::ProjectRepository, task_attempt::TaskAttempt,
 };
-use db::models::task_attempt::TaskAttempt;
 use deployment::Deployment;
 use serde::{Deserialize, Serialize};
+use services::services::worktree_manager::WorktreeManager;
+use sqlx::Error as SqlxError;
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::task_attempts::worktree::{AttemptWorktree, repair_attempt_worktrees},
+};
 
 #[derive(Debug, Serialize, TS)]
 pub struct ContainerInfo {
@@ -49,6 +59,250 @@ pub async fn get_container_info(
     Ok(ResponseJson(ApiResponse::success(container_info)))
 }
 
+/// Disk usage of one directory in the worktree base directory
+#[derive(Debug, Serialize, TS)]
+pub struct WorktreeUsage {
+    pub path: String,
+    pub size_bytes: u64,
+    /// Owning attempt; null for orphaned directories awaiting cleanup
+    pub attempt_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct WorktreeDiskUsage {
+    pub total_bytes: u64,
+    /// Largest first
+    pub worktrees: Vec<WorktreeUsage>,
+}
+
+pub async fn get_worktree_disk_usage(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<WorktreeDiskUsage>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let usage = WorktreeManager::disk_usage().await?;
+
+    let mut worktrees = Vec::with_capacity(usage.len());
+    for (path, size_bytes) in usage {
+        let owner_path = ProjectRepository::owning_attempt_worktree(&path);
+        let owner =
+            match TaskAttempt::resolve_container_ref(pool, &owner_path.to_string_lossy()).await {
+                Ok(owner) => Some(owner),
+                Err(sqlx::Error::RowNotFound) => None,
+                Err(e) => return Err(e.into()),
+            };
+        worktrees.push(WorktreeUsage {
+            path: path.to_string_lossy().to_string(),
+            size_bytes,
+            attempt_id: owner.map(|(attempt_id, _, _)| attempt_id),
+            task_id: owner.map(|(_, task_id, _)| task_id),
+            project_id: owner.map(|(_, _, project_id)| project_id),
+        });
+    }
+
+    Ok(ResponseJson(ApiResponse::success(WorktreeDiskUsage {
+        total_bytes: worktrees.iter().map(|w| w.size_bytes).sum(),
+        worktrees,
+    })))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum WorktreeState {
+    /// On disk and known to git
+    Healthy,
+    /// The attempt still uses it, but the directory is gone
+    Missing,
+    /// On disk, but git lost track of it; repairing re-links it
+    Unregistered,
+    /// On disk without an attempt using it, e.g. after a crash
+    Orphaned,
+}
+
+/// A worktree directory, or a worktree an attempt expects to exist
+#[derive(Debug, Serialize, TS)]
+pub struct WorktreeOverview {
+    pub path: String,
+    pub state: WorktreeState,
+    /// Branch checked out in the directory, when git can tell
+    pub branch: Option<String>,
+    pub attempt_id: Option<Uuid>,
+    pub task_id: Option<Uuid>,
+    pub project_id: Option<Uuid>,
+    /// For orphaned directories: the attempt working on the checked-out
+    /// branch, which the directory can be relinked to
+    pub suggested_attempt_id: Option<Uuid>,
+}
+
+/// Repository a directory in the worktree base directory belongs to: the
+/// project repository for main worktrees, or the additional repository the
+/// directory name is suffixed with
+async fn worktree_repo_path(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    owner_path: &Path,
+    path: &Path,
+) -> Result<Option<PathBuf>, ApiError> {
+    let pool = &deployment.db().pool;
+    if owner_path == path {
+        return Ok(Project::find_by_id(pool, project_id)
+            .await?
+            .map(|project| project.git_repo_path));
+    }
+    Ok(ProjectRepository::find_by_project_id(pool, project_id)
+        .await?
+        .into_iter()
+        .find(|repository| repository.worktree_path(owner_path) == path)
+        .map(|repository| repository.git_repo_path))
+}
+
+/// Every directory in the worktree base directory with its state, followed
+/// by the worktrees attempts expect but that are gone
+pub async fn get_worktrees(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<WorktreeOverview>>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let mut worktrees = Vec::new();
+    for path in WorktreeManager::worktree_dirs().await? {
+        let owner_path = ProjectRepository::owning_attempt_worktree(&path);
+        let owner =
+            match TaskAttempt::resolve_container_ref(pool, &owner_path.to_string_lossy()).await {
+                Ok(owner) => Some(owner),
+                Err(SqlxError::RowNotFound) => None,
+                Err(e) => return Err(e.into()),
+            };
+        let repo_path = match owner {
+            Some((_, _, project_id)) => {
+                worktree_repo_path(&deployment, project_id, &owner_path, &path).await?
+            }
+            None => None,
+        };
+
+        let (state, branch, suggested_attempt_id) = match repo_path {
+            Some(repo_path) => {
+                let health = WorktreeManager::inspect_worktree(&repo_path, &path).await?;
+                let state = if health.registered {
+                    WorktreeState::Healthy
+                } else {
+                    WorktreeState::Unregistered
+                };
+                (state, health.branch, None)
+            }
+            None => {
+                let branch = deployment.git().get_current_branch(&path).ok();
+                let suggested = match &branch {
+                    Some(branch) if owner.is_none() => {
+                        TaskAttempt::find_id_by_branch(pool, branch).await?
+                    }
+                    _ => None,
+                };
+                (WorktreeState::Orphaned, branch, suggested)
+            }
+        };
+        worktrees.push(WorktreeOverview {
+            path: path.to_string_lossy().to_string(),
+            state,
+            branch,
+            attempt_id: owner.map(|(attempt_id, _, _)| attempt_id),
+            task_id: owner.map(|(_, task_id, _)| task_id),
+            project_id: owner.map(|(_, _, project_id)| project_id),
+            suggested_attempt_id,
+        });
+    }
+
+    for (attempt_id, container_ref) in TaskAttempt::find_by_worktree_deleted(pool).await? {
+        if Path::new(&container_ref).exists() {
+            continue;
+        }
+        let (_, task_id, project_id) =
+            TaskAttempt::resolve_container_ref(pool, &container_ref).await?;
+        worktrees.push(WorktreeOverview {
+            path: container_ref,
+            state: WorktreeState::Missing,
+            branch: None,
+            attempt_id: Some(attempt_id),
+            task_id: Some(task_id),
+            project_id: Some(project_id),
+            suggested_attempt_id: None,
+        });
+    }
+
+    Ok(ResponseJson(ApiResponse::success(worktrees)))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct RelinkWorktreeRequest {
+    /// Existing worktree directory, typically an orphaned one
+    pub path: String,
+    pub attempt_id: Uuid,
+}
+
+/// Point an attempt at an existing worktree directory, e.g. one left behind
+/// by a crash, and re-link it with git. The directory must have the
+/// attempt's branch checked out, when git can still tell.
+pub async fn relink_worktree(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RelinkWorktreeRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<AttemptWorktree>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let path = PathBuf::from(payload.path.trim());
+    if !path.is_absolute() || !path.is_dir() {
+        return Err(ApiError::BadRequest(format!(
+            "{} is not an existing directory",
+            path.display()
+        )));
+    }
+    let task_attempt = TaskAttempt::find_by_id(pool, payload.attempt_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let path_str = path.to_string_lossy().to_string();
+    if task_attempt.container_ref.as_deref() != Some(path_str.as_str())
+        && TaskAttempt::container_ref_exists(pool, &path_str).await?
+    {
+        return Err(ApiError::Conflict(
+            "Another task attempt uses this worktree".to_string(),
+        ));
+    }
+    if let Some(current) = task_attempt.container_ref.as_deref()
+        && current != path_str
+        && Path::new(current).exists()
+    {
+        return Err(ApiError::Conflict(format!(
+            "Task attempt still has a worktree at {current}"
+        )));
+    }
+    if let Ok(branch) = deployment.git().get_current_branch(&path)
+        && branch != task_attempt.branch
+    {
+        return Err(ApiError::BadRequest(format!(
+            "{} has branch {branch} checked out, not {}",
+            path.display(),
+            task_attempt.branch
+        )));
+    }
+
+    let worktrees = repair_attempt_worktrees(&deployment, &task_attempt, &path).await?;
+    TaskAttempt::update_container_ref(pool, task_attempt.id, &path_str).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "worktree_relinked",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(worktrees)))
+}
+
 pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
-    Router::new().route("/containers/info", get(get_container_info))
+    Router::new()
+        .route("/containers/info", get(get_container_info))
+        .route("/containers/usage", get(get_worktree_disk_usage))
+        .route("/containers/worktrees", get(get_worktrees))
+        .route("/containers/worktrees/relink", post(relink_worktree))
 }
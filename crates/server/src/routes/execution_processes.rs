@@ -3,14 +3,15 @@ use axum::{
     Extension, Router,
     extract::{
         Path, Query, State,
-        ws::{WebSocket, WebSocketUpgrade},
+        ws::{Message, WebSocket, WebSocketUpgrade},
     },
     middleware::from_fn_with_state,
     response::{IntoResponse, Json as ResponseJson},
     routing::{get, post},
 };
-use db::models::execution_process::{
-    ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus,
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus},
+    execution_process_event::ExecutionProcessEvent,
 };
 use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
@@ -72,7 +73,7 @@ pub async fn stream_raw_logs_ws(
     }))
 }
 
-async fn handle_raw_logs_ws(
+pub(crate) async fn handle_raw_logs_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
     exec_id: Uuid,
@@ -180,6 +181,83 @@ async fn handle_normalized_logs_ws(
     Ok(())
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AgentEventsQuery {
+    /// Only return events after this sequence number
+    pub after_seq: Option<i64>,
+}
+
+/// Events persisted so far. Running executions append to these as they
+/// stream, so polling with `after_seq` picks up new ones.
+pub async fn get_agent_events(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<AgentEventsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ExecutionProcessEvent>>>, ApiError> {
+    let events = ExecutionProcessEvent::find_by_execution_process_id(
+        &deployment.db().pool,
+        execution_process.id,
+        query.after_seq,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(events)))
+}
+
+pub async fn stream_agent_events_ws(
+    ws: WebSocketUpgrade,
+    State(deployment): State<DeploymentImpl>,
+    Path(exec_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let stream = deployment
+        .container()
+        .stream_agent_events(&exec_id)
+        .await
+        .ok_or_else(|| {
+            ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound)
+        })?;
+
+    // One JSON event per message, then `{"finished":true}` like the log streams
+    let stream = stream
+        .map_ok(|event| {
+            let json = serde_json::to_string(&event)
+                .unwrap_or_else(|_| r#"{"error":"serialization_failed"}"#.to_string());
+            Message::Text(json.into())
+        })
+        .chain(futures_util::stream::once(async {
+            Ok::<_, std::io::Error>(LogMsg::Finished.to_ws_message_unchecked())
+        }))
+        .err_into::<anyhow::Error>()
+        .boxed();
+
+    Ok(ws.on_upgrade(move |socket| async move {
+        if let Err(e) = forward_ws_messages(socket, stream).await {
+            tracing::warn!("agent events WS closed: {}", e);
+        }
+    }))
+}
+
+async fn forward_ws_messages(
+    socket: WebSocket,
+    mut stream: impl futures_util::Stream<Item = anyhow::Result<Message>> + Unpin + Send + 'static,
+) -> anyhow::Result<()> {
+    let (mut sender, mut receiver) = socket.split();
+    tokio::spawn(async move { while let Some(Ok(_)) = receiver.next().await {} });
+    while let Some(item) = stream.next().await {
+        match item {
+            Ok(msg) => {
+                if sender.send(msg).await.is_err() {
+                    break;
+                }
+            }
+            Err(e) => {
+                tracing::error!("stream error: {}", e);
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
 pub async fn stop_execution_process(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(deployment): State<DeploymentImpl>,
@@ -253,6 +331,8 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/stop", post(stop_execution_process))
         .route("/raw-logs/ws", get(stream_raw_logs_ws))
         .route("/normalized-logs/ws", get(stream_normalized_logs_ws))
+        .route("/events", get(get_agent_events))
+        .route("/events/ws", get(stream_agent_events_ws))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_execution_process_middleware,
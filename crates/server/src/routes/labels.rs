@@ -0,0 +1,119 @@
+use axum::{
+    Extension, Json, Router, extract::State, middleware::from_fn_with_state,
+    response::Json as ResponseJson, routing::get,
+};
+use db::models::label::{CreateLabel, Label, UpdateLabel};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_label_middleware};
+
+pub async fn get_labels(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Label>>>, ApiError> {
+    let labels = Label::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+pub async fn get_label(
+    Extension(label): Extension<Label>,
+) -> Result<ResponseJson<ApiResponse<Label>>, ApiError> {
+    Ok(Json(ApiResponse::success(label)))
+}
+
+pub async fn create_label(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateLabel>,
+) -> Result<ResponseJson<ApiResponse<Label>>, ApiError> {
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Label name cannot be empty".to_string(),
+        ));
+    }
+    let label = Label::create(
+        &deployment.db().pool,
+        &CreateLabel {
+            name: name.to_string(),
+            ..payload
+        },
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "label_created",
+            serde_json::json!({
+                "label_id": label.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(label)))
+}
+
+pub async fn update_label(
+    Extension(label): Extension<Label>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateLabel>,
+) -> Result<ResponseJson<ApiResponse<Label>>, ApiError> {
+    let name = payload.name.as_deref().map(str::trim);
+    if name.is_some_and(str::is_empty) {
+        return Err(ApiError::BadRequest(
+            "Label name cannot be empty".to_string(),
+        ));
+    }
+    let updated_label = Label::update(
+        &deployment.db().pool,
+        label.id,
+        &UpdateLabel {
+            name: name.map(str::to_string),
+            color: payload.color,
+        },
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(updated_label)))
+}
+
+pub async fn delete_label(
+    Extension(label): Extension<Label>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = Label::delete(&deployment.db().pool, label.id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+/// Reject label ids that don't exist before they're attached to anything.
+pub async fn ensure_labels_exist(
+    deployment: &DeploymentImpl,
+    label_ids: &[Uuid],
+) -> Result<(), ApiError> {
+    let labels = Label::find_all(&deployment.db().pool).await?;
+    if let Some(missing) = label_ids
+        .iter()
+        .find(|id| !labels.iter().any(|label| label.id == **id))
+    {
+        return Err(ApiError::BadRequest(format!("Label {missing} not found")));
+    }
+    Ok(())
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let label_router = Router::new()
+        .route("/", get(get_label).put(update_label).delete(delete_label))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_label_middleware,
+        ));
+
+    let inner = Router::new()
+        .route("/", get(get_labels).post(create_label))
+        .nest("/{label_id}", label_router);
+
+    Router::new().nest("/labels", inner)
+}
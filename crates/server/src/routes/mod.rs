@@ -18,10 +18,15 @@ pub mod execution_processes;
 pub mod frontend;
 pub mod health;
 pub mod images;
+pub mod labels;
 pub mod projects;
+pub mod search;
 pub mod tags;
 pub mod task_attempts;
+pub mod task_templates;
 pub mod tasks;
+pub mod trash;
+pub mod workspaces;
 
 pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
     // Create routers with different middleware layers
@@ -33,10 +38,15 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .merge(drafts::router(&deployment))
         .merge(tasks::router(&deployment))
         .merge(task_attempts::router(&deployment))
+        .merge(task_templates::router(&deployment))
         .merge(execution_processes::router(&deployment))
         .merge(tags::router(&deployment))
+        .merge(labels::router(&deployment))
+        .merge(workspaces::router(&deployment))
         .merge(auth::router(&deployment))
         .merge(filesystem::router())
+        .merge(search::router())
+        .merge(trash::router())
         .merge(events::router(&deployment))
         .merge(approvals::router())
         .nest("/images", images::routes())
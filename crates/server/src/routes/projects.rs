@@ -1,41 +1,143 @@
+pub mod approval_rules;
+pub mod branch_template;
+pub mod columns;
+pub mod context_packs;
+pub mod env_vars;
+pub mod executor_fallbacks;
+pub mod hosting;
+pub mod mcp_servers;
+pub mod merge_settings;
+pub mod rebase_policy;
+pub mod sandbox;
+pub mod scripts;
+pub mod turn_tests;
+
 use std::path::Path;
 
 use axum::{
     Extension, Json, Router,
-    extract::{Query, State},
+    extract::{Path as AxumPath, Query, State},
     http::StatusCode,
     middleware::from_fn_with_state,
     response::Json as ResponseJson,
-    routing::{get, post},
+    routing::{delete, get, post, put},
 };
-use db::models::project::{
-    CreateProject, Project, ProjectError, SearchMatchType, SearchResult, UpdateProject,
+use db::models::{
+    attempt_usage::{AttemptUsage, UsageTotals},
+    external_task_link::ExternalProvider,
+    metrics::ProjectMetrics,
+    project::{
+        CreateProject, DuplicateProject, Project, ProjectError, SearchMatchType, SearchResult,
+        UpdateProject,
+    },
+    project_repository::{CreateProjectRepository, ProjectRepository},
+    proposed_task::ProposedTask,
+    queued_follow_up::QueuedFollowUp,
+    task::Task,
+    task_attempt::TaskAttempt,
 };
 use deployment::Deployment;
 use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
 use services::services::{
+    container::ContainerService,
     file_ranker::FileRanker,
     file_search_cache::{CacheError, SearchMode, SearchQuery},
     git::GitBranch,
+    github_issues::{self, GitHubIssuesError},
+    github_service::{GitHubService, GitHubServiceError, IssueFilter},
+    integrations,
 };
+use ts_rs::TS;
 use utils::{path::expand_tilde, response::ApiResponse};
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
 
+#[derive(Debug, Deserialize)]
+pub struct ProjectsQuery {
+    /// Also list archived projects
+    #[serde(default)]
+    pub include_archived: bool,
+}
+
 pub async fn get_projects(
     State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ProjectsQuery>,
 ) -> Result<ResponseJson<ApiResponse<Vec<Project>>>, ApiError> {
-    let projects = Project::find_all(&deployment.db().pool).await?;
+    let projects = Project::find_all(&deployment.db().pool, query.include_archived).await?;
     Ok(ResponseJson(ApiResponse::success(projects)))
 }
 
+/// Archived projects are read-only; reject changes to their tasks
+pub async fn ensure_project_not_archived(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+) -> Result<(), ApiError> {
+    if Project::is_archived(&deployment.db().pool, project_id).await? {
+        return Err(ApiError::Conflict(
+            "Project is archived; unarchive it first".to_string(),
+        ));
+    }
+    if Project::is_trashed(&deployment.db().pool, project_id).await? {
+        return Err(ApiError::Conflict(
+            "Project is in the trash; restore it first".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 pub async fn get_project(
     Extension(project): Extension<Project>,
 ) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
     Ok(ResponseJson(ApiResponse::success(project)))
 }
 
+pub async fn get_project_usage(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<UsageTotals>>, ApiError> {
+    let totals = AttemptUsage::totals_for_project(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(totals)))
+}
+
+/// Follow-up work proposed by the agents of the project's attempts that is
+/// still waiting to be accepted or dismissed
+pub async fn get_project_proposed_tasks(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProposedTask>>>, ApiError> {
+    let proposed =
+        ProposedTask::find_pending_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(proposed)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectMetricsQuery {
+    /// Number of calendar weeks to measure, the current one included
+    #[serde(default = "default_metrics_weeks")]
+    pub weeks: u32,
+}
+
+fn default_metrics_weeks() -> u32 {
+    12
+}
+
+pub async fn get_project_metrics(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ProjectMetricsQuery>,
+) -> Result<ResponseJson<ApiResponse<ProjectMetrics>>, ApiError> {
+    if !(1..=104).contains(&query.weeks) {
+        return Err(ApiError::BadRequest(
+            "Weeks must be between 1 and 104".to_string(),
+        ));
+    }
+    let metrics =
+        ProjectMetrics::for_project(&deployment.db().pool, project.id, query.weeks).await?;
+    Ok(ResponseJson(ApiResponse::success(metrics)))
+}
+
 pub async fn get_project_branches(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
@@ -44,6 +146,232 @@ pub async fn get_project_branches(
     Ok(ResponseJson(ApiResponse::success(branches)))
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct ImportGitHubIssuesRequest {
+    /// Only import issues carrying all of these labels
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Only import issues of this milestone number
+    pub milestone: Option<u64>,
+    /// Close each issue once its task is done
+    #[serde(default)]
+    pub sync_status: bool,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ImportGitHubIssuesResponse {
+    pub created: Vec<Task>,
+    /// Matching issues that already had a task
+    pub skipped: usize,
+}
+
+/// Create tasks from the open issues of the project's GitHub repository.
+/// Re-running an import only picks up issues that have no task yet.
+pub async fn import_github_issues(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ImportGitHubIssuesRequest>,
+) -> Result<ResponseJson<ApiResponse<ImportGitHubIssuesResponse>>, ApiError> {
+    let github_config = deployment.config().read().await.github.clone();
+    let Some(github_token) = github_config.token() else {
+        return Err(GitHubServiceError::TokenInvalid.into());
+    };
+    let github_service = GitHubService::new(&github_token)?;
+    let repo_info = deployment
+        .git()
+        .get_github_repo_info(&project.git_repo_path)?;
+
+    let imported = github_issues::import_issues(
+        &deployment.db().pool,
+        &github_service,
+        &repo_info,
+        project.id,
+        &IssueFilter {
+            labels: payload.labels,
+            milestone: payload.milestone,
+        },
+        payload.sync_status,
+    )
+    .await
+    .map_err(|e| match e {
+        GitHubIssuesError::NoGitHubToken => GitHubServiceError::TokenInvalid.into(),
+        GitHubIssuesError::GitHubServiceError(e) => ApiError::GitHubService(e),
+        GitHubIssuesError::Sqlx(e) => ApiError::Database(e),
+    })?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "github_issues_imported",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "created": imported.created.len(),
+                "skipped": imported.skipped,
+                "sync_status": payload.sync_status,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        ImportGitHubIssuesResponse {
+            created: imported.created,
+            skipped: imported.skipped,
+        },
+    )))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ImportExternalTicketsRequest {
+    pub provider: ExternalProvider,
+    /// JQL for Jira, a team key for Linear
+    pub query: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ImportExternalTicketsResponse {
+    pub created: Vec<Task>,
+    /// Matching tickets that already had a task
+    pub skipped: usize,
+}
+
+/// Create tasks from the tickets of a Jira or Linear query. Imported tasks
+/// keep their status in sync with their ticket in both directions.
+pub async fn import_external_tickets(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ImportExternalTicketsRequest>,
+) -> Result<ResponseJson<ApiResponse<ImportExternalTicketsResponse>>, ApiError> {
+    if payload.query.trim().is_empty() {
+        return Err(ApiError::BadRequest("Query cannot be empty".to_string()));
+    }
+    let integrations_config = deployment.config().read().await.integrations.clone();
+    let connector = integrations::connector_for(&integrations_config, payload.provider)?;
+
+    let imported = integrations::import_tickets(
+        &deployment.db().pool,
+        connector.as_ref(),
+        project.id,
+        &payload.query,
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "external_tickets_imported",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "provider": payload.provider.to_string(),
+                "created": imported.created.len(),
+                "skipped": imported.skipped,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        ImportExternalTicketsResponse {
+            created: imported.created,
+            skipped: imported.skipped,
+        },
+    )))
+}
+
+pub async fn get_project_repositories(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectRepository>>>, ApiError> {
+    let repositories =
+        ProjectRepository::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(repositories)))
+}
+
+/// Add another git repository to the project. Attempts started afterwards
+/// (and existing ones, once their worktree is next used) get a worktree of it
+/// on the attempt branch.
+pub async fn create_project_repository(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateProjectRepository>,
+) -> Result<ResponseJson<ApiResponse<ProjectRepository>>, ApiError> {
+    let name = payload.name.trim();
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(ApiError::BadRequest(
+            "Repository name may only contain letters, digits, '-', '_' and '.'".to_string(),
+        ));
+    }
+
+    let path = std::path::absolute(expand_tilde(&payload.git_repo_path))?;
+    if !path.join(".git").exists() {
+        return Err(ApiError::BadRequest(
+            "The specified directory is not a git repository".to_string(),
+        ));
+    }
+    if path == project.git_repo_path {
+        return Err(ApiError::BadRequest(
+            "The project's main repository cannot be added again".to_string(),
+        ));
+    }
+
+    let base_branch = payload.base_branch.trim();
+    if !deployment.git().check_branch_exists(&path, base_branch)? {
+        return Err(ApiError::BadRequest(format!(
+            "Branch '{base_branch}' does not exist in {}",
+            path.display()
+        )));
+    }
+
+    let pool = &deployment.db().pool;
+    let existing = ProjectRepository::find_by_project_id(pool, project.id).await?;
+    if existing
+        .iter()
+        .any(|repository| repository.name == name || repository.git_repo_path == path)
+    {
+        return Err(ApiError::Conflict(
+            "A repository with this name or path is already part of the project".to_string(),
+        ));
+    }
+
+    let repository = ProjectRepository::create(
+        pool,
+        project.id,
+        &CreateProjectRepository {
+            name: name.to_string(),
+            git_repo_path: path.to_string_lossy().to_string(),
+            base_branch: base_branch.to_string(),
+        },
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_repository_added",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "repository_id": repository.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(repository)))
+}
+
+/// Remove a repository from the project. Worktrees already provisioned for
+/// it are left in place until their attempt is cleaned up.
+pub async fn delete_project_repository(
+    State(deployment): State<DeploymentImpl>,
+    AxumPath((project_id, repository_id)): AxumPath<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let repository = ProjectRepository::find_by_id(pool, repository_id)
+        .await?
+        .filter(|repository| repository.project_id == project_id)
+        .ok_or(ApiError::BadRequest("Repository not found".to_string()))?;
+    ProjectRepository::delete(pool, repository.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub async fn create_project(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateProject>,
@@ -184,7 +512,14 @@ pub async fn update_project(
         dev_script,
         cleanup_script,
         copy_files,
+        execution_timeout_minutes,
+        auto_push_branches,
     } = payload;
+    if execution_timeout_minutes.is_some_and(|minutes| minutes <= 0) {
+        return Ok(ResponseJson(ApiResponse::error(
+            "Execution timeout must be a positive number of minutes",
+        )));
+    }
     // If git_repo_path is being changed, check if the new path is already used by another project
     let git_repo_path = if let Some(new_git_repo_path) = git_repo_path.map(|s| expand_tilde(&s))
         && new_git_repo_path != existing_project.git_repo_path
@@ -220,6 +555,8 @@ pub async fn update_project(
         dev_script,
         cleanup_script,
         copy_files,
+        execution_timeout_minutes,
+        auto_push_branches.unwrap_or(existing_project.auto_push_branches),
     )
     .await
     {
@@ -231,11 +568,13 @@ pub async fn update_project(
     }
 }
 
+/// Move the project, with its tasks, to the trash. Nothing is removed from
+/// disk until it is purged after the retention window.
 pub async fn delete_project(
     Extension(project): Extension<Project>,
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<()>>, StatusCode> {
-    match Project::delete(&deployment.db().pool, project.id).await {
+    match Project::soft_delete(&deployment.db().pool, project.id).await {
         Ok(rows_affected) => {
             if rows_affected == 0 {
                 Err(StatusCode::NOT_FOUND)
@@ -259,6 +598,164 @@ pub async fn delete_project(
     }
 }
 
+pub async fn restore_project(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if Project::restore(pool, project.id).await? == 0 {
+        return Err(ApiError::BadRequest(
+            "Project is not in the trash".to_string(),
+        ));
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_restored",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+/// Archive a project: stop its processes and tear down the worktrees of its
+/// attempts. Its data stays browsable, but nothing runs until it is
+/// unarchived.
+pub async fn archive_project(
+    State(deployment): State<DeploymentImpl>,
+    AxumPath(project_id): AxumPath<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+    // Flag the project first so that no chained action starts while the
+    // attempts are torn down
+    if Project::archive(pool, project.id).await? == 0 {
+        return Err(ApiError::Conflict(
+            "Project is already archived".to_string(),
+        ));
+    }
+
+    let attempts = TaskAttempt::find_by_project_id_with_project(pool, project.id).await?;
+    for (attempt_id, _, _) in attempts {
+        let Some(task_attempt) = TaskAttempt::find_by_id(pool, attempt_id).await? else {
+            continue;
+        };
+        QueuedFollowUp::delete_by_task_attempt_id(pool, task_attempt.id).await?;
+        if task_attempt.worktree_deleted {
+            continue;
+        }
+        deployment.container().delete(&task_attempt).await?;
+        TaskAttempt::mark_worktree_deleted(pool, task_attempt.id).await?;
+    }
+
+    let project = Project::find_by_id(pool, project.id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_archived",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+/// Make an archived project writable again. Worktrees are recreated as
+/// attempts need them.
+pub async fn unarchive_project(
+    State(deployment): State<DeploymentImpl>,
+    AxumPath(project_id): AxumPath<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if Project::unarchive(pool, project_id).await? == 0 {
+        return Err(ApiError::Conflict("Project is not archived".to_string()));
+    }
+    let project = Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_unarchived",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+/// Copy a project onto another repository to run a parallel experiment
+pub async fn duplicate_project(
+    State(deployment): State<DeploymentImpl>,
+    AxumPath(project_id): AxumPath<Uuid>,
+    Json(payload): Json<DuplicateProject>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let source = Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(ApiError::Database(sqlx::Error::RowNotFound))?;
+
+    let name = match payload.name.as_deref().map(str::trim) {
+        Some("") => {
+            return Err(ApiError::BadRequest(
+                "Project name cannot be empty".to_string(),
+            ));
+        }
+        Some(name) => name.to_string(),
+        None => format!("{} (copy)", source.name),
+    };
+
+    let path = std::path::absolute(expand_tilde(&payload.git_repo_path))?;
+    if !path.join(".git").exists() {
+        return Err(ApiError::BadRequest(
+            "The specified directory is not a git repository".to_string(),
+        ));
+    }
+    let git_repo_path = path.to_string_lossy().to_string();
+    if Project::find_by_git_repo_path(pool, &git_repo_path)
+        .await?
+        .is_some()
+    {
+        return Err(ApiError::Conflict(
+            "A project with this git repository path already exists".to_string(),
+        ));
+    }
+
+    let project = Project::duplicate(
+        pool,
+        &source,
+        Uuid::new_v4(),
+        &name,
+        &git_repo_path,
+        payload.include_open_tasks,
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_duplicated",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "source_project_id": source.id.to_string(),
+                "include_open_tasks": payload.include_open_tasks,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
 #[derive(serde::Deserialize)]
 pub struct OpenEditorRequest {
     editor_type: Option<String>,
@@ -511,12 +1008,140 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             get(get_project).put(update_project).delete(delete_project),
         )
         .route("/branches", get(get_project_branches))
+        .route("/usage", get(get_project_usage))
+        .route("/metrics", get(get_project_metrics))
+        .route("/proposed-tasks", get(get_project_proposed_tasks))
+        .route(
+            "/repositories",
+            get(get_project_repositories).post(create_project_repository),
+        )
+        .route("/github/import-issues", post(import_github_issues))
+        .route("/integrations/import", post(import_external_tickets))
+        .route(
+            "/approval-rules",
+            get(approval_rules::get_project_approval_rules)
+                .post(approval_rules::create_project_approval_rule),
+        )
+        .route(
+            "/branch-template",
+            get(branch_template::get_project_branch_template)
+                .put(branch_template::upsert_project_branch_template)
+                .delete(branch_template::delete_project_branch_template),
+        )
+        .route(
+            "/columns",
+            get(columns::get_project_columns).post(columns::create_project_column),
+        )
+        .route("/columns/reorder", post(columns::reorder_project_columns))
+        .route(
+            "/context-packs",
+            get(context_packs::get_project_context_packs)
+                .post(context_packs::create_project_context_pack),
+        )
+        .route(
+            "/env",
+            get(env_vars::get_project_env_vars).post(env_vars::upsert_project_env_var),
+        )
+        .route(
+            "/executor-fallbacks",
+            get(executor_fallbacks::get_project_executor_fallbacks)
+                .put(executor_fallbacks::set_project_executor_fallbacks),
+        )
+        .route(
+            "/hosting",
+            get(hosting::get_project_hosting)
+                .put(hosting::upsert_project_hosting)
+                .delete(hosting::delete_project_hosting),
+        )
+        .route(
+            "/mcp-servers",
+            get(mcp_servers::get_project_mcp_servers).post(mcp_servers::create_project_mcp_server),
+        )
+        .route(
+            "/merge-settings",
+            get(merge_settings::get_project_merge_settings)
+                .put(merge_settings::upsert_project_merge_settings)
+                .delete(merge_settings::delete_project_merge_settings),
+        )
+        .route(
+            "/rebase-policy",
+            get(rebase_policy::get_project_rebase_policy)
+                .put(rebase_policy::upsert_project_rebase_policy)
+                .delete(rebase_policy::delete_project_rebase_policy),
+        )
+        .route(
+            "/sandbox",
+            get(sandbox::get_project_sandbox)
+                .put(sandbox::upsert_project_sandbox)
+                .delete(sandbox::delete_project_sandbox),
+        )
+        .route(
+            "/scripts",
+            get(scripts::get_project_scripts).post(scripts::create_project_script),
+        )
+        .route("/scripts/reorder", post(scripts::reorder_project_scripts))
+        .route(
+            "/stale-attempts",
+            get(rebase_policy::get_project_stale_attempts),
+        )
+        .route(
+            "/turn-tests",
+            get(turn_tests::get_project_turn_tests)
+                .put(turn_tests::upsert_project_turn_tests)
+                .delete(turn_tests::delete_project_turn_tests),
+        )
         .route("/search", get(search_project_files))
         .route("/open-editor", post(open_project_in_editor))
+        .route("/restore", post(restore_project))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_project_middleware,
-        ));
+        ))
+        // Added after the project loader layer: these routes carry a second
+        // path parameter, so the handlers check ownership themselves
+        .route(
+            "/repositories/{repository_id}",
+            delete(delete_project_repository),
+        )
+        .route(
+            "/approval-rules/{rule_id}",
+            delete(approval_rules::delete_project_approval_rule),
+        )
+        .route(
+            "/columns/{column_id}",
+            put(columns::update_project_column).delete(columns::delete_project_column),
+        )
+        .route(
+            "/context-packs/{pack_id}",
+            put(context_packs::update_project_context_pack)
+                .delete(context_packs::delete_project_context_pack),
+        )
+        .route(
+            "/env/{env_var_id}",
+            delete(env_vars::delete_project_env_var),
+        )
+        .route(
+            "/mcp-servers/{server_id}",
+            put(mcp_servers::update_project_mcp_server)
+                .delete(mcp_servers::delete_project_mcp_server),
+        )
+        // Archiving and duplication skip the loader layer, which rejects
+        // writes to archived projects
+        .route("/archive", post(archive_project))
+        .route("/unarchive", post(unarchive_project))
+        .route("/duplicate", post(duplicate_project))
+        .route(
+            "/scripts/{script_id}",
+            put(scripts::update_project_script).delete(scripts::delete_project_script),
+        )
+        .route(
+            "/scripts/{script_id}/run",
+            post(scripts::run_project_script),
+        )
+        .route(
+            "/scripts/{script_id}/run/ws",
+            get(scripts::run_project_script_ws),
+        );
 
     let projects_router = Router::new()
         .route("/", get(get_projects).post(create_project))
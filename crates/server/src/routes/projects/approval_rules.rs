@@ -0,0 +1,72 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    project::Project,
+    project_approval_rule::{CreateProjectApprovalRule, ProjectApprovalRule},
+};
+use deployment::Deployment;
+use sqlx::Error as SqlxError;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub async fn get_project_approval_rules(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectApprovalRule>>>, ApiError> {
+    let rules = ProjectApprovalRule::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(rules)))
+}
+
+/// Add a rule. Matching tool calls of the project's agents are approved
+/// without asking from then on, including those of running agents.
+pub async fn create_project_approval_rule(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateProjectApprovalRule>,
+) -> Result<ResponseJson<ApiResponse<ProjectApprovalRule>>, ApiError> {
+    let tool_name = payload.tool_name.trim();
+    if tool_name.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Tool name cannot be empty; use '*' for any tool".to_string(),
+        ));
+    }
+    let pattern = payload
+        .pattern
+        .as_deref()
+        .map(str::trim)
+        .filter(|pattern| !pattern.is_empty());
+
+    let rule =
+        ProjectApprovalRule::create(&deployment.db().pool, project.id, tool_name, pattern).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_approval_rule_created",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "any_tool": tool_name == "*",
+                "has_pattern": pattern.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+pub async fn delete_project_approval_rule(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, rule_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let rule = ProjectApprovalRule::find_by_id(pool, rule_id)
+        .await?
+        .filter(|rule| rule.project_id == project_id)
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+    ProjectApprovalRule::delete(pool, rule.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
@@ -0,0 +1,54 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    project::Project,
+    project_branch_template::{ProjectBranchTemplate, UpsertProjectBranchTemplate},
+};
+use deployment::Deployment;
+use services::services::branch_naming::validate_branch_template;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Template the project's attempt branches are named from, or null when they
+/// use the default naming
+pub async fn get_project_branch_template(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ProjectBranchTemplate>>>, ApiError> {
+    let template =
+        ProjectBranchTemplate::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(template)))
+}
+
+/// Name the project's new attempt branches from a template such as
+/// `feat/{ticket}-{slug}`
+pub async fn upsert_project_branch_template(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertProjectBranchTemplate>,
+) -> Result<ResponseJson<ApiResponse<ProjectBranchTemplate>>, ApiError> {
+    let template = payload.template.trim();
+    validate_branch_template(template).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let template =
+        ProjectBranchTemplate::upsert(&deployment.db().pool, project.id, template).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_branch_template_set",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(template)))
+}
+
+/// Go back to the default naming for the project's attempt branches
+pub async fn delete_project_branch_template(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ProjectBranchTemplate::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
@@ -0,0 +1,181 @@
+use std::collections::HashSet;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    project::Project,
+    project_column::{
+        CreateProjectColumn, ProjectColumn, ReorderProjectColumns, UpdateProjectColumn,
+    },
+};
+use deployment::Deployment;
+use sqlx::Error as SqlxError;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub async fn get_project_columns(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectColumn>>>, ApiError> {
+    let columns = ProjectColumn::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(columns)))
+}
+
+async fn validate_column_fields(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    column_id: Option<Uuid>,
+    name: Option<&str>,
+    wip_limit: Option<i64>,
+) -> Result<(), ApiError> {
+    if let Some(name) = name {
+        if name.is_empty() {
+            return Err(ApiError::BadRequest(
+                "Column name cannot be empty".to_string(),
+            ));
+        }
+        if ProjectColumn::name_exists(&deployment.db().pool, project_id, name, column_id).await? {
+            return Err(ApiError::Conflict(format!(
+                "A column named '{name}' already exists"
+            )));
+        }
+    }
+    if wip_limit.is_some_and(|limit| limit < 1) {
+        return Err(ApiError::BadRequest(
+            "WIP limit must be at least 1".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub async fn create_project_column(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateProjectColumn>,
+) -> Result<ResponseJson<ApiResponse<ProjectColumn>>, ApiError> {
+    let name = payload.name.trim().to_string();
+    validate_column_fields(
+        &deployment,
+        project.id,
+        None,
+        Some(&name),
+        payload.wip_limit,
+    )
+    .await?;
+
+    let column = ProjectColumn::create(
+        &deployment.db().pool,
+        project.id,
+        &CreateProjectColumn { name, ..payload },
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_column_created",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "status": column.status.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(column)))
+}
+
+/// Rename a column, change its WIP limit, or map it onto another status.
+/// Every status keeps at least one column so its tasks stay visible.
+pub async fn update_project_column(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, column_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateProjectColumn>,
+) -> Result<ResponseJson<ApiResponse<ProjectColumn>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let column = ProjectColumn::find_by_id(pool, column_id)
+        .await?
+        .filter(|column| column.project_id == project_id)
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let name = payload.name.as_deref().map(str::trim);
+    let wip_limit = payload.wip_limit.unwrap_or(column.wip_limit);
+    validate_column_fields(&deployment, project_id, Some(column.id), name, wip_limit).await?;
+
+    let status = payload.status.unwrap_or(column.status.clone());
+    if status != column.status
+        && ProjectColumn::count_for_status(pool, project_id, column.status.clone()).await? <= 1
+    {
+        return Err(ApiError::Conflict(format!(
+            "'{}' is the only column for status {}",
+            column.name, column.status
+        )));
+    }
+
+    let updated = ProjectColumn::update(
+        pool,
+        column.id,
+        name.unwrap_or(&column.name),
+        status,
+        wip_limit,
+        payload
+            .enforce_wip_limit
+            .unwrap_or(column.enforce_wip_limit),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+/// Set the order of all columns of the project
+pub async fn reorder_project_columns(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ReorderProjectColumns>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectColumn>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let existing: HashSet<Uuid> = ProjectColumn::find_by_project_id(pool, project.id)
+        .await?
+        .into_iter()
+        .map(|column| column.id)
+        .collect();
+    let requested: HashSet<Uuid> = payload.column_ids.iter().copied().collect();
+    if requested.len() != payload.column_ids.len() || requested != existing {
+        return Err(ApiError::BadRequest(
+            "Column order must list every column of the project exactly once".to_string(),
+        ));
+    }
+
+    let mut tx = pool.begin().await?;
+    for (position, column_id) in payload.column_ids.iter().enumerate() {
+        ProjectColumn::set_position(&mut *tx, *column_id, position as i64).await?;
+    }
+    tx.commit().await?;
+
+    let columns = ProjectColumn::find_by_project_id(pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(columns)))
+}
+
+/// Delete a column. Its tasks move to the first remaining column of their
+/// status, so the last column of a status cannot be deleted.
+pub async fn delete_project_column(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, column_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let column = ProjectColumn::find_by_id(pool, column_id)
+        .await?
+        .filter(|column| column.project_id == project_id)
+        .ok_or(SqlxError::RowNotFound)?;
+    if ProjectColumn::count_for_status(pool, project_id, column.status.clone()).await? <= 1 {
+        return Err(ApiError::Conflict(format!(
+            "'{}' is the only column for status {}",
+            column.name, column.status
+        )));
+    }
+
+    ProjectColumn::delete(pool, column.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
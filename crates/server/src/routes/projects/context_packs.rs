@@ -0,0 +1,147 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    project::Project,
+    project_context_pack::{
+        CreateProjectContextPack, ProjectContextPack, UpdateProjectContextPack,
+    },
+};
+use deployment::Deployment;
+use ignore::overrides::OverrideBuilder;
+use sqlx::Error as SqlxError;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub async fn get_project_context_packs(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectContextPack>>>, ApiError> {
+    let packs = ProjectContextPack::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(packs)))
+}
+
+async fn validate_pack_fields(
+    deployment: &DeploymentImpl,
+    project: &Project,
+    pack_id: Option<Uuid>,
+    name: Option<&str>,
+    patterns: Option<&[String]>,
+) -> Result<(), ApiError> {
+    if let Some(name) = name {
+        if name.is_empty() {
+            return Err(ApiError::BadRequest(
+                "Context pack name cannot be empty".to_string(),
+            ));
+        }
+        if ProjectContextPack::name_exists(&deployment.db().pool, project.id, name, pack_id).await?
+        {
+            return Err(ApiError::Conflict(format!(
+                "A context pack named '{name}' already exists"
+            )));
+        }
+    }
+    // Patterns are matched like gitignore lines against the repository root
+    let mut overrides = OverrideBuilder::new(&project.git_repo_path);
+    for pattern in patterns.unwrap_or_default() {
+        if pattern.trim().is_empty() || overrides.add(pattern.trim()).is_err() {
+            return Err(ApiError::BadRequest(format!(
+                "Invalid file pattern '{pattern}'"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Add a pack. It is included in the prompts of attempts started afterwards.
+pub async fn create_project_context_pack(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateProjectContextPack>,
+) -> Result<ResponseJson<ApiResponse<ProjectContextPack>>, ApiError> {
+    let payload = CreateProjectContextPack {
+        name: payload.name.trim().to_string(),
+        ..payload
+    };
+    validate_pack_fields(
+        &deployment,
+        &project,
+        None,
+        Some(&payload.name),
+        payload.patterns.as_deref(),
+    )
+    .await?;
+
+    let pack = ProjectContextPack::create(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_context_pack_created",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "patterns": pack.patterns.len(),
+                "has_notes": pack.notes.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(pack)))
+}
+
+async fn find_project_context_pack(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    pack_id: Uuid,
+) -> Result<(Project, ProjectContextPack), ApiError> {
+    let pool = &deployment.db().pool;
+    let project = Project::find_by_id(pool, project_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let pack = ProjectContextPack::find_by_id(pool, pack_id)
+        .await?
+        .filter(|pack| pack.project_id == project_id)
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+    Ok((project, pack))
+}
+
+pub async fn update_project_context_pack(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, pack_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateProjectContextPack>,
+) -> Result<ResponseJson<ApiResponse<ProjectContextPack>>, ApiError> {
+    let (project, pack) = find_project_context_pack(&deployment, project_id, pack_id).await?;
+
+    let name = payload.name.as_deref().map(str::trim);
+    validate_pack_fields(
+        &deployment,
+        &project,
+        Some(pack.id),
+        name,
+        payload.patterns.as_deref(),
+    )
+    .await?;
+
+    let updated = ProjectContextPack::update(
+        &deployment.db().pool,
+        pack.id,
+        name.unwrap_or(&pack.name),
+        payload.patterns.as_deref().unwrap_or(&pack.patterns),
+        payload.notes.as_deref().or(pack.notes.as_deref()),
+        payload.enabled.unwrap_or(pack.enabled),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+pub async fn delete_project_context_pack(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, pack_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let (_, pack) = find_project_context_pack(&deployment, project_id, pack_id).await?;
+    ProjectContextPack::delete(&deployment.db().pool, pack.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
@@ -0,0 +1,72 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    project::Project,
+    project_env_var::{ProjectEnvVar, UpsertProjectEnvVar},
+};
+use deployment::Deployment;
+use services::services::secrets::ProjectEnvVarEntry;
+use sqlx::Error as SqlxError;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// The project's environment variables. Values of secrets are left out.
+pub async fn get_project_env_vars(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectEnvVarEntry>>>, ApiError> {
+    let env_vars = deployment
+        .secrets()
+        .list_env_vars(&deployment.db().pool, project.id)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(env_vars)))
+}
+
+/// Create a variable, or replace the value of the one with the same name. It
+/// applies to processes started afterwards.
+pub async fn upsert_project_env_var(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertProjectEnvVar>,
+) -> Result<ResponseJson<ApiResponse<ProjectEnvVarEntry>>, ApiError> {
+    let env_var = deployment
+        .secrets()
+        .set_env_var(
+            &deployment.db().pool,
+            project.id,
+            payload.name.trim(),
+            &payload.value,
+            payload.is_secret.unwrap_or(true),
+        )
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_env_var_set",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "is_secret": env_var.env_var.is_secret,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(env_var)))
+}
+
+pub async fn delete_project_env_var(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, env_var_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let env_var = ProjectEnvVar::find_by_id(pool, env_var_id)
+        .await?
+        .filter(|env_var| env_var.project_id == project_id)
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+    ProjectEnvVar::delete(pool, env_var.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
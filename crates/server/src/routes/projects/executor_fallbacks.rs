@@ -0,0 +1,50 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    project::Project,
+    project_executor_fallback::{ProjectExecutorFallback, SetProjectExecutorFallbacks},
+};
+use deployment::Deployment;
+use executors::profile::{ExecutorConfigs, ProfileError};
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// The project's fallback chain, in the order it is walked
+pub async fn get_project_executor_fallbacks(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectExecutorFallback>>>, ApiError> {
+    let fallbacks =
+        ProjectExecutorFallback::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(fallbacks)))
+}
+
+/// Replace the fallback chain; an empty list turns fallbacks off
+pub async fn set_project_executor_fallbacks(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetProjectExecutorFallbacks>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectExecutorFallback>>>, ApiError> {
+    let configs = ExecutorConfigs::get_cached();
+    for profile in &payload.profiles {
+        if configs.get_coding_agent(profile).is_none() {
+            return Err(ProfileError::NotFound(profile.clone()).into());
+        }
+    }
+
+    let pool = &deployment.db().pool;
+    ProjectExecutorFallback::replace(pool, project.id, &payload.profiles).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_executor_fallbacks_updated",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "fallback_count": payload.profiles.len(),
+            }),
+        )
+        .await;
+
+    let fallbacks = ProjectExecutorFallback::find_by_project_id(pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(fallbacks)))
+}
@@ -0,0 +1,50 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    project::Project,
+    project_hosting::{ProjectHosting, UpsertProjectHosting},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// The code host chosen for the project, or null when it is detected from the
+/// project's remote URL
+pub async fn get_project_hosting(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ProjectHosting>>>, ApiError> {
+    let hosting = ProjectHosting::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(hosting)))
+}
+
+/// Open, poll and merge the project's pull requests on the given code host
+pub async fn upsert_project_hosting(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertProjectHosting>,
+) -> Result<ResponseJson<ApiResponse<ProjectHosting>>, ApiError> {
+    let hosting =
+        ProjectHosting::upsert(&deployment.db().pool, project.id, payload.provider).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_hosting_set",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "provider": hosting.provider.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(hosting)))
+}
+
+/// Detect the project's code host from its remote URL again
+pub async fn delete_project_hosting(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ProjectHosting::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    project::Project,
+    project_mcp_server::{CreateProjectMcpServer, ProjectMcpServer, UpdateProjectMcpServer},
+};
+use deployment::Deployment;
+use services::services::secrets::is_valid_env_var_name;
+use sqlx::Error as SqlxError;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub async fn get_project_mcp_servers(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectMcpServer>>>, ApiError> {
+    let servers = ProjectMcpServer::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(servers)))
+}
+
+/// Names become keys of agents' configs, and dotted paths for Codex
+fn is_valid_server_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+async fn validate_server_fields(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    server_id: Option<Uuid>,
+    name: Option<&str>,
+    command: Option<&str>,
+    env: Option<&HashMap<String, String>>,
+) -> Result<(), ApiError> {
+    if let Some(name) = name {
+        if !is_valid_server_name(name) {
+            return Err(ApiError::BadRequest(
+                "Server name may only contain letters, digits, '-' and '_'".to_string(),
+            ));
+        }
+        if ProjectMcpServer::name_exists(&deployment.db().pool, project_id, name, server_id).await?
+        {
+            return Err(ApiError::Conflict(format!(
+                "An MCP server named '{name}' already exists"
+            )));
+        }
+    }
+    if command.is_some_and(str::is_empty) {
+        return Err(ApiError::BadRequest(
+            "Server command cannot be empty".to_string(),
+        ));
+    }
+    if let Some(name) = env.and_then(|env| env.keys().find(|name| !is_valid_env_var_name(name))) {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid environment variable name '{name}'"
+        )));
+    }
+    Ok(())
+}
+
+/// Add a server. It is given to agents started afterwards.
+pub async fn create_project_mcp_server(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateProjectMcpServer>,
+) -> Result<ResponseJson<ApiResponse<ProjectMcpServer>>, ApiError> {
+    let payload = CreateProjectMcpServer {
+        name: payload.name.trim().to_string(),
+        command: payload.command.trim().to_string(),
+        ..payload
+    };
+    validate_server_fields(
+        &deployment,
+        project.id,
+        None,
+        Some(&payload.name),
+        Some(&payload.command),
+        payload.env.as_ref(),
+    )
+    .await?;
+
+    let server = ProjectMcpServer::create(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_mcp_server_created",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "enabled": server.enabled,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(server)))
+}
+
+async fn find_project_mcp_server(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    server_id: Uuid,
+) -> Result<ProjectMcpServer, ApiError> {
+    ProjectMcpServer::find_by_id(&deployment.db().pool, server_id)
+        .await?
+        .filter(|server| server.project_id == project_id)
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))
+}
+
+pub async fn update_project_mcp_server(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, server_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateProjectMcpServer>,
+) -> Result<ResponseJson<ApiResponse<ProjectMcpServer>>, ApiError> {
+    let server = find_project_mcp_server(&deployment, project_id, server_id).await?;
+
+    let name = payload.name.as_deref().map(str::trim);
+    let command = payload.command.as_deref().map(str::trim);
+    validate_server_fields(
+        &deployment,
+        project_id,
+        Some(server.id),
+        name,
+        command,
+        payload.env.as_ref(),
+    )
+    .await?;
+
+    let updated = ProjectMcpServer::update(
+        &deployment.db().pool,
+        server.id,
+        name.unwrap_or(&server.name),
+        command.unwrap_or(&server.command),
+        payload.args.as_deref().unwrap_or(&server.args),
+        payload.env.as_ref().unwrap_or(&server.env),
+        payload.enabled.unwrap_or(server.enabled),
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+pub async fn delete_project_mcp_server(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, server_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let server = find_project_mcp_server(&deployment, project_id, server_id).await?;
+    ProjectMcpServer::delete(&deployment.db().pool, server.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
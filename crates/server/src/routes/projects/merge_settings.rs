@@ -0,0 +1,69 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    project::Project,
+    project_merge_settings::{ProjectMergeSettings, UpsertProjectMergeSettings},
+};
+use deployment::Deployment;
+use services::services::merge_message::validate_commit_message_template;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// How the project's attempts are merged, or null when local merges are
+/// squashed and pull requests merged the code host's default way
+pub async fn get_project_merge_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ProjectMergeSettings>>>, ApiError> {
+    let settings =
+        ProjectMergeSettings::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Set the strategy, and optionally the commit message template, the
+/// project's attempts and pull requests are merged with
+pub async fn upsert_project_merge_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertProjectMergeSettings>,
+) -> Result<ResponseJson<ApiResponse<ProjectMergeSettings>>, ApiError> {
+    let template = payload
+        .commit_message_template
+        .as_deref()
+        .map(str::trim)
+        .filter(|template| !template.is_empty());
+    if let Some(template) = template {
+        validate_commit_message_template(template)
+            .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    }
+    let settings = ProjectMergeSettings::upsert(
+        &deployment.db().pool,
+        project.id,
+        payload.strategy,
+        template,
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_merge_settings_set",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "strategy": settings.strategy.to_string(),
+                "has_commit_message_template": settings.commit_message_template.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Go back to squashing local merges and the code host's default for pull
+/// requests
+pub async fn delete_project_merge_settings(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ProjectMergeSettings::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
@@ -0,0 +1,63 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    attempt_base_status::AttemptBaseStatus,
+    project::Project,
+    project_rebase_policy::{ProjectRebasePolicy, UpsertProjectRebasePolicy},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// What happens to the project's attempts when their base branch moves, or
+/// null when they are left alone
+pub async fn get_project_rebase_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ProjectRebasePolicy>>>, ApiError> {
+    let policy = ProjectRebasePolicy::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+/// Watch the base branches of the project's open attempts and flag or rebase
+/// the attempts that fall behind
+pub async fn upsert_project_rebase_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertProjectRebasePolicy>,
+) -> Result<ResponseJson<ApiResponse<ProjectRebasePolicy>>, ApiError> {
+    let policy =
+        ProjectRebasePolicy::upsert(&deployment.db().pool, project.id, payload.mode).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_rebase_policy_set",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "mode": policy.mode.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(policy)))
+}
+
+/// Stop watching the base branches of the project's attempts
+pub async fn delete_project_rebase_policy(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ProjectRebasePolicy::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// The project's attempts that are behind their base branch, longest behind
+/// first
+pub async fn get_project_stale_attempts(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<AttemptBaseStatus>>>, ApiError> {
+    let statuses =
+        AttemptBaseStatus::find_stale_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(statuses)))
+}
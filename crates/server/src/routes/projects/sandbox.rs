@@ -0,0 +1,61 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    project::Project,
+    project_sandbox::{ProjectSandbox, UpsertProjectSandbox},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// The project's sandbox, or null when its agents run on the host
+pub async fn get_project_sandbox(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ProjectSandbox>>>, ApiError> {
+    let sandbox = ProjectSandbox::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(sandbox)))
+}
+
+/// Run the project's coding agents in a container of the image. It applies
+/// to agents started afterwards.
+pub async fn upsert_project_sandbox(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertProjectSandbox>,
+) -> Result<ResponseJson<ApiResponse<ProjectSandbox>>, ApiError> {
+    let image = payload.image.trim();
+    if image.is_empty() || image.chars().any(char::is_whitespace) {
+        return Err(ApiError::BadRequest(
+            "Sandbox image must be an image reference".to_string(),
+        ));
+    }
+    let bootstrap_script = payload
+        .bootstrap_script
+        .as_deref()
+        .filter(|script| !script.trim().is_empty());
+
+    let sandbox =
+        ProjectSandbox::upsert(&deployment.db().pool, project.id, image, bootstrap_script).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_sandbox_set",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "has_bootstrap_script": sandbox.bootstrap_script.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(sandbox)))
+}
+
+/// Run the project's coding agents on the host again
+pub async fn delete_project_sandbox(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ProjectSandbox::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
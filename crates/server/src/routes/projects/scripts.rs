@@ -0,0 +1,285 @@
+use std::collections::HashSet;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State, ws::WebSocketUpgrade},
+    response::{IntoResponse, Json as ResponseJson},
+};
+use db::models::{
+    execution_process::ExecutionProcess,
+    project::Project,
+    project_script::{
+        CreateProjectScript, ProjectScript, ProjectScriptKind, ReorderProjectScripts,
+        UpdateProjectScript,
+    },
+    task_attempt::TaskAttempt,
+};
+use deployment::Deployment;
+use executors::actions::{ExecutorAction, ExecutorActionType};
+use serde::Deserialize;
+use services::services::container::{ContainerService, project_script_request, script_run_reason};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::execution_processes::handle_raw_logs_ws};
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectScriptQuery {
+    #[serde(default)]
+    pub kind: Option<ProjectScriptKind>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct RunProjectScript {
+    /// Attempt whose worktree the script runs in
+    pub task_attempt_id: Uuid,
+}
+
+pub async fn get_project_scripts(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ProjectScriptQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectScript>>>, ApiError> {
+    let scripts =
+        ProjectScript::find_by_project_id(&deployment.db().pool, project.id, query.kind).await?;
+    Ok(ResponseJson(ApiResponse::success(scripts)))
+}
+
+async fn validate_script_fields(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    kind: ProjectScriptKind,
+    script_id: Option<Uuid>,
+    name: Option<&str>,
+    timeout_seconds: Option<i64>,
+    max_retries: Option<i64>,
+) -> Result<(), ApiError> {
+    if let Some(name) = name {
+        if name.is_empty() {
+            return Err(ApiError::BadRequest(
+                "Script name cannot be empty".to_string(),
+            ));
+        }
+        if ProjectScript::name_exists(&deployment.db().pool, project_id, kind, name, script_id)
+            .await?
+        {
+            return Err(ApiError::Conflict(format!(
+                "A script named '{name}' already exists"
+            )));
+        }
+    }
+    if timeout_seconds.is_some_and(|seconds| !(1..=i64::from(u32::MAX)).contains(&seconds)) {
+        return Err(ApiError::BadRequest(
+            "Timeout must be a positive number of seconds".to_string(),
+        ));
+    }
+    if max_retries.is_some_and(|retries| !(0..=i64::from(u32::MAX)).contains(&retries)) {
+        return Err(ApiError::BadRequest(
+            "Retries must be zero or more".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub async fn create_project_script(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateProjectScript>,
+) -> Result<ResponseJson<ApiResponse<ProjectScript>>, ApiError> {
+    let name = payload.name.trim().to_string();
+    validate_script_fields(
+        &deployment,
+        project.id,
+        payload.kind,
+        None,
+        Some(&name),
+        payload.timeout_seconds,
+        payload.max_retries,
+    )
+    .await?;
+
+    let script = ProjectScript::create(
+        &deployment.db().pool,
+        project.id,
+        &CreateProjectScript { name, ..payload },
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_script_created",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "kind": script.kind,
+                "has_timeout": script.timeout_seconds.is_some(),
+                "max_retries": script.max_retries,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(script)))
+}
+
+async fn find_project_script(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    script_id: Uuid,
+) -> Result<ProjectScript, ApiError> {
+    ProjectScript::find_by_id(&deployment.db().pool, script_id)
+        .await?
+        .filter(|script| script.project_id == project_id)
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))
+}
+
+pub async fn update_project_script(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, script_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateProjectScript>,
+) -> Result<ResponseJson<ApiResponse<ProjectScript>>, ApiError> {
+    let script = find_project_script(&deployment, project_id, script_id).await?;
+
+    let name = payload.name.as_deref().map(str::trim);
+    let timeout_seconds = payload.timeout_seconds.unwrap_or(script.timeout_seconds);
+    let max_retries = payload.max_retries.unwrap_or(script.max_retries);
+    validate_script_fields(
+        &deployment,
+        project_id,
+        script.kind,
+        Some(script.id),
+        name,
+        timeout_seconds,
+        Some(max_retries),
+    )
+    .await?;
+
+    let updated = ProjectScript::update(
+        &deployment.db().pool,
+        script.id,
+        name.unwrap_or(&script.name),
+        payload.script.as_deref().unwrap_or(&script.script),
+        timeout_seconds,
+        max_retries,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+/// Set the run order of all scripts of one kind
+pub async fn reorder_project_scripts(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ReorderProjectScripts>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectScript>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let existing: HashSet<Uuid> =
+        ProjectScript::find_by_project_id(pool, project.id, Some(payload.kind))
+            .await?
+            .into_iter()
+            .map(|script| script.id)
+            .collect();
+    let requested: HashSet<Uuid> = payload.script_ids.iter().copied().collect();
+    if requested.len() != payload.script_ids.len() || requested != existing {
+        return Err(ApiError::BadRequest(
+            "Script order must list every script of the kind exactly once".to_string(),
+        ));
+    }
+
+    let mut tx = pool.begin().await?;
+    for (position, script_id) in payload.script_ids.iter().enumerate() {
+        ProjectScript::set_position(&mut *tx, *script_id, position as i64).await?;
+    }
+    tx.commit().await?;
+
+    let scripts = ProjectScript::find_by_project_id(pool, project.id, Some(payload.kind)).await?;
+    Ok(ResponseJson(ApiResponse::success(scripts)))
+}
+
+pub async fn delete_project_script(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, script_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let script = find_project_script(&deployment, project_id, script_id).await?;
+    ProjectScript::delete(&deployment.db().pool, script.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Start a script in an attempt's worktree, with the script's timeout and
+/// retries
+async fn start_project_script(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    script_id: Uuid,
+    task_attempt_id: Uuid,
+) -> Result<ExecutionProcess, ApiError> {
+    let pool = &deployment.db().pool;
+    let script = find_project_script(deployment, project_id, script_id).await?;
+    if script.kind == ProjectScriptKind::Dev {
+        return Err(ApiError::BadRequest(
+            "Dev scripts run through the attempt's dev server".to_string(),
+        ));
+    }
+    let task_attempt = TaskAttempt::find_by_id(pool, task_attempt_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .filter(|task| task.project_id == project_id)
+        .ok_or_else(|| ApiError::BadRequest("Attempt not found for this project".to_string()))?;
+
+    deployment
+        .container()
+        .ensure_container_exists(&task_attempt)
+        .await?;
+    let request = project_script_request(&script);
+    let run_reason = script_run_reason(&request.context);
+    let executor_action = ExecutorAction::new(ExecutorActionType::ScriptRequest(request), None);
+    let execution_process = deployment
+        .container()
+        .start_execution(&task_attempt, &executor_action, &run_reason)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_script_run",
+            serde_json::json!({
+                "project_id": project_id.to_string(),
+                "task_id": task.id.to_string(),
+                "attempt_id": task_attempt.id.to_string(),
+                "kind": script.kind,
+            }),
+        )
+        .await;
+
+    Ok(execution_process)
+}
+
+/// Run a script; its output streams from the execution process' raw logs
+pub async fn run_project_script(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, script_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<RunProjectScript>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    let execution_process =
+        start_project_script(&deployment, project_id, script_id, payload.task_attempt_id).await?;
+    Ok(ResponseJson(ApiResponse::success(execution_process)))
+}
+
+/// Run a script and stream its output over the socket until it exits
+pub async fn run_project_script_ws(
+    ws: WebSocketUpgrade,
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, script_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<RunProjectScript>,
+) -> Result<impl IntoResponse, ApiError> {
+    let execution_process =
+        start_project_script(&deployment, project_id, script_id, query.task_attempt_id).await?;
+
+    Ok(ws.on_upgrade(move |socket| async move {
+        if let Err(e) = handle_raw_logs_ws(socket, deployment, execution_process.id).await {
+            tracing::warn!("project script WS closed: {}", e);
+        }
+    }))
+}
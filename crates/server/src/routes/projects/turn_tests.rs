@@ -0,0 +1,73 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    project::Project,
+    project_script::{ProjectScript, ProjectScriptKind},
+    project_turn_test::{ProjectTurnTest, UpsertProjectTurnTest},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Most follow-ups sent in a row for failing turn tests
+const MAX_FEEDBACK_ROUNDS: i64 = 10;
+
+/// The project's turn tests, or null when tests only run on demand
+pub async fn get_project_turn_tests(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ProjectTurnTest>>>, ApiError> {
+    let turn_tests = ProjectTurnTest::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(turn_tests)))
+}
+
+/// Run one of the project's test scripts after every coding agent turn. It
+/// applies to turns finishing afterwards.
+pub async fn upsert_project_turn_tests(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertProjectTurnTest>,
+) -> Result<ResponseJson<ApiResponse<ProjectTurnTest>>, ApiError> {
+    let pool = &deployment.db().pool;
+    ProjectScript::find_by_id(pool, payload.script_id)
+        .await?
+        .filter(|script| script.project_id == project.id && script.kind == ProjectScriptKind::Test)
+        .ok_or_else(|| ApiError::BadRequest("Not a test script of this project".to_string()))?;
+    let max_feedback_rounds = payload.max_feedback_rounds.unwrap_or(3);
+    if !(0..=MAX_FEEDBACK_ROUNDS).contains(&max_feedback_rounds) {
+        return Err(ApiError::BadRequest(format!(
+            "Feedback rounds must be between 0 and {MAX_FEEDBACK_ROUNDS}"
+        )));
+    }
+
+    let turn_tests = ProjectTurnTest::upsert(
+        pool,
+        project.id,
+        payload.script_id,
+        payload.feed_back_failures.unwrap_or(false),
+        max_feedback_rounds,
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_turn_tests_set",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "feed_back_failures": turn_tests.feed_back_failures,
+                "max_feedback_rounds": turn_tests.max_feedback_rounds,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(turn_tests)))
+}
+
+/// Stop running tests after coding agent turns
+pub async fn delete_project_turn_tests(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ProjectTurnTest::delete(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
@@ -0,0 +1,56 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::search::{SearchResult, SearchResultKind};
+use deployment::Deployment;
+use serde::Deserialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 100;
+
+#[derive(Debug, Deserialize, TS)]
+pub struct SearchQuery {
+    pub q: String,
+    /// Only search within this project
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+    #[serde(default)]
+    pub kind: Option<SearchResultKind>,
+    #[serde(default)]
+    pub limit: Option<i64>,
+}
+
+/// Full-text search over task titles and descriptions, attempt summaries and
+/// executor output, best matches first
+pub async fn search(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<SearchQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<SearchResult>>>, ApiError> {
+    if query.q.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Search query cannot be empty".to_string(),
+        ));
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let results = SearchResult::search(
+        &deployment.db().pool,
+        &query.q,
+        query.project_id,
+        query.kind,
+        limit,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(results)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/search", get(search))
+}
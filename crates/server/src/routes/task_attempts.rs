@@ -1,6 +1,21 @@
+pub mod checkpoints;
+pub mod ci_checks;
+pub mod compare;
+pub mod comparison_groups;
+pub mod conflicts;
 pub mod cursor_setup;
+pub mod diff_files;
 pub mod drafts;
+pub mod export;
+pub mod follow_up_queue;
+pub mod fork;
+pub mod partial_commit;
+pub mod plan;
+pub mod proposed_tasks;
+pub mod repositories;
+pub mod review_comments;
 pub mod util;
+pub mod worktree;
 
 use axum::{
     Extension, Json, Router,
@@ -11,56 +26,104 @@ use axum::{
     http::StatusCode,
     middleware::from_fn_with_state,
     response::{IntoResponse, Json as ResponseJson},
-    routing::{get, post},
+    routing::{delete, get, post, put},
 };
 use db::models::{
+    attempt_base_status::AttemptBaseStatus,
+    attempt_plan::AttemptPlan,
+    attempt_usage::{AttemptUsage, UsageTotals},
     draft::{Draft, DraftType},
+    execution_fallback::ExecutionFallback,
     execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    execution_resumption::ExecutionResumption,
+    label::{Label, SetLabels},
     merge::{Merge, MergeStatus, PrMerge, PullRequestInfo},
     project::{Project, ProjectError},
+    project_hosting::HostingProviderKind,
+    project_merge_settings::{MergeStrategy, ProjectMergeSettings},
+    project_script::ProjectScriptKind,
+    queued_follow_up::QueuedFollowUp,
     task::{Task, TaskRelationships, TaskStatus},
     task_attempt::{CreateTaskAttempt, TaskAttempt, TaskAttemptError},
+    task_dependency::TaskDependency,
+    turn_test_run::TurnTestRun,
 };
 use deployment::Deployment;
 use executors::{
     actions::{
-        ExecutorAction, ExecutorActionType,
-        coding_agent_follow_up::CodingAgentFollowUpRequest,
-        script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+        ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
     },
     executors::{CodingAgent, ExecutorError},
-    profile::{ExecutorConfigs, ExecutorProfileId},
+    profile::{ExecutorConfigs, ExecutorProfileId, ProfileError},
 };
 use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
+    commit_signing::CommitSignature,
     container::ContainerService,
     git::{ConflictOp, WorktreeResetOptions},
     github_service::{CreatePrRequest, GitHubService, GitHubServiceError},
+    hosting::{HostingError, PrMergeOptions},
+    merge_message::{CommitMessageContext, DEFAULT_COMMIT_MESSAGE_TEMPLATE, render_commit_message},
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
-use utils::response::ApiResponse;
+use utils::{approvals::ApprovalRequest, response::ApiResponse};
 use uuid::Uuid;
 
 use crate::{
     DeploymentImpl,
     error::ApiError,
     middleware::load_task_attempt_middleware,
-    routes::task_attempts::util::{ensure_worktree_path, handle_images_for_prompt},
+    routes::{
+        labels::ensure_labels_exist,
+        task_attempts::util::{
+            attempt_hosting_provider, attempt_repo_path, ensure_commits_signed,
+            ensure_not_archived, ensure_not_paused, ensure_plan_not_pending, ensure_worktree_path,
+            handle_images_for_prompt, project_hosting_token,
+        },
+        tasks::assignment::{assign_task_attempt, resolve_assignee_filter, unassign_task_attempt},
+    },
 };
 
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct RebaseTaskAttemptRequest {
     pub old_base_branch: Option<String>,
     pub new_base_branch: Option<String>,
+    /// How to bring in the base branch; defaults to rebase
+    #[serde(default)]
+    #[ts(optional)]
+    pub strategy: Option<RebaseStrategy>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum RebaseStrategy {
+    #[default]
+    Rebase,
+    Merge,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct RebaseTaskAttemptResponse {
+    pub strategy: RebaseStrategy,
+    pub head_commit: String,
+    /// New merge base with the target branch; diff views should reload from here
+    pub base_commit: String,
+    pub commits_ahead: usize,
+    pub commits_behind: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize, TS)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type", rename_all = "snake_case")]
 pub enum GitOperationError {
-    MergeConflicts { message: String, op: ConflictOp },
+    MergeConflicts {
+        message: String,
+        op: ConflictOp,
+        conflicted_files: Vec<String>,
+    },
     RebaseInProgress,
 }
 
@@ -87,11 +150,28 @@ pub struct ReplaceProcessResult {
     pub new_execution_id: Option<Uuid>,
 }
 
+/// Overrides of the project's merge settings for one merge
+#[derive(Debug, Default, Deserialize, Serialize, TS)]
+pub struct MergeTaskAttemptRequest {
+    #[serde(default)]
+    #[ts(optional)]
+    pub strategy: Option<MergeStrategy>,
+    /// Message of the squash or merge commit, instead of the one rendered
+    /// from the project's template
+    #[serde(default)]
+    #[ts(optional)]
+    pub commit_message: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct CreateGitHubPrRequest {
     pub title: String,
     pub body: Option<String>,
     pub target_branch: Option<String>,
+    /// Open the PR as a draft, to be marked ready for review later
+    #[serde(default)]
+    #[ts(optional)]
+    pub draft: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -104,6 +184,12 @@ pub struct FollowUpResponse {
 #[derive(Debug, Deserialize)]
 pub struct TaskAttemptQuery {
     pub task_id: Option<Uuid>,
+    /// Only return attempts carrying this label
+    #[serde(default)]
+    pub label_id: Option<Uuid>,
+    /// Only return attempts assigned to this user; `me` is the current user
+    #[serde(default)]
+    pub assignee: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -117,7 +203,14 @@ pub async fn get_task_attempts(
     Query(query): Query<TaskAttemptQuery>,
 ) -> Result<ResponseJson<ApiResponse<Vec<TaskAttempt>>>, ApiError> {
     let pool = &deployment.db().pool;
-    let attempts = TaskAttempt::fetch_all(pool, query.task_id).await?;
+    let mut attempts = TaskAttempt::fetch_all(pool, query.task_id).await?;
+    if let Some(label_id) = query.label_id {
+        let labelled = Label::task_attempt_ids(pool, label_id).await?;
+        attempts.retain(|attempt| labelled.contains(&attempt.id));
+    }
+    if let Some(assignee) = resolve_assignee_filter(&deployment, query.assignee).await? {
+        attempts.retain(|attempt| attempt.assignee.as_deref() == Some(assignee.as_str()));
+    }
     Ok(ResponseJson(ApiResponse::success(attempts)))
 }
 
@@ -134,6 +227,24 @@ pub struct CreateTaskAttemptBody {
     /// Executor profile specification
     pub executor_profile_id: ExecutorProfileId,
     pub base_branch: String,
+    /// Cancel runs of this attempt after this many minutes (overrides the project setting)
+    #[serde(default)]
+    #[ts(optional)]
+    pub execution_timeout_minutes: Option<i64>,
+    /// Stack the new attempt on this attempt's branch; `base_branch` is then ignored
+    #[serde(default)]
+    #[ts(optional)]
+    pub parent_attempt_id: Option<Uuid>,
+    /// Have the agent write a plan first, and only implement it once the plan
+    /// is approved
+    #[serde(default)]
+    #[ts(optional)]
+    pub plan_first: Option<bool>,
+    /// Have the agent answer without changing code, e.g. for questions about
+    /// the codebase; whatever it changes is discarded
+    #[serde(default)]
+    #[ts(optional)]
+    pub read_only: Option<bool>,
 }
 
 impl CreateTaskAttemptBody {
@@ -143,6 +254,101 @@ impl CreateTaskAttemptBody {
     }
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct SetExecutionTimeoutRequest {
+    /// Minutes before runs of this attempt are cancelled; null falls back to the project setting
+    pub minutes: Option<i64>,
+}
+
+fn validate_execution_timeout(minutes: Option<i64>) -> Result<(), ApiError> {
+    if minutes.is_some_and(|m| m <= 0) {
+        return Err(ApiError::BadRequest(
+            "Execution timeout must be a positive number of minutes".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub async fn set_task_attempt_timeout(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetExecutionTimeoutRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    validate_execution_timeout(payload.minutes)?;
+    let pool = &deployment.db().pool;
+    TaskAttempt::set_execution_timeout(pool, task_attempt.id, payload.minutes).await?;
+    // Runs already going pick up the new timeout right away
+    for process in running_agent_processes(&deployment, &task_attempt).await? {
+        deployment
+            .container()
+            .refresh_execution_timeout(process.id)
+            .await;
+    }
+    let task_attempt = TaskAttempt::find_by_id(pool, task_attempt.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    Ok(ResponseJson(ApiResponse::success(task_attempt)))
+}
+
+/// Load the attempt a new attempt of `task` is stacked on. Stacking is limited
+/// to attempts of the same project, since they share one repository.
+async fn load_parent_attempt(
+    deployment: &DeploymentImpl,
+    task: &Task,
+    parent_attempt_id: Uuid,
+) -> Result<TaskAttempt, ApiError> {
+    let pool = &deployment.db().pool;
+    let parent = TaskAttempt::find_by_id(pool, parent_attempt_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Parent attempt not found".to_string()))?;
+    let parent_task = parent
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    if parent_task.project_id != task.project_id {
+        return Err(ApiError::BadRequest(
+            "Parent attempt belongs to a different project".to_string(),
+        ));
+    }
+    Ok(parent)
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct AttemptStack {
+    /// Attempts this one is stacked on, from the bottom of the stack upwards
+    pub ancestors: Vec<TaskAttempt>,
+    /// Attempts stacked directly on this one
+    pub children: Vec<TaskAttempt>,
+}
+
+pub async fn get_task_attempt_stack(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<AttemptStack>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let mut ancestors = Vec::new();
+    let mut next = task_attempt.parent_attempt_id;
+    while let Some(parent_id) = next {
+        // Guard against cycles from manual edits
+        if parent_id == task_attempt.id || ancestors.iter().any(|a: &TaskAttempt| a.id == parent_id)
+        {
+            break;
+        }
+        let Some(parent) = TaskAttempt::find_by_id(pool, parent_id).await? else {
+            break;
+        };
+        next = parent.parent_attempt_id;
+        ancestors.push(parent);
+    }
+    ancestors.reverse();
+
+    let children = TaskAttempt::find_by_parent_attempt_id(pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(AttemptStack {
+        ancestors,
+        children,
+    })))
+}
+
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct RunAgentSetupRequest {
     pub executor_profile_id: ExecutorProfileId,
@@ -151,27 +357,74 @@ pub struct RunAgentSetupRequest {
 #[derive(Debug, Serialize, TS)]
 pub struct RunAgentSetupResponse {}
 
+/// Refuse to start work on a task whose blockers are unfinished, when the
+/// user enabled dependency enforcement.
+async fn ensure_dependencies_done(
+    deployment: &DeploymentImpl,
+    task: &Task,
+) -> Result<(), ApiError> {
+    if !deployment.config().read().await.enforce_task_dependencies {
+        return Ok(());
+    }
+    let blockers = TaskDependency::find_unfinished_blockers(&deployment.db().pool, task.id).await?;
+    if blockers.is_empty() {
+        return Ok(());
+    }
+    let titles = blockers
+        .iter()
+        .map(|blocker| format!("'{}'", blocker.title))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(ApiError::Conflict(format!(
+        "Task is blocked by unfinished tasks: {titles}"
+    )))
+}
+
 #[axum::debug_handler]
 pub async fn create_task_attempt(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateTaskAttemptBody>,
 ) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
     let executor_profile_id = payload.get_executor_profile_id();
+    if ExecutorConfigs::get_cached()
+        .get_coding_agent(&executor_profile_id)
+        .is_none()
+    {
+        return Err(ProfileError::NotFound(executor_profile_id).into());
+    }
+    validate_execution_timeout(payload.execution_timeout_minutes)?;
     let task = Task::find_by_id(&deployment.db().pool, payload.task_id)
         .await?
         .ok_or(SqlxError::RowNotFound)?;
+    if Task::is_trashed(&deployment.db().pool, task.id).await?
+        || Project::is_trashed(&deployment.db().pool, task.project_id).await?
+    {
+        return Err(ApiError::Conflict(
+            "Task is in the trash; restore it first".to_string(),
+        ));
+    }
+    ensure_dependencies_done(&deployment, &task).await?;
+
+    // A stacked attempt branches off (and later targets) its parent's branch
+    let base_branch = match payload.parent_attempt_id {
+        Some(parent_attempt_id) => {
+            let parent = load_parent_attempt(&deployment, &task, parent_attempt_id).await?;
+            parent.branch
+        }
+        None => payload.base_branch.clone(),
+    };
 
     let attempt_id = Uuid::new_v4();
     let git_branch_name = deployment
         .container()
-        .git_branch_from_task_attempt(&attempt_id, &task.title)
-        .await;
+        .git_branch_from_task_attempt(&attempt_id, &task, &executor_profile_id.executor)
+        .await?;
 
     let task_attempt = TaskAttempt::create(
         &deployment.db().pool,
         &CreateTaskAttempt {
             executor: executor_profile_id.executor,
-            base_branch: payload.base_branch.clone(),
+            base_branch,
             branch: git_branch_name.clone(),
         },
         attempt_id,
@@ -179,9 +432,40 @@ pub async fn create_task_attempt(
     )
     .await?;
 
+    if payload.execution_timeout_minutes.is_some() {
+        TaskAttempt::set_execution_timeout(
+            &deployment.db().pool,
+            task_attempt.id,
+            payload.execution_timeout_minutes,
+        )
+        .await?;
+    }
+    if let Some(parent_attempt_id) = payload.parent_attempt_id {
+        TaskAttempt::set_parent_attempt(&deployment.db().pool, task_attempt.id, parent_attempt_id)
+            .await?;
+    }
+    let read_only = payload.read_only.unwrap_or(false);
+    if read_only {
+        TaskAttempt::set_read_only(&deployment.db().pool, task_attempt.id, true).await?;
+    }
+    let task_attempt = if payload.execution_timeout_minutes.is_some()
+        || payload.parent_attempt_id.is_some()
+        || read_only
+    {
+        TaskAttempt::find_by_id(&deployment.db().pool, task_attempt.id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?
+    } else {
+        task_attempt
+    };
+
+    if payload.plan_first.unwrap_or(false) {
+        AttemptPlan::create(&deployment.db().pool, task_attempt.id).await?;
+    }
+
     if let Err(err) = deployment
         .container()
-        .start_attempt(&task_attempt, executor_profile_id.clone())
+        .queue_or_start_attempt(&task_attempt, executor_profile_id.clone())
         .await
     {
         tracing::error!("Failed to start task attempt: {}", err);
@@ -195,6 +479,8 @@ pub async fn create_task_attempt(
                 "variant": &executor_profile_id.variant,
                 "executor": &executor_profile_id.executor,
                 "attempt_id": task_attempt.id.to_string(),
+                "plan_first": payload.plan_first.unwrap_or(false),
+                "read_only": read_only,
             }),
         )
         .await;
@@ -249,6 +535,9 @@ pub async fn follow_up(
     Json(payload): Json<CreateFollowUpAttempt>,
 ) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
     tracing::info!("{:?}", task_attempt);
+    ensure_not_archived(&task_attempt)?;
+    ensure_not_paused(&task_attempt)?;
+    ensure_plan_not_pending(&deployment, &task_attempt).await?;
 
     // Ensure worktree exists (recreate if needed for cold task support)
     let _ = ensure_worktree_path(&deployment, &task_attempt).await?;
@@ -348,9 +637,7 @@ pub async fn follow_up(
             .await?;
     }
 
-    let cleanup_action = deployment
-        .container()
-        .cleanup_action(project.cleanup_script);
+    let cleanup_action = deployment.container().cleanup_action(&project).await?;
 
     let action_type = if let Some(session_id) = latest_session_id {
         ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
@@ -396,6 +683,7 @@ pub async fn replace_process(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<ReplaceProcessRequest>,
 ) -> Result<ResponseJson<ApiResponse<ReplaceProcessResult>>, ApiError> {
+    ensure_not_archived(&task_attempt)?;
     let pool = &deployment.db().pool;
     let proc_id = payload.process_id;
     let force_when_dirty = payload.force_when_dirty.unwrap_or(false);
@@ -598,9 +886,8 @@ pub async fn get_commit_info(
             "Missing sha param".to_string(),
         )));
     };
-    let wt_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
-    let wt = wt_buf.as_path();
-    let subject = deployment.git().get_commit_subject(wt, &sha)?;
+    let repo_path = attempt_repo_path(&deployment, &task_attempt).await?;
+    let subject = deployment.git().get_commit_subject(&repo_path, &sha)?;
     Ok(ResponseJson(ApiResponse::success(CommitInfo {
         sha,
         subject,
@@ -626,16 +913,21 @@ pub async fn compare_commit_to_head(
             "Missing sha param".to_string(),
         )));
     };
-    let wt_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
-    let wt = wt_buf.as_path();
-    let head_info = deployment.git().get_head_info(wt)?;
+    let repo_path = attempt_repo_path(&deployment, &task_attempt).await?;
+    let head_oid = if task_attempt.archived_at.is_some() {
+        deployment
+            .git()
+            .get_branch_oid(&repo_path, &task_attempt.branch)?
+    } else {
+        deployment.git().get_head_info(&repo_path)?.oid
+    };
     let (ahead_from_head, behind_from_head) =
         deployment
             .git()
-            .ahead_behind_commits_by_oid(wt, &head_info.oid, &target_oid)?;
+            .ahead_behind_commits_by_oid(&repo_path, &head_oid, &target_oid)?;
     let is_linear = behind_from_head == 0;
     Ok(ResponseJson(ApiResponse::success(CommitCompareResult {
-        head_oid: head_info.oid,
+        head_oid,
         target_oid,
         ahead_from_head,
         behind_from_head,
@@ -644,9 +936,57 @@ pub async fn compare_commit_to_head(
 }
 
 #[axum::debug_handler]
+fn render_merge_message(
+    template: &str,
+    task: &Task,
+    task_attempt: &TaskAttempt,
+) -> Result<String, ApiError> {
+    let context = CommitMessageContext {
+        title: &task.title,
+        description: task.description.as_deref(),
+        task_id: &task.id,
+        branch: &task_attempt.branch,
+        target_branch: &task_attempt.target_branch,
+    };
+    render_commit_message(template, &context).map_err(|e| ApiError::BadRequest(e.to_string()))
+}
+
+/// Strategy and commit message for merging an attempt: the request's, else
+/// the project's merge settings. Both are None when neither sets them.
+async fn resolve_merge_options(
+    deployment: &DeploymentImpl,
+    task: &Task,
+    task_attempt: &TaskAttempt,
+    request: MergeTaskAttemptRequest,
+) -> Result<PrMergeOptions, ApiError> {
+    let settings =
+        ProjectMergeSettings::find_by_project_id(&deployment.db().pool, task.project_id).await?;
+    let strategy = request
+        .strategy
+        .or(settings.as_ref().map(|settings| settings.strategy));
+    let commit_message = match request
+        .commit_message
+        .filter(|message| !message.trim().is_empty())
+    {
+        Some(message) => Some(message),
+        None => settings
+            .and_then(|settings| settings.commit_message_template)
+            .map(|template| render_merge_message(&template, task, task_attempt))
+            .transpose()?,
+    };
+    Ok(PrMergeOptions {
+        strategy,
+        commit_message,
+    })
+}
+
+/// Merge the attempt branch into its target branch locally. The strategy and
+/// commit message come from the request body, else the project's merge
+/// settings; without either the attempt is squashed.
 pub async fn merge_task_attempt(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
+    payload: Option<Json<MergeTaskAttemptRequest>>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
     let pool = &deployment.db().pool;
 
@@ -659,25 +999,20 @@ pub async fn merge_task_attempt(
     let worktree_path_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
     let worktree_path = worktree_path_buf.as_path();
 
-    let task_uuid_str = task.id.to_string();
-    let first_uuid_section = task_uuid_str.split('-').next().unwrap_or(&task_uuid_str);
-
-    // Create commit message with task title and description
-    let mut commit_message = format!("{} (vibe-kanban {})", ctx.task.title, first_uuid_section);
-
-    // Add description on next line if it exists
-    if let Some(description) = &ctx.task.description
-        && !description.trim().is_empty()
-    {
-        commit_message.push_str("\n\n");
-        commit_message.push_str(description);
-    }
+    let request = payload.map(|Json(request)| request).unwrap_or_default();
+    let options = resolve_merge_options(&deployment, &task, &task_attempt, request).await?;
+    let strategy = options.strategy.unwrap_or(MergeStrategy::Squash);
+    let commit_message = match options.commit_message {
+        Some(message) => message,
+        None => render_merge_message(DEFAULT_COMMIT_MESSAGE_TEMPLATE, &task, &task_attempt)?,
+    };
 
-    let merge_commit_id = deployment.git().merge_changes(
+    let merge_commit_id = deployment.git().merge_changes_with_strategy(
         &ctx.project.git_repo_path,
         worktree_path,
         &ctx.task_attempt.branch,
         &ctx.task_attempt.target_branch,
+        strategy,
         &commit_message,
     )?;
 
@@ -690,6 +1025,17 @@ pub async fn merge_task_attempt(
     .await?;
     Task::update_status(pool, ctx.task.id, TaskStatus::Done).await?;
 
+    if deployment
+        .config()
+        .read()
+        .await
+        .worktree_cleanup
+        .delete_on_merge
+    {
+        deployment.container().delete(&task_attempt).await?;
+        TaskAttempt::mark_worktree_deleted(pool, task_attempt.id).await?;
+    }
+
     deployment
         .track_if_analytics_allowed(
             "task_attempt_merged",
@@ -697,6 +1043,7 @@ pub async fn merge_task_attempt(
                 "task_id": ctx.task.id.to_string(),
                 "project_id": ctx.project.id.to_string(),
                 "attempt_id": task_attempt.id.to_string(),
+                "strategy": strategy.to_string(),
             }),
         )
         .await;
@@ -708,19 +1055,33 @@ pub async fn push_task_attempt_branch(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
-    let github_config = deployment.config().read().await.github.clone();
-    let Some(github_token) = github_config.token() else {
-        return Err(GitHubServiceError::TokenInvalid.into());
-    };
+    let (_, provider) = attempt_hosting_provider(&deployment, &task_attempt).await?;
+    provider.check_token().await?;
 
-    let github_service = GitHubService::new(&github_token)?;
-    github_service.check_token().await?;
+    let ws_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    ensure_commits_signed(&deployment, &task_attempt, &ws_path).await?;
+
+    deployment
+        .git()
+        .push_to_github(&ws_path, &task_attempt.branch, provider.token())?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Push a rewritten attempt branch (e.g. after a rebase) with
+/// `--force-with-lease`, refusing to overwrite commits pushed by others.
+pub async fn force_push_task_attempt_branch(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let (_, provider) = attempt_hosting_provider(&deployment, &task_attempt).await?;
+    provider.check_token().await?;
 
     let ws_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    ensure_commits_signed(&deployment, &task_attempt, &ws_path).await?;
 
     deployment
         .git()
-        .push_to_github(&ws_path, &task_attempt.branch, &github_token)?;
+        .force_push_with_lease(&ws_path, &task_attempt.branch, provider.token())?;
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
@@ -730,13 +1091,12 @@ pub async fn create_github_pr(
     Json(request): Json<CreateGitHubPrRequest>,
 ) -> Result<ResponseJson<ApiResponse<String, GitHubServiceError>>, ApiError> {
     let github_config = deployment.config().read().await.github.clone();
-    let Some(github_token) = github_config.token() else {
-        return Ok(ResponseJson(ApiResponse::error_with_data(
-            GitHubServiceError::TokenInvalid,
-        )));
+    let (project, provider) = match attempt_hosting_provider(&deployment, &task_attempt).await {
+        Ok(resolved) => resolved,
+        Err(ApiError::Hosting(e)) => return Ok(ResponseJson(pr_error_response(e))),
+        Err(e) => return Err(e),
     };
-    // Create GitHub service instance
-    let github_service = GitHubService::new(&github_token)?;
+    let provider_kind = provider.kind();
     // Get the task attempt to access the stored target branch
     let target_branch = request.target_branch.unwrap_or_else(|| {
         // Use the stored target branch from the task attempt as the default
@@ -752,23 +1112,21 @@ pub async fn create_github_pr(
     });
 
     let pool = &deployment.db().pool;
-    let task = task_attempt
-        .parent_task(pool)
-        .await?
-        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
-    let project = Project::find_by_id(pool, task.project_id)
-        .await?
-        .ok_or(ApiError::Project(ProjectError::ProjectNotFound))?;
-
     let workspace_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    ensure_commits_signed(&deployment, &task_attempt, &workspace_path).await?;
 
-    // Push the branch to GitHub first
+    // Push the branch to the code host first
     if let Err(e) =
         deployment
             .git()
-            .push_to_github(&workspace_path, &task_attempt.branch, &github_token)
+            .push_to_github(&workspace_path, &task_attempt.branch, provider.token())
     {
-        tracing::error!("Failed to push branch to GitHub: {}", e);
+        tracing::error!("Failed to push branch to {}: {}", provider_kind, e);
+        if provider_kind != HostingProviderKind::GitHub {
+            return Ok(ResponseJson(ApiResponse::error(
+                format!("Failed to push branch to {}: {}", provider_kind, e).as_str(),
+            )));
+        }
         let gh_e = GitHubServiceError::from(e);
         if gh_e.is_api_data() {
             return Ok(ResponseJson(ApiResponse::error_with_data(gh_e)));
@@ -798,19 +1156,19 @@ pub async fn create_github_pr(
     } else {
         target_branch
     };
-    // Create the PR using GitHub service
+    // Create the PR on the project's code host
     let pr_request = CreatePrRequest {
         title: request.title.clone(),
         body: request.body.clone(),
         head_branch: task_attempt.branch.clone(),
         base_branch: norm_target_branch_name.clone(),
+        draft: request.draft.unwrap_or(false),
     };
-    // Use GitService to get the remote URL, then create GitHubRepoInfo
-    let repo_info = deployment
+    let remote_url = deployment
         .git()
-        .get_github_repo_info(&project.git_repo_path)?;
+        .get_default_remote_url(&project.git_repo_path)?;
 
-    match github_service.create_pr(&repo_info, &pr_request).await {
+    match provider.create_pr(&remote_url, &pr_request).await {
         Ok(pr_info) => {
             // Update the task attempt with PR information
             if let Err(e) = Merge::create_pr(
@@ -819,6 +1177,7 @@ pub async fn create_github_pr(
                 &norm_target_branch_name,
                 pr_info.number,
                 &pr_info.url,
+                pr_info.is_draft,
             )
             .await
             {
@@ -831,11 +1190,12 @@ pub async fn create_github_pr(
             }
             deployment
                 .track_if_analytics_allowed(
-                    "github_pr_created",
+                    &format!("{provider_kind}_pr_created"),
                     serde_json::json!({
-                        "task_id": task.id.to_string(),
+                        "task_id": task_attempt.task_id.to_string(),
                         "project_id": project.id.to_string(),
                         "attempt_id": task_attempt.id.to_string(),
+                        "draft": pr_info.is_draft,
                     }),
                 )
                 .await;
@@ -844,21 +1204,127 @@ pub async fn create_github_pr(
         }
         Err(e) => {
             tracing::error!(
-                "Failed to create GitHub PR for attempt {}: {}",
+                "Failed to create {} PR for attempt {}: {}",
+                provider_kind,
                 task_attempt.id,
                 e
             );
-            if e.is_api_data() {
-                Ok(ResponseJson(ApiResponse::error_with_data(e)))
-            } else {
-                Ok(ResponseJson(ApiResponse::error(
-                    format!("Failed to create PR: {}", e).as_str(),
-                )))
-            }
+            Ok(ResponseJson(pr_error_response(e)))
         }
     }
 }
 
+/// GitHub errors the frontend prompts for (e.g. a missing token) are returned
+/// as data, anything else as a message
+fn pr_error_response(error: HostingError) -> ApiResponse<String, GitHubServiceError> {
+    match error {
+        HostingError::GitHub(e) if e.is_api_data() => ApiResponse::error_with_data(e),
+        e => ApiResponse::error(format!("Failed to create PR: {}", e).as_str()),
+    }
+}
+
+/// Merge the attempt's open PR on its code host. The task is moved to done
+/// like when the PR monitor sees the merge. Without a strategy in the request
+/// body or the project's merge settings, the host's default merge method is
+/// used.
+pub async fn merge_task_attempt_pr(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    payload: Option<Json<MergeTaskAttemptRequest>>,
+) -> Result<ResponseJson<ApiResponse<PullRequestInfo>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let Some(Merge::Pr(pr_merge)) =
+        Merge::find_latest_by_task_attempt_id(pool, task_attempt.id).await?
+    else {
+        return Err(ApiError::BadRequest(
+            "Task attempt has no pull request".to_string(),
+        ));
+    };
+    if !matches!(pr_merge.pr_info.status, MergeStatus::Open) {
+        return Err(ApiError::Conflict("Pull request is not open".to_string()));
+    }
+    if pr_merge.pr_info.is_draft {
+        return Err(ApiError::Conflict(
+            "Pull request is a draft; mark it ready for review first".to_string(),
+        ));
+    }
+
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let request = payload.map(|Json(request)| request).unwrap_or_default();
+    let options = resolve_merge_options(&deployment, &task, &task_attempt, request).await?;
+
+    let (project, provider) = attempt_hosting_provider(&deployment, &task_attempt).await?;
+    let pr_info = provider
+        .merge_pr(&pr_merge.pr_info.url, pr_merge.pr_info.number, &options)
+        .await?;
+    Merge::update_status(
+        pool,
+        pr_merge.id,
+        pr_info.status.clone(),
+        pr_info.merge_commit_sha.clone(),
+    )
+    .await?;
+
+    if matches!(pr_info.status, MergeStatus::Merged) {
+        Task::update_status(pool, task_attempt.task_id, TaskStatus::Done).await?;
+        deployment
+            .track_if_analytics_allowed(
+                "pr_merged",
+                serde_json::json!({
+                    "task_id": task_attempt.task_id.to_string(),
+                    "task_attempt_id": task_attempt.id.to_string(),
+                    "project_id": project.id.to_string(),
+                    "provider": provider.kind().to_string(),
+                    "strategy": options.strategy.map(|strategy| strategy.to_string()),
+                }),
+            )
+            .await;
+    }
+
+    Ok(ResponseJson(ApiResponse::success(pr_info)))
+}
+
+/// Take the attempt's draft PR out of draft so reviewers are asked for a review
+pub async fn mark_task_attempt_pr_ready(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<PullRequestInfo>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let Some(Merge::Pr(pr_merge)) =
+        Merge::find_latest_by_task_attempt_id(pool, task_attempt.id).await?
+    else {
+        return Err(ApiError::BadRequest(
+            "Task attempt has no pull request".to_string(),
+        ));
+    };
+    if !matches!(pr_merge.pr_info.status, MergeStatus::Open) {
+        return Err(ApiError::Conflict("Pull request is not open".to_string()));
+    }
+
+    let (project, provider) = attempt_hosting_provider(&deployment, &task_attempt).await?;
+    let pr_info = provider
+        .mark_pr_ready(&pr_merge.pr_info.url, pr_merge.pr_info.number)
+        .await?;
+    Merge::update_draft(pool, pr_merge.id, pr_info.is_draft).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "pr_marked_ready",
+            serde_json::json!({
+                "task_id": task_attempt.task_id.to_string(),
+                "task_attempt_id": task_attempt.id.to_string(),
+                "project_id": project.id.to_string(),
+                "provider": provider.kind().to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(pr_info)))
+}
+
 #[derive(serde::Deserialize)]
 pub struct OpenEditorRequest {
     editor_type: Option<String>,
@@ -966,34 +1432,35 @@ pub async fn get_task_attempt_branch_status(
         .await
         .ok()
         .map(|is_clean| !is_clean);
-    let head_oid = {
-        let wt_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
-        let wt = wt_buf.as_path();
-        deployment.git().get_head_info(wt).ok().map(|h| h.oid)
-    };
-    // Detect conflicts and operation in progress (best-effort)
-    let (is_rebase_in_progress, conflicted_files, conflict_op) = {
-        let wt_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
-        let wt = wt_buf.as_path();
-        let in_rebase = deployment.git().is_rebase_in_progress(wt).unwrap_or(false);
-        let conflicts = deployment
-            .git()
-            .get_conflicted_files(wt)
-            .unwrap_or_default();
-        let op = if conflicts.is_empty() {
-            None
+    // Archived attempts have no worktree to inspect; report their branch
+    let (head_oid, is_rebase_in_progress, conflicted_files, conflict_op, change_counts) =
+        if task_attempt.archived_at.is_some() {
+            let head_oid = deployment
+                .git()
+                .get_branch_oid(&ctx.project.git_repo_path, &task_attempt.branch)
+                .ok();
+            (head_oid, false, Vec::new(), None, Some((0, 0)))
         } else {
-            deployment.git().detect_conflict_op(wt).unwrap_or(None)
+            let wt_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+            let wt = wt_buf.as_path();
+            let head_oid = deployment.git().get_head_info(wt).ok().map(|h| h.oid);
+            // Detect conflicts and operation in progress (best-effort)
+            let in_rebase = deployment.git().is_rebase_in_progress(wt).unwrap_or(false);
+            let conflicts = deployment
+                .git()
+                .get_conflicted_files(wt)
+                .unwrap_or_default();
+            let op = if conflicts.is_empty() {
+                None
+            } else {
+                deployment.git().detect_conflict_op(wt).unwrap_or(None)
+            };
+            let counts = deployment.git().get_worktree_change_counts(wt).ok();
+            (head_oid, in_rebase, conflicts, op, counts)
         };
-        (in_rebase, conflicts, op)
-    };
-    let (uncommitted_count, untracked_count) = {
-        let wt_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
-        let wt = wt_buf.as_path();
-        match deployment.git().get_worktree_change_counts(wt) {
-            Ok((a, b)) => (Some(a), Some(b)),
-            Err(_) => (None, None),
-        }
+    let (uncommitted_count, untracked_count) = match change_counts {
+        Some((a, b)) => (Some(a), Some(b)),
+        None => (None, None),
     };
 
     let target_branch_type = deployment
@@ -1010,10 +1477,7 @@ pub async fn get_task_attempt_branch_status(
             (Some(a), Some(b))
         }
         BranchType::Remote => {
-            let github_config = deployment.config().read().await.github.clone();
-            let token = github_config
-                .token()
-                .ok_or(ApiError::GitHubService(GitHubServiceError::TokenInvalid))?;
+            let token = project_hosting_token(&deployment, &ctx.project).await?;
             let (remote_commits_ahead, remote_commits_behind) =
                 deployment.git().get_remote_branch_status(
                     &ctx.project.git_repo_path,
@@ -1035,10 +1499,7 @@ pub async fn get_task_attempt_branch_status(
     })) = merges.first()
     {
         // check remote status if the attempt has an open PR
-        let github_config = deployment.config().read().await.github.clone();
-        let token = github_config
-            .token()
-            .ok_or(ApiError::GitHubService(GitHubServiceError::TokenInvalid))?;
+        let token = project_hosting_token(&deployment, &ctx.project).await?;
         let (remote_commits_ahead, remote_commits_behind) =
             deployment.git().get_remote_branch_status(
                 &ctx.project.git_repo_path,
@@ -1069,6 +1530,32 @@ pub async fn get_task_attempt_branch_status(
     Ok(ResponseJson(ApiResponse::success(branch_status)))
 }
 
+/// Whether each commit of the attempt branch that its base branch lacks is
+/// signed, newest first
+pub async fn get_task_attempt_commit_signatures(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<CommitSignature>>>, ApiError> {
+    let repo_path = attempt_repo_path(&deployment, &task_attempt).await?;
+    let signatures = deployment.git().get_commit_signatures(
+        &repo_path,
+        &task_attempt.branch,
+        &task_attempt.target_branch,
+    )?;
+    Ok(ResponseJson(ApiResponse::success(signatures)))
+}
+
+/// How far the attempt is behind its base branch, as last checked by the base
+/// branch watcher; null when its project has no rebase policy
+pub async fn get_task_attempt_base_status(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<AttemptBaseStatus>>>, ApiError> {
+    let status =
+        AttemptBaseStatus::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(status)))
+}
+
 #[derive(serde::Deserialize, Debug, TS)]
 pub struct ChangeTargetBranchRequest {
     pub new_target_branch: String,
@@ -1145,14 +1632,14 @@ pub async fn rebase_task_attempt(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<RebaseTaskAttemptRequest>,
-) -> Result<ResponseJson<ApiResponse<(), GitOperationError>>, ApiError> {
+) -> Result<ResponseJson<ApiResponse<RebaseTaskAttemptResponse, GitOperationError>>, ApiError> {
+    let strategy = payload.strategy.unwrap_or_default();
     let old_base_branch = payload
         .old_base_branch
         .unwrap_or(task_attempt.target_branch.clone());
     let new_base_branch = payload
         .new_base_branch
         .unwrap_or(task_attempt.target_branch.clone());
-    let github_config = deployment.config().read().await.github.clone();
 
     let pool = &deployment.db().pool;
 
@@ -1186,36 +1673,69 @@ pub async fn rebase_task_attempt(
 
     let worktree_path_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
     let worktree_path = worktree_path_buf.as_path();
+    let token = project_hosting_token(&deployment, &ctx.project).await.ok();
+
+    let result = match strategy {
+        RebaseStrategy::Rebase => deployment.git().rebase_branch(
+            &ctx.project.git_repo_path,
+            worktree_path,
+            &new_base_branch,
+            &old_base_branch,
+            &task_attempt.branch.clone(),
+            token,
+        ),
+        RebaseStrategy::Merge => deployment.git().merge_base_into_branch(
+            &ctx.project.git_repo_path,
+            worktree_path,
+            &new_base_branch,
+            token,
+        ),
+    };
+    let head_commit = match result {
+        Ok(head_commit) => head_commit,
+        Err(e) => {
+            use services::services::git::GitServiceError;
+            return match e {
+                GitServiceError::MergeConflicts(msg) => {
+                    let conflicted_files = deployment
+                        .git()
+                        .get_conflicted_files(worktree_path)
+                        .unwrap_or_default();
+                    Ok(ResponseJson(ApiResponse::<
+                        RebaseTaskAttemptResponse,
+                        GitOperationError,
+                    >::error_with_data(
+                        GitOperationError::MergeConflicts {
+                            message: msg,
+                            op: match strategy {
+                                RebaseStrategy::Rebase => ConflictOp::Rebase,
+                                RebaseStrategy::Merge => ConflictOp::Merge,
+                            },
+                            conflicted_files,
+                        },
+                    )))
+                }
+                GitServiceError::RebaseInProgress => Ok(ResponseJson(ApiResponse::<
+                    RebaseTaskAttemptResponse,
+                    GitOperationError,
+                >::error_with_data(
+                    GitOperationError::RebaseInProgress,
+                ))),
+                other => Err(ApiError::GitService(other)),
+            };
+        }
+    };
 
-    let result = deployment.git().rebase_branch(
+    let base_commit = deployment.git().get_base_commit(
         &ctx.project.git_repo_path,
-        worktree_path,
+        &task_attempt.branch,
         &new_base_branch,
-        &old_base_branch,
-        &task_attempt.branch.clone(),
-        github_config.token(),
-    );
-    if let Err(e) = result {
-        use services::services::git::GitServiceError;
-        return match e {
-            GitServiceError::MergeConflicts(msg) => Ok(ResponseJson(ApiResponse::<
-                (),
-                GitOperationError,
-            >::error_with_data(
-                GitOperationError::MergeConflicts {
-                    message: msg,
-                    op: ConflictOp::Rebase,
-                },
-            ))),
-            GitServiceError::RebaseInProgress => Ok(ResponseJson(ApiResponse::<
-                (),
-                GitOperationError,
-            >::error_with_data(
-                GitOperationError::RebaseInProgress,
-            ))),
-            other => Err(ApiError::GitService(other)),
-        };
-    }
+    )?;
+    let (commits_ahead, commits_behind) = deployment.git().get_branch_status(
+        &ctx.project.git_repo_path,
+        &task_attempt.branch,
+        &new_base_branch,
+    )?;
 
     deployment
         .track_if_analytics_allowed(
@@ -1224,11 +1744,20 @@ pub async fn rebase_task_attempt(
                 "task_id": task.id.to_string(),
                 "project_id": ctx.project.id.to_string(),
                 "attempt_id": task_attempt.id.to_string(),
+                "strategy": strategy,
             }),
         )
         .await;
 
-    Ok(ResponseJson(ApiResponse::success(())))
+    Ok(ResponseJson(ApiResponse::success(
+        RebaseTaskAttemptResponse {
+            strategy,
+            head_commit,
+            base_commit: base_commit.to_string(),
+            commits_ahead,
+            commits_behind,
+        },
+    )))
 }
 
 #[axum::debug_handler]
@@ -1256,11 +1785,8 @@ pub async fn delete_task_attempt_file(
     Query(query): Query<DeleteFileQuery>,
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
-    let container_ref = deployment
-        .container()
-        .ensure_container_exists(&task_attempt)
-        .await?;
-    let worktree_path = std::path::Path::new(&container_ref);
+    let worktree_buf = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let worktree_path = worktree_buf.as_path();
 
     // Use GitService to delete file and commit
     let _commit_id = deployment
@@ -1284,6 +1810,7 @@ pub async fn start_dev_server(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    ensure_not_archived(&task_attempt)?;
     let pool = &deployment.db().pool;
 
     // Get parent task
@@ -1330,16 +1857,17 @@ pub async fn start_dev_server(
         }
     }
 
-    if let Some(dev_server) = project.dev_script {
-        // TODO: Derive script language from system config
-        let executor_action = ExecutorAction::new(
-            ExecutorActionType::ScriptRequest(ScriptRequest {
-                script: dev_server,
-                language: ScriptRequestLanguage::Bash,
-                context: ScriptContext::DevServer,
-            }),
-            None,
-        );
+    // A dev server is a single long-running process, so only the first dev
+    // script runs
+    let dev_script = deployment
+        .container()
+        .project_script_requests(&project, ProjectScriptKind::Dev)
+        .await?
+        .into_iter()
+        .next();
+    if let Some(dev_script) = dev_script {
+        let executor_action =
+            ExecutorAction::new(ExecutorActionType::ScriptRequest(dev_script), None);
 
         deployment
             .container()
@@ -1369,6 +1897,71 @@ pub async fn start_dev_server(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+#[derive(Debug, Serialize, TS)]
+pub struct AttemptUsageSummary {
+    pub totals: UsageTotals,
+    /// Usage reported by each coding agent execution of the attempt
+    pub executions: Vec<AttemptUsage>,
+}
+
+pub async fn get_task_attempt_usage(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<AttemptUsageSummary>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let totals = AttemptUsage::totals_for_task_attempt(pool, task_attempt.id).await?;
+    let executions = AttemptUsage::find_by_task_attempt_id(pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(AttemptUsageSummary {
+        totals,
+        executions,
+    })))
+}
+
+/// Tool calls of the attempt's agents waiting for approval, oldest first
+pub async fn get_pending_approvals(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ApprovalRequest>>>, ApiError> {
+    let processes =
+        ExecutionProcess::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id, false)
+            .await?;
+    let process_ids: Vec<Uuid> = processes.iter().map(|process| process.id).collect();
+    let pending = deployment.approvals().pending_for_executions(&process_ids);
+    Ok(ResponseJson(ApiResponse::success(pending)))
+}
+
+/// Runs of the attempt that fell back to another executor profile after the
+/// model provider refused a run, oldest first
+pub async fn get_attempt_fallbacks(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ExecutionFallback>>>, ApiError> {
+    let fallbacks =
+        ExecutionFallback::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(fallbacks)))
+}
+
+/// Runs of the attempt that continued an agent session interrupted by a
+/// server restart, oldest first
+pub async fn get_attempt_resumptions(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ExecutionResumption>>>, ApiError> {
+    let resumptions =
+        ExecutionResumption::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id)
+            .await?;
+    Ok(ResponseJson(ApiResponse::success(resumptions)))
+}
+
+/// Tests run after the attempt's coding agent turns, oldest first
+pub async fn get_attempt_turn_tests(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TurnTestRun>>>, ApiError> {
+    let runs = TurnTestRun::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(runs)))
+}
+
 pub async fn get_task_attempt_children(
     Extension(task_attempt): Extension<TaskAttempt>,
     State(deployment): State<DeploymentImpl>,
@@ -1417,6 +2010,189 @@ pub async fn stop_task_attempt_execution(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+pub async fn archive_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if task_attempt.archived_at.is_some() {
+        return Err(ApiError::Conflict(
+            "Task attempt is already archived".to_string(),
+        ));
+    }
+
+    // Stop any running processes and tear down the worktree; logs and the branch remain
+    deployment.container().delete(&task_attempt).await?;
+    TaskAttempt::mark_worktree_deleted(pool, task_attempt.id).await?;
+    TaskAttempt::archive(pool, task_attempt.id).await?;
+    QueuedFollowUp::delete_by_task_attempt_id(pool, task_attempt.id).await?;
+
+    let task_attempt = TaskAttempt::find_by_id(pool, task_attempt.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_archived",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(task_attempt)))
+}
+
+pub async fn unarchive_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if task_attempt.archived_at.is_none() {
+        return Err(ApiError::Conflict(
+            "Task attempt is not archived".to_string(),
+        ));
+    }
+
+    TaskAttempt::unarchive(pool, task_attempt.id).await?;
+
+    let task_attempt = TaskAttempt::find_by_id(pool, task_attempt.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_unarchived",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(task_attempt)))
+}
+
+pub async fn get_task_attempt_labels(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Label>>>, ApiError> {
+    let labels = Label::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+pub async fn set_task_attempt_labels(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetLabels>,
+) -> Result<ResponseJson<ApiResponse<Vec<Label>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    ensure_labels_exist(&deployment, &payload.label_ids).await?;
+    Label::set_for_task_attempt(pool, task_attempt.id, &payload.label_ids).await?;
+    let labels = Label::find_by_task_attempt_id(pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+/// Running agent processes of an attempt; dev servers are left alone by pause/resume
+async fn running_agent_processes(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+) -> Result<Vec<ExecutionProcess>, ApiError> {
+    let processes =
+        ExecutionProcess::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id, false)
+            .await?;
+    Ok(processes
+        .into_iter()
+        .filter(|p| {
+            p.status == ExecutionProcessStatus::Running
+                && p.run_reason != ExecutionProcessRunReason::DevServer
+        })
+        .collect())
+}
+
+/// Suspend the attempt's running executor. The worktree and process stay in
+/// place, and follow-ups are rejected until the attempt is resumed.
+pub async fn pause_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if task_attempt.paused_at.is_some() {
+        return Err(ApiError::Conflict(
+            "Task attempt is already paused".to_string(),
+        ));
+    }
+
+    let processes = running_agent_processes(&deployment, &task_attempt).await?;
+    if processes.is_empty() {
+        return Err(ApiError::Conflict(
+            "Task attempt has no running execution to pause".to_string(),
+        ));
+    }
+    for process in &processes {
+        deployment.container().pause_execution(process).await?;
+    }
+    TaskAttempt::mark_paused(pool, task_attempt.id).await?;
+    for process in &processes {
+        deployment
+            .container()
+            .refresh_execution_timeout(process.id)
+            .await;
+    }
+
+    let task_attempt = TaskAttempt::find_by_id(pool, task_attempt.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_paused",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(task_attempt)))
+}
+
+pub async fn resume_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if task_attempt.paused_at.is_none() {
+        return Err(ApiError::Conflict("Task attempt is not paused".to_string()));
+    }
+
+    let processes = running_agent_processes(&deployment, &task_attempt).await?;
+    for process in &processes {
+        deployment.container().resume_execution(process).await?;
+    }
+    // Moves the processes' timeouts back by the time they were paused
+    TaskAttempt::clear_paused(pool, task_attempt.id).await?;
+    for process in &processes {
+        deployment
+            .container()
+            .refresh_execution_timeout(process.id)
+            .await;
+    }
+
+    let task_attempt = TaskAttempt::find_by_id(pool, task_attempt.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_resumed",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(task_attempt)))
+}
+
 #[derive(Debug, Serialize, TS)]
 pub struct AttachPrResponse {
     pub pr_attached: bool,
@@ -1476,6 +2252,7 @@ pub async fn attach_existing_pr(
             &task_attempt.target_branch,
             pr_info.number,
             &pr_info.url,
+            pr_info.is_draft,
         )
         .await?;
 
@@ -1515,6 +2292,12 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_attempt_id_router = Router::new()
         .route("/", get(get_task_attempt))
         .route("/follow-up", post(follow_up))
+        .route(
+            "/follow-up/queue",
+            get(follow_up_queue::get_follow_up_queue)
+                .post(follow_up_queue::enqueue_follow_up)
+                .delete(follow_up_queue::clear_follow_up_queue),
+        )
         .route("/run-agent-setup", post(run_agent_setup))
         .route(
             "/draft",
@@ -1524,29 +2307,157 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         )
         .route("/draft/queue", post(drafts::set_draft_queue))
         .route("/replace-process", post(replace_process))
+        .route("/fork", post(fork::fork_task_attempt))
+        .route("/forks", get(fork::get_attempt_forks))
         .route("/commit-info", get(get_commit_info))
         .route("/commit-compare", get(compare_commit_to_head))
         .route("/start-dev-server", post(start_dev_server))
         .route("/branch-status", get(get_task_attempt_branch_status))
+        .route("/base-status", get(get_task_attempt_base_status))
+        .route("/ci-checks", get(ci_checks::get_attempt_ci_checks))
+        .route(
+            "/ci-checks/refresh",
+            post(ci_checks::refresh_attempt_ci_checks),
+        )
+        .route(
+            "/commit-signatures",
+            get(get_task_attempt_commit_signatures),
+        )
         .route("/diff/ws", get(stream_task_attempt_diff_ws))
+        .route("/diff/files", get(diff_files::get_attempt_diff_files))
+        .route("/diff/file", get(diff_files::get_attempt_diff_file))
         .route("/merge", post(merge_task_attempt))
+        .route(
+            "/partial-commit",
+            post(partial_commit::commit_selected_changes),
+        )
         .route("/push", post(push_task_attempt_branch))
+        .route("/push/force", post(force_push_task_attempt_branch))
         .route("/rebase", post(rebase_task_attempt))
+        .route("/conflicts", get(conflicts::get_attempt_conflicts))
+        .route(
+            "/conflicts/resolve",
+            post(conflicts::resolve_attempt_conflicts),
+        )
+        .route(
+            "/conflicts/continue",
+            post(conflicts::continue_attempt_conflicts),
+        )
+        .route(
+            "/conflicts/agent",
+            post(conflicts::resolve_attempt_conflicts_with_agent),
+        )
         .route("/conflicts/abort", post(abort_conflicts_task_attempt))
         .route("/pr", post(create_github_pr))
         .route("/pr/attach", post(attach_existing_pr))
+        .route("/pr/merge", post(merge_task_attempt_pr))
+        .route("/pr/ready", post(mark_task_attempt_pr_ready))
+        .route(
+            "/pr/comments",
+            get(review_comments::get_attempt_review_comments),
+        )
+        .route(
+            "/pr/comments/refresh",
+            post(review_comments::refresh_attempt_review_comments),
+        )
+        .route(
+            "/pr/comments/follow-up",
+            post(review_comments::send_review_comments),
+        )
         .route("/open-editor", post(open_task_attempt_in_editor))
         .route("/delete-file", post(delete_task_attempt_file))
         .route("/children", get(get_task_attempt_children))
+        .route("/stack", get(get_task_attempt_stack))
+        .route("/usage", get(get_task_attempt_usage))
+        .route("/approvals", get(get_pending_approvals))
+        .route("/fallbacks", get(get_attempt_fallbacks))
+        .route("/resumptions", get(get_attempt_resumptions))
+        .route("/turn-tests", get(get_attempt_turn_tests))
+        .route("/plan", get(plan::get_attempt_plan))
+        .route("/plan/approve", post(plan::approve_attempt_plan))
+        .route(
+            "/proposed-tasks",
+            get(proposed_tasks::get_attempt_proposed_tasks),
+        )
+        .route("/assign", post(assign_task_attempt))
+        .route("/unassign", post(unassign_task_attempt))
+        .route("/export", get(export::export_task_attempt))
+        .route(
+            "/repositories/diff",
+            get(repositories::get_repositories_diff),
+        )
+        .route(
+            "/repositories/commit",
+            post(repositories::commit_repositories),
+        )
+        .route(
+            "/repositories/pr",
+            post(repositories::create_repository_prs),
+        )
+        .route(
+            "/labels",
+            get(get_task_attempt_labels).put(set_task_attempt_labels),
+        )
+        .route("/timeout", put(set_task_attempt_timeout))
         .route("/stop", post(stop_task_attempt_execution))
+        .route("/archive", post(archive_task_attempt))
+        .route("/unarchive", post(unarchive_task_attempt))
+        .route("/pause", post(pause_task_attempt))
+        .route("/resume", post(resume_task_attempt))
         .route("/change-target-branch", post(change_target_branch))
+        .route(
+            "/checkpoints",
+            get(checkpoints::get_checkpoints).post(checkpoints::create_checkpoint),
+        )
+        .route("/worktree", get(worktree::get_attempt_worktree))
+        .route("/worktree/repair", post(worktree::repair_attempt_worktree))
+        .route(
+            "/worktree/relocate",
+            post(worktree::relocate_attempt_worktree),
+        )
         .layer(from_fn_with_state(
             deployment.clone(),
             load_task_attempt_middleware,
-        ));
+        ))
+        // Added after the attempt loader layer: these routes carry a second path
+        // parameter, so the handlers load the attempt themselves
+        .route(
+            "/checkpoints/{checkpoint_id}",
+            delete(checkpoints::delete_checkpoint),
+        )
+        .route(
+            "/checkpoints/{checkpoint_id}/resume",
+            post(checkpoints::resume_from_checkpoint),
+        )
+        .route(
+            "/follow-up/queue/{queued_follow_up_id}",
+            delete(follow_up_queue::delete_queued_follow_up),
+        )
+        .route(
+            "/proposed-tasks/{proposed_task_id}/accept",
+            post(proposed_tasks::accept_proposed_task),
+        )
+        .route(
+            "/proposed-tasks/{proposed_task_id}/dismiss",
+            post(proposed_tasks::dismiss_proposed_task),
+        );
 
     let task_attempts_router = Router::new()
         .route("/", get(get_task_attempts).post(create_task_attempt))
+        .route("/compare", get(compare::compare_task_attempts))
+        .route(
+            "/comparison-groups",
+            get(comparison_groups::get_comparison_groups)
+                .post(comparison_groups::create_comparison_group),
+        )
+        .route(
+            "/comparison-groups/{group_id}",
+            get(comparison_groups::get_comparison_group),
+        )
+        .route(
+            "/comparison-groups/{group_id}/winner",
+            post(comparison_groups::pick_comparison_winner),
+        )
         .nest("/{id}", task_attempt_id_router);
 
     Router::new().nest("/task-attempts", task_attempts_router)
@@ -0,0 +1,193 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    checkpoint::{Checkpoint, CreateCheckpoint},
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    task_attempt::{TaskAttempt, TaskAttemptError},
+};
+use deployment::Deployment;
+use executors::profile::ExecutorProfileId;
+use serde::Deserialize;
+use services::services::container::ContainerService;
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::task_attempts::util::{ensure_worktree_path, start_attempt_at_commit},
+};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateCheckpointRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ResumeFromCheckpointRequest {
+    /// Prompt sent to the resumed session; falls back to the task prompt when empty
+    pub prompt: Option<String>,
+    /// Optional variant override
+    pub variant: Option<String>,
+}
+
+pub async fn get_checkpoints(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Checkpoint>>>, ApiError> {
+    let checkpoints =
+        Checkpoint::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(checkpoints)))
+}
+
+#[axum::debug_handler]
+pub async fn create_checkpoint(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateCheckpointRequest>,
+) -> Result<ResponseJson<ApiResponse<Checkpoint>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return Err(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+            "Checkpoint name cannot be empty".to_string(),
+        )));
+    }
+
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+
+    // Only committed state is captured, so refuse to checkpoint a dirty worktree
+    if !deployment
+        .container()
+        .is_container_clean(&task_attempt)
+        .await?
+    {
+        return Err(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+            "Worktree has uncommitted changes; wait for the agent to finish or commit them first"
+                .to_string(),
+        )));
+    }
+
+    let head_info = deployment.git().get_head_info(&worktree_path)?;
+    let latest_coding_agent = ExecutionProcess::find_latest_by_task_attempt_and_run_reason(
+        pool,
+        task_attempt.id,
+        &ExecutionProcessRunReason::CodingAgent,
+    )
+    .await?;
+    let session_id =
+        ExecutionProcess::find_latest_session_id_by_task_attempt(pool, task_attempt.id).await?;
+
+    let checkpoint = Checkpoint::create(
+        pool,
+        task_attempt.id,
+        &CreateCheckpoint {
+            name: name.to_string(),
+            commit_oid: head_info.oid,
+            execution_process_id: latest_coding_agent.map(|p| p.id),
+            session_id,
+        },
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_checkpoint_created",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+                "checkpoint_id": checkpoint.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(checkpoint)))
+}
+
+pub async fn delete_checkpoint(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_attempt_id, checkpoint_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let (_, checkpoint) = load_checkpoint(&deployment, task_attempt_id, checkpoint_id).await?;
+    Checkpoint::delete(&deployment.db().pool, checkpoint.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Start a new attempt for the same task whose worktree is reset to the
+/// checkpoint commit and whose agent continues the checkpointed session.
+#[axum::debug_handler]
+pub async fn resume_from_checkpoint(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_attempt_id, checkpoint_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<ResumeFromCheckpointRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let (task_attempt, checkpoint) =
+        load_checkpoint(&deployment, task_attempt_id, checkpoint_id).await?;
+
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let base_profile_id =
+        ExecutionProcess::latest_executor_profile_for_attempt(pool, task_attempt.id).await?;
+    let executor_profile_id = ExecutorProfileId {
+        executor: base_profile_id.executor,
+        variant: payload.variant.or(base_profile_id.variant),
+    };
+    let prompt = payload
+        .prompt
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| task.to_prompt());
+
+    let new_attempt = start_attempt_at_commit(
+        &deployment,
+        &task,
+        &task_attempt,
+        &executor_profile_id,
+        &checkpoint.commit_oid,
+        checkpoint.session_id.clone(),
+        prompt,
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_resumed_from_checkpoint",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "source_attempt_id": task_attempt.id.to_string(),
+                "attempt_id": new_attempt.id.to_string(),
+                "checkpoint_id": checkpoint.id.to_string(),
+                "executor": &executor_profile_id.executor,
+                "variant": &executor_profile_id.variant,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(new_attempt)))
+}
+
+/// Load the attempt and one of its checkpoints from the path parameters.
+async fn load_checkpoint(
+    deployment: &DeploymentImpl,
+    task_attempt_id: Uuid,
+    checkpoint_id: Uuid,
+) -> Result<(TaskAttempt, Checkpoint), ApiError> {
+    let pool = &deployment.db().pool;
+    let task_attempt = TaskAttempt::find_by_id(pool, task_attempt_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let checkpoint = Checkpoint::find_by_id(pool, checkpoint_id)
+        .await?
+        .filter(|c| c.task_attempt_id == task_attempt.id)
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+            "Checkpoint not found".to_string(),
+        )))?;
+    Ok((task_attempt, checkpoint))
+}
@@ -0,0 +1,40 @@
+use axum::{Extension, extract::State, response::Json as ResponseJson};
+use db::models::{ci_check::CiCheck, task_attempt::TaskAttempt};
+use deployment::Deployment;
+use services::services::ci_checks;
+use utils::response::ApiResponse;
+
+use crate::{
+    DeploymentImpl, error::ApiError, routes::task_attempts::util::attempt_hosting_provider,
+};
+
+/// CI checks of the attempt's pushed branch, by name. They are fetched from
+/// the code host while the attempt has an open pull request.
+pub async fn get_attempt_ci_checks(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<CiCheck>>>, ApiError> {
+    let checks = CiCheck::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(checks)))
+}
+
+/// Fetch the CI checks of the attempt's pushed branch now, e.g. for a branch
+/// without a pull request, which isn't polled
+pub async fn refresh_attempt_ci_checks(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<CiCheck>>>, ApiError> {
+    let (project, provider) = attempt_hosting_provider(&deployment, &task_attempt).await?;
+    let remote_url = deployment
+        .git()
+        .get_default_remote_url(&project.git_repo_path)?;
+    let checks = ci_checks::sync_ci_checks(
+        &deployment.db().pool,
+        provider.as_ref(),
+        &remote_url,
+        task_attempt.id,
+        &task_attempt.branch,
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(checks)))
+}
@@ -0,0 +1,155 @@
+use std::path::Path;
+
+use axum::{
+    extract::{Query, State},
+    response::Json as ResponseJson,
+};
+use db::models::task_attempt::{TaskAttempt, TaskAttemptError};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::git::{DiffTarget, GitCommitSummary, GitService};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::{
+    diff::{Diff, compute_line_change_counts},
+    response::ApiResponse,
+};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize)]
+pub struct CompareAttemptsQuery {
+    pub a: Uuid,
+    pub b: Uuid,
+}
+
+#[derive(Debug, Default, Serialize, TS)]
+pub struct DiffSummaryStats {
+    pub files_changed: usize,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct AttemptComparisonSide {
+    pub attempt_id: Uuid,
+    pub branch: String,
+    pub target_branch: String,
+    /// Commits on the attempt branch that are not on its target branch
+    pub commits: Vec<GitCommitSummary>,
+    /// Paths changed relative to the target branch
+    pub files_changed: Vec<String>,
+    pub stats: DiffSummaryStats,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct AttemptComparison {
+    pub task_id: Uuid,
+    pub a: AttemptComparisonSide,
+    pub b: AttemptComparisonSide,
+    /// File diffs going from attempt `a`'s branch to attempt `b`'s branch
+    pub diffs: Vec<Diff>,
+    pub stats: DiffSummaryStats,
+}
+
+/// Compare the committed state of two attempts of the same task.
+pub async fn compare_task_attempts(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<CompareAttemptsQuery>,
+) -> Result<ResponseJson<ApiResponse<AttemptComparison>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if query.a == query.b {
+        return Err(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+            "Cannot compare an attempt with itself".to_string(),
+        )));
+    }
+
+    let attempt_a = TaskAttempt::find_by_id(pool, query.a)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let attempt_b = TaskAttempt::find_by_id(pool, query.b)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    if attempt_a.task_id != attempt_b.task_id {
+        return Err(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+            "Attempts belong to different tasks".to_string(),
+        )));
+    }
+
+    let project = attempt_a
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let repo_path = project.git_repo_path.as_path();
+
+    let diffs = deployment.git().get_diffs(
+        DiffTarget::Branch {
+            repo_path,
+            branch_name: &attempt_b.branch,
+            base_branch: &attempt_a.branch,
+        },
+        None,
+    )?;
+    let stats = summarize_diffs(&diffs);
+
+    let a = comparison_side(&deployment, repo_path, &attempt_a)?;
+    let b = comparison_side(&deployment, repo_path, &attempt_b)?;
+
+    Ok(ResponseJson(ApiResponse::success(AttemptComparison {
+        task_id: attempt_a.task_id,
+        a,
+        b,
+        diffs,
+        stats,
+    })))
+}
+
+fn comparison_side(
+    deployment: &DeploymentImpl,
+    repo_path: &Path,
+    attempt: &TaskAttempt,
+) -> Result<AttemptComparisonSide, ApiError> {
+    let commits =
+        deployment
+            .git()
+            .get_branch_commits(repo_path, &attempt.branch, &attempt.target_branch)?;
+    let diffs = deployment.git().get_diffs(
+        DiffTarget::Branch {
+            repo_path,
+            branch_name: &attempt.branch,
+            base_branch: &attempt.target_branch,
+        },
+        None,
+    )?;
+
+    Ok(AttemptComparisonSide {
+        attempt_id: attempt.id,
+        branch: attempt.branch.clone(),
+        target_branch: attempt.target_branch.clone(),
+        commits,
+        files_changed: diffs.iter().map(GitService::diff_path).collect(),
+        stats: summarize_diffs(&diffs),
+    })
+}
+
+pub(crate) fn summarize_diffs(diffs: &[Diff]) -> DiffSummaryStats {
+    diffs
+        .iter()
+        .fold(DiffSummaryStats::default(), |mut stats, diff| {
+            let (additions, deletions) = match (diff.additions, diff.deletions) {
+                (Some(additions), Some(deletions)) => (additions, deletions),
+                _ => compute_line_change_counts(
+                    diff.old_content.as_deref().unwrap_or(""),
+                    diff.new_content.as_deref().unwrap_or(""),
+                ),
+            };
+            stats.files_changed += 1;
+            stats.additions += additions;
+            stats.deletions += deletions;
+            stats
+        })
+}
@@ -0,0 +1,285 @@
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+};
+use chrono::Utc;
+use db::models::{
+    attempt_usage::{AttemptUsage, UsageTotals},
+    comparison_group::ComparisonGroup,
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    task::Task,
+    task_attempt::{CreateTaskAttempt, TaskAttempt},
+};
+use deployment::Deployment;
+use executors::profile::{ExecutorConfigs, ExecutorProfileId, ProfileError};
+use serde::{Deserialize, Serialize};
+use services::services::{container::ContainerService, git::DiffTarget};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::task_attempts::{
+        compare::{DiffSummaryStats, summarize_diffs},
+        ensure_dependencies_done, validate_execution_timeout,
+    },
+};
+
+/// Most attempts one comparison can start
+const MAX_COMPARISON_ATTEMPTS: usize = 8;
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateComparisonGroup {
+    pub task_id: Uuid,
+    pub base_branch: String,
+    /// One attempt is started per profile, in this order
+    pub executor_profile_ids: Vec<ExecutorProfileId>,
+    /// Cancel runs of the attempts after this many minutes (overrides the project setting)
+    #[serde(default)]
+    #[ts(optional)]
+    pub execution_timeout_minutes: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ComparisonGroupsQuery {
+    pub task_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct PickComparisonWinner {
+    pub task_attempt_id: Uuid,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ComparisonAttemptSummary {
+    pub attempt: TaskAttempt,
+    pub executor_profile_id: ExecutorProfileId,
+    /// Status of the latest coding agent run; null before the attempt starts
+    pub status: Option<ExecutionProcessStatus>,
+    /// Changes on the attempt branch relative to its target branch; null
+    /// while the branch does not exist yet
+    pub diff: Option<DiffSummaryStats>,
+    /// Whether the latest test script run passed; null when none finished
+    pub tests_passed: Option<bool>,
+    pub usage: UsageTotals,
+    /// Time spent in coding agent runs, including the one running
+    pub duration_seconds: i64,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ComparisonGroupSummary {
+    pub group: ComparisonGroup,
+    pub attempts: Vec<ComparisonAttemptSummary>,
+}
+
+/// Start one attempt of a task per executor profile, at the same time and
+/// from the same base branch, and group them for comparison
+pub async fn create_comparison_group(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateComparisonGroup>,
+) -> Result<ResponseJson<ApiResponse<ComparisonGroupSummary>>, ApiError> {
+    let profiles = &payload.executor_profile_ids;
+    if profiles.len() < 2 || profiles.len() > MAX_COMPARISON_ATTEMPTS {
+        return Err(ApiError::BadRequest(format!(
+            "A comparison needs between 2 and {MAX_COMPARISON_ATTEMPTS} executor profiles"
+        )));
+    }
+    let configs = ExecutorConfigs::get_cached();
+    for profile in profiles {
+        if configs.get_coding_agent(profile).is_none() {
+            return Err(ProfileError::NotFound(profile.clone()).into());
+        }
+    }
+    validate_execution_timeout(payload.execution_timeout_minutes)?;
+    let pool = &deployment.db().pool;
+    let task = Task::find_by_id(pool, payload.task_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    ensure_dependencies_done(&deployment, &task).await?;
+
+    let mut attempts = Vec::with_capacity(profiles.len());
+    for profile in profiles {
+        let attempt_id = Uuid::new_v4();
+        let branch = deployment
+            .container()
+            .git_branch_from_task_attempt(&attempt_id, &task, &profile.executor)
+            .await?;
+        let attempt = TaskAttempt::create(
+            pool,
+            &CreateTaskAttempt {
+                executor: profile.executor,
+                base_branch: payload.base_branch.clone(),
+                branch,
+            },
+            attempt_id,
+            task.id,
+        )
+        .await?;
+        if payload.execution_timeout_minutes.is_some() {
+            TaskAttempt::set_execution_timeout(pool, attempt.id, payload.execution_timeout_minutes)
+                .await?;
+        }
+        attempts.push((attempt.id, profile.clone()));
+    }
+    let group = ComparisonGroup::create(pool, task.id, &attempts).await?;
+
+    for (attempt_id, profile) in &attempts {
+        let attempt = TaskAttempt::find_by_id(pool, *attempt_id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        if let Err(err) = deployment
+            .container()
+            .queue_or_start_attempt(&attempt, profile.clone())
+            .await
+        {
+            tracing::error!("Failed to start comparison attempt {}: {}", attempt.id, err);
+        }
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "comparison_group_started",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "comparison_group_id": group.id.to_string(),
+                "attempt_count": attempts.len(),
+                "executors": profiles.iter().map(|profile| profile.to_string()).collect::<Vec<_>>(),
+            }),
+        )
+        .await;
+
+    let summary = summarize_group(&deployment, group).await?;
+    Ok(ResponseJson(ApiResponse::success(summary)))
+}
+
+/// The comparison groups of a task, newest first
+pub async fn get_comparison_groups(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ComparisonGroupsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ComparisonGroup>>>, ApiError> {
+    let groups = ComparisonGroup::find_by_task_id(&deployment.db().pool, query.task_id).await?;
+    Ok(ResponseJson(ApiResponse::success(groups)))
+}
+
+/// Where each attempt of the group stands: run status, diff size, tests,
+/// cost and time spent
+pub async fn get_comparison_group(
+    State(deployment): State<DeploymentImpl>,
+    Path(group_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<ComparisonGroupSummary>>, ApiError> {
+    let group = ComparisonGroup::find_by_id(&deployment.db().pool, group_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let summary = summarize_group(&deployment, group).await?;
+    Ok(ResponseJson(ApiResponse::success(summary)))
+}
+
+/// Record the attempt the user picked. The other attempts are left as they
+/// are, to be archived or deleted by the user.
+pub async fn pick_comparison_winner(
+    State(deployment): State<DeploymentImpl>,
+    Path(group_id): Path<Uuid>,
+    Json(payload): Json<PickComparisonWinner>,
+) -> Result<ResponseJson<ApiResponse<ComparisonGroup>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let group = ComparisonGroup::find_by_id(pool, group_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let members = ComparisonGroup::find_attempts(pool, group.id).await?;
+    if !members
+        .iter()
+        .any(|member| member.task_attempt_id == payload.task_attempt_id)
+    {
+        return Err(ApiError::BadRequest(
+            "Attempt is not part of this comparison".to_string(),
+        ));
+    }
+    ComparisonGroup::set_winner(pool, group.id, payload.task_attempt_id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "comparison_winner_picked",
+            serde_json::json!({
+                "comparison_group_id": group.id.to_string(),
+                "attempt_id": payload.task_attempt_id.to_string(),
+            }),
+        )
+        .await;
+
+    let group = ComparisonGroup::find_by_id(pool, group.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    Ok(ResponseJson(ApiResponse::success(group)))
+}
+
+async fn summarize_group(
+    deployment: &DeploymentImpl,
+    group: ComparisonGroup,
+) -> Result<ComparisonGroupSummary, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = Task::find_by_id(pool, group.task_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let mut attempts = Vec::new();
+    for member in ComparisonGroup::find_attempts(pool, group.id).await? {
+        let Some(attempt) = TaskAttempt::find_by_id(pool, member.task_attempt_id).await? else {
+            continue;
+        };
+        let processes = ExecutionProcess::find_by_task_attempt_id(pool, attempt.id, false).await?;
+        let agent_runs = processes
+            .iter()
+            .filter(|process| process.run_reason == ExecutionProcessRunReason::CodingAgent);
+        let now = Utc::now();
+        let duration_seconds = agent_runs
+            .clone()
+            .map(|process| {
+                (process.completed_at.unwrap_or(now) - process.started_at)
+                    .num_seconds()
+                    .max(0)
+            })
+            .sum();
+        let status = agent_runs.last().map(|process| process.status.clone());
+        let tests_passed = processes
+            .iter()
+            .filter(|process| process.run_reason == ExecutionProcessRunReason::TestScript)
+            .filter_map(|process| match process.status {
+                ExecutionProcessStatus::Completed => Some(true),
+                ExecutionProcessStatus::Failed => Some(false),
+                _ => None,
+            })
+            .next_back();
+        let diff = deployment
+            .git()
+            .get_diffs(
+                DiffTarget::Branch {
+                    repo_path: &project.git_repo_path,
+                    branch_name: &attempt.branch,
+                    base_branch: &attempt.target_branch,
+                },
+                None,
+            )
+            .ok()
+            .map(|diffs| summarize_diffs(&diffs));
+        let usage = AttemptUsage::totals_for_task_attempt(pool, attempt.id).await?;
+
+        attempts.push(ComparisonAttemptSummary {
+            attempt,
+            executor_profile_id: member.executor_profile_id.0,
+            status,
+            diff,
+            tests_passed,
+            usage,
+            duration_seconds,
+        });
+    }
+
+    Ok(ComparisonGroupSummary { group, attempts })
+}
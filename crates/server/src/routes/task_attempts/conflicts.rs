@@ -0,0 +1,224 @@
+use std::path::Path;
+
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    task_attempt::TaskAttempt,
+};
+use deployment::Deployment;
+use executors::actions::{
+    ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
+    coding_agent_initial::CodingAgentInitialRequest,
+};
+use serde::{Deserialize, Serialize};
+use services::services::{
+    conflicts::{self, ConflictedFile, FileResolution},
+    container::ContainerService,
+    git::{ConflictOp, GitServiceError},
+};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::task_attempts::{
+        GitOperationError,
+        util::{ensure_not_archived, ensure_not_paused, ensure_worktree_path},
+    },
+};
+
+/// The operation stopped on conflicts in the attempt's worktree, if any, and
+/// the files still conflicting
+#[derive(Debug, Serialize, TS)]
+pub struct AttemptConflicts {
+    pub op: Option<ConflictOp>,
+    pub files: Vec<ConflictedFile>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ResolveConflictsRequest {
+    pub files: Vec<FileResolution>,
+}
+
+fn read_conflicts(
+    deployment: &DeploymentImpl,
+    worktree_path: &Path,
+) -> Result<AttemptConflicts, ApiError> {
+    Ok(AttemptConflicts {
+        op: deployment.git().detect_conflict_op(worktree_path)?,
+        files: conflicts::conflicted_files(worktree_path)?,
+    })
+}
+
+/// Conflicted files of the attempt's worktree, split into merged and
+/// conflicting parts with the base, ours and theirs version of each
+pub async fn get_attempt_conflicts(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<AttemptConflicts>>, ApiError> {
+    // Archived attempts have no worktree, so nothing can be mid-conflict
+    if task_attempt.archived_at.is_some() {
+        return Ok(ResponseJson(ApiResponse::success(AttemptConflicts {
+            op: None,
+            files: Vec::new(),
+        })));
+    }
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    Ok(ResponseJson(ApiResponse::success(read_conflicts(
+        &deployment,
+        &worktree_path,
+    )?)))
+}
+
+/// Apply a resolution to some of the conflicted files and stage them.
+/// Returns the conflicts left.
+pub async fn resolve_attempt_conflicts(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ResolveConflictsRequest>,
+) -> Result<ResponseJson<ApiResponse<AttemptConflicts>>, ApiError> {
+    ensure_not_archived(&task_attempt)?;
+    if payload.files.is_empty() {
+        return Err(ApiError::BadRequest("No files to resolve".to_string()));
+    }
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    conflicts::resolve_files(&worktree_path, &payload.files)?;
+    let remaining = read_conflicts(&deployment, &worktree_path)?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "conflicts_resolved",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+                "files": payload.files.len(),
+                "remaining": remaining.files.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(remaining)))
+}
+
+/// Complete the operation once every conflict is resolved. Returns the new
+/// HEAD commit, or the next conflicts when a later commit of a rebase or
+/// cherry-pick conflicts too.
+pub async fn continue_attempt_conflicts(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<String, GitOperationError>>, ApiError> {
+    ensure_not_archived(&task_attempt)?;
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let op = deployment.git().detect_conflict_op(&worktree_path)?;
+
+    let head_commit = match deployment.git().continue_conflicts(&worktree_path) {
+        Ok(head_commit) => head_commit,
+        Err(GitServiceError::MergeConflicts(message)) => {
+            let conflicted_files = deployment
+                .git()
+                .get_conflicted_files(&worktree_path)
+                .unwrap_or_default();
+            return Ok(ResponseJson(ApiResponse::error_with_data(
+                GitOperationError::MergeConflicts {
+                    message,
+                    op: op.unwrap_or(ConflictOp::Rebase),
+                    conflicted_files,
+                },
+            )));
+        }
+        Err(e) => return Err(ApiError::GitService(e)),
+    };
+
+    deployment
+        .track_if_analytics_allowed(
+            "conflicts_continued",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+                "op": op,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(head_commit)))
+}
+
+/// Hand the conflicts to the attempt's coding agent, continuing its session
+/// when the executor kept one. The agent resolves the files and completes
+/// the operation itself.
+pub async fn resolve_attempt_conflicts_with_agent(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    ensure_not_archived(&task_attempt)?;
+    ensure_not_paused(&task_attempt)?;
+    let pool = &deployment.db().pool;
+
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let files = deployment.git().get_conflicted_files(&worktree_path)?;
+    let op = match deployment.git().detect_conflict_op(&worktree_path)? {
+        Some(op) if !files.is_empty() => op,
+        _ => {
+            return Err(ApiError::BadRequest(
+                "Task attempt has no conflicts to resolve".to_string(),
+            ));
+        }
+    };
+
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let project = task
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let prompt = conflicts::resolve_conflicts_prompt(
+        &op,
+        &task_attempt.branch,
+        &task_attempt.target_branch,
+        &files,
+    );
+    let executor_profile_id =
+        ExecutionProcess::latest_executor_profile_for_attempt(pool, task_attempt.id).await?;
+    let latest_session_id =
+        ExecutionProcess::find_latest_session_id_by_task_attempt(pool, task_attempt.id).await?;
+
+    let action_type = match latest_session_id {
+        Some(session_id) => {
+            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt,
+                session_id,
+                executor_profile_id,
+            })
+        }
+        None => ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt: format!("{}\n\n{prompt}", task.to_prompt()),
+            executor_profile_id,
+        }),
+    };
+    let cleanup_action = deployment.container().cleanup_action(&project).await?;
+    let action = ExecutorAction::new(action_type, cleanup_action);
+
+    let execution_process = deployment
+        .container()
+        .start_execution(
+            &task_attempt,
+            &action,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "conflicts_sent_to_agent",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+                "op": op,
+                "files": files.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(execution_process)))
+}
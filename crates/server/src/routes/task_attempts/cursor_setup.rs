@@ -73,6 +73,8 @@ fi
             script: install_script,
             language: ScriptRequestLanguage::Bash,
             context: ScriptContext::SetupScript,
+            timeout_seconds: None,
+            retries: 0,
         };
 
         // Second action (chained): Login
@@ -81,6 +83,8 @@ fi
             script: login_script,
             language: ScriptRequestLanguage::Bash,
             context: ScriptContext::SetupScript,
+            timeout_seconds: None,
+            retries: 0,
         };
 
         // Chain them: install → login
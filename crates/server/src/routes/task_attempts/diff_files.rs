@@ -0,0 +1,67 @@
+use axum::{
+    Extension,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+};
+use db::models::task_attempt::TaskAttempt;
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::container::ContainerService;
+use utils::{
+    diff::{DiffFileHunks, DiffFilePage},
+    response::ApiResponse,
+};
+
+use crate::{DeploymentImpl, error::ApiError};
+
+const DEFAULT_PAGE_SIZE: usize = 100;
+const MAX_PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Deserialize)]
+pub struct DiffFilesQuery {
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DiffFileQuery {
+    /// Path the file is listed under
+    pub path: String,
+}
+
+/// Page of the files the attempt changes, ordered by path. Unlike the diff
+/// stream, only the files on the page are read, so attempts changing
+/// thousands of files can be listed.
+pub async fn get_attempt_diff_files(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<DiffFilesQuery>,
+) -> Result<ResponseJson<ApiResponse<DiffFilePage>>, ApiError> {
+    let offset = query.offset.unwrap_or(0);
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let page = deployment
+        .container()
+        .diff_files(&task_attempt, offset, limit)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(page)))
+}
+
+/// Hunks of one file the attempt changes. Binary and oversized files come
+/// back flagged, without hunks.
+pub async fn get_attempt_diff_file(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<DiffFileQuery>,
+) -> Result<ResponseJson<ApiResponse<DiffFileHunks>>, ApiError> {
+    let hunks = deployment
+        .container()
+        .diff_file_hunks(&task_attempt, &query.path)
+        .await?
+        .ok_or_else(|| {
+            ApiError::BadRequest(format!("{} is not changed by this attempt", query.path))
+        })?;
+    Ok(ResponseJson(ApiResponse::success(hunks)))
+}
@@ -0,0 +1,336 @@
+use std::{collections::BTreeMap, fmt::Write as _};
+
+use axum::{
+    Extension,
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    task::Task,
+    task_attempt::TaskAttempt,
+};
+use deployment::Deployment;
+use executors::{
+    actions::ExecutorActionType,
+    logs::{
+        NormalizedEntry, NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch,
+    },
+    profile::ExecutorProfileId,
+};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    container::ContainerService,
+    git::{DiffTarget, GitService},
+};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::{
+    diff::{Diff, create_unified_diff},
+    log_msg::LogMsg,
+};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Md,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// One coding agent run (initial request or follow-up) of an attempt
+#[derive(Debug, Serialize, TS)]
+pub struct TranscriptExecution {
+    pub execution_process_id: Uuid,
+    pub status: ExecutionProcessStatus,
+    pub is_follow_up: bool,
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    pub prompt: Option<String>,
+    /// Normalized conversation: agent messages, tool calls, errors
+    pub entries: Vec<NormalizedEntry>,
+    pub before_head_commit: Option<String>,
+    pub after_head_commit: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct AttemptTranscript {
+    pub task: Task,
+    pub attempt: TaskAttempt,
+    pub executions: Vec<TranscriptExecution>,
+    /// Changes on the attempt branch relative to its target branch
+    pub diffs: Vec<Diff>,
+    pub exported_at: DateTime<Utc>,
+}
+
+/// Download a self-contained transcript of the attempt as JSON or Markdown.
+pub async fn export_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, ApiError> {
+    let transcript = build_transcript(&deployment, task_attempt).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_exported",
+            serde_json::json!({
+                "attempt_id": transcript.attempt.id.to_string(),
+                "format": match query.format {
+                    ExportFormat::Json => "json",
+                    ExportFormat::Md => "md",
+                },
+            }),
+        )
+        .await;
+
+    let (body, content_type, extension) = match query.format {
+        ExportFormat::Json => (
+            serde_json::to_string_pretty(&transcript).map_err(std::io::Error::other)?,
+            "application/json",
+            "json",
+        ),
+        ExportFormat::Md => (
+            render_markdown(&transcript),
+            "text/markdown; charset=utf-8",
+            "md",
+        ),
+    };
+    let disposition = format!(
+        "attachment; filename=\"attempt-{}.{extension}\"",
+        transcript.attempt.id
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+async fn build_transcript(
+    deployment: &DeploymentImpl,
+    task_attempt: TaskAttempt,
+) -> Result<AttemptTranscript, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let project = task
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let processes = ExecutionProcess::find_by_task_attempt_id(pool, task_attempt.id, false).await?;
+    let mut executions = Vec::new();
+    for process in processes {
+        if process.run_reason != ExecutionProcessRunReason::CodingAgent {
+            continue;
+        }
+        let (prompt, executor_profile_id, is_follow_up) =
+            match process.executor_action().map(|action| action.typ()) {
+                Ok(ExecutorActionType::CodingAgentInitialRequest(request)) => (
+                    Some(request.prompt.clone()),
+                    Some(request.executor_profile_id.clone()),
+                    false,
+                ),
+                Ok(ExecutorActionType::CodingAgentFollowUpRequest(request)) => (
+                    Some(request.prompt.clone()),
+                    Some(request.executor_profile_id.clone()),
+                    true,
+                ),
+                _ => (None, None, false),
+            };
+        let entries = collect_entries(deployment, process.id).await;
+
+        executions.push(TranscriptExecution {
+            execution_process_id: process.id,
+            status: process.status,
+            is_follow_up,
+            executor_profile_id,
+            prompt,
+            entries,
+            before_head_commit: process.before_head_commit,
+            after_head_commit: process.after_head_commit,
+            started_at: process.started_at,
+            completed_at: process.completed_at,
+        });
+    }
+
+    let diffs = deployment
+        .git()
+        .get_diffs(
+            DiffTarget::Branch {
+                repo_path: project.git_repo_path.as_path(),
+                branch_name: &task_attempt.branch,
+                base_branch: &task_attempt.target_branch,
+            },
+            None,
+        )
+        .unwrap_or_else(|e| {
+            tracing::warn!(
+                "Failed to compute diff for export of attempt {}: {}",
+                task_attempt.id,
+                e
+            );
+            Vec::new()
+        });
+
+    Ok(AttemptTranscript {
+        task,
+        attempt: task_attempt,
+        executions,
+        diffs,
+        exported_at: Utc::now(),
+    })
+}
+
+/// Snapshot of the normalized conversation of one execution. Running
+/// processes contribute what they've produced so far.
+async fn collect_entries(deployment: &DeploymentImpl, exec_id: Uuid) -> Vec<NormalizedEntry> {
+    let container = deployment.container();
+    let messages = if let Some(store) = container.get_msg_store_by_id(&exec_id).await {
+        store.get_history()
+    } else if let Some(stream) = container.stream_normalized_logs(&exec_id).await {
+        stream
+            .filter_map(|msg| async move { msg.ok() })
+            .collect()
+            .await
+    } else {
+        Vec::new()
+    };
+
+    // Later patches replace earlier versions of the same entry
+    let mut entries = BTreeMap::new();
+    for msg in &messages {
+        if let LogMsg::JsonPatch(patch) = msg
+            && let Some((index, entry)) = extract_normalized_entry_from_patch(patch)
+        {
+            entries.insert(index, entry);
+        }
+    }
+    entries.into_values().collect()
+}
+
+fn render_markdown(transcript: &AttemptTranscript) -> String {
+    let mut out = String::new();
+    let attempt = &transcript.attempt;
+
+    let _ = writeln!(out, "# {}\n", transcript.task.title);
+    if let Some(description) = transcript
+        .task
+        .description
+        .as_deref()
+        .filter(|d| !d.trim().is_empty())
+    {
+        let _ = writeln!(out, "{}\n", description.trim());
+    }
+    let _ = writeln!(out, "- Attempt: `{}`", attempt.id);
+    let _ = writeln!(
+        out,
+        "- Branch: `{}` → `{}`",
+        attempt.branch, attempt.target_branch
+    );
+    let _ = writeln!(out, "- Executor: {}", attempt.executor);
+    let _ = writeln!(out, "- Exported: {}\n", transcript.exported_at.to_rfc3339());
+
+    for (i, execution) in transcript.executions.iter().enumerate() {
+        let kind = if execution.is_follow_up {
+            "Follow-up"
+        } else {
+            "Initial request"
+        };
+        let _ = write!(out, "## {}. {kind}", i + 1);
+        if let Some(profile) = &execution.executor_profile_id {
+            let _ = write!(out, " ({profile})");
+        }
+        let _ = writeln!(out, " — {:?}\n", execution.status);
+
+        if let Some(prompt) = &execution.prompt {
+            let _ = writeln!(out, "### Prompt\n");
+            let _ = writeln!(out, "{}\n", quote(prompt));
+        }
+        if !execution.entries.is_empty() {
+            let _ = writeln!(out, "### Conversation\n");
+        }
+        for entry in &execution.entries {
+            render_entry(&mut out, entry);
+        }
+    }
+
+    if !transcript.diffs.is_empty() {
+        let _ = writeln!(out, "## Changes\n");
+        for diff in &transcript.diffs {
+            let path = GitService::diff_path(diff);
+            let _ = writeln!(out, "### `{path}`\n");
+            if diff.content_omitted {
+                let _ = writeln!(out, "_Content omitted_\n");
+                continue;
+            }
+            let unified = create_unified_diff(
+                &path,
+                diff.old_content.as_deref().unwrap_or(""),
+                diff.new_content.as_deref().unwrap_or(""),
+            );
+            let _ = writeln!(out, "{}\n", code_block(&unified, "diff"));
+        }
+    }
+
+    out
+}
+
+fn render_entry(out: &mut String, entry: &NormalizedEntry) {
+    let content = entry.content.trim();
+    let _ = match &entry.entry_type {
+        NormalizedEntryType::UserMessage => writeln!(out, "**User:**\n\n{}\n", quote(content)),
+        NormalizedEntryType::UserFeedback { denied_tool } => writeln!(
+            out,
+            "**User feedback** (denied `{denied_tool}`):\n\n{}\n",
+            quote(content)
+        ),
+        NormalizedEntryType::AssistantMessage => writeln!(out, "**Assistant:**\n\n{content}\n"),
+        NormalizedEntryType::ToolUse { tool_name, .. } => {
+            writeln!(
+                out,
+                "**Tool `{tool_name}`:**\n\n{}\n",
+                code_block(content, "")
+            )
+        }
+        NormalizedEntryType::Thinking => writeln!(out, "_Thinking:_\n\n{}\n", quote(content)),
+        NormalizedEntryType::SystemMessage => writeln!(out, "_System:_ {content}\n"),
+        NormalizedEntryType::ErrorMessage { .. } => writeln!(out, "**Error:** {content}\n"),
+        NormalizedEntryType::Loading | NormalizedEntryType::NextAction { .. } => Ok(()),
+    };
+}
+
+fn quote(text: &str) -> String {
+    text.trim()
+        .lines()
+        .map(|line| format!("> {line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Fence `content`, using a fence longer than any backtick run inside it.
+fn code_block(content: &str, lang: &str) -> String {
+    let longest_run = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!("{fence}{lang}\n{}\n{fence}", content.trim_end())
+}
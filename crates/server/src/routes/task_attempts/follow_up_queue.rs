@@ -0,0 +1,90 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    queued_follow_up::{CreateQueuedFollowUp, QueuedFollowUp},
+    task_attempt::{TaskAttempt, TaskAttemptError},
+};
+use deployment::Deployment;
+use services::services::container::ContainerService;
+use sqlx::Error as SqlxError;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::task_attempts::util::ensure_not_archived};
+
+pub async fn get_follow_up_queue(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<QueuedFollowUp>>>, ApiError> {
+    let queue =
+        QueuedFollowUp::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(queue)))
+}
+
+/// Append a follow-up prompt to the attempt's queue. Queued prompts are sent
+/// one at a time, each when the previous turn finishes; if the attempt is
+/// idle the prompt is sent right away.
+pub async fn enqueue_follow_up(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateQueuedFollowUp>,
+) -> Result<ResponseJson<ApiResponse<Vec<QueuedFollowUp>>>, ApiError> {
+    ensure_not_archived(&task_attempt)?;
+    if payload.prompt.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Follow-up prompt cannot be empty".to_string(),
+        ));
+    }
+
+    let pool = &deployment.db().pool;
+    let queued = QueuedFollowUp::create(pool, task_attempt.id, &payload).await?;
+    let started = deployment
+        .container()
+        .start_queued_follow_up(&task_attempt)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "follow_up_queued",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+                "queued_follow_up_id": queued.id.to_string(),
+                "started_immediately": started,
+            }),
+        )
+        .await;
+
+    let queue = QueuedFollowUp::find_by_task_attempt_id(pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(queue)))
+}
+
+pub async fn delete_queued_follow_up(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_attempt_id, queued_follow_up_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<Vec<QueuedFollowUp>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let task_attempt = TaskAttempt::find_by_id(pool, task_attempt_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let queue = QueuedFollowUp::find_by_task_attempt_id(pool, task_attempt.id).await?;
+    if !queue.iter().any(|queued| queued.id == queued_follow_up_id) {
+        return Err(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+            "Queued follow-up not found".to_string(),
+        )));
+    }
+    QueuedFollowUp::delete(pool, queued_follow_up_id).await?;
+
+    let queue = QueuedFollowUp::find_by_task_attempt_id(pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(queue)))
+}
+
+pub async fn clear_follow_up_queue(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    QueuedFollowUp::delete_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
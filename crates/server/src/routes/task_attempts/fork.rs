@@ -0,0 +1,137 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    attempt_fork::AttemptFork,
+    execution_process::ExecutionProcess,
+    task_attempt::{TaskAttempt, TaskAttemptError},
+};
+use deployment::Deployment;
+use executors::{actions::ExecutorActionType, profile::ExecutorProfileId};
+use serde::{Deserialize, Serialize};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl, error::ApiError, routes::task_attempts::util::start_attempt_at_commit,
+};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ForkTaskAttemptRequest {
+    /// Coding agent run to fork at; its prompt is replaced by `prompt`
+    pub process_id: Uuid,
+    pub prompt: String,
+    /// Run the fork with another variant of the same agent
+    #[serde(default)]
+    #[ts(optional)]
+    pub variant: Option<String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct AttemptForks {
+    /// Where this attempt was forked from, if it was
+    pub forked_from: Option<AttemptFork>,
+    /// Attempts forked from this one
+    pub forks: Vec<AttemptFork>,
+}
+
+/// Fork the attempt's conversation at a past coding agent run into a new
+/// attempt of the same task. The new attempt's branch starts from the commit
+/// the attempt was at before that run, and its agent continues the session
+/// as it was then, with `prompt` in place of the original prompt. The
+/// original attempt is left untouched.
+pub async fn fork_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ForkTaskAttemptRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    if payload.prompt.trim().is_empty() {
+        return Err(ApiError::BadRequest("Prompt cannot be empty".to_string()));
+    }
+    let pool = &deployment.db().pool;
+    let process = ExecutionProcess::find_by_id(pool, payload.process_id)
+        .await?
+        .filter(|process| process.task_attempt_id == task_attempt.id)
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+            "Process does not belong to this attempt".to_string(),
+        )))?;
+    let original_profile = match process
+        .executor_action()
+        .map_err(|e| ApiError::TaskAttempt(TaskAttemptError::ValidationError(e.to_string())))?
+        .typ()
+    {
+        ExecutorActionType::CodingAgentInitialRequest(request) => {
+            request.executor_profile_id.clone()
+        }
+        ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+            request.executor_profile_id.clone()
+        }
+        _ => {
+            return Err(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+                "Only coding agent runs can be forked".to_string(),
+            )));
+        }
+    };
+    let executor_profile_id = ExecutorProfileId {
+        executor: original_profile.executor,
+        variant: payload.variant.or(original_profile.variant),
+    };
+
+    let base_commit = match process.before_head_commit.clone() {
+        Some(commit) => Some(commit),
+        None => {
+            ExecutionProcess::find_prev_after_head_commit(pool, task_attempt.id, process.id).await?
+        }
+    }
+    .ok_or(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+        "No commit was recorded before this run".to_string(),
+    )))?;
+    let session_id =
+        ExecutionProcess::find_session_id_before(pool, task_attempt.id, process.id).await?;
+
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    // Forked at the first run there is no conversation to continue, and the
+    // fork starts a new session
+    let fork = start_attempt_at_commit(
+        &deployment,
+        &task,
+        &task_attempt,
+        &executor_profile_id,
+        &base_commit,
+        session_id,
+        payload.prompt,
+    )
+    .await?;
+    AttemptFork::create(pool, fork.id, task_attempt.id, process.id, &base_commit).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_forked",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "source_attempt_id": task_attempt.id.to_string(),
+                "attempt_id": fork.id.to_string(),
+                "executor": &executor_profile_id.executor,
+                "variant": &executor_profile_id.variant,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(fork)))
+}
+
+pub async fn get_attempt_forks(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<AttemptForks>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let forked_from = AttemptFork::find_by_task_attempt_id(pool, task_attempt.id).await?;
+    let forks = AttemptFork::find_by_source_task_attempt_id(pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(AttemptForks {
+        forked_from,
+        forks,
+    })))
+}
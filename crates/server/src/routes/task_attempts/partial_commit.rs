@@ -0,0 +1,90 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::task_attempt::{TaskAttempt, TaskAttemptError};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::git::DiffSelection;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::task_attempts::{running_agent_processes, util::ensure_worktree_path},
+};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct PartialCommitRequest {
+    /// Files to keep, by the paths and hunk indices of the diff file endpoints
+    pub files: Vec<DiffSelection>,
+    pub message: String,
+    /// Discard the changes left out instead of leaving them uncommitted.
+    /// Refused while the branch has committed changes to files that aren't
+    /// selected in full.
+    #[serde(default)]
+    #[ts(optional)]
+    pub discard_rest: Option<bool>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct PartialCommitResponse {
+    pub commit_sha: String,
+}
+
+/// Commit only the selected files and hunks of the attempt's worktree, on
+/// top of its branch. The commit replaces what the branch had committed, so
+/// afterwards the branch's diff against its base holds just the selection and
+/// merging or opening a pull request lands only that. Committed changes left
+/// out go back to being uncommitted.
+pub async fn commit_selected_changes(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<PartialCommitRequest>,
+) -> Result<ResponseJson<ApiResponse<PartialCommitResponse>>, ApiError> {
+    if request.message.trim().is_empty() {
+        return Err(ApiError::BadRequest("Commit message is empty".to_string()));
+    }
+    if !running_agent_processes(&deployment, &task_attempt)
+        .await?
+        .is_empty()
+    {
+        return Err(ApiError::Conflict(
+            "Task attempt has a running execution; wait for it to finish".to_string(),
+        ));
+    }
+
+    let pool = &deployment.db().pool;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let ctx = TaskAttempt::load_context(pool, task_attempt.id, task.id, task.project_id).await?;
+
+    let worktree_path = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let base_commit = deployment.git().get_base_commit(
+        &ctx.project.git_repo_path,
+        &task_attempt.branch,
+        &task_attempt.target_branch,
+    )?;
+    let commit_sha = deployment.git().commit_selected_changes(
+        &worktree_path,
+        &base_commit,
+        &request.files,
+        &request.message,
+        request.discard_rest.unwrap_or(false),
+    )?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_attempt_partial_commit",
+            serde_json::json!({
+                "task_id": task_attempt.task_id.to_string(),
+                "attempt_id": task_attempt.id.to_string(),
+                "files": request.files.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(PartialCommitResponse {
+        commit_sha,
+    })))
+}
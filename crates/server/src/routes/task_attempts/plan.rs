@@ -0,0 +1,114 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    attempt_plan::{ApproveAttemptPlan, AttemptPlan, AttemptPlanStatus},
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    task_attempt::TaskAttempt,
+};
+use deployment::Deployment;
+use executors::actions::{
+    ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
+    coding_agent_initial::CodingAgentInitialRequest,
+};
+use services::services::container::ContainerService;
+use sqlx::Error as SqlxError;
+use utils::response::ApiResponse;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::task_attempts::util::{ensure_not_archived, ensure_not_paused, ensure_worktree_path},
+};
+
+/// The attempt's plan, or null when the attempt was not started plan-first
+pub async fn get_attempt_plan(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<AttemptPlan>>>, ApiError> {
+    let plan = AttemptPlan::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(plan)))
+}
+
+/// Approve the plan awaiting approval, optionally edited, and start
+/// implementing it. The implementation continues the planning run's session
+/// when the executor kept one, and is followed by the project's cleanup
+/// scripts like any other coding agent run.
+pub async fn approve_attempt_plan(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ApproveAttemptPlan>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    ensure_not_archived(&task_attempt)?;
+    ensure_not_paused(&task_attempt)?;
+    let pool = &deployment.db().pool;
+
+    let current = AttemptPlan::find_by_task_attempt_id(pool, task_attempt.id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Task attempt has no plan".to_string()))?;
+    if current.status != AttemptPlanStatus::AwaitingApproval {
+        return Err(ApiError::Conflict(
+            "Task attempt has no plan awaiting approval".to_string(),
+        ));
+    }
+    let plan_text = match payload.plan {
+        Some(plan) if !plan.trim().is_empty() => plan,
+        Some(_) => return Err(ApiError::BadRequest("Plan cannot be empty".to_string())),
+        None => current.plan.clone().unwrap_or_default(),
+    };
+    let plan = AttemptPlan::approve(pool, task_attempt.id, &plan_text)
+        .await?
+        .ok_or_else(|| ApiError::Conflict("Plan was approved concurrently".to_string()))?;
+
+    let _ = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let project = task
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let executor_profile_id =
+        ExecutionProcess::latest_executor_profile_for_attempt(pool, task_attempt.id).await?;
+    let latest_session_id =
+        ExecutionProcess::find_latest_session_id_by_task_attempt(pool, task_attempt.id).await?;
+
+    let action_type = match latest_session_id {
+        Some(session_id) => {
+            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt: format!("The plan was approved. Implement it now:\n\n{plan_text}"),
+                session_id,
+                executor_profile_id,
+            })
+        }
+        None => ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt: format!(
+                "{}\n\nImplement the task following this approved plan:\n\n{plan_text}",
+                task.to_prompt()
+            ),
+            executor_profile_id,
+        }),
+    };
+    let cleanup_action = deployment.container().cleanup_action(&project).await?;
+    let action = ExecutorAction::new(action_type, cleanup_action);
+
+    let execution_process = deployment
+        .container()
+        .start_execution(
+            &task_attempt,
+            &action,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "attempt_plan_approved",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+                "plan_edited": plan.plan != current.plan,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(execution_process)))
+}
@@ -0,0 +1,119 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    proposed_task::{AcceptProposedTask, ProposedTask, ProposedTaskStatus},
+    task::{CreateTask, Task},
+    task_attempt::{TaskAttempt, TaskAttemptError},
+};
+use deployment::Deployment;
+use sqlx::Error as SqlxError;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::projects::ensure_project_not_archived};
+
+/// Follow-up work the attempt's agent proposed, in the order it was proposed
+pub async fn get_attempt_proposed_tasks(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProposedTask>>>, ApiError> {
+    let proposed =
+        ProposedTask::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(proposed)))
+}
+
+/// Create a task on the board from a pending proposal, as a subtask of the
+/// attempt that proposed it. The title and description can be edited on the
+/// way.
+pub async fn accept_proposed_task(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_attempt_id, proposed_task_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<AcceptProposedTask>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let (task_attempt, proposed) =
+        load_pending_proposal(&deployment, task_attempt_id, proposed_task_id).await?;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    ensure_project_not_archived(&deployment, task.project_id).await?;
+
+    let title = payload
+        .title
+        .as_deref()
+        .map(str::trim)
+        .unwrap_or(&proposed.title);
+    if title.is_empty() {
+        return Err(ApiError::BadRequest("Title cannot be empty".to_string()));
+    }
+    let description = payload.description.or(proposed.description);
+
+    let created = Task::create(
+        pool,
+        &CreateTask {
+            project_id: task.project_id,
+            title: title.to_string(),
+            description,
+            parent_task_attempt: Some(task_attempt.id),
+            image_ids: None,
+            parent_task_id: None,
+            priority: None,
+            due_date: None,
+            assignee: None,
+        },
+        Uuid::new_v4(),
+    )
+    .await?;
+    ProposedTask::accept(pool, proposed.id, created.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "proposed_task_accepted",
+            serde_json::json!({
+                "task_id": created.id.to_string(),
+                "project_id": task.project_id.to_string(),
+                "attempt_id": task_attempt.id.to_string(),
+                "edited": title != proposed.title,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(created)))
+}
+
+pub async fn dismiss_proposed_task(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_attempt_id, proposed_task_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let (_, proposed) =
+        load_pending_proposal(&deployment, task_attempt_id, proposed_task_id).await?;
+    ProposedTask::dismiss(&deployment.db().pool, proposed.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+async fn load_pending_proposal(
+    deployment: &DeploymentImpl,
+    task_attempt_id: Uuid,
+    proposed_task_id: Uuid,
+) -> Result<(TaskAttempt, ProposedTask), ApiError> {
+    let pool = &deployment.db().pool;
+    let task_attempt = TaskAttempt::find_by_id(pool, task_attempt_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let proposed = ProposedTask::find_by_id(pool, proposed_task_id)
+        .await?
+        .filter(|proposed| proposed.task_attempt_id == task_attempt.id)
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+            "Proposed task not found".to_string(),
+        )))?;
+    if proposed.status != ProposedTaskStatus::Pending {
+        return Err(ApiError::Conflict(
+            "Proposed task was already accepted or dismissed".to_string(),
+        ));
+    }
+    Ok((task_attempt, proposed))
+}
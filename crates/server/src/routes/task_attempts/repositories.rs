@@ -0,0 +1,292 @@
+use std::path::PathBuf;
+
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    merge::{Merge, PullRequestInfo},
+    project::Project,
+    project_repository::ProjectRepository,
+    task_attempt::TaskAttempt,
+};
+use deployment::Deployment;
+use git2::BranchType;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    git::DiffTarget,
+    github_service::{CreatePrRequest, GitHubService, GitHubServiceError},
+};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::{diff::Diff, response::ApiResponse};
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::task_attempts::util::{ensure_not_archived, ensure_worktree_path},
+};
+
+/// Changes of one repository of a multi-repository attempt
+#[derive(Debug, Serialize, TS)]
+pub struct RepositoryDiff {
+    /// None for the project's main repository
+    pub repository_id: Option<Uuid>,
+    pub name: String,
+    pub base_branch: String,
+    /// Committed and uncommitted changes relative to the base branch
+    pub diffs: Vec<Diff>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CommitRepositoriesRequest {
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct RepositoryCommitResult {
+    pub repository_id: Option<Uuid>,
+    pub name: String,
+    /// False when the worktree had nothing to commit
+    pub committed: bool,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateRepositoryPrsRequest {
+    pub title: String,
+    pub body: Option<String>,
+    /// Open the PRs as drafts
+    #[serde(default)]
+    #[ts(optional)]
+    pub draft: Option<bool>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct RepositoryPrResult {
+    pub repository_id: Option<Uuid>,
+    pub name: String,
+    pub url: Option<String>,
+    pub error: Option<String>,
+}
+
+/// One worktree of an attempt: the main repository or an additional one
+struct AttemptRepository {
+    repository_id: Option<Uuid>,
+    name: String,
+    git_repo_path: PathBuf,
+    worktree_path: PathBuf,
+    base_branch: String,
+}
+
+async fn attempt_repositories(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+) -> Result<Vec<AttemptRepository>, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let project = Project::find_by_id(pool, task.project_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let worktree_path = ensure_worktree_path(deployment, task_attempt).await?;
+
+    let mut repositories = vec![AttemptRepository {
+        repository_id: None,
+        name: project.name,
+        git_repo_path: project.git_repo_path,
+        worktree_path: worktree_path.clone(),
+        base_branch: task_attempt.target_branch.clone(),
+    }];
+    for repository in ProjectRepository::find_by_project_id(pool, project.id).await? {
+        repositories.push(AttemptRepository {
+            repository_id: Some(repository.id),
+            worktree_path: repository.worktree_path(&worktree_path),
+            name: repository.name,
+            git_repo_path: repository.git_repo_path,
+            base_branch: repository.base_branch,
+        });
+    }
+    Ok(repositories)
+}
+
+/// Diff of every repository of the attempt, main repository first.
+pub async fn get_repositories_diff(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<RepositoryDiff>>>, ApiError> {
+    ensure_not_archived(&task_attempt)?;
+    let mut results = Vec::new();
+    for repository in attempt_repositories(&deployment, &task_attempt).await? {
+        let base_commit = deployment.git().get_base_commit(
+            &repository.git_repo_path,
+            &task_attempt.branch,
+            &repository.base_branch,
+        )?;
+        let diffs = deployment.git().get_diffs(
+            DiffTarget::Worktree {
+                worktree_path: &repository.worktree_path,
+                base_commit: &base_commit,
+            },
+            None,
+        )?;
+        results.push(RepositoryDiff {
+            repository_id: repository.repository_id,
+            name: repository.name,
+            base_branch: repository.base_branch,
+            diffs,
+        });
+    }
+    Ok(ResponseJson(ApiResponse::success(results)))
+}
+
+/// Commit outstanding changes in every worktree of the attempt.
+pub async fn commit_repositories(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<CommitRepositoriesRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<RepositoryCommitResult>>>, ApiError> {
+    ensure_not_archived(&task_attempt)?;
+    if request.message.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Commit message cannot be empty".to_string(),
+        ));
+    }
+
+    let mut results = Vec::new();
+    for repository in attempt_repositories(&deployment, &task_attempt).await? {
+        let committed = deployment
+            .git()
+            .commit(&repository.worktree_path, request.message.trim())?;
+        results.push(RepositoryCommitResult {
+            repository_id: repository.repository_id,
+            name: repository.name,
+            committed,
+        });
+    }
+    Ok(ResponseJson(ApiResponse::success(results)))
+}
+
+/// Push the attempt branch of every repository and open one pull request per
+/// repository. A failure in one repository does not stop the others; the
+/// main repository's PR is recorded on the attempt like with `/pr`.
+pub async fn create_repository_prs(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(request): Json<CreateRepositoryPrsRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<RepositoryPrResult>>>, ApiError> {
+    let github_config = deployment.config().read().await.github.clone();
+    let Some(github_token) = github_config.token() else {
+        return Err(GitHubServiceError::TokenInvalid.into());
+    };
+    let github_service = GitHubService::new(&github_token)?;
+
+    let mut results = Vec::new();
+    for repository in attempt_repositories(&deployment, &task_attempt).await? {
+        let outcome = open_pr(
+            &deployment,
+            &github_service,
+            &github_token,
+            &repository,
+            &task_attempt.branch,
+            &request,
+        )
+        .await;
+        let (url, error) = match outcome {
+            Ok((base_branch, pr_info)) => {
+                if repository.repository_id.is_none()
+                    && let Err(e) = Merge::create_pr(
+                        &deployment.db().pool,
+                        task_attempt.id,
+                        &base_branch,
+                        pr_info.number,
+                        &pr_info.url,
+                        pr_info.is_draft,
+                    )
+                    .await
+                {
+                    tracing::error!("Failed to update task attempt PR status: {}", e);
+                }
+                (Some(pr_info.url), None)
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to create PR in '{}' for attempt {}: {}",
+                    repository.name,
+                    task_attempt.id,
+                    e
+                );
+                (None, Some(e))
+            }
+        };
+        results.push(RepositoryPrResult {
+            repository_id: repository.repository_id,
+            name: repository.name,
+            url,
+            error,
+        });
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "github_repository_prs_created",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+                "repositories": results.len(),
+                "succeeded": results.iter().filter(|r| r.url.is_some()).count(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(results)))
+}
+
+async fn open_pr(
+    deployment: &DeploymentImpl,
+    github_service: &GitHubService,
+    github_token: &str,
+    repository: &AttemptRepository,
+    branch: &str,
+    request: &CreateRepositoryPrsRequest,
+) -> Result<(String, PullRequestInfo), String> {
+    let git = deployment.git();
+    git.push_to_github(&repository.worktree_path, branch, github_token)
+        .map_err(|e| format!("Failed to push branch to GitHub: {e}"))?;
+
+    let base_branch = remote_branch_name(deployment, repository).map_err(|e| e.to_string())?;
+    let repo_info = git
+        .get_github_repo_info(&repository.git_repo_path)
+        .map_err(|e| e.to_string())?;
+    let pr_request = CreatePrRequest {
+        title: request.title.clone(),
+        body: request.body.clone(),
+        head_branch: branch.to_string(),
+        base_branch,
+        draft: request.draft.unwrap_or(false),
+    };
+    let pr_info = github_service
+        .create_pr(&repo_info, &pr_request)
+        .await
+        .map_err(|e| format!("Failed to create PR: {e}"))?;
+    Ok((pr_request.base_branch, pr_info))
+}
+
+/// Base branch name as the PR API expects it, without the remote prefix of
+/// remote-tracking branches.
+fn remote_branch_name(
+    deployment: &DeploymentImpl,
+    repository: &AttemptRepository,
+) -> Result<String, ApiError> {
+    let git = deployment.git();
+    let base_branch = &repository.base_branch;
+    if !matches!(
+        git.find_branch_type(&repository.git_repo_path, base_branch)?,
+        BranchType::Remote
+    ) {
+        return Ok(base_branch.clone());
+    }
+    let remote = git.get_remote_name_from_branch_name(&repository.worktree_path, base_branch)?;
+    Ok(base_branch
+        .strip_prefix(&format!("{remote}/"))
+        .unwrap_or(base_branch)
+        .to_string())
+}
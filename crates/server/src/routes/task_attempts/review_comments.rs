@@ -0,0 +1,163 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    merge::Merge,
+    pr_review_comment::PrReviewComment,
+    task_attempt::TaskAttempt,
+};
+use deployment::Deployment;
+use executors::actions::{
+    ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
+    coding_agent_initial::CodingAgentInitialRequest,
+};
+use serde::Deserialize;
+use services::services::{container::ContainerService, review_comments};
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::task_attempts::util::{
+        attempt_hosting_provider, ensure_not_archived, ensure_not_paused, ensure_plan_not_pending,
+        ensure_worktree_path,
+    },
+};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct SendReviewCommentsRequest {
+    pub comment_ids: Vec<Uuid>,
+    /// Added after the comments, e.g. which suggestions to leave out
+    #[serde(default)]
+    #[ts(optional)]
+    pub instructions: Option<String>,
+}
+
+/// Review comments on the attempt's pull requests, oldest first. They are
+/// fetched from the code host while the pull request is open.
+pub async fn get_attempt_review_comments(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<PrReviewComment>>>, ApiError> {
+    let comments =
+        PrReviewComment::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(comments)))
+}
+
+/// Fetch the review comments of the attempt's pull requests now instead of
+/// waiting for the next poll
+pub async fn refresh_attempt_review_comments(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<PrReviewComment>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let pr_merges: Vec<_> = Merge::find_by_task_attempt_id(pool, task_attempt.id)
+        .await?
+        .into_iter()
+        .filter_map(|merge| match merge {
+            Merge::Pr(pr_merge) => Some(pr_merge),
+            Merge::Direct(_) => None,
+        })
+        .collect();
+    if pr_merges.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Task attempt has no pull request".to_string(),
+        ));
+    }
+
+    let (_, provider) = attempt_hosting_provider(&deployment, &task_attempt).await?;
+    for pr_merge in &pr_merges {
+        review_comments::sync_review_comments(pool, provider.as_ref(), pr_merge).await?;
+    }
+
+    let comments = PrReviewComment::find_by_task_attempt_id(pool, task_attempt.id).await?;
+    Ok(ResponseJson(ApiResponse::success(comments)))
+}
+
+/// Send the selected review comments to the attempt's coding agent as a
+/// follow-up, continuing its session when the executor kept one
+pub async fn send_review_comments(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SendReviewCommentsRequest>,
+) -> Result<ResponseJson<ApiResponse<ExecutionProcess>>, ApiError> {
+    ensure_not_archived(&task_attempt)?;
+    ensure_not_paused(&task_attempt)?;
+    ensure_plan_not_pending(&deployment, &task_attempt).await?;
+    let pool = &deployment.db().pool;
+
+    if payload.comment_ids.is_empty() {
+        return Err(ApiError::BadRequest(
+            "No review comments selected".to_string(),
+        ));
+    }
+    let comments: Vec<_> = PrReviewComment::find_by_task_attempt_id(pool, task_attempt.id)
+        .await?
+        .into_iter()
+        .filter(|comment| payload.comment_ids.contains(&comment.id))
+        .collect();
+    if comments.len() != payload.comment_ids.len() {
+        return Err(ApiError::BadRequest(
+            "Review comment not found on this attempt".to_string(),
+        ));
+    }
+
+    let _ = ensure_worktree_path(&deployment, &task_attempt).await?;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let project = task
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let prompt =
+        review_comments::review_comments_prompt(&comments, payload.instructions.as_deref());
+    let executor_profile_id =
+        ExecutionProcess::latest_executor_profile_for_attempt(pool, task_attempt.id).await?;
+    let latest_session_id =
+        ExecutionProcess::find_latest_session_id_by_task_attempt(pool, task_attempt.id).await?;
+
+    let action_type = match latest_session_id {
+        Some(session_id) => {
+            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt,
+                session_id,
+                executor_profile_id,
+            })
+        }
+        None => ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt: format!("{}\n\n{prompt}", task.to_prompt()),
+            executor_profile_id,
+        }),
+    };
+    let cleanup_action = deployment.container().cleanup_action(&project).await?;
+    let action = ExecutorAction::new(action_type, cleanup_action);
+
+    let execution_process = deployment
+        .container()
+        .start_execution(
+            &task_attempt,
+            &action,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?;
+    for comment in &comments {
+        PrReviewComment::mark_sent(pool, comment.id, execution_process.id).await?;
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "review_comments_sent",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+                "comments": comments.len(),
+                "has_instructions": payload.instructions.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(execution_process)))
+}
@@ -1,15 +1,35 @@
-use db::models::image::TaskImage;
+use db::models::{
+    attempt_plan::{AttemptPlan, AttemptPlanStatus},
+    image::TaskImage,
+    project::{Project, ProjectError},
+    task::Task,
+    task_attempt::{CreateTaskAttempt, TaskAttempt, TaskAttemptError},
+};
 use deployment::Deployment;
-use services::services::{container::ContainerService, image::ImageService};
+use executors::{
+    actions::{
+        ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
+        coding_agent_initial::CodingAgentInitialRequest,
+    },
+    profile::ExecutorProfileId,
+};
+use services::services::{
+    container::ContainerService,
+    hosting::{self, HostingProvider},
+    image::ImageService,
+};
+use sqlx::Error as SqlxError;
 use uuid::Uuid;
 
 use crate::error::ApiError;
 
-/// Resolve and ensure the worktree path for a task attempt.
+/// Resolve and ensure the worktree path for a task attempt. Archived attempts
+/// are refused rather than getting their worktree recreated.
 pub async fn ensure_worktree_path(
     deployment: &crate::DeploymentImpl,
     attempt: &db::models::task_attempt::TaskAttempt,
 ) -> Result<std::path::PathBuf, ApiError> {
+    ensure_not_archived(attempt)?;
     let container_ref = deployment
         .container()
         .ensure_container_exists(attempt)
@@ -17,6 +37,201 @@ pub async fn ensure_worktree_path(
     Ok(std::path::PathBuf::from(container_ref))
 }
 
+/// Repository to read the attempt's commits from: its worktree, or for
+/// archived attempts, which have none, the project repository. Reads must go
+/// through the attempt branch rather than HEAD.
+pub async fn attempt_repo_path(
+    deployment: &crate::DeploymentImpl,
+    attempt: &TaskAttempt,
+) -> Result<std::path::PathBuf, ApiError> {
+    if attempt.archived_at.is_none() {
+        return ensure_worktree_path(deployment, attempt).await;
+    }
+    let pool = &deployment.db().pool;
+    let task = attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    let project = task
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    Ok(project.git_repo_path)
+}
+
+/// Create a new attempt of the source attempt's task whose branch starts at
+/// `commit` instead of the tip of the target branch, and run its agent with
+/// `prompt`: continuing `session_id` when given, otherwise in a new session.
+/// The run waits in the execution queue when executors are at capacity.
+pub async fn start_attempt_at_commit(
+    deployment: &crate::DeploymentImpl,
+    task: &Task,
+    source: &TaskAttempt,
+    executor_profile_id: &ExecutorProfileId,
+    commit: &str,
+    session_id: Option<String>,
+    prompt: String,
+) -> Result<TaskAttempt, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = task
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let attempt_id = Uuid::new_v4();
+    let branch = deployment
+        .container()
+        .git_branch_from_task_attempt(&attempt_id, task, &executor_profile_id.executor)
+        .await?;
+    let attempt = TaskAttempt::create(
+        pool,
+        &CreateTaskAttempt {
+            executor: executor_profile_id.executor,
+            base_branch: source.target_branch.clone(),
+            branch,
+        },
+        attempt_id,
+        task.id,
+    )
+    .await?;
+    if source.read_only {
+        TaskAttempt::set_read_only(pool, attempt.id, true).await?;
+    }
+
+    // The worktree is created from the target branch, then moved to the commit
+    let worktree = ensure_worktree_path(deployment, &attempt).await?;
+    deployment
+        .git()
+        .reset_worktree_to_commit(&worktree, commit, true)?;
+    let attempt = TaskAttempt::find_by_id(pool, attempt.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    let action_type = match session_id {
+        Some(session_id) => {
+            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt,
+                session_id,
+                executor_profile_id: executor_profile_id.clone(),
+            })
+        }
+        None => ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt,
+            executor_profile_id: executor_profile_id.clone(),
+        }),
+    };
+    let cleanup_action = deployment.container().cleanup_action(&project).await?;
+    // Fresh worktrees still need the project's setup scripts before the agent runs
+    let (action, run_reason) = deployment
+        .container()
+        .with_setup_scripts(&project, ExecutorAction::new(action_type, cleanup_action))
+        .await?;
+    deployment
+        .container()
+        .queue_or_start_execution(&attempt, executor_profile_id.clone(), action, run_reason)
+        .await?;
+
+    Ok(attempt)
+}
+
+/// Provider for the code host of the project an attempt belongs to
+pub async fn attempt_hosting_provider(
+    deployment: &crate::DeploymentImpl,
+    attempt: &TaskAttempt,
+) -> Result<(Project, Box<dyn HostingProvider>), ApiError> {
+    let pool = &deployment.db().pool;
+    let task = attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    let project = Project::find_by_id(pool, task.project_id)
+        .await?
+        .ok_or(ApiError::Project(ProjectError::ProjectNotFound))?;
+    let config = deployment.config().read().await.clone();
+    let provider = hosting::resolve_provider(pool, deployment.git(), &config, &project).await?;
+    Ok((project, provider))
+}
+
+/// Token for fetching from and pushing to the code host of a project
+pub async fn project_hosting_token(
+    deployment: &crate::DeploymentImpl,
+    project: &Project,
+) -> Result<String, ApiError> {
+    let config = deployment.config().read().await.clone();
+    let provider =
+        hosting::resolve_provider(&deployment.db().pool, deployment.git(), &config, project)
+            .await?;
+    Ok(provider.token().to_string())
+}
+
+/// Reject pushing the attempt branch while it has unsigned commits, when the
+/// config requires signed ones.
+pub async fn ensure_commits_signed(
+    deployment: &crate::DeploymentImpl,
+    attempt: &TaskAttempt,
+    worktree_path: &std::path::Path,
+) -> Result<(), ApiError> {
+    let require_signed = deployment
+        .config()
+        .read()
+        .await
+        .commit_signing
+        .as_ref()
+        .is_some_and(|signing| signing.require_signed);
+    if !require_signed {
+        return Ok(());
+    }
+    let unsigned: Vec<_> = deployment
+        .git()
+        .get_commit_signatures(worktree_path, &attempt.branch, &attempt.target_branch)?
+        .into_iter()
+        .filter(|commit| !commit.signed)
+        .map(|commit| commit.sha[..7].to_string())
+        .collect();
+    if !unsigned.is_empty() {
+        return Err(ApiError::BadRequest(format!(
+            "Signed commits are required; sign or rewrite these commits first: {}",
+            unsigned.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Reject operations that would need a worktree on an archived attempt.
+pub fn ensure_not_archived(attempt: &TaskAttempt) -> Result<(), ApiError> {
+    if attempt.archived_at.is_some() {
+        return Err(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+            "Task attempt is archived; unarchive it first".to_string(),
+        )));
+    }
+    Ok(())
+}
+
+/// Reject follow-up prompts while the attempt's executor is suspended.
+pub fn ensure_not_paused(attempt: &TaskAttempt) -> Result<(), ApiError> {
+    if attempt.paused_at.is_some() {
+        return Err(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+            "Task attempt is paused; resume it first".to_string(),
+        )));
+    }
+    Ok(())
+}
+
+/// Reject follow-up prompts while the attempt's plan awaits approval; the
+/// approve endpoint starts the implementation instead.
+pub async fn ensure_plan_not_pending(
+    deployment: &crate::DeploymentImpl,
+    attempt: &TaskAttempt,
+) -> Result<(), ApiError> {
+    let plan = AttemptPlan::find_by_task_attempt_id(&deployment.db().pool, attempt.id).await?;
+    if plan.is_some_and(|plan| plan.status == AttemptPlanStatus::AwaitingApproval) {
+        return Err(ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+            "Task attempt's plan awaits approval; approve it first".to_string(),
+        )));
+    }
+    Ok(())
+}
+
 /// Associate images to the task, copy into worktree, and canonicalize paths in the prompt.
 /// Returns the transformed prompt.
 pub async fn handle_images_for_prompt(
@@ -0,0 +1,298 @@
+use std::path::{Path, PathBuf};
+
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessStatus},
+    project::Project,
+    project_repository::ProjectRepository,
+    task_attempt::{TaskAttempt, TaskAttemptError},
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::worktree_manager::WorktreeManager;
+use sqlx::Error as SqlxError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::{projects::ensure_project_not_archived, task_attempts::util::ensure_not_archived},
+};
+
+/// One of the worktrees of an attempt: its main worktree, or the worktree
+/// of one of the project's additional repositories
+#[derive(Debug, Serialize, TS)]
+pub struct AttemptWorktree {
+    pub path: String,
+    /// Name of the additional repository; null for the main worktree
+    pub repository: Option<String>,
+    pub path_exists: bool,
+    /// Git has live metadata for a worktree at this path
+    pub registered: bool,
+    /// Branch checked out in the worktree, when registered
+    pub branch: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct RelocateWorktreeRequest {
+    /// Absolute path the worktree moves to; it must not exist yet
+    pub path: String,
+}
+
+/// A worktree of an attempt and the repository it belongs to
+struct WorktreeLocation {
+    repo_path: PathBuf,
+    worktree_path: PathBuf,
+    repository: Option<String>,
+}
+
+/// The attempt's worktrees if its main worktree lived at `container_ref`.
+/// Additional repositories without the attempt branch are left out: their
+/// worktree is only created on first use.
+async fn worktree_locations(
+    deployment: &DeploymentImpl,
+    project: &Project,
+    task_attempt: &TaskAttempt,
+    container_ref: &Path,
+) -> Result<Vec<WorktreeLocation>, ApiError> {
+    let mut locations = vec![WorktreeLocation {
+        repo_path: project.git_repo_path.clone(),
+        worktree_path: container_ref.to_path_buf(),
+        repository: None,
+    }];
+    for repository in
+        ProjectRepository::find_by_project_id(&deployment.db().pool, project.id).await?
+    {
+        if deployment
+            .git()
+            .check_branch_exists(&repository.git_repo_path, &task_attempt.branch)?
+        {
+            locations.push(WorktreeLocation {
+                worktree_path: repository.worktree_path(container_ref),
+                repo_path: repository.git_repo_path,
+                repository: Some(repository.name),
+            });
+        }
+    }
+    Ok(locations)
+}
+
+async fn attempt_project(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+) -> Result<Project, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = task_attempt
+        .parent_task(pool)
+        .await?
+        .ok_or(ApiError::TaskAttempt(TaskAttemptError::TaskNotFound))?;
+    Ok(Project::find_by_id(pool, task.project_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?)
+}
+
+fn container_ref(task_attempt: &TaskAttempt) -> Result<PathBuf, ApiError> {
+    task_attempt
+        .container_ref
+        .as_deref()
+        .map(PathBuf::from)
+        .ok_or_else(|| {
+            ApiError::TaskAttempt(TaskAttemptError::ValidationError(
+                "Task attempt has no worktree yet".to_string(),
+            ))
+        })
+}
+
+/// Worktrees must not change under a running agent or dev server
+async fn ensure_no_running_processes(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+) -> Result<(), ApiError> {
+    let processes =
+        ExecutionProcess::find_by_task_attempt_id(&deployment.db().pool, task_attempt.id, false)
+            .await?;
+    if processes
+        .iter()
+        .any(|p| p.status == ExecutionProcessStatus::Running)
+    {
+        return Err(ApiError::Conflict(
+            "Task attempt has running processes; stop them first".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+async fn inspect_locations(
+    locations: Vec<WorktreeLocation>,
+) -> Result<Vec<AttemptWorktree>, ApiError> {
+    let mut worktrees = Vec::with_capacity(locations.len());
+    for location in locations {
+        let health =
+            WorktreeManager::inspect_worktree(&location.repo_path, &location.worktree_path).await?;
+        worktrees.push(AttemptWorktree {
+            path: location.worktree_path.to_string_lossy().to_string(),
+            repository: location.repository,
+            path_exists: health.path_exists,
+            registered: health.registered,
+            branch: health.branch,
+        });
+    }
+    Ok(worktrees)
+}
+
+/// Repair every worktree of the attempt as if its main worktree lived at
+/// `container_ref`, and record that the attempt has worktrees again
+pub async fn repair_attempt_worktrees(
+    deployment: &DeploymentImpl,
+    task_attempt: &TaskAttempt,
+    container_ref: &Path,
+) -> Result<Vec<AttemptWorktree>, ApiError> {
+    let project = attempt_project(deployment, task_attempt).await?;
+    ensure_project_not_archived(deployment, project.id).await?;
+    ensure_not_archived(task_attempt)?;
+    ensure_no_running_processes(deployment, task_attempt).await?;
+
+    let locations = worktree_locations(deployment, &project, task_attempt, container_ref).await?;
+    for location in &locations {
+        WorktreeManager::repair_worktree(
+            &location.repo_path,
+            &task_attempt.branch,
+            &location.worktree_path,
+        )
+        .await?;
+    }
+    TaskAttempt::mark_worktree_restored(&deployment.db().pool, task_attempt.id).await?;
+    inspect_locations(locations).await
+}
+
+/// State of the attempt's worktrees, without changing anything
+pub async fn get_attempt_worktree(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<AttemptWorktree>>>, ApiError> {
+    let container_ref = container_ref(&task_attempt)?;
+    let project = attempt_project(&deployment, &task_attempt).await?;
+    let locations =
+        worktree_locations(&deployment, &project, &task_attempt, &container_ref).await?;
+    Ok(ResponseJson(ApiResponse::success(
+        inspect_locations(locations).await?,
+    )))
+}
+
+/// Re-link the attempt's worktrees with git, recreating the ones that cannot
+/// be re-linked from the attempt branch
+pub async fn repair_attempt_worktree(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<AttemptWorktree>>>, ApiError> {
+    let container_ref = container_ref(&task_attempt)?;
+    let worktrees = repair_attempt_worktrees(&deployment, &task_attempt, &container_ref).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "worktree_repaired",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+                "worktrees": worktrees.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(worktrees)))
+}
+
+/// Move the attempt's worktrees to a new directory and point the attempt at
+/// it. When one of them fails to move, those already moved are moved back.
+pub async fn relocate_attempt_worktree(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<RelocateWorktreeRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<AttemptWorktree>>>, ApiError> {
+    let container_ref = container_ref(&task_attempt)?;
+    let new_path = PathBuf::from(payload.path.trim());
+    if !new_path.is_absolute() {
+        return Err(ApiError::BadRequest(
+            "Worktree path must be absolute".to_string(),
+        ));
+    }
+    // Directory names with "--" are reserved for additional repositories
+    if new_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_none_or(|name| name.contains("--"))
+    {
+        return Err(ApiError::BadRequest(
+            "Worktree directory name must be valid UTF-8 without \"--\"".to_string(),
+        ));
+    }
+
+    let project = attempt_project(&deployment, &task_attempt).await?;
+    ensure_project_not_archived(&deployment, project.id).await?;
+    ensure_not_archived(&task_attempt)?;
+    ensure_no_running_processes(&deployment, &task_attempt).await?;
+
+    let locations =
+        worktree_locations(&deployment, &project, &task_attempt, &container_ref).await?;
+    let targets = worktree_locations(&deployment, &project, &task_attempt, &new_path).await?;
+    let moves: Vec<_> = locations.into_iter().zip(targets).collect();
+    if let Some((_, taken)) = moves
+        .iter()
+        .find(|(_, target)| target.worktree_path.exists())
+    {
+        return Err(ApiError::Conflict(format!(
+            "{} already exists",
+            taken.worktree_path.display()
+        )));
+    }
+
+    for (index, (location, target)) in moves.iter().enumerate() {
+        if let Err(e) = WorktreeManager::relocate_worktree(
+            &location.repo_path,
+            &task_attempt.branch,
+            &location.worktree_path,
+            &target.worktree_path,
+        )
+        .await
+        {
+            // Move back the worktrees already moved, so the attempt keeps
+            // working from its current directory
+            for (location, target) in moves[..index].iter().rev() {
+                if let Err(rollback_error) = WorktreeManager::relocate_worktree(
+                    &location.repo_path,
+                    &task_attempt.branch,
+                    &target.worktree_path,
+                    &location.worktree_path,
+                )
+                .await
+                {
+                    tracing::error!(
+                        "Failed to move worktree {} back to {}: {}",
+                        target.worktree_path.display(),
+                        location.worktree_path.display(),
+                        rollback_error
+                    );
+                }
+            }
+            return Err(e.into());
+        }
+    }
+    let pool = &deployment.db().pool;
+    TaskAttempt::update_container_ref(pool, task_attempt.id, &new_path.to_string_lossy()).await?;
+    TaskAttempt::mark_worktree_restored(pool, task_attempt.id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "worktree_relocated",
+            serde_json::json!({
+                "attempt_id": task_attempt.id.to_string(),
+                "worktrees": moves.len(),
+            }),
+        )
+        .await;
+
+    let targets = moves.into_iter().map(|(_, target)| target).collect();
+    Ok(ResponseJson(ApiResponse::success(
+        inspect_locations(targets).await?,
+    )))
+}
@@ -0,0 +1,154 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::{
+    project::Project,
+    task_template::{CreateTaskTemplate, TaskTemplate, UpdateTaskTemplate},
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_task_template_middleware};
+
+#[derive(Debug, Deserialize)]
+pub struct TaskTemplateQuery {
+    /// Include the templates of this project next to the global ones
+    #[serde(default)]
+    pub project_id: Option<Uuid>,
+}
+
+pub async fn get_task_templates(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskTemplateQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskTemplate>>>, ApiError> {
+    let templates = TaskTemplate::find_all(&deployment.db().pool, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(templates)))
+}
+
+pub async fn get_task_template(
+    Extension(template): Extension<TaskTemplate>,
+) -> Result<ResponseJson<ApiResponse<TaskTemplate>>, ApiError> {
+    Ok(Json(ApiResponse::success(template)))
+}
+
+fn validate_template_fields(
+    name: Option<&str>,
+    title: Option<&str>,
+    base_branch: Option<&str>,
+) -> Result<(), ApiError> {
+    if name.is_some_and(|name| name.trim().is_empty()) {
+        return Err(ApiError::BadRequest(
+            "Template name cannot be empty".to_string(),
+        ));
+    }
+    if title.is_some_and(|title| title.trim().is_empty()) {
+        return Err(ApiError::BadRequest(
+            "Template title cannot be empty".to_string(),
+        ));
+    }
+    if base_branch.is_some_and(|branch| branch.trim().is_empty()) {
+        return Err(ApiError::BadRequest(
+            "Base branch cannot be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub async fn create_task_template(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskTemplate>,
+) -> Result<ResponseJson<ApiResponse<TaskTemplate>>, ApiError> {
+    validate_template_fields(
+        Some(&payload.name),
+        Some(&payload.title),
+        payload.base_branch.as_deref(),
+    )?;
+    if let Some(project_id) = payload.project_id
+        && Project::find_by_id(&deployment.db().pool, project_id)
+            .await?
+            .is_none()
+    {
+        return Err(ApiError::BadRequest("Project not found".to_string()));
+    }
+
+    let template = TaskTemplate::create(
+        &deployment.db().pool,
+        &CreateTaskTemplate {
+            name: payload.name.trim().to_string(),
+            ..payload
+        },
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_template_created",
+            serde_json::json!({
+                "template_id": template.id.to_string(),
+                "is_global": template.project_id.is_none(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(template)))
+}
+
+pub async fn update_task_template(
+    Extension(template): Extension<TaskTemplate>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateTaskTemplate>,
+) -> Result<ResponseJson<ApiResponse<TaskTemplate>>, ApiError> {
+    validate_template_fields(
+        payload.name.as_deref(),
+        payload.title.as_deref(),
+        payload.base_branch.as_deref(),
+    )?;
+    let updated_template = TaskTemplate::update(
+        &deployment.db().pool,
+        template.id,
+        &UpdateTaskTemplate {
+            name: payload.name.as_deref().map(|name| name.trim().to_string()),
+            ..payload
+        },
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(updated_template)))
+}
+
+pub async fn delete_task_template(
+    Extension(template): Extension<TaskTemplate>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = TaskTemplate::delete(&deployment.db().pool, template.id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let template_router = Router::new()
+        .route(
+            "/",
+            get(get_task_template)
+                .put(update_task_template)
+                .delete(delete_task_template),
+        )
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_task_template_middleware,
+        ));
+
+    let inner = Router::new()
+        .route("/", get(get_task_templates).post(create_task_template))
+        .nest("/{template_id}", template_router);
+
+    Router::new().nest("/task-templates", inner)
+}
@@ -1,50 +1,142 @@
-use std::path::PathBuf;
+pub mod assignment;
+pub mod attachments;
+pub mod bulk;
+pub mod comments;
+pub mod dependencies;
+pub mod recurrence;
+pub mod schedule;
+pub mod swimlanes;
+pub mod wip_limits;
+
+use std::collections::HashMap;
 
 use anyhow;
 use axum::{
     Extension, Json, Router,
     extract::{
-        Query, State,
+        DefaultBodyLimit, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
-    http::StatusCode,
     middleware::from_fn_with_state,
     response::{IntoResponse, Json as ResponseJson},
-    routing::{get, post},
+    routing::{delete, get, post, put},
 };
+use chrono::{DateTime, Utc};
 use db::models::{
+    attempt_usage::{AttemptUsage, UsageTotals},
+    external_task_link::ExternalTaskLink,
     image::TaskImage,
-    task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
+    label::{Label, SetLabels},
+    metrics::TaskMetrics,
+    project::Project,
+    project_column::ProjectColumn,
+    task::{CreateTask, Task, TaskPriority, TaskWithAttemptStatus, UpdateTask},
+    task_activity::TaskActivity,
     task_attempt::{CreateTaskAttempt, TaskAttempt},
+    task_template::TaskTemplate,
 };
 use deployment::Deployment;
 use executors::profile::ExecutorProfileId;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
-use services::services::container::{
-    ContainerService, WorktreeCleanupData, cleanup_worktrees_direct,
-};
+use services::services::{container::ContainerService, image::MAX_ATTACHMENT_SIZE_BYTES};
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
-use utils::response::ApiResponse;
+use utils::{response::ApiResponse, text::fill_template};
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError, middleware::load_task_middleware};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_task_middleware,
+    routes::{labels::ensure_labels_exist, projects::ensure_project_not_archived},
+};
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskSort {
+    /// Newest first
+    #[default]
+    CreatedAt,
+    /// Earliest due first; tasks without a due date last
+    DueDate,
+    /// Most urgent first
+    Priority,
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TaskQuery {
     pub project_id: Uuid,
+    /// Only return tasks carrying this label
+    #[serde(default)]
+    pub label_id: Option<Uuid>,
+    /// Only return tasks of this priority
+    #[serde(default)]
+    pub priority: Option<TaskPriority>,
+    /// Only return tasks due before this time
+    #[serde(default)]
+    pub due_before: Option<DateTime<Utc>>,
+    /// Only return overdue (or, when false, not overdue) tasks
+    #[serde(default)]
+    pub overdue: Option<bool>,
+    #[serde(default)]
+    pub sort: TaskSort,
+    /// Reverse the sort order
+    #[serde(default)]
+    pub reverse: bool,
+    /// Only return tasks assigned to this user; `me` is the current user
+    #[serde(default)]
+    pub assignee: Option<String>,
+    /// Return the tasks bucketed into swimlanes
+    #[serde(default)]
+    pub group_by: Option<swimlanes::SwimlaneGroupBy>,
 }
 
 pub async fn get_tasks(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<TaskQuery>,
-) -> Result<ResponseJson<ApiResponse<Vec<TaskWithAttemptStatus>>>, ApiError> {
-    let tasks =
+) -> Result<ResponseJson<ApiResponse<swimlanes::TaskListing>>, ApiError> {
+    let mut tasks =
         Task::find_by_project_id_with_attempt_status(&deployment.db().pool, query.project_id)
             .await?;
+    let all_tasks = query.group_by.map(|_| tasks.clone());
+
+    if let Some(label_id) = query.label_id {
+        let labelled = Label::task_ids(&deployment.db().pool, label_id).await?;
+        tasks.retain(|task| labelled.contains(&task.id));
+    }
+    if let Some(priority) = query.priority {
+        tasks.retain(|task| task.priority == priority);
+    }
+    if let Some(due_before) = query.due_before {
+        tasks.retain(|task| task.due_date.is_some_and(|due_date| due_date < due_before));
+    }
+    if let Some(overdue) = query.overdue {
+        tasks.retain(|task| task.is_overdue == overdue);
+    }
+    if let Some(assignee) = assignment::resolve_assignee_filter(&deployment, query.assignee).await?
+    {
+        tasks.retain(|task| task.assignee.as_deref() == Some(assignee.as_str()));
+    }
+
+    // Tasks come newest first; the sorts below are stable, so ties keep that order
+    match query.sort {
+        TaskSort::CreatedAt => {}
+        TaskSort::DueDate => tasks.sort_by_key(|task| (task.due_date.is_none(), task.due_date)),
+        TaskSort::Priority => tasks.sort_by_key(|task| std::cmp::Reverse(task.priority)),
+    }
+    if query.reverse {
+        tasks.reverse();
+    }
 
-    Ok(ResponseJson(ApiResponse::success(tasks)))
+    let listing = match (query.group_by, all_tasks) {
+        (Some(group_by), Some(all_tasks)) => swimlanes::TaskListing::Swimlanes(
+            swimlanes::group_tasks(&deployment, query.project_id, &all_tasks, tasks, group_by)
+                .await?,
+        ),
+        _ => swimlanes::TaskListing::Tasks(tasks),
+    };
+    Ok(ResponseJson(ApiResponse::success(listing)))
 }
 
 pub async fn stream_tasks_ws(
@@ -101,11 +193,101 @@ pub async fn get_task(
     Ok(ResponseJson(ApiResponse::success(task)))
 }
 
+pub async fn get_task_usage(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<UsageTotals>>, ApiError> {
+    let totals = AttemptUsage::totals_for_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(totals)))
+}
+
+pub async fn get_task_metrics(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskMetrics>>, ApiError> {
+    let metrics = TaskMetrics::for_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(metrics)))
+}
+
+pub async fn get_task_labels(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Label>>>, ApiError> {
+    let labels = Label::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+pub async fn set_task_labels(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<SetLabels>,
+) -> Result<ResponseJson<ApiResponse<Vec<Label>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    ensure_labels_exist(&deployment, &payload.label_ids).await?;
+    Label::set_for_task(pool, task.id, &payload.label_ids).await?;
+    let labels = Label::find_by_task_id(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+pub async fn get_task_subtasks(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    let subtasks = Task::find_subtasks(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(subtasks)))
+}
+
+/// The Jira or Linear ticket the task was imported from, if any
+pub async fn get_task_external_link(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<ExternalTaskLink>>>, ApiError> {
+    let link = ExternalTaskLink::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(link)))
+}
+
+/// History of the task: status changes, attempts, pull requests and merges
+pub async fn get_task_activity(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskActivity>>>, ApiError> {
+    let activity = TaskActivity::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(activity)))
+}
+
+/// A parent task must belong to the same project and must not be the task
+/// itself or one of its subtasks.
+async fn ensure_valid_parent_task(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    task_id: Option<Uuid>,
+    parent_task_id: Uuid,
+) -> Result<(), ApiError> {
+    let pool = &deployment.db().pool;
+    if !Task::exists(pool, parent_task_id, project_id).await? {
+        return Err(ApiError::BadRequest(
+            "Parent task not found in this project".to_string(),
+        ));
+    }
+    if let Some(task_id) = task_id
+        && Task::is_self_or_descendant_of(pool, parent_task_id, task_id).await?
+    {
+        return Err(ApiError::Conflict(
+            "A task cannot be nested under itself or one of its subtasks".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 pub async fn create_task(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateTask>,
 ) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
     let id = Uuid::new_v4();
+    ensure_project_not_archived(&deployment, payload.project_id).await?;
+    if let Some(parent_task_id) = payload.parent_task_id {
+        ensure_valid_parent_task(&deployment, payload.project_id, None, parent_task_id).await?;
+    }
 
     tracing::debug!(
         "Creating task '{}' in project {}",
@@ -127,6 +309,7 @@ pub async fn create_task(
             "project_id": payload.project_id,
             "has_description": task.description.is_some(),
             "has_images": payload.image_ids.is_some(),
+            "is_subtask": payload.parent_task_id.is_some(),
             }),
         )
         .await;
@@ -145,6 +328,11 @@ pub async fn create_task_and_start(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateAndStartTaskRequest>,
 ) -> Result<ResponseJson<ApiResponse<TaskWithAttemptStatus>>, ApiError> {
+    ensure_project_not_archived(&deployment, payload.task.project_id).await?;
+    if let Some(parent_task_id) = payload.task.parent_task_id {
+        ensure_valid_parent_task(&deployment, payload.task.project_id, None, parent_task_id)
+            .await?;
+    }
     let task_id = Uuid::new_v4();
     let task = Task::create(&deployment.db().pool, &payload.task, task_id).await?;
 
@@ -163,17 +351,34 @@ pub async fn create_task_and_start(
             }),
         )
         .await;
+    start_attempt_for_new_task(
+        &deployment,
+        task,
+        payload.executor_profile_id,
+        payload.base_branch,
+    )
+    .await
+    .map(|task| ResponseJson(ApiResponse::success(task)))
+}
+
+/// Create and start the first attempt of a freshly created task.
+async fn start_attempt_for_new_task(
+    deployment: &DeploymentImpl,
+    task: Task,
+    executor_profile_id: ExecutorProfileId,
+    base_branch: String,
+) -> Result<TaskWithAttemptStatus, ApiError> {
     let attempt_id = Uuid::new_v4();
     let git_branch_name = deployment
         .container()
-        .git_branch_from_task_attempt(&attempt_id, &task.title)
-        .await;
+        .git_branch_from_task_attempt(&attempt_id, &task, &executor_profile_id.executor)
+        .await?;
 
     let task_attempt = TaskAttempt::create(
         &deployment.db().pool,
         &CreateTaskAttempt {
-            executor: payload.executor_profile_id.executor,
-            base_branch: payload.base_branch,
+            executor: executor_profile_id.executor,
+            base_branch,
             branch: git_branch_name,
         },
         attempt_id,
@@ -182,17 +387,17 @@ pub async fn create_task_and_start(
     .await?;
     let is_attempt_running = deployment
         .container()
-        .start_attempt(&task_attempt, payload.executor_profile_id.clone())
+        .queue_or_start_attempt(&task_attempt, executor_profile_id.clone())
         .await
         .inspect_err(|err| tracing::error!("Failed to start task attempt: {}", err))
-        .is_ok();
+        .is_ok_and(|process| process.is_some());
     deployment
         .track_if_analytics_allowed(
             "task_attempt_started",
             serde_json::json!({
                 "task_id": task.id.to_string(),
-                "executor": &payload.executor_profile_id.executor,
-                "variant": &payload.executor_profile_id.variant,
+                "executor": &executor_profile_id.executor,
+                "variant": &executor_profile_id.variant,
                 "attempt_id": task_attempt.id.to_string(),
             }),
         )
@@ -201,15 +406,127 @@ pub async fn create_task_and_start(
     let task = Task::find_by_id(&deployment.db().pool, task.id)
         .await?
         .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+    let column_id = Task::find_column_id(&deployment.db().pool, task.id).await?;
 
     tracing::info!("Started attempt for task {}", task.id);
-    Ok(ResponseJson(ApiResponse::success(TaskWithAttemptStatus {
+    Ok(TaskWithAttemptStatus {
+        is_overdue: task.is_overdue(Utc::now()),
+        column_id,
         task,
         has_in_progress_attempt: is_attempt_running,
         has_merged_attempt: false,
         last_attempt_failed: false,
+        is_blocked: false,
         executor: task_attempt.executor,
-    })))
+        ci_checks: Vec::new(),
+        ci_state: None,
+    })
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateTaskFromTemplate {
+    pub template_id: Uuid,
+    pub project_id: Uuid,
+    /// Values for the template's `{{placeholders}}`
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    /// Start an attempt right away
+    #[serde(default)]
+    pub start: bool,
+    /// Overrides the template's default executor
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    /// Overrides the template's default base branch
+    pub base_branch: Option<String>,
+}
+
+/// Instantiate a task template, filling in its placeholders, and optionally
+/// start an attempt with the template's default executor and base branch.
+pub async fn create_task_from_template(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskFromTemplate>,
+) -> Result<ResponseJson<ApiResponse<TaskWithAttemptStatus>>, ApiError> {
+    let pool = &deployment.db().pool;
+    ensure_project_not_archived(&deployment, payload.project_id).await?;
+    let template = TaskTemplate::find_by_id(pool, payload.template_id)
+        .await?
+        .filter(|template| {
+            template
+                .project_id
+                .is_none_or(|project_id| project_id == payload.project_id)
+        })
+        .ok_or(ApiError::BadRequest("Task template not found".to_string()))?;
+
+    let missing_variables = |missing: Vec<String>| {
+        ApiError::BadRequest(format!(
+            "Missing template variables: {}",
+            missing.join(", ")
+        ))
+    };
+    let title = fill_template(&template.title, &payload.variables).map_err(missing_variables)?;
+    let description = template
+        .description
+        .as_deref()
+        .map(|description| fill_template(description, &payload.variables))
+        .transpose()
+        .map_err(missing_variables)?;
+
+    // Resolve the start parameters before creating anything
+    let start_with = if payload.start {
+        let executor_profile_id = payload
+            .executor_profile_id
+            .or(template.executor_profile_id.map(|profile| profile.0))
+            .ok_or(ApiError::BadRequest(
+                "No executor given and the template has no default".to_string(),
+            ))?;
+        let base_branch =
+            payload
+                .base_branch
+                .or(template.base_branch)
+                .ok_or(ApiError::BadRequest(
+                    "No base branch given and the template has no default".to_string(),
+                ))?;
+        Some((executor_profile_id, base_branch))
+    } else {
+        None
+    };
+
+    let task = Task::create(
+        pool,
+        &CreateTask::from_title_description(payload.project_id, title, description),
+        Uuid::new_v4(),
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_created_from_template",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+                "template_id": template.id.to_string(),
+                "started": start_with.is_some(),
+            }),
+        )
+        .await;
+
+    let task = match start_with {
+        Some((executor_profile_id, base_branch)) => {
+            start_attempt_for_new_task(&deployment, task, executor_profile_id, base_branch).await?
+        }
+        None => TaskWithAttemptStatus {
+            is_overdue: task.is_overdue(Utc::now()),
+            column_id: Task::find_column_id(&deployment.db().pool, task.id).await?,
+            task,
+            has_in_progress_attempt: false,
+            has_merged_attempt: false,
+            last_attempt_failed: false,
+            is_blocked: false,
+            executor: String::new(),
+            ci_checks: Vec::new(),
+            ci_state: None,
+        },
+    };
+    Ok(ResponseJson(ApiResponse::success(task)))
 }
 
 pub async fn update_task(
@@ -217,6 +534,33 @@ pub async fn update_task(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<UpdateTask>,
 ) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let column = match payload.column_id {
+        Some(column_id) => Some(
+            ProjectColumn::find_by_id(&deployment.db().pool, column_id)
+                .await?
+                .filter(|column| column.project_id == existing_task.project_id)
+                .ok_or(ApiError::BadRequest(
+                    "Column not found in this project".to_string(),
+                ))?,
+        ),
+        None => None,
+    };
+    let status = match (&column, payload.status) {
+        (Some(column), Some(status)) if column.status != status => {
+            return Err(ApiError::BadRequest(format!(
+                "Column '{}' holds tasks with status {}",
+                column.name, column.status
+            )));
+        }
+        (Some(column), _) => column.status.clone(),
+        (None, status) => status.unwrap_or(existing_task.status.clone()),
+    };
+    let column_move =
+        wip_limits::resolve_move(&deployment, &existing_task, column.as_ref(), &status).await?;
+    if let Some(column_move) = &column_move {
+        wip_limits::check_wip_limit(&deployment, existing_task.id, &column_move.to).await?;
+    }
+
     // Use existing values if not provided in update
     let title = payload.title.unwrap_or(existing_task.title);
     let description = match payload.description {
@@ -224,10 +568,21 @@ pub async fn update_task(
         Some(s) => Some(s),                     // Non-empty string = update description
         None => existing_task.description,      // Field omitted = keep existing
     };
-    let status = payload.status.unwrap_or(existing_task.status);
     let parent_task_attempt = payload
         .parent_task_attempt
         .or(existing_task.parent_task_attempt);
+    if let Some(parent_task_id) = payload.parent_task_id {
+        ensure_valid_parent_task(
+            &deployment,
+            existing_task.project_id,
+            Some(existing_task.id),
+            parent_task_id,
+        )
+        .await?;
+    }
+    let parent_task_id = payload.parent_task_id.or(existing_task.parent_task_id);
+    let priority = payload.priority.unwrap_or(existing_task.priority);
+    let due_date = payload.due_date.unwrap_or(existing_task.due_date);
 
     let task = Task::update(
         &deployment.db().pool,
@@ -237,8 +592,21 @@ pub async fn update_task(
         description,
         status,
         parent_task_attempt,
+        parent_task_id,
+        priority,
+        due_date,
     )
     .await?;
+    if let Some(column) = &column {
+        Task::set_column(&deployment.db().pool, task.id, Some(column.id)).await?;
+    }
+    if let Some(column_move) = &column_move {
+        wip_limits::publish_wip_status(&deployment, column_move).await?;
+    }
+    // The parent's status may have been rolled up into another column
+    if task.parent_task_id.is_some() {
+        wip_limits::publish_project_wip_status(&deployment, task.project_id).await?;
+    }
 
     if let Some(image_ids) = &payload.image_ids {
         TaskImage::delete_by_task_id(&deployment.db().pool, task.id).await?;
@@ -248,10 +616,12 @@ pub async fn update_task(
     Ok(ResponseJson(ApiResponse::success(task)))
 }
 
+/// Move the task to the trash. Its attempts and worktrees are kept until it
+/// is restored or purged after the retention window.
 pub async fn delete_task(
     Extension(task): Extension<Task>,
     State(deployment): State<DeploymentImpl>,
-) -> Result<(StatusCode, ResponseJson<ApiResponse<()>>), ApiError> {
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
     // Validate no running execution processes
     if deployment
         .container()
@@ -261,110 +631,121 @@ pub async fn delete_task(
         return Err(ApiError::Conflict("Task has running execution processes. Please wait for them to complete or stop them first.".to_string()));
     }
 
-    // Gather task attempts data needed for background cleanup
-    let attempts = TaskAttempt::fetch_all(&deployment.db().pool, Some(task.id))
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch task attempts for task {}: {}", task.id, e);
-            ApiError::TaskAttempt(e)
-        })?;
-
-    // Gather cleanup data before deletion
-    let project = task
-        .parent_project(&deployment.db().pool)
-        .await?
-        .ok_or_else(|| ApiError::Database(SqlxError::RowNotFound))?;
-
-    let cleanup_data: Vec<WorktreeCleanupData> = attempts
-        .iter()
-        .filter_map(|attempt| {
-            attempt
-                .container_ref
-                .as_ref()
-                .map(|worktree_path| WorktreeCleanupData {
-                    attempt_id: attempt.id,
-                    worktree_path: PathBuf::from(worktree_path),
-                    git_repo_path: Some(project.git_repo_path.clone()),
-                })
-        })
-        .collect();
-
-    // Use a transaction to ensure atomicity: either all operations succeed or all are rolled back
-    let mut tx = deployment.db().pool.begin().await?;
-
-    // Nullify parent_task_attempt for all child tasks before deletion
-    // This breaks parent-child relationships to avoid foreign key constraint violations
-    let mut total_children_affected = 0u64;
-    for attempt in &attempts {
-        let children_affected = Task::nullify_children_by_attempt_id(&mut *tx, attempt.id).await?;
-        total_children_affected += children_affected;
-    }
-
-    // Delete task from database (FK CASCADE will handle task_attempts)
-    let rows_affected = Task::delete(&mut *tx, task.id).await?;
-
+    let rows_affected = Task::soft_delete(&deployment.db().pool, task.id).await?;
     if rows_affected == 0 {
         return Err(ApiError::Database(SqlxError::RowNotFound));
     }
 
-    // Commit the transaction - if this fails, all changes are rolled back
-    tx.commit().await?;
-
-    if total_children_affected > 0 {
-        tracing::info!(
-            "Nullified {} child task references before deleting task {}",
-            total_children_affected,
-            task.id
-        );
-    }
-
     deployment
         .track_if_analytics_allowed(
             "task_deleted",
             serde_json::json!({
                 "task_id": task.id.to_string(),
                 "project_id": task.project_id.to_string(),
-                "attempt_count": attempts.len(),
             }),
         )
         .await;
 
-    // Spawn background worktree cleanup task
-    let task_id = task.id;
-    tokio::spawn(async move {
-        let span = tracing::info_span!("background_worktree_cleanup", task_id = %task_id);
-        let _enter = span.enter();
+    Ok(ResponseJson(ApiResponse::success(())))
+}
 
-        tracing::info!(
-            "Starting background cleanup for task {} ({} worktrees)",
-            task_id,
-            cleanup_data.len()
-        );
+pub async fn restore_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if Project::is_trashed(pool, task.project_id).await? {
+        return Err(ApiError::Conflict(
+            "The task's project is in the trash. Restore the project first.".to_string(),
+        ));
+    }
+    if Task::restore(pool, task.id).await? == 0 {
+        return Err(ApiError::BadRequest("Task is not in the trash".to_string()));
+    }
 
-        if let Err(e) = cleanup_worktrees_direct(&cleanup_data).await {
-            tracing::error!(
-                "Background worktree cleanup failed for task {}: {}",
-                task_id,
-                e
-            );
-        } else {
-            tracing::info!("Background cleanup completed for task {}", task_id);
-        }
-    });
+    deployment
+        .track_if_analytics_allowed(
+            "task_restored",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+            }),
+        )
+        .await;
 
-    // Return 202 Accepted to indicate deletion was scheduled
-    Ok((StatusCode::ACCEPTED, ResponseJson(ApiResponse::success(()))))
+    let task = Task::find_by_id(pool, task.id)
+        .await?
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+    Ok(ResponseJson(ApiResponse::success(task)))
 }
 
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_id_router = Router::new()
         .route("/", get(get_task).put(update_task).delete(delete_task))
-        .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
+        .route("/usage", get(get_task_usage))
+        .route("/metrics", get(get_task_metrics))
+        .route("/subtasks", get(get_task_subtasks))
+        .route("/external-link", get(get_task_external_link))
+        .route("/activity", get(get_task_activity))
+        .route("/restore", post(restore_task))
+        .route("/assign", post(assignment::assign_task))
+        .route("/unassign", post(assignment::unassign_task))
+        .route("/labels", get(get_task_labels).put(set_task_labels))
+        .route(
+            "/schedule",
+            get(schedule::get_task_schedule)
+                .put(schedule::upsert_task_schedule)
+                .delete(schedule::delete_task_schedule),
+        )
+        .route(
+            "/recurrence",
+            get(recurrence::get_task_recurrence)
+                .put(recurrence::upsert_task_recurrence)
+                .delete(recurrence::delete_task_recurrence),
+        )
+        .route(
+            "/dependencies",
+            get(dependencies::get_task_dependencies).post(dependencies::add_task_dependency),
+        )
+        .route(
+            "/comments",
+            get(comments::get_task_comments).post(comments::create_task_comment),
+        )
+        .route(
+            "/attachments",
+            get(attachments::get_task_attachments)
+                .post(attachments::upload_task_attachment)
+                // Leave room for the multipart framing around the largest file
+                .layer(DefaultBodyLimit::max(
+                    MAX_ATTACHMENT_SIZE_BYTES as usize + 1024 * 1024,
+                )),
+        )
+        .layer(from_fn_with_state(deployment.clone(), load_task_middleware))
+        // Added after the task loader layer: these routes carry a second path
+        // parameter, so the handlers load the task themselves
+        .route(
+            "/dependencies/{depends_on_task_id}",
+            delete(dependencies::delete_task_dependency),
+        )
+        .route(
+            "/comments/{comment_id}",
+            put(comments::update_task_comment).delete(comments::delete_task_comment),
+        )
+        .route(
+            "/attachments/{attachment_id}",
+            delete(attachments::delete_task_attachment),
+        )
+        .route(
+            "/attachments/{attachment_id}/file",
+            get(attachments::serve_task_attachment),
+        );
 
     let inner = Router::new()
         .route("/", get(get_tasks).post(create_task))
         .route("/stream/ws", get(stream_tasks_ws))
         .route("/create-and-start", post(create_task_and_start))
+        .route("/from_template", post(create_task_from_template))
+        .route("/bulk", post(bulk::bulk_update_tasks))
         .nest("/{task_id}", task_id_router);
 
     // mount under /projects/:project_id/tasks
@@ -0,0 +1,103 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{task::Task, task_attempt::TaskAttempt};
+use deployment::Deployment;
+use serde::Deserialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Value of the `assignee` list filter that stands for the current user
+pub const CURRENT_USER_FILTER: &str = "me";
+
+#[derive(Debug, Deserialize, TS)]
+pub struct AssignRequest {
+    /// Defaults to the configured GitHub username
+    #[serde(default)]
+    #[ts(optional)]
+    pub assignee: Option<String>,
+}
+
+/// The user of this server, as identified by the configured GitHub username
+pub async fn current_user(deployment: &DeploymentImpl) -> Option<String> {
+    deployment.config().read().await.github.username.clone()
+}
+
+/// Resolve an `assignee` list filter, turning `me` into the current user
+pub async fn resolve_assignee_filter(
+    deployment: &DeploymentImpl,
+    assignee: Option<String>,
+) -> Result<Option<String>, ApiError> {
+    match assignee {
+        Some(assignee) if assignee == CURRENT_USER_FILTER => {
+            current_user(deployment).await.map(Some).ok_or_else(|| {
+                ApiError::BadRequest("Sign in to GitHub to filter by your own work".to_string())
+            })
+        }
+        other => Ok(other),
+    }
+}
+
+pub(crate) async fn resolve_assignee(
+    deployment: &DeploymentImpl,
+    request: AssignRequest,
+) -> Result<String, ApiError> {
+    match request.assignee.as_deref().map(str::trim) {
+        Some(assignee) if !assignee.is_empty() => Ok(assignee.to_string()),
+        _ => current_user(deployment).await.ok_or_else(|| {
+            ApiError::BadRequest(
+                "Pass an assignee or sign in to GitHub to assign yourself".to_string(),
+            )
+        }),
+    }
+}
+
+pub async fn assign_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<AssignRequest>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let assignee = resolve_assignee(&deployment, payload).await?;
+    let task = Task::set_assignee(&deployment.db().pool, task.id, Some(&assignee)).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_assigned",
+            serde_json::json!({ "task_id": task.id.to_string() }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
+pub async fn unassign_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let task = Task::set_assignee(&deployment.db().pool, task.id, None).await?;
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
+pub async fn assign_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<AssignRequest>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    let assignee = resolve_assignee(&deployment, payload).await?;
+    TaskAttempt::set_assignee(&deployment.db().pool, task_attempt.id, Some(&assignee)).await?;
+    Ok(ResponseJson(ApiResponse::success(TaskAttempt {
+        assignee: Some(assignee),
+        ..task_attempt
+    })))
+}
+
+pub async fn unassign_task_attempt(
+    Extension(task_attempt): Extension<TaskAttempt>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskAttempt>>, ApiError> {
+    TaskAttempt::set_assignee(&deployment.db().pool, task_attempt.id, None).await?;
+    Ok(ResponseJson(ApiResponse::success(TaskAttempt {
+        assignee: None,
+        ..task_attempt
+    })))
+}
@@ -0,0 +1,107 @@
+use axum::{
+    Extension,
+    body::Body,
+    extract::{Multipart, Path, State},
+    http::{StatusCode, header},
+    response::{Json as ResponseJson, Response},
+};
+use db::models::{task::Task, task_attachment::TaskAttachment};
+use deployment::Deployment;
+use services::services::image::ImageError;
+use tokio::fs::File;
+use tokio_util::io::ReaderStream;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub async fn get_task_attachments(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskAttachment>>>, ApiError> {
+    let attachments = TaskAttachment::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(attachments)))
+}
+
+/// Attach the multipart `file` field to the task. Attempts started afterwards
+/// find it in their worktree and get its path in the prompt.
+pub async fn upload_task_attachment(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    mut multipart: Multipart,
+) -> Result<ResponseJson<ApiResponse<TaskAttachment>>, ApiError> {
+    while let Some(field) = multipart.next_field().await? {
+        if field.name() == Some("file") {
+            let filename = field
+                .file_name()
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| "attachment".to_string());
+            let data = field.bytes().await?;
+            let attachment = deployment
+                .image()
+                .store_attachment(task.id, &data, &filename)
+                .await?;
+
+            deployment
+                .track_if_analytics_allowed(
+                    "task_attachment_uploaded",
+                    serde_json::json!({
+                        "task_id": task.id.to_string(),
+                        "size_bytes": attachment.size_bytes,
+                        "mime_type": attachment.mime_type,
+                    }),
+                )
+                .await;
+
+            return Ok(ResponseJson(ApiResponse::success(attachment)));
+        }
+    }
+
+    Err(ApiError::BadRequest(
+        "Missing multipart field 'file'".to_string(),
+    ))
+}
+
+async fn find_task_attachment(
+    deployment: &DeploymentImpl,
+    task_id: Uuid,
+    attachment_id: Uuid,
+) -> Result<TaskAttachment, ApiError> {
+    TaskAttachment::find_by_id(&deployment.db().pool, attachment_id)
+        .await?
+        .filter(|attachment| attachment.task_id == task_id)
+        .ok_or(ApiError::Image(ImageError::AttachmentNotFound))
+}
+
+/// Download an attachment under its original name
+pub async fn serve_task_attachment(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Response, ApiError> {
+    let attachment = find_task_attachment(&deployment, task_id, attachment_id).await?;
+    let file = File::open(deployment.image().get_attachment_path(&attachment)).await?;
+    let metadata = file.metadata().await?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, &attachment.mime_type)
+        .header(header::CONTENT_LENGTH, metadata.len())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"{}\"",
+                attachment.original_name.replace('"', "")
+            ),
+        )
+        .body(Body::from_stream(ReaderStream::new(file)))
+        .map_err(|e| ApiError::Image(ImageError::ResponseBuildError(e.to_string())))
+}
+
+pub async fn delete_task_attachment(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let attachment = find_task_attachment(&deployment, task_id, attachment_id).await?;
+    deployment.image().delete_attachment(&attachment).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
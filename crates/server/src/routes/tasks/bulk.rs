@@ -0,0 +1,199 @@
+use axum::{Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    label::Label,
+    project::Project,
+    task::{Task, TaskStatus},
+    task_attempt::TaskAttempt,
+    task_dependency::TaskDependency,
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::container::ContainerService;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::{
+        labels::ensure_labels_exist,
+        projects::ensure_project_not_archived,
+        tasks::wip_limits::{check_bulk_wip_limits, publish_project_wip_status, resolve_move},
+    },
+};
+
+#[derive(Debug, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum BulkTaskAction {
+    SetStatus {
+        status: TaskStatus,
+    },
+    Delete,
+    MoveToProject {
+        project_id: Uuid,
+    },
+    /// Attach labels, keeping the labels the tasks already carry
+    AddLabels {
+        label_ids: Vec<Uuid>,
+    },
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct BulkTaskRequest {
+    pub task_ids: Vec<Uuid>,
+    pub action: BulkTaskAction,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct BulkTaskResponse {
+    pub affected: usize,
+}
+
+/// Apply one action to many tasks in a single transaction: either every task
+/// is changed or none is.
+pub async fn bulk_update_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<BulkTaskRequest>,
+) -> Result<ResponseJson<ApiResponse<BulkTaskResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let mut task_ids = payload.task_ids;
+    task_ids.sort();
+    task_ids.dedup();
+    if task_ids.is_empty() {
+        return Err(ApiError::BadRequest("No tasks given".to_string()));
+    }
+
+    let mut tasks = Vec::with_capacity(task_ids.len());
+    for task_id in &task_ids {
+        let task = Task::find_by_id(pool, *task_id)
+            .await?
+            .ok_or(ApiError::BadRequest(format!("Task {task_id} not found")))?;
+        tasks.push(task);
+    }
+
+    // Validate everything up front so the transaction only does writes
+    for task in &tasks {
+        ensure_project_not_archived(&deployment, task.project_id).await?;
+    }
+    match &payload.action {
+        BulkTaskAction::SetStatus { status } => {
+            let mut column_moves = Vec::new();
+            for task in &tasks {
+                if let Some(column_move) = resolve_move(&deployment, task, None, status).await? {
+                    column_moves.push(column_move);
+                }
+            }
+            check_bulk_wip_limits(&deployment, &column_moves).await?;
+        }
+        BulkTaskAction::Delete => {
+            for task in &tasks {
+                if deployment
+                    .container()
+                    .has_running_processes(task.id)
+                    .await?
+                {
+                    return Err(ApiError::Conflict(format!(
+                        "Task '{}' has running execution processes",
+                        task.title
+                    )));
+                }
+            }
+        }
+        BulkTaskAction::MoveToProject { project_id } => {
+            if Project::find_by_id(pool, *project_id).await?.is_none() {
+                return Err(ApiError::BadRequest("Project not found".to_string()));
+            }
+            ensure_project_not_archived(&deployment, *project_id).await?;
+            // Attempt worktrees belong to the old project's repository
+            for task in &tasks {
+                if !TaskAttempt::fetch_all(pool, Some(task.id))
+                    .await?
+                    .is_empty()
+                {
+                    return Err(ApiError::Conflict(format!(
+                        "Task '{}' has attempts and cannot change project",
+                        task.title
+                    )));
+                }
+            }
+        }
+        BulkTaskAction::AddLabels { label_ids } => {
+            ensure_labels_exist(&deployment, label_ids).await?;
+        }
+    }
+
+    let mut tx = pool.begin().await?;
+    for task in &tasks {
+        match &payload.action {
+            BulkTaskAction::SetStatus { status } => {
+                Task::set_status(&mut *tx, task.id, status.clone()).await?;
+            }
+            BulkTaskAction::Delete => {
+                Task::soft_delete(&mut *tx, task.id).await?;
+            }
+            BulkTaskAction::MoveToProject { project_id } => {
+                Task::detach_subtasks(&mut *tx, task.id).await?;
+                TaskDependency::delete_for_task(&mut *tx, task.id).await?;
+                Task::move_to_project(&mut *tx, task.id, *project_id).await?;
+            }
+            BulkTaskAction::AddLabels { label_ids } => {
+                for label_id in label_ids {
+                    Label::add_to_task(&mut *tx, task.id, *label_id).await?;
+                }
+            }
+        }
+    }
+    tx.commit().await?;
+
+    // Parents of changed or removed subtasks re-derive their status
+    if matches!(
+        payload.action,
+        BulkTaskAction::SetStatus { .. } | BulkTaskAction::Delete
+    ) {
+        let mut parent_ids: Vec<Uuid> = tasks
+            .iter()
+            .filter_map(|task| task.parent_task_id)
+            .filter(|parent_id| !task_ids.contains(parent_id))
+            .collect();
+        parent_ids.sort();
+        parent_ids.dedup();
+        for parent_id in parent_ids {
+            Task::roll_up_status(pool, Some(parent_id)).await?;
+        }
+    }
+
+    // Columns of every touched project may have crossed their WIP limits
+    let mut project_ids: Vec<Uuid> = match payload.action {
+        BulkTaskAction::AddLabels { .. } => Vec::new(),
+        _ => tasks.iter().map(|task| task.project_id).collect(),
+    };
+    if let BulkTaskAction::MoveToProject { project_id } = payload.action {
+        project_ids.push(project_id);
+    }
+    project_ids.sort();
+    project_ids.dedup();
+    for project_id in project_ids {
+        publish_project_wip_status(&deployment, project_id).await?;
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "tasks_bulk_updated",
+            serde_json::json!({
+                "action": match payload.action {
+                    BulkTaskAction::SetStatus { .. } => "set_status",
+                    BulkTaskAction::Delete => "delete",
+                    BulkTaskAction::MoveToProject { .. } => "move_to_project",
+                    BulkTaskAction::AddLabels { .. } => "add_labels",
+                },
+                "task_count": tasks.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(BulkTaskResponse {
+        affected: tasks.len(),
+    })))
+}
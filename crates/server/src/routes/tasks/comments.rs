@@ -0,0 +1,127 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    task::Task,
+    task_attempt::TaskAttempt,
+    task_comment::{CreateTaskComment, TaskComment, UpdateTaskComment},
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use sqlx::Error as SqlxError;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use super::assignment::current_user;
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize)]
+pub struct TaskCommentQuery {
+    /// Only return the comments about this attempt
+    #[serde(default)]
+    pub task_attempt_id: Option<Uuid>,
+}
+
+pub async fn get_task_comments(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskCommentQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskComment>>>, ApiError> {
+    let comments =
+        TaskComment::find_by_task_id(&deployment.db().pool, task.id, query.task_attempt_id).await?;
+    Ok(ResponseJson(ApiResponse::success(comments)))
+}
+
+/// Comment on a task. A comment can be about one of the task's attempts and
+/// anchored to a file (and line) of that attempt's diff.
+pub async fn create_task_comment(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskComment>,
+) -> Result<ResponseJson<ApiResponse<TaskComment>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if payload.body.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Comment body cannot be empty".to_string(),
+        ));
+    }
+    if let Some(attempt_id) = payload.task_attempt_id
+        && !TaskAttempt::find_by_id(pool, attempt_id)
+            .await?
+            .is_some_and(|attempt| attempt.task_id == task.id)
+    {
+        return Err(ApiError::BadRequest(
+            "Attempt not found for this task".to_string(),
+        ));
+    }
+    if payload.file_path.is_some() && payload.task_attempt_id.is_none() {
+        return Err(ApiError::BadRequest(
+            "Only comments about an attempt can be anchored to a file".to_string(),
+        ));
+    }
+    if let Some(line_number) = payload.line_number
+        && (payload.file_path.is_none() || line_number < 1)
+    {
+        return Err(ApiError::BadRequest(
+            "A line anchor needs a file and a line number of at least 1".to_string(),
+        ));
+    }
+
+    let author = match payload.author.as_deref().map(str::trim) {
+        Some(author) if !author.is_empty() => author.to_string(),
+        _ => current_user(&deployment)
+            .await
+            .unwrap_or_else(|| "You".to_string()),
+    };
+    let comment = TaskComment::create(pool, task.id, &author, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_comment_created",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "on_attempt": comment.task_attempt_id.is_some(),
+                "anchored": comment.file_path.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(comment)))
+}
+
+async fn find_task_comment(
+    deployment: &DeploymentImpl,
+    task_id: Uuid,
+    comment_id: Uuid,
+) -> Result<TaskComment, ApiError> {
+    TaskComment::find_by_id(&deployment.db().pool, comment_id)
+        .await?
+        .filter(|comment| comment.task_id == task_id)
+        .ok_or(ApiError::Database(SqlxError::RowNotFound))
+}
+
+pub async fn update_task_comment(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, comment_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateTaskComment>,
+) -> Result<ResponseJson<ApiResponse<TaskComment>>, ApiError> {
+    let comment = find_task_comment(&deployment, task_id, comment_id).await?;
+    if payload.body.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Comment body cannot be empty".to_string(),
+        ));
+    }
+    let updated = TaskComment::update(&deployment.db().pool, comment.id, &payload.body).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+pub async fn delete_task_comment(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let comment = find_task_comment(&deployment, task_id, comment_id).await?;
+    TaskComment::delete(&deployment.db().pool, comment.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
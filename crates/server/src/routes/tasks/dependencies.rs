@@ -0,0 +1,77 @@
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    task::Task,
+    task_dependency::{CreateTaskDependency, TaskDependencies, TaskDependency},
+};
+use deployment::Deployment;
+use sqlx::Error as SqlxError;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub async fn get_task_dependencies(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<TaskDependencies>>, ApiError> {
+    let dependencies = TaskDependency::find_for_task(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(dependencies)))
+}
+
+/// Mark the task as blocked by another task of the same project. Edges that
+/// would make the dependency graph cyclic are rejected.
+pub async fn add_task_dependency(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskDependency>,
+) -> Result<ResponseJson<ApiResponse<TaskDependencies>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if !Task::exists(pool, payload.depends_on_task_id, task.project_id).await? {
+        return Err(ApiError::BadRequest(
+            "Blocking task not found in this project".to_string(),
+        ));
+    }
+    if TaskDependency::exists(pool, task.id, payload.depends_on_task_id).await? {
+        return Err(ApiError::Conflict("Dependency already exists".to_string()));
+    }
+    if TaskDependency::would_create_cycle(pool, task.id, payload.depends_on_task_id).await? {
+        return Err(ApiError::Conflict(
+            "Dependency would create a cycle".to_string(),
+        ));
+    }
+
+    TaskDependency::create(pool, task.id, payload.depends_on_task_id).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_dependency_added",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "depends_on_task_id": payload.depends_on_task_id.to_string(),
+            }),
+        )
+        .await;
+
+    let dependencies = TaskDependency::find_for_task(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(dependencies)))
+}
+
+pub async fn delete_task_dependency(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, depends_on_task_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<TaskDependencies>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let task = Task::find_by_id(pool, task_id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    if TaskDependency::delete(pool, task.id, depends_on_task_id).await? == 0 {
+        return Err(ApiError::BadRequest("Dependency not found".to_string()));
+    }
+
+    let dependencies = TaskDependency::find_for_task(pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(dependencies)))
+}
@@ -0,0 +1,49 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use db::models::{
+    task::Task,
+    task_recurrence::{TaskRecurrence, UpsertTaskRecurrence},
+};
+use deployment::Deployment;
+use utils::{recurrence::RecurrenceRule, response::ApiResponse};
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub async fn get_task_recurrence(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<TaskRecurrence>>>, ApiError> {
+    let recurrence = TaskRecurrence::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(recurrence)))
+}
+
+/// Create or replace the task's recurrence rule. The next occurrence is
+/// created once the task is done.
+pub async fn upsert_task_recurrence(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertTaskRecurrence>,
+) -> Result<ResponseJson<ApiResponse<TaskRecurrence>>, ApiError> {
+    RecurrenceRule::parse(&payload.rule).map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    let recurrence =
+        TaskRecurrence::upsert(&deployment.db().pool, task.id, payload.rule.trim()).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_recurrence_saved",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(recurrence)))
+}
+
+pub async fn delete_task_recurrence(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    TaskRecurrence::delete_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
@@ -0,0 +1,68 @@
+use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use chrono::Utc;
+use db::models::{
+    task::Task,
+    task_schedule::{TaskSchedule, UpsertTaskSchedule},
+};
+use deployment::Deployment;
+use utils::{cron::CronSchedule, response::ApiResponse};
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub async fn get_task_schedule(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Option<TaskSchedule>>>, ApiError> {
+    let schedule = TaskSchedule::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(schedule)))
+}
+
+/// Create or replace the task's schedule. The next run is computed from the
+/// cron expression relative to now.
+pub async fn upsert_task_schedule(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertTaskSchedule>,
+) -> Result<ResponseJson<ApiResponse<TaskSchedule>>, ApiError> {
+    let cron = CronSchedule::parse(&payload.cron_expression)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let next_run_at = cron.next_after(Utc::now()).ok_or_else(|| {
+        ApiError::BadRequest("Cron expression never matches a future time".to_string())
+    })?;
+    if payload.base_branch.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Base branch cannot be empty".to_string(),
+        ));
+    }
+
+    let schedule = TaskSchedule::upsert(
+        &deployment.db().pool,
+        task.id,
+        &UpsertTaskSchedule {
+            cron_expression: payload.cron_expression.trim().to_string(),
+            ..payload
+        },
+        Some(next_run_at),
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_schedule_saved",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "enabled": schedule.enabled,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(schedule)))
+}
+
+pub async fn delete_task_schedule(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    TaskSchedule::delete_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
@@ -0,0 +1,179 @@
+use std::collections::{BTreeMap, HashMap};
+
+use db::models::{label::Label, project_column::ProjectColumn, task::TaskWithAttemptStatus};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// What the swimlanes of the task listing are keyed on
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum SwimlaneGroupBy {
+    /// A task with several labels shows in the lane of each
+    Label,
+    Assignee,
+    /// Parent task
+    Epic,
+}
+
+/// Tasks in one column of the board
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct BoardColumnCount {
+    pub column_id: Uuid,
+    pub name: String,
+    pub count: usize,
+    pub wip_limit: Option<i64>,
+    /// The column holds more tasks than its WIP limit
+    pub over_wip_limit: bool,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct SwimlaneColumnCount {
+    pub column_id: Uuid,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct Swimlane {
+    /// Label id, assignee or parent task id; null for the lane of tasks
+    /// without one
+    pub key: Option<String>,
+    pub name: String,
+    pub count: usize,
+    /// Tasks of the lane per column, in board order
+    pub columns: Vec<SwimlaneColumnCount>,
+    pub tasks: Vec<TaskWithAttemptStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct SwimlaneBoard {
+    pub group_by: SwimlaneGroupBy,
+    /// Counts over every task of the project, regardless of the listing's
+    /// filters, in board order
+    pub columns: Vec<BoardColumnCount>,
+    /// Ordered by name, with the lane of tasks without a key last
+    pub lanes: Vec<Swimlane>,
+}
+
+/// The task listing: a flat list, or swimlanes when `group_by` is set
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(untagged)]
+pub enum TaskListing {
+    Tasks(Vec<TaskWithAttemptStatus>),
+    Swimlanes(SwimlaneBoard),
+}
+
+/// Bucket `tasks`, already filtered and sorted, into swimlanes. `all_tasks`
+/// are every task of the project, for column counts and epic names.
+pub async fn group_tasks(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    all_tasks: &[TaskWithAttemptStatus],
+    tasks: Vec<TaskWithAttemptStatus>,
+    group_by: SwimlaneGroupBy,
+) -> Result<SwimlaneBoard, ApiError> {
+    let pool = &deployment.db().pool;
+    let board_columns = ProjectColumn::find_by_project_id(pool, project_id).await?;
+    let columns = board_columns
+        .iter()
+        .map(|column| {
+            let count = all_tasks
+                .iter()
+                .filter(|task| task.column_id == Some(column.id))
+                .count();
+            BoardColumnCount {
+                column_id: column.id,
+                name: column.name.clone(),
+                count,
+                wip_limit: column.wip_limit,
+                over_wip_limit: column.wip_limit.is_some_and(|limit| count as i64 > limit),
+            }
+        })
+        .collect();
+
+    let mut task_labels: HashMap<Uuid, Vec<(Uuid, String)>> = HashMap::new();
+    if matches!(group_by, SwimlaneGroupBy::Label) {
+        let label_names: HashMap<Uuid, String> = Label::find_all(pool)
+            .await?
+            .into_iter()
+            .map(|label| (label.id, label.name))
+            .collect();
+        for (task_id, label_id) in Label::task_label_ids_for_project(pool, project_id).await? {
+            if let Some(name) = label_names.get(&label_id) {
+                task_labels
+                    .entry(task_id)
+                    .or_default()
+                    .push((label_id, name.clone()));
+            }
+        }
+    }
+    let task_titles: HashMap<Uuid, &str> = all_tasks
+        .iter()
+        .map(|task| (task.id, task.title.as_str()))
+        .collect();
+
+    // Keyed on (is the keyless lane, name, key) so that lanes sort by name
+    // with the keyless lane last
+    let mut lanes: BTreeMap<(bool, String, Option<String>), Vec<TaskWithAttemptStatus>> =
+        BTreeMap::new();
+    for task in tasks {
+        let keys: Vec<(Option<String>, String)> = match group_by {
+            SwimlaneGroupBy::Label => match task_labels.get(&task.id) {
+                Some(labels) => labels
+                    .iter()
+                    .map(|(id, name)| (Some(id.to_string()), name.clone()))
+                    .collect(),
+                None => vec![(None, "No label".to_string())],
+            },
+            SwimlaneGroupBy::Assignee => match &task.assignee {
+                Some(assignee) => vec![(Some(assignee.clone()), assignee.clone())],
+                None => vec![(None, "Unassigned".to_string())],
+            },
+            SwimlaneGroupBy::Epic => match task.parent_task_id {
+                Some(parent_id) => {
+                    let name = task_titles
+                        .get(&parent_id)
+                        .copied()
+                        .unwrap_or("Unknown epic");
+                    vec![(Some(parent_id.to_string()), name.to_string())]
+                }
+                None => vec![(None, "No epic".to_string())],
+            },
+        };
+        for (key, name) in keys {
+            lanes
+                .entry((key.is_none(), name, key))
+                .or_default()
+                .push(task.clone());
+        }
+    }
+
+    let lanes = lanes
+        .into_iter()
+        .map(|((_, name, key), tasks)| Swimlane {
+            key,
+            name,
+            count: tasks.len(),
+            columns: board_columns
+                .iter()
+                .map(|column| SwimlaneColumnCount {
+                    column_id: column.id,
+                    count: tasks
+                        .iter()
+                        .filter(|task| task.column_id == Some(column.id))
+                        .count(),
+                })
+                .collect(),
+            tasks,
+        })
+        .collect();
+
+    Ok(SwimlaneBoard {
+        group_by,
+        columns,
+        lanes,
+    })
+}
@@ -0,0 +1,155 @@
+use db::models::{
+    project_column::ProjectColumn,
+    task::{Task, TaskStatus},
+};
+use deployment::Deployment;
+use services::services::events::{self, wip_limit_patch};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// A task leaving one board column for another
+pub struct ColumnMove {
+    pub from: Option<ProjectColumn>,
+    pub to: ProjectColumn,
+}
+
+/// The columns a task moves between when it is pinned to `column`, or when
+/// its status changes to `status`. None when it stays in its column.
+pub async fn resolve_move(
+    deployment: &DeploymentImpl,
+    task: &Task,
+    column: Option<&ProjectColumn>,
+    status: &TaskStatus,
+) -> Result<Option<ColumnMove>, ApiError> {
+    let pool = &deployment.db().pool;
+    let to = match column {
+        Some(column) => column.clone(),
+        None if *status != task.status => {
+            match ProjectColumn::find_first_for_status(pool, task.project_id, status.clone())
+                .await?
+            {
+                Some(column) => column,
+                None => return Ok(None),
+            }
+        }
+        None => return Ok(None),
+    };
+    let from_id = Task::find_column_id(pool, task.id).await?;
+    if from_id == Some(to.id) {
+        return Ok(None);
+    }
+    let from = match from_id {
+        Some(id) => ProjectColumn::find_by_id(pool, id).await?,
+        None => None,
+    };
+    Ok(Some(ColumnMove { from, to }))
+}
+
+/// Check the WIP limit of the column a task moves into. Enforced limits
+/// reject the move; others let it through with a warning.
+pub async fn check_wip_limit(
+    deployment: &DeploymentImpl,
+    task_id: Uuid,
+    column: &ProjectColumn,
+) -> Result<(), ApiError> {
+    let Some(wip_limit) = column.wip_limit else {
+        return Ok(());
+    };
+    let count = ProjectColumn::task_count(&deployment.db().pool, column.id, Some(task_id)).await?;
+    if count < wip_limit {
+        return Ok(());
+    }
+    if column.enforce_wip_limit {
+        return Err(ApiError::Conflict(format!(
+            "Column '{}' is at its WIP limit of {wip_limit}",
+            column.name
+        )));
+    }
+    tracing::warn!(
+        "Task {} takes column '{}' over its WIP limit ({}/{})",
+        task_id,
+        column.name,
+        count + 1,
+        wip_limit
+    );
+    Ok(())
+}
+
+/// Check the WIP limits of the columns many tasks move into at once. Each
+/// target column is checked against all of the tasks entering it.
+pub async fn check_bulk_wip_limits(
+    deployment: &DeploymentImpl,
+    column_moves: &[ColumnMove],
+) -> Result<(), ApiError> {
+    let mut targets: Vec<(&ProjectColumn, i64)> = Vec::new();
+    for column_move in column_moves {
+        match targets
+            .iter_mut()
+            .find(|(column, _)| column.id == column_move.to.id)
+        {
+            Some((_, moving)) => *moving += 1,
+            None => targets.push((&column_move.to, 1)),
+        }
+    }
+    for (column, moving) in targets {
+        let Some(wip_limit) = column.wip_limit else {
+            continue;
+        };
+        let count = ProjectColumn::task_count(&deployment.db().pool, column.id, None).await?;
+        if count + moving <= wip_limit {
+            continue;
+        }
+        if column.enforce_wip_limit {
+            return Err(ApiError::Conflict(format!(
+                "Moving {moving} tasks takes column '{}' over its WIP limit of {wip_limit}",
+                column.name
+            )));
+        }
+        tracing::warn!(
+            "Bulk move takes column '{}' over its WIP limit ({}/{})",
+            column.name,
+            count + moving,
+            wip_limit
+        );
+    }
+    Ok(())
+}
+
+/// Publish the columns of a move that went over, or back within, their WIP
+/// limit on the events stream
+pub async fn publish_wip_status(
+    deployment: &DeploymentImpl,
+    column_move: &ColumnMove,
+) -> Result<(), ApiError> {
+    let pool = &deployment.db().pool;
+    let msg_store = deployment.events().msg_store();
+    if let Some(status) = column_move.to.wip_status(pool).await?
+        && status.is_over_limit()
+    {
+        msg_store.push_patch(wip_limit_patch::add(&status));
+    }
+    // The column held one task more before the move
+    if let Some(from) = &column_move.from
+        && let Some(status) = from.wip_status(pool).await?
+        && status.count == status.wip_limit
+    {
+        msg_store.push_patch(wip_limit_patch::remove(from.id));
+    }
+    Ok(())
+}
+
+/// Publish the WIP status of every limited column in a project, for changes
+/// that may have moved several tasks at once
+pub async fn publish_project_wip_status(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+) -> Result<(), ApiError> {
+    events::publish_project_wip_status(
+        &deployment.db().pool,
+        deployment.events().msg_store(),
+        project_id,
+    )
+    .await?;
+    Ok(())
+}
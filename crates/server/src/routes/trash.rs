@@ -0,0 +1,18 @@
+use axum::{Router, extract::State, response::Json as ResponseJson, routing::get};
+use db::models::trash::TrashItem;
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Deleted tasks and projects that can still be restored
+pub async fn get_trash(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TrashItem>>>, ApiError> {
+    let items = TrashItem::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(items)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/trash", get(get_trash))
+}
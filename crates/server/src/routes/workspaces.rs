@@ -0,0 +1,200 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    middleware::from_fn_with_state,
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::{
+    attempt_usage::{AttemptUsage, UsageTotals},
+    project::Project,
+    task::{Task, TaskWithAttemptStatus},
+    workspace::{CreateWorkspace, UpdateWorkspace, Workspace, WorkspaceProjectStats},
+};
+use deployment::Deployment;
+use serde::Serialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::load_workspace_middleware};
+
+/// Combined stats of the projects in a workspace
+#[derive(Debug, Serialize, TS)]
+pub struct WorkspaceStats {
+    pub projects: Vec<WorkspaceProjectStats>,
+    pub usage: UsageTotals,
+}
+
+pub async fn get_workspaces(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Workspace>>>, ApiError> {
+    let workspaces = Workspace::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(workspaces)))
+}
+
+pub async fn get_workspace(
+    Extension(workspace): Extension<Workspace>,
+) -> Result<ResponseJson<ApiResponse<Workspace>>, ApiError> {
+    Ok(Json(ApiResponse::success(workspace)))
+}
+
+pub async fn create_workspace(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateWorkspace>,
+) -> Result<ResponseJson<ApiResponse<Workspace>>, ApiError> {
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Workspace name cannot be empty".to_string(),
+        ));
+    }
+    let workspace = Workspace::create(
+        &deployment.db().pool,
+        &CreateWorkspace {
+            name: name.to_string(),
+            ..payload
+        },
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "workspace_created",
+            serde_json::json!({
+                "workspace_id": workspace.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(workspace)))
+}
+
+pub async fn update_workspace(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpdateWorkspace>,
+) -> Result<ResponseJson<ApiResponse<Workspace>>, ApiError> {
+    let name = payload.name.as_deref().map(str::trim);
+    if name.is_some_and(str::is_empty) {
+        return Err(ApiError::BadRequest(
+            "Workspace name cannot be empty".to_string(),
+        ));
+    }
+    let updated = Workspace::update(
+        &deployment.db().pool,
+        workspace.id,
+        &UpdateWorkspace {
+            name: name.map(str::to_string),
+            description: payload.description,
+        },
+    )
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+/// Delete the workspace; its projects are kept and become ungrouped
+pub async fn delete_workspace(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = Workspace::delete(&deployment.db().pool, workspace.id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+pub async fn get_workspace_projects(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Project>>>, ApiError> {
+    let projects = Project::find_by_workspace_id(&deployment.db().pool, workspace.id).await?;
+    Ok(ResponseJson(ApiResponse::success(projects)))
+}
+
+/// Board of every project in the workspace. Each task carries its
+/// `project_id`, so clients can tell the projects apart.
+pub async fn get_workspace_tasks(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskWithAttemptStatus>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let mut tasks = Vec::new();
+    for project in Project::find_by_workspace_id(pool, workspace.id).await? {
+        tasks.extend(Task::find_by_project_id_with_attempt_status(pool, project.id).await?);
+    }
+    tasks.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(ResponseJson(ApiResponse::success(tasks)))
+}
+
+pub async fn get_workspace_stats(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<WorkspaceStats>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let stats = WorkspaceStats {
+        projects: Workspace::project_stats(pool, workspace.id).await?,
+        usage: AttemptUsage::totals_for_workspace(pool, workspace.id).await?,
+    };
+    Ok(ResponseJson(ApiResponse::success(stats)))
+}
+
+pub async fn add_workspace_project(
+    State(deployment): State<DeploymentImpl>,
+    Path((workspace_id, project_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if Workspace::find_by_id(pool, workspace_id).await?.is_none() {
+        return Err(ApiError::Database(sqlx::Error::RowNotFound));
+    }
+    if Project::set_workspace(pool, project_id, Some(workspace_id)).await? == 0 {
+        return Err(ApiError::BadRequest("Project not found".to_string()));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub async fn remove_workspace_project(
+    State(deployment): State<DeploymentImpl>,
+    Path((workspace_id, project_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let project = Project::find_by_id(pool, project_id)
+        .await?
+        .filter(|project| project.workspace_id == Some(workspace_id))
+        .ok_or(ApiError::BadRequest(
+            "Project not found in this workspace".to_string(),
+        ))?;
+    Project::set_workspace(pool, project.id, None).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    let workspace_router = Router::new()
+        .route(
+            "/",
+            get(get_workspace)
+                .put(update_workspace)
+                .delete(delete_workspace),
+        )
+        .route("/projects", get(get_workspace_projects))
+        .route("/tasks", get(get_workspace_tasks))
+        .route("/stats", get(get_workspace_stats))
+        .layer(from_fn_with_state(
+            deployment.clone(),
+            load_workspace_middleware,
+        ))
+        // Added after the workspace loader layer: this route carries a second
+        // path parameter, so the handlers load the workspace themselves
+        .route(
+            "/projects/{project_id}",
+            put(add_workspace_project).delete(remove_workspace_project),
+        );
+
+    let inner = Router::new()
+        .route("/", get(get_workspaces).post(create_workspace))
+        .nest("/{workspace_id}", workspace_router);
+
+    Router::new().nest("/workspaces", inner)
+}
@@ -29,8 +29,7 @@ use uuid::Uuid;
 struct PendingApproval {
     entry_index: usize,
     entry: NormalizedEntry,
-    execution_process_id: Uuid,
-    tool_name: String,
+    request: ApprovalRequest,
     response_tx: oneshot::Sender<ApprovalStatus>,
 }
 
@@ -104,8 +103,7 @@ impl Approvals {
                     PendingApproval {
                         entry_index: idx,
                         entry: matching_tool,
-                        execution_process_id: request.execution_process_id,
-                        tool_name: request.tool_name.clone(),
+                        request: request.clone(),
                         response_tx: tx,
                     },
                 );
@@ -139,36 +137,47 @@ impl Approvals {
         pool: &SqlitePool,
         id: &str,
         req: ApprovalResponse,
+    ) -> Result<(ApprovalStatus, ToolContext), ApprovalError> {
+        self.resolve(pool, id, req.status).await
+    }
+
+    /// Settle a pending request with `status`, unblocking the waiting tool call
+    #[tracing::instrument(skip(self, id, status))]
+    pub async fn resolve(
+        &self,
+        pool: &SqlitePool,
+        id: &str,
+        status: ApprovalStatus,
     ) -> Result<(ApprovalStatus, ToolContext), ApprovalError> {
         if let Some((_, p)) = self.pending.remove(id) {
-            self.completed.insert(id.to_string(), req.status.clone());
-            let _ = p.response_tx.send(req.status.clone());
+            self.completed.insert(id.to_string(), status.clone());
+            let _ = p.response_tx.send(status.clone());
 
-            if let Some(store) = self.msg_store_by_id(&p.execution_process_id).await {
-                let status = ToolStatus::from_approval_status(&req.status).ok_or(
+            if let Some(store) = self.msg_store_by_id(&p.request.execution_process_id).await {
+                let tool_status = ToolStatus::from_approval_status(&status).ok_or(
                     ApprovalError::Custom(anyhow::anyhow!("Invalid approval status")),
                 )?;
                 let updated_entry = p
                     .entry
-                    .with_tool_status(status)
+                    .with_tool_status(tool_status)
                     .ok_or(ApprovalError::NoToolUseEntry)?;
 
                 store.push_patch(ConversationPatch::replace(p.entry_index, updated_entry));
             } else {
                 tracing::warn!(
                     "No msg_store found for execution_process_id: {}",
-                    p.execution_process_id
+                    p.request.execution_process_id
                 );
             }
 
             let tool_ctx = ToolContext {
-                tool_name: p.tool_name,
-                execution_process_id: p.execution_process_id,
+                tool_name: p.request.tool_name,
+                execution_process_id: p.request.execution_process_id,
             };
 
             // If approved or denied, and task is still InReview, move back to InProgress
             if matches!(
-                req.status,
+                status,
                 ApprovalStatus::Approved | ApprovalStatus::Denied { .. }
             ) && let Ok(ctx) =
                 ExecutionProcess::load_context(pool, tool_ctx.execution_process_id).await
@@ -181,7 +190,7 @@ impl Approvals {
                 );
             }
 
-            Ok((req.status, tool_ctx))
+            Ok((status, tool_ctx))
         } else if self.completed.contains_key(id) {
             Err(ApprovalError::AlreadyCompleted)
         } else {
@@ -224,7 +233,8 @@ impl Approvals {
 
                 let store = {
                     let map = msg_stores.read().await;
-                    map.get(&pending_approval.execution_process_id).cloned()
+                    map.get(&pending_approval.request.execution_process_id)
+                        .cloned()
                 };
 
                 if let Some(store) = store {
@@ -245,13 +255,25 @@ impl Approvals {
                 } else {
                     tracing::warn!(
                         "No msg_store found for execution_process_id: {}",
-                        pending_approval.execution_process_id
+                        pending_approval.request.execution_process_id
                     );
                 }
             }
         });
     }
 
+    /// Requests still waiting for a response, oldest first
+    pub fn pending_for_executions(&self, execution_process_ids: &[Uuid]) -> Vec<ApprovalRequest> {
+        let mut requests: Vec<ApprovalRequest> = self
+            .pending
+            .iter()
+            .filter(|entry| execution_process_ids.contains(&entry.request.execution_process_id))
+            .map(|entry| entry.request.clone())
+            .collect();
+        requests.sort_by_key(|request| request.created_at);
+        requests
+    }
+
     async fn msg_store_by_id(&self, execution_process_id: &Uuid) -> Option<Arc<MsgStore>> {
         let map = self.msg_stores.read().await;
         map.get(execution_process_id).cloned()
@@ -398,4 +420,34 @@ mod tests {
             "Should not match different tool ids"
         );
     }
+
+    #[test]
+    fn test_project_approval_rule_matching() {
+        use db::models::project_approval_rule::ProjectApprovalRule;
+        use serde_json::json;
+
+        let rule = |tool_name: &str, pattern: Option<&str>| ProjectApprovalRule {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            tool_name: tool_name.to_string(),
+            pattern: pattern.map(str::to_string),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+
+        // Claude Code passes the command as a string, Codex as argv
+        let cargo_test = rule("bash", Some("cargo test*"));
+        assert!(cargo_test.matches("Bash", &json!({ "command": "cargo test -p db" })));
+        assert!(cargo_test.matches("bash", &json!({ "command": ["cargo", "test"] })));
+        assert!(!cargo_test.matches("bash", &json!({ "command": "cargo publish" })));
+        assert!(!cargo_test.matches("edit", &json!({ "command": "cargo test" })));
+
+        let docs = rule("*", Some("*/docs/*.md"));
+        assert!(docs.matches("Edit", &json!({ "file_path": "/repo/docs/intro.md" })));
+        assert!(!docs.matches("Edit", &json!({ "file_path": "/repo/src/main.rs" })));
+        assert!(!docs.matches("Edit", &json!({ "changes": {} })));
+
+        assert!(rule("Read", None).matches("read", &json!({})));
+        assert!(!rule("bash", Some("ls")).matches("bash", &json!({ "command": "ls -la" })));
+    }
 }
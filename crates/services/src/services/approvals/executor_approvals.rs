@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use db::{self, DBService};
+use db::{
+    self, DBService,
+    models::{execution_process::ExecutionProcess, project_approval_rule::ProjectApprovalRule},
+};
 use executors::approvals::{ExecutorApprovalError, ExecutorApprovalService};
 use serde_json::Value;
 use utils::approvals::{ApprovalRequest, ApprovalStatus, CreateApprovalRequest};
@@ -25,6 +28,28 @@ impl ExecutorApprovalBridge {
     }
 }
 
+impl ExecutorApprovalBridge {
+    /// Whether one of the project's approval rules covers the call. Rules
+    /// that cannot be loaded approve nothing, so the user is asked instead.
+    async fn auto_approved(&self, tool_name: &str, tool_input: &Value) -> bool {
+        let pool = &self.db.pool;
+        let rules = match ExecutionProcess::load_context(pool, self.execution_process_id).await {
+            Ok(ctx) => ProjectApprovalRule::find_by_project_id(pool, ctx.task.project_id).await,
+            Err(e) => {
+                tracing::warn!("Failed to load context for approval rules: {}", e);
+                return false;
+            }
+        };
+        match rules {
+            Ok(rules) => rules.iter().any(|rule| rule.matches(tool_name, tool_input)),
+            Err(e) => {
+                tracing::warn!("Failed to load project approval rules: {}", e);
+                false
+            }
+        }
+    }
+}
+
 #[async_trait]
 impl ExecutorApprovalService for ExecutorApprovalBridge {
     async fn request_tool_approval(
@@ -33,6 +58,15 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
         tool_input: Value,
         tool_call_id: &str,
     ) -> Result<ApprovalStatus, ExecutorApprovalError> {
+        if self.auto_approved(tool_name, &tool_input).await {
+            tracing::debug!(
+                "Tool call {} of '{}' approved by a project rule",
+                tool_call_id,
+                tool_name
+            );
+            return Ok(ApprovalStatus::Approved);
+        }
+
         super::ensure_task_in_review(&self.db.pool, self.execution_process_id).await;
 
         let request = ApprovalRequest::from_create(
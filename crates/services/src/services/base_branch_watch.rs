@@ -0,0 +1,91 @@
+use db::models::{attempt_base_status::AttemptBaseStatus, project_rebase_policy::RebasePolicyMode};
+
+/// What the base branch watcher does with an attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseBranchAction {
+    /// The attempt branch has every commit of its base branch
+    UpToDate,
+    /// Record that the attempt is behind its base branch
+    MarkStale,
+    /// Rebase the idle attempt onto its base branch
+    Rebase,
+}
+
+/// Decide what to do with an attempt `commits_behind` its base branch, whose
+/// tip is `base_commit`. Attempts are only rebased while idle, and not again
+/// onto a base commit they already failed to rebase onto.
+pub fn base_branch_action(
+    mode: RebasePolicyMode,
+    commits_behind: usize,
+    idle: bool,
+    previous: Option<&AttemptBaseStatus>,
+    base_commit: &str,
+) -> BaseBranchAction {
+    if commits_behind == 0 {
+        return BaseBranchAction::UpToDate;
+    }
+    let failed_before = previous
+        .is_some_and(|status| status.base_commit == base_commit && status.rebase_error.is_some());
+    if mode == RebasePolicyMode::Rebase && idle && !failed_before {
+        BaseBranchAction::Rebase
+    } else {
+        BaseBranchAction::MarkStale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn status(base_commit: &str, rebase_error: Option<&str>) -> AttemptBaseStatus {
+        AttemptBaseStatus {
+            task_attempt_id: Uuid::new_v4(),
+            base_commit: base_commit.to_string(),
+            commits_behind: 2,
+            stale_since: Some(Utc::now()),
+            rebased_at: None,
+            rebase_error: rebase_error.map(str::to_string),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn rebases_idle_attempts_once_per_base_commit() {
+        use RebasePolicyMode::{Flag, Rebase};
+
+        assert_eq!(
+            base_branch_action(Rebase, 0, true, None, "b1"),
+            BaseBranchAction::UpToDate
+        );
+        assert_eq!(
+            base_branch_action(Flag, 3, true, None, "b1"),
+            BaseBranchAction::MarkStale
+        );
+        assert_eq!(
+            base_branch_action(Rebase, 3, true, None, "b1"),
+            BaseBranchAction::Rebase
+        );
+        assert_eq!(
+            base_branch_action(Rebase, 3, false, None, "b1"),
+            BaseBranchAction::MarkStale
+        );
+
+        let failed = status("b1", Some("conflicts"));
+        assert_eq!(
+            base_branch_action(Rebase, 3, true, Some(&failed), "b1"),
+            BaseBranchAction::MarkStale
+        );
+        assert_eq!(
+            base_branch_action(Rebase, 4, true, Some(&failed), "b2"),
+            BaseBranchAction::Rebase
+        );
+        let flagged = status("b1", None);
+        assert_eq!(
+            base_branch_action(Rebase, 3, true, Some(&flagged), "b1"),
+            BaseBranchAction::Rebase
+        );
+    }
+}
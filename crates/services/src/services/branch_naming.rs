@@ -0,0 +1,206 @@
+//! Attempt branch names rendered from templates such as `feat/{ticket}-{slug}`
+
+use chrono::{NaiveDate, Utc};
+use thiserror::Error;
+use utils::text::{git_branch_id, short_uuid};
+use uuid::Uuid;
+
+/// Used for projects without a template of their own
+pub const DEFAULT_BRANCH_TEMPLATE: &str = "{prefix}/{id}-{slug}";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BranchTemplateError {
+    #[error("Branch template is empty")]
+    Empty,
+    #[error("Unknown placeholder {{{0}}} in branch template")]
+    UnknownPlaceholder(String),
+    #[error("Unbalanced braces in branch template")]
+    UnbalancedBraces,
+    #[error("Branch template gives an invalid branch name: {0}")]
+    InvalidBranchName(String),
+}
+
+/// Values of the placeholders for one attempt
+pub struct BranchNameContext<'a> {
+    /// `git_branch_prefix` from the config
+    pub prefix: &'a str,
+    /// Key of the tracker ticket or number of the GitHub issue the task is
+    /// linked to
+    pub ticket: Option<&'a str>,
+    pub task_title: &'a str,
+    pub attempt_id: &'a Uuid,
+    /// 1 for the task's first attempt
+    pub attempt_number: i64,
+    pub executor: &'a str,
+    pub date: NaiveDate,
+}
+
+/// Keep the characters of a ticket key that are safe in a branch name
+fn ticket_component(ticket: &str) -> String {
+    ticket
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+fn placeholder_value(
+    name: &str,
+    context: &BranchNameContext,
+) -> Result<String, BranchTemplateError> {
+    Ok(match name {
+        "prefix" => context.prefix.to_string(),
+        "ticket" => context.ticket.map(ticket_component).unwrap_or_default(),
+        "slug" => git_branch_id(context.task_title),
+        "id" => short_uuid(context.attempt_id),
+        "attempt" => context.attempt_number.to_string(),
+        "executor" => git_branch_id(context.executor),
+        "date" => context.date.format("%Y%m%d").to_string(),
+        other => return Err(BranchTemplateError::UnknownPlaceholder(other.to_string())),
+    })
+}
+
+/// Drop the separators empty placeholders leave behind, e.g. `feat/-slug`
+/// without a ticket becomes `feat/slug`
+fn tidy(name: &str) -> String {
+    name.split('/')
+        .map(|component| {
+            let mut tidied = String::with_capacity(component.len());
+            for c in component.chars() {
+                if c == '-' && tidied.ends_with('-') {
+                    continue;
+                }
+                tidied.push(c);
+            }
+            tidied.trim_matches(['-', '_', '.']).to_string()
+        })
+        .filter(|component| !component.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Branch name for an attempt from `template`
+pub fn render_branch_name(
+    template: &str,
+    context: &BranchNameContext,
+) -> Result<String, BranchTemplateError> {
+    if template.trim().is_empty() {
+        return Err(BranchTemplateError::Empty);
+    }
+    let mut rendered = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err(BranchTemplateError::UnbalancedBraces);
+        }
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let end = after
+            .find('}')
+            .ok_or(BranchTemplateError::UnbalancedBraces)?;
+        rendered.push_str(&placeholder_value(&after[..end], context)?);
+        rest = &after[end + 1..];
+    }
+    rendered.push_str(rest);
+
+    let name = tidy(&rendered);
+    if name.is_empty() || !git2::Branch::name_is_valid(&name).unwrap_or(false) {
+        return Err(BranchTemplateError::InvalidBranchName(name));
+    }
+    Ok(name)
+}
+
+/// Check a template before it is saved by rendering it for a sample attempt
+pub fn validate_branch_template(template: &str) -> Result<(), BranchTemplateError> {
+    let context = BranchNameContext {
+        prefix: "vk",
+        ticket: Some("ABC-123"),
+        task_title: "Add login page",
+        attempt_id: &Uuid::nil(),
+        attempt_number: 1,
+        executor: "CLAUDE_CODE",
+        date: Utc::now().date_naive(),
+    };
+    render_branch_name(template, &context).map(|_| ())
+}
+
+/// `name` for the first try at a branch that doesn't exist yet, then
+/// `name-2`, `name-3`, …
+pub fn numbered_branch_name(name: &str, try_number: usize) -> String {
+    if try_number <= 1 {
+        name.to_string()
+    } else {
+        format!("{name}-{try_number}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context<'a>(ticket: Option<&'a str>, attempt_id: &'a Uuid) -> BranchNameContext<'a> {
+        BranchNameContext {
+            prefix: "vk",
+            ticket,
+            task_title: "Fix the login redirect!",
+            attempt_id,
+            attempt_number: 3,
+            executor: "CLAUDE_CODE",
+            date: NaiveDate::from_ymd_opt(2025, 12, 8).unwrap(),
+        }
+    }
+
+    #[test]
+    fn renders_placeholders_and_tidies_empty_ones() {
+        let id = Uuid::parse_str("a1b2c3d4-0000-0000-0000-000000000000").unwrap();
+        assert_eq!(
+            render_branch_name("feat/{ticket}-{slug}", &context(Some("PROJ-42"), &id)).unwrap(),
+            "feat/PROJ-42-fix-the-login-re"
+        );
+        assert_eq!(
+            render_branch_name("feat/{ticket}-{slug}", &context(None, &id)).unwrap(),
+            "feat/fix-the-login-re"
+        );
+        assert_eq!(
+            render_branch_name("{executor}/{date}-{attempt}", &context(None, &id)).unwrap(),
+            "claude-code/20251208-3"
+        );
+        assert_eq!(
+            render_branch_name(DEFAULT_BRANCH_TEMPLATE, &context(None, &id)).unwrap(),
+            "vk/a1b2-fix-the-login-re"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_templates() {
+        assert_eq!(
+            validate_branch_template("feat/{title}"),
+            Err(BranchTemplateError::UnknownPlaceholder("title".to_string()))
+        );
+        assert_eq!(
+            validate_branch_template("feat/{slug"),
+            Err(BranchTemplateError::UnbalancedBraces)
+        );
+        assert_eq!(
+            validate_branch_template("  "),
+            Err(BranchTemplateError::Empty)
+        );
+        assert!(matches!(
+            validate_branch_template("feat..{slug}"),
+            Err(BranchTemplateError::InvalidBranchName(_))
+        ));
+        assert!(validate_branch_template("feat/{ticket}-{slug}").is_ok());
+    }
+
+    #[test]
+    fn numbers_colliding_names() {
+        assert_eq!(numbered_branch_name("feat/x", 1), "feat/x");
+        assert_eq!(numbered_branch_name("feat/x", 2), "feat/x-2");
+    }
+}
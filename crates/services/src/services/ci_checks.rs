@@ -0,0 +1,25 @@
+use db::models::ci_check::CiCheck;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::services::hosting::{HostingError, HostingProvider};
+
+/// Copy the CI checks of an attempt's pushed branch from its code host,
+/// updating changed ones and forgetting those that didn't run on the
+/// branch's latest commit. Returns the attempt's checks.
+pub async fn sync_ci_checks(
+    pool: &SqlitePool,
+    provider: &dyn HostingProvider,
+    remote_url: &str,
+    task_attempt_id: Uuid,
+    branch: &str,
+) -> Result<Vec<CiCheck>, HostingError> {
+    let checks = provider.list_ci_checks(remote_url, branch).await?;
+    // Delete first so the upserts that follow bring the board up to date
+    let names: Vec<String> = checks.iter().map(|check| check.name.clone()).collect();
+    CiCheck::delete_missing(pool, task_attempt_id, &names).await?;
+    for check in &checks {
+        CiCheck::upsert(pool, task_attempt_id, check).await?;
+    }
+    Ok(CiCheck::find_by_task_attempt_id(pool, task_attempt_id).await?)
+}
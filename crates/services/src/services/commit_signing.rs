@@ -0,0 +1,133 @@
+//! Signing commits with a GPG or SSH key. The git CLI signs the commits it
+//! makes itself when run with [`git_config`]; commits created through libgit2
+//! are signed with [`sign`].
+use std::{
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+use serde::Serialize;
+use thiserror::Error;
+use ts_rs::TS;
+use utils::shell::resolve_executable_path_blocking;
+
+use crate::services::config::{CommitSigningConfig, SigningFormat};
+
+#[derive(Debug, Error)]
+pub enum CommitSigningError {
+    #[error("Failed to run {0}: {1}")]
+    Spawn(String, std::io::Error),
+    #[error("{0} failed to sign the commit: {1}")]
+    Failed(String, String),
+}
+
+/// A commit of an attempt branch and whether it carries a signature. The
+/// signature isn't checked against trusted keys; the code host does that.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct CommitSignature {
+    pub sha: String,
+    pub subject: String,
+    pub signed: bool,
+}
+
+fn program(signing: &CommitSigningConfig) -> &str {
+    signing.program.as_deref().unwrap_or(match signing.format {
+        SigningFormat::Gpg => "gpg",
+        SigningFormat::Ssh => "ssh-keygen",
+    })
+}
+
+/// Config that makes git sign the commits it creates, including the ones it
+/// rewrites during a rebase
+pub fn git_config(signing: &CommitSigningConfig) -> Vec<(String, String)> {
+    let (format, program_key) = match signing.format {
+        SigningFormat::Gpg => ("openpgp", "gpg.program"),
+        SigningFormat::Ssh => ("ssh", "gpg.ssh.program"),
+    };
+    let mut config = vec![
+        ("commit.gpgsign".to_string(), "true".to_string()),
+        ("gpg.format".to_string(), format.to_string()),
+        ("user.signingkey".to_string(), signing.key.clone()),
+    ];
+    if let Some(program) = &signing.program {
+        config.push((program_key.to_string(), program.clone()));
+    }
+    config
+}
+
+/// Detached signature of a commit's content, the way git invokes `gpg` and
+/// `ssh-keygen` for it
+pub fn sign(signing: &CommitSigningConfig, content: &str) -> Result<String, CommitSigningError> {
+    let program = program(signing).to_string();
+    let args = match signing.format {
+        SigningFormat::Gpg => vec!["--status-fd=2", "-bsau", signing.key.as_str()],
+        SigningFormat::Ssh => vec!["-Y", "sign", "-n", "git", "-f", signing.key.as_str()],
+    };
+    let executable =
+        resolve_executable_path_blocking(&program).unwrap_or_else(|| PathBuf::from(&program));
+    let mut child = Command::new(executable)
+        .args(&args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| CommitSigningError::Spawn(program.clone(), e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(content.as_bytes())
+            .map_err(|e| CommitSigningError::Spawn(program.clone(), e))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| CommitSigningError::Spawn(program.clone(), e))?;
+
+    let signature = String::from_utf8_lossy(&output.stdout).into_owned();
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(CommitSigningError::Failed(program, stderr));
+    }
+    if signature.trim().is_empty() {
+        return Err(CommitSigningError::Failed(
+            program,
+            "no signature was written".to_string(),
+        ));
+    }
+    Ok(signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn git_config_selects_the_format_and_program() {
+        let gpg = CommitSigningConfig {
+            format: SigningFormat::Gpg,
+            key: "ABCD1234".to_string(),
+            program: None,
+            require_signed: false,
+        };
+        assert_eq!(
+            git_config(&gpg),
+            vec![
+                ("commit.gpgsign".to_string(), "true".to_string()),
+                ("gpg.format".to_string(), "openpgp".to_string()),
+                ("user.signingkey".to_string(), "ABCD1234".to_string()),
+            ]
+        );
+
+        let ssh = CommitSigningConfig {
+            format: SigningFormat::Ssh,
+            key: "/home/me/.ssh/id_ed25519.pub".to_string(),
+            program: Some("/opt/bin/op-ssh-sign".to_string()),
+            require_signed: true,
+        };
+        let config = git_config(&ssh);
+        assert!(config.contains(&("gpg.format".to_string(), "ssh".to_string())));
+        assert!(config.contains(&(
+            "gpg.ssh.program".to_string(),
+            "/opt/bin/op-ssh-sign".to_string()
+        )));
+    }
+}
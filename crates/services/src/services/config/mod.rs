@@ -23,6 +23,15 @@ pub type EditorType = versions::v7::EditorType;
 pub type GitHubConfig = versions::v7::GitHubConfig;
 pub type UiLanguage = versions::v7::UiLanguage;
 pub type ShowcaseState = versions::v7::ShowcaseState;
+pub type WorktreeCleanupConfig = versions::v7::WorktreeCleanupConfig;
+pub type IntegrationsConfig = versions::v7::IntegrationsConfig;
+pub type JiraConfig = versions::v7::JiraConfig;
+pub type LinearConfig = versions::v7::LinearConfig;
+pub type HostingConfig = versions::v7::HostingConfig;
+pub type GitLabConfig = versions::v7::GitLabConfig;
+pub type BitbucketConfig = versions::v7::BitbucketConfig;
+pub type SigningFormat = versions::v7::SigningFormat;
+pub type CommitSigningConfig = versions::v7::CommitSigningConfig;
 
 /// Will always return config, trying old schemas or eventually returning default
 pub async fn load_config_from_file(config_path: &PathBuf) -> Config {
@@ -17,6 +17,114 @@ pub struct ShowcaseState {
     pub seen_features: Vec<String>,
 }
 
+fn default_worktree_max_age_days() -> Option<u32> {
+    Some(3)
+}
+
+fn default_trash_retention_days() -> Option<u32> {
+    Some(30)
+}
+
+/// When attempt worktrees are deleted. Branches and logs are always kept.
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct WorktreeCleanupConfig {
+    /// Delete an attempt's worktree as soon as its changes are merged
+    #[serde(default)]
+    pub delete_on_merge: bool,
+    /// Delete worktrees idle for this many days; null keeps them forever
+    #[serde(default = "default_worktree_max_age_days")]
+    pub max_age_days: Option<u32>,
+}
+
+impl Default for WorktreeCleanupConfig {
+    fn default() -> Self {
+        Self {
+            delete_on_merge: false,
+            max_age_days: default_worktree_max_age_days(),
+        }
+    }
+}
+
+/// Jira Cloud credentials; the API token is used with basic auth
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct JiraConfig {
+    /// Site URL, e.g. https://acme.atlassian.net
+    pub base_url: String,
+    pub email: String,
+    pub api_token: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct LinearConfig {
+    /// Personal API key
+    pub api_key: String,
+}
+
+/// Connections to external issue trackers
+#[derive(Clone, Debug, Serialize, Deserialize, TS, Default)]
+pub struct IntegrationsConfig {
+    #[serde(default)]
+    pub jira: Option<JiraConfig>,
+    #[serde(default)]
+    pub linear: Option<LinearConfig>,
+}
+
+fn default_gitlab_base_url() -> String {
+    "https://gitlab.com".to_string()
+}
+
+/// GitLab credentials for gitlab.com or a self-hosted instance
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct GitLabConfig {
+    /// Instance URL, e.g. https://gitlab.example.com
+    #[serde(default = "default_gitlab_base_url")]
+    pub base_url: String,
+    /// Personal access token with the `api` scope
+    pub token: String,
+}
+
+/// Bitbucket Cloud credentials; the app password is used with basic auth
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct BitbucketConfig {
+    /// Bitbucket username, not the account email
+    pub username: String,
+    /// App password with repository write and pull request write permissions
+    pub app_password: String,
+}
+
+/// Code hosts pull requests can be opened on besides GitHub
+#[derive(Clone, Debug, Serialize, Deserialize, TS, Default)]
+pub struct HostingConfig {
+    #[serde(default)]
+    pub gitlab: Option<GitLabConfig>,
+    #[serde(default)]
+    pub bitbucket: Option<BitbucketConfig>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SigningFormat {
+    Gpg,
+    Ssh,
+}
+
+/// How commits made by Vibe Kanban and by executors in attempt worktrees are
+/// signed
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct CommitSigningConfig {
+    pub format: SigningFormat,
+    /// GPG key id, or the path of an SSH private key (or of its public key
+    /// when the private key is in ssh-agent)
+    pub key: String,
+    /// Program to sign with instead of `gpg` or `ssh-keygen`
+    #[serde(default)]
+    pub program: Option<String>,
+    /// Refuse to push attempt branches or open pull requests for them while
+    /// they have unsigned commits
+    #[serde(default)]
+    pub require_signed: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS, EnumString)]
 #[ts(use_ts_enum)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -49,6 +157,25 @@ pub struct Config {
     pub git_branch_prefix: String,
     #[serde(default)]
     pub showcases: ShowcaseState,
+    #[serde(default)]
+    pub max_concurrent_executions: Option<u32>,
+    #[serde(default)]
+    pub max_concurrent_executions_per_project: Option<u32>,
+    #[serde(default)]
+    pub worktree_cleanup: WorktreeCleanupConfig,
+    /// Refuse to start attempts on tasks whose blocking tasks are not done
+    #[serde(default)]
+    pub enforce_task_dependencies: bool,
+    #[serde(default)]
+    pub integrations: IntegrationsConfig,
+    /// Days deleted tasks and projects stay in the trash; null keeps them
+    /// until restored
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: Option<u32>,
+    #[serde(default)]
+    pub hosting: HostingConfig,
+    #[serde(default)]
+    pub commit_signing: Option<CommitSigningConfig>,
 }
 
 impl Config {
@@ -99,6 +226,14 @@ impl Config {
             language: old_config.language,
             git_branch_prefix: default_git_branch_prefix(),
             showcases: ShowcaseState::default(),
+            max_concurrent_executions: None,
+            max_concurrent_executions_per_project: None,
+            worktree_cleanup: WorktreeCleanupConfig::default(),
+            enforce_task_dependencies: false,
+            integrations: IntegrationsConfig::default(),
+            trash_retention_days: default_trash_retention_days(),
+            hosting: HostingConfig::default(),
+            commit_signing: None,
         })
     }
 }
@@ -144,6 +279,14 @@ impl Default for Config {
             language: UiLanguage::default(),
             git_branch_prefix: default_git_branch_prefix(),
             showcases: ShowcaseState::default(),
+            max_concurrent_executions: None,
+            max_concurrent_executions_per_project: None,
+            worktree_cleanup: WorktreeCleanupConfig::default(),
+            enforce_task_dependencies: false,
+            integrations: IntegrationsConfig::default(),
+            trash_retention_days: default_trash_retention_days(),
+            hosting: HostingConfig::default(),
+            commit_signing: None,
         }
     }
 }
@@ -0,0 +1,405 @@
+use std::path::Path;
+
+use git2::{IndexEntry, Repository};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use ts_rs::TS;
+
+use super::{
+    git::ConflictOp,
+    git_cli::{GitCli, GitCliError},
+};
+
+#[derive(Debug, Error)]
+pub enum ConflictError {
+    #[error(transparent)]
+    Git(#[from] git2::Error),
+    #[error(transparent)]
+    GitCli(#[from] GitCliError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("{0} has no unresolved conflict")]
+    NotConflicted(String),
+    #[error("Invalid resolution for {0}: {1}")]
+    InvalidResolution(String, String),
+}
+
+/// Part of a conflicted file, as merged line by line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum ConflictSegment {
+    /// Lines that merged cleanly
+    Clean { text: String },
+    /// Lines both sides changed differently, with their merge base version
+    Conflict {
+        base: String,
+        ours: String,
+        theirs: String,
+    },
+}
+
+/// A file with an unresolved conflict. During a rebase "ours" is the branch
+/// being rebased onto and "theirs" the attempt's commit being replayed;
+/// during a merge "ours" is the attempt branch.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ConflictedFile {
+    pub path: String,
+    pub ours_deleted: bool,
+    pub theirs_deleted: bool,
+    pub binary: bool,
+    /// The file split into merged and conflicting parts; empty when it cannot
+    /// be merged line by line because it is binary or one side deleted it
+    pub segments: Vec<ConflictSegment>,
+}
+
+/// How to resolve one conflicting part of a file
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum HunkResolution {
+    Ours,
+    Theirs,
+    Base,
+    /// Our lines followed by theirs
+    Both,
+    Custom {
+        text: String,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct FileResolution {
+    pub path: String,
+    /// One per conflict segment of the file, in order. Files without
+    /// segments take a single `ours`, `theirs` or `custom` resolution for the
+    /// whole file, where picking a side that deleted the file deletes it.
+    pub resolutions: Vec<HunkResolution>,
+}
+
+struct ConflictSides {
+    path: String,
+    base: Option<Vec<u8>>,
+    ours: Option<Vec<u8>>,
+    theirs: Option<Vec<u8>>,
+}
+
+/// The unresolved conflicts in the worktree, in path order
+pub fn conflicted_files(worktree_path: &Path) -> Result<Vec<ConflictedFile>, ConflictError> {
+    read_conflicts(worktree_path)?
+        .iter()
+        .map(describe)
+        .collect()
+}
+
+/// Write the resolved content of each file to the worktree and stage it.
+/// Every file is checked before any is written.
+pub fn resolve_files(
+    worktree_path: &Path,
+    resolutions: &[FileResolution],
+) -> Result<(), ConflictError> {
+    let conflicts = read_conflicts(worktree_path)?;
+    let mut resolved = Vec::with_capacity(resolutions.len());
+    for resolution in resolutions {
+        let sides = conflicts
+            .iter()
+            .find(|sides| sides.path == resolution.path)
+            .ok_or_else(|| ConflictError::NotConflicted(resolution.path.clone()))?;
+        let file = describe(sides)?;
+        let content = if file.segments.is_empty() {
+            resolve_whole_file(sides, &resolution.resolutions)?
+        } else {
+            Some(
+                apply_resolutions(&file.segments, &resolution.resolutions)
+                    .map_err(|e| ConflictError::InvalidResolution(sides.path.clone(), e))?
+                    .into_bytes(),
+            )
+        };
+        resolved.push((sides.path.as_str(), content));
+    }
+
+    let git = GitCli::new();
+    for (path, content) in resolved {
+        let target = worktree_path.join(path);
+        match content {
+            Some(content) => {
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&target, content)?;
+            }
+            None => {
+                if target.exists() {
+                    std::fs::remove_file(&target)?;
+                }
+            }
+        }
+        git.stage_path(worktree_path, path)?;
+    }
+    Ok(())
+}
+
+/// Files listed in the prompt; the agent finds the rest with git
+const MAX_PROMPT_FILES: usize = 12;
+
+/// Instructions for a coding agent to resolve the conflicts itself and
+/// complete the operation
+pub fn resolve_conflicts_prompt(
+    op: &ConflictOp,
+    attempt_branch: &str,
+    base_branch: &str,
+    files: &[String],
+) -> String {
+    let (header, op_name) = match op {
+        ConflictOp::Rebase => (
+            format!("Rebase conflicts while rebasing '{attempt_branch}' onto '{base_branch}'."),
+            "rebase",
+        ),
+        ConflictOp::Merge => (
+            format!("Merge conflicts while merging into '{attempt_branch}'."),
+            "merge",
+        ),
+        ConflictOp::CherryPick => (
+            format!("Cherry-pick conflicts on '{attempt_branch}'."),
+            "cherry-pick",
+        ),
+        ConflictOp::Revert => (format!("Revert conflicts on '{attempt_branch}'."), "revert"),
+    };
+    let mut prompt = header;
+    if !files.is_empty() {
+        prompt.push_str("\n\nFiles with conflicts:");
+        for file in files.iter().take(MAX_PROMPT_FILES) {
+            prompt.push_str(&format!("\n- {file}"));
+        }
+    }
+    prompt.push_str(&format!(
+        "\n\nPlease resolve each file carefully, then complete the {op_name}. When continuing, ensure the {op_name} does not hang (set `GIT_EDITOR=true` or use a non-interactive editor)."
+    ));
+    prompt
+}
+
+fn read_conflicts(worktree_path: &Path) -> Result<Vec<ConflictSides>, ConflictError> {
+    let repo = Repository::open(worktree_path)?;
+    let index = repo.index()?;
+    let blob = |entry: &Option<IndexEntry>| -> Result<Option<Vec<u8>>, git2::Error> {
+        entry
+            .as_ref()
+            .map(|entry| repo.find_blob(entry.id).map(|blob| blob.content().to_vec()))
+            .transpose()
+    };
+
+    let mut conflicts = Vec::new();
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        let Some(entry) = conflict
+            .our
+            .as_ref()
+            .or(conflict.their.as_ref())
+            .or(conflict.ancestor.as_ref())
+        else {
+            continue;
+        };
+        conflicts.push(ConflictSides {
+            path: String::from_utf8_lossy(&entry.path).to_string(),
+            base: blob(&conflict.ancestor)?,
+            ours: blob(&conflict.our)?,
+            theirs: blob(&conflict.their)?,
+        });
+    }
+    conflicts.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(conflicts)
+}
+
+fn describe(sides: &ConflictSides) -> Result<ConflictedFile, ConflictError> {
+    let binary = [&sides.base, &sides.ours, &sides.theirs]
+        .into_iter()
+        .flatten()
+        .any(|content| is_binary(content));
+    let segments = match (&sides.ours, &sides.theirs) {
+        (Some(ours), Some(theirs)) if !binary => {
+            let base = sides.base.as_deref().unwrap_or_default();
+            parse_merge_output(&merge_file(ours, base, theirs)?)
+        }
+        _ => Vec::new(),
+    };
+    Ok(ConflictedFile {
+        path: sides.path.clone(),
+        ours_deleted: sides.ours.is_none(),
+        theirs_deleted: sides.theirs.is_none(),
+        binary,
+        segments,
+    })
+}
+
+/// Same heuristic as git: a NUL byte in the first 8000 bytes
+fn is_binary(content: &[u8]) -> bool {
+    content.iter().take(8000).any(|byte| *byte == 0)
+}
+
+fn merge_file(ours: &[u8], base: &[u8], theirs: &[u8]) -> Result<String, ConflictError> {
+    let dir = tempfile::tempdir()?;
+    let (ours_path, base_path, theirs_path) = (
+        dir.path().join("ours"),
+        dir.path().join("base"),
+        dir.path().join("theirs"),
+    );
+    std::fs::write(&ours_path, ours)?;
+    std::fs::write(&base_path, base)?;
+    std::fs::write(&theirs_path, theirs)?;
+    Ok(GitCli::new().merge_file(&ours_path, &base_path, &theirs_path)?)
+}
+
+fn resolve_whole_file(
+    sides: &ConflictSides,
+    resolutions: &[HunkResolution],
+) -> Result<Option<Vec<u8>>, ConflictError> {
+    let invalid =
+        |reason: &str| ConflictError::InvalidResolution(sides.path.clone(), reason.into());
+    match resolutions {
+        [HunkResolution::Ours] => Ok(sides.ours.clone()),
+        [HunkResolution::Theirs] => Ok(sides.theirs.clone()),
+        [HunkResolution::Custom { text }] => Ok(Some(text.clone().into_bytes())),
+        [_] => Err(invalid("pick ours, theirs or custom content for the file")),
+        _ => Err(invalid("the file takes exactly one resolution")),
+    }
+}
+
+/// Split `git merge-file --diff3` output into merged and conflicting parts
+fn parse_merge_output(merged: &str) -> Vec<ConflictSegment> {
+    enum Section {
+        Clean,
+        Ours,
+        Base,
+        Theirs,
+    }
+
+    let mut segments = Vec::new();
+    let mut section = Section::Clean;
+    let (mut clean, mut ours, mut base, mut theirs) =
+        (String::new(), String::new(), String::new(), String::new());
+    for line in merged.split_inclusive('\n') {
+        let marker = line.trim_end_matches(['\n', '\r']);
+        match (&section, marker) {
+            (Section::Clean, "<<<<<<< ours") => {
+                if !clean.is_empty() {
+                    segments.push(ConflictSegment::Clean {
+                        text: std::mem::take(&mut clean),
+                    });
+                }
+                section = Section::Ours;
+            }
+            (Section::Ours, "||||||| base") => section = Section::Base,
+            (Section::Ours | Section::Base, "=======") => section = Section::Theirs,
+            (Section::Theirs, ">>>>>>> theirs") => {
+                segments.push(ConflictSegment::Conflict {
+                    base: std::mem::take(&mut base),
+                    ours: std::mem::take(&mut ours),
+                    theirs: std::mem::take(&mut theirs),
+                });
+                section = Section::Clean;
+            }
+            (Section::Clean, _) => clean.push_str(line),
+            (Section::Ours, _) => ours.push_str(line),
+            (Section::Base, _) => base.push_str(line),
+            (Section::Theirs, _) => theirs.push_str(line),
+        }
+    }
+    if !clean.is_empty() {
+        segments.push(ConflictSegment::Clean { text: clean });
+    }
+    segments
+}
+
+/// The file content with each conflict replaced by its resolution
+fn apply_resolutions(
+    segments: &[ConflictSegment],
+    resolutions: &[HunkResolution],
+) -> Result<String, String> {
+    let conflicts = segments
+        .iter()
+        .filter(|segment| matches!(segment, ConflictSegment::Conflict { .. }))
+        .count();
+    if resolutions.len() != conflicts {
+        return Err(format!(
+            "expected {conflicts} resolutions, got {}",
+            resolutions.len()
+        ));
+    }
+
+    let mut resolutions = resolutions.iter();
+    let mut content = String::new();
+    for segment in segments {
+        match segment {
+            ConflictSegment::Clean { text } => content.push_str(text),
+            ConflictSegment::Conflict { base, ours, theirs } => {
+                match resolutions.next().expect("one resolution per conflict") {
+                    HunkResolution::Ours => content.push_str(ours),
+                    HunkResolution::Theirs => content.push_str(theirs),
+                    HunkResolution::Base => content.push_str(base),
+                    HunkResolution::Both => {
+                        content.push_str(ours);
+                        content.push_str(theirs);
+                    }
+                    HunkResolution::Custom { text } => content.push_str(text),
+                }
+            }
+        }
+    }
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_resolves_merge_file_output() {
+        let merged = "a\n<<<<<<< ours\nB1\n||||||| base\nb\n=======\nB2\n>>>>>>> theirs\nc\n";
+        let segments = parse_merge_output(merged);
+        assert_eq!(
+            segments,
+            vec![
+                ConflictSegment::Clean {
+                    text: "a\n".to_string()
+                },
+                ConflictSegment::Conflict {
+                    base: "b\n".to_string(),
+                    ours: "B1\n".to_string(),
+                    theirs: "B2\n".to_string(),
+                },
+                ConflictSegment::Clean {
+                    text: "c\n".to_string()
+                },
+            ]
+        );
+
+        assert_eq!(
+            apply_resolutions(&segments, &[HunkResolution::Both]).unwrap(),
+            "a\nB1\nB2\nc\n"
+        );
+        assert_eq!(
+            apply_resolutions(
+                &segments,
+                &[HunkResolution::Custom {
+                    text: "merged\n".to_string()
+                }]
+            )
+            .unwrap(),
+            "a\nmerged\nc\n"
+        );
+        assert!(apply_resolutions(&segments, &[]).is_err());
+    }
+
+    #[test]
+    fn prompt_names_the_operation_and_files() {
+        let prompt = resolve_conflicts_prompt(
+            &ConflictOp::Rebase,
+            "vk/feature",
+            "main",
+            &["src/lib.rs".to_string()],
+        );
+        assert!(prompt.starts_with("Rebase conflicts while rebasing 'vk/feature' onto 'main'."));
+        assert!(prompt.contains("Files with conflicts:\n- src/lib.rs"));
+        assert!(prompt.contains("complete the rebase"));
+    }
+}
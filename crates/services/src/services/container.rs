@@ -2,20 +2,36 @@ use std::{
     collections::HashMap,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use anyhow::{Error as AnyhowError, anyhow};
 use async_trait::async_trait;
+use chrono::Utc;
 use db::{
     DBService,
     models::{
+        attempt_plan::{AttemptPlan, AttemptPlanStatus},
+        attempt_usage::{AttemptUsage, UpsertAttemptUsage},
+        execution_fallback::ExecutionFallback,
         execution_process::{
             CreateExecutionProcess, ExecutionContext, ExecutionProcess, ExecutionProcessRunReason,
             ExecutionProcessStatus,
         },
+        execution_process_event::ExecutionProcessEvent,
         execution_process_logs::ExecutionProcessLogs,
+        execution_resumption::ExecutionResumption,
         executor_session::{CreateExecutorSession, ExecutorSession},
+        external_task_link::ExternalTaskLink,
+        github_issue_link::GitHubIssueLink,
+        project::Project,
+        project_branch_template::ProjectBranchTemplate,
+        project_context_pack::ProjectContextPack,
+        project_executor_fallback::ProjectExecutorFallback,
+        project_repository::ProjectRepository,
+        project_script::{ProjectScript, ProjectScriptKind},
         task::{Task, TaskStatus},
+        task_attachment::TaskAttachment,
         task_attempt::{TaskAttempt, TaskAttemptError},
     },
 };
@@ -26,8 +42,13 @@ use executors::{
         coding_agent_initial::CodingAgentInitialRequest,
         script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
     },
-    executors::{ExecutorError, StandardCodingAgentExecutor},
-    logs::{NormalizedEntry, NormalizedEntryError, NormalizedEntryType, utils::ConversationPatch},
+    executors::{BaseCodingAgent, ExecutorError, StandardCodingAgentExecutor},
+    logs::{
+        NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
+        events::{AgentEvent, AgentEventProjector},
+        provider_errors::{find_provider_failure, parse_retry_after},
+        utils::ConversationPatch,
+    },
     profile::{ExecutorConfigs, ExecutorProfileId, to_default_variant},
 };
 use futures::{StreamExt, future};
@@ -35,19 +56,80 @@ use sqlx::Error as SqlxError;
 use thiserror::Error;
 use tokio::{sync::RwLock, task::JoinHandle};
 use utils::{
+    diff::{DiffFileHunks, DiffFilePage},
     log_msg::LogMsg,
     msg_store::MsgStore,
-    text::{git_branch_id, short_uuid},
 };
 use uuid::Uuid;
 
 use crate::services::{
+    branch_naming::{
+        BranchNameContext, DEFAULT_BRANCH_TEMPLATE, numbered_branch_name, render_branch_name,
+    },
+    context_packs::context_packs_prompt,
+    events::publish_project_wip_status,
+    execution_queue::{ExecutionQueue, PreparedExecution},
     git::{GitService, GitServiceError},
     image::ImageService,
+    proposed_tasks::PROPOSAL_INSTRUCTIONS,
+    secrets::SecretsError,
     worktree_manager::{WorktreeError, WorktreeManager},
 };
 pub type ContainerRef = String;
 
+/// Appended to the task prompt of the planning run of a plan-first attempt
+const PLAN_FIRST_INSTRUCTIONS: &str = "\n\nDo not implement this task yet and do not modify any files. Investigate what is needed and reply with a step-by-step implementation plan. The plan will be reviewed, and you will be asked to implement it once it is approved.";
+
+/// Appended to the task prompt of a read-only attempt
+const READ_ONLY_INSTRUCTIONS: &str = "\n\nThis is a read-only task: answer it by reading and running the code, but do not modify, create or delete any files, and do not commit. Any changes you make will be discarded.";
+
+/// Prompt of the run continuing an agent session interrupted by a restart
+const RESUME_INSTRUCTIONS: &str = "Your previous turn was interrupted because Vibe Kanban restarted. Check the current state of the worktree and continue where you left off.";
+
+/// Script request running a project script with its timeout and retries
+pub fn project_script_request(script: &ProjectScript) -> ScriptRequest {
+    ScriptRequest {
+        script: script.script.clone(),
+        language: ScriptRequestLanguage::Bash,
+        context: script_context(script.kind),
+        timeout_seconds: script
+            .timeout_seconds
+            .and_then(|seconds| u32::try_from(seconds).ok()),
+        retries: u32::try_from(script.max_retries).unwrap_or(0),
+    }
+}
+
+fn script_context(kind: ProjectScriptKind) -> ScriptContext {
+    match kind {
+        ProjectScriptKind::Setup => ScriptContext::SetupScript,
+        ProjectScriptKind::Dev => ScriptContext::DevServer,
+        ProjectScriptKind::Test => ScriptContext::TestScript,
+        ProjectScriptKind::Cleanup => ScriptContext::CleanupScript,
+    }
+}
+
+pub fn script_run_reason(context: &ScriptContext) -> ExecutionProcessRunReason {
+    match context {
+        ScriptContext::SetupScript => ExecutionProcessRunReason::SetupScript,
+        ScriptContext::CleanupScript => ExecutionProcessRunReason::CleanupScript,
+        ScriptContext::TestScript => ExecutionProcessRunReason::TestScript,
+        ScriptContext::DevServer => ExecutionProcessRunReason::DevServer,
+    }
+}
+
+/// Chain scripts to run one after the other, followed by `next`
+fn chain_scripts(
+    scripts: Vec<ScriptRequest>,
+    next: Option<Box<ExecutorAction>>,
+) -> Option<Box<ExecutorAction>> {
+    scripts.into_iter().rev().fold(next, |next, script| {
+        Some(Box::new(ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(script),
+            next,
+        )))
+    })
+}
+
 /// Data needed for background worktree cleanup (doesn't require DB access)
 #[derive(Debug, Clone)]
 pub struct WorktreeCleanupData {
@@ -99,6 +181,14 @@ pub enum ContainerError {
     #[error(transparent)]
     TaskAttemptError(#[from] TaskAttemptError),
     #[error(transparent)]
+    Secrets(#[from] SecretsError),
+    #[error("Project is archived")]
+    ProjectArchived,
+    #[error("Task attempt is archived")]
+    AttemptArchived,
+    #[error("Task is in the trash")]
+    TaskTrashed,
+    #[error(transparent)]
     Other(#[from] AnyhowError), // Catches any unclassified errors
 }
 
@@ -110,6 +200,25 @@ pub trait ContainerService {
 
     fn git(&self) -> &GitService;
 
+    fn execution_queue(&self) -> &ExecutionQueue;
+
+    /// Store of the events stream
+    fn events_msg_store(&self) -> &Arc<MsgStore>;
+
+    /// Publish the WIP limit status of the project's columns after the
+    /// container moved one of its tasks to another status
+    async fn publish_wip_status(&self, project_id: Uuid) {
+        if let Err(e) =
+            publish_project_wip_status(&self.db().pool, self.events_msg_store(), project_id).await
+        {
+            tracing::error!(
+                "Failed to publish WIP limit status of project {}: {}",
+                project_id,
+                e
+            );
+        }
+    }
+
     fn task_attempt_to_current_dir(&self, task_attempt: &TaskAttempt) -> PathBuf;
 
     async fn create(&self, task_attempt: &TaskAttempt) -> Result<ContainerRef, ContainerError>;
@@ -138,6 +247,21 @@ pub trait ContainerService {
         Ok(false)
     }
 
+    /// Archived projects are read-only: nothing runs for them
+    async fn ensure_project_not_archived(
+        &self,
+        task_attempt: &TaskAttempt,
+    ) -> Result<(), ContainerError> {
+        let task = task_attempt
+            .parent_task(&self.db().pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        if Project::is_archived(&self.db().pool, task.project_id).await? {
+            return Err(ContainerError::ProjectArchived);
+        }
+        Ok(())
+    }
+
     /// Stop execution processes for task attempts without cleanup
     async fn stop_task_processes(
         &self,
@@ -149,20 +273,81 @@ pub trait ContainerService {
         Ok(())
     }
 
-    fn cleanup_action(&self, cleanup_script: Option<String>) -> Option<Box<ExecutorAction>> {
-        cleanup_script.map(|script| {
-            Box::new(ExecutorAction::new(
-                ExecutorActionType::ScriptRequest(ScriptRequest {
-                    script,
-                    language: ScriptRequestLanguage::Bash,
-                    context: ScriptContext::CleanupScript,
-                }),
-                None,
-            ))
-        })
+    /// Scripts of `kind` for the project, in run order. A kind without script
+    /// records falls back to the project's legacy script column.
+    async fn project_script_requests(
+        &self,
+        project: &Project,
+        kind: ProjectScriptKind,
+    ) -> Result<Vec<ScriptRequest>, ContainerError> {
+        let scripts =
+            ProjectScript::find_by_project_id(&self.db().pool, project.id, Some(kind)).await?;
+        if !scripts.is_empty() {
+            return Ok(scripts.iter().map(project_script_request).collect());
+        }
+        let legacy_script = match kind {
+            ProjectScriptKind::Setup => project.setup_script.clone(),
+            ProjectScriptKind::Dev => project.dev_script.clone(),
+            ProjectScriptKind::Cleanup => project.cleanup_script.clone(),
+            ProjectScriptKind::Test => None,
+        };
+        Ok(legacy_script
+            .map(|script| ScriptRequest {
+                script,
+                language: ScriptRequestLanguage::Bash,
+                context: script_context(kind),
+                timeout_seconds: None,
+                retries: 0,
+            })
+            .into_iter()
+            .collect())
+    }
+
+    /// The project's cleanup scripts, chained to run after a coding agent
+    async fn cleanup_action(
+        &self,
+        project: &Project,
+    ) -> Result<Option<Box<ExecutorAction>>, ContainerError> {
+        let scripts = self
+            .project_script_requests(project, ProjectScriptKind::Cleanup)
+            .await?;
+        Ok(chain_scripts(scripts, None))
+    }
+
+    /// Put the project's setup scripts in front of `action`, in order, so that
+    /// a failing one stops the chain. Returns the action to start and its run
+    /// reason.
+    async fn with_setup_scripts(
+        &self,
+        project: &Project,
+        action: ExecutorAction,
+    ) -> Result<(ExecutorAction, ExecutionProcessRunReason), ContainerError> {
+        let scripts = self
+            .project_script_requests(project, ProjectScriptKind::Setup)
+            .await?;
+        if scripts.is_empty() {
+            return Ok((action, ExecutionProcessRunReason::CodingAgent));
+        }
+        let mut action = action;
+        for script in scripts.into_iter().rev() {
+            action = ExecutorAction::new(
+                ExecutorActionType::ScriptRequest(script),
+                Some(Box::new(action)),
+            );
+        }
+        Ok((action, ExecutionProcessRunReason::SetupScript))
     }
 
     async fn try_stop(&self, task_attempt: &TaskAttempt) {
+        // an attempt still waiting for capacity has nothing running yet
+        if let Err(e) = self.execution_queue().remove(task_attempt.id).await {
+            tracing::error!(
+                "Failed to remove task attempt {} from the queue: {}",
+                task_attempt.id,
+                e
+            );
+        }
+
         // stop all execution processes for this attempt
         if let Ok(processes) =
             ExecutionProcess::find_by_task_attempt_id(&self.db().pool, task_attempt.id, false).await
@@ -205,6 +390,34 @@ pub trait ContainerService {
         status: ExecutionProcessStatus,
     ) -> Result<(), ContainerError>;
 
+    /// Suspend a running execution process, leaving its container untouched
+    async fn pause_execution(
+        &self,
+        execution_process: &ExecutionProcess,
+    ) -> Result<(), ContainerError>;
+
+    /// Continue an execution process suspended by `pause_execution`
+    async fn resume_execution(
+        &self,
+        execution_process: &ExecutionProcess,
+    ) -> Result<(), ContainerError>;
+
+    /// Re-read the timeout of a running execution process after it was
+    /// paused or resumed, or its attempt's timeout was changed
+    async fn refresh_execution_timeout(&self, execution_process_id: Uuid);
+
+    /// Stop what is left of the OS process of an execution the previous
+    /// server run left running; its output can no longer be read
+    async fn stop_orphaned_process(&self, execution_process_id: Uuid)
+    -> Result<(), ContainerError>;
+
+    /// Start the oldest queued follow-up of an attempt if nothing is running
+    /// for it. Returns whether a follow-up was started.
+    async fn start_queued_follow_up(
+        &self,
+        task_attempt: &TaskAttempt,
+    ) -> Result<bool, ContainerError>;
+
     async fn try_commit_changes(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError>;
 
     async fn copy_project_files(
@@ -221,6 +434,22 @@ pub trait ContainerService {
         stats_only: bool,
     ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, ContainerError>;
 
+    /// Page of the files an attempt changes, read from the same source as
+    /// `stream_diff` but without loading every file's contents
+    async fn diff_files(
+        &self,
+        task_attempt: &TaskAttempt,
+        offset: usize,
+        limit: usize,
+    ) -> Result<DiffFilePage, ContainerError>;
+
+    /// Hunks of one file an attempt changes, or None if it doesn't change it
+    async fn diff_file_hunks(
+        &self,
+        task_attempt: &TaskAttempt,
+        path: &str,
+    ) -> Result<Option<DiffFileHunks>, ContainerError>;
+
     /// Fetch the MsgStore for a given execution ID, panicking if missing.
     async fn get_msg_store_by_id(&self, uuid: &Uuid) -> Option<Arc<MsgStore>> {
         let map = self.msg_stores().read().await;
@@ -229,14 +458,64 @@ pub trait ContainerService {
 
     async fn git_branch_prefix(&self) -> String;
 
-    async fn git_branch_from_task_attempt(&self, attempt_id: &Uuid, task_title: &str) -> String {
-        let task_title_id = git_branch_id(task_title);
+    /// Branch for a new attempt of `task`, named from its project's branch
+    /// template. A number is appended when a branch of that name exists.
+    async fn git_branch_from_task_attempt(
+        &self,
+        attempt_id: &Uuid,
+        task: &Task,
+        executor: &BaseCodingAgent,
+    ) -> Result<String, ContainerError> {
+        let pool = &self.db().pool;
+        let project = task
+            .parent_project(pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        let template = ProjectBranchTemplate::find_by_project_id(pool, project.id)
+            .await?
+            .map(|template| template.template);
+        let ticket = match ExternalTaskLink::find_by_task_id(pool, task.id).await? {
+            Some(link) => Some(link.external_key),
+            None => GitHubIssueLink::find_by_task_id(pool, task.id)
+                .await?
+                .map(|link| link.issue_number.to_string()),
+        };
         let prefix = self.git_branch_prefix().await;
-
-        if prefix.is_empty() {
-            format!("{}-{}", short_uuid(attempt_id), task_title_id)
-        } else {
-            format!("{}/{}-{}", prefix, short_uuid(attempt_id), task_title_id)
+        let executor = executor.to_string();
+        let context = BranchNameContext {
+            prefix: &prefix,
+            ticket: ticket.as_deref(),
+            task_title: &task.title,
+            attempt_id,
+            attempt_number: TaskAttempt::count_by_task_id(pool, task.id).await? + 1,
+            executor: &executor,
+            date: Utc::now().date_naive(),
+        };
+        let name = template
+            .as_deref()
+            .map(|template| render_branch_name(template, &context))
+            .unwrap_or_else(|| render_branch_name(DEFAULT_BRANCH_TEMPLATE, &context))
+            .or_else(|e| {
+                // The template was valid when saved, but e.g. a ticket key
+                // can still make the name invalid
+                tracing::warn!("Using the default branch name for task {}: {}", task.id, e);
+                render_branch_name(DEFAULT_BRANCH_TEMPLATE, &context)
+            })
+            .map_err(|e| ContainerError::Other(anyhow!(e)))?;
+
+        let mut try_number = 1;
+        loop {
+            let candidate = numbered_branch_name(&name, try_number);
+            if !self
+                .git()
+                .check_branch_exists(&project.git_repo_path, &candidate)?
+                && TaskAttempt::find_id_by_branch(pool, &candidate)
+                    .await?
+                    .is_none()
+            {
+                return Ok(candidate);
+            }
+            try_number += 1;
         }
     }
 
@@ -368,7 +647,9 @@ pub trait ContainerService {
                 }
             };
 
-            if let Err(err) = self.ensure_container_exists(&task_attempt).await {
+            if task_attempt.archived_at.is_none()
+                && let Err(err) = self.ensure_container_exists(&task_attempt).await
+            {
                 tracing::warn!(
                     "Failed to recreate worktree before log normalization for task attempt {}: {}",
                     task_attempt.id,
@@ -420,6 +701,65 @@ pub trait ContainerService {
         }
     }
 
+    /// Stream the agent events of a coding agent execution: live from its
+    /// store while it runs, persisted ones afterwards. Executions from before
+    /// events were persisted are projected from their normalized logs.
+    async fn stream_agent_events(
+        &self,
+        id: &Uuid,
+    ) -> Option<futures::stream::BoxStream<'static, Result<AgentEvent, std::io::Error>>> {
+        if let Some(store) = self.get_msg_store_by_id(id).await {
+            return Some(AgentEventProjector::project(store.history_plus_stream()));
+        }
+
+        match ExecutionProcessEvent::find_by_execution_process_id(&self.db().pool, *id, None).await
+        {
+            Ok(events) if !events.is_empty() => {
+                Some(futures::stream::iter(events.into_iter().map(|row| Ok(row.event.0))).boxed())
+            }
+            Ok(_) => self
+                .stream_normalized_logs(id)
+                .await
+                .map(AgentEventProjector::project),
+            Err(e) => {
+                tracing::error!("Failed to fetch agent events for execution {}: {}", id, e);
+                None
+            }
+        }
+    }
+
+    /// Persist the agent events of a coding agent execution as they stream
+    fn spawn_stream_agent_events_to_db(&self, execution_id: &Uuid) -> JoinHandle<()> {
+        let execution_id = *execution_id;
+        let msg_stores = self.msg_stores().clone();
+        let db = self.db().clone();
+
+        tokio::spawn(async move {
+            let store = {
+                let map = msg_stores.read().await;
+                map.get(&execution_id).cloned()
+            };
+            let Some(store) = store else {
+                return;
+            };
+
+            let mut events = AgentEventProjector::project(store.history_plus_stream());
+            let mut seq = 0;
+            while let Some(Ok(event)) = events.next().await {
+                if let Err(e) =
+                    ExecutionProcessEvent::append(&db.pool, execution_id, seq, &event).await
+                {
+                    tracing::error!(
+                        "Failed to persist agent event for execution {}: {}",
+                        execution_id,
+                        e
+                    );
+                }
+                seq += 1;
+            }
+        })
+    }
+
     fn spawn_stream_raw_logs_to_db(&self, execution_id: &Uuid) -> JoinHandle<()> {
         let execution_id = *execution_id;
         let msg_stores = self.msg_stores().clone();
@@ -484,6 +824,29 @@ pub trait ContainerService {
                                 );
                             }
                         }
+                        LogMsg::Usage(usage) => {
+                            if let Err(e) = AttemptUsage::upsert(
+                                &db.pool,
+                                execution_id,
+                                &UpsertAttemptUsage {
+                                    model: usage.model.clone(),
+                                    input_tokens: usage.input_tokens as i64,
+                                    output_tokens: usage.output_tokens as i64,
+                                    cache_read_input_tokens: usage.cache_read_input_tokens as i64,
+                                    cache_creation_input_tokens: usage.cache_creation_input_tokens
+                                        as i64,
+                                    cost_usd: usage.cost_usd,
+                                },
+                            )
+                            .await
+                            {
+                                tracing::error!(
+                                    "Failed to record usage for execution process {}: {}",
+                                    execution_id,
+                                    e
+                                );
+                            }
+                        }
                         LogMsg::Finished => {
                             break;
                         }
@@ -499,6 +862,8 @@ pub trait ContainerService {
         task_attempt: &TaskAttempt,
         executor_profile_id: ExecutorProfileId,
     ) -> Result<ExecutionProcess, ContainerError> {
+        self.ensure_project_not_archived(task_attempt).await?;
+
         // Create container
         self.create(task_attempt).await?;
 
@@ -526,53 +891,195 @@ pub trait ContainerService {
                 .as_ref()
                 .ok_or_else(|| ContainerError::Other(anyhow!("Container ref not found")))?,
         );
-        let prompt = ImageService::canonicalise_image_paths(&task.to_prompt(), &worktree_path);
+        let mut prompt = ImageService::canonicalise_image_paths(&task.to_prompt(), &worktree_path);
 
-        let cleanup_action = self.cleanup_action(project.cleanup_script);
+        // The project's context packs go first, so the task reads against them
+        let context_packs =
+            ProjectContextPack::find_by_project_id(&self.db().pool, project.id).await?;
+        if let Some(context) = context_packs_prompt(&context_packs, &worktree_path) {
+            prompt.insert_str(0, &context);
+        }
 
-        // Choose whether to execute the setup_script or coding agent first
-        let execution_process = if let Some(setup_script) = project.setup_script {
-            let executor_action = ExecutorAction::new(
-                ExecutorActionType::ScriptRequest(ScriptRequest {
-                    script: setup_script,
-                    language: ScriptRequestLanguage::Bash,
-                    context: ScriptContext::SetupScript,
-                }),
-                // once the setup script is done, run the initial coding agent request
-                Some(Box::new(ExecutorAction::new(
-                    ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
-                        prompt,
-                        executor_profile_id: executor_profile_id.clone(),
-                    }),
-                    cleanup_action,
-                ))),
+        // Attachments were copied into the worktree when it was created
+        let attachments = TaskAttachment::find_by_task_id(&self.db().pool, task.id).await?;
+        if let Some(context) =
+            ImageService::attachments_prompt_context(&attachments, &worktree_path)
+        {
+            prompt.push_str(&context);
+        }
+
+        // Point the agent at the worktrees of the project's other repositories
+        let repositories =
+            ProjectRepository::find_by_project_id(&self.db().pool, project.id).await?;
+        if !repositories.is_empty() {
+            prompt.push_str(
+                "\n\nThis task spans several repositories, each checked out on the same branch:",
             );
+            for repository in &repositories {
+                prompt.push_str(&format!(
+                    "\n- {}: {}",
+                    repository.name,
+                    repository.worktree_path(&worktree_path).display()
+                ));
+            }
+        }
 
-            self.start_execution(
-                &task_attempt,
-                &executor_action,
-                &ExecutionProcessRunReason::SetupScript,
-            )
+        if task_attempt.read_only {
+            prompt.push_str(READ_ONLY_INSTRUCTIONS);
+        }
+        prompt.push_str(PROPOSAL_INSTRUCTIONS);
+
+        // A plan-first attempt starts with a run that only plans, so there is
+        // nothing for the cleanup script to do yet
+        let planning = AttemptPlan::find_by_task_attempt_id(&self.db().pool, task_attempt.id)
             .await?
+            .is_some_and(|plan| plan.status == AttemptPlanStatus::Planning);
+        let cleanup_action = if planning {
+            prompt.push_str(PLAN_FIRST_INSTRUCTIONS);
+            None
         } else {
-            let executor_action = ExecutorAction::new(
-                ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
-                    prompt,
-                    executor_profile_id: executor_profile_id.clone(),
-                }),
-                cleanup_action,
-            );
+            self.cleanup_action(&project).await?
+        };
+        let executor_action = ExecutorAction::new(
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt,
+                executor_profile_id: executor_profile_id.clone(),
+            }),
+            cleanup_action,
+        );
+        let (executor_action, run_reason) =
+            self.with_setup_scripts(&project, executor_action).await?;
 
-            self.start_execution(
-                &task_attempt,
-                &executor_action,
-                &ExecutionProcessRunReason::CodingAgent,
-            )
+        let execution_process = self
+            .start_execution(&task_attempt, &executor_action, &run_reason)
+            .await?;
+        Ok(execution_process)
+    }
+
+    /// Start the attempt if the concurrency limits allow it, otherwise queue it.
+    /// Returns None when the attempt was queued.
+    async fn queue_or_start_attempt(
+        &self,
+        task_attempt: &TaskAttempt,
+        executor_profile_id: ExecutorProfileId,
+    ) -> Result<Option<ExecutionProcess>, ContainerError> {
+        self.queue_or_start(task_attempt, executor_profile_id, None)
+            .await
+    }
+
+    /// Like `queue_or_start_attempt`, for an attempt whose first action was
+    /// prepared by the caller instead of built from the task prompt
+    async fn queue_or_start_execution(
+        &self,
+        task_attempt: &TaskAttempt,
+        executor_profile_id: ExecutorProfileId,
+        executor_action: ExecutorAction,
+        run_reason: ExecutionProcessRunReason,
+    ) -> Result<Option<ExecutionProcess>, ContainerError> {
+        self.queue_or_start(
+            task_attempt,
+            executor_profile_id,
+            Some(PreparedExecution {
+                executor_action,
+                run_reason,
+                resumes: None,
+            }),
+        )
+        .await
+    }
+
+    async fn queue_or_start(
+        &self,
+        task_attempt: &TaskAttempt,
+        executor_profile_id: ExecutorProfileId,
+        prepared: Option<PreparedExecution>,
+    ) -> Result<Option<ExecutionProcess>, ContainerError> {
+        let task = task_attempt
+            .parent_task(&self.db().pool)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+
+        if let Some(_reservation) = self
+            .execution_queue()
+            .try_reserve(task.project_id, &executor_profile_id)
             .await?
+        {
+            return Ok(Some(
+                self.start_queue_entry(task_attempt, executor_profile_id, prepared)
+                    .await?,
+            ));
+        }
+
+        let position = self
+            .execution_queue()
+            .enqueue(
+                task_attempt.id,
+                task.project_id,
+                executor_profile_id,
+                prepared,
+            )
+            .await?;
+        tracing::info!(
+            "Queued task attempt {} at position {}",
+            task_attempt.id,
+            position
+        );
+        // Attempts ahead of it that could start were refusing this one
+        self.dispatch_queued_attempts().await?;
+        Ok(None)
+    }
+
+    async fn start_queue_entry(
+        &self,
+        task_attempt: &TaskAttempt,
+        executor_profile_id: ExecutorProfileId,
+        prepared: Option<PreparedExecution>,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        let Some(prepared) = prepared else {
+            return self.start_attempt(task_attempt, executor_profile_id).await;
         };
+        let execution_process = self
+            .start_execution(
+                task_attempt,
+                &prepared.executor_action,
+                &prepared.run_reason,
+            )
+            .await?;
+        if let Some(interrupted_id) = prepared.resumes {
+            self.record_resumption(&execution_process, interrupted_id)
+                .await?;
+        }
         Ok(execution_process)
     }
 
+    /// Start queued attempts for as long as there is capacity for them
+    async fn dispatch_queued_attempts(&self) -> Result<(), ContainerError> {
+        while let Some((entry, _reservation)) = self.execution_queue().pop_ready().await? {
+            let Some(task_attempt) =
+                TaskAttempt::find_by_id(&self.db().pool, entry.task_attempt_id).await?
+            else {
+                continue;
+            };
+            if let Err(e) = self
+                .start_queue_entry(&task_attempt, entry.executor_profile_id, entry.prepared)
+                .await
+            {
+                tracing::error!(
+                    "Failed to start queued task attempt {}: {}",
+                    task_attempt.id,
+                    e
+                );
+                // The entry is gone from the queue; move the task to review so
+                // the failed start doesn't go unnoticed
+                if let Some(task) = task_attempt.parent_task(&self.db().pool).await? {
+                    Task::update_status(&self.db().pool, task.id, TaskStatus::InReview).await?;
+                    self.publish_wip_status(task.project_id).await;
+                }
+            }
+        }
+        Ok(())
+    }
+
     async fn start_execution(
         &self,
         task_attempt: &TaskAttempt,
@@ -584,10 +1091,26 @@ pub trait ContainerService {
             .parent_task(&self.db().pool)
             .await?
             .ok_or(SqlxError::RowNotFound)?;
+        if Project::is_archived(&self.db().pool, task.project_id).await? {
+            return Err(ContainerError::ProjectArchived);
+        }
+        if Task::is_trashed(&self.db().pool, task.id).await?
+            || Project::is_trashed(&self.db().pool, task.project_id).await?
+        {
+            return Err(ContainerError::TaskTrashed);
+        }
         if task.status != TaskStatus::InProgress
-            && run_reason != &ExecutionProcessRunReason::DevServer
+            && !matches!(
+                run_reason,
+                ExecutionProcessRunReason::DevServer | ExecutionProcessRunReason::TestScript
+            )
         {
             Task::update_status(&self.db().pool, task.id, TaskStatus::InProgress).await?;
+            self.publish_wip_status(task.project_id).await;
+        }
+        // A new run supersedes any earlier timeout
+        if run_reason != &ExecutionProcessRunReason::DevServer {
+            TaskAttempt::clear_timed_out(&self.db().pool, task_attempt.id).await?;
         }
         // Create new execution process record
         // Capture current HEAD as the "before" commit for this execution
@@ -613,6 +1136,10 @@ pub trait ContainerService {
         )
         .await?;
 
+        if let Some(executor) = executor_action.base_executor() {
+            self.execution_queue().rate_limiter().record_start(executor);
+        }
+
         if let Some(prompt) = match executor_action.typ() {
             ExecutorActionType::CodingAgentInitialRequest(coding_agent_request) => {
                 Some(coding_agent_request.prompt.clone())
@@ -658,6 +1185,7 @@ pub trait ContainerService {
                 );
             }
             Task::update_status(&self.db().pool, task.id, TaskStatus::InReview).await?;
+            self.publish_wip_status(task.project_id).await;
 
             // Emit stderr error message
             let log_message = LogMsg::Stderr(format!("Failed to start execution: {start_error}"));
@@ -712,6 +1240,7 @@ pub trait ContainerService {
                 ExecutorConfigs::get_cached().get_coding_agent(executor_profile_id)
             {
                 executor.normalize_logs(msg_store, &self.task_attempt_to_current_dir(task_attempt));
+                self.spawn_stream_agent_events_to_db(&execution_process.id);
             } else {
                 tracing::error!(
                     "Failed to resolve profile '{:?}' for normalization",
@@ -734,20 +1263,12 @@ pub trait ContainerService {
         };
 
         // Determine the run reason of the next action
-        let next_run_reason = match (action.typ(), next_action.typ()) {
-            (ExecutorActionType::ScriptRequest(_), ExecutorActionType::ScriptRequest(_)) => {
-                ExecutionProcessRunReason::SetupScript
+        let next_run_reason = match next_action.typ() {
+            ExecutorActionType::ScriptRequest(script) => script_run_reason(&script.context),
+            ExecutorActionType::CodingAgentInitialRequest(_)
+            | ExecutorActionType::CodingAgentFollowUpRequest(_) => {
+                ExecutionProcessRunReason::CodingAgent
             }
-            (
-                ExecutorActionType::CodingAgentInitialRequest(_)
-                | ExecutorActionType::CodingAgentFollowUpRequest(_),
-                ExecutorActionType::ScriptRequest(_),
-            ) => ExecutionProcessRunReason::CleanupScript,
-            (
-                _,
-                ExecutorActionType::CodingAgentFollowUpRequest(_)
-                | ExecutorActionType::CodingAgentInitialRequest(_),
-            ) => ExecutionProcessRunReason::CodingAgent,
         };
 
         self.start_execution(&ctx.task_attempt, next_action, &next_run_reason)
@@ -757,6 +1278,246 @@ pub trait ContainerService {
         Ok(())
     }
 
+    /// Run a failed script again while it has retries left, keeping the rest
+    /// of its chain. Returns whether a retry was started.
+    async fn try_retry_script(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError> {
+        let action = ctx.execution_process.executor_action()?;
+        let ExecutorActionType::ScriptRequest(script) = action.typ() else {
+            return Ok(false);
+        };
+        if script.retries == 0 {
+            return Ok(false);
+        }
+        let retry = ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                retries: script.retries - 1,
+                ..script.clone()
+            }),
+            action.next_action.clone(),
+        );
+        tracing::info!(
+            "Retrying script for task attempt {} ({} retries left)",
+            ctx.task_attempt.id,
+            script.retries - 1
+        );
+        self.start_execution(&ctx.task_attempt, &retry, &ctx.execution_process.run_reason)
+            .await?;
+        Ok(true)
+    }
+
+    /// Re-run a coding agent run the model provider refused (rate limits,
+    /// quota, outages) with the next profile of the project's fallback chain.
+    /// A follow-up can only continue its session with a profile of the same
+    /// executor, so profiles of other executors are skipped for follow-ups.
+    /// Returns whether a fallback run was started.
+    async fn try_fallback(&self, ctx: &ExecutionContext) -> Result<bool, ContainerError> {
+        let action = ctx.execution_process.executor_action()?;
+        let failed_profile = match action.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(req) => &req.executor_profile_id,
+            ExecutorActionType::CodingAgentFollowUpRequest(req) => &req.executor_profile_id,
+            _ => return Ok(false),
+        };
+        let pool = &self.db().pool;
+        let chain = ProjectExecutorFallback::find_by_project_id(pool, ctx.task.project_id).await?;
+        if chain.is_empty() {
+            return Ok(false);
+        }
+        let Some(reason) = self
+            .get_msg_store_by_id(&ctx.execution_process.id)
+            .await
+            .and_then(|store| find_provider_failure(&store.get_history()))
+        else {
+            return Ok(false);
+        };
+
+        // Walk on from the failed profile when it is itself in the chain
+        let start = chain
+            .iter()
+            .position(|fallback| fallback.executor_profile_id.0 == *failed_profile)
+            .map_or(0, |index| index + 1);
+        let next = chain[start..]
+            .iter()
+            .map(|fallback| &fallback.executor_profile_id.0)
+            .find(|profile| match action.typ() {
+                ExecutorActionType::CodingAgentFollowUpRequest(_) => {
+                    profile.executor == failed_profile.executor
+                }
+                _ => true,
+            });
+        let Some(next) = next else {
+            tracing::info!(
+                "No executor fallback left for task attempt {} after: {}",
+                ctx.task_attempt.id,
+                reason
+            );
+            return Ok(false);
+        };
+
+        let typ = match action.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(req) => {
+                ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                    executor_profile_id: next.clone(),
+                    ..req.clone()
+                })
+            }
+            ExecutorActionType::CodingAgentFollowUpRequest(req) => {
+                ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                    executor_profile_id: next.clone(),
+                    ..req.clone()
+                })
+            }
+            _ => return Ok(false),
+        };
+        let fallback = ExecutorAction::new(typ, action.next_action.clone());
+        tracing::info!(
+            "Falling back to {} for task attempt {} after provider error: {}",
+            next,
+            ctx.task_attempt.id,
+            reason
+        );
+        let execution_process = self
+            .start_execution(
+                &ctx.task_attempt,
+                &fallback,
+                &ctx.execution_process.run_reason,
+            )
+            .await?;
+        ExecutionFallback::create(
+            pool,
+            execution_process.id,
+            ctx.execution_process.id,
+            ctx.task_attempt.id,
+            next,
+            &reason,
+        )
+        .await?;
+        Ok(true)
+    }
+
+    /// Track the model provider's rate limit from a finished coding agent run.
+    /// Returns how long new attempts with its executor are held back when the
+    /// provider refused the run.
+    async fn update_provider_limits(&self, ctx: &ExecutionContext) -> Option<Duration> {
+        let executor = ctx
+            .execution_process
+            .executor_action()
+            .ok()?
+            .base_executor()?;
+        let rate_limiter = self.execution_queue().rate_limiter();
+        match ctx.execution_process.status {
+            ExecutionProcessStatus::Completed => {
+                rate_limiter.record_success(executor);
+                None
+            }
+            ExecutionProcessStatus::Failed => {
+                let error = self
+                    .get_msg_store_by_id(&ctx.execution_process.id)
+                    .await
+                    .and_then(|store| find_provider_failure(&store.get_history()))?;
+                let backoff =
+                    rate_limiter.record_limit(executor, &error, parse_retry_after(&error));
+                tracing::warn!(
+                    "{} was rate limited by its provider, holding back new attempts for {}s: {}",
+                    executor,
+                    backoff.as_secs(),
+                    error
+                );
+                Some(backoff)
+            }
+            _ => None,
+        }
+    }
+
+    /// Continue the agent session of a coding agent run that a server restart
+    /// interrupted, with the rest of its chain. The new run goes through the
+    /// execution queue, so call `dispatch_queued_attempts` once all are
+    /// handled. Returns false when the run had not recorded a session to
+    /// continue.
+    async fn resume_interrupted_execution(
+        &self,
+        execution_process: &ExecutionProcess,
+    ) -> Result<bool, ContainerError> {
+        let action = execution_process.executor_action()?;
+        let executor_profile_id = match action.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(req) => req.executor_profile_id.clone(),
+            ExecutorActionType::CodingAgentFollowUpRequest(req) => req.executor_profile_id.clone(),
+            _ => return Ok(false),
+        };
+        let pool = &self.db().pool;
+        let Some(session_id) =
+            ExecutorSession::find_by_execution_process_id(pool, execution_process.id)
+                .await?
+                .and_then(|session| session.session_id)
+        else {
+            return Ok(false);
+        };
+        let task_attempt = TaskAttempt::find_by_id(pool, execution_process.task_attempt_id)
+            .await?
+            .ok_or(SqlxError::RowNotFound)?;
+        if task_attempt.archived_at.is_some() {
+            return Ok(false);
+        }
+        self.ensure_container_exists(&task_attempt).await?;
+
+        let follow_up = ExecutorAction::new(
+            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt: RESUME_INSTRUCTIONS.to_string(),
+                session_id: session_id.clone(),
+                executor_profile_id: executor_profile_id.clone(),
+            }),
+            action.next_action.clone(),
+        );
+        let started = self
+            .queue_or_start(
+                &task_attempt,
+                executor_profile_id,
+                Some(PreparedExecution {
+                    executor_action: follow_up,
+                    run_reason: ExecutionProcessRunReason::CodingAgent,
+                    resumes: Some(execution_process.id),
+                }),
+            )
+            .await?;
+        if started.is_none() {
+            tracing::info!(
+                "Queued the resumption of session {} of interrupted execution process {}",
+                session_id,
+                execution_process.id
+            );
+        }
+        Ok(true)
+    }
+
+    /// Link a run to the interrupted run whose session it continues
+    async fn record_resumption(
+        &self,
+        resumed: &ExecutionProcess,
+        interrupted_id: Uuid,
+    ) -> Result<(), ContainerError> {
+        let pool = &self.db().pool;
+        let session_id = ExecutorSession::find_by_execution_process_id(pool, interrupted_id)
+            .await?
+            .and_then(|session| session.session_id)
+            .unwrap_or_default();
+        let last_event_seq = ExecutionProcessEvent::last_seq(pool, interrupted_id).await?;
+        ExecutionResumption::create(
+            pool,
+            resumed.id,
+            interrupted_id,
+            resumed.task_attempt_id,
+            &session_id,
+            last_event_seq,
+        )
+        .await?;
+        tracing::info!(
+            "Resumed session {} of interrupted execution process {} as {}",
+            session_id,
+            interrupted_id,
+            resumed.id
+        );
+        Ok(())
+    }
+
     async fn exit_plan_mode_tool(&self, ctx: ExecutionContext) -> Result<(), ContainerError> {
         let execution_id = ctx.execution_process.id;
 
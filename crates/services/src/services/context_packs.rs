@@ -0,0 +1,150 @@
+use std::path::Path;
+
+use db::models::project_context_pack::ProjectContextPack;
+use ignore::{WalkBuilder, overrides::OverrideBuilder};
+
+/// Largest file whose contents are included; larger files are only named
+const MAX_FILE_BYTES: u64 = 32 * 1024;
+/// Contents included across all packs; files beyond it are only named
+const MAX_TOTAL_BYTES: usize = 128 * 1024;
+/// Files matched by one pack, e.g. when a glob is broader than meant
+const MAX_FILES_PER_PACK: usize = 100;
+
+/// Context to prepend to the task prompt from the project's enabled packs:
+/// their notes, and the files in `worktree` matching their patterns. Files
+/// that are too large, or do not fit anymore, are listed by path for the
+/// agent to read itself.
+pub fn context_packs_prompt(packs: &[ProjectContextPack], worktree: &Path) -> Option<String> {
+    let mut sections = Vec::new();
+    let mut included_bytes = 0;
+    for pack in packs.iter().filter(|pack| pack.enabled) {
+        let mut section = format!("## {}", pack.name);
+        if let Some(notes) = pack.notes.as_deref().map(str::trim)
+            && !notes.is_empty()
+        {
+            section.push_str("\n\n");
+            section.push_str(notes);
+        }
+
+        let mut listed = Vec::new();
+        for path in matching_files(worktree, &pack.patterns) {
+            let relative = path.strip_prefix(worktree).unwrap_or(&path);
+            let display = relative.to_string_lossy().replace('\\', "/");
+            let contents = std::fs::metadata(&path)
+                .ok()
+                .filter(|metadata| metadata.len() <= MAX_FILE_BYTES)
+                .and_then(|_| std::fs::read_to_string(&path).ok())
+                .filter(|contents| included_bytes + contents.len() <= MAX_TOTAL_BYTES);
+            match contents {
+                Some(contents) => {
+                    included_bytes += contents.len();
+                    section.push_str(&format!(
+                        "\n\n`{display}`:\n```\n{}\n```",
+                        contents.trim_end()
+                    ));
+                }
+                None => listed.push(display),
+            }
+        }
+        if !listed.is_empty() {
+            section.push_str("\n\nAlso read these files:");
+            for path in listed {
+                section.push_str(&format!("\n- {path}"));
+            }
+        }
+        sections.push(section);
+    }
+    if sections.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "# Project context\n\n{}\n\n# Task\n\n",
+        sections.join("\n\n")
+    ))
+}
+
+/// Files in `worktree` matching any of `patterns`, in path order. Files
+/// ignored by git are skipped unless a pattern names them.
+fn matching_files(worktree: &Path, patterns: &[String]) -> Vec<std::path::PathBuf> {
+    let mut overrides = OverrideBuilder::new(worktree);
+    for pattern in patterns.iter().map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        if let Err(e) = overrides.add(pattern) {
+            tracing::warn!("Skipping invalid context pack pattern '{}': {}", pattern, e);
+        }
+    }
+    let Ok(overrides) = overrides.build() else {
+        return Vec::new();
+    };
+    if overrides.is_empty() {
+        return Vec::new();
+    }
+
+    let mut files: Vec<_> = WalkBuilder::new(worktree)
+        .overrides(overrides)
+        .build()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+        .map(|entry| entry.into_path())
+        .take(MAX_FILES_PER_PACK)
+        .collect();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use sqlx::types::Json;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn pack(name: &str, patterns: &[&str], notes: Option<&str>) -> ProjectContextPack {
+        ProjectContextPack {
+            id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            name: name.to_string(),
+            patterns: Json(patterns.iter().map(|p| p.to_string()).collect()),
+            notes: notes.map(str::to_string),
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn includes_notes_and_matching_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("docs")).unwrap();
+        std::fs::write(dir.path().join("docs/architecture.md"), "Layers\n").unwrap();
+        std::fs::write(dir.path().join("docs/other.txt"), "skip").unwrap();
+        std::fs::write(
+            dir.path().join("big.md"),
+            "x".repeat(MAX_FILE_BYTES as usize + 1),
+        )
+        .unwrap();
+
+        let mut disabled = pack("Disabled", &["*"], Some("Never shown"));
+        disabled.enabled = false;
+        let packs = [
+            pack(
+                "Architecture",
+                &["docs/*.md", "big.md"],
+                Some("Use the service layer."),
+            ),
+            disabled,
+        ];
+        let prompt = context_packs_prompt(&packs, dir.path()).unwrap();
+
+        assert!(
+            prompt.starts_with("# Project context\n\n## Architecture\n\nUse the service layer.")
+        );
+        assert!(prompt.contains("`docs/architecture.md`:\n```\nLayers\n```"));
+        assert!(prompt.contains("Also read these files:\n- big.md"));
+        assert!(!prompt.contains("other.txt"));
+        assert!(!prompt.contains("Never shown"));
+        assert!(prompt.ends_with("# Task\n\n"));
+
+        assert!(context_packs_prompt(&packs[1..], dir.path()).is_none());
+    }
+}
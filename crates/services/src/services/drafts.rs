@@ -235,7 +235,7 @@ impl DraftsService {
             .ok_or(SqlxError::RowNotFound)
             .map_err(DraftsServiceError::from)?;
 
-        let cleanup_action = container.cleanup_action(project.cleanup_script);
+        let cleanup_action = container.cleanup_action(&project).await?;
 
         let mut prompt = draft.prompt.clone();
         if let Some(image_ids) = &draft.image_ids {
@@ -3,8 +3,10 @@ use std::{str::FromStr, sync::Arc};
 use db::{
     DBService,
     models::{
+        ci_check::CiCheck,
         draft::{Draft, DraftType},
         execution_process::ExecutionProcess,
+        project_column::ProjectColumn,
         task::Task,
         task_attempt::TaskAttempt,
     },
@@ -22,9 +24,34 @@ mod streams;
 #[path = "events/types.rs"]
 pub mod types;
 
-pub use patches::{draft_patch, execution_process_patch, task_attempt_patch, task_patch};
+pub use patches::{
+    draft_patch, execution_process_patch, execution_queue_patch, task_attempt_patch, task_patch,
+    wip_limit_patch,
+};
 pub use types::{EventError, EventPatch, EventPatchInner, HookTables, RecordTypes};
 
+/// Publish which WIP-limited columns of the project are over their limit,
+/// after status changes that don't go through a single tracked column move:
+/// automatic moves when executions start and end, bulk changes and parent
+/// tasks rolling up the status of their subtasks
+pub async fn publish_project_wip_status(
+    pool: &SqlitePool,
+    msg_store: &MsgStore,
+    project_id: Uuid,
+) -> Result<(), SqlxError> {
+    for column in ProjectColumn::find_by_project_id(pool, project_id).await? {
+        let Some(status) = column.wip_status(pool).await? else {
+            continue;
+        };
+        if status.is_over_limit() {
+            msg_store.push_patch(wip_limit_patch::add(&status));
+        } else {
+            msg_store.push_patch(wip_limit_patch::remove(column.id));
+        }
+    }
+    Ok(())
+}
+
 #[derive(Clone)]
 pub struct EventService {
     msg_store: Arc<MsgStore>,
@@ -172,6 +199,11 @@ impl EventService {
                                     // Deletions handled in preupdate hook for reliable data capture
                                     return;
                                 }
+                                (HookTables::CiChecks, SqliteOperation::Delete) => {
+                                    // Checks are dropped before a newer commit's checks are
+                                    // stored, and those update the task
+                                    return;
+                                }
                                 (HookTables::Tasks, _) => {
                                     match Task::find_by_rowid(&db.pool, rowid).await {
                                         Ok(Some(task)) => RecordTypes::Task(task),
@@ -219,6 +251,31 @@ impl EventService {
                                         }
                                     }
                                 }
+                                (HookTables::CiChecks, _) => {
+                                    // Checks reach the board on their attempt's task
+                                    match CiCheck::find_by_rowid(&db.pool, rowid).await {
+                                        Ok(Some(check)) => {
+                                            if let Err(err) =
+                                                EventService::push_task_update_for_attempt(
+                                                    &db.pool,
+                                                    msg_store_for_hook.clone(),
+                                                    check.task_attempt_id,
+                                                )
+                                                .await
+                                            {
+                                                tracing::error!(
+                                                    "Failed to push task update after CI check change: {:?}",
+                                                    err
+                                                );
+                                            }
+                                        }
+                                        Ok(None) => {}
+                                        Err(e) => {
+                                            tracing::error!("Failed to fetch ci_check: {:?}", e);
+                                        }
+                                    }
+                                    return;
+                                }
                                 (HookTables::Drafts, _) => {
                                     match Draft::find_by_rowid(&db.pool, rowid).await {
                                         Ok(Some(draft)) => match draft.draft_type {
@@ -255,17 +312,22 @@ impl EventService {
                                             task.project_id,
                                         )
                                         .await
-                                        && let Some(task_with_status) =
-                                            task_list.into_iter().find(|t| t.id == task.id)
                                     {
-                                        let patch = match hook.operation {
-                                            SqliteOperation::Insert => {
-                                                task_patch::add(&task_with_status)
-                                            }
-                                            SqliteOperation::Update => {
-                                                task_patch::replace(&task_with_status)
+                                        let patch = match (
+                                            task_list.into_iter().find(|t| t.id == task.id),
+                                            &hook.operation,
+                                        ) {
+                                            // Adding over an existing task replaces it, and
+                                            // also brings back a task restored from the trash
+                                            (
+                                                Some(task_with_status),
+                                                SqliteOperation::Insert | SqliteOperation::Update,
+                                            ) => task_patch::add(&task_with_status),
+                                            (Some(task_with_status), _) => {
+                                                task_patch::replace(&task_with_status) // fallback
                                             }
-                                            _ => task_patch::replace(&task_with_status), // fallback
+                                            // Only trashed tasks are missing from the list
+                                            (None, _) => task_patch::remove(task.id),
                                         };
                                         msg_store_for_hook.push_patch(patch);
                                         return;
@@ -1,12 +1,15 @@
 use db::models::{
     draft::{Draft, DraftType},
     execution_process::ExecutionProcess,
+    project_column::ColumnWipStatus,
     task::TaskWithAttemptStatus,
     task_attempt::TaskAttempt,
 };
 use json_patch::{AddOperation, Patch, PatchOperation, RemoveOperation, ReplaceOperation};
 use uuid::Uuid;
 
+use crate::services::execution_queue::QueuedAttempt;
+
 // Shared helper to escape JSON Pointer segments
 fn escape_pointer_segment(s: &str) -> String {
     s.replace('~', "~0").replace('/', "~1")
@@ -202,3 +205,51 @@ pub mod task_attempt_patch {
         })])
     }
 }
+
+/// Helper functions for creating execution queue patches
+pub mod execution_queue_patch {
+    use super::*;
+
+    /// Replace the full queue snapshot (entries are ordered by position)
+    pub fn replace(queue: &[QueuedAttempt]) -> Patch {
+        Patch(vec![PatchOperation::Replace(ReplaceOperation {
+            path: "/execution_queue"
+                .try_into()
+                .expect("Execution queue path should be valid"),
+            value: serde_json::to_value(queue)
+                .expect("Execution queue serialization should not fail"),
+        })])
+    }
+}
+
+/// Helper functions for creating patches of columns over their WIP limit
+pub mod wip_limit_patch {
+    use super::*;
+
+    fn column_path(column_id: Uuid) -> String {
+        format!(
+            "/wip_limits/{}",
+            escape_pointer_segment(&column_id.to_string())
+        )
+    }
+
+    /// Create patch for a column that is over its WIP limit
+    pub fn add(status: &ColumnWipStatus) -> Patch {
+        Patch(vec![PatchOperation::Add(AddOperation {
+            path: column_path(status.column_id)
+                .try_into()
+                .expect("WIP limit path should be valid"),
+            value: serde_json::to_value(status)
+                .expect("WIP limit status serialization should not fail"),
+        })])
+    }
+
+    /// Create patch for a column that is back within its WIP limit
+    pub fn remove(column_id: Uuid) -> Patch {
+        Patch(vec![PatchOperation::Remove(RemoveOperation {
+            path: column_path(column_id)
+                .try_into()
+                .expect("WIP limit path should be valid"),
+        })])
+    }
+}
@@ -32,6 +32,8 @@ pub enum HookTables {
     ExecutionProcesses,
     #[strum(to_string = "drafts")]
     Drafts,
+    #[strum(to_string = "ci_checks")]
+    CiChecks,
 }
 
 #[derive(Serialize, Deserialize, TS)]
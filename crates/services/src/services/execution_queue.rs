@@ -0,0 +1,449 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex as StdMutex},
+};
+
+use chrono::{DateTime, Utc};
+use db::{
+    DBService,
+    models::{
+        execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+        execution_queue_entry::ExecutionQueueEntry,
+    },
+};
+use executors::{actions::ExecutorAction, executors::BaseCodingAgent, profile::ExecutorProfileId};
+use serde::Serialize;
+use sqlx::{Error as SqlxError, types::Json};
+use tokio::sync::{Mutex, RwLock};
+use ts_rs::TS;
+use utils::msg_store::MsgStore;
+use uuid::Uuid;
+
+use super::{config::Config, events::execution_queue_patch, rate_limiter::ProviderRateLimiter};
+
+/// An attempt waiting for executor capacity, as exposed on the events stream
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct QueuedAttempt {
+    pub task_attempt_id: Uuid,
+    pub project_id: Uuid,
+    /// Zero-based position in the queue
+    pub position: usize,
+    pub queued_at: DateTime<Utc>,
+}
+
+/// Action a queued attempt runs when it starts, for attempts that don't
+/// start from the task prompt, like forks continuing a session
+#[derive(Debug, Clone)]
+pub struct PreparedExecution {
+    pub executor_action: ExecutorAction,
+    pub run_reason: ExecutionProcessRunReason,
+    /// Run a server restart interrupted, that this one continues
+    pub resumes: Option<Uuid>,
+}
+
+#[derive(Debug, Clone)]
+pub struct QueueEntry {
+    pub task_attempt_id: Uuid,
+    pub project_id: Uuid,
+    pub executor_profile_id: ExecutorProfileId,
+    /// None to start the attempt from the task prompt
+    pub prepared: Option<PreparedExecution>,
+    pub queued_at: DateTime<Utc>,
+}
+
+impl QueueEntry {
+    fn from_stored(stored: ExecutionQueueEntry) -> Self {
+        let prepared = match (stored.executor_action, stored.run_reason) {
+            (Some(Json(executor_action)), Some(run_reason)) => Some(PreparedExecution {
+                executor_action,
+                run_reason,
+                resumes: stored.resumes_execution_process_id,
+            }),
+            _ => None,
+        };
+        Self {
+            task_attempt_id: stored.task_attempt_id,
+            project_id: stored.project_id,
+            executor_profile_id: stored.executor_profile_id.0,
+            prepared,
+            queued_at: stored.queued_at,
+        }
+    }
+
+    fn to_stored(&self) -> ExecutionQueueEntry {
+        ExecutionQueueEntry {
+            task_attempt_id: self.task_attempt_id,
+            project_id: self.project_id,
+            executor_profile_id: Json(self.executor_profile_id.clone()),
+            executor_action: self
+                .prepared
+                .as_ref()
+                .map(|p| Json(p.executor_action.clone())),
+            run_reason: self.prepared.as_ref().map(|p| p.run_reason.clone()),
+            resumes_execution_process_id: self.prepared.as_ref().and_then(|p| p.resumes),
+            queued_at: self.queued_at,
+        }
+    }
+}
+
+/// Starts that passed the capacity check but whose execution process may not
+/// be running yet, counted per project
+type Reservations = Arc<StdMutex<HashMap<Uuid, i64>>>;
+
+/// Capacity held for one start until it is dropped, by which time the started
+/// execution process counts as running
+#[must_use]
+pub struct StartReservation {
+    reservations: Reservations,
+    project_id: Uuid,
+}
+
+impl Drop for StartReservation {
+    fn drop(&mut self) {
+        let mut reservations = self.reservations.lock().unwrap();
+        if let Some(count) = reservations.get_mut(&self.project_id) {
+            *count -= 1;
+            if *count <= 0 {
+                reservations.remove(&self.project_id);
+            }
+        }
+    }
+}
+
+/// Running counts per project with the reserved starts added
+fn with_reservations(
+    mut running: Vec<(Uuid, i64)>,
+    reservations: &HashMap<Uuid, i64>,
+) -> Vec<(Uuid, i64)> {
+    for (project_id, reserved) in reservations {
+        match running.iter_mut().find(|(id, _)| id == project_id) {
+            Some((_, count)) => *count += reserved,
+            None => running.push((*project_id, *reserved)),
+        }
+    }
+    running
+}
+
+/// Index of the first entry that could start now: its project has capacity
+/// and its executor is not rate limited
+fn first_ready(
+    entries: &VecDeque<QueueEntry>,
+    running: &[(Uuid, i64)],
+    limits: ConcurrencyLimits,
+    is_limited: impl Fn(BaseCodingAgent) -> bool,
+) -> Option<usize> {
+    entries.iter().position(|e| {
+        limits.has_capacity(running, e.project_id) && !is_limited(e.executor_profile_id.executor)
+    })
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ConcurrencyLimits {
+    global: Option<usize>,
+    per_project: Option<usize>,
+}
+
+impl ConcurrencyLimits {
+    fn has_capacity(&self, running: &[(Uuid, i64)], project_id: Uuid) -> bool {
+        let total: i64 = running.iter().map(|(_, count)| count).sum();
+        let in_project = running
+            .iter()
+            .find(|(id, _)| *id == project_id)
+            .map(|(_, count)| *count)
+            .unwrap_or(0);
+        let within_global = self.global.is_none_or(|max| (total as usize) < max);
+        let within_project = self
+            .per_project
+            .is_none_or(|max| (in_project as usize) < max);
+        within_global && within_project
+    }
+}
+
+/// FIFO queue of attempt starts, gated by the concurrency limits in the user
+/// config and by the rate limits of the executors' model providers. Entries
+/// are stored in the database too, so the queue survives a restart.
+#[derive(Clone)]
+pub struct ExecutionQueue {
+    db: DBService,
+    config: Arc<RwLock<Config>>,
+    entries: Arc<Mutex<VecDeque<QueueEntry>>>,
+    reservations: Reservations,
+    events_msg_store: Arc<MsgStore>,
+    rate_limiter: ProviderRateLimiter,
+}
+
+impl ExecutionQueue {
+    pub fn new(
+        db: DBService,
+        config: Arc<RwLock<Config>>,
+        events_msg_store: Arc<MsgStore>,
+        rate_limiter: ProviderRateLimiter,
+    ) -> Self {
+        Self {
+            db,
+            config,
+            entries: Arc::new(Mutex::new(VecDeque::new())),
+            reservations: Arc::new(StdMutex::new(HashMap::new())),
+            events_msg_store,
+            rate_limiter,
+        }
+    }
+
+    /// Load the entries stored before the last shutdown
+    pub async fn restore(&self) -> Result<(), SqlxError> {
+        let stored = ExecutionQueueEntry::find_all(&self.db.pool).await?;
+        let mut entries = self.entries.lock().await;
+        *entries = stored.into_iter().map(QueueEntry::from_stored).collect();
+        if !entries.is_empty() {
+            tracing::info!("Restored {} queued task attempts", entries.len());
+        }
+        self.publish(&entries);
+        Ok(())
+    }
+
+    pub fn rate_limiter(&self) -> &ProviderRateLimiter {
+        &self.rate_limiter
+    }
+
+    async fn limits(&self) -> ConcurrencyLimits {
+        let config = self.config.read().await;
+        ConcurrencyLimits {
+            global: config.max_concurrent_executions.map(|n| n as usize),
+            per_project: config
+                .max_concurrent_executions_per_project
+                .map(|n| n as usize),
+        }
+    }
+
+    async fn running_and_reserved(&self) -> Result<Vec<(Uuid, i64)>, SqlxError> {
+        let running = ExecutionProcess::count_running_by_project(&self.db.pool).await?;
+        Ok(with_reservations(
+            running,
+            &self.reservations.lock().unwrap(),
+        ))
+    }
+
+    fn reserve(&self, project_id: Uuid) -> StartReservation {
+        *self
+            .reservations
+            .lock()
+            .unwrap()
+            .entry(project_id)
+            .or_insert(0) += 1;
+        StartReservation {
+            reservations: self.reservations.clone(),
+            project_id,
+        }
+    }
+
+    /// Reserve capacity for a new attempt in `project_id` if it may start
+    /// right away: its executor's provider is not rate limited, no queued
+    /// attempt that could start now is waiting ahead of it and the limits are
+    /// not reached. Queued attempts held back by a rate limit or by their own
+    /// project's limit don't hold it up. The check and the reservation happen
+    /// under the queue lock, so concurrent starts can't both take the last slot.
+    pub async fn try_reserve(
+        &self,
+        project_id: Uuid,
+        executor_profile_id: &ExecutorProfileId,
+    ) -> Result<Option<StartReservation>, SqlxError> {
+        if self.rate_limiter.is_limited(executor_profile_id.executor) {
+            return Ok(None);
+        }
+        let limits = self.limits().await;
+        let entries = self.entries.lock().await;
+        let running = self.running_and_reserved().await?;
+        if first_ready(&entries, &running, limits, |executor| {
+            self.rate_limiter.is_limited(executor)
+        })
+        .is_some()
+            || !limits.has_capacity(&running, project_id)
+        {
+            return Ok(None);
+        }
+        Ok(Some(self.reserve(project_id)))
+    }
+
+    /// Append an attempt to the queue and return its position. An attempt
+    /// that is already queued keeps its place.
+    pub async fn enqueue(
+        &self,
+        task_attempt_id: Uuid,
+        project_id: Uuid,
+        executor_profile_id: ExecutorProfileId,
+        prepared: Option<PreparedExecution>,
+    ) -> Result<usize, SqlxError> {
+        let mut entries = self.entries.lock().await;
+        if let Some(position) = entries
+            .iter()
+            .position(|e| e.task_attempt_id == task_attempt_id)
+        {
+            return Ok(position);
+        }
+        let entry = QueueEntry {
+            task_attempt_id,
+            project_id,
+            executor_profile_id,
+            prepared,
+            queued_at: Utc::now(),
+        };
+        ExecutionQueueEntry::create(&self.db.pool, &entry.to_stored()).await?;
+        entries.push_back(entry);
+        self.publish(&entries);
+        Ok(entries.len() - 1)
+    }
+
+    /// Drop an attempt from the queue; returns false if it was not queued
+    pub async fn remove(&self, task_attempt_id: Uuid) -> Result<bool, SqlxError> {
+        let mut entries = self.entries.lock().await;
+        let Some(index) = entries
+            .iter()
+            .position(|e| e.task_attempt_id == task_attempt_id)
+        else {
+            return Ok(false);
+        };
+        ExecutionQueueEntry::delete(&self.db.pool, task_attempt_id).await?;
+        entries.remove(index);
+        self.publish(&entries);
+        Ok(true)
+    }
+
+    pub async fn position(&self, task_attempt_id: Uuid) -> Option<usize> {
+        self.entries
+            .lock()
+            .await
+            .iter()
+            .position(|e| e.task_attempt_id == task_attempt_id)
+    }
+
+    pub async fn snapshot(&self) -> Vec<QueuedAttempt> {
+        Self::to_queued(&self.entries.lock().await)
+    }
+
+    /// Take the first queued attempt whose project currently has capacity and
+    /// whose executor is not rate limited, with the capacity reserved for it
+    pub async fn pop_ready(&self) -> Result<Option<(QueueEntry, StartReservation)>, SqlxError> {
+        let limits = self.limits().await;
+        let mut entries = self.entries.lock().await;
+        let running = self.running_and_reserved().await?;
+        let Some(index) = first_ready(&entries, &running, limits, |executor| {
+            self.rate_limiter.is_limited(executor)
+        }) else {
+            return Ok(None);
+        };
+        ExecutionQueueEntry::delete(&self.db.pool, entries[index].task_attempt_id).await?;
+        let Some(entry) = entries.remove(index) else {
+            return Ok(None);
+        };
+        self.publish(&entries);
+        let reservation = self.reserve(entry.project_id);
+        Ok(Some((entry, reservation)))
+    }
+
+    fn to_queued(entries: &VecDeque<QueueEntry>) -> Vec<QueuedAttempt> {
+        entries
+            .iter()
+            .enumerate()
+            .map(|(position, e)| QueuedAttempt {
+                task_attempt_id: e.task_attempt_id,
+                project_id: e.project_id,
+                position,
+                queued_at: e.queued_at,
+            })
+            .collect()
+    }
+
+    fn publish(&self, entries: &VecDeque<QueueEntry>) {
+        self.events_msg_store
+            .push_patch(execution_queue_patch::replace(&Self::to_queued(entries)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capacity_respects_global_and_project_limits() {
+        let project_a = Uuid::new_v4();
+        let project_b = Uuid::new_v4();
+        let running = vec![(project_a, 2), (project_b, 1)];
+
+        let unlimited = ConcurrencyLimits::default();
+        assert!(unlimited.has_capacity(&running, project_a));
+
+        let global = ConcurrencyLimits {
+            global: Some(3),
+            per_project: None,
+        };
+        assert!(!global.has_capacity(&running, project_b));
+
+        let per_project = ConcurrencyLimits {
+            global: None,
+            per_project: Some(2),
+        };
+        assert!(!per_project.has_capacity(&running, project_a));
+        assert!(per_project.has_capacity(&running, project_b));
+        assert!(per_project.has_capacity(&running, Uuid::new_v4()));
+    }
+
+    fn entry(project_id: Uuid, executor: BaseCodingAgent) -> QueueEntry {
+        QueueEntry {
+            task_attempt_id: Uuid::new_v4(),
+            project_id,
+            executor_profile_id: ExecutorProfileId::new(executor),
+            prepared: None,
+            queued_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn full_project_does_not_hold_up_others() {
+        let full = Uuid::new_v4();
+        let free = Uuid::new_v4();
+        let limits = ConcurrencyLimits {
+            global: None,
+            per_project: Some(1),
+        };
+        let running = vec![(full, 1)];
+        let entries = VecDeque::from([
+            entry(full, BaseCodingAgent::ClaudeCode),
+            entry(free, BaseCodingAgent::Amp),
+            entry(free, BaseCodingAgent::ClaudeCode),
+        ]);
+
+        assert_eq!(first_ready(&entries, &running, limits, |_| false), Some(1));
+        assert_eq!(
+            first_ready(&entries, &running, limits, |executor| {
+                executor == BaseCodingAgent::Amp
+            }),
+            Some(2)
+        );
+        assert_eq!(first_ready(&entries, &running, limits, |_| true), None);
+    }
+
+    #[test]
+    fn reserved_starts_count_against_capacity() {
+        let project_a = Uuid::new_v4();
+        let project_b = Uuid::new_v4();
+        let reservations = Arc::new(StdMutex::new(HashMap::new()));
+        let limits = ConcurrencyLimits {
+            global: None,
+            per_project: Some(1),
+        };
+
+        *reservations.lock().unwrap().entry(project_b).or_insert(0) += 1;
+        let reservation = StartReservation {
+            reservations: reservations.clone(),
+            project_id: project_b,
+        };
+        let running = with_reservations(vec![(project_a, 1)], &reservations.lock().unwrap());
+        assert!(!limits.has_capacity(&running, project_a));
+        assert!(!limits.has_capacity(&running, project_b));
+
+        drop(reservation);
+        assert!(reservations.lock().unwrap().is_empty());
+        let running = with_reservations(vec![(project_a, 1)], &reservations.lock().unwrap());
+        assert!(limits.has_capacity(&running, project_b));
+    }
+}
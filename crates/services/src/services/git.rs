@@ -1,6 +1,11 @@
-use std::{collections::HashMap, path::Path};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    sync::{Arc, RwLock},
+};
 
 use chrono::{DateTime, Utc};
+use db::models::project_merge_settings::MergeStrategy;
 use git2::{
     BranchType, Delta, DiffFindOptions, DiffOptions, Error as GitError, Reference, Remote,
     Repository, Sort, build::CheckoutBuilder,
@@ -8,12 +13,20 @@ use git2::{
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use ts_rs::TS;
-use utils::diff::{Diff, DiffChangeKind, FileDiffDetails};
+use utils::diff::{
+    Diff, DiffChangeKind, DiffFile, DiffFileHunks, DiffFilePage, FileDiffDetails,
+    apply_selected_hunks, compute_line_change_counts, count_unified_diff_hunks,
+    create_unified_diff_hunks,
+};
 
 // Import for file ranking functionality
 use super::file_ranker::FileStat;
 use super::git_cli::{ChangeType, GitCli, GitCliError, StatusDiffEntry, StatusDiffOptions};
-use crate::services::github_service::GitHubRepoInfo;
+use crate::services::{
+    commit_signing::{self, CommitSignature, CommitSigningError},
+    config::CommitSigningConfig,
+    github_service::GitHubRepoInfo,
+};
 
 #[derive(Debug, Error)]
 pub enum GitServiceError {
@@ -37,10 +50,17 @@ pub enum GitServiceError {
     TokenUnavailable,
     #[error("Rebase in progress; resolve or abort it before retrying")]
     RebaseInProgress,
+    #[error(transparent)]
+    CommitSigning(#[from] CommitSigningError),
+    #[error("Invalid selection: {0}")]
+    InvalidSelection(String),
 }
 /// Service for managing Git operations in task execution workflows
 #[derive(Clone)]
-pub struct GitService {}
+pub struct GitService {
+    /// Shared by all clones, so a config change reaches each of them
+    commit_signing: Arc<RwLock<Option<CommitSigningConfig>>>,
+}
 
 // Max inline diff size for UI (in bytes). Files larger than this will have
 // their contents omitted from the diff stream to avoid UI crashes.
@@ -65,6 +85,15 @@ pub struct GitBranch {
     pub last_commit_date: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct GitCommitSummary {
+    pub sha: String,
+    pub subject: String,
+    pub author_name: Option<String>,
+    #[ts(type = "Date")]
+    pub committed_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone)]
 pub struct HeadInfo {
     pub branch: String,
@@ -139,6 +168,128 @@ pub enum DiffTarget<'p> {
     },
 }
 
+/// Unchanged lines around each change of a file's hunks. Hunks are selected
+/// by index, so they must be listed and applied with the same value.
+pub const DIFF_HUNK_CONTEXT_LINES: usize = 3;
+
+/// A changed file selected for a partial commit
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct DiffSelection {
+    /// Path the file is listed under in the diff
+    pub path: String,
+    /// Indices of the hunks to keep; all of the file's changes when missing
+    #[serde(default)]
+    #[ts(optional)]
+    pub hunks: Option<Vec<usize>>,
+}
+
+/// Contents of one side of a changed file, read up to the inline size limit
+enum DiffSideContent {
+    /// The file doesn't exist on this side, e.g. because it was added
+    Missing,
+    Text(String),
+    Binary,
+    TooLarge,
+}
+
+impl DiffSideContent {
+    fn into_text(self) -> String {
+        match self {
+            Self::Text(text) => text,
+            _ => String::new(),
+        }
+    }
+}
+
+/// The two trees a diff compares, for reading its files one at a time
+struct DiffSides<'r> {
+    repo: &'r Repository,
+    old_tree: git2::Tree<'r>,
+    /// None when the new side is the worktree on disk
+    new_tree: Option<git2::Tree<'r>>,
+}
+
+impl DiffSides<'_> {
+    fn old_size(&self, path: &str) -> Option<usize> {
+        Self::tree_size(self.repo, &self.old_tree, path)
+    }
+
+    fn new_size(&self, path: &str) -> Option<usize> {
+        match &self.new_tree {
+            Some(tree) => Self::tree_size(self.repo, tree, path),
+            None => std::fs::metadata(self.repo.workdir()?.join(path))
+                .ok()
+                .map(|md| md.len() as usize),
+        }
+    }
+
+    fn read_old(&self, path: &str) -> DiffSideContent {
+        Self::read_tree(self.repo, &self.old_tree, path)
+    }
+
+    fn read_new(&self, path: &str) -> DiffSideContent {
+        match &self.new_tree {
+            Some(tree) => Self::read_tree(self.repo, tree, path),
+            None => self.read_workdir(path),
+        }
+    }
+
+    fn tree_size(repo: &Repository, tree: &git2::Tree, path: &str) -> Option<usize> {
+        let entry = tree.get_path(Path::new(path)).ok()?;
+        if entry.kind() != Some(git2::ObjectType::Blob) {
+            return None;
+        }
+        // Only the object header is read, not the blob itself
+        let (size, _) = repo.odb().ok()?.read_header(entry.id()).ok()?;
+        Some(size)
+    }
+
+    fn read_tree(repo: &Repository, tree: &git2::Tree, path: &str) -> DiffSideContent {
+        match Self::tree_size(repo, tree, path) {
+            None => return DiffSideContent::Missing,
+            Some(size) if size > MAX_INLINE_DIFF_BYTES => return DiffSideContent::TooLarge,
+            Some(_) => {}
+        }
+        let Ok(blob) = tree
+            .get_path(Path::new(path))
+            .and_then(|entry| repo.find_blob(entry.id()))
+        else {
+            return DiffSideContent::Missing;
+        };
+        if blob.is_binary() {
+            return DiffSideContent::Binary;
+        }
+        match std::str::from_utf8(blob.content()) {
+            Ok(text) => DiffSideContent::Text(text.to_string()),
+            Err(_) => DiffSideContent::Binary,
+        }
+    }
+
+    fn read_workdir(&self, path: &str) -> DiffSideContent {
+        let Some(workdir) = self.repo.workdir() else {
+            return DiffSideContent::Missing;
+        };
+        let abs_path = workdir.join(path);
+        match std::fs::metadata(&abs_path) {
+            Ok(md) if md.len() as usize > MAX_INLINE_DIFF_BYTES => {
+                return DiffSideContent::TooLarge;
+            }
+            Ok(_) => {}
+            Err(_) => return DiffSideContent::Missing,
+        }
+        let Ok(bytes) = std::fs::read(&abs_path) else {
+            return DiffSideContent::Missing;
+        };
+        // Same binary guard as read_file_to_string
+        if bytes.contains(&0) {
+            return DiffSideContent::Binary;
+        }
+        String::from_utf8(bytes)
+            .map(DiffSideContent::Text)
+            .unwrap_or(DiffSideContent::Binary)
+    }
+}
+
 impl Default for GitService {
     fn default() -> Self {
         Self::new()
@@ -148,7 +299,67 @@ impl Default for GitService {
 impl GitService {
     /// Create a new GitService for the given repository path
     pub fn new() -> Self {
-        Self {}
+        Self {
+            commit_signing: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Sign the commits this service makes from now on with `signing`, or
+    /// stop signing them
+    pub fn set_commit_signing(&self, signing: Option<CommitSigningConfig>) {
+        *self
+            .commit_signing
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = signing;
+    }
+
+    fn commit_signing(&self) -> Option<CommitSigningConfig> {
+        self.commit_signing
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Git CLI for commands that create commits, which signs them when
+    /// signing is configured
+    fn git_cli(&self) -> GitCli {
+        match self.commit_signing() {
+            Some(signing) => GitCli::with_config(commit_signing::git_config(&signing)),
+            None => GitCli::new(),
+        }
+    }
+
+    /// Create a commit with libgit2, signed when signing is configured.
+    /// `update_ref` is moved to the commit like with [`Repository::commit`].
+    fn create_commit(
+        &self,
+        repo: &Repository,
+        update_ref: Option<&str>,
+        signature: &git2::Signature,
+        message: &str,
+        tree: &git2::Tree,
+        parents: &[&git2::Commit],
+    ) -> Result<git2::Oid, GitServiceError> {
+        let Some(signing) = self.commit_signing() else {
+            return Ok(repo.commit(update_ref, signature, signature, message, tree, parents)?);
+        };
+        let buffer = repo.commit_create_buffer(signature, signature, message, tree, parents)?;
+        let content = buffer.as_str().ok_or_else(|| {
+            GitServiceError::InvalidRepository("Commit is not valid UTF-8".to_string())
+        })?;
+        let commit_signature = commit_signing::sign(&signing, content)?;
+        let commit_id = repo.commit_signed(content, &commit_signature, None)?;
+        match update_ref {
+            // Move the branch HEAD points at rather than detaching HEAD
+            Some("HEAD") => {
+                repo.head()?.set_target(commit_id, message)?;
+            }
+            Some(refname) => {
+                repo.reference(refname, commit_id, true, message)?;
+            }
+            None => {}
+        }
+        Ok(commit_id)
     }
 
     /// Open the repository
@@ -250,10 +461,10 @@ impl GitService {
         let tree = repo.find_tree(tree_id)?;
 
         // Create initial commit on main branch
-        let _commit_id = repo.commit(
+        let _commit_id = self.create_commit(
+            repo,
             Some("refs/heads/main"),
             &signature,
-            &signature,
             "Initial commit",
             &tree,
             &[],
@@ -267,7 +478,7 @@ impl GitService {
 
     pub fn commit(&self, path: &Path, message: &str) -> Result<bool, GitServiceError> {
         // Use Git CLI to respect sparse-checkout semantics for staging and commit
-        let git = GitCli::new();
+        let git = self.git_cli();
         let has_changes = git
             .has_changes(path)
             .map_err(|e| GitServiceError::InvalidRepository(format!("git status failed: {e}")))?;
@@ -553,6 +764,458 @@ impl GitService {
             .unwrap_or_default()
     }
 
+    /// Path a DiffFile is listed and looked up by
+    pub fn diff_file_path(file: &DiffFile) -> &str {
+        file.new_path
+            .as_deref()
+            .or(file.old_path.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Files changed by `target`, ordered by path. Contents aren't read, so
+    /// oversized files are flagged but binary files and line counts are only
+    /// known for the files of a page from [`Self::get_diff_file_page`].
+    pub fn list_diff_files(&self, target: DiffTarget) -> Result<Vec<DiffFile>, GitServiceError> {
+        self.with_diff_files(target, |_, files| files)
+    }
+
+    /// Up to `limit` of the files changed by `target`, starting at `offset`.
+    /// Only the files on the page are read to count their lines.
+    pub fn get_diff_file_page(
+        &self,
+        target: DiffTarget,
+        offset: usize,
+        limit: usize,
+    ) -> Result<DiffFilePage, GitServiceError> {
+        self.with_diff_files(target, |sides, files| {
+            let total = files.len();
+            let files: Vec<DiffFile> = files
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .map(|mut file| {
+                    Self::load_diff_file(sides, &mut file);
+                    file
+                })
+                .collect();
+            let end = offset + files.len();
+            DiffFilePage {
+                files,
+                total,
+                next_offset: (end < total).then_some(end),
+            }
+        })
+    }
+
+    /// Hunks of the file listed at `path`, with `context_lines` unchanged
+    /// lines around each change. None when `target` doesn't change the file.
+    pub fn get_diff_file_hunks(
+        &self,
+        target: DiffTarget,
+        path: &str,
+        context_lines: usize,
+    ) -> Result<Option<DiffFileHunks>, GitServiceError> {
+        self.with_diff_files(target, |sides, files| {
+            let mut file = files
+                .into_iter()
+                .find(|file| Self::diff_file_path(file) == path)?;
+            let hunks = Self::load_diff_file(sides, &mut file)
+                .map(|(old, new)| create_unified_diff_hunks(&old, &new, context_lines))
+                .unwrap_or_default();
+            Some(DiffFileHunks { file, hunks })
+        })
+    }
+
+    /// Commit only the selected changes of a worktree. The commit goes on top
+    /// of HEAD with the tree of `base_commit` plus the selection, replacing
+    /// what HEAD had committed, so the branch's diff against the base holds
+    /// just the selection. The changes left out, committed or not, stay in
+    /// the worktree uncommitted, or are discarded with `discard_rest`. As
+    /// that would drop committed work, `discard_rest` is refused while HEAD
+    /// changes files that aren't selected in full. Returns the new commit's
+    /// SHA.
+    pub fn commit_selected_changes(
+        &self,
+        worktree_path: &Path,
+        base_commit: &Commit,
+        selection: &[DiffSelection],
+        message: &str,
+        discard_rest: bool,
+    ) -> Result<String, GitServiceError> {
+        if selection.is_empty() {
+            return Err(GitServiceError::InvalidSelection(
+                "No changes selected".to_string(),
+            ));
+        }
+        let target = DiffTarget::Worktree {
+            worktree_path,
+            base_commit,
+        };
+        let tree_id = self.with_diff_files(target, |sides, files| {
+            if discard_rest {
+                Self::ensure_commits_selected(sides, &files, selection)?;
+            }
+            Self::selected_tree(sides, &files, selection)
+        })??;
+
+        let repo = self.open_repo(worktree_path)?;
+        let tree = repo.find_tree(tree_id)?;
+        let head = repo.head()?.peel_to_commit()?;
+        let signature = self.signature_with_fallback(&repo)?;
+        let commit_id = self.create_commit(&repo, None, &signature, message, &tree, &[&head])?;
+        let sha = commit_id.to_string();
+
+        if discard_rest {
+            self.discard_worktree_changes(worktree_path, &sha)?;
+        } else {
+            // Move the branch and index only; the rest stays in the files
+            GitCli::new()
+                .git(worktree_path, ["reset", "--mixed", sha.as_str()])
+                .map_err(|e| {
+                    GitServiceError::InvalidRepository(format!("git reset --mixed failed: {e}"))
+                })?;
+        }
+        Ok(sha)
+    }
+
+    /// Refuse a selection that leaves out changes HEAD has committed since
+    /// the diff's old side; only files selected without hunks count as kept
+    fn ensure_commits_selected(
+        sides: &DiffSides,
+        files: &[DiffFile],
+        selection: &[DiffSelection],
+    ) -> Result<(), GitServiceError> {
+        let repo = sides.repo;
+        let head_tree = repo.head()?.peel_to_tree()?;
+        let committed = repo.diff_tree_to_tree(Some(&sides.old_tree), Some(&head_tree), None)?;
+        let kept: HashSet<&str> = selection
+            .iter()
+            .filter(|selected| selected.hunks.is_none())
+            .filter_map(|selected| {
+                files
+                    .iter()
+                    .find(|file| Self::diff_file_path(file) == selected.path)
+            })
+            .flat_map(|file| [file.old_path.as_deref(), file.new_path.as_deref()])
+            .flatten()
+            .collect();
+        let left_out: Vec<String> = committed
+            .deltas()
+            .filter_map(|delta| delta.new_file().path().or(delta.old_file().path()))
+            .map(|path| path.to_string_lossy().into_owned())
+            .filter(|path| !kept.contains(path.as_str()))
+            .collect();
+        if !left_out.is_empty() {
+            return Err(GitServiceError::InvalidSelection(format!(
+                "Discarding the rest would drop committed changes to {}; select them in full or keep the rest",
+                left_out.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// Tree of the diff's old side with the selected changes applied
+    fn selected_tree(
+        sides: &DiffSides,
+        files: &[DiffFile],
+        selection: &[DiffSelection],
+    ) -> Result<git2::Oid, GitServiceError> {
+        let repo = sides.repo;
+        let workdir = repo.workdir().ok_or_else(|| {
+            GitServiceError::InvalidRepository("Repository has no working directory".to_string())
+        })?;
+        let mut update = git2::build::TreeUpdateBuilder::new();
+        for selected in selection {
+            let file = files
+                .iter()
+                .find(|file| Self::diff_file_path(file) == selected.path)
+                .ok_or_else(|| {
+                    GitServiceError::InvalidSelection(format!("{} is not changed", selected.path))
+                })?;
+            if let Some(old_path) = &file.old_path
+                && file.new_path.as_ref() != Some(old_path)
+                && !matches!(file.change, DiffChangeKind::Copied)
+            {
+                update.remove(old_path.as_str());
+            }
+            if selected
+                .hunks
+                .as_ref()
+                .is_some_and(|hunks| hunks.is_empty())
+            {
+                return Err(GitServiceError::InvalidSelection(format!(
+                    "No hunks of {} are selected; leave the file out instead",
+                    selected.path
+                )));
+            }
+            let Some(new_path) = &file.new_path else {
+                if selected.hunks.is_some() {
+                    return Err(GitServiceError::InvalidSelection(format!(
+                        "{} is deleted; select the whole file",
+                        selected.path
+                    )));
+                }
+                continue;
+            };
+
+            let abs_path = workdir.join(new_path);
+            let metadata = std::fs::symlink_metadata(&abs_path)?;
+            let (content, mode) = if metadata.file_type().is_symlink() {
+                if selected.hunks.is_some() {
+                    return Err(GitServiceError::InvalidSelection(format!(
+                        "{} is a symlink; select the whole file",
+                        selected.path
+                    )));
+                }
+                let link = std::fs::read_link(&abs_path)?;
+                (
+                    link.to_string_lossy().into_owned().into_bytes(),
+                    git2::FileMode::Link,
+                )
+            } else {
+                let content = match &selected.hunks {
+                    None => std::fs::read(&abs_path)?,
+                    Some(hunks) => {
+                        let mut file = file.clone();
+                        let (old, new) =
+                            Self::load_diff_file(sides, &mut file).ok_or_else(|| {
+                                GitServiceError::InvalidSelection(format!(
+                                    "{} is binary or too large to select hunks from",
+                                    selected.path
+                                ))
+                            })?;
+                        let count = count_unified_diff_hunks(&old, &new, DIFF_HUNK_CONTEXT_LINES);
+                        if let Some(index) = hunks.iter().find(|&&index| index >= count) {
+                            return Err(GitServiceError::InvalidSelection(format!(
+                                "{} has no hunk {index}; it has {count}",
+                                selected.path
+                            )));
+                        }
+                        apply_selected_hunks(&old, &new, DIFF_HUNK_CONTEXT_LINES, hunks)
+                            .into_bytes()
+                    }
+                };
+                (content, Self::workdir_file_mode(&metadata))
+            };
+            let blob_id = repo.blob(&content)?;
+            update.upsert(new_path.as_str(), blob_id, mode);
+        }
+        Ok(update.create_updated(repo, &sides.old_tree)?)
+    }
+
+    fn workdir_file_mode(metadata: &std::fs::Metadata) -> git2::FileMode {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if metadata.permissions().mode() & 0o111 != 0 {
+                return git2::FileMode::BlobExecutable;
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = metadata;
+        git2::FileMode::Blob
+    }
+
+    /// List the files `target` changes and hand them to `f`, which can read
+    /// their contents through the DiffSides
+    fn with_diff_files<T>(
+        &self,
+        target: DiffTarget,
+        f: impl FnOnce(&DiffSides, Vec<DiffFile>) -> T,
+    ) -> Result<T, GitServiceError> {
+        match target {
+            DiffTarget::Worktree {
+                worktree_path,
+                base_commit,
+            } => {
+                // Use Git CLI to compute diff vs base to avoid sparse false deletions
+                let repo = Repository::open(worktree_path)?;
+                let old_tree = repo.find_commit(base_commit.as_oid())?.tree()?;
+                let files = GitCli::new()
+                    .diff_status(worktree_path, base_commit, StatusDiffOptions::default())
+                    .map_err(|e| {
+                        GitServiceError::InvalidRepository(format!("git diff failed: {e}"))
+                    })?
+                    .into_iter()
+                    .map(|e| {
+                        let (change, old_path, new_path) = Self::status_entry_change(e);
+                        DiffFile {
+                            change,
+                            old_path,
+                            new_path,
+                            binary: false,
+                            too_large: false,
+                            additions: None,
+                            deletions: None,
+                        }
+                    })
+                    .collect();
+                let sides = DiffSides {
+                    repo: &repo,
+                    old_tree,
+                    new_tree: None,
+                };
+                Ok(Self::pass_diff_files(&sides, files, f))
+            }
+            DiffTarget::Branch {
+                repo_path,
+                branch_name,
+                base_branch,
+            } => {
+                let repo = self.open_repo(repo_path)?;
+                let old_tree = Self::find_branch(&repo, base_branch)?
+                    .get()
+                    .peel_to_commit()?
+                    .tree()?;
+                let new_tree = Self::find_branch(&repo, branch_name)?
+                    .get()
+                    .peel_to_commit()?
+                    .tree()?;
+                Self::with_tree_diff_files(&repo, old_tree, new_tree, f)
+            }
+            DiffTarget::Commit {
+                repo_path,
+                commit_sha,
+            } => {
+                let repo = self.open_repo(repo_path)?;
+                let commit_oid = git2::Oid::from_str(commit_sha).map_err(|_| {
+                    GitServiceError::InvalidRepository(format!("Invalid commit SHA: {commit_sha}"))
+                })?;
+                let commit = repo.find_commit(commit_oid)?;
+                let parent = commit.parent(0).map_err(|_| {
+                    GitServiceError::InvalidRepository(
+                        "Commit has no parent; cannot diff a squash merge without a baseline"
+                            .into(),
+                    )
+                })?;
+                Self::with_tree_diff_files(&repo, parent.tree()?, commit.tree()?, f)
+            }
+        }
+    }
+
+    fn with_tree_diff_files<'r, T>(
+        repo: &'r Repository,
+        old_tree: git2::Tree<'r>,
+        new_tree: git2::Tree<'r>,
+        f: impl FnOnce(&DiffSides, Vec<DiffFile>) -> T,
+    ) -> Result<T, GitServiceError> {
+        let mut diff_opts = DiffOptions::new();
+        diff_opts.include_typechange(true);
+        let mut diff =
+            repo.diff_tree_to_tree(Some(&old_tree), Some(&new_tree), Some(&mut diff_opts))?;
+
+        // Enable rename detection
+        let mut find_opts = DiffFindOptions::new();
+        diff.find_similar(Some(&mut find_opts))?;
+
+        let files = diff
+            .deltas()
+            .filter(|delta| delta.status() != Delta::Unreadable)
+            .map(|delta| Self::delta_to_diff_file(&delta))
+            .collect();
+        let sides = DiffSides {
+            repo,
+            old_tree,
+            new_tree: Some(new_tree),
+        };
+        Ok(Self::pass_diff_files(&sides, files, f))
+    }
+
+    /// Flag oversized files by their sizes alone and sort the files by path
+    /// before handing them to `f`
+    fn pass_diff_files<T>(
+        sides: &DiffSides,
+        mut files: Vec<DiffFile>,
+        f: impl FnOnce(&DiffSides, Vec<DiffFile>) -> T,
+    ) -> T {
+        for file in &mut files {
+            let old_size = file.old_path.as_deref().and_then(|p| sides.old_size(p));
+            let new_size = file.new_path.as_deref().and_then(|p| sides.new_size(p));
+            file.too_large = old_size
+                .max(new_size)
+                .is_some_and(|size| size > MAX_INLINE_DIFF_BYTES);
+        }
+        files.sort_by(|a, b| Self::diff_file_path(a).cmp(Self::diff_file_path(b)));
+        f(sides, files)
+    }
+
+    fn delta_to_diff_file(delta: &git2::DiffDelta) -> DiffFile {
+        let status = delta.status();
+        let path_of = |file: git2::DiffFile| file.path().map(|p| p.to_string_lossy().to_string());
+        let old_path = match status {
+            Delta::Added | Delta::Untracked => None,
+            _ => path_of(delta.old_file()),
+        };
+        let new_path = match status {
+            Delta::Deleted => None,
+            _ => path_of(delta.new_file()),
+        };
+        let change = match status {
+            Delta::Added | Delta::Untracked => DiffChangeKind::Added,
+            Delta::Deleted => DiffChangeKind::Deleted,
+            Delta::Renamed => DiffChangeKind::Renamed,
+            Delta::Copied => DiffChangeKind::Copied,
+            // Same blob under a different mode, e.g. chmod +x
+            Delta::Modified
+                if delta.old_file().id() == delta.new_file().id()
+                    && delta.old_file().mode() != delta.new_file().mode() =>
+            {
+                DiffChangeKind::PermissionChange
+            }
+            _ => DiffChangeKind::Modified,
+        };
+        DiffFile {
+            change,
+            old_path,
+            new_path,
+            binary: false,
+            too_large: false,
+            additions: None,
+            deletions: None,
+        }
+    }
+
+    /// Read both sides of `file`, flag it when either is binary or too large,
+    /// and count its changed lines. Returns both texts when the file can be
+    /// diffed line by line.
+    fn load_diff_file(sides: &DiffSides, file: &mut DiffFile) -> Option<(String, String)> {
+        if file.too_large {
+            return None;
+        }
+        let old = file
+            .old_path
+            .as_deref()
+            .map_or(DiffSideContent::Missing, |path| sides.read_old(path));
+        let new = file
+            .new_path
+            .as_deref()
+            .map_or(DiffSideContent::Missing, |path| sides.read_new(path));
+        let (old, new) = match (old, new) {
+            (DiffSideContent::TooLarge, _) | (_, DiffSideContent::TooLarge) => {
+                file.too_large = true;
+                return None;
+            }
+            (DiffSideContent::Binary, _) | (_, DiffSideContent::Binary) => {
+                file.binary = true;
+                return None;
+            }
+            (old, new) => (old.into_text(), new.into_text()),
+        };
+
+        // Reported as modified with identical contents: only the mode changed
+        if matches!(file.change, DiffChangeKind::Modified) && old == new {
+            file.change = DiffChangeKind::PermissionChange;
+        }
+        let (additions, deletions) = if old.is_empty() || new.is_empty() {
+            (new.lines().count(), old.lines().count())
+        } else {
+            compute_line_change_counts(&old, &new)
+        };
+        file.additions = Some(additions);
+        file.deletions = Some(deletions);
+        Some((old, new))
+    }
+
     /// Helper function to convert blob to string content
     fn blob_to_string(blob: &git2::Blob) -> Option<String> {
         if blob.is_binary() {
@@ -637,11 +1300,10 @@ impl GitService {
         }
     }
 
-    /// Create Diff entries from git_cli::StatusDiffEntry
-    /// New Diff format is flattened with change kind, paths, and optional contents.
-    fn status_entry_to_diff(repo: &Repository, base_tree: &git2::Tree, e: StatusDiffEntry) -> Diff {
+    /// Change kind and old/new paths of a git_cli::StatusDiffEntry
+    fn status_entry_change(e: StatusDiffEntry) -> (DiffChangeKind, Option<String>, Option<String>) {
         // Map ChangeType to DiffChangeKind
-        let mut change = match e.change {
+        let change = match e.change {
             ChangeType::Added => DiffChangeKind::Added,
             ChangeType::Deleted => DiffChangeKind::Deleted,
             ChangeType::Modified => DiffChangeKind::Modified,
@@ -663,6 +1325,13 @@ impl GitService {
             ChangeType::Renamed | ChangeType::Copied => (e.old_path.clone(), Some(e.path.clone())),
             ChangeType::Unknown(_) => (e.old_path.clone(), Some(e.path.clone())),
         };
+        (change, old_path_opt, new_path_opt)
+    }
+
+    /// Create Diff entries from git_cli::StatusDiffEntry
+    /// New Diff format is flattened with change kind, paths, and optional contents.
+    fn status_entry_to_diff(repo: &Repository, base_tree: &git2::Tree, e: StatusDiffEntry) -> Diff {
+        let (mut change, old_path_opt, new_path_opt) = Self::status_entry_change(e);
 
         // Decide if we should omit content by size (either side)
         let mut content_omitted = false;
@@ -768,6 +1437,28 @@ impl GitService {
         task_branch_name: &str,
         base_branch_name: &str,
         commit_message: &str,
+    ) -> Result<String, GitServiceError> {
+        self.merge_changes_with_strategy(
+            base_worktree_path,
+            task_worktree_path,
+            task_branch_name,
+            base_branch_name,
+            MergeStrategy::Squash,
+            commit_message,
+        )
+    }
+
+    /// Merge the task branch into the base branch with `strategy` and return
+    /// the new tip of the base branch. The rebase strategy fast-forwards the
+    /// base branch, so `commit_message` is unused for it.
+    pub fn merge_changes_with_strategy(
+        &self,
+        base_worktree_path: &Path,
+        task_worktree_path: &Path,
+        task_branch_name: &str,
+        base_branch_name: &str,
+        strategy: MergeStrategy,
+        commit_message: &str,
     ) -> Result<String, GitServiceError> {
         // Open the repositories
         let task_repo = self.open_repo(task_worktree_path)?;
@@ -788,7 +1479,7 @@ impl GitService {
         match self.find_checkout_path_for_branch(base_worktree_path, base_branch_name)? {
             Some(base_checkout_path) => {
                 // base branch is checked out somewhere - use CLI merge
-                let git_cli = GitCli::new();
+                let git_cli = self.git_cli();
 
                 // Safety check: base branch has no staged changes
                 if git_cli
@@ -805,16 +1496,28 @@ impl GitService {
 
                 // Use CLI merge in base context
                 self.ensure_cli_commit_identity(&base_checkout_path)?;
-                let sha = git_cli
-                    .merge_squash_commit(
+                let sha = match strategy {
+                    MergeStrategy::Squash => git_cli.merge_squash_commit(
                         &base_checkout_path,
                         base_branch_name,
                         task_branch_name,
                         commit_message,
-                    )
-                    .map_err(|e| {
-                        GitServiceError::InvalidRepository(format!("CLI merge failed: {e}"))
-                    })?;
+                    ),
+                    MergeStrategy::MergeCommit => git_cli.merge_no_ff_commit(
+                        &base_checkout_path,
+                        base_branch_name,
+                        task_branch_name,
+                        commit_message,
+                    ),
+                    MergeStrategy::Rebase => git_cli.merge_fast_forward(
+                        &base_checkout_path,
+                        base_branch_name,
+                        task_branch_name,
+                    ),
+                }
+                .map_err(|e| {
+                    GitServiceError::InvalidRepository(format!("CLI merge failed: {e}"))
+                })?;
 
                 // Update task branch ref for continuity
                 let task_refname = format!("refs/heads/{task_branch_name}");
@@ -835,28 +1538,50 @@ impl GitService {
                 let base_commit = base_branch.get().peel_to_commit()?;
                 let task_commit = task_branch.get().peel_to_commit()?;
 
-                // Create the squash commit in-memory (no checkout) and update the base branch ref
+                // Create the commit in-memory (no checkout) and update the base branch ref
                 let signature = self.signature_with_fallback(&task_repo)?;
-                let squash_commit_id = self.perform_squash_merge(
-                    &task_repo,
-                    &base_commit,
-                    &task_commit,
-                    &signature,
-                    commit_message,
-                    base_branch_name,
-                )?;
+                let merged_commit_id = match strategy {
+                    MergeStrategy::Squash => self.perform_squash_merge(
+                        &task_repo,
+                        &base_commit,
+                        &task_commit,
+                        &signature,
+                        commit_message,
+                        base_branch_name,
+                    )?,
+                    MergeStrategy::MergeCommit => self.perform_merge_commit(
+                        &task_repo,
+                        &base_commit,
+                        &task_commit,
+                        &signature,
+                        commit_message,
+                        base_branch_name,
+                    )?,
+                    MergeStrategy::Rebase => {
+                        // The task branch contains the base branch (checked
+                        // above), so this is a fast-forward
+                        let refname = format!("refs/heads/{base_branch_name}");
+                        task_repo.reference(
+                            &refname,
+                            task_commit.id(),
+                            true,
+                            "Fast-forward merge",
+                        )?;
+                        task_commit.id()
+                    }
+                };
 
-                // Update the task branch to the new squash commit so follow-up
+                // Update the task branch to the merged commit so follow-up
                 // work can continue from the merged state without conflicts.
                 let task_refname = format!("refs/heads/{task_branch_name}");
                 base_repo.reference(
                     &task_refname,
-                    squash_commit_id,
+                    merged_commit_id,
                     true,
-                    "Reset task branch after squash merge",
+                    "Reset task branch after merge",
                 )?;
 
-                Ok(squash_commit_id.to_string())
+                Ok(merged_commit_id.to_string())
             }
         }
     }
@@ -936,6 +1661,22 @@ impl GitService {
         self.get_branch_status_inner(&repo, &branch_ref, &base_branch_ref)
     }
 
+    /// Update a remote-tracking branch from its remote. Local branches are
+    /// left as they are.
+    pub fn fetch_remote_branch(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        github_token: &str,
+    ) -> Result<(), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let branch_ref = Self::find_branch(&repo, branch_name)?.into_reference();
+        if branch_ref.is_remote() {
+            self.fetch_branch_from_remote(&repo, github_token, &branch_ref)?;
+        }
+        Ok(())
+    }
+
     pub fn is_worktree_clean(&self, worktree_path: &Path) -> Result<bool, GitServiceError> {
         let repo = self.open_repo(worktree_path)?;
         match self.check_worktree_clean(&repo) {
@@ -1065,6 +1806,64 @@ impl GitService {
         Ok(commit.summary().unwrap_or("(no subject)").to_string())
     }
 
+    /// List commits reachable from `branch_name` but not from `base_branch`, newest first
+    pub fn get_branch_commits(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        base_branch: &str,
+    ) -> Result<Vec<GitCommitSummary>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let branch_oid = Self::find_branch(&repo, branch_name)?
+            .get()
+            .peel_to_commit()?
+            .id();
+        let base_oid = Self::find_branch(&repo, base_branch)?
+            .get()
+            .peel_to_commit()?
+            .id();
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+        revwalk.push(branch_oid)?;
+        revwalk.hide(base_oid)?;
+
+        let mut commits = Vec::new();
+        for oid in revwalk {
+            let commit = repo.find_commit(oid?)?;
+            commits.push(GitCommitSummary {
+                sha: commit.id().to_string(),
+                subject: commit.summary().unwrap_or("(no subject)").to_string(),
+                author_name: commit.author().name().map(|s| s.to_string()),
+                committed_at: DateTime::from_timestamp(commit.time().seconds(), 0)
+                    .unwrap_or_else(Utc::now),
+            });
+        }
+        Ok(commits)
+    }
+
+    /// Whether each commit of `branch_name` that `base_branch` lacks is
+    /// signed, newest first
+    pub fn get_commit_signatures(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+        base_branch: &str,
+    ) -> Result<Vec<CommitSignature>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let commits = self.get_branch_commits(repo_path, branch_name, base_branch)?;
+        let mut signatures = Vec::with_capacity(commits.len());
+        for commit in commits {
+            let oid = git2::Oid::from_str(&commit.sha)?;
+            signatures.push(CommitSignature {
+                signed: repo.extract_signature(&oid, None).is_ok(),
+                sha: commit.sha,
+                subject: commit.subject,
+            });
+        }
+        Ok(signatures)
+    }
+
     /// Compare two OIDs and return (ahead, behind) counts: how many commits
     /// `from_oid` is ahead of and behind `to_oid`.
     pub fn ahead_behind_commits_by_oid(
@@ -1168,6 +1967,40 @@ impl GitService {
         Ok(())
     }
 
+    /// Reset the worktree to `commit_sha` and remove untracked files, keeping
+    /// ignored ones such as installed dependencies and the task images and
+    /// attachments copied into the worktree. Returns whether there was
+    /// anything to discard.
+    pub fn discard_worktree_changes(
+        &self,
+        worktree_path: &Path,
+        commit_sha: &str,
+    ) -> Result<bool, GitServiceError> {
+        let cli = super::git_cli::GitCli::new();
+        let has_changes = cli
+            .has_changes(worktree_path)
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git status failed: {e}")))?;
+        if !has_changes && self.get_head_info(worktree_path)?.oid == commit_sha {
+            return Ok(false);
+        }
+        self.reset_worktree_to_commit(worktree_path, commit_sha, true)?;
+        let images = format!("/{}/", utils::path::VIBE_IMAGES_DIR);
+        let attachments = format!("/{}/", utils::path::VIBE_ATTACHMENTS_DIR);
+        cli.git(
+            worktree_path,
+            [
+                "clean",
+                "-fd",
+                "-e",
+                images.as_str(),
+                "-e",
+                attachments.as_str(),
+            ],
+        )
+        .map_err(|e| GitServiceError::InvalidRepository(format!("git clean failed: {e}")))?;
+        Ok(true)
+    }
+
     /// Convenience: Get author of HEAD commit
     pub fn get_head_author(
         &self,
@@ -1342,6 +2175,59 @@ impl GitService {
         commit_message: &str,
         base_branch_name: &str,
     ) -> Result<git2::Oid, GitServiceError> {
+        let tree = Self::merged_tree(repo, base_commit, task_commit)?;
+
+        // Create a squash commit: use merged tree with base_commit as sole parent
+        let squash_commit_id = self.create_commit(
+            repo,
+            None,           // Don't update any reference yet
+            signature,      // Author and committer
+            commit_message, // Custom message
+            &tree,          // Merged tree content
+            &[base_commit], // Single parent: base branch commit
+        )?;
+
+        // Update the base branch reference to point to the new commit
+        let refname = format!("refs/heads/{base_branch_name}");
+        repo.reference(&refname, squash_commit_id, true, "Squash merge")?;
+
+        Ok(squash_commit_id)
+    }
+
+    /// Merge commit of the task commit into the base commit, created without
+    /// touching any working tree, that the base branch is moved to
+    fn perform_merge_commit(
+        &self,
+        repo: &Repository,
+        base_commit: &git2::Commit,
+        task_commit: &git2::Commit,
+        signature: &git2::Signature,
+        commit_message: &str,
+        base_branch_name: &str,
+    ) -> Result<git2::Oid, GitServiceError> {
+        let tree = Self::merged_tree(repo, base_commit, task_commit)?;
+        let merge_commit_id = self.create_commit(
+            repo,
+            None,
+            signature,
+            commit_message,
+            &tree,
+            &[base_commit, task_commit],
+        )?;
+
+        let refname = format!("refs/heads/{base_branch_name}");
+        repo.reference(&refname, merge_commit_id, true, "Merge commit")?;
+
+        Ok(merge_commit_id)
+    }
+
+    /// Tree of the task commit merged into the base commit, failing on
+    /// conflicts
+    fn merged_tree<'a>(
+        repo: &'a Repository,
+        base_commit: &git2::Commit,
+        task_commit: &git2::Commit,
+    ) -> Result<git2::Tree<'a>, GitServiceError> {
         // In-memory merge to detect conflicts without touching the working tree
         let mut merge_opts = git2::MergeOptions::new();
         // Safety and correctness options
@@ -1358,23 +2244,7 @@ impl GitService {
 
         // Write the merged tree back to the repository
         let tree_id = index.write_tree_to(repo)?;
-        let tree = repo.find_tree(tree_id)?;
-
-        // Create a squash commit: use merged tree with base_commit as sole parent
-        let squash_commit_id = repo.commit(
-            None,           // Don't update any reference yet
-            signature,      // Author
-            signature,      // Committer
-            commit_message, // Custom message
-            &tree,          // Merged tree content
-            &[base_commit], // Single parent: base branch commit
-        )?;
-
-        // Update the base branch reference to point to the new commit
-        let refname = format!("refs/heads/{base_branch_name}");
-        repo.reference(&refname, squash_commit_id, true, "Squash merge")?;
-
-        Ok(squash_commit_id)
+        Ok(repo.find_tree(tree_id)?)
     }
 
     /// Rebase a worktree branch onto a new base
@@ -1397,7 +2267,7 @@ impl GitService {
 
         // If a rebase is already in progress, refuse to proceed instead of
         // aborting (which might destroy user changes mid-rebase).
-        let git = GitCli::new();
+        let git = self.git_cli();
         if git.is_rebase_in_progress(worktree_path).unwrap_or(false) {
             return Err(GitServiceError::RebaseInProgress);
         }
@@ -1472,6 +2342,60 @@ impl GitService {
         Ok(final_commit.id().to_string())
     }
 
+    /// Merge the latest `base_branch` into the task branch checked out in
+    /// `worktree_path`. The counterpart of [`Self::rebase_branch`] for users who
+    /// prefer not to rewrite the attempt's history.
+    pub fn merge_base_into_branch(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        base_branch: &str,
+        github_token: Option<String>,
+    ) -> Result<String, GitServiceError> {
+        let worktree_repo = Repository::open(worktree_path)?;
+        let main_repo = self.open_repo(repo_path)?;
+        self.check_worktree_clean(&worktree_repo)?;
+
+        let git = self.git_cli();
+        if git.is_rebase_in_progress(worktree_path).unwrap_or(false)
+            || git.is_merge_in_progress(worktree_path).unwrap_or(false)
+        {
+            return Err(GitServiceError::RebaseInProgress);
+        }
+
+        let base_ref = Self::find_branch(&main_repo, base_branch)?.into_reference();
+        if base_ref.is_remote() {
+            let github_token = github_token.ok_or(GitServiceError::TokenUnavailable)?;
+            self.fetch_branch_from_remote(&main_repo, &github_token, &base_ref)?;
+        }
+
+        self.ensure_cli_commit_identity(worktree_path)?;
+        match git.merge_into_current(worktree_path, base_branch) {
+            Ok(()) => {}
+            Err(GitCliError::CommandFailed(stderr)) => {
+                let conflicted = git.is_merge_in_progress(worktree_path).unwrap_or(false)
+                    || stderr.contains("CONFLICT");
+                if conflicted {
+                    return Err(GitServiceError::MergeConflicts(format!(
+                        "Merging '{base_branch}' produced conflicts. Resolve conflicts and then commit or abort."
+                    )));
+                }
+                return Err(GitServiceError::InvalidRepository(format!(
+                    "Merge failed: {}",
+                    stderr.lines().next().unwrap_or("")
+                )));
+            }
+            Err(e) => {
+                return Err(GitServiceError::InvalidRepository(format!(
+                    "git merge failed: {e}"
+                )));
+            }
+        }
+
+        let final_commit = worktree_repo.head()?.peel_to_commit()?;
+        Ok(final_commit.id().to_string())
+    }
+
     pub fn find_branch_type(
         &self,
         repo_path: &Path,
@@ -1593,6 +2517,50 @@ impl GitService {
         Ok(())
     }
 
+    /// Complete the rebase, merge, cherry-pick or revert in progress in this
+    /// worktree once every conflict is resolved and staged. A rebase stopping
+    /// at the next conflicting commit fails with `MergeConflicts` again.
+    /// Returns the new HEAD commit.
+    pub fn continue_conflicts(&self, worktree_path: &Path) -> Result<String, GitServiceError> {
+        let remaining = self.get_conflicted_files(worktree_path)?;
+        if !remaining.is_empty() {
+            return Err(GitServiceError::MergeConflicts(format!(
+                "Resolve all conflicts before continuing. Conflicted files: {}.",
+                remaining.join(", ")
+            )));
+        }
+        let op = self.detect_conflict_op(worktree_path)?.ok_or_else(|| {
+            GitServiceError::InvalidRepository(
+                "No rebase, merge, cherry-pick or revert is in progress".to_string(),
+            )
+        })?;
+
+        self.ensure_cli_commit_identity(worktree_path)?;
+        let git = self.git_cli();
+        let result = match op {
+            ConflictOp::Rebase => git.continue_rebase(worktree_path),
+            ConflictOp::Merge => git.continue_merge(worktree_path),
+            ConflictOp::CherryPick => git.continue_cherry_pick(worktree_path),
+            ConflictOp::Revert => git.continue_revert(worktree_path),
+        };
+        if let Err(e) = result {
+            let conflicts = self.get_conflicted_files(worktree_path).unwrap_or_default();
+            if !conflicts.is_empty() {
+                return Err(GitServiceError::MergeConflicts(format!(
+                    "The next commit conflicts as well. Conflicted files: {}.",
+                    conflicts.join(", ")
+                )));
+            }
+            return Err(GitServiceError::InvalidRepository(format!(
+                "Continuing failed: {e}"
+            )));
+        }
+
+        let repo = Repository::open(worktree_path)?;
+        let head = repo.head()?.peel_to_commit()?;
+        Ok(head.id().to_string())
+    }
+
     pub fn find_branch<'a>(
         repo: &'a Repository,
         branch_name: &str,
@@ -1645,10 +2613,10 @@ impl GitService {
         let parent_commit = head.peel_to_commit()?;
 
         let commit_message = format!("Delete file: {file_path}");
-        let commit_id = repo.commit(
+        let commit_id = self.create_commit(
+            &repo,
             Some("HEAD"),
             &signature,
-            &signature,
             &commit_message,
             &tree,
             &[&parent_commit],
@@ -1673,21 +2641,27 @@ impl GitService {
         }
     }
 
-    /// Extract GitHub owner and repo name from git repo path
-    pub fn get_github_repo_info(
-        &self,
-        repo_path: &Path,
-    ) -> Result<GitHubRepoInfo, GitServiceError> {
+    /// URL of the repository's default remote
+    pub fn get_default_remote_url(&self, repo_path: &Path) -> Result<String, GitServiceError> {
         let repo = self.open_repo(repo_path)?;
         let remote_name = self.default_remote_name(&repo);
         let remote = repo.find_remote(&remote_name).map_err(|_| {
             GitServiceError::InvalidRepository(format!("No '{remote_name}' remote found"))
         })?;
 
-        let url = remote
+        remote
             .url()
-            .ok_or_else(|| GitServiceError::InvalidRepository("Remote has no URL".to_string()))?;
-        GitHubRepoInfo::from_remote_url(url).map_err(|e| {
+            .map(str::to_string)
+            .ok_or_else(|| GitServiceError::InvalidRepository("Remote has no URL".to_string()))
+    }
+
+    /// Extract GitHub owner and repo name from git repo path
+    pub fn get_github_repo_info(
+        &self,
+        repo_path: &Path,
+    ) -> Result<GitHubRepoInfo, GitServiceError> {
+        let url = self.get_default_remote_url(repo_path)?;
+        GitHubRepoInfo::from_remote_url(&url).map_err(|e| {
             GitServiceError::InvalidRepository(format!("Failed to parse remote URL: {e}"))
         })
     }
@@ -1754,7 +2728,53 @@ impl GitService {
             return Err(e.into());
         }
 
-        let mut branch = Self::find_branch(&repo, branch_name)?;
+        Self::update_remote_tracking_branch(&repo, &remote_name, branch_name)
+    }
+
+    /// Push a branch that may have been rewritten (rebase, reset) without
+    /// clobbering commits someone else pushed: the remote branch is only
+    /// overwritten if it still matches our remote-tracking ref. Uncommitted
+    /// changes in the worktree are left alone.
+    pub fn force_push_with_lease(
+        &self,
+        worktree_path: &Path,
+        branch_name: &str,
+        github_token: &str,
+    ) -> Result<(), GitServiceError> {
+        let repo = Repository::open(worktree_path)?;
+        let remote_name = self.default_remote_name(&repo);
+        let remote = repo.find_remote(&remote_name)?;
+        let remote_url = remote
+            .url()
+            .ok_or_else(|| GitServiceError::InvalidRepository("Remote has no URL".to_string()))?;
+        let https_url = self.convert_to_https_url(remote_url);
+
+        let expected_remote_oid = repo
+            .refname_to_id(&format!("refs/remotes/{remote_name}/{branch_name}"))
+            .ok()
+            .map(|oid| oid.to_string());
+
+        let git_cli = GitCli::new();
+        if let Err(e) = git_cli.force_push_with_lease_token(
+            worktree_path,
+            &https_url,
+            branch_name,
+            expected_remote_oid.as_deref(),
+            github_token,
+        ) {
+            tracing::error!("Force push with lease failed: {}", e);
+            return Err(e.into());
+        }
+
+        Self::update_remote_tracking_branch(&repo, &remote_name, branch_name)
+    }
+
+    fn update_remote_tracking_branch(
+        repo: &Repository,
+        remote_name: &str,
+        branch_name: &str,
+    ) -> Result<(), GitServiceError> {
+        let mut branch = Self::find_branch(repo, branch_name)?;
         if !branch.get().is_remote() {
             if let Some(branch_target) = branch.get().target() {
                 let remote_ref = format!("refs/remotes/{remote_name}/{branch_name}");
@@ -1772,13 +2792,24 @@ impl GitService {
     }
 
     pub fn convert_to_https_url(&self, url: &str) -> String {
-        // Convert SSH URL to HTTPS URL if necessary
-        let new_url = if url.starts_with("git@github.com:") {
-            // Convert git@github.com:owner/repo.git to https://github.com/owner/repo.git
-            url.replace("git@github.com:", "https://github.com/")
-        } else if url.starts_with("ssh://git@github.com/") {
-            // Convert ssh://git@github.com/owner/repo.git to https://github.com/owner/repo.git
-            url.replace("ssh://git@github.com/", "https://github.com/")
+        // Convert SSH URL to HTTPS URL if necessary, for any host (GitHub,
+        // GitLab, self-hosted instances)
+        let new_url = if let Some(rest) = url.strip_prefix("ssh://git@") {
+            // Convert ssh://git@host[:port]/owner/repo.git to https://host/owner/repo.git;
+            // the SSH port means nothing over HTTPS
+            match rest.split_once('/') {
+                Some((host, path)) => {
+                    let host = host.split(':').next().unwrap_or(host);
+                    format!("https://{host}/{path}")
+                }
+                None => url.to_string(),
+            }
+        } else if let Some(rest) = url.strip_prefix("git@") {
+            // Convert git@host:owner/repo.git to https://host/owner/repo.git
+            match rest.split_once(':') {
+                Some((host, path)) => format!("https://{host}/{path}"),
+                None => url.to_string(),
+            }
         } else {
             url.to_string()
         };
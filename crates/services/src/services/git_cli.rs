@@ -42,7 +42,10 @@ pub enum GitCliError {
 }
 
 #[derive(Clone, Default)]
-pub struct GitCli;
+pub struct GitCli {
+    /// Config passed to every command with `-c`, e.g. to sign commits
+    config: Vec<(String, String)>,
+}
 
 /// Parsed change type from `git diff --name-status` output
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -71,6 +74,8 @@ pub struct WorktreeEntry {
     pub path: String,
     pub head_sha: String,
     pub branch: Option<String>,
+    /// Git considers the entry stale, e.g. because its directory is gone
+    pub prunable: bool,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -80,7 +85,13 @@ pub struct StatusDiffOptions {
 
 impl GitCli {
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// A `GitCli` that runs every command with `config` set, without touching
+    /// the repository's config
+    pub fn with_config(config: Vec<(String, String)>) -> Self {
+        Self { config }
     }
 
     /// Run `git -C <repo> worktree add <path> <branch>` (optionally creating the branch with -b)
@@ -132,6 +143,39 @@ impl GitCli {
         Ok(())
     }
 
+    /// Run `git -C <repo> worktree repair <path>` to re-link a worktree whose
+    /// directory or repository was moved without git knowing
+    pub fn worktree_repair(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+    ) -> Result<(), GitCliError> {
+        let args: Vec<OsString> = vec![
+            "worktree".into(),
+            "repair".into(),
+            worktree_path.as_os_str().into(),
+        ];
+        self.git(repo_path, args)?;
+        Ok(())
+    }
+
+    /// Run `git -C <repo> worktree move <from> <to>`
+    pub fn worktree_move(
+        &self,
+        repo_path: &Path,
+        from: &Path,
+        to: &Path,
+    ) -> Result<(), GitCliError> {
+        let args: Vec<OsString> = vec![
+            "worktree".into(),
+            "move".into(),
+            from.as_os_str().into(),
+            to.as_os_str().into(),
+        ];
+        self.git(repo_path, args)?;
+        Ok(())
+    }
+
     /// Return true if there are any changes in the working tree (staged or unstaged).
     pub fn has_changes(&self, worktree_path: &Path) -> Result<bool, GitCliError> {
         let out = self.git(worktree_path, ["status", "--porcelain"])?;
@@ -256,6 +300,7 @@ impl GitCli {
         let mut current_path: Option<String> = None;
         let mut current_head: Option<String> = None;
         let mut current_branch: Option<String> = None;
+        let mut current_prunable = false;
 
         for line in out.lines() {
             let line = line.trim();
@@ -267,6 +312,7 @@ impl GitCli {
                         path,
                         head_sha: head,
                         branch: current_branch.take(),
+                        prunable: std::mem::take(&mut current_prunable),
                     });
                 }
             } else if let Some(path) = line.strip_prefix("worktree ") {
@@ -278,6 +324,8 @@ impl GitCli {
                 current_branch = branch_ref
                     .strip_prefix("refs/heads/")
                     .map(|name| name.to_string());
+            } else if line == "prunable" || line.starts_with("prunable ") {
+                current_prunable = true;
             }
         }
 
@@ -287,6 +335,7 @@ impl GitCli {
                 path,
                 head_sha: head,
                 branch: current_branch,
+                prunable: current_prunable,
             });
         }
 
@@ -333,20 +382,51 @@ impl GitCli {
         remote_url: &str,
         branch: &str,
         token: &str,
+    ) -> Result<(), GitCliError> {
+        self.push_branch_with_token(repo_path, remote_url, branch, None, token)
+    }
+
+    /// Push `branch`, overwriting the remote branch only if it still points at
+    /// `expected_remote_oid` (`None`: only if it does not exist yet).
+    pub fn force_push_with_lease_token(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        branch: &str,
+        expected_remote_oid: Option<&str>,
+        token: &str,
+    ) -> Result<(), GitCliError> {
+        let lease = format!(
+            "--force-with-lease=refs/heads/{branch}:{}",
+            expected_remote_oid.unwrap_or("")
+        );
+        self.push_branch_with_token(repo_path, remote_url, branch, Some(lease), token)
+    }
+
+    fn push_branch_with_token(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        branch: &str,
+        lease: Option<String>,
+        token: &str,
     ) -> Result<(), GitCliError> {
         let refspec = format!("refs/heads/{branch}:refs/heads/{branch}");
         let auth_header = self.build_auth_header(token);
         let envs = self.build_token_env(&auth_header);
 
-        let args = [
+        let mut args = vec![
             OsString::from("-c"),
             OsString::from("credential.helper="),
             OsString::from("--config-env"),
             OsString::from("http.extraHeader=GIT_HTTP_EXTRAHEADER"),
             OsString::from("push"),
-            OsString::from(remote_url),
-            OsString::from(refspec),
         ];
+        if let Some(lease) = lease {
+            args.push(OsString::from(lease));
+        }
+        args.push(OsString::from(remote_url));
+        args.push(OsString::from(refspec));
 
         match self.git_with_env(repo_path, args, &envs) {
             Ok(_) => Ok(()),
@@ -435,6 +515,16 @@ impl GitCli {
         Ok(())
     }
 
+    /// Merge `branch` into the branch checked out in `worktree_path`, creating a merge commit.
+    pub fn merge_into_current(
+        &self,
+        worktree_path: &Path,
+        branch: &str,
+    ) -> Result<(), GitCliError> {
+        self.git(worktree_path, ["merge", "--no-edit", branch])
+            .map(|_| ())
+    }
+
     /// Return true if there is a rebase in progress in this worktree.
     /// We treat this as true when either of Git's rebase state directories exists:
     /// - rebase-merge (interactive rebase)
@@ -539,6 +629,43 @@ impl GitCli {
         Ok(sha)
     }
 
+    /// Merge `from_branch` into `base_branch` with a merge commit, even when
+    /// a fast-forward is possible
+    pub fn merge_no_ff_commit(
+        &self,
+        repo_path: &Path,
+        base_branch: &str,
+        from_branch: &str,
+        message: &str,
+    ) -> Result<String, GitCliError> {
+        self.git(repo_path, ["checkout", base_branch]).map(|_| ())?;
+        self.git(repo_path, ["merge", "--no-ff", "-m", message, from_branch])
+            .map(|_| ())?;
+        let sha = self
+            .git(repo_path, ["rev-parse", "HEAD"])?
+            .trim()
+            .to_string();
+        Ok(sha)
+    }
+
+    /// Move `base_branch` forward to `from_branch`, failing if that needs a
+    /// merge commit
+    pub fn merge_fast_forward(
+        &self,
+        repo_path: &Path,
+        base_branch: &str,
+        from_branch: &str,
+    ) -> Result<String, GitCliError> {
+        self.git(repo_path, ["checkout", base_branch]).map(|_| ())?;
+        self.git(repo_path, ["merge", "--ff-only", from_branch])
+            .map(|_| ())?;
+        let sha = self
+            .git(repo_path, ["rev-parse", "HEAD"])?
+            .trim()
+            .to_string();
+        Ok(sha)
+    }
+
     /// Update a ref to a specific sha in the repo.
     pub fn update_ref(
         &self,
@@ -572,6 +699,74 @@ impl GitCli {
         self.git(worktree_path, ["revert", "--abort"]).map(|_| ())
     }
 
+    /// Continue an in-progress rebase after its conflicts were staged, keeping
+    /// the commit messages as they are
+    pub fn continue_rebase(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git_with_env(
+            worktree_path,
+            ["rebase", "--continue"],
+            &[(OsString::from("GIT_EDITOR"), OsString::from("true"))],
+        )
+        .map(|_| ())
+    }
+
+    /// Conclude an in-progress merge with its prepared message
+    pub fn continue_merge(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git(worktree_path, ["commit", "--no-edit"]).map(|_| ())
+    }
+
+    pub fn continue_cherry_pick(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git_with_env(
+            worktree_path,
+            ["cherry-pick", "--continue"],
+            &[(OsString::from("GIT_EDITOR"), OsString::from("true"))],
+        )
+        .map(|_| ())
+    }
+
+    pub fn continue_revert(&self, worktree_path: &Path) -> Result<(), GitCliError> {
+        self.git_with_env(
+            worktree_path,
+            ["revert", "--continue"],
+            &[(OsString::from("GIT_EDITOR"), OsString::from("true"))],
+        )
+        .map(|_| ())
+    }
+
+    /// Stage one path as it is in the working tree, including its deletion
+    pub fn stage_path(&self, worktree_path: &Path, path: &str) -> Result<(), GitCliError> {
+        self.git(worktree_path, ["add", "-A", "--", path])
+            .map(|_| ())
+    }
+
+    /// Three-way merge of three file versions with `git merge-file --diff3`.
+    /// Returns the merged text, with conflicts between markers labelled
+    /// `ours`, `base` and `theirs`.
+    pub fn merge_file(
+        &self,
+        ours: &Path,
+        base: &Path,
+        theirs: &Path,
+    ) -> Result<String, GitCliError> {
+        self.ensure_available()?;
+        let git = resolve_executable_path_blocking("git").ok_or(GitCliError::NotAvailable)?;
+        let out = Command::new(&git)
+            .args(["merge-file", "-p", "--diff3"])
+            .args(["-L", "ours", "-L", "base", "-L", "theirs"])
+            .arg(ours)
+            .arg(base)
+            .arg(theirs)
+            .output()
+            .map_err(|e| GitCliError::CommandFailed(e.to_string()))?;
+        // The exit code is the number of conflicts, capped at 127
+        match out.status.code() {
+            Some(0..=127) => Ok(String::from_utf8_lossy(&out.stdout).to_string()),
+            _ => Err(GitCliError::CommandFailed(
+                String::from_utf8_lossy(&out.stderr).trim().to_string(),
+            )),
+        }
+    }
+
     /// List files currently in a conflicted (unmerged) state in the worktree.
     pub fn get_conflicted_files(&self, worktree_path: &Path) -> Result<Vec<String>, GitCliError> {
         // `--diff-filter=U` lists paths with unresolved conflicts
@@ -607,8 +802,15 @@ impl GitCli {
         }
     }
 
+    /// Basic auth header for a token. Credentials given as `user:password`,
+    /// for hosts that need a username like Bitbucket, are sent as they are.
     fn build_auth_header(&self, token: &str) -> String {
-        let auth_value = BASE64_STANDARD.encode(format!("x-access-token:{token}"));
+        let credentials = if token.contains(':') {
+            token.to_string()
+        } else {
+            format!("x-access-token:{token}")
+        };
+        let auth_value = BASE64_STANDARD.encode(credentials);
         format!("Authorization: Basic {auth_value}")
     }
 
@@ -660,6 +862,9 @@ impl GitCli {
         let git = resolve_executable_path_blocking("git").ok_or(GitCliError::NotAvailable)?;
         let mut cmd = Command::new(&git);
         cmd.arg("-C").arg(repo_path);
+        for (key, value) in &self.config {
+            cmd.arg("-c").arg(format!("{key}={value}"));
+        }
         for a in args {
             cmd.arg(a);
         }
@@ -688,6 +893,9 @@ impl GitCli {
         let git = resolve_executable_path_blocking("git").ok_or(GitCliError::NotAvailable)?;
         let mut cmd = Command::new(&git);
         cmd.arg("-C").arg(repo_path);
+        for (key, value) in &self.config {
+            cmd.arg("-c").arg(format!("{key}={value}"));
+        }
         for (k, v) in envs {
             cmd.env(k, v);
         }
@@ -0,0 +1,176 @@
+use std::{sync::Arc, time::Duration};
+
+use db::{
+    DBService,
+    models::{
+        github_issue_link::{CreateGitHubIssueLink, GitHubIssueLink},
+        task::{CreateTask, Task},
+    },
+};
+use sqlx::{SqlitePool, error::Error as SqlxError};
+use thiserror::Error;
+use tokio::{sync::RwLock, time::interval};
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use crate::services::{
+    config::Config,
+    github_service::{GitHubIssue, GitHubRepoInfo, GitHubService, GitHubServiceError, IssueFilter},
+};
+
+#[derive(Debug, Error)]
+pub enum GitHubIssuesError {
+    #[error("No GitHub token configured")]
+    NoGitHubToken,
+    #[error(transparent)]
+    GitHubServiceError(#[from] GitHubServiceError),
+    #[error(transparent)]
+    Sqlx(#[from] SqlxError),
+}
+
+/// Tasks created by an import, and how many matching issues already had one
+#[derive(Debug)]
+pub struct ImportedIssues {
+    pub created: Vec<Task>,
+    pub skipped: usize,
+}
+
+/// Create one task per open issue of the repository matching `filter`.
+/// Issues that were imported before are skipped, so importing is repeatable.
+pub async fn import_issues(
+    pool: &SqlitePool,
+    github_service: &GitHubService,
+    repo_info: &GitHubRepoInfo,
+    project_id: Uuid,
+    filter: &IssueFilter,
+    sync_status: bool,
+) -> Result<ImportedIssues, GitHubIssuesError> {
+    let issues = github_service.list_issues(repo_info, filter).await?;
+    let linked =
+        GitHubIssueLink::linked_issue_numbers(pool, &repo_info.owner, &repo_info.repo_name).await?;
+
+    let mut created = Vec::new();
+    let mut skipped = 0;
+    for issue in issues {
+        if linked.contains(&issue.number) {
+            skipped += 1;
+            continue;
+        }
+        let task = Task::create(
+            pool,
+            &CreateTask::from_title_description(
+                project_id,
+                issue.title.clone(),
+                Some(issue_description(&issue)),
+            ),
+            Uuid::new_v4(),
+        )
+        .await?;
+        GitHubIssueLink::create(
+            pool,
+            &CreateGitHubIssueLink {
+                task_id: task.id,
+                repo_owner: &repo_info.owner,
+                repo_name: &repo_info.repo_name,
+                issue_number: issue.number,
+                issue_url: &issue.url,
+                sync_status,
+            },
+        )
+        .await?;
+        created.push(task);
+    }
+
+    info!(
+        "Imported {} issues from {}/{} ({} already imported)",
+        created.len(),
+        repo_info.owner,
+        repo_info.repo_name,
+        skipped
+    );
+    Ok(ImportedIssues { created, skipped })
+}
+
+/// Issue body followed by a link back to the issue
+fn issue_description(issue: &GitHubIssue) -> String {
+    match issue.body.as_deref().map(str::trim) {
+        Some(body) if !body.is_empty() => format!("{body}\n\nImported from {}", issue.url),
+        _ => format!("Imported from {}", issue.url),
+    }
+}
+
+/// Closes the GitHub issues of imported tasks once the tasks are done
+pub struct GitHubIssueSyncService {
+    db: DBService,
+    config: Arc<RwLock<Config>>,
+    poll_interval: Duration,
+}
+
+impl GitHubIssueSyncService {
+    pub async fn spawn(db: DBService, config: Arc<RwLock<Config>>) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            config,
+            poll_interval: Duration::from_secs(60),
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting GitHub issue sync service with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            match self.close_done_issues().await {
+                Err(GitHubIssuesError::NoGitHubToken) => {}
+                Err(e) => error!("Error syncing GitHub issues: {}", e),
+                Ok(()) => {}
+            }
+        }
+    }
+
+    async fn close_done_issues(&self) -> Result<(), GitHubIssuesError> {
+        let pending = GitHubIssueLink::find_pending_close(&self.db.pool).await?;
+        if pending.is_empty() {
+            debug!("No GitHub issues to close");
+            return Ok(());
+        }
+
+        let github_config = self.config.read().await.github.clone();
+        let Some(github_token) = github_config.token() else {
+            warn!(
+                "No GitHub token configured, cannot close {} issues",
+                pending.len()
+            );
+            return Err(GitHubIssuesError::NoGitHubToken);
+        };
+        let github_service = GitHubService::new(&github_token)?;
+
+        for link in pending {
+            let repo_info = GitHubRepoInfo {
+                owner: link.repo_owner.clone(),
+                repo_name: link.repo_name.clone(),
+            };
+            match github_service
+                .close_issue(&repo_info, link.issue_number)
+                .await
+            {
+                Ok(()) => {
+                    info!(
+                        "Closed issue {} of done task {}",
+                        link.issue_url, link.task_id
+                    );
+                    GitHubIssueLink::mark_closed(&self.db.pool, link.task_id).await?;
+                }
+                Err(e) => error!("Failed to close issue {}: {}", link.issue_url, e),
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,15 +1,23 @@
 use std::time::Duration;
 
 use backon::{ExponentialBuilder, Retryable};
-use db::models::merge::{MergeStatus, PullRequestInfo};
-use octocrab::{Octocrab, OctocrabBuilder, models::IssueState};
+use db::models::{
+    ci_check::{CiCheckState, CreateCiCheck},
+    merge::{MergeStatus, PullRequestInfo},
+    pr_review_comment::CreatePrReviewComment,
+    project_merge_settings::MergeStrategy,
+};
+use octocrab::{Octocrab, OctocrabBuilder, models::IssueState, params::pulls::MergeMethod};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::info;
 use ts_rs::TS;
 
-use crate::services::{git::GitServiceError, git_cli::GitCliError};
+use crate::services::{
+    git::GitServiceError, git_cli::GitCliError, hosting::PrMergeOptions,
+    merge_message::split_commit_message,
+};
 
 #[derive(Debug, Error, Serialize, Deserialize, TS)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -145,6 +153,54 @@ pub struct CreatePrRequest {
     pub body: Option<String>,
     pub head_branch: String,
     pub base_branch: String,
+    /// Open the pull request as a draft
+    pub draft: bool,
+}
+
+/// Which issues to fetch; open issues only
+#[derive(Debug, Clone, Default)]
+pub struct IssueFilter {
+    /// Issues must carry all of these labels
+    pub labels: Vec<String>,
+    /// Milestone number
+    pub milestone: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GitHubIssue {
+    pub number: i64,
+    pub title: String,
+    pub body: Option<String>,
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRunList {
+    check_runs: Vec<CheckRun>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRun {
+    name: String,
+    head_sha: String,
+    /// `queued`, `in_progress` or `completed`
+    status: String,
+    conclusion: Option<String>,
+    html_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CombinedStatus {
+    sha: String,
+    statuses: Vec<CommitStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitStatus {
+    context: String,
+    /// `pending`, `success`, `failure` or `error`
+    state: String,
+    target_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -271,6 +327,7 @@ impl GitHubService {
             .pulls(&repo_info.owner, &repo_info.repo_name)
             .create(&request.title, &request.head_branch, &request.base_branch)
             .body(request.body.as_deref().unwrap_or(""))
+            .draft(request.draft)
             .send()
             .await
             .map(Self::map_pull_request)
@@ -330,6 +387,267 @@ impl GitHubService {
         .await
     }
 
+    /// Merge a pull request and return its new status. Without a strategy in
+    /// `options` GitHub makes a merge commit.
+    pub async fn merge_pr(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        pr_number: i64,
+        options: &PrMergeOptions,
+    ) -> Result<PullRequestInfo, GitHubServiceError> {
+        let pulls = self.client.pulls(&repo_info.owner, &repo_info.repo_name);
+        let mut merge = pulls.merge(pr_number as u64);
+        if let Some(strategy) = options.strategy {
+            merge = merge.method(match strategy {
+                MergeStrategy::Squash => MergeMethod::Squash,
+                MergeStrategy::Rebase => MergeMethod::Rebase,
+                MergeStrategy::MergeCommit => MergeMethod::Merge,
+            });
+        }
+        if let Some(message) = &options.commit_message {
+            let (title, body) = split_commit_message(message);
+            merge = merge.title(title);
+            if let Some(body) = body {
+                merge = merge.message(body);
+            }
+        }
+        merge
+            .send()
+            .await
+            .map_err(|err| match GitHubServiceError::from(err) {
+                GitHubServiceError::Client(source) => GitHubServiceError::PullRequest(format!(
+                    "Failed to merge PR #{pr_number}: {source}",
+                    source = format_octocrab_error(&source),
+                )),
+                other => other,
+            })?;
+
+        info!(
+            "Merged GitHub PR #{} in {}/{}",
+            pr_number, repo_info.owner, repo_info.repo_name
+        );
+
+        self.update_pr_status(repo_info, pr_number).await
+    }
+
+    /// Take a draft pull request out of draft. The REST API can't do this, so
+    /// it goes through GraphQL.
+    pub async fn mark_pr_ready(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        pr_number: i64,
+    ) -> Result<PullRequestInfo, GitHubServiceError> {
+        let pr = self
+            .client
+            .pulls(&repo_info.owner, &repo_info.repo_name)
+            .get(pr_number as u64)
+            .await?;
+        if pr.draft != Some(true) {
+            return Ok(Self::map_pull_request(pr));
+        }
+        let node_id = pr.node_id.ok_or_else(|| {
+            GitHubServiceError::PullRequest(format!("PR #{pr_number} has no node id"))
+        })?;
+
+        let response: serde_json::Value = self
+            .client
+            .graphql(&serde_json::json!({
+                "query": "mutation($id: ID!) { markPullRequestReadyForReview(input: { pullRequestId: $id }) { pullRequest { isDraft } } }",
+                "variables": { "id": node_id },
+            }))
+            .await?;
+        if let Some(errors) = response.get("errors") {
+            return Err(GitHubServiceError::PullRequest(format!(
+                "Failed to mark PR #{pr_number} ready for review: {errors}"
+            )));
+        }
+
+        info!(
+            "Marked GitHub PR #{} in {}/{} ready for review",
+            pr_number, repo_info.owner, repo_info.repo_name
+        );
+        self.update_pr_status(repo_info, pr_number).await
+    }
+
+    /// Inline review comments of a pull request, and the summaries of its
+    /// reviews that have one
+    pub async fn list_review_comments(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        pr_number: i64,
+    ) -> Result<Vec<CreatePrReviewComment>, GitHubServiceError> {
+        (|| async {
+            self.list_review_comments_internal(repo_info, pr_number)
+                .await
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e| e.should_retry())
+        .notify(|err: &GitHubServiceError, dur: Duration| {
+            tracing::warn!(
+                "GitHub API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
+    async fn list_review_comments_internal(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        pr_number: i64,
+    ) -> Result<Vec<CreatePrReviewComment>, GitHubServiceError> {
+        let map_err = |err| match GitHubServiceError::from(err) {
+            GitHubServiceError::Client(source) => GitHubServiceError::PullRequest(format!(
+                "Failed to list review comments of PR #{pr_number}: {source}",
+                source = format_octocrab_error(&source),
+            )),
+            other => other,
+        };
+        let pulls = self.client.pulls(&repo_info.owner, &repo_info.repo_name);
+
+        let first_page = pulls
+            .list_comments(Some(pr_number as u64))
+            .per_page(100)
+            .send()
+            .await
+            .map_err(map_err)?;
+        let comments = self.client.all_pages(first_page).await.map_err(map_err)?;
+        let first_page = pulls
+            .list_reviews(pr_number as u64)
+            .per_page(100)
+            .send()
+            .await
+            .map_err(map_err)?;
+        let reviews = self.client.all_pages(first_page).await.map_err(map_err)?;
+
+        let mut review_comments: Vec<_> = reviews
+            .into_iter()
+            .filter_map(|review| {
+                let body = review.body.filter(|body| !body.trim().is_empty())?;
+                Some(CreatePrReviewComment {
+                    external_id: format!("review:{}", review.id.0),
+                    author: review.user.map(|user| user.login).unwrap_or_default(),
+                    body,
+                    path: None,
+                    line: None,
+                    url: Some(review.html_url.to_string()),
+                    commented_at: review.submitted_at?,
+                })
+            })
+            .collect();
+        review_comments.extend(comments.into_iter().map(|comment| {
+            CreatePrReviewComment {
+                external_id: format!("comment:{}", comment.id.0),
+                author: comment.user.map(|user| user.login).unwrap_or_default(),
+                body: comment.body,
+                path: Some(comment.path),
+                line: comment
+                    .line
+                    .or(comment.original_line)
+                    .map(|line| line as i64),
+                url: Some(comment.html_url.to_string()),
+                commented_at: comment.created_at,
+            }
+        }));
+        Ok(review_comments)
+    }
+
+    /// Check runs and commit statuses of the latest commit of `branch`
+    pub async fn list_ci_checks(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        branch: &str,
+    ) -> Result<Vec<CreateCiCheck>, GitHubServiceError> {
+        (|| async { self.list_ci_checks_internal(repo_info, branch).await })
+            .retry(
+                &ExponentialBuilder::default()
+                    .with_min_delay(Duration::from_secs(1))
+                    .with_max_delay(Duration::from_secs(30))
+                    .with_max_times(3)
+                    .with_jitter(),
+            )
+            .when(|e| e.should_retry())
+            .notify(|err: &GitHubServiceError, dur: Duration| {
+                tracing::warn!(
+                    "GitHub API call failed, retrying after {:.2}s: {}",
+                    dur.as_secs_f64(),
+                    err
+                );
+            })
+            .await
+    }
+
+    async fn list_ci_checks_internal(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        branch: &str,
+    ) -> Result<Vec<CreateCiCheck>, GitHubServiceError> {
+        let map_err = |err| match GitHubServiceError::from(err) {
+            GitHubServiceError::Client(source) => GitHubServiceError::Branch(format!(
+                "Failed to list CI checks of branch '{branch}': {source}",
+                source = format_octocrab_error(&source),
+            )),
+            other => other,
+        };
+        let commit = format!(
+            "/repos/{}/{}/commits/heads/{branch}",
+            repo_info.owner, repo_info.repo_name
+        );
+
+        let check_runs: CheckRunList = self
+            .client
+            .get(format!("{commit}/check-runs?per_page=100"), None::<&()>)
+            .await
+            .map_err(map_err)?;
+        let combined: CombinedStatus = self
+            .client
+            .get(format!("{commit}/status"), None::<&()>)
+            .await
+            .map_err(map_err)?;
+
+        let mut checks: Vec<_> = check_runs
+            .check_runs
+            .into_iter()
+            .map(|run| CreateCiCheck {
+                state: Self::check_run_state(&run.status, run.conclusion.as_deref()),
+                name: run.name,
+                url: run.html_url,
+                commit_sha: run.head_sha,
+            })
+            .collect();
+        checks.extend(combined.statuses.into_iter().map(|status| CreateCiCheck {
+            state: match status.state.as_str() {
+                "success" => CiCheckState::Success,
+                "failure" | "error" => CiCheckState::Failure,
+                _ => CiCheckState::Pending,
+            },
+            name: status.context,
+            url: status.target_url,
+            commit_sha: combined.sha.clone(),
+        }));
+        Ok(checks)
+    }
+
+    fn check_run_state(status: &str, conclusion: Option<&str>) -> CiCheckState {
+        if status != "completed" {
+            return CiCheckState::Pending;
+        }
+        match conclusion {
+            Some("success") => CiCheckState::Success,
+            Some("failure" | "timed_out" | "cancelled" | "action_required" | "startup_failure") => {
+                CiCheckState::Failure
+            }
+            _ => CiCheckState::Neutral,
+        }
+    }
+
     fn map_pull_request(pr: octocrab::models::pulls::PullRequest) -> PullRequestInfo {
         let state = match pr.state {
             Some(IssueState::Open) => MergeStatus::Open,
@@ -350,6 +668,7 @@ impl GitHubService {
             status: state,
             merged_at: pr.merged_at.map(|dt| dt.naive_utc().and_utc()),
             merge_commit_sha: pr.merge_commit_sha,
+            is_draft: pr.draft.unwrap_or(false),
         }
     }
 
@@ -408,6 +727,96 @@ impl GitHubService {
         Ok(pr_infos)
     }
 
+    /// List the open issues of a repository matching `filter`. Pull requests,
+    /// which the issues API also returns, are left out.
+    pub async fn list_issues(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        filter: &IssueFilter,
+    ) -> Result<Vec<GitHubIssue>, GitHubServiceError> {
+        (|| async { self.list_issues_internal(repo_info, filter).await })
+            .retry(
+                &ExponentialBuilder::default()
+                    .with_min_delay(Duration::from_secs(1))
+                    .with_max_delay(Duration::from_secs(30))
+                    .with_max_times(3)
+                    .with_jitter(),
+            )
+            .when(|e| e.should_retry())
+            .notify(|err: &GitHubServiceError, dur: Duration| {
+                tracing::warn!(
+                    "GitHub API call failed, retrying after {:.2}s: {}",
+                    dur.as_secs_f64(),
+                    err
+                );
+            })
+            .await
+    }
+
+    async fn list_issues_internal(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        filter: &IssueFilter,
+    ) -> Result<Vec<GitHubIssue>, GitHubServiceError> {
+        let issues_handler = self.client.issues(&repo_info.owner, &repo_info.repo_name);
+        let mut request = issues_handler
+            .list()
+            .state(octocrab::params::State::Open)
+            .per_page(100);
+        if !filter.labels.is_empty() {
+            request = request.labels(&filter.labels);
+        }
+        if let Some(milestone) = filter.milestone {
+            request = request.milestone(milestone);
+        }
+        let first_page =
+            request
+                .send()
+                .await
+                .map_err(|err| match GitHubServiceError::from(err) {
+                    GitHubServiceError::Client(source) => GitHubServiceError::Repository(format!(
+                        "Failed to list issues of {}/{}: {}",
+                        repo_info.owner,
+                        repo_info.repo_name,
+                        format_octocrab_error(&source)
+                    )),
+                    other => other,
+                })?;
+        let issues = self.client.all_pages(first_page).await?;
+
+        Ok(issues
+            .into_iter()
+            .filter(|issue| issue.pull_request.is_none())
+            .map(|issue| GitHubIssue {
+                number: issue.number as i64,
+                title: issue.title,
+                body: issue.body,
+                url: issue.html_url.to_string(),
+            })
+            .collect())
+    }
+
+    pub async fn close_issue(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        issue_number: i64,
+    ) -> Result<(), GitHubServiceError> {
+        self.client
+            .issues(&repo_info.owner, &repo_info.repo_name)
+            .update(issue_number as u64)
+            .state(IssueState::Closed)
+            .send()
+            .await
+            .map_err(|err| match GitHubServiceError::from(err) {
+                GitHubServiceError::Client(source) => GitHubServiceError::Repository(format!(
+                    "Failed to close issue #{issue_number}: {source}",
+                    source = format_octocrab_error(&source),
+                )),
+                other => other,
+            })?;
+        Ok(())
+    }
+
     /// List repositories for the authenticated user with pagination
     #[cfg(feature = "cloud")]
     pub async fn list_repositories(
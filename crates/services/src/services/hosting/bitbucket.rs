@@ -0,0 +1,430 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use db::models::{
+    ci_check::{CiCheckState, CreateCiCheck},
+    merge::{MergeStatus, PullRequestInfo},
+    pr_review_comment::CreatePrReviewComment,
+    project_hosting::HostingProviderKind,
+    project_merge_settings::MergeStrategy,
+};
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::info;
+
+use super::{HostingError, HostingProvider, PrMergeOptions};
+use crate::services::{config::BitbucketConfig, github_service::CreatePrRequest};
+
+const BITBUCKET_API_URL: &str = "https://api.bitbucket.org/2.0";
+
+/// Bitbucket Cloud REST API 2.0, authenticated with a username and an app
+/// password
+pub struct BitbucketProvider {
+    client: reqwest::Client,
+    username: String,
+    app_password: String,
+    /// `username:app_password`, which git sends as is
+    git_credentials: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BitbucketPullRequest {
+    id: i64,
+    title: String,
+    #[serde(default)]
+    draft: bool,
+    /// `OPEN`, `MERGED`, `DECLINED` or `SUPERSEDED`
+    state: String,
+    links: PullRequestLinks,
+    merge_commit: Option<MergeCommit>,
+    updated_on: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestLinks {
+    html: Link,
+}
+
+#[derive(Debug, Deserialize)]
+struct Link {
+    href: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeCommit {
+    hash: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommentPage {
+    values: Vec<Comment>,
+    /// URL of the next page
+    next: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Comment {
+    id: i64,
+    content: CommentContent,
+    user: Option<CommentUser>,
+    created_on: DateTime<Utc>,
+    #[serde(default)]
+    deleted: bool,
+    inline: Option<InlinePosition>,
+    links: Option<CommentLinks>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommentContent {
+    raw: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommentUser {
+    display_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct InlinePosition {
+    path: String,
+    /// Line in the new version of the file
+    to: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommentLinks {
+    html: Option<Link>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BranchRef {
+    target: MergeCommit,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatusPage {
+    values: Vec<CommitStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitStatus {
+    key: String,
+    name: Option<String>,
+    /// `INPROGRESS`, `SUCCESSFUL`, `FAILED` or `STOPPED`
+    state: String,
+    url: Option<String>,
+}
+
+fn commit_status_state(state: &str) -> CiCheckState {
+    match state {
+        "SUCCESSFUL" => CiCheckState::Success,
+        "FAILED" => CiCheckState::Failure,
+        "STOPPED" => CiCheckState::Neutral,
+        _ => CiCheckState::Pending,
+    }
+}
+
+impl BitbucketProvider {
+    pub fn new(config: &BitbucketConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+        Self {
+            client,
+            username: config.username.clone(),
+            app_password: config.app_password.clone(),
+            git_credentials: format!("{}:{}", config.username, config.app_password),
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, format!("{BITBUCKET_API_URL}/{path}"))
+            .basic_auth(&self.username, Some(&self.app_password))
+            .header("Accept", "application/json")
+    }
+
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, HostingError> {
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(HostingError::TokenInvalid(HostingProviderKind::Bitbucket));
+        }
+        let body = response.text().await.unwrap_or_default();
+        Err(HostingError::Api(
+            HostingProviderKind::Bitbucket,
+            format!("{status}: {body}"),
+        ))
+    }
+
+    /// `workspace/repo_slug` of a remote or pull request URL
+    fn repository(url: &str) -> Result<String, HostingError> {
+        repository_path(url).ok_or_else(|| {
+            HostingError::InvalidUrl(HostingProviderKind::Bitbucket, url.to_string())
+        })
+    }
+
+    fn map_pull_request(pr: BitbucketPullRequest) -> PullRequestInfo {
+        let status = match pr.state.as_str() {
+            "OPEN" => MergeStatus::Open,
+            "MERGED" => MergeStatus::Merged,
+            "DECLINED" | "SUPERSEDED" => MergeStatus::Closed,
+            _ => MergeStatus::Unknown,
+        };
+        PullRequestInfo {
+            number: pr.id,
+            url: pr.links.html.href,
+            // Bitbucket does not report when a pull request was merged; it is
+            // last updated by the merge
+            merged_at: matches!(status, MergeStatus::Merged)
+                .then_some(pr.updated_on)
+                .flatten(),
+            status,
+            merge_commit_sha: pr.merge_commit.map(|commit| commit.hash),
+            is_draft: pr.draft,
+        }
+    }
+}
+
+/// `workspace/repo_slug` from an HTTPS, SSH or pull request URL on
+/// bitbucket.org
+fn repository_path(url: &str) -> Option<String> {
+    let re =
+        Regex::new(r"bitbucket\.org[:/](?P<workspace>[^/]+)/(?P<repo>[^/]+?)(?:\.git)?(?:/|$)")
+            .ok()?;
+    let caps = re.captures(url)?;
+    Some(format!("{}/{}", &caps["workspace"], &caps["repo"]))
+}
+
+#[async_trait]
+impl HostingProvider for BitbucketProvider {
+    fn kind(&self) -> HostingProviderKind {
+        HostingProviderKind::Bitbucket
+    }
+
+    fn token(&self) -> &str {
+        &self.git_credentials
+    }
+
+    async fn check_token(&self) -> Result<(), HostingError> {
+        self.send(self.request(reqwest::Method::GET, "user"))
+            .await?;
+        Ok(())
+    }
+
+    async fn create_pr(
+        &self,
+        remote_url: &str,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, HostingError> {
+        let repository = Self::repository(remote_url)?;
+        let pr: BitbucketPullRequest = self
+            .send(
+                self.request(
+                    reqwest::Method::POST,
+                    &format!("repositories/{repository}/pullrequests"),
+                )
+                .json(&json!({
+                    "title": request.title,
+                    "description": request.body.as_deref().unwrap_or(""),
+                    "source": { "branch": { "name": request.head_branch } },
+                    "destination": { "branch": { "name": request.base_branch } },
+                    "draft": request.draft,
+                })),
+            )
+            .await?
+            .json()
+            .await?;
+
+        info!(
+            "Created Bitbucket PR #{} for branch {} in {}",
+            pr.id, request.head_branch, repository
+        );
+        Ok(Self::map_pull_request(pr))
+    }
+
+    async fn fetch_pr(&self, pr_url: &str, number: i64) -> Result<PullRequestInfo, HostingError> {
+        let repository = Self::repository(pr_url)?;
+        let pr: BitbucketPullRequest = self
+            .send(self.request(
+                reqwest::Method::GET,
+                &format!("repositories/{repository}/pullrequests/{number}"),
+            ))
+            .await?
+            .json()
+            .await?;
+        Ok(Self::map_pull_request(pr))
+    }
+
+    async fn merge_pr(
+        &self,
+        pr_url: &str,
+        number: i64,
+        options: &PrMergeOptions,
+    ) -> Result<PullRequestInfo, HostingError> {
+        let repository = Self::repository(pr_url)?;
+        let mut body = serde_json::Map::new();
+        if let Some(strategy) = options.strategy {
+            let merge_strategy = match strategy {
+                MergeStrategy::Squash => "squash",
+                MergeStrategy::Rebase => "fast_forward",
+                MergeStrategy::MergeCommit => "merge_commit",
+            };
+            body.insert("merge_strategy".to_string(), json!(merge_strategy));
+        }
+        if let Some(message) = &options.commit_message {
+            body.insert("message".to_string(), json!(message));
+        }
+        // Large merges are completed asynchronously (202 Accepted), so the
+        // state is read back instead of taken from the response
+        self.send(
+            self.request(
+                reqwest::Method::POST,
+                &format!("repositories/{repository}/pullrequests/{number}/merge"),
+            )
+            .json(&body),
+        )
+        .await?;
+
+        info!("Merged Bitbucket PR #{} in {}", number, repository);
+        self.fetch_pr(pr_url, number).await
+    }
+
+    async fn mark_pr_ready(
+        &self,
+        pr_url: &str,
+        number: i64,
+    ) -> Result<PullRequestInfo, HostingError> {
+        let repository = Self::repository(pr_url)?;
+        let path = format!("repositories/{repository}/pullrequests/{number}");
+        let pr: BitbucketPullRequest = self
+            .send(self.request(reqwest::Method::GET, &path))
+            .await?
+            .json()
+            .await?;
+        if !pr.draft {
+            return Ok(Self::map_pull_request(pr));
+        }
+        // Updates replace the title, so the current one is sent along
+        let pr: BitbucketPullRequest = self
+            .send(
+                self.request(reqwest::Method::PUT, &path)
+                    .json(&json!({ "title": pr.title, "draft": false })),
+            )
+            .await?
+            .json()
+            .await?;
+
+        info!("Marked Bitbucket PR #{} in {} ready", number, repository);
+        Ok(Self::map_pull_request(pr))
+    }
+
+    async fn list_review_comments(
+        &self,
+        pr_url: &str,
+        number: i64,
+    ) -> Result<Vec<CreatePrReviewComment>, HostingError> {
+        let repository = Self::repository(pr_url)?;
+        let mut comments = Vec::new();
+        let mut request = self.request(
+            reqwest::Method::GET,
+            &format!("repositories/{repository}/pullrequests/{number}/comments?pagelen=100"),
+        );
+        loop {
+            let page: CommentPage = self.send(request).await?.json().await?;
+            comments.extend(
+                page.values
+                    .into_iter()
+                    .filter(|comment| !comment.deleted)
+                    .map(|comment| CreatePrReviewComment {
+                        external_id: comment.id.to_string(),
+                        author: comment
+                            .user
+                            .map(|user| user.display_name)
+                            .unwrap_or_default(),
+                        body: comment.content.raw,
+                        path: comment.inline.as_ref().map(|inline| inline.path.clone()),
+                        line: comment.inline.and_then(|inline| inline.to),
+                        url: comment
+                            .links
+                            .and_then(|links| links.html)
+                            .map(|link| link.href),
+                        commented_at: comment.created_on,
+                    }),
+            );
+            let Some(next) = page.next else {
+                break;
+            };
+            request = self
+                .client
+                .get(next)
+                .basic_auth(&self.username, Some(&self.app_password))
+                .header("Accept", "application/json");
+        }
+        Ok(comments)
+    }
+
+    async fn list_ci_checks(
+        &self,
+        remote_url: &str,
+        branch: &str,
+    ) -> Result<Vec<CreateCiCheck>, HostingError> {
+        let repository = Self::repository(remote_url)?;
+        let branch: BranchRef = self
+            .send(self.request(
+                reqwest::Method::GET,
+                &format!("repositories/{repository}/refs/branches/{branch}"),
+            ))
+            .await?
+            .json()
+            .await?;
+        let sha = branch.target.hash;
+        let page: StatusPage = self
+            .send(self.request(
+                reqwest::Method::GET,
+                &format!("repositories/{repository}/commit/{sha}/statuses?pagelen=100"),
+            ))
+            .await?
+            .json()
+            .await?;
+        Ok(page
+            .values
+            .into_iter()
+            .map(|status| CreateCiCheck {
+                state: commit_status_state(&status.state),
+                name: status.name.unwrap_or(status.key),
+                url: status.url,
+                commit_sha: sha.clone(),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_repository_from_remote_and_pull_request_urls() {
+        for (url, expected) in [
+            ("git@bitbucket.org:acme/app.git", Some("acme/app")),
+            ("https://jane@bitbucket.org/acme/app.git", Some("acme/app")),
+            (
+                "https://bitbucket.org/acme/app/pull-requests/7",
+                Some("acme/app"),
+            ),
+            ("https://github.com/acme/app.git", None),
+        ] {
+            assert_eq!(repository_path(url).as_deref(), expected, "{url}");
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+use db::models::{
+    ci_check::CreateCiCheck, merge::PullRequestInfo, pr_review_comment::CreatePrReviewComment,
+    project_hosting::HostingProviderKind,
+};
+
+use super::{HostingError, HostingProvider, PrMergeOptions};
+use crate::services::github_service::{CreatePrRequest, GitHubRepoInfo, GitHubService};
+
+/// GitHub through [`GitHubService`]
+pub struct GitHubProvider {
+    service: GitHubService,
+    token: String,
+}
+
+impl GitHubProvider {
+    pub fn new(token: &str) -> Result<Self, HostingError> {
+        Ok(Self {
+            service: GitHubService::new(token)?,
+            token: token.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl HostingProvider for GitHubProvider {
+    fn kind(&self) -> HostingProviderKind {
+        HostingProviderKind::GitHub
+    }
+
+    fn token(&self) -> &str {
+        &self.token
+    }
+
+    async fn check_token(&self) -> Result<(), HostingError> {
+        Ok(self.service.check_token().await?)
+    }
+
+    async fn create_pr(
+        &self,
+        remote_url: &str,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, HostingError> {
+        let repo_info = GitHubRepoInfo::from_remote_url(remote_url)?;
+        Ok(self.service.create_pr(&repo_info, request).await?)
+    }
+
+    async fn fetch_pr(&self, pr_url: &str, number: i64) -> Result<PullRequestInfo, HostingError> {
+        let repo_info = GitHubRepoInfo::from_remote_url(pr_url)?;
+        Ok(self.service.update_pr_status(&repo_info, number).await?)
+    }
+
+    async fn merge_pr(
+        &self,
+        pr_url: &str,
+        number: i64,
+        options: &PrMergeOptions,
+    ) -> Result<PullRequestInfo, HostingError> {
+        let repo_info = GitHubRepoInfo::from_remote_url(pr_url)?;
+        Ok(self.service.merge_pr(&repo_info, number, options).await?)
+    }
+
+    async fn mark_pr_ready(
+        &self,
+        pr_url: &str,
+        number: i64,
+    ) -> Result<PullRequestInfo, HostingError> {
+        let repo_info = GitHubRepoInfo::from_remote_url(pr_url)?;
+        Ok(self.service.mark_pr_ready(&repo_info, number).await?)
+    }
+
+    async fn list_review_comments(
+        &self,
+        pr_url: &str,
+        number: i64,
+    ) -> Result<Vec<CreatePrReviewComment>, HostingError> {
+        let repo_info = GitHubRepoInfo::from_remote_url(pr_url)?;
+        Ok(self
+            .service
+            .list_review_comments(&repo_info, number)
+            .await?)
+    }
+
+    async fn list_ci_checks(
+        &self,
+        remote_url: &str,
+        branch: &str,
+    ) -> Result<Vec<CreateCiCheck>, HostingError> {
+        let repo_info = GitHubRepoInfo::from_remote_url(remote_url)?;
+        Ok(self.service.list_ci_checks(&repo_info, branch).await?)
+    }
+}
@@ -0,0 +1,462 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use db::models::{
+    ci_check::{CiCheckState, CreateCiCheck},
+    merge::{MergeStatus, PullRequestInfo},
+    pr_review_comment::CreatePrReviewComment,
+    project_hosting::HostingProviderKind,
+    project_merge_settings::MergeStrategy,
+};
+use serde::Deserialize;
+use serde_json::json;
+use tracing::info;
+
+use super::{HostingError, HostingProvider, PrMergeOptions};
+use crate::services::{config::GitLabConfig, github_service::CreatePrRequest};
+
+/// GitLab REST API v4, on gitlab.com or a self-hosted instance. Pull requests
+/// are merge requests, numbered by their project scoped `iid`.
+pub struct GitLabProvider {
+    client: reqwest::Client,
+    base_url: String,
+    token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeRequest {
+    iid: i64,
+    title: String,
+    web_url: String,
+    #[serde(default)]
+    draft: bool,
+    /// `opened`, `closed`, `locked` or `merged`
+    state: String,
+    merged_at: Option<DateTime<Utc>>,
+    merge_commit_sha: Option<String>,
+    squash_commit_sha: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Note {
+    id: i64,
+    body: String,
+    author: NoteAuthor,
+    created_at: DateTime<Utc>,
+    /// Notes GitLab adds itself, e.g. when commits are pushed
+    system: bool,
+    position: Option<NotePosition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NoteAuthor {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct NotePosition {
+    new_path: Option<String>,
+    new_line: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Branch {
+    commit: BranchCommit,
+}
+
+#[derive(Debug, Deserialize)]
+struct BranchCommit {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitStatus {
+    name: String,
+    /// `created`, `waiting_for_resource`, `preparing`, `pending`, `running`,
+    /// `success`, `failed`, `canceled`, `skipped` or `manual`
+    status: String,
+    target_url: Option<String>,
+}
+
+/// Notes fetched per page
+const NOTES_PER_PAGE: usize = 100;
+
+impl GitLabProvider {
+    pub fn new(config: &GitLabConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+        Self {
+            client,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            token: config.token.clone(),
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, format!("{}/api/v4/{path}", self.base_url))
+            .header("PRIVATE-TOKEN", &self.token)
+            .header("Accept", "application/json")
+    }
+
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, HostingError> {
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(HostingError::TokenInvalid(HostingProviderKind::GitLab));
+        }
+        let body = response.text().await.unwrap_or_default();
+        Err(HostingError::Api(
+            HostingProviderKind::GitLab,
+            format!("{status}: {body}"),
+        ))
+    }
+
+    /// URL encoded project path, which the API accepts in place of the id
+    fn project_id(url: &str) -> Result<String, HostingError> {
+        project_path(url)
+            .map(|path| path.replace('/', "%2F"))
+            .ok_or_else(|| HostingError::InvalidUrl(HostingProviderKind::GitLab, url.to_string()))
+    }
+
+    fn map_merge_request(mr: MergeRequest) -> PullRequestInfo {
+        let status = match mr.state.as_str() {
+            "opened" | "locked" => MergeStatus::Open,
+            "merged" => MergeStatus::Merged,
+            "closed" => MergeStatus::Closed,
+            _ => MergeStatus::Unknown,
+        };
+        PullRequestInfo {
+            number: mr.iid,
+            url: mr.web_url,
+            status,
+            merged_at: mr.merged_at,
+            merge_commit_sha: mr.merge_commit_sha.or(mr.squash_commit_sha),
+            is_draft: mr.draft,
+        }
+    }
+}
+
+fn commit_status_state(status: &str) -> CiCheckState {
+    match status {
+        "success" => CiCheckState::Success,
+        "failed" | "canceled" => CiCheckState::Failure,
+        "skipped" | "manual" => CiCheckState::Neutral,
+        _ => CiCheckState::Pending,
+    }
+}
+
+/// Prefixes that make GitLab treat a merge request as a draft
+const DRAFT_PREFIXES: [&str; 3] = ["draft:", "[draft]", "(draft)"];
+
+/// Title of a merge request with the draft prefix GitLab marks drafts by
+fn draft_title(title: &str) -> String {
+    format!("Draft: {title}")
+}
+
+/// Title of a merge request without its draft prefixes
+fn ready_title(title: &str) -> &str {
+    let mut title = title.trim_start();
+    while let Some(prefix) = DRAFT_PREFIXES.iter().find(|prefix| {
+        title
+            .get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+    }) {
+        title = title[prefix.len()..].trim_start();
+    }
+    title
+}
+
+/// `group/subgroup/project` from a remote URL (HTTPS, `ssh://` or
+/// `git@host:path`) or a merge request URL
+fn project_path(url: &str) -> Option<String> {
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?.1,
+        None => url.split_once(':')?.1,
+    };
+    let path = path.split("/-/").next()?.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    path.contains('/').then(|| path.to_string())
+}
+
+#[async_trait]
+impl HostingProvider for GitLabProvider {
+    fn kind(&self) -> HostingProviderKind {
+        HostingProviderKind::GitLab
+    }
+
+    fn token(&self) -> &str {
+        &self.token
+    }
+
+    async fn check_token(&self) -> Result<(), HostingError> {
+        self.send(self.request(reqwest::Method::GET, "user"))
+            .await?;
+        Ok(())
+    }
+
+    async fn create_pr(
+        &self,
+        remote_url: &str,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, HostingError> {
+        let project_id = Self::project_id(remote_url)?;
+        let mr: MergeRequest = self
+            .send(
+                self.request(
+                    reqwest::Method::POST,
+                    &format!("projects/{project_id}/merge_requests"),
+                )
+                .json(&json!({
+                    "source_branch": request.head_branch,
+                    "target_branch": request.base_branch,
+                    "title": if request.draft {
+                        draft_title(&request.title)
+                    } else {
+                        request.title.clone()
+                    },
+                    "description": request.body.as_deref().unwrap_or(""),
+                })),
+            )
+            .await?
+            .json()
+            .await?;
+
+        info!(
+            "Created GitLab MR !{} for branch {} in {}",
+            mr.iid,
+            request.head_branch,
+            project_id.replace("%2F", "/")
+        );
+        Ok(Self::map_merge_request(mr))
+    }
+
+    async fn fetch_pr(&self, pr_url: &str, number: i64) -> Result<PullRequestInfo, HostingError> {
+        let project_id = Self::project_id(pr_url)?;
+        let mr: MergeRequest = self
+            .send(self.request(
+                reqwest::Method::GET,
+                &format!("projects/{project_id}/merge_requests/{number}"),
+            ))
+            .await?
+            .json()
+            .await?;
+        Ok(Self::map_merge_request(mr))
+    }
+
+    async fn merge_pr(
+        &self,
+        pr_url: &str,
+        number: i64,
+        options: &PrMergeOptions,
+    ) -> Result<PullRequestInfo, HostingError> {
+        let project_id = Self::project_id(pr_url)?;
+        // Whether a merge request is rebased or gets a merge commit is a
+        // setting of the GitLab project; only squashing can be chosen per merge
+        let squash = match options.strategy {
+            Some(MergeStrategy::Squash) => Some(true),
+            Some(MergeStrategy::MergeCommit) => Some(false),
+            Some(MergeStrategy::Rebase) => {
+                return Err(HostingError::Unsupported(
+                    HostingProviderKind::GitLab,
+                    "choosing the rebase strategy per merge request".to_string(),
+                ));
+            }
+            None => None,
+        };
+        let mut body = serde_json::Map::new();
+        if let Some(squash) = squash {
+            body.insert("squash".to_string(), json!(squash));
+        }
+        if let Some(message) = &options.commit_message {
+            body.insert("squash_commit_message".to_string(), json!(message));
+            body.insert("merge_commit_message".to_string(), json!(message));
+        }
+        let mr: MergeRequest = self
+            .send(
+                self.request(
+                    reqwest::Method::PUT,
+                    &format!("projects/{project_id}/merge_requests/{number}/merge"),
+                )
+                .json(&body),
+            )
+            .await?
+            .json()
+            .await?;
+
+        info!(
+            "Merged GitLab MR !{} in {}",
+            number,
+            project_id.replace("%2F", "/")
+        );
+        Ok(Self::map_merge_request(mr))
+    }
+
+    async fn mark_pr_ready(
+        &self,
+        pr_url: &str,
+        number: i64,
+    ) -> Result<PullRequestInfo, HostingError> {
+        let project_id = Self::project_id(pr_url)?;
+        let path = format!("projects/{project_id}/merge_requests/{number}");
+        let mr: MergeRequest = self
+            .send(self.request(reqwest::Method::GET, &path))
+            .await?
+            .json()
+            .await?;
+        if !mr.draft {
+            return Ok(Self::map_merge_request(mr));
+        }
+        let mr: MergeRequest = self
+            .send(
+                self.request(reqwest::Method::PUT, &path)
+                    .json(&json!({ "title": ready_title(&mr.title) })),
+            )
+            .await?
+            .json()
+            .await?;
+
+        info!(
+            "Marked GitLab MR !{} in {} ready",
+            number,
+            project_id.replace("%2F", "/")
+        );
+        Ok(Self::map_merge_request(mr))
+    }
+
+    async fn list_review_comments(
+        &self,
+        pr_url: &str,
+        number: i64,
+    ) -> Result<Vec<CreatePrReviewComment>, HostingError> {
+        let project_id = Self::project_id(pr_url)?;
+        let mut comments = Vec::new();
+        for page in 1.. {
+            let notes: Vec<Note> = self
+                .send(self.request(
+                    reqwest::Method::GET,
+                    &format!(
+                        "projects/{project_id}/merge_requests/{number}/notes?sort=asc&order_by=created_at&per_page={NOTES_PER_PAGE}&page={page}"
+                    ),
+                ))
+                .await?
+                .json()
+                .await?;
+            let last_page = notes.len() < NOTES_PER_PAGE;
+            comments.extend(notes.into_iter().filter(|note| !note.system).map(|note| {
+                let (path, line) = note.position.map_or((None, None), |position| {
+                    (position.new_path, position.new_line)
+                });
+                CreatePrReviewComment {
+                    external_id: note.id.to_string(),
+                    author: note.author.username,
+                    body: note.body,
+                    path,
+                    line,
+                    url: Some(format!("{pr_url}#note_{}", note.id)),
+                    commented_at: note.created_at,
+                }
+            }));
+            if last_page {
+                break;
+            }
+        }
+        Ok(comments)
+    }
+
+    async fn list_ci_checks(
+        &self,
+        remote_url: &str,
+        branch: &str,
+    ) -> Result<Vec<CreateCiCheck>, HostingError> {
+        let project_id = Self::project_id(remote_url)?;
+        let branch: Branch = self
+            .send(self.request(
+                reqwest::Method::GET,
+                &format!(
+                    "projects/{project_id}/repository/branches/{}",
+                    branch.replace('/', "%2F")
+                ),
+            ))
+            .await?
+            .json()
+            .await?;
+        let sha = branch.commit.id;
+        let statuses: Vec<CommitStatus> = self
+            .send(self.request(
+                reqwest::Method::GET,
+                &format!("projects/{project_id}/repository/commits/{sha}/statuses?per_page=100"),
+            ))
+            .await?
+            .json()
+            .await?;
+        Ok(statuses
+            .into_iter()
+            .map(|status| CreateCiCheck {
+                state: commit_status_state(&status.status),
+                name: status.name,
+                url: status.target_url,
+                commit_sha: sha.clone(),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_project_path_from_remote_and_merge_request_urls() {
+        for (url, expected) in [
+            ("git@gitlab.com:group/repo.git", Some("group/repo")),
+            (
+                "https://gitlab.com/group/sub/repo.git",
+                Some("group/sub/repo"),
+            ),
+            (
+                "ssh://git@gitlab.example.com:2222/group/repo.git",
+                Some("group/repo"),
+            ),
+            (
+                "https://gitlab.com/group/sub/repo/-/merge_requests/12",
+                Some("group/sub/repo"),
+            ),
+            ("https://gitlab.com/repo", None),
+        ] {
+            assert_eq!(project_path(url).as_deref(), expected, "{url}");
+        }
+    }
+
+    #[test]
+    fn strips_draft_prefixes_from_titles() {
+        assert_eq!(draft_title("Add login"), "Draft: Add login");
+        for title in [
+            "Draft: Add login",
+            "draft:Add login",
+            "[Draft] Add login",
+            "(DRAFT) Draft: Add login",
+            "Add login",
+        ] {
+            assert_eq!(ready_title(title), "Add login", "{title}");
+        }
+    }
+
+    #[test]
+    fn maps_commit_statuses_to_check_states() {
+        assert_eq!(commit_status_state("running"), CiCheckState::Pending);
+        assert_eq!(commit_status_state("created"), CiCheckState::Pending);
+        assert_eq!(commit_status_state("success"), CiCheckState::Success);
+        assert_eq!(commit_status_state("canceled"), CiCheckState::Failure);
+        assert_eq!(commit_status_state("manual"), CiCheckState::Neutral);
+    }
+}
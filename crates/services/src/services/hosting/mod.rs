@@ -0,0 +1,243 @@
+//! Code hosts pull requests are opened, polled and merged on. GitHub is the
+//! default; a project can pick another host, or have it detected from its
+//! remote URL.
+
+pub mod bitbucket;
+pub mod github;
+pub mod gitlab;
+
+use async_trait::async_trait;
+use db::models::{
+    ci_check::CreateCiCheck,
+    merge::PullRequestInfo,
+    pr_review_comment::CreatePrReviewComment,
+    project::Project,
+    project_hosting::{HostingProviderKind, ProjectHosting},
+    project_merge_settings::MergeStrategy,
+};
+use sqlx::{SqlitePool, error::Error as SqlxError};
+use thiserror::Error;
+
+use crate::services::{
+    config::{Config, HostingConfig},
+    git::{GitService, GitServiceError},
+    github_service::{CreatePrRequest, GitHubServiceError},
+    hosting::{bitbucket::BitbucketProvider, github::GitHubProvider, gitlab::GitLabProvider},
+};
+
+#[derive(Debug, Error)]
+pub enum HostingError {
+    #[error("{0} is not configured")]
+    NotConfigured(HostingProviderKind),
+    #[error("{0} token is invalid or expired")]
+    TokenInvalid(HostingProviderKind),
+    #[error(transparent)]
+    GitHub(#[from] GitHubServiceError),
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+    #[error("{0} API error: {1}")]
+    Api(HostingProviderKind, String),
+    #[error("Invalid {0} URL: {1}")]
+    InvalidUrl(HostingProviderKind, String),
+    #[error("{0} does not support {1}")]
+    Unsupported(HostingProviderKind, String),
+    #[error(transparent)]
+    Git(#[from] GitServiceError),
+    #[error(transparent)]
+    Sqlx(#[from] SqlxError),
+}
+
+/// How a pull request is merged
+#[derive(Debug, Clone, Default)]
+pub struct PrMergeOptions {
+    /// The host's default merge method when None
+    pub strategy: Option<MergeStrategy>,
+    /// Message of the squash or merge commit; the host writes one when None
+    pub commit_message: Option<String>,
+}
+
+#[async_trait]
+pub trait HostingProvider: Send + Sync {
+    fn kind(&self) -> HostingProviderKind;
+
+    /// Token branches are pushed and fetched with over HTTPS
+    fn token(&self) -> &str;
+
+    async fn check_token(&self) -> Result<(), HostingError>;
+
+    /// Open a pull request in the repository at `remote_url`
+    async fn create_pr(
+        &self,
+        remote_url: &str,
+        request: &CreatePrRequest,
+    ) -> Result<PullRequestInfo, HostingError>;
+
+    /// Current state of the pull request at `pr_url`
+    async fn fetch_pr(&self, pr_url: &str, number: i64) -> Result<PullRequestInfo, HostingError>;
+
+    /// Merge the pull request at `pr_url` and return its new state
+    async fn merge_pr(
+        &self,
+        pr_url: &str,
+        number: i64,
+        options: &PrMergeOptions,
+    ) -> Result<PullRequestInfo, HostingError>;
+
+    /// Take the draft pull request at `pr_url` out of draft and return its new
+    /// state
+    async fn mark_pr_ready(
+        &self,
+        pr_url: &str,
+        number: i64,
+    ) -> Result<PullRequestInfo, HostingError>;
+
+    /// Review comments left by people on the pull request at `pr_url`
+    async fn list_review_comments(
+        &self,
+        pr_url: &str,
+        number: i64,
+    ) -> Result<Vec<CreatePrReviewComment>, HostingError>;
+
+    /// CI checks of the latest commit of `branch` in the repository at
+    /// `remote_url`, which may also be the URL of a pull request
+    async fn list_ci_checks(
+        &self,
+        remote_url: &str,
+        branch: &str,
+    ) -> Result<Vec<CreateCiCheck>, HostingError>;
+}
+
+/// Provider for a code host, built from the configured credentials
+pub fn provider_for(
+    config: &Config,
+    kind: HostingProviderKind,
+) -> Result<Box<dyn HostingProvider>, HostingError> {
+    match kind {
+        HostingProviderKind::GitHub => {
+            let token = config
+                .github
+                .token()
+                .ok_or(GitHubServiceError::TokenInvalid)?;
+            Ok(Box::new(GitHubProvider::new(&token)?))
+        }
+        HostingProviderKind::GitLab => config
+            .hosting
+            .gitlab
+            .as_ref()
+            .map(|gitlab| Box::new(GitLabProvider::new(gitlab)) as Box<dyn HostingProvider>)
+            .ok_or(HostingError::NotConfigured(kind)),
+        HostingProviderKind::Bitbucket => config
+            .hosting
+            .bitbucket
+            .as_ref()
+            .map(|bitbucket| {
+                Box::new(BitbucketProvider::new(bitbucket)) as Box<dyn HostingProvider>
+            })
+            .ok_or(HostingError::NotConfigured(kind)),
+    }
+}
+
+/// Code host of a project: the one chosen for it, or else the one its remote
+/// URL points at
+pub async fn provider_kind(
+    pool: &SqlitePool,
+    git: &GitService,
+    hosting: &HostingConfig,
+    project: &Project,
+) -> Result<HostingProviderKind, HostingError> {
+    if let Some(project_hosting) = ProjectHosting::find_by_project_id(pool, project.id).await? {
+        return Ok(project_hosting.provider);
+    }
+    Ok(git
+        .get_default_remote_url(&project.git_repo_path)
+        .map(|url| detect_provider(&url, hosting))
+        .unwrap_or(HostingProviderKind::GitHub))
+}
+
+/// Provider for the code host of a project
+pub async fn resolve_provider(
+    pool: &SqlitePool,
+    git: &GitService,
+    config: &Config,
+    project: &Project,
+) -> Result<Box<dyn HostingProvider>, HostingError> {
+    let kind = provider_kind(pool, git, &config.hosting, project).await?;
+    provider_for(config, kind)
+}
+
+/// Code host a remote URL points at. Hosts other than github.com and
+/// bitbucket.org count as GitLab when they match the configured instance or
+/// are named like one.
+pub fn detect_provider(remote_url: &str, hosting: &HostingConfig) -> HostingProviderKind {
+    let Some(host) = url_host(remote_url) else {
+        return HostingProviderKind::GitHub;
+    };
+    if host == "github.com" {
+        return HostingProviderKind::GitHub;
+    }
+    if host == "bitbucket.org" {
+        return HostingProviderKind::Bitbucket;
+    }
+    let gitlab_host = hosting
+        .gitlab
+        .as_ref()
+        .and_then(|gitlab| url_host(&gitlab.base_url));
+    if gitlab_host.as_deref() == Some(host.as_str()) || host.contains("gitlab") {
+        return HostingProviderKind::GitLab;
+    }
+    HostingProviderKind::GitHub
+}
+
+/// Lowercase host of an HTTPS, `ssh://` or `git@host:path` URL
+fn url_host(url: &str) -> Option<String> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split('/').next()?;
+    let authority = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = authority.split(':').next()?.trim();
+    (!host.is_empty()).then(|| host.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::config::GitLabConfig;
+
+    #[test]
+    fn detects_the_provider_from_the_remote_url() {
+        let hosting = HostingConfig {
+            gitlab: Some(GitLabConfig {
+                base_url: "https://code.acme.dev/".to_string(),
+                token: "token".to_string(),
+            }),
+            bitbucket: None,
+        };
+        for (url, expected) in [
+            ("git@github.com:owner/repo.git", HostingProviderKind::GitHub),
+            (
+                "https://gitlab.com/group/repo.git",
+                HostingProviderKind::GitLab,
+            ),
+            (
+                "git@bitbucket.org:acme/app.git",
+                HostingProviderKind::Bitbucket,
+            ),
+            (
+                "ssh://git@gitlab.example.com:2222/group/repo.git",
+                HostingProviderKind::GitLab,
+            ),
+            (
+                "git@code.acme.dev:team/app.git",
+                HostingProviderKind::GitLab,
+            ),
+            (
+                "https://git.example.com/team/app.git",
+                HostingProviderKind::GitHub,
+            ),
+            ("/srv/repos/app.git", HostingProviderKind::GitHub),
+        ] {
+            assert_eq!(detect_provider(url, &hosting), expected, "{url}");
+        }
+    }
+}
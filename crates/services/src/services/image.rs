@@ -3,7 +3,10 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use db::models::image::{CreateImage, Image};
+use db::models::{
+    image::{CreateImage, Image},
+    task_attachment::{CreateTaskAttachment, TaskAttachment},
+};
 use regex::{Captures, Regex};
 use sha2::{Digest, Sha256};
 use sqlx::SqlitePool;
@@ -28,23 +31,46 @@ pub enum ImageError {
 
     #[error("Failed to build response: {0}")]
     ResponseBuildError(String),
+
+    #[error("Attachment is empty")]
+    EmptyAttachment,
+
+    #[error("Attachment too large: {0} bytes (max: {1} bytes)")]
+    AttachmentTooLarge(u64, u64),
+
+    #[error("Task attachments would take {0} bytes (max: {1} bytes)")]
+    TaskAttachmentsTooLarge(u64, u64),
+
+    #[error("Attachment not found")]
+    AttachmentNotFound,
 }
 
+/// Largest single file that can be attached to a task
+pub const MAX_ATTACHMENT_SIZE_BYTES: u64 = 50 * 1024 * 1024;
+
 #[derive(Clone)]
 pub struct ImageService {
     cache_dir: PathBuf,
+    attachments_dir: PathBuf,
     pool: SqlitePool,
     max_size_bytes: u64,
+    max_attachment_size_bytes: u64,
+    max_task_attachments_bytes: u64,
 }
 
 impl ImageService {
     pub fn new(pool: SqlitePool) -> Result<Self, ImageError> {
         let cache_dir = utils::cache_dir().join("images");
         fs::create_dir_all(&cache_dir)?;
+        let attachments_dir = utils::assets::asset_dir().join("attachments");
+        fs::create_dir_all(&attachments_dir)?;
         Ok(Self {
             cache_dir,
+            attachments_dir,
             pool,
             max_size_bytes: 20 * 1024 * 1024, // 20MB default
+            max_attachment_size_bytes: MAX_ATTACHMENT_SIZE_BYTES,
+            max_task_attachments_bytes: 200 * 1024 * 1024, // 200MB per task
         })
     }
 
@@ -233,4 +259,291 @@ impl ImageService {
         })
         .into_owned()
     }
+
+    /// Attach a file to a task. The content type comes from the file's
+    /// signature, then its extension; uploading the same file twice returns the
+    /// existing attachment.
+    pub async fn store_attachment(
+        &self,
+        task_id: Uuid,
+        data: &[u8],
+        original_filename: &str,
+    ) -> Result<TaskAttachment, ImageError> {
+        let file_size = data.len() as u64;
+        if file_size == 0 {
+            return Err(ImageError::EmptyAttachment);
+        }
+        if file_size > self.max_attachment_size_bytes {
+            return Err(ImageError::AttachmentTooLarge(
+                file_size,
+                self.max_attachment_size_bytes,
+            ));
+        }
+
+        let hash = format!("{:x}", Sha256::digest(data));
+        if let Some(existing) =
+            TaskAttachment::find_by_task_and_hash(&self.pool, task_id, &hash).await?
+        {
+            tracing::debug!("Reusing existing attachment with hash {}", hash);
+            return Ok(existing);
+        }
+
+        let total = TaskAttachment::total_size_for_task(&self.pool, task_id).await? as u64;
+        if total + file_size > self.max_task_attachments_bytes {
+            return Err(ImageError::TaskAttachmentsTooLarge(
+                total + file_size,
+                self.max_task_attachments_bytes,
+            ));
+        }
+
+        // Keep the original name readable for the agent, prefixed so that two
+        // files with the same name don't collide
+        let original_name = Path::new(original_filename)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .filter(|name| !name.is_empty())
+            .unwrap_or("attachment");
+        let new_filename = format!(
+            "{}-{}",
+            &Uuid::new_v4().simple().to_string()[..8],
+            sanitize_file_name(original_name)
+        );
+        fs::write(self.attachments_dir.join(&new_filename), data)?;
+
+        let attachment = TaskAttachment::create(
+            &self.pool,
+            task_id,
+            &CreateTaskAttachment {
+                file_path: new_filename,
+                original_name: original_name.to_string(),
+                mime_type: detect_content_type(data, original_name).to_string(),
+                size_bytes: file_size as i64,
+                hash,
+            },
+        )
+        .await?;
+        Ok(attachment)
+    }
+
+    pub fn get_attachment_path(&self, attachment: &TaskAttachment) -> PathBuf {
+        self.attachments_dir.join(&attachment.file_path)
+    }
+
+    pub async fn delete_attachment(&self, attachment: &TaskAttachment) -> Result<(), ImageError> {
+        let file_path = self.get_attachment_path(attachment);
+        if file_path.exists() {
+            fs::remove_file(file_path)?;
+        }
+        TaskAttachment::delete(&self.pool, attachment.id).await?;
+        Ok(())
+    }
+
+    /// Remove stored files whose attachment row is gone, which happens when
+    /// the task is deleted
+    pub async fn delete_orphaned_attachments(&self) -> Result<(), ImageError> {
+        let referenced: std::collections::HashSet<String> =
+            TaskAttachment::all_file_paths(&self.pool)
+                .await?
+                .into_iter()
+                .collect();
+        let mut deleted_count = 0;
+        for entry in fs::read_dir(&self.attachments_dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if entry.file_type()?.is_file() && !referenced.contains(&name) {
+                match fs::remove_file(entry.path()) {
+                    Ok(_) => deleted_count += 1,
+                    Err(e) => {
+                        tracing::error!("Failed to delete orphaned attachment {}: {}", name, e)
+                    }
+                }
+            }
+        }
+        if deleted_count > 0 {
+            tracing::info!("Deleted {} orphaned attachment files", deleted_count);
+        }
+        Ok(())
+    }
+
+    pub async fn copy_attachments_by_task_to_worktree(
+        &self,
+        worktree_path: &Path,
+        task_id: Uuid,
+    ) -> Result<(), ImageError> {
+        let attachments = TaskAttachment::find_by_task_id(&self.pool, task_id).await?;
+        if attachments.is_empty() {
+            return Ok(());
+        }
+
+        let attachments_dir = worktree_path.join(utils::path::VIBE_ATTACHMENTS_DIR);
+        std::fs::create_dir_all(&attachments_dir)?;
+
+        // Create .gitignore to ignore all files in this directory
+        let gitignore_path = attachments_dir.join(".gitignore");
+        if !gitignore_path.exists() {
+            std::fs::write(&gitignore_path, "*\n")?;
+        }
+
+        for attachment in attachments {
+            let src = self.get_attachment_path(&attachment);
+            let dst = attachments_dir.join(&attachment.file_path);
+            if src.exists() {
+                if let Err(e) = std::fs::copy(&src, &dst) {
+                    tracing::error!("Failed to copy {}: {}", attachment.file_path, e);
+                }
+            } else {
+                tracing::warn!("Missing attachment file: {}", src.display());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Prompt section listing where the task's attachments were copied to in
+    /// the worktree, or `None` when the task has none
+    pub fn attachments_prompt_context(
+        attachments: &[TaskAttachment],
+        worktree_path: &Path,
+    ) -> Option<String> {
+        if attachments.is_empty() {
+            return None;
+        }
+        let mut context = String::from("\n\nFiles attached to this task:");
+        for attachment in attachments {
+            let path = worktree_path
+                .join(utils::path::VIBE_ATTACHMENTS_DIR)
+                .join(&attachment.file_path);
+            context.push_str(&format!(
+                "\n- {} ({}, {} bytes): {}",
+                attachment.original_name,
+                attachment.mime_type,
+                attachment.size_bytes,
+                path.to_string_lossy().replace('\\', "/")
+            ));
+        }
+        Some(context)
+    }
+}
+
+/// Keep file names portable: anything but ASCII letters, digits, `.`, `-` and
+/// `_` becomes `_`
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Content type of an attachment from its leading bytes, falling back to the
+/// file extension and then to whether the content is text
+pub fn detect_content_type(data: &[u8], file_name: &str) -> &'static str {
+    const SIGNATURES: [(&[u8], &str); 8] = [
+        (b"%PDF-", "application/pdf"),
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"\x1f\x8b", "application/gzip"),
+        (b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+        (b"PK\x03\x04", "application/zip"),
+    ];
+
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+
+    if let Some((_, mime)) = SIGNATURES.iter().find(|(magic, _)| data.starts_with(magic)) {
+        // Office documents and design files are zip containers
+        if *mime == "application/zip"
+            && let Some(mime) = content_type_for_extension(&extension)
+        {
+            return mime;
+        }
+        return mime;
+    }
+    if data.len() >= 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        return "image/webp";
+    }
+
+    if let Some(mime) = content_type_for_extension(&extension) {
+        return mime;
+    }
+    let sample = &data[..data.len().min(8192)];
+    match std::str::from_utf8(sample) {
+        Ok(_) => "text/plain",
+        // The sample may end in the middle of a multi-byte character
+        Err(e) if e.error_len().is_none() => "text/plain",
+        Err(_) => "application/octet-stream",
+    }
+}
+
+fn content_type_for_extension(extension: &str) -> Option<&'static str> {
+    let mime = match extension {
+        "txt" | "log" => "text/plain",
+        "md" | "markdown" => "text/markdown",
+        "csv" => "text/csv",
+        "tsv" => "text/tab-separated-values",
+        "json" => "application/json",
+        "yaml" | "yml" => "application/yaml",
+        "toml" => "application/toml",
+        "xml" => "application/xml",
+        "html" | "htm" => "text/html",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "fig" => "application/x-figma",
+        "sketch" => "application/x-sketch",
+        "zip" => "application/zip",
+        _ => return None,
+    };
+    Some(mime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_content_type_from_signature_extension_and_text() {
+        assert_eq!(
+            detect_content_type(b"%PDF-1.7 ...", "spec"),
+            "application/pdf"
+        );
+        assert_eq!(
+            detect_content_type(b"PK\x03\x04rest", "report.xlsx"),
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+        );
+        assert_eq!(
+            detect_content_type(b"PK\x03\x04rest", "bundle"),
+            "application/zip"
+        );
+        assert_eq!(detect_content_type(b"a,b\n1,2\n", "data.CSV"), "text/csv");
+        assert_eq!(detect_content_type(b"plain notes", "NOTES"), "text/plain");
+        assert_eq!(
+            detect_content_type(&[0, 159, 146, 150], "blob"),
+            "application/octet-stream"
+        );
+    }
+
+    #[test]
+    fn sanitizes_attachment_file_names() {
+        assert_eq!(sanitize_file_name("Q3 plan (v2).pdf"), "Q3_plan__v2_.pdf");
+        assert_eq!(
+            sanitize_file_name("design-export_1.fig"),
+            "design-export_1.fig"
+        );
+    }
 }
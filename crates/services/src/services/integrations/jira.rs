@@ -0,0 +1,190 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use db::models::{external_task_link::ExternalProvider, task::TaskStatus};
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{ExternalTicket, IntegrationError, TicketConnector};
+use crate::services::config::JiraConfig;
+
+const MAX_RESULTS: u32 = 100;
+
+/// Jira Cloud REST API v2. Ticket states are Jira's status categories
+/// (`new`, `indeterminate`, `done`), which every workflow maps onto.
+pub struct JiraConnector {
+    client: reqwest::Client,
+    base_url: String,
+    email: String,
+    api_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    issues: Vec<JiraIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraIssue {
+    id: String,
+    key: String,
+    fields: JiraIssueFields,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraIssueFields {
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    description: Option<String>,
+    status: JiraStatus,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraStatus {
+    #[serde(rename = "statusCategory")]
+    status_category: JiraStatusCategory,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraStatusCategory {
+    key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TransitionsResponse {
+    transitions: Vec<JiraTransition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraTransition {
+    id: String,
+    to: JiraStatus,
+}
+
+impl JiraConnector {
+    pub fn new(config: &JiraConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+        Self {
+            client,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            email: config.email.clone(),
+            api_token: config.api_token.clone(),
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, format!("{}/rest/api/2/{path}", self.base_url))
+            .basic_auth(&self.email, Some(&self.api_token))
+            .header("Accept", "application/json")
+    }
+
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, IntegrationError> {
+        let response = request.send().await?;
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        Err(IntegrationError::Api(
+            ExternalProvider::Jira,
+            format!("{status}: {body}"),
+        ))
+    }
+
+    /// Status category a task status should be shown as
+    fn target_category(status: &TaskStatus) -> &'static str {
+        match status {
+            TaskStatus::Todo => "new",
+            TaskStatus::InProgress | TaskStatus::InReview => "indeterminate",
+            TaskStatus::Done | TaskStatus::Cancelled => "done",
+        }
+    }
+}
+
+#[async_trait]
+impl TicketConnector for JiraConnector {
+    fn provider(&self) -> ExternalProvider {
+        ExternalProvider::Jira
+    }
+
+    async fn search(&self, query: &str) -> Result<Vec<ExternalTicket>, IntegrationError> {
+        let response: SearchResponse = self
+            .send(self.request(reqwest::Method::GET, "search").query(&[
+                ("jql", query),
+                ("fields", "summary,description,status"),
+                ("maxResults", &MAX_RESULTS.to_string()),
+            ]))
+            .await?
+            .json()
+            .await?;
+
+        Ok(response
+            .issues
+            .into_iter()
+            .map(|issue| ExternalTicket {
+                url: format!("{}/browse/{}", self.base_url, issue.key),
+                id: issue.id,
+                key: issue.key,
+                title: issue.fields.summary,
+                description: issue.fields.description,
+                state: issue.fields.status.status_category.key,
+            })
+            .collect())
+    }
+
+    async fn fetch_state(&self, external_id: &str) -> Result<String, IntegrationError> {
+        let issue: JiraIssue = self
+            .send(
+                self.request(reqwest::Method::GET, &format!("issue/{external_id}"))
+                    .query(&[("fields", "summary,status")]),
+            )
+            .await?
+            .json()
+            .await?;
+        Ok(issue.fields.status.status_category.key)
+    }
+
+    async fn transition(
+        &self,
+        external_id: &str,
+        status: &TaskStatus,
+    ) -> Result<Option<String>, IntegrationError> {
+        let path = format!("issue/{external_id}/transitions");
+        let target = Self::target_category(status);
+        let available: TransitionsResponse = self
+            .send(self.request(reqwest::Method::GET, &path))
+            .await?
+            .json()
+            .await?;
+        let Some(transition) = available
+            .transitions
+            .into_iter()
+            .find(|transition| transition.to.status_category.key == target)
+        else {
+            return Ok(None);
+        };
+
+        self.send(
+            self.request(reqwest::Method::POST, &path)
+                .json(&json!({ "transition": { "id": transition.id } })),
+        )
+        .await?;
+        Ok(Some(target.to_string()))
+    }
+
+    fn task_status(&self, state: &str) -> TaskStatus {
+        match state {
+            "indeterminate" => TaskStatus::InProgress,
+            "done" => TaskStatus::Done,
+            _ => TaskStatus::Todo,
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use db::models::{external_task_link::ExternalProvider, task::TaskStatus};
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+
+use super::{ExternalTicket, IntegrationError, TicketConnector};
+use crate::services::config::LinearConfig;
+
+const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
+
+/// Linear GraphQL API. Ticket states are Linear's workflow state types
+/// (`triage`, `backlog`, `unstarted`, `started`, `completed`, `canceled`).
+pub struct LinearConnector {
+    client: reqwest::Client,
+    api_key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Nodes<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssuesData {
+    issues: Nodes<LinearIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LinearIssue {
+    id: String,
+    identifier: String,
+    title: String,
+    description: Option<String>,
+    url: String,
+    state: LinearState,
+}
+
+#[derive(Debug, Deserialize)]
+struct LinearState {
+    id: String,
+    #[serde(rename = "type")]
+    state_type: String,
+    #[serde(default)]
+    position: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueStateData {
+    issue: IssueState,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueState {
+    state: LinearState,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueTeamStatesData {
+    issue: IssueTeamStates,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueTeamStates {
+    team: TeamStates,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamStates {
+    states: Nodes<LinearState>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueUpdateData {
+    #[serde(rename = "issueUpdate")]
+    issue_update: IssueUpdatePayload,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueUpdatePayload {
+    success: bool,
+}
+
+impl LinearConnector {
+    pub fn new(config: &LinearConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap();
+        Self {
+            client,
+            api_key: config.api_key.clone(),
+        }
+    }
+
+    async fn graphql<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: Value,
+    ) -> Result<T, IntegrationError> {
+        let response = self
+            .client
+            .post(LINEAR_API_URL)
+            .header("Authorization", &self.api_key)
+            .json(&json!({ "query": query, "variables": variables }))
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(IntegrationError::Api(
+                ExternalProvider::Linear,
+                format!("{status}: {body}"),
+            ));
+        }
+
+        let response: GraphQlResponse<T> = response.json().await?;
+        match response.data {
+            Some(data) if response.errors.is_empty() => Ok(data),
+            _ => Err(IntegrationError::Api(
+                ExternalProvider::Linear,
+                response
+                    .errors
+                    .into_iter()
+                    .map(|error| error.message)
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            )),
+        }
+    }
+
+    /// Workflow state type a task status should be shown as
+    fn target_state_type(status: &TaskStatus) -> &'static str {
+        match status {
+            TaskStatus::Todo => "unstarted",
+            TaskStatus::InProgress | TaskStatus::InReview => "started",
+            TaskStatus::Done => "completed",
+            TaskStatus::Cancelled => "canceled",
+        }
+    }
+}
+
+#[async_trait]
+impl TicketConnector for LinearConnector {
+    fn provider(&self) -> ExternalProvider {
+        ExternalProvider::Linear
+    }
+
+    /// Open issues of the team with the given key
+    async fn search(&self, query: &str) -> Result<Vec<ExternalTicket>, IntegrationError> {
+        let data: IssuesData = self
+            .graphql(
+                r#"query($teamKey: String!) {
+                    issues(first: 100, filter: {
+                        team: { key: { eq: $teamKey } },
+                        state: { type: { nin: ["completed", "canceled"] } }
+                    }) {
+                        nodes { id identifier title description url state { id type position } }
+                    }
+                }"#,
+                json!({ "teamKey": query.trim() }),
+            )
+            .await?;
+
+        Ok(data
+            .issues
+            .nodes
+            .into_iter()
+            .map(|issue| ExternalTicket {
+                id: issue.id,
+                key: issue.identifier,
+                title: issue.title,
+                description: issue.description,
+                url: issue.url,
+                state: issue.state.state_type,
+            })
+            .collect())
+    }
+
+    async fn fetch_state(&self, external_id: &str) -> Result<String, IntegrationError> {
+        let data: IssueStateData = self
+            .graphql(
+                r#"query($id: String!) {
+                    issue(id: $id) { state { id type position } }
+                }"#,
+                json!({ "id": external_id }),
+            )
+            .await?;
+        Ok(data.issue.state.state_type)
+    }
+
+    async fn transition(
+        &self,
+        external_id: &str,
+        status: &TaskStatus,
+    ) -> Result<Option<String>, IntegrationError> {
+        let target = Self::target_state_type(status);
+        let data: IssueTeamStatesData = self
+            .graphql(
+                r#"query($id: String!) {
+                    issue(id: $id) { team { states { nodes { id type position } } } }
+                }"#,
+                json!({ "id": external_id }),
+            )
+            .await?;
+        // Teams can have several states of a type; the first column wins
+        let Some(state) = data
+            .issue
+            .team
+            .states
+            .nodes
+            .into_iter()
+            .filter(|state| state.state_type == target)
+            .min_by(|a, b| a.position.total_cmp(&b.position))
+        else {
+            return Ok(None);
+        };
+
+        let data: IssueUpdateData = self
+            .graphql(
+                r#"mutation($id: String!, $stateId: String!) {
+                    issueUpdate(id: $id, input: { stateId: $stateId }) { success }
+                }"#,
+                json!({ "id": external_id, "stateId": state.id }),
+            )
+            .await?;
+        if !data.issue_update.success {
+            return Err(IntegrationError::Api(
+                ExternalProvider::Linear,
+                format!("Failed to move issue {external_id} to {target}"),
+            ));
+        }
+        Ok(Some(target.to_string()))
+    }
+
+    fn task_status(&self, state: &str) -> TaskStatus {
+        match state {
+            "started" => TaskStatus::InProgress,
+            "completed" => TaskStatus::Done,
+            "canceled" => TaskStatus::Cancelled,
+            _ => TaskStatus::Todo,
+        }
+    }
+}
@@ -0,0 +1,303 @@
+//! Connectors to external issue trackers. Tickets are imported as tasks and
+//! their status is kept in sync in both directions, so the board can execute
+//! work tracked elsewhere.
+
+pub mod jira;
+pub mod linear;
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use db::{
+    DBService,
+    models::{
+        external_task_link::{
+            CreateExternalTaskLink, ExternalProvider, ExternalTaskLink, ExternalTaskLinkWithStatus,
+        },
+        task::{CreateTask, Task, TaskStatus},
+    },
+};
+use sqlx::{SqlitePool, error::Error as SqlxError};
+use thiserror::Error;
+use tokio::{sync::RwLock, time::interval};
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use crate::services::{
+    config::{Config, IntegrationsConfig},
+    integrations::{jira::JiraConnector, linear::LinearConnector},
+};
+
+#[derive(Debug, Error)]
+pub enum IntegrationError {
+    #[error("{0} integration is not configured")]
+    NotConfigured(ExternalProvider),
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+    #[error("{0} API error: {1}")]
+    Api(ExternalProvider, String),
+    #[error(transparent)]
+    Sqlx(#[from] SqlxError),
+}
+
+/// A ticket as reported by the tracker
+#[derive(Debug, Clone)]
+pub struct ExternalTicket {
+    /// Identifier used by the tracker's API
+    pub id: String,
+    /// Human readable key, e.g. ENG-123
+    pub key: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub url: String,
+    /// Tracker specific state, mapped with [`TicketConnector::task_status`]
+    pub state: String,
+}
+
+#[async_trait]
+pub trait TicketConnector: Send + Sync {
+    fn provider(&self) -> ExternalProvider;
+
+    /// Tickets matching a provider specific query: JQL for Jira, a team key
+    /// for Linear
+    async fn search(&self, query: &str) -> Result<Vec<ExternalTicket>, IntegrationError>;
+
+    /// Current state of a ticket
+    async fn fetch_state(&self, external_id: &str) -> Result<String, IntegrationError>;
+
+    /// Move a ticket to the state matching `status` and return its new state,
+    /// or `None` when the tracker's workflow offers no such transition
+    async fn transition(
+        &self,
+        external_id: &str,
+        status: &TaskStatus,
+    ) -> Result<Option<String>, IntegrationError>;
+
+    /// Task status a tracker state corresponds to
+    fn task_status(&self, state: &str) -> TaskStatus;
+}
+
+/// Connector for a provider, built from the configured credentials
+pub fn connector_for(
+    config: &IntegrationsConfig,
+    provider: ExternalProvider,
+) -> Result<Box<dyn TicketConnector>, IntegrationError> {
+    match provider {
+        ExternalProvider::Jira => config
+            .jira
+            .as_ref()
+            .map(|jira| Box::new(JiraConnector::new(jira)) as Box<dyn TicketConnector>)
+            .ok_or(IntegrationError::NotConfigured(provider)),
+        ExternalProvider::Linear => config
+            .linear
+            .as_ref()
+            .map(|linear| Box::new(LinearConnector::new(linear)) as Box<dyn TicketConnector>)
+            .ok_or(IntegrationError::NotConfigured(provider)),
+    }
+}
+
+/// Tasks created by an import, and how many matching tickets already had one
+#[derive(Debug)]
+pub struct ImportedTickets {
+    pub created: Vec<Task>,
+    pub skipped: usize,
+}
+
+/// Create one task per ticket matching `query`. Tickets that were imported
+/// before are skipped, so importing is repeatable.
+pub async fn import_tickets(
+    pool: &SqlitePool,
+    connector: &dyn TicketConnector,
+    project_id: Uuid,
+    query: &str,
+) -> Result<ImportedTickets, IntegrationError> {
+    let provider = connector.provider();
+    let tickets = connector.search(query).await?;
+    let linked = ExternalTaskLink::linked_external_ids(pool, provider).await?;
+
+    let mut created = Vec::new();
+    let mut skipped = 0;
+    for ticket in tickets {
+        if linked.contains(&ticket.id) {
+            skipped += 1;
+            continue;
+        }
+        let mut task = Task::create(
+            pool,
+            &CreateTask::from_title_description(
+                project_id,
+                format!("{}: {}", ticket.key, ticket.title),
+                Some(ticket_description(&ticket)),
+            ),
+            Uuid::new_v4(),
+        )
+        .await?;
+        let status = connector.task_status(&ticket.state);
+        if status != task.status {
+            Task::update_status(pool, task.id, status.clone()).await?;
+            task.status = status.clone();
+        }
+        ExternalTaskLink::create(
+            pool,
+            &CreateExternalTaskLink {
+                task_id: task.id,
+                provider,
+                external_id: &ticket.id,
+                external_key: &ticket.key,
+                external_url: &ticket.url,
+                last_synced_status: status,
+                remote_state: &ticket.state,
+            },
+        )
+        .await?;
+        created.push(task);
+    }
+
+    info!(
+        "Imported {} {} tickets ({} already imported)",
+        created.len(),
+        provider,
+        skipped
+    );
+    Ok(ImportedTickets { created, skipped })
+}
+
+/// Ticket description followed by a link back to the ticket
+fn ticket_description(ticket: &ExternalTicket) -> String {
+    match ticket.description.as_deref().map(str::trim) {
+        Some(description) if !description.is_empty() => {
+            format!("{description}\n\nImported from {}", ticket.url)
+        }
+        _ => format!("Imported from {}", ticket.url),
+    }
+}
+
+/// Statuses the trackers cannot tell apart count as unchanged
+fn same_stage(a: &TaskStatus, b: &TaskStatus) -> bool {
+    let stage = |status: &TaskStatus| match status {
+        TaskStatus::InReview => TaskStatus::InProgress,
+        other => other.clone(),
+    };
+    stage(a) == stage(b)
+}
+
+/// Keeps linked tasks and tickets in step. A ticket that moved since the last
+/// sync wins over a task that moved, so edits in the tracker are never lost.
+pub struct IntegrationSyncService {
+    db: DBService,
+    config: Arc<RwLock<Config>>,
+    poll_interval: Duration,
+}
+
+impl IntegrationSyncService {
+    pub async fn spawn(db: DBService, config: Arc<RwLock<Config>>) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            config,
+            poll_interval: Duration::from_secs(120),
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting integration sync service with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            for provider in [ExternalProvider::Jira, ExternalProvider::Linear] {
+                match self.sync_provider(provider).await {
+                    Err(IntegrationError::NotConfigured(_)) => {}
+                    Err(e) => error!("Error syncing {} tickets: {}", provider, e),
+                    Ok(()) => {}
+                }
+            }
+        }
+    }
+
+    async fn sync_provider(&self, provider: ExternalProvider) -> Result<(), IntegrationError> {
+        let links = ExternalTaskLink::find_by_provider_with_status(&self.db.pool, provider).await?;
+        if links.is_empty() {
+            debug!("No {} tickets to sync", provider);
+            return Ok(());
+        }
+
+        let integrations = self.config.read().await.integrations.clone();
+        let connector = match connector_for(&integrations, provider) {
+            Ok(connector) => connector,
+            Err(e) => {
+                warn!(
+                    "{} is not configured, cannot sync {} tickets",
+                    provider,
+                    links.len()
+                );
+                return Err(e);
+            }
+        };
+
+        for link in links {
+            if let Err(e) = self.sync_link(connector.as_ref(), &link).await {
+                error!(
+                    "Failed to sync {} with task {}: {}",
+                    link.link.external_url, link.link.task_id, e
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn sync_link(
+        &self,
+        connector: &dyn TicketConnector,
+        link: &ExternalTaskLinkWithStatus,
+    ) -> Result<(), IntegrationError> {
+        let pool = &self.db.pool;
+        let ExternalTaskLinkWithStatus { link, task_status } = link;
+        let remote_state = connector.fetch_state(&link.external_id).await?;
+
+        if remote_state != link.remote_state {
+            let remote_status = connector.task_status(&remote_state);
+            let status = if same_stage(task_status, &remote_status) {
+                task_status.clone()
+            } else {
+                info!(
+                    "{} moved to '{}', updating task {}",
+                    link.external_key, remote_state, link.task_id
+                );
+                Task::update_status(pool, link.task_id, remote_status.clone()).await?;
+                remote_status
+            };
+            ExternalTaskLink::mark_synced(pool, link.task_id, status, &remote_state).await?;
+        } else if *task_status != link.last_synced_status {
+            let new_state = if same_stage(task_status, &connector.task_status(&remote_state)) {
+                remote_state
+            } else {
+                match connector.transition(&link.external_id, task_status).await? {
+                    Some(new_state) => {
+                        info!(
+                            "Task {} moved to {}, {} is now '{}'",
+                            link.task_id, task_status, link.external_key, new_state
+                        );
+                        new_state
+                    }
+                    // Recorded as synced anyway, so the transition is not retried forever
+                    None => {
+                        warn!(
+                            "{} has no transition matching task status {}",
+                            link.external_key, task_status
+                        );
+                        remote_state
+                    }
+                }
+            };
+            ExternalTaskLink::mark_synced(pool, link.task_id, task_status.clone(), &new_state)
+                .await?;
+        }
+        Ok(())
+    }
+}
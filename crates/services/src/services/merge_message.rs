@@ -0,0 +1,163 @@
+//! Messages of the commits attempts are merged with, rendered from templates
+//! such as `{title} ({task_id})`
+
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Used for projects without a template of their own
+pub const DEFAULT_COMMIT_MESSAGE_TEMPLATE: &str =
+    "{title} (vibe-kanban {task_id})\n\n{description}";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CommitMessageTemplateError {
+    #[error("Commit message template is empty")]
+    Empty,
+    #[error("Unknown placeholder {{{0}}} in commit message template")]
+    UnknownPlaceholder(String),
+    #[error("Unbalanced braces in commit message template")]
+    UnbalancedBraces,
+}
+
+/// Values of the placeholders for one merge
+pub struct CommitMessageContext<'a> {
+    pub title: &'a str,
+    pub description: Option<&'a str>,
+    pub task_id: &'a Uuid,
+    /// Attempt branch being merged
+    pub branch: &'a str,
+    pub target_branch: &'a str,
+}
+
+fn placeholder_value(
+    name: &str,
+    context: &CommitMessageContext,
+) -> Result<String, CommitMessageTemplateError> {
+    Ok(match name {
+        "title" => context.title.to_string(),
+        "description" => context.description.unwrap_or_default().trim().to_string(),
+        // First section of the task id, as in the default message
+        "task_id" => context
+            .task_id
+            .to_string()
+            .split('-')
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+        "branch" => context.branch.to_string(),
+        "target_branch" => context.target_branch.to_string(),
+        other => {
+            return Err(CommitMessageTemplateError::UnknownPlaceholder(
+                other.to_string(),
+            ));
+        }
+    })
+}
+
+/// Commit message for a merge from `template`. Trailing blank lines, e.g.
+/// from an empty description, are dropped.
+pub fn render_commit_message(
+    template: &str,
+    context: &CommitMessageContext,
+) -> Result<String, CommitMessageTemplateError> {
+    if template.trim().is_empty() {
+        return Err(CommitMessageTemplateError::Empty);
+    }
+    let mut rendered = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err(CommitMessageTemplateError::UnbalancedBraces);
+        }
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let end = after
+            .find('}')
+            .ok_or(CommitMessageTemplateError::UnbalancedBraces)?;
+        rendered.push_str(&placeholder_value(&after[..end], context)?);
+        rest = &after[end + 1..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered.trim_end().to_string())
+}
+
+/// Check a template before it is saved by rendering it for a sample merge
+pub fn validate_commit_message_template(template: &str) -> Result<(), CommitMessageTemplateError> {
+    let context = CommitMessageContext {
+        title: "Add login page",
+        description: None,
+        task_id: &Uuid::nil(),
+        branch: "vk/0000-add-login-page",
+        target_branch: "main",
+    };
+    render_commit_message(template, &context).map(|_| ())
+}
+
+/// First line of a commit message, and the rest of it if there is any
+pub fn split_commit_message(message: &str) -> (&str, Option<&str>) {
+    match message.split_once('\n') {
+        Some((title, body)) => {
+            let body = body.trim();
+            (title.trim_end(), (!body.is_empty()).then_some(body))
+        }
+        None => (message, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_templates_and_drops_trailing_blank_lines() {
+        let task_id = Uuid::parse_str("a1b2c3d4-0000-0000-0000-000000000000").unwrap();
+        let mut context = CommitMessageContext {
+            title: "Fix the login redirect",
+            description: Some("Redirect to the page the user came from.\n"),
+            task_id: &task_id,
+            branch: "vk/a1b2-fix-the-login-re",
+            target_branch: "main",
+        };
+        assert_eq!(
+            render_commit_message(DEFAULT_COMMIT_MESSAGE_TEMPLATE, &context).unwrap(),
+            "Fix the login redirect (vibe-kanban a1b2c3d4)\n\nRedirect to the page the user came from."
+        );
+        context.description = None;
+        assert_eq!(
+            render_commit_message(DEFAULT_COMMIT_MESSAGE_TEMPLATE, &context).unwrap(),
+            "Fix the login redirect (vibe-kanban a1b2c3d4)"
+        );
+        assert_eq!(
+            render_commit_message("{title}\n\nMerges {branch} into {target_branch}", &context)
+                .unwrap(),
+            "Fix the login redirect\n\nMerges vk/a1b2-fix-the-login-re into main"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_templates() {
+        assert_eq!(
+            validate_commit_message_template("{title} {ticket}"),
+            Err(CommitMessageTemplateError::UnknownPlaceholder(
+                "ticket".to_string()
+            ))
+        );
+        assert_eq!(
+            validate_commit_message_template("{title"),
+            Err(CommitMessageTemplateError::UnbalancedBraces)
+        );
+        assert_eq!(
+            validate_commit_message_template("\n"),
+            Err(CommitMessageTemplateError::Empty)
+        );
+        assert!(validate_commit_message_template("{title} ({task_id})").is_ok());
+    }
+
+    #[test]
+    fn splits_the_title_from_the_body() {
+        assert_eq!(split_commit_message("Fix login"), ("Fix login", None));
+        assert_eq!(
+            split_commit_message("Fix login\n\nRedirect back"),
+            ("Fix login", Some("Redirect back"))
+        );
+    }
+}
@@ -1,19 +1,35 @@
 pub mod analytics;
 pub mod approvals;
 pub mod auth;
+pub mod base_branch_watch;
+pub mod branch_naming;
+pub mod ci_checks;
+pub mod commit_signing;
 pub mod config;
+pub mod conflicts;
 pub mod container;
+pub mod context_packs;
 pub mod diff_stream;
 pub mod drafts;
 pub mod events;
+pub mod execution_queue;
 pub mod file_ranker;
 pub mod file_search_cache;
 pub mod filesystem;
 pub mod filesystem_watcher;
 pub mod git;
 pub mod git_cli;
+pub mod github_issues;
 pub mod github_service;
+pub mod hosting;
 pub mod image;
+pub mod integrations;
+pub mod merge_message;
 pub mod notification;
 pub mod pr_monitor;
+pub mod proposed_tasks;
+pub mod rate_limiter;
+pub mod review_comments;
+pub mod secrets;
+pub mod turn_tests;
 pub mod worktree_manager;
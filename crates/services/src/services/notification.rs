@@ -15,8 +15,11 @@ static WSL_ROOT_PATH_CACHE: OnceLock<Option<String>> = OnceLock::new();
 
 impl NotificationService {
     pub async fn notify_execution_halted(mut config: NotificationConfig, ctx: &ExecutionContext) {
+        let timed_out = matches!(ctx.execution_process.status, ExecutionProcessStatus::Killed)
+            && ctx.task_attempt.timed_out_at.is_some();
+
         // If the process was intentionally killed by user, suppress sound
-        if matches!(ctx.execution_process.status, ExecutionProcessStatus::Killed) {
+        if matches!(ctx.execution_process.status, ExecutionProcessStatus::Killed) && !timed_out {
             config.sound_enabled = false;
         }
 
@@ -30,6 +33,10 @@ impl NotificationService {
                 "❌ '{}' execution failed\nBranch: {:?}\nExecutor: {}",
                 ctx.task.title, ctx.task_attempt.branch, ctx.task_attempt.executor
             ),
+            ExecutionProcessStatus::Killed if timed_out => format!(
+                "⏱️ '{}' exceeded its execution timeout and was cancelled\nBranch: {:?}\nExecutor: {}",
+                ctx.task.title, ctx.task_attempt.branch, ctx.task_attempt.executor
+            ),
             ExecutionProcessStatus::Killed => format!(
                 "🛑 '{}' execution cancelled by user\nBranch: {:?}\nExecutor: {}",
                 ctx.task.title, ctx.task_attempt.branch, ctx.task_attempt.executor
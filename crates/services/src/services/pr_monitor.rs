@@ -4,6 +4,7 @@ use db::{
     DBService,
     models::{
         merge::{Merge, MergeStatus, PrMerge},
+        project::Project,
         task::{Task, TaskStatus},
         task_attempt::{TaskAttempt, TaskAttemptError},
     },
@@ -16,23 +17,26 @@ use tracing::{debug, error, info, warn};
 
 use crate::services::{
     analytics::AnalyticsContext,
+    ci_checks,
     config::Config,
-    github_service::{GitHubRepoInfo, GitHubService, GitHubServiceError},
+    git::GitService,
+    github_service::GitHubServiceError,
+    hosting::{self, HostingError},
+    review_comments,
 };
 
 #[derive(Debug, Error)]
 enum PrMonitorError {
-    #[error("No GitHub token configured")]
-    NoGitHubToken,
     #[error(transparent)]
-    GitHubServiceError(#[from] GitHubServiceError),
+    Hosting(#[from] HostingError),
     #[error(transparent)]
     TaskAttemptError(#[from] TaskAttemptError),
     #[error(transparent)]
     Sqlx(#[from] SqlxError),
 }
 
-/// Service to monitor GitHub PRs and update task status when they are merged
+/// Service to monitor PRs on the projects' code hosts, copying their review
+/// comments and CI checks and updating task status when they are merged
 pub struct PrMonitorService {
     db: DBService,
     config: Arc<RwLock<Config>>,
@@ -73,7 +77,7 @@ impl PrMonitorService {
         }
     }
 
-    /// Check all open PRs for updates with the provided GitHub token
+    /// Check all open PRs for updates with the configured tokens
     async fn check_all_open_prs(&self) -> Result<(), PrMonitorError> {
         let open_prs = Merge::get_open_prs(&self.db.pool).await?;
 
@@ -86,9 +90,14 @@ impl PrMonitorService {
 
         for pr_merge in open_prs {
             match self.check_pr_status(&pr_merge).await {
-                Err(PrMonitorError::NoGitHubToken) => {
+                Err(PrMonitorError::Hosting(HostingError::GitHub(
+                    GitHubServiceError::TokenInvalid,
+                ))) => {
                     warn!("No GitHub token configured, cannot check PR status");
                 }
+                Err(PrMonitorError::Hosting(HostingError::NotConfigured(provider))) => {
+                    warn!("{} is not configured, cannot check PR status", provider);
+                }
                 Err(e) => {
                     error!(
                         "Error checking PR #{} for attempt {}: {}",
@@ -103,15 +112,15 @@ impl PrMonitorService {
 
     /// Check the status of a specific PR
     async fn check_pr_status(&self, pr_merge: &PrMerge) -> Result<(), PrMonitorError> {
-        let github_config = self.config.read().await.github.clone();
-        let github_token = github_config.token().ok_or(PrMonitorError::NoGitHubToken)?;
-
-        let github_service = GitHubService::new(&github_token)?;
-
-        let repo_info = GitHubRepoInfo::from_remote_url(&pr_merge.pr_info.url)?;
+        let Some((task_attempt, project)) = self.pr_attempt(pr_merge).await? else {
+            return Ok(());
+        };
+        let config = self.config.read().await.clone();
+        let provider =
+            hosting::resolve_provider(&self.db.pool, &GitService::new(), &config, &project).await?;
 
-        let pr_status = github_service
-            .update_pr_status(&repo_info, pr_merge.pr_info.number)
+        let pr_status = provider
+            .fetch_pr(&pr_merge.pr_info.url, pr_merge.pr_info.number)
             .await?;
 
         debug!(
@@ -119,9 +128,42 @@ impl PrMonitorService {
             pr_merge.pr_info.number, pr_status.status
         );
 
+        match review_comments::sync_review_comments(&self.db.pool, provider.as_ref(), pr_merge)
+            .await
+        {
+            Ok(0) => {}
+            Ok(added) => info!(
+                "PR #{} has {} new review comments",
+                pr_merge.pr_info.number, added
+            ),
+            Err(e) => warn!(
+                "Failed to fetch review comments of PR #{}: {}",
+                pr_merge.pr_info.number, e
+            ),
+        }
+
+        if let Err(e) = ci_checks::sync_ci_checks(
+            &self.db.pool,
+            provider.as_ref(),
+            &pr_merge.pr_info.url,
+            task_attempt.id,
+            &task_attempt.branch,
+        )
+        .await
+        {
+            warn!(
+                "Failed to fetch CI checks of PR #{}: {}",
+                pr_merge.pr_info.number, e
+            );
+        }
+
+        if pr_status.is_draft != pr_merge.pr_info.is_draft {
+            Merge::update_draft(&self.db.pool, pr_merge.id, pr_status.is_draft).await?;
+        }
+
         // Update the PR status in the database
         if !matches!(&pr_status.status, MergeStatus::Open) {
-            // Update merge status with the latest information from GitHub
+            // Update merge status with the latest information from the code host
             Merge::update_status(
                 &self.db.pool,
                 pr_merge.id,
@@ -131,10 +173,7 @@ impl PrMonitorService {
             .await?;
 
             // If the PR was merged, update the task status to done
-            if matches!(&pr_status.status, MergeStatus::Merged)
-                && let Some(task_attempt) =
-                    TaskAttempt::find_by_id(&self.db.pool, pr_merge.task_attempt_id).await?
-            {
+            if matches!(&pr_status.status, MergeStatus::Merged) {
                 info!(
                     "PR #{} was merged, updating task {} to done",
                     pr_merge.pr_info.number, task_attempt.task_id
@@ -161,4 +200,22 @@ impl PrMonitorService {
 
         Ok(())
     }
+
+    /// Attempt a PR was opened for, and its project
+    async fn pr_attempt(
+        &self,
+        pr_merge: &PrMerge,
+    ) -> Result<Option<(TaskAttempt, Project)>, PrMonitorError> {
+        let Some(task_attempt) =
+            TaskAttempt::find_by_id(&self.db.pool, pr_merge.task_attempt_id).await?
+        else {
+            return Ok(None);
+        };
+        let Some(task) = task_attempt.parent_task(&self.db.pool).await? else {
+            return Ok(None);
+        };
+        Ok(Project::find_by_id(&self.db.pool, task.project_id)
+            .await?
+            .map(|project| (task_attempt, project)))
+    }
 }
@@ -0,0 +1,124 @@
+/// Appended to the task prompt, so that the agent's suggestions for later work
+/// can be picked out of its final message
+pub const PROPOSAL_INSTRUCTIONS: &str = "\n\nIf you come across work that is outside this task but worth doing later, end your final message with one line per item in the form `FOLLOW-UP: <title> - <details>`, where the details are optional.";
+
+/// Markers a proposal line starts with, lowercase. Agents that were not asked
+/// for the convention often write TODO lines instead.
+const MARKERS: &[&str] = &["follow-up:", "follow up:", "followup:", "todo:"];
+/// More proposals in one message are most likely not meant as such
+const MAX_PROPOSALS: usize = 20;
+const MAX_TITLE_CHARS: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proposal {
+    pub title: String,
+    pub description: Option<String>,
+}
+
+/// Read the follow-up work proposed in an agent message. Markdown list
+/// markers and emphasis around the marker are ignored, as agents render the
+/// convention differently, and so are lines in code blocks.
+pub fn parse_proposals(message: &str) -> Vec<Proposal> {
+    let mut proposals: Vec<Proposal> = Vec::new();
+    let mut in_code_block = false;
+    for line in message.lines() {
+        let line = line.trim();
+        if line.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+        let Some(proposal) = parse_line(line) else {
+            continue;
+        };
+        if proposals.iter().any(|p| p.title == proposal.title) {
+            continue;
+        }
+        proposals.push(proposal);
+        if proposals.len() == MAX_PROPOSALS {
+            break;
+        }
+    }
+    proposals
+}
+
+fn parse_line(line: &str) -> Option<Proposal> {
+    let line = strip_list_marker(line).trim_start_matches(['*', '_', '`']);
+    let lower = line.to_lowercase();
+    let marker = MARKERS.iter().find(|marker| lower.starts_with(*marker))?;
+    let rest = line[marker.len()..]
+        .trim_start_matches(['*', '_', '`'])
+        .trim()
+        .trim_end_matches(['*', '_', '`']);
+
+    let (title, description) = match rest.split_once(" - ").or_else(|| rest.split_once(" — ")) {
+        Some((title, description)) => (title.trim(), Some(description.trim())),
+        None => (rest, None),
+    };
+    let title = title.trim_matches(['*', '_', '`']).trim();
+    if title.is_empty() {
+        return None;
+    }
+    Some(Proposal {
+        title: title.chars().take(MAX_TITLE_CHARS).collect(),
+        description: description
+            .filter(|description| !description.is_empty())
+            .map(str::to_string),
+    })
+}
+
+/// `- `, `* `, `+ ` or `1. ` in front of a list item
+fn strip_list_marker(line: &str) -> &str {
+    if let Some(rest) = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+    {
+        return rest.trim_start();
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0
+        && let Some(rest) = line[digits..]
+            .strip_prefix(". ")
+            .or_else(|| line[digits..].strip_prefix(") "))
+    {
+        return rest.trim_start();
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_proposals_in_agent_markdown() {
+        let message = "Implemented the endpoint.\n\n\
+                       FOLLOW-UP: Add pagination - the list grows with every run\n\
+                       - **Follow-up:** Cache the branch list\n\
+                       2. TODO: `Remove the legacy route`\n\
+                       ```rust\n// TODO: not a proposal\n```\n\
+                       FOLLOW-UP: Add pagination - repeated\n\
+                       FOLLOW-UP:   ";
+        assert_eq!(
+            parse_proposals(message),
+            vec![
+                Proposal {
+                    title: "Add pagination".to_string(),
+                    description: Some("the list grows with every run".to_string()),
+                },
+                Proposal {
+                    title: "Cache the branch list".to_string(),
+                    description: None,
+                },
+                Proposal {
+                    title: "Remove the legacy route".to_string(),
+                    description: None,
+                },
+            ]
+        );
+        assert!(parse_proposals("Nothing left to do.").is_empty());
+    }
+}
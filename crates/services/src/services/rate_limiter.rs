@@ -0,0 +1,173 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use executors::executors::BaseCodingAgent;
+use serde::Serialize;
+use ts_rs::TS;
+
+/// Wait after the first provider refusal when the provider gives no hint
+const BASE_BACKOFF: Duration = Duration::from_secs(30);
+/// Longest wait, whatever the provider or the number of refusals in a row
+const MAX_BACKOFF: Duration = Duration::from_secs(30 * 60);
+/// Window over which started runs are counted
+const USAGE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Where an executor's model provider stands, as exposed by the API
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ProviderLimitStatus {
+    pub executor: BaseCodingAgent,
+    /// New attempts with this executor are queued until then; null when
+    /// they start right away
+    pub limited_until: Option<DateTime<Utc>>,
+    /// Provider refusals in a row; a successful run resets it
+    pub consecutive_limits: u32,
+    pub last_error: Option<String>,
+    pub last_limited_at: Option<DateTime<Utc>>,
+    /// Coding agent runs started in the last minute
+    pub recent_starts: usize,
+}
+
+#[derive(Debug, Default)]
+struct ProviderState {
+    limited_until: Option<DateTime<Utc>>,
+    consecutive_limits: u32,
+    last_error: Option<String>,
+    last_limited_at: Option<DateTime<Utc>>,
+    starts: Vec<DateTime<Utc>>,
+}
+
+impl ProviderState {
+    fn record_limit(
+        &mut self,
+        error: &str,
+        retry_after: Option<Duration>,
+        now: DateTime<Utc>,
+    ) -> Duration {
+        self.consecutive_limits = self.consecutive_limits.saturating_add(1);
+        // Doubles with every refusal in a row, unless the provider said how long
+        let doublings = self.consecutive_limits.saturating_sub(1).min(16);
+        let exponential = BASE_BACKOFF.saturating_mul(1 << doublings);
+        let backoff = retry_after.unwrap_or(exponential).min(MAX_BACKOFF);
+        let until = now + chrono::Duration::from_std(backoff).unwrap_or_default();
+        self.limited_until = Some(
+            self.limited_until
+                .map_or(until, |current| current.max(until)),
+        );
+        self.last_error = Some(error.to_string());
+        self.last_limited_at = Some(now);
+        backoff
+    }
+
+    fn is_limited(&self, now: DateTime<Utc>) -> bool {
+        self.limited_until.is_some_and(|until| until > now)
+    }
+
+    fn prune_starts(&mut self, now: DateTime<Utc>) {
+        let window = chrono::Duration::from_std(USAGE_WINDOW).unwrap_or_default();
+        self.starts.retain(|started| now - *started < window);
+    }
+}
+
+/// Tracks model provider refusals (rate limits, quota, overload) per
+/// executor, so that new attempts with a refused executor wait in the
+/// execution queue instead of failing one after another
+#[derive(Clone, Default)]
+pub struct ProviderRateLimiter {
+    providers: Arc<Mutex<HashMap<BaseCodingAgent, ProviderState>>>,
+}
+
+impl ProviderRateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a refusal and hold back new runs of the executor; returns how
+    /// long they are held back
+    pub fn record_limit(
+        &self,
+        executor: BaseCodingAgent,
+        error: &str,
+        retry_after: Option<Duration>,
+    ) -> Duration {
+        let mut providers = self.providers.lock().unwrap();
+        providers
+            .entry(executor)
+            .or_default()
+            .record_limit(error, retry_after, Utc::now())
+    }
+
+    /// A run went through: the provider accepts requests again
+    pub fn record_success(&self, executor: BaseCodingAgent) {
+        if let Some(state) = self.providers.lock().unwrap().get_mut(&executor) {
+            state.consecutive_limits = 0;
+            state.limited_until = None;
+        }
+    }
+
+    pub fn record_start(&self, executor: BaseCodingAgent) {
+        let now = Utc::now();
+        let mut providers = self.providers.lock().unwrap();
+        let state = providers.entry(executor).or_default();
+        state.prune_starts(now);
+        state.starts.push(now);
+    }
+
+    pub fn is_limited(&self, executor: BaseCodingAgent) -> bool {
+        self.providers
+            .lock()
+            .unwrap()
+            .get(&executor)
+            .is_some_and(|state| state.is_limited(Utc::now()))
+    }
+
+    pub fn snapshot(&self) -> Vec<ProviderLimitStatus> {
+        let now = Utc::now();
+        let mut providers = self.providers.lock().unwrap();
+        let mut statuses: Vec<_> = providers
+            .iter_mut()
+            .map(|(executor, state)| {
+                state.prune_starts(now);
+                ProviderLimitStatus {
+                    executor: *executor,
+                    limited_until: state.limited_until.filter(|until| *until > now),
+                    consecutive_limits: state.consecutive_limits,
+                    last_error: state.last_error.clone(),
+                    last_limited_at: state.last_limited_at,
+                    recent_starts: state.starts.len(),
+                }
+            })
+            .collect();
+        statuses.sort_by_key(|status| status.executor.to_string());
+        statuses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_and_respects_hints() {
+        let now = Utc::now();
+        let mut state = ProviderState::default();
+
+        assert_eq!(state.record_limit("429", None, now), BASE_BACKOFF);
+        assert_eq!(state.record_limit("429", None, now), BASE_BACKOFF * 2);
+        assert!(state.is_limited(now));
+
+        let hinted = Duration::from_secs(5);
+        assert_eq!(state.record_limit("429", Some(hinted), now), hinted);
+        // A shorter hint does not cut an earlier, longer wait short
+        assert!(state.is_limited(now + chrono::Duration::seconds(30)));
+
+        for _ in 0..20 {
+            state.record_limit("overloaded", None, now);
+        }
+        assert_eq!(state.record_limit("overloaded", None, now), MAX_BACKOFF);
+        assert!(!state.is_limited(now + chrono::Duration::hours(1)));
+    }
+}
@@ -0,0 +1,95 @@
+use db::models::{merge::PrMerge, pr_review_comment::PrReviewComment};
+use sqlx::SqlitePool;
+
+use crate::services::hosting::{HostingError, HostingProvider};
+
+/// Copy the review comments of a pull request from its code host, updating
+/// edited ones and forgetting deleted ones. Returns how many are new.
+pub async fn sync_review_comments(
+    pool: &SqlitePool,
+    provider: &dyn HostingProvider,
+    pr_merge: &PrMerge,
+) -> Result<usize, HostingError> {
+    let comments = provider
+        .list_review_comments(&pr_merge.pr_info.url, pr_merge.pr_info.number)
+        .await?;
+    let mut added = 0;
+    for comment in &comments {
+        if PrReviewComment::upsert(pool, pr_merge.task_attempt_id, pr_merge.id, comment).await? {
+            added += 1;
+        }
+    }
+    let external_ids: Vec<String> = comments
+        .into_iter()
+        .map(|comment| comment.external_id)
+        .collect();
+    PrReviewComment::delete_missing(pool, pr_merge.id, &external_ids).await?;
+    Ok(added)
+}
+
+/// Follow-up prompt asking the agent to address review comments, with the
+/// file and line of inline ones
+pub fn review_comments_prompt(comments: &[PrReviewComment], instructions: Option<&str>) -> String {
+    let mut prompt =
+        "Reviewers left these comments on the pull request. Address each of them:".to_string();
+    for comment in comments {
+        let location = match (&comment.path, comment.line) {
+            (Some(path), Some(line)) => format!(" on `{path}` line {line}"),
+            (Some(path), None) => format!(" on `{path}`"),
+            _ => String::new(),
+        };
+        prompt.push_str(&format!(
+            "\n\n### {}{location}\n\n{}",
+            comment.author,
+            comment.body.trim()
+        ));
+    }
+    if let Some(instructions) = instructions.map(str::trim)
+        && !instructions.is_empty()
+    {
+        prompt.push_str("\n\n");
+        prompt.push_str(instructions);
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn comment(author: &str, body: &str, path: Option<&str>, line: Option<i64>) -> PrReviewComment {
+        PrReviewComment {
+            id: Uuid::new_v4(),
+            task_attempt_id: Uuid::new_v4(),
+            merge_id: Uuid::new_v4(),
+            external_id: "1".to_string(),
+            author: author.to_string(),
+            body: body.to_string(),
+            path: path.map(str::to_string),
+            line,
+            url: None,
+            commented_at: Utc::now(),
+            execution_process_id: None,
+            sent_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn prompt_lists_comments_with_their_location() {
+        let comments = [
+            comment("alice", "Looks good overall.\n", None, None),
+            comment("bob", "Handle the error here", Some("src/lib.rs"), Some(12)),
+        ];
+        let prompt = review_comments_prompt(&comments, Some("  Keep the public API.  "));
+
+        assert!(prompt.contains("### alice\n\nLooks good overall.\n\n### bob"));
+        assert!(prompt.contains("### bob on `src/lib.rs` line 12\n\nHandle the error here"));
+        assert!(prompt.ends_with("\n\nKeep the public API."));
+        assert!(!review_comments_prompt(&comments, Some(" ")).ends_with(' '));
+    }
+}
@@ -0,0 +1,230 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use db::models::project_env_var::ProjectEnvVar;
+use executors::env::ExecutionEnv;
+use ring::{
+    aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey},
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use uuid::Uuid;
+
+const KEY_LEN: usize = 32;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SecretsError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Failed to encrypt or decrypt a value")]
+    Crypto,
+
+    #[error("Invalid secrets key file: {0}")]
+    InvalidKey(PathBuf),
+
+    #[error("Invalid environment variable name: {0}")]
+    InvalidName(String),
+}
+
+impl From<ring::error::Unspecified> for SecretsError {
+    fn from(_: ring::error::Unspecified) -> Self {
+        SecretsError::Crypto
+    }
+}
+
+/// A project environment variable as returned by the API. Only non-secret
+/// values are included.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ProjectEnvVarEntry {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub env_var: ProjectEnvVar,
+    pub value: Option<String>,
+}
+
+/// Encrypts project environment variables at rest with AES-256-GCM. The key
+/// lives in the asset directory, next to but outside of the database.
+#[derive(Clone)]
+pub struct SecretsService {
+    key: Arc<LessSafeKey>,
+    rng: SystemRandom,
+}
+
+impl SecretsService {
+    pub fn new() -> Result<Self, SecretsError> {
+        Self::from_key_file(&utils::assets::asset_dir().join("secrets.key"))
+    }
+
+    /// Load the key at `path`, generating it on first use
+    pub fn from_key_file(path: &Path) -> Result<Self, SecretsError> {
+        let rng = SystemRandom::new();
+        let key_bytes = if path.exists() {
+            fs::read(path)?
+        } else {
+            let mut key_bytes = vec![0u8; KEY_LEN];
+            rng.fill(&mut key_bytes)?;
+            write_key_file(path, &key_bytes)?;
+            key_bytes
+        };
+        if key_bytes.len() != KEY_LEN {
+            return Err(SecretsError::InvalidKey(path.to_path_buf()));
+        }
+        let key = UnboundKey::new(&AES_256_GCM, &key_bytes)
+            .map_err(|_| SecretsError::InvalidKey(path.to_path_buf()))?;
+        Ok(Self {
+            key: Arc::new(LessSafeKey::new(key)),
+            rng,
+        })
+    }
+
+    /// Encrypt a value; the random nonce is prepended to the ciphertext
+    pub fn encrypt(&self, plaintext: &str) -> Result<Vec<u8>, SecretsError> {
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        self.rng.fill(&mut nonce_bytes)?;
+        let mut in_out = plaintext.as_bytes().to_vec();
+        self.key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce_bytes),
+            Aad::empty(),
+            &mut in_out,
+        )?;
+        let mut sealed = nonce_bytes.to_vec();
+        sealed.extend_from_slice(&in_out);
+        Ok(sealed)
+    }
+
+    pub fn decrypt(&self, sealed: &[u8]) -> Result<String, SecretsError> {
+        if sealed.len() < NONCE_LEN {
+            return Err(SecretsError::Crypto);
+        }
+        let (nonce_bytes, ciphertext) = sealed.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce_bytes)?;
+        let mut in_out = ciphertext.to_vec();
+        let plaintext = self.key.open_in_place(nonce, Aad::empty(), &mut in_out)?;
+        String::from_utf8(plaintext.to_vec()).map_err(|_| SecretsError::Crypto)
+    }
+
+    pub async fn list_env_vars(
+        &self,
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<ProjectEnvVarEntry>, SecretsError> {
+        ProjectEnvVar::find_by_project_id(pool, project_id)
+            .await?
+            .into_iter()
+            .map(|env_var| self.entry(env_var))
+            .collect()
+    }
+
+    pub async fn set_env_var(
+        &self,
+        pool: &SqlitePool,
+        project_id: Uuid,
+        name: &str,
+        value: &str,
+        is_secret: bool,
+    ) -> Result<ProjectEnvVarEntry, SecretsError> {
+        if !is_valid_env_var_name(name) {
+            return Err(SecretsError::InvalidName(name.to_string()));
+        }
+        let encrypted_value = self.encrypt(value)?;
+        let env_var =
+            ProjectEnvVar::upsert(pool, project_id, name, &encrypted_value, is_secret).await?;
+        self.entry(env_var)
+    }
+
+    /// The decrypted variables of a project, to set on the processes of its
+    /// attempts
+    pub async fn project_env(
+        &self,
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<ExecutionEnv, SecretsError> {
+        let mut vars = HashMap::new();
+        for env_var in ProjectEnvVar::find_by_project_id(pool, project_id).await? {
+            let value = self.decrypt(&env_var.encrypted_value)?;
+            vars.insert(env_var.name, value);
+        }
+        Ok(ExecutionEnv::new(vars))
+    }
+
+    fn entry(&self, env_var: ProjectEnvVar) -> Result<ProjectEnvVarEntry, SecretsError> {
+        let value = if env_var.is_secret {
+            None
+        } else {
+            Some(self.decrypt(&env_var.encrypted_value)?)
+        };
+        Ok(ProjectEnvVarEntry { env_var, value })
+    }
+}
+
+/// Letters, digits and underscores, not starting with a digit
+pub fn is_valid_env_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn write_key_file(path: &Path, key_bytes: &[u8]) -> Result<(), std::io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    file.write_all(key_bytes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_values_and_reuses_the_key_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets.key");
+        let service = SecretsService::from_key_file(&path).unwrap();
+        let sealed = service.encrypt("hunter2").unwrap();
+        assert_ne!(&sealed[NONCE_LEN..], b"hunter2");
+
+        let reloaded = SecretsService::from_key_file(&path).unwrap();
+        assert_eq!(reloaded.decrypt(&sealed).unwrap(), "hunter2");
+    }
+
+    #[test]
+    fn rejects_tampered_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = SecretsService::from_key_file(&dir.path().join("secrets.key")).unwrap();
+        let mut sealed = service.encrypt("value").unwrap();
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+        assert!(service.decrypt(&sealed).is_err());
+    }
+
+    #[test]
+    fn validates_env_var_names() {
+        assert!(is_valid_env_var_name("API_KEY"));
+        assert!(is_valid_env_var_name("_private1"));
+        assert!(!is_valid_env_var_name("1ST"));
+        assert!(!is_valid_env_var_name("WITH-DASH"));
+        assert!(!is_valid_env_var_name(""));
+    }
+}
@@ -0,0 +1,127 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// Lines of test output quoted back to the agent
+const FEEDBACK_OUTPUT_LINES: usize = 80;
+
+lazy_static! {
+    static ref COUNT_RE: Regex =
+        Regex::new(r"(?i)\b(\d+)\s+(passed|passing|failed|failing|skipped|ignored|pending)\b")
+            .unwrap();
+    static ref GO_RESULT_RE: Regex = Regex::new(r"^\s*--- (PASS|FAIL|SKIP):").unwrap();
+}
+
+/// Pass and fail counts read from test output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TestCounts {
+    pub passed: Option<i64>,
+    pub failed: Option<i64>,
+    pub skipped: Option<i64>,
+}
+
+impl TestCounts {
+    fn add(count: &mut Option<i64>, n: i64) {
+        *count = Some(count.unwrap_or(0) + n);
+    }
+}
+
+/// Read the counts from the summary lines of common test runners: cargo test,
+/// jest, vitest, pytest, mocha and go test. Summaries are added up, so that
+/// runners printing one per test binary, like cargo, are counted in full.
+pub fn parse_test_counts(output: &str) -> TestCounts {
+    let mut counts = TestCounts::default();
+    for line in output.lines() {
+        let trimmed = line.trim_start();
+        // jest and vitest also count test files, which would count twice
+        if trimmed.starts_with("Test Suites:") || trimmed.starts_with("Test Files") {
+            continue;
+        }
+        if let Some(result) = GO_RESULT_RE.captures(line) {
+            match &result[1] {
+                "PASS" => TestCounts::add(&mut counts.passed, 1),
+                "FAIL" => TestCounts::add(&mut counts.failed, 1),
+                _ => TestCounts::add(&mut counts.skipped, 1),
+            }
+            continue;
+        }
+        for count in COUNT_RE.captures_iter(line) {
+            let Ok(n) = count[1].parse::<i64>() else {
+                continue;
+            };
+            match count[2].to_lowercase().as_str() {
+                "passed" | "passing" => TestCounts::add(&mut counts.passed, n),
+                "failed" | "failing" => TestCounts::add(&mut counts.failed, n),
+                _ => TestCounts::add(&mut counts.skipped, n),
+            }
+        }
+    }
+    counts
+}
+
+/// Follow-up prompt asking the agent to fix the tests its turn broke, with
+/// the end of the test output
+pub fn feedback_prompt(counts: &TestCounts, output: &str) -> String {
+    let summary = match counts.failed {
+        Some(failed) => format!("{failed} failed"),
+        None => "the test command failed".to_string(),
+    };
+    let lines: Vec<&str> = output.lines().collect();
+    let tail = lines[lines.len().saturating_sub(FEEDBACK_OUTPUT_LINES)..].join("\n");
+    format!(
+        "The project's tests were run after your last changes and did not pass ({summary}). \
+         Fix the failures, then stop.\n\nEnd of the test output:\n```\n{tail}\n```"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_common_runner_summaries() {
+        let cargo = "test result: ok. 12 passed; 0 failed; 1 ignored; 0 measured\n\
+                     test result: FAILED. 3 passed; 2 failed; 0 ignored; 0 measured";
+        assert_eq!(
+            parse_test_counts(cargo),
+            TestCounts {
+                passed: Some(15),
+                failed: Some(2),
+                skipped: Some(1),
+            }
+        );
+
+        let jest = "Test Suites: 1 failed, 4 passed, 5 total\n\
+                    Tests:       2 failed, 1 skipped, 30 passed, 33 total";
+        assert_eq!(
+            parse_test_counts(jest),
+            TestCounts {
+                passed: Some(30),
+                failed: Some(2),
+                skipped: Some(1),
+            }
+        );
+
+        let pytest = "==== 1 failed, 10 passed, 2 skipped in 0.52s ====";
+        assert_eq!(
+            parse_test_counts(pytest),
+            TestCounts {
+                passed: Some(10),
+                failed: Some(1),
+                skipped: Some(2),
+            }
+        );
+
+        let go =
+            "=== RUN   TestA\n--- PASS: TestA (0.00s)\n=== RUN   TestB\n--- FAIL: TestB (0.01s)";
+        assert_eq!(
+            parse_test_counts(go),
+            TestCounts {
+                passed: Some(1),
+                failed: Some(1),
+                skipped: None,
+            }
+        );
+
+        assert_eq!(parse_test_counts("Build finished"), TestCounts::default());
+    }
+}
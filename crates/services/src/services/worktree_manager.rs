@@ -38,6 +38,24 @@ pub enum WorktreeError {
     BranchNotFound(String),
     #[error("Repository error: {0}")]
     Repository(String),
+    #[error("Branch {0} is already checked out at {1}")]
+    BranchCheckedOut(String, String),
+}
+
+/// How a worktree looks to git and on disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeHealth {
+    pub path_exists: bool,
+    /// Git has live metadata for a worktree at this path
+    pub registered: bool,
+    /// Branch checked out in the worktree, when registered
+    pub branch: Option<String>,
+}
+
+impl WorktreeHealth {
+    pub fn is_healthy(&self) -> bool {
+        self.path_exists && self.registered
+    }
 }
 
 pub struct WorktreeManager;
@@ -83,16 +101,8 @@ impl WorktreeManager {
     ) -> Result<(), WorktreeError> {
         let path_str = worktree_path.to_string_lossy().to_string();
 
-        // Get or create a lock for this specific worktree path
-        let lock = {
-            let mut locks = WORKTREE_CREATION_LOCKS.lock().unwrap();
-            locks
-                .entry(path_str.clone())
-                .or_insert_with(|| Arc::new(tokio::sync::Mutex::new(())))
-                .clone()
-        };
-
         // Acquire the lock for this specific worktree path
+        let lock = Self::path_lock(worktree_path);
         let _guard = lock.lock().await;
 
         // Check if worktree already exists and is properly set up
@@ -106,6 +116,175 @@ impl WorktreeManager {
         Self::recreate_worktree_internal(repo_path, branch_name, worktree_path).await
     }
 
+    /// Get or create the lock serializing operations on one worktree path
+    fn path_lock(worktree_path: &Path) -> Arc<tokio::sync::Mutex<()>> {
+        let mut locks = WORKTREE_CREATION_LOCKS.lock().unwrap();
+        locks
+            .entry(worktree_path.to_string_lossy().to_string())
+            .or_insert_with(|| Arc::new(tokio::sync::Mutex::new(())))
+            .clone()
+    }
+
+    /// Inspect a worktree without changing anything (non-blocking)
+    pub async fn inspect_worktree(
+        repo_path: &Path,
+        worktree_path: &Path,
+    ) -> Result<WorktreeHealth, WorktreeError> {
+        let repo_path = repo_path.to_path_buf();
+        let worktree_path = worktree_path.to_path_buf();
+        tokio::task::spawn_blocking(move || Self::worktree_health(&repo_path, &worktree_path))
+            .await
+            .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))?
+    }
+
+    fn worktree_health(
+        repo_path: &Path,
+        worktree_path: &Path,
+    ) -> Result<WorktreeHealth, WorktreeError> {
+        let entry = GitCli::new()
+            .list_worktrees(repo_path)
+            .map_err(|e| WorktreeError::GitCli(e.to_string()))?
+            .into_iter()
+            .find(|entry| Self::same_path(Path::new(&entry.path), worktree_path));
+        Ok(WorktreeHealth {
+            path_exists: worktree_path.exists(),
+            registered: entry.as_ref().is_some_and(|entry| !entry.prunable),
+            branch: entry.and_then(|entry| entry.branch),
+        })
+    }
+
+    /// Git reports canonical paths, which differ from ours behind symlinks
+    /// such as the macOS temp directory
+    fn same_path(a: &Path, b: &Path) -> bool {
+        a == b
+            || matches!(
+                (a.canonicalize(), b.canonicalize()),
+                (Ok(a), Ok(b)) if a == b
+            )
+    }
+
+    /// Bring a worktree back in line with git after a crash or a manual move:
+    /// stale metadata is pruned, an existing directory is re-linked with
+    /// `git worktree repair`, and a worktree that is still unusable is
+    /// recreated from its branch. Commits on the branch survive; files in a
+    /// directory git does not recognize as the worktree do not.
+    pub async fn repair_worktree(
+        repo_path: &Path,
+        branch_name: &str,
+        worktree_path: &Path,
+    ) -> Result<WorktreeHealth, WorktreeError> {
+        let lock = Self::path_lock(worktree_path);
+        let _guard = lock.lock().await;
+
+        let repo_path_owned = repo_path.to_path_buf();
+        let worktree_path_owned = worktree_path.to_path_buf();
+        let branch_name_owned = branch_name.to_string();
+        let health =
+            tokio::task::spawn_blocking(move || -> Result<WorktreeHealth, WorktreeError> {
+                let git = GitCli::new();
+                if let Err(e) = git.worktree_prune(&repo_path_owned) {
+                    debug!("git worktree prune non-fatal error: {}", e);
+                }
+                if worktree_path_owned.exists()
+                    && let Err(e) = git.worktree_repair(&repo_path_owned, &worktree_path_owned)
+                {
+                    debug!("git worktree repair non-fatal error: {}", e);
+                }
+
+                let health = Self::worktree_health(&repo_path_owned, &worktree_path_owned)?;
+                if !health.registered {
+                    // A branch can only be checked out once, so a checkout that
+                    // survived elsewhere would make recreating the worktree fail
+                    let checkout = git
+                        .list_worktrees(&repo_path_owned)
+                        .map_err(|e| WorktreeError::GitCli(e.to_string()))?
+                        .into_iter()
+                        .find(|entry| {
+                            !entry.prunable
+                                && entry.branch.as_deref() == Some(branch_name_owned.as_str())
+                        });
+                    if let Some(checkout) = checkout {
+                        return Err(WorktreeError::BranchCheckedOut(
+                            branch_name_owned,
+                            checkout.path,
+                        ));
+                    }
+                }
+                Ok(health)
+            })
+            .await
+            .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))??;
+
+        if health.is_healthy() {
+            info!("Worktree at {} is linked", worktree_path.display());
+            return Ok(health);
+        }
+
+        info!(
+            "Worktree at {} could not be re-linked, recreating it",
+            worktree_path.display()
+        );
+        Self::recreate_worktree_internal(repo_path, branch_name, worktree_path).await?;
+        Self::inspect_worktree(repo_path, worktree_path).await
+    }
+
+    /// Move a worktree to `new_path` with `git worktree move`. A worktree
+    /// whose directory is gone is recreated at the new path from its branch.
+    pub async fn relocate_worktree(
+        repo_path: &Path,
+        branch_name: &str,
+        worktree_path: &Path,
+        new_path: &Path,
+    ) -> Result<(), WorktreeError> {
+        if new_path.exists() {
+            return Err(WorktreeError::InvalidPath(format!(
+                "{} already exists",
+                new_path.display()
+            )));
+        }
+
+        // Lock both paths, in a stable order so opposite moves cannot deadlock
+        let (first, second) = if worktree_path <= new_path {
+            (worktree_path, new_path)
+        } else {
+            (new_path, worktree_path)
+        };
+        let first_lock = Self::path_lock(first);
+        let second_lock = Self::path_lock(second);
+        let _first_guard = first_lock.lock().await;
+        let _second_guard = second_lock.lock().await;
+
+        let health = Self::inspect_worktree(repo_path, worktree_path).await?;
+        if !health.is_healthy() {
+            info!(
+                "Worktree at {} is not usable, creating it at {} instead of moving it",
+                worktree_path.display(),
+                new_path.display()
+            );
+            return Self::recreate_worktree_internal(repo_path, branch_name, new_path).await;
+        }
+
+        let repo_path = repo_path.to_path_buf();
+        let worktree_path = worktree_path.to_path_buf();
+        let new_path = new_path.to_path_buf();
+        tokio::task::spawn_blocking(move || -> Result<(), WorktreeError> {
+            if let Some(parent) = new_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            GitCli::new()
+                .worktree_move(&repo_path, &worktree_path, &new_path)
+                .map_err(|e| WorktreeError::GitCli(e.to_string()))?;
+            info!(
+                "Moved worktree from {} to {}",
+                worktree_path.display(),
+                new_path.display()
+            );
+            Ok(())
+        })
+        .await
+        .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))?
+    }
+
     /// Internal worktree recreation function (always recreates)
     async fn recreate_worktree_internal(
         repo_path: &Path,
@@ -183,7 +362,9 @@ impl WorktreeManager {
             // Try to find the worktree - if it exists and is valid, we're good
             match repo.find_worktree(worktree_name) {
                 Ok(_) => Ok(true),
-                Err(_) => Ok(false),
+                // Worktrees keep their metadata name when moved or re-linked,
+                // so look for one at this path under another name
+                Err(_) => Ok(Self::worktree_health(&repo_path, &worktree_path)?.registered),
             }
         })
         .await
@@ -382,14 +563,7 @@ impl WorktreeManager {
         let path_str = worktree_path.to_string_lossy().to_string();
 
         // Get the same lock to ensure we don't interfere with creation
-        let lock = {
-            let mut locks = WORKTREE_CREATION_LOCKS.lock().unwrap();
-            locks
-                .entry(path_str.clone())
-                .or_insert_with(|| Arc::new(tokio::sync::Mutex::new(())))
-                .clone()
-        };
-
+        let lock = Self::path_lock(worktree_path);
         let _guard = lock.lock().await;
 
         if let Some(worktree_name) = worktree_path.file_name().and_then(|n| n.to_str()) {
@@ -477,4 +651,67 @@ impl WorktreeManager {
     pub fn get_worktree_base_dir() -> std::path::PathBuf {
         utils::path::get_vibe_kanban_temp_dir().join("worktrees")
     }
+
+    /// Directories in the worktree base directory (non-blocking)
+    pub async fn worktree_dirs() -> Result<Vec<PathBuf>, WorktreeError> {
+        let base_dir = Self::get_worktree_base_dir();
+        tokio::task::spawn_blocking(move || {
+            if !base_dir.exists() {
+                return Ok(Vec::new());
+            }
+            let mut dirs = Vec::new();
+            for entry in std::fs::read_dir(&base_dir)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    dirs.push(path);
+                }
+            }
+            dirs.sort();
+            Ok(dirs)
+        })
+        .await
+        .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))?
+    }
+
+    /// Size on disk of every directory in the worktree base directory,
+    /// largest first (non-blocking)
+    pub async fn disk_usage() -> Result<Vec<(PathBuf, u64)>, WorktreeError> {
+        let base_dir = Self::get_worktree_base_dir();
+        tokio::task::spawn_blocking(move || {
+            if !base_dir.exists() {
+                return Ok(Vec::new());
+            }
+            let mut usage = Vec::new();
+            for entry in std::fs::read_dir(&base_dir)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    let size = Self::directory_size(&path);
+                    usage.push((path, size));
+                }
+            }
+            usage.sort_by(|a, b| b.1.cmp(&a.1));
+            Ok(usage)
+        })
+        .await
+        .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))?
+    }
+
+    /// Total size of the files below `path`; symlinks are not followed and
+    /// unreadable entries are skipped
+    fn directory_size(path: &Path) -> u64 {
+        let Ok(entries) = std::fs::read_dir(path) else {
+            return 0;
+        };
+        entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let metadata = entry.path().symlink_metadata().ok()?;
+                Some(if metadata.is_dir() {
+                    Self::directory_size(&entry.path())
+                } else {
+                    metadata.len()
+                })
+            })
+            .sum()
+    }
 }
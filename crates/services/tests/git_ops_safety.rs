@@ -291,6 +291,81 @@ fn push_with_token_reports_non_fast_forward() {
     }
 }
 
+#[test]
+fn force_push_with_lease_only_overwrites_expected_remote_state() {
+    let temp_dir = TempDir::new().unwrap();
+    let remote_path = temp_dir.path().join("remote.git");
+    Repository::init_bare(&remote_path).expect("init bare remote");
+    let remote_url = remote_path.to_str().expect("remote path str");
+
+    let seed_path = temp_dir.path().join("seed");
+    let service = GitService::new();
+    service
+        .initialize_repo_with_main_branch(&seed_path)
+        .expect("init seed repo");
+    let seed_repo = Repository::open(&seed_path).expect("open seed repo");
+    configure_user(&seed_repo);
+    seed_repo.remote("origin", remote_url).expect("add remote");
+    push_ref(&seed_repo, "refs/heads/main", "refs/heads/main");
+    Repository::open_bare(&remote_path)
+        .expect("open bare remote")
+        .set_head("refs/heads/main")
+        .expect("set remote HEAD");
+
+    let local_path = temp_dir.path().join("local");
+    let local_repo = Repository::clone(remote_url, &local_path).expect("clone local");
+    configure_user(&local_repo);
+    checkout_branch(&local_repo, "main");
+    let seen_remote_oid = local_repo
+        .refname_to_id("refs/remotes/origin/main")
+        .expect("tracking ref")
+        .to_string();
+
+    // Someone else pushes after our last fetch
+    let updater_path = temp_dir.path().join("updater");
+    let updater_repo = Repository::clone(remote_url, &updater_path).expect("clone updater");
+    configure_user(&updater_repo);
+    checkout_branch(&updater_repo, "main");
+    write_file(&updater_path, "file.txt", "upstream change\n");
+    commit_all(&updater_repo, "upstream commit");
+    push_ref(&updater_repo, "refs/heads/main", "refs/heads/main");
+    let upstream_oid = updater_repo.head().unwrap().target().unwrap().to_string();
+
+    write_file(&local_path, "file.txt", "local change\n");
+    commit_all(&local_repo, "local commit");
+
+    let git_cli = GitCli::new();
+    let stale = git_cli.force_push_with_lease_token(
+        &local_path,
+        remote_url,
+        "main",
+        Some(&seen_remote_oid),
+        "dummy-token",
+    );
+    assert!(stale.is_err(), "stale lease must not overwrite remote");
+    let remote_head = Repository::open_bare(&remote_path)
+        .unwrap()
+        .refname_to_id("refs/heads/main")
+        .unwrap()
+        .to_string();
+    assert_eq!(remote_head, upstream_oid);
+
+    git_cli
+        .force_push_with_lease_token(
+            &local_path,
+            remote_url,
+            "main",
+            Some(&upstream_oid),
+            "dummy-token",
+        )
+        .expect("lease matching the remote succeeds");
+    let remote_head = Repository::open_bare(&remote_path)
+        .unwrap()
+        .refname_to_id("refs/heads/main")
+        .unwrap();
+    assert_eq!(remote_head, local_repo.head().unwrap().target().unwrap());
+}
+
 #[test]
 fn fetch_with_token_missing_ref_returns_error() {
     let temp_dir = TempDir::new().unwrap();
@@ -1410,3 +1485,39 @@ fn merge_base_ahead_of_task_should_error() {
         "Merge should error when base branch is ahead of task branch"
     );
 }
+
+#[test]
+fn worktree_repair_and_move_follow_a_moved_worktree() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let git = GitCli::new();
+    let feature_entry = |git: &GitCli| {
+        git.list_worktrees(&repo_path)
+            .unwrap()
+            .into_iter()
+            .find(|w| w.branch.as_deref() == Some("feature"))
+            .expect("feature worktree listed")
+    };
+
+    // Moved by hand: git still points at the old location
+    let moved = td.path().join("wt-moved");
+    fs::rename(&worktree_path, &moved).unwrap();
+    assert!(feature_entry(&git).prunable);
+
+    git.worktree_repair(&repo_path, &moved).unwrap();
+    let entry = feature_entry(&git);
+    assert!(!entry.prunable);
+    assert_eq!(
+        Path::new(&entry.path).canonicalize().unwrap(),
+        moved.canonicalize().unwrap()
+    );
+
+    let relocated = td.path().join("wt-relocated");
+    git.worktree_move(&repo_path, &moved, &relocated).unwrap();
+    assert!(!moved.exists());
+    assert!(relocated.join("feat.txt").exists());
+    assert_eq!(
+        Path::new(&feature_entry(&git).path).canonicalize().unwrap(),
+        relocated.canonicalize().unwrap()
+    );
+}
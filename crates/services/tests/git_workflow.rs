@@ -4,8 +4,10 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use db::models::project_merge_settings::MergeStrategy;
 use services::services::{
-    git::{DiffTarget, GitService},
+    config::{CommitSigningConfig, SigningFormat},
+    git::{DiffSelection, DiffTarget, GitService, GitServiceError},
     github_service::{GitHubRepoInfo, GitHubServiceError},
 };
 use tempfile::TempDir;
@@ -197,6 +199,262 @@ fn diff_added_binary_file_has_no_content() {
     assert!(bin.new_content.is_none());
 }
 
+#[test]
+fn diff_files_are_paged_and_hunks_read_per_file() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    let base: String = (1..=20).map(|i| format!("line {i}\n")).collect();
+    write_file(&repo_path, "a.txt", &base);
+    let _ = s.commit(&repo_path, "base").unwrap();
+
+    s.create_branch(&repo_path, "feature").unwrap();
+    s.checkout_branch(&repo_path, "feature").unwrap();
+    write_file(
+        &repo_path,
+        "a.txt",
+        &base.replace("line 10\n", "line ten\n"),
+    );
+    write_file(&repo_path, "b.txt", "new\n");
+    let mut f = fs::File::create(repo_path.join("c.bin")).unwrap();
+    f.write_all(&[0u8, 1, 2, 3]).unwrap();
+    let _ = s.commit(&repo_path, "change").unwrap();
+
+    let target = || DiffTarget::Branch {
+        repo_path: Path::new(&repo_path),
+        branch_name: "feature",
+        base_branch: "main",
+    };
+
+    // Listing reads no contents
+    let files = s.list_diff_files(target()).unwrap();
+    let paths: Vec<_> = files.iter().map(GitService::diff_file_path).collect();
+    assert_eq!(paths, ["a.txt", "b.txt", "c.bin"]);
+    assert!(files.iter().all(|f| f.additions.is_none() && !f.binary));
+
+    let first = s.get_diff_file_page(target(), 0, 2).unwrap();
+    assert_eq!(first.total, 3);
+    assert_eq!(first.next_offset, Some(2));
+    assert_eq!(
+        (first.files[0].additions, first.files[0].deletions),
+        (Some(1), Some(1))
+    );
+    assert_eq!(first.files[1].additions, Some(1));
+
+    let second = s.get_diff_file_page(target(), 2, 2).unwrap();
+    assert_eq!(second.next_offset, None);
+    assert!(second.files[0].binary);
+    assert_eq!(second.files[0].additions, None);
+
+    let hunks = s
+        .get_diff_file_hunks(target(), "a.txt", 3)
+        .unwrap()
+        .unwrap();
+    assert_eq!(hunks.hunks.len(), 1);
+    assert!(hunks.hunks[0].starts_with("@@ -7,7 +7,7 @@"));
+    assert!(hunks.hunks[0].contains("-line 10\n+line ten\n"));
+    let binary = s
+        .get_diff_file_hunks(target(), "c.bin", 3)
+        .unwrap()
+        .unwrap();
+    assert!(binary.file.binary && binary.hunks.is_empty());
+    assert!(
+        s.get_diff_file_hunks(target(), "missing.txt", 3)
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn commit_selected_changes_keeps_only_the_selection() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    let base: String = (1..=20).map(|i| format!("line {i}\n")).collect();
+    write_file(&repo_path, "a.txt", &base);
+    write_file(&repo_path, "c.txt", "c\n");
+    let _ = s.commit(&repo_path, "base").unwrap();
+
+    s.create_branch(&repo_path, "feature").unwrap();
+    s.checkout_branch(&repo_path, "feature").unwrap();
+    let changed = base
+        .replace("line 2\n", "line two\n")
+        .replace("line 18\n", "line eighteen\n");
+    write_file(&repo_path, "a.txt", &changed);
+    write_file(&repo_path, "c.txt", "changed\n");
+    let _ = s.commit(&repo_path, "agent changes").unwrap();
+    write_file(&repo_path, "b.txt", "new\n");
+
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let branch = || DiffTarget::Branch {
+        repo_path: Path::new(&repo_path),
+        branch_name: "feature",
+        base_branch: "main",
+    };
+
+    // First hunk of a.txt and all of b.txt; c.txt and the rest of a.txt stay
+    // in the worktree
+    let selection = vec![
+        DiffSelection {
+            path: "a.txt".to_string(),
+            hunks: Some(vec![0]),
+        },
+        DiffSelection {
+            path: "b.txt".to_string(),
+            hunks: None,
+        },
+    ];
+    s.commit_selected_changes(&repo_path, &base_commit, &selection, "keep some", false)
+        .unwrap();
+    let paths: Vec<_> = s
+        .list_diff_files(branch())
+        .unwrap()
+        .iter()
+        .map(|f| GitService::diff_file_path(f).to_string())
+        .collect();
+    assert_eq!(paths, ["a.txt", "b.txt"]);
+    let a = s
+        .get_diff_file_hunks(branch(), "a.txt", 3)
+        .unwrap()
+        .unwrap();
+    assert_eq!(a.hunks.len(), 1);
+    assert!(a.hunks[0].contains("+line two\n"));
+    assert_eq!(
+        fs::read_to_string(repo_path.join("a.txt")).unwrap(),
+        changed
+    );
+    assert_eq!(
+        fs::read_to_string(repo_path.join("c.txt")).unwrap(),
+        "changed\n"
+    );
+
+    // discard_rest won't drop b.txt, which the branch now has committed
+    let selection = vec![DiffSelection {
+        path: "a.txt".to_string(),
+        hunks: None,
+    }];
+    assert!(matches!(
+        s.commit_selected_changes(&repo_path, &base_commit, &selection, "keep a", true),
+        Err(GitServiceError::InvalidSelection(_))
+    ));
+    assert_eq!(
+        fs::read_to_string(repo_path.join("c.txt")).unwrap(),
+        "changed\n"
+    );
+
+    // Everything left over is dropped with discard_rest once the committed
+    // files are kept
+    let selection = vec![
+        DiffSelection {
+            path: "a.txt".to_string(),
+            hunks: None,
+        },
+        DiffSelection {
+            path: "b.txt".to_string(),
+            hunks: None,
+        },
+    ];
+    s.commit_selected_changes(&repo_path, &base_commit, &selection, "keep a and b", true)
+        .unwrap();
+    assert!(s.is_worktree_clean(&repo_path).unwrap());
+    assert_eq!(fs::read_to_string(repo_path.join("c.txt")).unwrap(), "c\n");
+    assert_eq!(
+        fs::read_to_string(repo_path.join("b.txt")).unwrap(),
+        "new\n"
+    );
+    assert_eq!(
+        fs::read_to_string(repo_path.join("a.txt")).unwrap(),
+        changed
+    );
+
+    let missing = vec![DiffSelection {
+        path: "missing.txt".to_string(),
+        hunks: None,
+    }];
+    assert!(matches!(
+        s.commit_selected_changes(&repo_path, &base_commit, &missing, "nothing", false),
+        Err(GitServiceError::InvalidSelection(_))
+    ));
+}
+
+#[test]
+fn discard_worktree_changes_keeps_task_images_and_attachments() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    write_file(&repo_path, "a.txt", "a\n");
+    let _ = s.commit(&repo_path, "base").unwrap();
+    let base = s.get_head_info(&repo_path).unwrap().oid;
+
+    write_file(&repo_path, "a.txt", "changed\n");
+    write_file(&repo_path, "stray.txt", "stray\n");
+    write_file(&repo_path, ".vibe-images/shot.png", "png");
+    write_file(&repo_path, ".vibe-attachments/spec.pdf", "pdf");
+
+    assert!(s.discard_worktree_changes(&repo_path, &base).unwrap());
+    assert_eq!(fs::read_to_string(repo_path.join("a.txt")).unwrap(), "a\n");
+    assert!(!repo_path.join("stray.txt").exists());
+    assert!(repo_path.join(".vibe-images/shot.png").exists());
+    assert!(repo_path.join(".vibe-attachments/spec.pdf").exists());
+}
+
+#[test]
+fn commit_selected_changes_rejects_invalid_hunk_selections() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    let base: String = (1..=20).map(|i| format!("line {i}\n")).collect();
+    write_file(&repo_path, "a.txt", &base);
+    write_file(&repo_path, "target.txt", "target\n");
+    #[cfg(unix)]
+    std::os::unix::fs::symlink("target.txt", repo_path.join("link")).unwrap();
+    let _ = s.commit(&repo_path, "base").unwrap();
+
+    s.create_branch(&repo_path, "feature").unwrap();
+    s.checkout_branch(&repo_path, "feature").unwrap();
+    let changed = base
+        .replace("line 2\n", "line two\n")
+        .replace("line 18\n", "line eighteen\n");
+    write_file(&repo_path, "a.txt", &changed);
+    #[cfg(unix)]
+    {
+        fs::remove_file(repo_path.join("link")).unwrap();
+        std::os::unix::fs::symlink("a.txt", repo_path.join("link")).unwrap();
+    }
+    let base_commit = s.get_base_commit(&repo_path, "feature", "main").unwrap();
+    let head = s.get_head_info(&repo_path).unwrap().oid;
+
+    let invalid = [
+        // Nothing of the file selected
+        DiffSelection {
+            path: "a.txt".to_string(),
+            hunks: Some(vec![]),
+        },
+        // a.txt has two hunks
+        DiffSelection {
+            path: "a.txt".to_string(),
+            hunks: Some(vec![0, 2]),
+        },
+        // Symlinks have no hunks
+        DiffSelection {
+            path: "link".to_string(),
+            hunks: Some(vec![0]),
+        },
+    ];
+    for selection in invalid {
+        assert!(matches!(
+            s.commit_selected_changes(&repo_path, &base_commit, &[selection], "bad", false),
+            Err(GitServiceError::InvalidSelection(_))
+        ));
+    }
+    // Nothing was committed and the worktree is untouched
+    assert_eq!(s.get_head_info(&repo_path).unwrap().oid, head);
+    assert_eq!(
+        fs::read_to_string(repo_path.join("a.txt")).unwrap(),
+        changed
+    );
+}
+
 #[test]
 fn initialize_and_default_branch_and_head_info() {
     let td = TempDir::new().unwrap();
@@ -564,6 +822,18 @@ fn convert_to_https_url_handles_common_git_forms() {
 
     let converted = svc.convert_to_https_url("https://github.com/owner/repo/");
     assert_eq!(converted, "https://github.com/owner/repo.git");
+
+    let gitlab_ssh_url = "git@gitlab.example.com:group/subgroup/repo.git";
+    assert_eq!(
+        svc.convert_to_https_url(gitlab_ssh_url),
+        "https://gitlab.example.com/group/subgroup/repo.git"
+    );
+
+    let ssh_url_with_port = "ssh://git@gitlab.example.com:2222/group/repo.git";
+    assert_eq!(
+        svc.convert_to_https_url(ssh_url_with_port),
+        "https://gitlab.example.com/group/repo.git"
+    );
 }
 
 #[test]
@@ -644,3 +914,123 @@ fn squash_merge_libgit2_sets_author_without_user() {
         assert_eq!(email.as_deref(), Some("noreply@vibekanban.com"));
     }
 }
+
+#[test]
+fn signs_cli_and_libgit2_commits_when_configured() {
+    let td = TempDir::new().unwrap();
+    let key_path = td.path().join("signing_key");
+    let keygen = std::process::Command::new("ssh-keygen")
+        .args(["-q", "-t", "ed25519", "-N", ""])
+        .arg("-f")
+        .arg(&key_path)
+        .status();
+    if !keygen.is_ok_and(|status| status.success()) {
+        // ssh-keygen isn't available
+        return;
+    }
+
+    let repo_path = init_repo_main(&td);
+    let worktree_path = td.path().join("wt_signed");
+    let s = GitService::new();
+    s.create_branch(&repo_path, "feature").unwrap();
+    s.add_worktree(&repo_path, &worktree_path, "feature", false)
+        .unwrap();
+    write_file(&worktree_path, "a.txt", "a\n");
+    s.commit(&worktree_path, "unsigned").unwrap();
+
+    s.set_commit_signing(Some(CommitSigningConfig {
+        format: SigningFormat::Ssh,
+        key: key_path.to_string_lossy().into_owned(),
+        program: None,
+        require_signed: true,
+    }));
+    write_file(&worktree_path, "b.txt", "b\n");
+    s.commit(&worktree_path, "signed").unwrap();
+
+    let signatures = s
+        .get_commit_signatures(&worktree_path, "feature", "main")
+        .unwrap();
+    let flags: Vec<_> = signatures
+        .iter()
+        .map(|commit| (commit.subject.as_str(), commit.signed))
+        .collect();
+    assert_eq!(flags, vec![("signed", true), ("unsigned", false)]);
+
+    // With main not checked out, the squash merge is created through libgit2
+    s.create_branch(&repo_path, "dev").unwrap();
+    s.checkout_branch(&repo_path, "dev").unwrap();
+    let merge_sha = s
+        .merge_changes(&repo_path, &worktree_path, "feature", "main", "squash")
+        .unwrap();
+    let repo = git2::Repository::open(&repo_path).unwrap();
+    let merge_oid = git2::Oid::from_str(&merge_sha).unwrap();
+    assert!(repo.extract_signature(&merge_oid, None).is_ok());
+    assert_eq!(s.get_branch_oid(&repo_path, "main").unwrap(), merge_sha);
+}
+
+#[test]
+fn merges_with_a_merge_commit_or_a_fast_forward() {
+    fn repo_with_feature_commits(td: &TempDir, name: &str) -> (PathBuf, PathBuf) {
+        let root = td.path().join(name);
+        fs::create_dir_all(&root).unwrap();
+        let repo_path = root.join("repo");
+        let s = GitService::new();
+        s.initialize_repo_with_main_branch(&repo_path).unwrap();
+        s.configure_user(&repo_path, "Test User", "test@example.com")
+            .unwrap();
+        let worktree_path = root.join("wt");
+        s.create_branch(&repo_path, "feature").unwrap();
+        s.add_worktree(&repo_path, &worktree_path, "feature", false)
+            .unwrap();
+        write_file(&worktree_path, "a.txt", "a\n");
+        s.commit(&worktree_path, "first").unwrap();
+        write_file(&worktree_path, "b.txt", "b\n");
+        s.commit(&worktree_path, "second").unwrap();
+        (repo_path, worktree_path)
+    }
+
+    let td = TempDir::new().unwrap();
+    let s = GitService::new();
+
+    // main not checked out: the merge commit is created through libgit2
+    let (repo_path, worktree_path) = repo_with_feature_commits(&td, "merge_commit");
+    s.create_branch(&repo_path, "dev").unwrap();
+    s.checkout_branch(&repo_path, "dev").unwrap();
+    let feature_tip = s.get_branch_oid(&repo_path, "feature").unwrap();
+    let merge_sha = s
+        .merge_changes_with_strategy(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            MergeStrategy::MergeCommit,
+            "Merge feature",
+        )
+        .unwrap();
+    let repo = git2::Repository::open(&repo_path).unwrap();
+    let merge_commit = repo
+        .find_commit(git2::Oid::from_str(&merge_sha).unwrap())
+        .unwrap();
+    assert_eq!(merge_commit.parent_count(), 2);
+    assert_eq!(merge_commit.parent_id(1).unwrap().to_string(), feature_tip);
+    assert_eq!(merge_commit.message(), Some("Merge feature"));
+    assert_eq!(s.get_branch_oid(&repo_path, "main").unwrap(), merge_sha);
+
+    // main checked out: the git CLI fast-forwards it to the attempt's commits
+    let (repo_path, worktree_path) = repo_with_feature_commits(&td, "rebase");
+    s.checkout_branch(&repo_path, "main").unwrap();
+    let feature_tip = s.get_branch_oid(&repo_path, "feature").unwrap();
+    let merge_sha = s
+        .merge_changes_with_strategy(
+            &repo_path,
+            &worktree_path,
+            "feature",
+            "main",
+            MergeStrategy::Rebase,
+            "unused",
+        )
+        .unwrap();
+    assert_eq!(merge_sha, feature_tip);
+    assert_eq!(s.get_branch_oid(&repo_path, "main").unwrap(), feature_tip);
+    assert!(repo_path.join("b.txt").exists());
+}
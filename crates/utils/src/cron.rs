@@ -0,0 +1,255 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+
+/// A standard five-field cron expression (`minute hour day-of-month month day-of-week`),
+/// evaluated in UTC.
+///
+/// Each field accepts `*`, single values, ranges (`1-5`), steps (`*/15`, `0-30/10`) and
+/// comma separated lists of those. Day-of-week is `0-7` where both `0` and `7` are Sunday.
+/// The `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly` shorthands are also accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// Day-of-month and day-of-week restrictions are OR-ed when both are set
+    dom_restricted: bool,
+    dow_restricted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronParseError(String);
+
+impl fmt::Display for CronParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid cron expression: {}", self.0)
+    }
+}
+
+impl std::error::Error for CronParseError {}
+
+/// Searching further than this without a match means the expression never fires
+/// (e.g. `0 0 31 2 *`).
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 5;
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self, CronParseError> {
+        let expression = expression.trim();
+        let expanded = match expression {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            other => other,
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, dom, month, dow] = fields[..] else {
+            return Err(CronParseError(format!(
+                "expected 5 fields, found {}",
+                fields.len()
+            )));
+        };
+
+        let mut days_of_week = parse_field(dow, 0, 7, "day-of-week")?;
+        // Fold 7 (Sunday) onto 0
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, "minute")?,
+            hours: parse_field(hour, 0, 23, "hour")?,
+            days_of_month: parse_field(dom, 1, 31, "day-of-month")?,
+            months: parse_field(month, 1, 12, "month")?,
+            days_of_week,
+            dom_restricted: dom != "*",
+            dow_restricted: dow != "*",
+        })
+    }
+
+    /// The first time strictly after `after` (at minute resolution) matching the schedule,
+    /// or None if the expression can never match.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = start + Duration::days(MAX_LOOKAHEAD_DAYS);
+        let mut t = start;
+
+        while t < limit {
+            if !bit(self.months, t.month()) {
+                t = start_of_next_month(t)?;
+                continue;
+            }
+            if !self.day_matches(t) {
+                t = start_of_day(t)? + Duration::days(1);
+                continue;
+            }
+            if !bit(self.hours, t.hour()) {
+                t = t.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if !bit(self.minutes, t.minute()) {
+                t += Duration::minutes(1);
+                continue;
+            }
+            return Some(t);
+        }
+        None
+    }
+
+    fn day_matches(&self, t: DateTime<Utc>) -> bool {
+        let dom = bit(self.days_of_month, t.day());
+        let dow = bit(self.days_of_week, t.weekday().num_days_from_sunday());
+        match (self.dom_restricted, self.dow_restricted) {
+            (true, true) => dom || dow,
+            (true, false) => dom,
+            (false, true) => dow,
+            (false, false) => true,
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = CronParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+fn bit(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+fn start_of_day(t: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    t.with_hour(0)?.with_minute(0)
+}
+
+fn start_of_next_month(t: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let (year, month) = if t.month() == 12 {
+        (t.year() + 1, 1)
+    } else {
+        (t.year(), t.month() + 1)
+    };
+    Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()
+}
+
+fn parse_field(field: &str, min: u32, max: u32, name: &str) -> Result<u64, CronParseError> {
+    let invalid = |detail: &str| CronParseError(format!("{name} field '{field}' {detail}"));
+    let number = |s: &str| -> Result<u32, CronParseError> {
+        let value: u32 = s.parse().map_err(|_| invalid("is not a number"))?;
+        if value < min || value > max {
+            return Err(invalid(&format!("must be between {min} and {max}")));
+        }
+        Ok(value)
+    };
+
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| invalid("has an invalid step"))?;
+                if step == 0 {
+                    return Err(invalid("has a zero step"));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            let (start, end) = (number(start)?, number(end)?);
+            if start > end {
+                return Err(invalid("has a reversed range"));
+            }
+            (start, end)
+        } else {
+            let value = number(range)?;
+            // `5/15` means "from 5 to the end, every 15"
+            if part.contains('/') {
+                (value, max)
+            } else {
+                (value, value)
+            }
+        };
+
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn next_run_for_common_expressions() {
+        let now = at(2025, 10, 15, 13, 7);
+
+        let nightly = CronSchedule::parse("0 2 * * *").unwrap();
+        assert_eq!(nightly.next_after(now), Some(at(2025, 10, 16, 2, 0)));
+
+        let quarter_hourly = CronSchedule::parse("*/15 * * * *").unwrap();
+        assert_eq!(
+            quarter_hourly.next_after(now),
+            Some(at(2025, 10, 15, 13, 15))
+        );
+
+        // 2025-10-15 is a Wednesday; next Monday is the 20th
+        let weekly = CronSchedule::parse("30 9 * * 1").unwrap();
+        assert_eq!(weekly.next_after(now), Some(at(2025, 10, 20, 9, 30)));
+
+        let monthly = CronSchedule::parse("@monthly").unwrap();
+        assert_eq!(monthly.next_after(now), Some(at(2025, 11, 1, 0, 0)));
+
+        let new_year = CronSchedule::parse("0 0 1 1 *").unwrap();
+        assert_eq!(new_year.next_after(now), Some(at(2026, 1, 1, 0, 0)));
+    }
+
+    #[test]
+    fn next_run_is_strictly_after() {
+        let schedule = CronSchedule::parse("0 2 * * *").unwrap();
+        let now = at(2025, 10, 15, 2, 0);
+        assert_eq!(schedule.next_after(now), Some(at(2025, 10, 16, 2, 0)));
+    }
+
+    #[test]
+    fn sunday_as_seven_and_or_day_matching() {
+        let sunday = CronSchedule::parse("0 0 * * 7").unwrap();
+        assert_eq!(
+            sunday.next_after(at(2025, 10, 15, 0, 0)),
+            Some(at(2025, 10, 19, 0, 0))
+        );
+
+        // Restricted day-of-month and day-of-week match either
+        let either = CronSchedule::parse("0 0 1 * 5").unwrap();
+        assert_eq!(
+            either.next_after(at(2025, 10, 15, 0, 0)),
+            Some(at(2025, 10, 17, 0, 0))
+        );
+    }
+
+    #[test]
+    fn impossible_and_invalid_expressions() {
+        let never = CronSchedule::parse("0 0 31 2 *").unwrap();
+        assert_eq!(never.next_after(at(2025, 1, 1, 0, 0)), None);
+
+        assert!(CronSchedule::parse("* * * *").is_err());
+        assert!(CronSchedule::parse("60 * * * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        assert!(CronSchedule::parse("5-1 * * * *").is_err());
+        assert!(CronSchedule::parse("a * * * *").is_err());
+    }
+}
@@ -29,6 +29,43 @@ pub struct Diff {
     pub deletions: Option<usize>,
 }
 
+// A changed file listed without its contents, for diffs too large to send whole
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffFile {
+    pub change: DiffChangeKind,
+    pub old_path: Option<String>,
+    pub new_path: Option<String>,
+    /// True when either side is binary; such files have no hunks
+    pub binary: bool,
+    /// True when either side is over the inline size limit; such files have no hunks
+    pub too_large: bool,
+    /// Line counts, known once the file's contents have been compared
+    pub additions: Option<usize>,
+    pub deletions: Option<usize>,
+}
+
+/// One page of a diff's files, ordered by path
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffFilePage {
+    pub files: Vec<DiffFile>,
+    /// Number of changed files in the whole diff
+    pub total: usize,
+    /// Offset of the next page, if there is one
+    pub next_offset: Option<usize>,
+}
+
+/// Hunks of one changed file
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffFileHunks {
+    pub file: DiffFile,
+    /// Unified diff hunks with their `@@` headers; empty for binary and
+    /// oversized files
+    pub hunks: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
 #[serde(rename_all = "camelCase")]
@@ -91,6 +128,61 @@ pub fn create_unified_diff(file_path: &str, old: &str, new: &str) -> String {
     out
 }
 
+/// Hunks of the unified diff between two texts, each with its `@@` header and
+/// up to `context_lines` unchanged lines around the changes.
+pub fn create_unified_diff_hunks(old: &str, new: &str, context_lines: usize) -> Vec<String> {
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(context_lines)
+        .iter_hunks()
+        .map(|hunk| hunk.to_string())
+        .collect()
+}
+
+/// Number of hunks [`create_unified_diff_hunks`] splits the diff into
+pub fn count_unified_diff_hunks(old: &str, new: &str, context_lines: usize) -> usize {
+    TextDiff::from_lines(old, new)
+        .grouped_ops(context_lines)
+        .into_iter()
+        .filter(|ops| !ops.is_empty())
+        .count()
+}
+
+/// `old` with only the changes of the selected hunks applied. Hunks are
+/// numbered as by [`create_unified_diff_hunks`] with the same `context_lines`.
+pub fn apply_selected_hunks(
+    old: &str,
+    new: &str,
+    context_lines: usize,
+    selected: &[usize],
+) -> String {
+    let diff = TextDiff::from_lines(old, new);
+    let old_lines = diff.old_slices();
+    let new_lines = diff.new_slices();
+
+    let mut out = String::with_capacity(old.len().max(new.len()));
+    let mut cursor = 0;
+    let groups = diff
+        .grouped_ops(context_lines)
+        .into_iter()
+        .filter(|ops| !ops.is_empty());
+    for (index, ops) in groups.enumerate() {
+        if !selected.contains(&index) {
+            continue;
+        }
+        let (Some(first), Some(last)) = (ops.first(), ops.last()) else {
+            continue;
+        };
+        let old_range = first.old_range().start..last.old_range().end;
+        let new_range = first.new_range().start..last.new_range().end;
+        out.extend(old_lines[cursor..old_range.start].iter().copied());
+        out.extend(new_lines[new_range].iter().copied());
+        cursor = old_range.end;
+    }
+    out.extend(old_lines[cursor..].iter().copied());
+    out
+}
+
 /// Compute addition/deletion counts between two text snapshots.
 pub fn compute_line_change_counts(old: &str, new: &str) -> (usize, usize) {
     let old = ensure_newline(old);
@@ -5,12 +5,14 @@ use directories::ProjectDirs;
 pub mod approvals;
 pub mod assets;
 pub mod browser;
+pub mod cron;
 pub mod diff;
 pub mod git;
 pub mod log_msg;
 pub mod msg_store;
 pub mod path;
 pub mod port_file;
+pub mod recurrence;
 pub mod response;
 pub mod sentry;
 pub mod shell;
@@ -0,0 +1,357 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Utc, Weekday};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A subset of iCalendar (RFC 5545) recurrence rules, evaluated in UTC, e.g.
+/// `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH`.
+///
+/// Supported parts are `FREQ` (`DAILY`, `WEEKLY`, `MONTHLY` or `YEARLY`), `INTERVAL`,
+/// `BYDAY` (plain weekday codes, for daily and weekly rules), `BYMONTHDAY` (a single day,
+/// for monthly rules), `COUNT` and `UNTIL` (`YYYYMMDD` or `YYYYMMDDTHHMMSSZ`). An `RRULE:`
+/// prefix is ignored. Weeks start on Monday.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    frequency: Frequency,
+    interval: u32,
+    /// One bit per weekday, Monday first; 0 when unrestricted
+    days_of_week: u8,
+    day_of_month: Option<u32>,
+    count: Option<u32>,
+    until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceParseError(String);
+
+impl fmt::Display for RecurrenceParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid recurrence rule: {}", self.0)
+    }
+}
+
+impl std::error::Error for RecurrenceParseError {}
+
+impl RecurrenceRule {
+    pub fn parse(rule: &str) -> Result<Self, RecurrenceParseError> {
+        let rule = rule.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut days_of_week = 0u8;
+        let mut day_of_month = None;
+        let mut count = None;
+        let mut until = None;
+        for part in rule.split(';').filter(|part| !part.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| RecurrenceParseError(format!("'{part}' is not a KEY=VALUE pair")))?;
+            let value = value.to_ascii_uppercase();
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        other => {
+                            return Err(RecurrenceParseError(format!(
+                                "unsupported frequency '{other}'"
+                            )));
+                        }
+                    })
+                }
+                "INTERVAL" => interval = parse_positive("INTERVAL", &value)?,
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        days_of_week |= 1 << parse_weekday(day)?.num_days_from_monday();
+                    }
+                }
+                "BYMONTHDAY" => {
+                    let day = parse_positive("BYMONTHDAY", &value)?;
+                    if day > 31 {
+                        return Err(RecurrenceParseError(
+                            "BYMONTHDAY must be between 1 and 31".to_string(),
+                        ));
+                    }
+                    day_of_month = Some(day);
+                }
+                "COUNT" => count = Some(parse_positive("COUNT", &value)?),
+                "UNTIL" => until = Some(parse_until(&value)?),
+                other => {
+                    return Err(RecurrenceParseError(format!("unsupported part '{other}'")));
+                }
+            }
+        }
+
+        let frequency =
+            frequency.ok_or_else(|| RecurrenceParseError("FREQ is required".to_string()))?;
+        if days_of_week != 0 && !matches!(frequency, Frequency::Daily | Frequency::Weekly) {
+            return Err(RecurrenceParseError(
+                "BYDAY is only supported for daily and weekly rules".to_string(),
+            ));
+        }
+        if day_of_month.is_some() && frequency != Frequency::Monthly {
+            return Err(RecurrenceParseError(
+                "BYMONTHDAY is only supported for monthly rules".to_string(),
+            ));
+        }
+        if count.is_some() && until.is_some() {
+            return Err(RecurrenceParseError(
+                "COUNT and UNTIL cannot be combined".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            frequency,
+            interval,
+            days_of_week,
+            day_of_month,
+            count,
+            until,
+        })
+    }
+
+    /// Total number of occurrences, when limited by `COUNT`
+    pub fn count(&self) -> Option<u32> {
+        self.count
+    }
+
+    /// The first occurrence strictly after `previous`, at the same time of day, or None
+    /// once the rule has ended.
+    pub fn next_after(&self, previous: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let next = match self.frequency {
+            Frequency::Daily | Frequency::Weekly if self.days_of_week != 0 => {
+                self.next_matching_day(previous)?
+            }
+            Frequency::Daily => previous + Duration::days(i64::from(self.interval)),
+            Frequency::Weekly => previous + Duration::weeks(i64::from(self.interval)),
+            Frequency::Monthly => self.next_in_month(previous)?,
+            Frequency::Yearly => self.next_in_year(previous)?,
+        };
+        match self.until {
+            Some(until) if next > until => None,
+            _ => Some(next),
+        }
+    }
+
+    /// Next day on one of the `BYDAY` weekdays, in a day or week that is a multiple of
+    /// the interval away from `previous`
+    fn next_matching_day(&self, previous: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let interval = i64::from(self.interval);
+        let start = previous.date_naive();
+        let mut t = previous;
+        for _ in 0..7 * (interval + 1) {
+            t += Duration::days(1);
+            let periods = match self.frequency {
+                Frequency::Daily => (t.date_naive() - start).num_days(),
+                _ => (start_of_week(t.date_naive()) - start_of_week(start)).num_weeks(),
+            };
+            let weekday_bit = 1 << t.weekday().num_days_from_monday();
+            if periods % interval == 0 && self.days_of_week & weekday_bit != 0 {
+                return Some(t);
+            }
+        }
+        None
+    }
+
+    /// Next date on the rule's day of the month (or the day of `previous`) every
+    /// interval months, skipping months too short to have it
+    fn next_in_month(&self, previous: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let day = self.day_of_month.unwrap_or(previous.day());
+        let first_month = i64::from(previous.year()) * 12 + i64::from(previous.month0());
+        // Months repeat every year, so a day that exists at all shows up within 12 steps
+        for step in 0..=12 {
+            let month = first_month + step * i64::from(self.interval);
+            let year = i32::try_from(month.div_euclid(12)).ok()?;
+            let Some(date) = NaiveDate::from_ymd_opt(year, month.rem_euclid(12) as u32 + 1, day)
+            else {
+                continue;
+            };
+            let candidate = date.and_time(previous.time()).and_utc();
+            if candidate > previous {
+                return Some(candidate);
+            }
+        }
+        None
+    }
+
+    /// Same month and day every interval years, skipping years without the day
+    /// (February 29th)
+    fn next_in_year(&self, previous: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        for step in 1..=8 {
+            let year = i64::from(previous.year()) + step * i64::from(self.interval);
+            let year = i32::try_from(year).ok()?;
+            if let Some(date) = NaiveDate::from_ymd_opt(year, previous.month(), previous.day()) {
+                return Some(date.and_time(previous.time()).and_utc());
+            }
+        }
+        None
+    }
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = RecurrenceParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+fn start_of_week(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+fn parse_positive(name: &str, value: &str) -> Result<u32, RecurrenceParseError> {
+    match value.parse::<u32>() {
+        Ok(number) if number > 0 => Ok(number),
+        _ => Err(RecurrenceParseError(format!(
+            "{name} must be a positive number"
+        ))),
+    }
+}
+
+fn parse_weekday(day: &str) -> Result<Weekday, RecurrenceParseError> {
+    Ok(match day {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        other => {
+            return Err(RecurrenceParseError(format!(
+                "unsupported BYDAY value '{other}'"
+            )));
+        }
+    })
+}
+
+/// A date includes occurrences during the whole day
+fn parse_until(value: &str) -> Result<DateTime<Utc>, RecurrenceParseError> {
+    let invalid = || RecurrenceParseError(format!("UNTIL '{value}' is not a valid UTC date"));
+    if let Some(date_time) = value.strip_suffix('Z') {
+        return NaiveDateTime::parse_from_str(date_time, "%Y%m%dT%H%M%S")
+            .map(|date_time| date_time.and_utc())
+            .map_err(|_| invalid());
+    }
+    NaiveDate::parse_from_str(value, "%Y%m%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(23, 59, 59))
+        .map(|date_time| date_time.and_utc())
+        .ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn next_occurrence_for_simple_rules() {
+        let previous = at(2025, 10, 15, 9, 30);
+
+        let daily = RecurrenceRule::parse("FREQ=DAILY").unwrap();
+        assert_eq!(daily.next_after(previous), Some(at(2025, 10, 16, 9, 30)));
+
+        let fortnightly = RecurrenceRule::parse("RRULE:FREQ=WEEKLY;INTERVAL=2").unwrap();
+        assert_eq!(
+            fortnightly.next_after(previous),
+            Some(at(2025, 10, 29, 9, 30))
+        );
+
+        let yearly = RecurrenceRule::parse("freq=yearly").unwrap();
+        assert_eq!(yearly.next_after(previous), Some(at(2026, 10, 15, 9, 30)));
+    }
+
+    #[test]
+    fn weekly_on_weekdays() {
+        // 2025-10-15 is a Wednesday
+        let rule = RecurrenceRule::parse("FREQ=WEEKLY;BYDAY=MO,TH").unwrap();
+        assert_eq!(
+            rule.next_after(at(2025, 10, 15, 9, 0)),
+            Some(at(2025, 10, 16, 9, 0))
+        );
+        assert_eq!(
+            rule.next_after(at(2025, 10, 16, 9, 0)),
+            Some(at(2025, 10, 20, 9, 0))
+        );
+
+        // Every other week: the Monday after the Thursday is skipped
+        let biweekly = RecurrenceRule::parse("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH").unwrap();
+        assert_eq!(
+            biweekly.next_after(at(2025, 10, 16, 9, 0)),
+            Some(at(2025, 10, 27, 9, 0))
+        );
+
+        let weekdays = RecurrenceRule::parse("FREQ=DAILY;BYDAY=MO,TU,WE,TH,FR").unwrap();
+        assert_eq!(
+            weekdays.next_after(at(2025, 10, 17, 9, 0)),
+            Some(at(2025, 10, 20, 9, 0))
+        );
+    }
+
+    #[test]
+    fn monthly_skips_short_months() {
+        let rule = RecurrenceRule::parse("FREQ=MONTHLY").unwrap();
+        assert_eq!(
+            rule.next_after(at(2025, 1, 31, 8, 0)),
+            Some(at(2025, 3, 31, 8, 0))
+        );
+
+        let mid_month = RecurrenceRule::parse("FREQ=MONTHLY;BYMONTHDAY=15").unwrap();
+        assert_eq!(
+            mid_month.next_after(at(2025, 10, 5, 8, 0)),
+            Some(at(2025, 10, 15, 8, 0))
+        );
+        assert_eq!(
+            mid_month.next_after(at(2025, 12, 15, 8, 0)),
+            Some(at(2026, 1, 15, 8, 0))
+        );
+
+        let leap_day = RecurrenceRule::parse("FREQ=YEARLY").unwrap();
+        assert_eq!(
+            leap_day.next_after(at(2024, 2, 29, 0, 0)),
+            Some(at(2028, 2, 29, 0, 0))
+        );
+    }
+
+    #[test]
+    fn until_ends_the_rule() {
+        let rule = RecurrenceRule::parse("FREQ=DAILY;UNTIL=20251016").unwrap();
+        assert_eq!(
+            rule.next_after(at(2025, 10, 15, 9, 0)),
+            Some(at(2025, 10, 16, 9, 0))
+        );
+        assert_eq!(rule.next_after(at(2025, 10, 16, 9, 0)), None);
+
+        let rule = RecurrenceRule::parse("FREQ=DAILY;COUNT=3").unwrap();
+        assert_eq!(rule.count(), Some(3));
+    }
+
+    #[test]
+    fn invalid_rules() {
+        assert!(RecurrenceRule::parse("").is_err());
+        assert!(RecurrenceRule::parse("INTERVAL=2").is_err());
+        assert!(RecurrenceRule::parse("FREQ=HOURLY").is_err());
+        assert!(RecurrenceRule::parse("FREQ=DAILY;INTERVAL=0").is_err());
+        assert!(RecurrenceRule::parse("FREQ=MONTHLY;BYDAY=MO").is_err());
+        assert!(RecurrenceRule::parse("FREQ=WEEKLY;BYDAY=1MO").is_err());
+        assert!(RecurrenceRule::parse("FREQ=DAILY;COUNT=2;UNTIL=20251016").is_err());
+        assert!(RecurrenceRule::parse("FREQ=DAILY;UNTIL=2025-10-16").is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use regex::Regex;
 use uuid::Uuid;
 
@@ -22,3 +24,62 @@ pub fn short_uuid(u: &Uuid) -> String {
     let full = u.simple().to_string();
     full.chars().take(4).collect() // grab the first 4 chars
 }
+
+fn placeholder_regex() -> Regex {
+    Regex::new(r"\{\{\s*([A-Za-z0-9_.-]+)\s*\}\}").unwrap()
+}
+
+/// Names of the `{{placeholder}}`s in `text`, in order of first appearance
+pub fn template_placeholders(text: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for captures in placeholder_regex().captures_iter(text) {
+        let name = captures[1].to_string();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Replace every `{{placeholder}}` in `text` with its value from `variables`.
+/// Returns the names of the placeholders without a value as the error.
+pub fn fill_template(
+    text: &str,
+    variables: &HashMap<String, String>,
+) -> Result<String, Vec<String>> {
+    let missing: Vec<String> = template_placeholders(text)
+        .into_iter()
+        .filter(|name| !variables.contains_key(name))
+        .collect();
+    if !missing.is_empty() {
+        return Err(missing);
+    }
+    Ok(placeholder_regex()
+        .replace_all(text, |captures: &regex::Captures| {
+            variables[&captures[1]].clone()
+        })
+        .into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_placeholders_with_and_without_spaces() {
+        let variables = HashMap::from([
+            ("dep".to_string(), "serde".to_string()),
+            ("version".to_string(), "1.0.228".to_string()),
+        ]);
+        assert_eq!(
+            fill_template("Bump {{dep}} to {{ version }} ({{dep}})", &variables).unwrap(),
+            "Bump serde to 1.0.228 (serde)"
+        );
+    }
+
+    #[test]
+    fn reports_missing_placeholders_once() {
+        let err = fill_template("{{a}} {{b}} {{a}}", &HashMap::new()).unwrap_err();
+        assert_eq!(err, vec!["a".to_string(), "b".to_string()]);
+    }
+}
@@ -18,7 +18,10 @@ type ExecutorType =
   | 'CURSOR_AGENT'
   | 'COPILOT'
   | 'OPENCODE'
-  | 'QWEN_CODE';
+  | 'QWEN_CODE'
+  | 'AIDER'
+  | 'OPENHANDS'
+  | 'CUSTOM';
 
 interface ExecutorConfigFormProps {
   executor: ExecutorType;
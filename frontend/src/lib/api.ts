@@ -3,10 +3,16 @@
 import {
   ApprovalStatus,
   ApiResponse,
+  ApproveAttemptPlan,
+  AttemptForks,
+  AttemptPlan,
   BranchStatus,
   CheckTokenResponse,
   Config,
   CommitInfo,
+  ComparisonGroup,
+  ComparisonGroupSummary,
+  CreateComparisonGroup,
   CreateFollowUpAttempt,
   CreateGitHubPrRequest,
   CreateTask,
@@ -14,12 +20,19 @@ import {
   CreateTaskAttemptBody,
   CreateTag,
   DeviceFlowStartResponse,
+  DenyApprovalRequest,
   DevicePollStatus,
   DirectoryListResponse,
   DirectoryEntry,
   EditorType,
+  ExecutionFallback,
   ExecutionProcess,
+  ExecutionProcessEvent,
+  ExecutionResumption,
+  TurnTestRun,
+  ForkTaskAttemptRequest,
   GitBranch,
+  PickComparisonWinner,
   Project,
   CreateProject,
   RepositoryInfo,
@@ -43,12 +56,33 @@ import {
   DraftResponse,
   UpdateFollowUpDraftRequest,
   GitOperationError,
+  ApprovalRequest,
   ApprovalResponse,
   RebaseTaskAttemptRequest,
   ChangeTargetBranchRequest,
   ChangeTargetBranchResponse,
   RunAgentSetupRequest,
   RunAgentSetupResponse,
+  BaseCodingAgent,
+  CodingAgent,
+  ProviderLimitStatus,
+  ProposedTask,
+  AcceptProposedTask,
+  PullRequestInfo,
+  AttemptWorktree,
+  RelocateWorktreeRequest,
+  AttemptConflicts,
+  ResolveConflictsRequest,
+  PrReviewComment,
+  SendReviewCommentsRequest,
+  AttemptBaseStatus,
+  CommitSignature,
+  MergeTaskAttemptRequest,
+  CiCheck,
+  DiffFilePage,
+  DiffFileHunks,
+  PartialCommitRequest,
+  PartialCommitResponse,
 } from 'shared/types';
 
 // Re-export types for convenience
@@ -238,6 +272,11 @@ export const projectsApi = {
     return handleApiResponse<void>(response);
   },
 
+  getProposedTasks: async (id: string): Promise<ProposedTask[]> => {
+    const response = await makeRequest(`/api/projects/${id}/proposed-tasks`);
+    return handleApiResponse<ProposedTask[]>(response);
+  },
+
   openEditor: async (
     id: string,
     editorType?: EditorType
@@ -272,6 +311,11 @@ export const projectsApi = {
     );
     return handleApiResponse<SearchResult[]>(response);
   },
+
+  getStaleAttempts: async (id: string): Promise<AttemptBaseStatus[]> => {
+    const response = await makeRequest(`/api/projects/${id}/stale-attempts`);
+    return handleApiResponse<AttemptBaseStatus[]>(response);
+  },
 };
 
 // Task Management APIs
@@ -347,6 +391,128 @@ export const attemptsApi = {
     return handleApiResponse<TaskAttempt>(response);
   },
 
+  getPlan: async (attemptId: string): Promise<AttemptPlan | null> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}/plan`);
+    return handleApiResponse<AttemptPlan | null>(response);
+  },
+
+  approvePlan: async (
+    attemptId: string,
+    data: ApproveAttemptPlan
+  ): Promise<ExecutionProcess> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/plan/approve`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ExecutionProcess>(response);
+  },
+
+  fork: async (
+    attemptId: string,
+    data: ForkTaskAttemptRequest
+  ): Promise<TaskAttempt> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}/fork`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<TaskAttempt>(response);
+  },
+
+  getForks: async (attemptId: string): Promise<AttemptForks> => {
+    const response = await makeRequest(`/api/task-attempts/${attemptId}/forks`);
+    return handleApiResponse<AttemptForks>(response);
+  },
+
+  getFallbacks: async (attemptId: string): Promise<ExecutionFallback[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/fallbacks`
+    );
+    return handleApiResponse<ExecutionFallback[]>(response);
+  },
+
+  getResumptions: async (attemptId: string): Promise<ExecutionResumption[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/resumptions`
+    );
+    return handleApiResponse<ExecutionResumption[]>(response);
+  },
+
+  getTurnTests: async (attemptId: string): Promise<TurnTestRun[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/turn-tests`
+    );
+    return handleApiResponse<TurnTestRun[]>(response);
+  },
+
+  getProposedTasks: async (attemptId: string): Promise<ProposedTask[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/proposed-tasks`
+    );
+    return handleApiResponse<ProposedTask[]>(response);
+  },
+
+  acceptProposedTask: async (
+    attemptId: string,
+    proposedTaskId: string,
+    data: AcceptProposedTask = {}
+  ): Promise<Task> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/proposed-tasks/${proposedTaskId}/accept`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<Task>(response);
+  },
+
+  dismissProposedTask: async (
+    attemptId: string,
+    proposedTaskId: string
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/proposed-tasks/${proposedTaskId}/dismiss`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+
+  startComparison: async (
+    data: CreateComparisonGroup
+  ): Promise<ComparisonGroupSummary> => {
+    const response = await makeRequest(`/api/task-attempts/comparison-groups`, {
+      method: 'POST',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ComparisonGroupSummary>(response);
+  },
+
+  getComparison: async (groupId: string): Promise<ComparisonGroupSummary> => {
+    const response = await makeRequest(
+      `/api/task-attempts/comparison-groups/${groupId}`
+    );
+    return handleApiResponse<ComparisonGroupSummary>(response);
+  },
+
+  pickComparisonWinner: async (
+    groupId: string,
+    data: PickComparisonWinner
+  ): Promise<ComparisonGroup> => {
+    const response = await makeRequest(
+      `/api/task-attempts/comparison-groups/${groupId}/winner`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ComparisonGroup>(response);
+  },
+
   stop: async (attemptId: string): Promise<void> => {
     const response = await makeRequest(`/api/task-attempts/${attemptId}/stop`, {
       method: 'POST',
@@ -502,11 +668,90 @@ export const attemptsApi = {
     return handleApiResponse<BranchStatus>(response);
   },
 
-  merge: async (attemptId: string): Promise<void> => {
+  getBaseStatus: async (
+    attemptId: string
+  ): Promise<AttemptBaseStatus | null> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/base-status`
+    );
+    return handleApiResponse<AttemptBaseStatus | null>(response);
+  },
+
+  getCiChecks: async (attemptId: string): Promise<CiCheck[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/ci-checks`
+    );
+    return handleApiResponse<CiCheck[]>(response);
+  },
+
+  refreshCiChecks: async (attemptId: string): Promise<CiCheck[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/ci-checks/refresh`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<CiCheck[]>(response);
+  },
+
+  getDiffFiles: async (
+    attemptId: string,
+    offset = 0,
+    limit?: number
+  ): Promise<DiffFilePage> => {
+    const params = new URLSearchParams({ offset: String(offset) });
+    if (limit !== undefined) {
+      params.set('limit', String(limit));
+    }
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/diff/files?${params.toString()}`
+    );
+    return handleApiResponse<DiffFilePage>(response);
+  },
+
+  getDiffFile: async (
+    attemptId: string,
+    path: string
+  ): Promise<DiffFileHunks> => {
+    const params = new URLSearchParams({ path });
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/diff/file?${params.toString()}`
+    );
+    return handleApiResponse<DiffFileHunks>(response);
+  },
+
+  commitSelectedChanges: async (
+    attemptId: string,
+    data: PartialCommitRequest
+  ): Promise<PartialCommitResponse> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/partial-commit`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<PartialCommitResponse>(response);
+  },
+
+  getCommitSignatures: async (
+    attemptId: string
+  ): Promise<CommitSignature[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/commit-signatures`
+    );
+    return handleApiResponse<CommitSignature[]>(response);
+  },
+
+  merge: async (
+    attemptId: string,
+    data: MergeTaskAttemptRequest = {}
+  ): Promise<void> => {
     const response = await makeRequest(
       `/api/task-attempts/${attemptId}/merge`,
       {
         method: 'POST',
+        body: JSON.stringify(data),
       }
     );
     return handleApiResponse<void>(response);
@@ -547,6 +792,51 @@ export const attemptsApi = {
     return handleApiResponse<ChangeTargetBranchResponse>(response);
   },
 
+  getConflicts: async (attemptId: string): Promise<AttemptConflicts> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/conflicts`
+    );
+    return handleApiResponse<AttemptConflicts>(response);
+  },
+
+  resolveConflicts: async (
+    attemptId: string,
+    data: ResolveConflictsRequest
+  ): Promise<AttemptConflicts> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/conflicts/resolve`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<AttemptConflicts>(response);
+  },
+
+  continueConflicts: async (
+    attemptId: string
+  ): Promise<Result<string, GitOperationError>> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/conflicts/continue`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponseAsResult<string, GitOperationError>(response);
+  },
+
+  resolveConflictsWithAgent: async (
+    attemptId: string
+  ): Promise<ExecutionProcess> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/conflicts/agent`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<ExecutionProcess>(response);
+  },
+
   abortConflicts: async (attemptId: string): Promise<void> => {
     const response = await makeRequest(
       `/api/task-attempts/${attemptId}/conflicts/abort`,
@@ -568,6 +858,94 @@ export const attemptsApi = {
     return handleApiResponseAsResult<string, GitHubServiceError>(response);
   },
 
+  mergePR: async (
+    attemptId: string,
+    data: MergeTaskAttemptRequest = {}
+  ): Promise<PullRequestInfo> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pr/merge`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<PullRequestInfo>(response);
+  },
+
+  markPRReady: async (attemptId: string): Promise<PullRequestInfo> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pr/ready`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<PullRequestInfo>(response);
+  },
+
+  getReviewComments: async (attemptId: string): Promise<PrReviewComment[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pr/comments`
+    );
+    return handleApiResponse<PrReviewComment[]>(response);
+  },
+
+  refreshReviewComments: async (
+    attemptId: string
+  ): Promise<PrReviewComment[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pr/comments/refresh`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<PrReviewComment[]>(response);
+  },
+
+  sendReviewComments: async (
+    attemptId: string,
+    data: SendReviewCommentsRequest
+  ): Promise<ExecutionProcess> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/pr/comments/follow-up`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<ExecutionProcess>(response);
+  },
+
+  getWorktree: async (attemptId: string): Promise<AttemptWorktree[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/worktree`
+    );
+    return handleApiResponse<AttemptWorktree[]>(response);
+  },
+
+  repairWorktree: async (attemptId: string): Promise<AttemptWorktree[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/worktree/repair`,
+      {
+        method: 'POST',
+      }
+    );
+    return handleApiResponse<AttemptWorktree[]>(response);
+  },
+
+  relocateWorktree: async (
+    attemptId: string,
+    data: RelocateWorktreeRequest
+  ): Promise<AttemptWorktree[]> => {
+    const response = await makeRequest(
+      `/api/task-attempts/${attemptId}/worktree/relocate`,
+      {
+        method: 'POST',
+        body: JSON.stringify(data),
+      }
+    );
+    return handleApiResponse<AttemptWorktree[]>(response);
+  },
+
   startDevServer: async (attemptId: string): Promise<void> => {
     const response = await makeRequest(
       `/api/task-attempts/${attemptId}/start-dev-server`,
@@ -633,6 +1011,17 @@ export const executionProcessesApi = {
     );
     return handleApiResponse<void>(response);
   },
+
+  getEvents: async (
+    processId: string,
+    afterSeq?: number
+  ): Promise<ExecutionProcessEvent[]> => {
+    const query = afterSeq !== undefined ? `?after_seq=${afterSeq}` : '';
+    const response = await makeRequest(
+      `/api/execution-processes/${processId}/events${query}`
+    );
+    return handleApiResponse<ExecutionProcessEvent[]>(response);
+  },
 };
 
 // File System APIs
@@ -786,6 +1175,51 @@ export const profilesApi = {
     });
     return handleApiResponse<string>(response);
   },
+  getProfile: async (
+    executor: BaseCodingAgent,
+    variant: string
+  ): Promise<CodingAgent> => {
+    const response = await makeRequest(
+      `/api/profiles/${executor}/${encodeURIComponent(variant)}`
+    );
+    return handleApiResponse<CodingAgent>(response);
+  },
+  upsertProfile: async (
+    executor: BaseCodingAgent,
+    variant: string,
+    config: CodingAgent
+  ): Promise<CodingAgent> => {
+    const response = await makeRequest(
+      `/api/profiles/${executor}/${encodeURIComponent(variant)}`,
+      {
+        method: 'PUT',
+        body: JSON.stringify(config),
+      }
+    );
+    return handleApiResponse<CodingAgent>(response);
+  },
+  deleteProfile: async (
+    executor: BaseCodingAgent,
+    variant: string
+  ): Promise<void> => {
+    const response = await makeRequest(
+      `/api/profiles/${executor}/${encodeURIComponent(variant)}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
+  getRateLimits: async (): Promise<ProviderLimitStatus[]> => {
+    const response = await makeRequest('/api/rate-limits');
+    return handleApiResponse<ProviderLimitStatus[]>(response);
+  },
+  clearRateLimit: async (executor: BaseCodingAgent): Promise<void> => {
+    const response = await makeRequest(`/api/rate-limits/${executor}`, {
+      method: 'DELETE',
+    });
+    return handleApiResponse<void>(response);
+  },
 };
 
 // Images API
@@ -867,4 +1301,27 @@ export const approvalsApi = {
 
     return handleApiResponse<ApprovalStatus>(res);
   },
+
+  getPending: async (attemptId: string): Promise<ApprovalRequest[]> => {
+    const res = await makeRequest(`/api/task-attempts/${attemptId}/approvals`);
+    return handleApiResponse<ApprovalRequest[]>(res);
+  },
+
+  approve: async (approvalId: string): Promise<ApprovalStatus> => {
+    const res = await makeRequest(`/api/approvals/${approvalId}/approve`, {
+      method: 'POST',
+    });
+    return handleApiResponse<ApprovalStatus>(res);
+  },
+
+  deny: async (
+    approvalId: string,
+    payload: DenyApprovalRequest
+  ): Promise<ApprovalStatus> => {
+    const res = await makeRequest(`/api/approvals/${approvalId}/deny`, {
+      method: 'POST',
+      body: JSON.stringify(payload),
+    });
+    return handleApiResponse<ApprovalStatus>(res);
+  },
 };
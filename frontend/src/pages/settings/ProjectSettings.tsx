@@ -214,6 +214,8 @@ export function ProjectSettings() {
         dev_script: draft.dev_script.trim() || null,
         cleanup_script: draft.cleanup_script.trim() || null,
         copy_files: draft.copy_files.trim() || null,
+        execution_timeout_minutes:
+          selectedProject.execution_timeout_minutes ?? undefined,
       };
 
       updateProject.mutate({
@@ -8,31 +8,86 @@ export type DirectoryEntry = { name: string, path: string, is_directory: boolean
 
 export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
 
-export type Project = { id: string, name: string, git_repo_path: string, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, created_at: Date, updated_at: Date, };
+export type Project = { id: string, name: string, git_repo_path: string, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, 
+/**
+ * Cancel executions running longer than this many minutes
+ */
+execution_timeout_minutes: bigint | null, 
+/**
+ * Push attempt branches to the remote after every coding agent turn
+ */
+auto_push_branches: boolean, 
+/**
+ * Workspace the project is grouped under
+ */
+workspace_id: string | null, 
+/**
+ * Archived projects are read-only and run nothing
+ */
+archived_at: Date | null, created_at: Date, updated_at: Date, };
 
 export type CreateProject = { name: string, git_repo_path: string, use_existing_repo: boolean, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, };
 
-export type UpdateProject = { name: string | null, git_repo_path: string | null, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, };
+export type UpdateProject = { name: string | null, git_repo_path: string | null, setup_script: string | null, dev_script: string | null, cleanup_script: string | null, copy_files: string | null, execution_timeout_minutes?: bigint, 
+/**
+ * Left unchanged when omitted
+ */
+auto_push_branches?: boolean, };
+
+export type DuplicateProject = { 
+/**
+ * Defaults to the source project's name with a " (copy)" suffix
+ */
+name: string | null, 
+/**
+ * Existing git repository the copy points at
+ */
+git_repo_path: string, 
+/**
+ * Also copy the tasks that are not done or cancelled, as to do
+ */
+include_open_tasks: boolean, };
 
 export type SearchResult = { path: string, is_file: boolean, match_type: SearchMatchType, };
 
 export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
 
+export type ProjectRepository = { id: string, project_id: string, 
+/**
+ * Short unique name within the project, used in the worktree directory name
+ */
+name: string, git_repo_path: string, 
+/**
+ * Branch the attempt branch is created from in this repository
+ */
+base_branch: string, created_at: string, updated_at: string, };
+
+export type CreateProjectRepository = { name: string, git_repo_path: string, base_branch: string, };
+
 export type ExecutorAction = { typ: ExecutorActionType, next_action: ExecutorAction | null, };
 
 export type McpConfig = { servers: { [key in string]?: JsonValue }, servers_path: Array<string>, template: JsonValue, preconfigured: JsonValue, is_toml_config: boolean, };
 
 export type ExecutorActionType = { "type": "CodingAgentInitialRequest" } & CodingAgentInitialRequest | { "type": "CodingAgentFollowUpRequest" } & CodingAgentFollowUpRequest | { "type": "ScriptRequest" } & ScriptRequest;
 
-export type ScriptContext = "SetupScript" | "CleanupScript" | "DevServer";
+export type ScriptContext = "SetupScript" | "CleanupScript" | "TestScript" | "DevServer";
 
-export type ScriptRequest = { script: string, language: ScriptRequestLanguage, context: ScriptContext, };
+export type ScriptRequest = { script: string, language: ScriptRequestLanguage, context: ScriptContext, 
+/**
+ * Cancel the script after this many seconds instead of the attempt's
+ * execution timeout
+ */
+timeout_seconds: number | null, 
+/**
+ * How many more times to run the script if it fails
+ */
+retries: number, };
 
 export type ScriptRequestLanguage = "Bash";
 
-export enum BaseCodingAgent { CLAUDE_CODE = "CLAUDE_CODE", AMP = "AMP", GEMINI = "GEMINI", CODEX = "CODEX", OPENCODE = "OPENCODE", CURSOR_AGENT = "CURSOR_AGENT", QWEN_CODE = "QWEN_CODE", COPILOT = "COPILOT" }
+export enum BaseCodingAgent { CLAUDE_CODE = "CLAUDE_CODE", AMP = "AMP", GEMINI = "GEMINI", CODEX = "CODEX", OPENCODE = "OPENCODE", CURSOR_AGENT = "CURSOR_AGENT", QWEN_CODE = "QWEN_CODE", COPILOT = "COPILOT", AIDER = "AIDER", OPENHANDS = "OPENHANDS", CUSTOM = "CUSTOM" }
 
-export type CodingAgent = { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot };
+export type CodingAgent = { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "AIDER": Aider } | { "OPENHANDS": Openhands } | { "CUSTOM": Custom };
 
 export type Tag = { id: string, tag_name: string, content: string, created_at: string, updated_at: string, };
 
@@ -40,19 +95,450 @@ export type CreateTag = { tag_name: string, content: string, };
 
 export type UpdateTag = { tag_name: string | null, content: string | null, };
 
+export type Label = { id: string, name: string, 
+/**
+ * Display color, e.g. "#f97316"
+ */
+color: string | null, created_at: string, updated_at: string, };
+
+export type CreateLabel = { name: string, color: string | null, };
+
+export type UpdateLabel = { name: string | null, color: string | null, };
+
+export type SetLabels = { label_ids: Array<string>, };
+
 export type TagSearchParams = { search: string | null, };
 
+export type SearchQuery = { q: string, 
+/**
+ * Only search within this project
+ */
+project_id: string | null, kind: SearchResultKind | null, limit: bigint | null, };
+
+export type SearchResultKind = "task" | "attempt_summary" | "execution_log";
+
+export type SearchResult = { kind: SearchResultKind, project_id: string, project_name: string, task_id: string, task_title: string, 
+/**
+ * Set for attempt summaries and execution logs
+ */
+task_attempt_id: string | null, 
+/**
+ * Set for attempt summaries and execution logs
+ */
+execution_process_id: string | null, 
+/**
+ * Excerpt around the match, with matched terms wrapped in `**`
+ */
+snippet: string, };
+
 export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelled";
 
-export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, created_at: string, updated_at: string, };
+export type TaskPriority = "low" | "medium" | "high" | "urgent";
 
-export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, has_merged_attempt: boolean, last_attempt_failed: boolean, executor: string, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, created_at: string, updated_at: string, };
+export type TaskComment = { id: string, task_id: string, 
+/**
+ * Attempt the comment is about, if any
+ */
+task_attempt_id: string | null, author: string, 
+/**
+ * Markdown
+ */
+body: string, 
+/**
+ * File of the attempt's diff the comment is anchored to
+ */
+file_path: string | null, 
+/**
+ * Line of `file_path` in the new version of the file
+ */
+line_number: bigint | null, created_at: string, updated_at: string, };
+
+export type CreateTaskComment = { body: string, 
+/**
+ * Defaults to the configured GitHub username
+ */
+author: string | null, task_attempt_id: string | null, file_path: string | null, line_number: bigint | null, };
+
+export type UpdateTaskComment = { body: string, };
+
+export type TaskAttachment = { id: string, task_id: string, file_path: string, original_name: string, mime_type: string, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
+
+export type TaskActivityKind = "created" | "status_changed" | "attempt_started" | "attempt_finished" | "pr_opened" | "pr_closed" | "merged";
+
+export type TaskActivity = { id: string, task_id: string, task_attempt_id: string | null, kind: TaskActivityKind, 
+/**
+ * Set for `status_changed`
+ */
+from_status: TaskStatus | null, 
+/**
+ * Set for `status_changed`
+ */
+to_status: TaskStatus | null, 
+/**
+ * Final process status for `attempt_finished`, PR URL or merge commit for
+ * PR and merge entries
+ */
+detail: string | null, created_at: string, };
+
+export type TaskTransition = { id: string, task_id: string, status: TaskStatus, 
+/**
+ * Column the task showed in; null once the column is deleted
+ */
+column_id: string | null, created_at: string, };
+
+export type AssignRequest = { 
+/**
+ * Defaults to the configured GitHub username
+ */
+assignee?: string, };
+
+export type TrashItemKind = "project" | "task";
+
+export type TrashItem = { kind: TrashItemKind, id: string, 
+/**
+ * Project name or task title
+ */
+name: string, 
+/**
+ * Project the task belongs to; null for projects
+ */
+project_id: string | null, deleted_at: string, };
+
+export type Workspace = { id: string, name: string, description: string | null, created_at: string, updated_at: string, };
+
+export type CreateWorkspace = { name: string, description: string | null, };
+
+export type UpdateWorkspace = { name: string | null, description: string | null, };
+
+export type WorkspaceProjectStats = { project_id: string, project_name: string, todo: bigint, inprogress: bigint, inreview: bigint, done: bigint, cancelled: bigint, };
+
+export type WorkspaceStats = { projects: Array<WorkspaceProjectStats>, usage: UsageTotals, };
+
+export type ProjectColumn = { id: string, project_id: string, name: string, position: bigint, 
+/**
+ * Status of the tasks in this column
+ */
+status: TaskStatus, 
+/**
+ * Maximum number of tasks the column should hold
+ */
+wip_limit: bigint | null, 
+/**
+ * Reject moves into the column once it holds `wip_limit` tasks, instead
+ * of allowing them with a warning
+ */
+enforce_wip_limit: boolean, created_at: string, updated_at: string, };
+
+export type ProjectEnvVar = { id: string, project_id: string, name: string, 
+/**
+ * Secret values are write-only through the API
+ */
+is_secret: boolean, created_at: string, updated_at: string, };
+
+export type UpsertProjectEnvVar = { name: string, value: string, 
+/**
+ * Defaults to true
+ */
+is_secret?: boolean, };
+
+export type ProjectEnvVarEntry = { value: string | null, id: string, project_id: string, name: string, 
+/**
+ * Secret values are write-only through the API
+ */
+is_secret: boolean, created_at: string, updated_at: string, };
+
+export type ProjectMcpServer = { id: string, project_id: string, 
+/**
+ * Key of the server in the agent's config
+ */
+name: string, command: string, args: string[], env: Record<string, string>, 
+/**
+ * Disabled servers are kept but not given to agents
+ */
+enabled: boolean, created_at: string, updated_at: string, };
+
+export type CreateProjectMcpServer = { name: string, command: string, args?: Array<string>, env?: { [key in string]?: string }, 
+/**
+ * Defaults to true
+ */
+enabled?: boolean, };
+
+export type UpdateProjectMcpServer = { name: string | null, command: string | null, args: Array<string> | null, env: { [key in string]?: string } | null, enabled: boolean | null, };
+
+export type ProjectContextPack = { id: string, project_id: string, name: string, 
+/**
+ * Files to include, as paths or gitignore-style globs relative to the
+ * repository root
+ */
+patterns: string[], 
+/**
+ * Free-form notes included as they are
+ */
+notes: string | null, 
+/**
+ * Disabled packs are kept but not included
+ */
+enabled: boolean, created_at: string, updated_at: string, };
+
+export type CreateProjectContextPack = { name: string, patterns?: Array<string>, notes?: string, 
+/**
+ * Defaults to true
+ */
+enabled?: boolean, };
+
+export type UpdateProjectContextPack = { name: string | null, patterns: Array<string> | null, 
+/**
+ * An empty string removes the notes
+ */
+notes: string | null, enabled: boolean | null, };
+
+export type ProjectApprovalRule = { id: string, project_id: string, 
+/**
+ * Tool name as reported by the agent, e.g. `Bash` or `edit`, compared
+ * case-insensitively; `*` matches any tool
+ */
+tool_name: string, 
+/**
+ * Wildcard pattern the call's command or path must match, e.g.
+ * `cargo test*`; any call of the tool matches when null
+ */
+pattern: string | null, created_at: string, updated_at: string, };
+
+export type CreateProjectApprovalRule = { tool_name: string, pattern?: string, };
+
+export type ProjectExecutorFallback = { project_id: string, 
+/**
+ * Order in the chain, starting at 0
+ */
+position: bigint, executor_profile_id: ExecutorProfileId, created_at: string, };
+
+export type SetProjectExecutorFallbacks = { 
+/**
+ * Profiles to fall back to, tried in order
+ */
+profiles: Array<ExecutorProfileId>, };
+
+export type ProjectSandbox = { project_id: string, image: string, 
+/**
+ * Shell script baked into a layer on top of the image, e.g. to install
+ * the agent's CLI
+ */
+bootstrap_script: string | null, created_at: string, updated_at: string, };
+
+export type UpsertProjectSandbox = { image: string, bootstrap_script?: string, };
+
+export type HostingProviderKind = "github" | "gitlab" | "bitbucket";
+
+export type ProjectHosting = { project_id: string, provider: HostingProviderKind, created_at: string, updated_at: string, };
+
+export type UpsertProjectHosting = { provider: HostingProviderKind, };
+
+export type RebasePolicyMode = "flag" | "rebase";
+
+export type ProjectRebasePolicy = { project_id: string, mode: RebasePolicyMode, created_at: string, updated_at: string, };
+
+export type UpsertProjectRebasePolicy = { mode: RebasePolicyMode, };
+
+export type ProjectBranchTemplate = { project_id: string, 
+/**
+ * e.g. `feat/{ticket}-{slug}`
+ */
+template: string, created_at: string, updated_at: string, };
+
+export type UpsertProjectBranchTemplate = { template: string, };
+
+export type MergeStrategy = "squash" | "rebase" | "merge_commit";
+
+export type ProjectMergeSettings = { project_id: string, strategy: MergeStrategy, 
+/**
+ * Message of squash and merge commits, e.g. `{title} ({task_id})`
+ */
+commit_message_template: string | null, created_at: string, updated_at: string, };
+
+export type UpsertProjectMergeSettings = { strategy: MergeStrategy, commit_message_template: string | null, };
+
+export type ProjectTurnTest = { project_id: string, 
+/**
+ * A test script of the project
+ */
+script_id: string, 
+/**
+ * Send failing results back to the agent as a follow-up
+ */
+feed_back_failures: boolean, 
+/**
+ * Follow-ups sent in a row before failures are left to the user
+ */
+max_feedback_rounds: bigint, created_at: string, updated_at: string, };
+
+export type UpsertProjectTurnTest = { script_id: string, feed_back_failures?: boolean, max_feedback_rounds?: bigint, };
+
+export type CreateProjectColumn = { name: string, status: TaskStatus, wip_limit: bigint | null, enforce_wip_limit: boolean, };
+
+export type UpdateProjectColumn = { name: string | null, status: TaskStatus | null, 
+/**
+ * New limit, or null to remove it; left unchanged when omitted
+ */
+wip_limit?: bigint | null, enforce_wip_limit: boolean | null, };
+
+export type ColumnWipStatus = { column_id: string, project_id: string, name: string, count: bigint, wip_limit: bigint, };
+
+export type ReorderProjectColumns = { 
+/**
+ * Every column of the project, in the new order
+ */
+column_ids: Array<string>, };
+
+export type ProjectScriptKind = "setup" | "dev" | "test" | "cleanup";
+
+export type ProjectScript = { id: string, project_id: string, kind: ProjectScriptKind, name: string, script: string, 
+/**
+ * Run order among the project's scripts of the same kind
+ */
+position: bigint, 
+/**
+ * Cancel a run after this many seconds. Dev servers are never timed out.
+ */
+timeout_seconds: bigint | null, 
+/**
+ * How many times a failed or timed out run is retried
+ */
+max_retries: bigint, created_at: string, updated_at: string, };
+
+export type CreateProjectScript = { kind: ProjectScriptKind, name: string, script: string, timeout_seconds: bigint | null, max_retries: bigint | null, };
+
+export type UpdateProjectScript = { name: string | null, script: string | null, 
+/**
+ * New timeout, or null to remove it; left unchanged when omitted
+ */
+timeout_seconds?: bigint | null, max_retries: bigint | null, };
+
+export type ReorderProjectScripts = { kind: ProjectScriptKind, 
+/**
+ * Every script of the kind, in the new run order
+ */
+script_ids: Array<string>, };
+
+export type RunProjectScript = { 
+/**
+ * Attempt whose worktree the script runs in
+ */
+task_attempt_id: string, };
+
+export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, 
+/**
+ * Parent task when this is a subtask
+ */
+parent_task_id: string | null, priority: TaskPriority, due_date: string | null, 
+/**
+ * Who is working on the task, e.g. a GitHub username
+ */
+assignee: string | null, created_at: string, updated_at: string, };
+
+export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, has_merged_attempt: boolean, last_attempt_failed: boolean, 
+/**
+ * Some task this one depends on is not done yet
+ */
+is_blocked: boolean, 
+/**
+ * Board column the task shows in
+ */
+column_id: string | null, 
+/**
+ * The due date has passed and the task is not done or cancelled
+ */
+is_overdue: boolean, executor: string, 
+/**
+ * CI checks of the latest attempt's pushed branch
+ */
+ci_checks: Array<CiCheck>, 
+/**
+ * Combined state of `ci_checks`: failure if any failed, pending while
+ * any runs, success otherwise. Null without checks.
+ */
+ci_state: CiCheckState | null, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_task_attempt: string | null, 
+/**
+ * Parent task when this is a subtask
+ */
+parent_task_id: string | null, priority: TaskPriority, due_date: string | null, 
+/**
+ * Who is working on the task, e.g. a GitHub username
+ */
+assignee: string | null, created_at: string, updated_at: string, };
 
 export type TaskRelationships = { parent_task: Task | null, current_attempt: TaskAttempt, children: Array<Task>, };
 
-export type CreateTask = { project_id: string, title: string, description: string | null, parent_task_attempt: string | null, image_ids: Array<string> | null, };
+export type CreateTask = { project_id: string, title: string, description: string | null, parent_task_attempt: string | null, image_ids: Array<string> | null, 
+/**
+ * Create the task as a subtask of this task
+ */
+parent_task_id?: string, priority?: TaskPriority, due_date?: string, assignee?: string, };
+
+export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_task_attempt: string | null, image_ids: Array<string> | null, 
+/**
+ * Move the task under this parent task; left unchanged when omitted
+ */
+parent_task_id?: string, priority?: TaskPriority, 
+/**
+ * Move the task into this board column; its status follows the column
+ */
+column_id?: string, 
+/**
+ * New due date, or null to clear it; left unchanged when omitted
+ */
+due_date?: string | null, };
+
+export type TaskSchedule = { id: string, task_id: string, 
+/**
+ * Five-field cron expression, evaluated in UTC
+ */
+cron_expression: string, executor_profile_id: ExecutorProfileId, base_branch: string, enabled: boolean, next_run_at: string | null, last_run_at: string | null, last_task_attempt_id: string | null, created_at: string, updated_at: string, };
+
+export type UpsertTaskSchedule = { cron_expression: string, executor_profile_id: ExecutorProfileId, base_branch: string, enabled: boolean, };
+
+export type TaskRecurrence = { id: string, task_id: string, 
+/**
+ * RRULE-like rule, e.g. `FREQ=WEEKLY;BYDAY=MO`, evaluated in UTC
+ */
+rule: string, 
+/**
+ * Which occurrence the task is, starting at 1
+ */
+occurrence: bigint, created_at: string, updated_at: string, };
+
+export type UpsertTaskRecurrence = { rule: string, };
+
+export type TaskDependency = { task_id: string, depends_on_task_id: string, created_at: string, };
+
+export type CreateTaskDependency = { depends_on_task_id: string, };
+
+export type TaskDependencies = { 
+/**
+ * Tasks that must be done before this one
+ */
+blocked_by: Array<Task>, 
+/**
+ * Tasks waiting for this one
+ */
+blocks: Array<Task>, };
+
+export type TaskTemplate = { id: string, 
+/**
+ * None for templates available in every project
+ */
+project_id: string | null, name: string, title: string, description: string | null, 
+/**
+ * Executor used when a task created from the template is started
+ */
+executor_profile_id: ExecutorProfileId | null, 
+/**
+ * Base branch used when a task created from the template is started
+ */
+base_branch: string | null, created_at: string, updated_at: string, };
+
+export type CreateTaskTemplate = { project_id: string | null, name: string, title: string, description: string | null, executor_profile_id: ExecutorProfileId | null, base_branch: string | null, };
 
-export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_task_attempt: string | null, image_ids: Array<string> | null, };
+export type UpdateTaskTemplate = { name: string | null, title: string | null, description: string | null, executor_profile_id: ExecutorProfileId | null, base_branch: string | null, };
 
 export type Image = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
 
@@ -88,13 +574,135 @@ export type ChangeTargetBranchResponse = { new_target_branch: string, status: [n
 
 export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, base_branch: string, };
 
-export type CreateGitHubPrRequest = { title: string, body: string | null, target_branch: string | null, };
+export type CreateTaskFromTemplate = { template_id: string, project_id: string, 
+/**
+ * Values for the template's `{{placeholders}}`
+ */
+variables: { [key in string]?: string }, 
+/**
+ * Start an attempt right away
+ */
+start: boolean, 
+/**
+ * Overrides the template's default executor
+ */
+executor_profile_id: ExecutorProfileId | null, 
+/**
+ * Overrides the template's default base branch
+ */
+base_branch: string | null, };
+
+export type BulkTaskAction = { "type": "set_status", status: TaskStatus, } | { "type": "delete" } | { "type": "move_to_project", project_id: string, } | { "type": "add_labels", label_ids: Array<string>, };
+
+export type BulkTaskRequest = { task_ids: Array<string>, action: BulkTaskAction, };
+
+export type BulkTaskResponse = { affected: number, };
+
+export type SwimlaneGroupBy = "label" | "assignee" | "epic";
+
+export type BoardColumnCount = { column_id: string, name: string, count: number, wip_limit: bigint | null, 
+/**
+ * The column holds more tasks than its WIP limit
+ */
+over_wip_limit: boolean, };
+
+export type SwimlaneColumnCount = { column_id: string, count: number, };
+
+export type Swimlane = { 
+/**
+ * Label id, assignee or parent task id; null for the lane of tasks
+ * without one
+ */
+key: string | null, name: string, count: number, 
+/**
+ * Tasks of the lane per column, in board order
+ */
+columns: Array<SwimlaneColumnCount>, tasks: Array<TaskWithAttemptStatus>, };
+
+export type SwimlaneBoard = { group_by: SwimlaneGroupBy, 
+/**
+ * Counts over every task of the project, regardless of the listing's
+ * filters, in board order
+ */
+columns: Array<BoardColumnCount>, 
+/**
+ * Ordered by name, with the lane of tasks without a key last
+ */
+lanes: Array<Swimlane>, };
+
+export type TaskListing = Array<TaskWithAttemptStatus> | SwimlaneBoard;
+
+export type ImportGitHubIssuesRequest = { 
+/**
+ * Only import issues carrying all of these labels
+ */
+labels: Array<string>, 
+/**
+ * Only import issues of this milestone number
+ */
+milestone: bigint | null, 
+/**
+ * Close each issue once its task is done
+ */
+sync_status: boolean, };
+
+export type ImportGitHubIssuesResponse = { created: Array<Task>, 
+/**
+ * Matching issues that already had a task
+ */
+skipped: number, };
+
+export type ImportExternalTicketsRequest = { provider: ExternalProvider, 
+/**
+ * JQL for Jira, a team key for Linear
+ */
+query: string, };
+
+export type ImportExternalTicketsResponse = { created: Array<Task>, 
+/**
+ * Matching tickets that already had a task
+ */
+skipped: number, };
+
+export type ExternalProvider = "jira" | "linear";
+
+export type ExternalTaskLink = { task_id: string, provider: ExternalProvider, external_id: string, external_key: string, external_url: string, 
+/**
+ * Task status as of the last sync
+ */
+last_synced_status: TaskStatus, 
+/**
+ * Tracker state as of the last sync
+ */
+remote_state: string, synced_at: string, created_at: string, };
+
+export type MergeTaskAttemptRequest = { strategy?: MergeStrategy, 
+/**
+ * Message of the squash or merge commit, instead of the one rendered
+ * from the project's template
+ */
+commit_message?: string, };
+
+export type CreateGitHubPrRequest = { title: string, body: string | null, target_branch: string | null, 
+/**
+ * Open the PR as a draft, to be marked ready for review later
+ */
+draft?: boolean, };
 
 export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
 
 export enum GitHubServiceError { TOKEN_INVALID = "TOKEN_INVALID", INSUFFICIENT_PERMISSIONS = "INSUFFICIENT_PERMISSIONS", REPO_NOT_FOUND_OR_NO_ACCESS = "REPO_NOT_FOUND_OR_NO_ACCESS" }
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, github_login_acknowledged: boolean, telemetry_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean | null, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, };
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, github_login_acknowledged: boolean, telemetry_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean | null, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, max_concurrent_executions: number | null, max_concurrent_executions_per_project: number | null, worktree_cleanup: WorktreeCleanupConfig, 
+/**
+ * Refuse to start attempts on tasks whose blocking tasks are not done
+ */
+enforce_task_dependencies: boolean, integrations: IntegrationsConfig, 
+/**
+ * Days deleted tasks and projects stay in the trash; null keeps them
+ * until restored
+ */
+trash_retention_days: number | null, hosting: HostingConfig, commit_signing: CommitSigningConfig | null, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -112,6 +720,121 @@ export type UiLanguage = "BROWSER" | "EN" | "JA" | "ES" | "KO";
 
 export type ShowcaseState = { seen_features: Array<string>, };
 
+export type WorktreeCleanupConfig = { 
+/**
+ * Delete an attempt's worktree as soon as its changes are merged
+ */
+delete_on_merge: boolean, 
+/**
+ * Delete worktrees idle for this many days; null keeps them forever
+ */
+max_age_days: number | null, };
+
+export type IntegrationsConfig = { jira: JiraConfig | null, linear: LinearConfig | null, };
+
+export type JiraConfig = { 
+/**
+ * Site URL, e.g. https://acme.atlassian.net
+ */
+base_url: string, email: string, api_token: string, };
+
+export type LinearConfig = { 
+/**
+ * Personal API key
+ */
+api_key: string, };
+
+export type HostingConfig = { gitlab: GitLabConfig | null, bitbucket: BitbucketConfig | null, };
+
+export type GitLabConfig = { 
+/**
+ * Instance URL, e.g. https://gitlab.example.com
+ */
+base_url: string, 
+/**
+ * Personal access token with the `api` scope
+ */
+token: string, };
+
+export type BitbucketConfig = { 
+/**
+ * Bitbucket username, not the account email
+ */
+username: string, 
+/**
+ * App password with repository write and pull request write permissions
+ */
+app_password: string, };
+
+export type SigningFormat = "gpg" | "ssh";
+
+export type CommitSigningConfig = { format: SigningFormat, 
+/**
+ * GPG key id, or the path of an SSH private key (or of its public key
+ * when the private key is in ssh-agent)
+ */
+key: string, 
+/**
+ * Program to sign with instead of `gpg` or `ssh-keygen`
+ */
+program: string | null, 
+/**
+ * Refuse to push attempt branches or open pull requests for them while
+ * they have unsigned commits
+ */
+require_signed: boolean, };
+
+export type WorktreeUsage = { path: string, size_bytes: bigint, 
+/**
+ * Owning attempt; null for orphaned directories awaiting cleanup
+ */
+attempt_id: string | null, task_id: string | null, project_id: string | null, };
+
+export type WorktreeDiskUsage = { total_bytes: bigint, 
+/**
+ * Largest first
+ */
+worktrees: Array<WorktreeUsage>, };
+
+export type WorktreeState = "healthy" | "missing" | "unregistered" | "orphaned";
+
+export type WorktreeOverview = { path: string, state: WorktreeState, 
+/**
+ * Branch checked out in the directory, when git can tell
+ */
+branch: string | null, attempt_id: string | null, task_id: string | null, project_id: string | null, 
+/**
+ * For orphaned directories: the attempt working on the checked-out
+ * branch, which the directory can be relinked to
+ */
+suggested_attempt_id: string | null, };
+
+export type RelinkWorktreeRequest = { 
+/**
+ * Existing worktree directory, typically an orphaned one
+ */
+path: string, attempt_id: string, };
+
+export type AttemptWorktree = { path: string, 
+/**
+ * Name of the additional repository; null for the main worktree
+ */
+repository: string | null, path_exists: boolean, 
+/**
+ * Git has live metadata for a worktree at this path
+ */
+registered: boolean, 
+/**
+ * Branch checked out in the worktree, when registered
+ */
+branch: string | null, };
+
+export type RelocateWorktreeRequest = { 
+/**
+ * Absolute path the worktree moves to; it must not exist yet
+ */
+path: string, };
+
 export type DeviceFlowStartResponse = { user_code: string, verification_uri: string, expires_in: number, interval: number, };
 
 export enum DevicePollStatus { SLOW_DOWN = "SLOW_DOWN", AUTHORIZATION_PENDING = "AUTHORIZATION_PENDING", SUCCESS = "SUCCESS" }
@@ -120,17 +843,74 @@ export enum CheckTokenResponse { VALID = "VALID", INVALID = "INVALID" }
 
 export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
 
+export type GitCommitSummary = { sha: string, subject: string, author_name: string | null, committed_at: Date, };
+
 export type Diff = { change: DiffChangeKind, oldPath: string | null, newPath: string | null, oldContent: string | null, newContent: string | null, 
 /**
  * True when file contents are intentionally omitted (e.g., too large)
  */
 contentOmitted: boolean, 
 /**
- * Optional precomputed stats for omitted content
+ * Optional precomputed stats for omitted content
+ */
+additions: number | null, deletions: number | null, };
+
+export type DiffChangeKind = "added" | "deleted" | "modified" | "renamed" | "copied" | "permissionChange";
+
+export type DiffFile = { change: DiffChangeKind, oldPath: string | null, newPath: string | null, 
+/**
+ * True when either side is binary; such files have no hunks
+ */
+binary: boolean, 
+/**
+ * True when either side is over the inline size limit; such files have no hunks
+ */
+tooLarge: boolean, 
+/**
+ * Line counts, known once the file's contents have been compared
+ */
+additions: number | null, deletions: number | null, };
+
+export type DiffFilePage = { files: Array<DiffFile>, 
+/**
+ * Number of changed files in the whole diff
+ */
+total: number, 
+/**
+ * Offset of the next page, if there is one
+ */
+nextOffset: number | null, };
+
+export type DiffFileHunks = { file: DiffFile, 
+/**
+ * Unified diff hunks with their `@@` headers; empty for binary and
+ * oversized files
+ */
+hunks: Array<string>, };
+
+export type DiffSelection = { 
+/**
+ * Path the file is listed under in the diff
+ */
+path: string, 
+/**
+ * Indices of the hunks to keep; all of the file's changes when missing
+ */
+hunks?: Array<number>, };
+
+export type PartialCommitRequest = { 
+/**
+ * Files to keep, by the paths and hunk indices of the diff file endpoints
+ */
+files: Array<DiffSelection>, message: string, 
+/**
+ * Discard the changes left out instead of leaving them uncommitted.
+ * Refused while the branch has committed changes to files that aren't
+ * selected in full.
  */
-additions: number | null, deletions: number | null, };
+discard_rest?: boolean, };
 
-export type DiffChangeKind = "added" | "deleted" | "modified" | "renamed" | "copied" | "permissionChange";
+export type PartialCommitResponse = { commit_sha: string, };
 
 export type RepositoryInfo = { id: bigint, name: string, full_name: string, owner: string, description: string | null, clone_url: string, ssh_url: string, default_branch: string, private: boolean, };
 
@@ -154,7 +934,7 @@ executor: BaseCodingAgent,
  */
 variant: string | null, };
 
-export type ExecutorConfig = { [key in string]?: { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } };
+export type ExecutorConfig = { [key in string]?: { "CLAUDE_CODE": ClaudeCode } | { "AMP": Amp } | { "GEMINI": Gemini } | { "CODEX": Codex } | { "OPENCODE": Opencode } | { "CURSOR_AGENT": CursorAgent } | { "QWEN_CODE": QwenCode } | { "COPILOT": Copilot } | { "AIDER": Aider } | { "OPENHANDS": Openhands } | { "CUSTOM": Custom } };
 
 export enum BaseAgentCapability { SESSION_FORK = "SESSION_FORK", SETUP_HELPER = "SETUP_HELPER" }
 
@@ -180,10 +960,43 @@ export type ReasoningSummaryFormat = "none" | "experimental";
 
 export type CursorAgent = { append_prompt: AppendPrompt, force?: boolean | null, model?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
+export type CustomOutputFormat = "text" | "json_lines";
+
+export type CustomEntryType = "assistant" | "thinking" | "system" | "error" | "tool" | "ignore";
+
+export type CustomOutputRule = { 
+/**
+ * Text output: regex a line must match
+ */
+pattern?: string | null, 
+/**
+ * JSON output: path a line must have, e.g. `$.type` or `$.items[0].kind`
+ */
+json_path?: string | null, 
+/**
+ * JSON output: value the `json_path` must equal, compared as text
+ */
+equals?: string | null, entry_type: CustomEntryType, 
+/**
+ * Text output: content template with `$1` or `${name}` for capture
+ * groups. JSON output: path of the content. Defaults to the whole line.
+ */
+content?: string | null, 
+/**
+ * Tool name shown for `tool` entries
+ */
+tool_name?: string | null, };
+
+export type Custom = { append_prompt: AppendPrompt, command: string, follow_up_command?: string | null, env?: { [key in string]?: string }, working_dir?: string | null, output_format: CustomOutputFormat, rules?: Array<CustomOutputRule>, session_id?: string | null, };
+
 export type Copilot = { append_prompt: AppendPrompt, model?: string | null, allow_all_tools?: boolean | null, allow_tool?: string | null, deny_tool?: string | null, add_dir?: Array<string> | null, disable_mcp_server?: Array<string> | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
+export type Aider = { append_prompt: AppendPrompt, model?: string | null, architect?: boolean | null, edit_format?: string | null, auto_commits?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+
 export type Opencode = { append_prompt: AppendPrompt, model?: string | null, agent?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
+export type Openhands = { append_prompt: AppendPrompt, model?: string | null, agent?: string | null, max_iterations?: number | null, image?: string | null, runtime_image?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
+
 export type QwenCode = { append_prompt: AppendPrompt, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, };
 
 export type AppendPrompt = string | null;
@@ -204,15 +1017,61 @@ export type CreateTaskAttemptBody = { task_id: string,
 /**
  * Executor profile specification
  */
-executor_profile_id: ExecutorProfileId, base_branch: string, };
+executor_profile_id: ExecutorProfileId, base_branch: string, 
+/**
+ * Cancel runs of this attempt after this many minutes (overrides the project setting)
+ */
+execution_timeout_minutes?: bigint, 
+/**
+ * Stack the new attempt on this attempt's branch; `base_branch` is then ignored
+ */
+parent_attempt_id?: string, 
+/**
+ * Have the agent write a plan first, and only implement it once the plan
+ * is approved
+ */
+plan_first?: boolean, 
+/**
+ * Have the agent answer without changing code, e.g. for questions about
+ * the codebase; whatever it changes is discarded
+ */
+read_only?: boolean, };
+
+export type SetExecutionTimeoutRequest = { 
+/**
+ * Minutes before runs of this attempt are cancelled; null falls back to the project setting
+ */
+minutes: bigint | null, };
+
+export type AttemptStack = { 
+/**
+ * Attempts this one is stacked on, from the bottom of the stack upwards
+ */
+ancestors: Array<TaskAttempt>, 
+/**
+ * Attempts stacked directly on this one
+ */
+children: Array<TaskAttempt>, };
 
 export type RunAgentSetupRequest = { executor_profile_id: ExecutorProfileId, };
 
 export type RunAgentSetupResponse = Record<string, never>;
 
-export type RebaseTaskAttemptRequest = { old_base_branch: string | null, new_base_branch: string | null, };
+export type RebaseTaskAttemptRequest = { old_base_branch: string | null, new_base_branch: string | null, 
+/**
+ * How to bring in the base branch; defaults to rebase
+ */
+strategy?: RebaseStrategy, };
+
+export type RebaseStrategy = "rebase" | "merge";
+
+export type RebaseTaskAttemptResponse = { strategy: RebaseStrategy, head_commit: string, 
+/**
+ * New merge base with the target branch; diff views should reload from here
+ */
+base_commit: string, commits_ahead: number, commits_behind: number, };
 
-export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, } | { "type": "rebase_in_progress" };
+export type GitOperationError = { "type": "merge_conflicts", message: string, op: ConflictOp, conflicted_files: Array<string>, } | { "type": "rebase_in_progress" };
 
 export type ReplaceProcessRequest = { 
 /**
@@ -236,6 +1095,40 @@ force_when_dirty: boolean | null,
  */
 perform_git_reset: boolean | null, };
 
+export type ForkTaskAttemptRequest = { 
+/**
+ * Coding agent run to fork at; its prompt is replaced by `prompt`
+ */
+process_id: string, prompt: string, 
+/**
+ * Run the fork with another variant of the same agent
+ */
+variant?: string, };
+
+export type AttemptFork = { task_attempt_id: string, 
+/**
+ * Null once the source attempt is deleted
+ */
+source_task_attempt_id: string | null, 
+/**
+ * The coding agent run whose prompt the fork replaced
+ */
+source_execution_process_id: string | null, 
+/**
+ * Commit the fork's branch started from
+ */
+base_commit: string, created_at: string, };
+
+export type AttemptForks = { 
+/**
+ * Where this attempt was forked from, if it was
+ */
+forked_from: AttemptFork | null, 
+/**
+ * Attempts forked from this one
+ */
+forks: Array<AttemptFork>, };
+
 export type CommitInfo = { sha: string, subject: string, };
 
 export type BranchStatus = { commits_behind: number | null, commits_ahead: number | null, has_uncommitted_changes: boolean | null, head_oid: string | null, uncommitted_count: number | null, untracked_count: number | null, target_branch_name: string, remote_commits_behind: number | null, remote_commits_ahead: number | null, merges: Array<Merge>, 
@@ -254,7 +1147,206 @@ conflicted_files: Array<string>, };
 
 export type ConflictOp = "rebase" | "merge" | "cherry_pick" | "revert";
 
-export type TaskAttempt = { id: string, task_id: string, container_ref: string | null, branch: string, target_branch: string, executor: string, worktree_deleted: boolean, setup_completed_at: string | null, created_at: string, updated_at: string, };
+export type ConflictSegment = { "type": "clean", text: string, } | { "type": "conflict", base: string, ours: string, theirs: string, };
+
+export type ConflictedFile = { path: string, ours_deleted: boolean, theirs_deleted: boolean, binary: boolean, 
+/**
+ * The file split into merged and conflicting parts; empty when it cannot
+ * be merged line by line because it is binary or one side deleted it
+ */
+segments: Array<ConflictSegment>, };
+
+export type HunkResolution = { "type": "ours" } | { "type": "theirs" } | { "type": "base" } | { "type": "both" } | { "type": "custom", text: string, };
+
+export type FileResolution = { path: string, 
+/**
+ * One per conflict segment of the file, in order. Files without
+ * segments take a single `ours`, `theirs` or `custom` resolution for the
+ * whole file, where picking a side that deleted the file deletes it.
+ */
+resolutions: Array<HunkResolution>, };
+
+export type AttemptConflicts = { op: ConflictOp | null, files: Array<ConflictedFile>, };
+
+export type ResolveConflictsRequest = { files: Array<FileResolution>, };
+
+export type PrReviewComment = { id: string, task_attempt_id: string, 
+/**
+ * The pull request the comment was left on
+ */
+merge_id: string, 
+/**
+ * Id of the comment on the code host
+ */
+external_id: string, author: string, body: string, 
+/**
+ * File of an inline comment
+ */
+path: string | null, 
+/**
+ * Line of an inline comment in the new version of the file
+ */
+line: bigint | null, url: string | null, commented_at: string, 
+/**
+ * The follow-up the comment was sent to the agent in
+ */
+execution_process_id: string | null, sent_at: string | null, created_at: string, updated_at: string, };
+
+export type SendReviewCommentsRequest = { comment_ids: Array<string>, 
+/**
+ * Added after the comments, e.g. which suggestions to leave out
+ */
+instructions?: string, };
+
+export type AttemptBaseStatus = { task_attempt_id: string, 
+/**
+ * Tip of the base branch when checked
+ */
+base_commit: string, commits_behind: bigint, 
+/**
+ * Since when the base branch has commits the attempt branch lacks; null
+ * when the attempt is up to date
+ */
+stale_since: string | null, 
+/**
+ * When the attempt was last rebased automatically
+ */
+rebased_at: string | null, 
+/**
+ * Why rebasing onto `base_commit` failed
+ */
+rebase_error: string | null, updated_at: string, };
+
+export type CommitSignature = { sha: string, subject: string, signed: boolean, };
+
+export type CiCheckState = "pending" | "success" | "failure" | "neutral";
+
+export type CiCheck = { id: string, task_attempt_id: string, 
+/**
+ * Check run name or status context
+ */
+name: string, state: CiCheckState, 
+/**
+ * Page of the check on the CI service or code host
+ */
+url: string | null, 
+/**
+ * Commit of the branch the check ran on
+ */
+commit_sha: string, created_at: string, updated_at: string, };
+
+export type TaskAttempt = { id: string, task_id: string, container_ref: string | null, branch: string, target_branch: string, executor: string, worktree_deleted: boolean, setup_completed_at: string | null, archived_at: string | null, execution_timeout_minutes: bigint | null, timed_out_at: string | null, paused_at: string | null, parent_attempt_id: string | null, assignee: string | null, read_only: boolean, created_at: string, updated_at: string, };
+
+export type Checkpoint = { id: string, task_attempt_id: string, name: string, commit_oid: string, execution_process_id: string | null, session_id: string | null, created_at: string, };
+
+export type CreateCheckpointRequest = { name: string, };
+
+export type ResumeFromCheckpointRequest = { 
+/**
+ * Prompt sent to the resumed session; falls back to the task prompt when empty
+ */
+prompt: string | null, 
+/**
+ * Optional variant override
+ */
+variant: string | null, };
+
+export type DiffSummaryStats = { files_changed: number, additions: number, deletions: number, };
+
+export type AttemptComparisonSide = { attempt_id: string, branch: string, target_branch: string, 
+/**
+ * Commits on the attempt branch that are not on its target branch
+ */
+commits: Array<GitCommitSummary>, 
+/**
+ * Paths changed relative to the target branch
+ */
+files_changed: Array<string>, stats: DiffSummaryStats, };
+
+export type AttemptComparison = { task_id: string, a: AttemptComparisonSide, b: AttemptComparisonSide, 
+/**
+ * File diffs going from attempt `a`'s branch to attempt `b`'s branch
+ */
+diffs: Array<Diff>, stats: DiffSummaryStats, };
+
+export type ComparisonGroup = { id: string, task_id: string, 
+/**
+ * The attempt the user picked, once they did
+ */
+winner_attempt_id: string | null, created_at: string, updated_at: string, };
+
+export type ComparisonGroupAttempt = { task_attempt_id: string, comparison_group_id: string, position: bigint, executor_profile_id: ExecutorProfileId, };
+
+export type CreateComparisonGroup = { task_id: string, base_branch: string, 
+/**
+ * One attempt is started per profile, in this order
+ */
+executor_profile_ids: Array<ExecutorProfileId>, 
+/**
+ * Cancel runs of the attempts after this many minutes (overrides the project setting)
+ */
+execution_timeout_minutes?: bigint, };
+
+export type PickComparisonWinner = { task_attempt_id: string, };
+
+export type ComparisonAttemptSummary = { attempt: TaskAttempt, executor_profile_id: ExecutorProfileId, 
+/**
+ * Status of the latest coding agent run; null before the attempt starts
+ */
+status: ExecutionProcessStatus | null, 
+/**
+ * Changes on the attempt branch relative to its target branch; null
+ * while the branch does not exist yet
+ */
+diff: DiffSummaryStats | null, 
+/**
+ * Whether the latest test script run passed; null when none finished
+ */
+tests_passed: boolean | null, usage: UsageTotals, 
+/**
+ * Time spent in coding agent runs, including the one running
+ */
+duration_seconds: bigint, };
+
+export type ComparisonGroupSummary = { group: ComparisonGroup, attempts: Array<ComparisonAttemptSummary>, };
+
+export type TranscriptExecution = { execution_process_id: string, status: ExecutionProcessStatus, is_follow_up: boolean, executor_profile_id: ExecutorProfileId | null, prompt: string | null, 
+/**
+ * Normalized conversation: agent messages, tool calls, errors
+ */
+entries: Array<NormalizedEntry>, before_head_commit: string | null, after_head_commit: string | null, started_at: string, completed_at: string | null, };
+
+export type AttemptTranscript = { task: Task, attempt: TaskAttempt, executions: Array<TranscriptExecution>, 
+/**
+ * Changes on the attempt branch relative to its target branch
+ */
+diffs: Array<Diff>, exported_at: string, };
+
+export type RepositoryDiff = { 
+/**
+ * None for the project's main repository
+ */
+repository_id: string | null, name: string, base_branch: string, 
+/**
+ * Committed and uncommitted changes relative to the base branch
+ */
+diffs: Array<Diff>, };
+
+export type CommitRepositoriesRequest = { message: string, };
+
+export type RepositoryCommitResult = { repository_id: string | null, name: string, 
+/**
+ * False when the worktree had nothing to commit
+ */
+committed: boolean, };
+
+export type CreateRepositoryPrsRequest = { title: string, body: string | null, 
+/**
+ * Open the PRs as drafts
+ */
+draft?: boolean, };
+
+export type RepositoryPrResult = { repository_id: string | null, name: string, url: string | null, error: string | null, };
 
 export type ExecutionProcess = { id: string, task_attempt_id: string, run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, 
 /**
@@ -274,7 +1366,185 @@ dropped: boolean, started_at: string, completed_at: string | null, created_at: s
 
 export enum ExecutionProcessStatus { running = "running", completed = "completed", failed = "failed", killed = "killed" }
 
-export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "codingagent" | "devserver";
+export type ExecutionProcessRunReason = "setupscript" | "cleanupscript" | "testscript" | "codingagent" | "devserver";
+
+export type ExecutionProcessEvent = { execution_process_id: string, seq: bigint, event: AgentEvent, created_at: string, };
+
+export type ExecutionFallback = { execution_process_id: string, failed_execution_process_id: string, task_attempt_id: string, 
+/**
+ * The profile the run actually used
+ */
+executor_profile_id: ExecutorProfileId, 
+/**
+ * The provider error of the failed run
+ */
+reason: string, created_at: string, };
+
+export type ExecutionResumption = { execution_process_id: string, interrupted_execution_process_id: string, task_attempt_id: string, 
+/**
+ * The agent session that was continued
+ */
+session_id: string, 
+/**
+ * Last agent event stored for the interrupted run; null when it had none
+ */
+last_event_seq: bigint | null, created_at: string, };
+
+export type TurnTestRun = { 
+/**
+ * The test script run
+ */
+execution_process_id: string, 
+/**
+ * The turn that was tested
+ */
+coding_agent_process_id: string, task_attempt_id: string, 
+/**
+ * Null when the output has no count
+ */
+passed: bigint | null, failed: bigint | null, skipped: bigint | null, 
+/**
+ * Null while the tests run
+ */
+success: boolean | null, 
+/**
+ * Follow-up the failures were sent to the agent in
+ */
+follow_up_execution_process_id: string | null, created_at: string, completed_at: string | null, };
+
+export type QueuedAttempt = { task_attempt_id: string, project_id: string, 
+/**
+ * Zero-based position in the queue
+ */
+position: number, queued_at: string, };
+
+export type ProviderLimitStatus = { executor: BaseCodingAgent, 
+/**
+ * New attempts with this executor are queued until then; null when
+ * they start right away
+ */
+limited_until: string | null, 
+/**
+ * Provider refusals in a row; a successful run resets it
+ */
+consecutive_limits: number, last_error: string | null, last_limited_at: string | null, 
+/**
+ * Coding agent runs started in the last minute
+ */
+recent_starts: number, };
+
+export type AttemptUsage = { id: string, execution_process_id: string, task_attempt_id: string, model: string | null, input_tokens: bigint, output_tokens: bigint, cache_read_input_tokens: bigint, cache_creation_input_tokens: bigint, 
+/**
+ * Cost in USD, when the executor reports one
+ */
+cost_usd: number | null, created_at: string, updated_at: string, };
+
+export type AttemptPlanStatus = "planning" | "awaiting_approval" | "approved";
+
+export type AttemptPlan = { task_attempt_id: string, status: AttemptPlanStatus, 
+/**
+ * The plan as written by the agent, or as edited when it was approved
+ */
+plan: string | null, 
+/**
+ * The planning run the plan was taken from
+ */
+execution_process_id: string | null, approved_at: string | null, created_at: string, updated_at: string, };
+
+export type ApproveAttemptPlan = { 
+/**
+ * Edited plan to implement instead of the one the agent wrote
+ */
+plan?: string, };
+
+export type ProposedTaskStatus = "pending" | "accepted" | "dismissed";
+
+export type ProposedTask = { id: string, task_attempt_id: string, 
+/**
+ * The coding agent run that proposed it
+ */
+execution_process_id: string | null, title: string, description: string | null, status: ProposedTaskStatus, 
+/**
+ * The task created when it was accepted
+ */
+task_id: string | null, created_at: string, updated_at: string, };
+
+export type AcceptProposedTask = { 
+/**
+ * Title of the task instead of the proposed one
+ */
+title?: string, 
+/**
+ * Description of the task instead of the proposed one
+ */
+description?: string, };
+
+export type UsageTotals = { input_tokens: bigint, output_tokens: bigint, cache_read_input_tokens: bigint, cache_creation_input_tokens: bigint, 
+/**
+ * Sum of reported costs; null when no execution reported a cost
+ */
+cost_usd: number | null, 
+/**
+ * Number of execution processes that reported usage
+ */
+executions: bigint, };
+
+export type DurationStats = { count: bigint, average_seconds: number | null, median_seconds: number | null, 
+/**
+ * 85% of the durations are at most this long
+ */
+p85_seconds: number | null, };
+
+export type ColumnTime = { column_id: string, name: string, status: TaskStatus, 
+/**
+ * Tasks that spent time in the column
+ */
+tasks: bigint, total_seconds: number, average_seconds: number, };
+
+export type WeeklyThroughput = { week_start: string, completed: bigint, };
+
+export type AttemptDuration = { task_attempt_id: string, task_id: string, created_at: string, 
+/**
+ * Time the coding agent ran, summed over its finished runs
+ */
+agent_seconds: number, 
+/**
+ * From the first process start to the last process end; null until a
+ * process has finished
+ */
+wall_seconds: number | null, };
+
+export type ProjectMetrics = { 
+/**
+ * Start of the measured period, on a Monday
+ */
+since: string, 
+/**
+ * From when work started on a task (its first move to in progress, or
+ * its creation if it never was) to when it was last moved to done, for
+ * tasks completed during the period
+ */
+cycle_time: DurationStats, time_in_column: Array<ColumnTime>, 
+/**
+ * One entry per week of the period, oldest first
+ */
+throughput: Array<WeeklyThroughput>, 
+/**
+ * Coding agent time of the attempts started during the period
+ */
+attempt_agent_time: DurationStats, };
+
+export type TaskMetrics = { 
+/**
+ * Set once the task is done
+ */
+cycle_time_seconds: number | null, time_in_column: Array<ColumnTime>, attempts: Array<AttemptDuration>, transitions: Array<TaskTransition>, };
+
+export type AttemptUsageSummary = { totals: UsageTotals, 
+/**
+ * Usage reported by each coding agent execution of the attempt
+ */
+executions: Array<AttemptUsage>, };
 
 export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMerge;
 
@@ -284,12 +1554,20 @@ export type PrMerge = { id: string, task_attempt_id: string, created_at: string,
 
 export type MergeStatus = "open" | "merged" | "closed" | "unknown";
 
-export type PullRequestInfo = { number: bigint, url: string, status: MergeStatus, merged_at: string | null, merge_commit_sha: string | null, };
+export type PullRequestInfo = { number: bigint, url: string, status: MergeStatus, merged_at: string | null, merge_commit_sha: string | null, 
+/**
+ * Open but not ready for review yet
+ */
+is_draft: boolean, };
 
 export type Draft = { id: string, task_attempt_id: string, draft_type: DraftType, retry_process_id: string | null, prompt: string, queued: boolean, sending: boolean, variant: string | null, image_ids: Array<string> | null, created_at: string, updated_at: string, version: bigint, };
 
 export type DraftType = "follow_up" | "retry";
 
+export type QueuedFollowUp = { id: string, task_attempt_id: string, prompt: string, variant: string | null, image_ids: Array<string> | null, created_at: string, };
+
+export type CreateQueuedFollowUp = { prompt: string, variant: string | null, image_ids: Array<string> | null, };
+
 export type CommandExitStatus = { "type": "exit_code", code: number, } | { "type": "success", success: boolean, };
 
 export type CommandRunResult = { exit_status: CommandExitStatus | null, output: string | null, };
@@ -326,10 +1604,26 @@ export type ToolStatus = { "status": "created" } | { "status": "success" } | { "
 
 export type PatchType = { "type": "NORMALIZED_ENTRY", "content": NormalizedEntry } | { "type": "STDOUT", "content": string } | { "type": "STDERR", "content": string } | { "type": "DIFF", "content": Diff };
 
+export type MessageRole = "user" | "assistant" | "system" | "thinking";
+
+export type AgentEvent = { "type": "message", entry_index: number, role: MessageRole, content: string, } | { "type": "tool_call", entry_index: number, tool_name: string, action: ActionType, content: string, } | { "type": "tool_result", entry_index: number, tool_name: string, status: ToolStatus, 
+/**
+ * The action with its result, e.g. a command's output
+ */
+action: ActionType, } | { "type": "approval_requested", entry_index: number, tool_name: string, approval_id: string, action: ActionType, timeout_at: string, } | { "type": "approval_resolved", entry_index: number, approval_id: string, approved: boolean, } | { "type": "file_edit", entry_index: number, path: string, changes: Array<FileChange>, } | { "type": "error", entry_index: number, error_type: NormalizedEntryError, content: string, } | { "type": "usage", input_tokens: bigint, output_tokens: bigint, cache_read_input_tokens: bigint, cache_creation_input_tokens: bigint, cost_usd: number | null, model: string | null, };
+
 export type ApprovalStatus = { "status": "pending" } | { "status": "approved" } | { "status": "denied", reason?: string, } | { "status": "timed_out" };
 
 export type CreateApprovalRequest = { tool_name: string, tool_input: JsonValue, tool_call_id: string, };
 
 export type ApprovalResponse = { execution_process_id: string, status: ApprovalStatus, };
 
+export type ApprovalRequest = { id: string, tool_name: string, tool_input: JsonValue, tool_call_id: string, execution_process_id: string, created_at: string, timeout_at: string, };
+
+export type DenyApprovalRequest = { 
+/**
+ * Passed to the agent as the reason the call was denied
+ */
+reason?: string, };
+
 export type JsonValue = number | string | boolean | Array<JsonValue> | { [key in string]?: JsonValue } | null;
\ No newline at end of file